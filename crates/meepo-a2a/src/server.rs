@@ -18,7 +18,7 @@ use uuid::Uuid;
 
 use meepo_core::agent::Agent;
 use meepo_core::tools::ToolRegistry;
-use meepo_core::types::{ChannelType, IncomingMessage};
+use meepo_core::types::{ChannelType, IncomingMessage, Priority};
 
 use crate::protocol::*;
 
@@ -239,6 +239,9 @@ impl A2aServer {
                 content: prompt,
                 channel: ChannelType::Internal,
                 timestamp: Utc::now(),
+                priority: Priority::Normal,
+                command: None,
+                thread_id: None,
             };
             let result = agent.handle_message(incoming).await;
 
@@ -6,6 +6,7 @@ use serde_json::Value;
 use tracing::{debug, info};
 
 use meepo_core::tools::ToolHandler;
+use meepo_core::truncate_chars;
 
 use crate::client::{A2aClient, PeerAgentConfig};
 
@@ -104,7 +105,7 @@ impl ToolHandler for DelegateToAgentTool {
         debug!(
             "Delegating task to agent at {}: {}",
             base_url,
-            &task[..task.len().min(100)]
+            truncate_chars(task, 100)
         );
 
         // Fetch agent card first (optional, for logging)
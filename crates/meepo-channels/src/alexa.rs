@@ -10,14 +10,14 @@
 //!   3. Copy the Skill ID and set it in config
 //!   4. Enable the skill on your Alexa device
 
-use crate::bus::MessageChannel;
+use crate::bus::{IncomingSender, MessageChannel};
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
-use meepo_core::types::{ChannelType, IncomingMessage, MessageKind, OutgoingMessage};
+use meepo_core::types::{ChannelType, MessageKind, OutgoingMessage};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{RwLock, mpsc};
+use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
 /// Alexa channel adapter using Alexa Skills Kit
@@ -45,7 +45,7 @@ impl AlexaChannel {
 
 #[async_trait]
 impl MessageChannel for AlexaChannel {
-    async fn start(&self, _tx: mpsc::Sender<IncomingMessage>) -> Result<()> {
+    async fn start(&self, _tx: IncomingSender) -> Result<()> {
         info!("Alexa channel starting (skill_id: {})", self.skill_id);
 
         if self.skill_id.is_empty() {
@@ -112,6 +112,11 @@ impl MessageChannel for AlexaChannel {
     fn channel_type(&self) -> ChannelType {
         ChannelType::Alexa
     }
+
+    fn supports_kind(&self, kind: &MessageKind) -> bool {
+        // Alexa handles its own wait UX
+        *kind != MessageKind::Acknowledgment
+    }
 }
 
 #[cfg(test)]
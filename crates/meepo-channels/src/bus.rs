@@ -1,50 +1,506 @@
 //! Central message bus for routing messages between channels and the agent
 
+use crate::rate_limit::{RateLimitedChannel, RateLimiter};
+use crate::retry::{RetryPolicy, RetryingChannel};
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
-#[cfg(test)]
-use meepo_core::types::MessageKind;
-use meepo_core::types::{ChannelType, IncomingMessage, OutgoingMessage};
-use std::collections::HashMap;
+use lru::LruCache;
+use meepo_core::safe_mode::SafeMode;
+use meepo_core::types::{ChannelType, IncomingMessage, MessageKind, OutgoingMessage, Priority};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
+
+/// How often `MessageBus::recv` checks whether any channel can still deliver messages
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Every channel has dropped its sender, so no further incoming messages can arrive.
+/// Usually this means all channel tasks have exited or panicked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("all message channels have closed; no further incoming messages can arrive")]
+pub struct BusClosed;
+
+/// An outgoing message that could not be delivered, with why
+#[derive(Debug, Clone)]
+pub struct DeadLetter {
+    pub message: OutgoingMessage,
+    pub reason: String,
+    pub failed_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Bounded queue of undeliverable outgoing messages, shared by the bus and its sender.
+/// When full, the oldest entry is dropped to make room.
+#[derive(Debug, Clone)]
+pub struct DeadLetterQueue {
+    entries: Arc<Mutex<VecDeque<DeadLetter>>>,
+    capacity: usize,
+}
+
+impl DeadLetterQueue {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    /// Record an undeliverable message
+    pub fn push(&self, message: OutgoingMessage, reason: impl Into<String>) {
+        let reason = reason.into();
+        warn!("Dead-lettering message for {}: {}", message.channel, reason);
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(DeadLetter {
+            message,
+            reason,
+            failed_at: chrono::Utc::now(),
+        });
+    }
+
+    /// Remove and return every queued dead letter, oldest first
+    pub fn drain(&self) -> Vec<DeadLetter> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.drain(..).collect()
+    }
+
+    /// Number of queued dead letters
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Sending half of the incoming queue, handed to every channel adapter.
+/// Routes each message to the high- or normal-priority lane by its `priority`.
+#[derive(Debug, Clone)]
+pub struct IncomingSender {
+    normal: mpsc::Sender<IncomingMessage>,
+    high: mpsc::Sender<IncomingMessage>,
+}
+
+impl IncomingSender {
+    /// Queue a message for the agent, waiting if its lane is full
+    pub async fn send(
+        &self,
+        msg: IncomingMessage,
+    ) -> Result<(), mpsc::error::SendError<IncomingMessage>> {
+        match msg.priority {
+            Priority::High => self.high.send(msg).await,
+            Priority::Normal => self.normal.send(msg).await,
+        }
+    }
+
+    /// Whether the bus has stopped receiving
+    pub fn is_closed(&self) -> bool {
+        self.normal.is_closed()
+    }
+}
+
+/// Receiving half of the incoming queue: drains high priority first,
+/// FIFO within each priority level
+pub(crate) struct IncomingQueue {
+    normal: mpsc::Receiver<IncomingMessage>,
+    high: mpsc::Receiver<IncomingMessage>,
+}
+
+impl IncomingQueue {
+    async fn recv(&mut self) -> Option<IncomingMessage> {
+        tokio::select! {
+            biased;
+            Some(msg) = self.high.recv() => Some(msg),
+            Some(msg) = self.normal.recv() => Some(msg),
+            else => None,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.high.is_empty() && self.normal.is_empty()
+    }
+
+    fn is_closed(&self) -> bool {
+        self.high.is_closed() && self.normal.is_closed()
+    }
+}
+
+/// Create an incoming queue with `buffer_size` slots per priority level
+pub(crate) fn incoming_queue(buffer_size: usize) -> (IncomingSender, IncomingQueue) {
+    let (normal_tx, normal_rx) = mpsc::channel(buffer_size);
+    let (high_tx, high_rx) = mpsc::channel(buffer_size);
+    (
+        IncomingSender {
+            normal: normal_tx,
+            high: high_tx,
+        },
+        IncomingQueue {
+            normal: normal_rx,
+            high: high_rx,
+        },
+    )
+}
+
+/// Counters describing what the bus has done with incoming messages
+#[derive(Debug, Default)]
+pub struct BusMetrics {
+    duplicates_dropped: AtomicU64,
+}
+
+impl BusMetrics {
+    /// Incoming messages dropped as duplicates, by message id or intake dedup
+    pub fn duplicates_dropped(&self) -> u64 {
+        self.duplicates_dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// Which parts of an incoming message make up its dedup fingerprint
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DedupKey {
+    /// Same sender and content on any channel (catches cross-posts and redelivery)
+    #[default]
+    SenderContent,
+    /// Same sender and content on the same channel (only catches redelivery)
+    SenderContentChannel,
+    /// Same content from anyone, on any channel
+    Content,
+}
+
+/// Drop incoming messages whose fingerprint was already seen within `window`
+#[derive(Debug, Clone)]
+pub struct IntakeDedupConfig {
+    pub window: Duration,
+    pub key: DedupKey,
+}
+
+/// Remembers recent message fingerprints for intake dedup
+struct IntakeDedup {
+    config: IntakeDedupConfig,
+    seen: HashMap<u64, Instant>,
+}
+
+impl IntakeDedup {
+    fn new(config: IntakeDedupConfig) -> Self {
+        Self {
+            config,
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Record `msg` and report whether it duplicates one seen within the window
+    fn is_duplicate(&mut self, msg: &IncomingMessage) -> bool {
+        let now = Instant::now();
+        let window = self.config.window;
+        self.seen
+            .retain(|_, seen_at| now.duration_since(*seen_at) < window);
+
+        let fingerprint = self.fingerprint(msg);
+        if self.seen.contains_key(&fingerprint) {
+            return true;
+        }
+        self.seen.insert(fingerprint, now);
+        false
+    }
+
+    fn fingerprint(&self, msg: &IncomingMessage) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        match self.config.key {
+            DedupKey::SenderContent => msg.sender.hash(&mut hasher),
+            DedupKey::SenderContentChannel => {
+                msg.sender.hash(&mut hasher);
+                msg.channel.hash(&mut hasher);
+            }
+            DedupKey::Content => {}
+        }
+        normalize_content(&msg.content).hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// Drop incoming messages whose id was already delivered. At most `capacity`
+/// ids are remembered (least recently seen are evicted first), each for `window`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdDedupConfig {
+    /// 0 disables id dedup
+    pub capacity: usize,
+    pub window: Duration,
+}
+
+impl Default for IdDedupConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            window: Duration::from_secs(600),
+        }
+    }
+}
+
+/// Recently delivered message ids, bounded by count and age
+struct RecentIds {
+    window: Duration,
+    seen: LruCache<String, Instant>,
+}
+
+impl RecentIds {
+    /// `None` when `config` disables id dedup
+    fn new(config: IdDedupConfig) -> Option<Self> {
+        let capacity = NonZeroUsize::new(config.capacity)?;
+        Some(Self {
+            window: config.window,
+            seen: LruCache::new(capacity),
+        })
+    }
+
+    /// Record `id` and report whether it was already seen within the window
+    fn is_duplicate(&mut self, id: &str) -> bool {
+        let now = Instant::now();
+        if let Some(seen_at) = self.seen.get(id)
+            && now.duration_since(*seen_at) < self.window
+        {
+            return true;
+        }
+        self.seen.put(id.to_string(), now);
+        false
+    }
+}
+
+/// Lowercase and collapse whitespace so trivially different copies match
+fn normalize_content(content: &str) -> String {
+    content
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Pass `msg` through id dedup and intake dedup, counting it if it's dropped
+fn accept_incoming(
+    recent_ids: &mut Option<RecentIds>,
+    dedup: &mut Option<IntakeDedup>,
+    metrics: &BusMetrics,
+    msg: &IncomingMessage,
+) -> bool {
+    let redelivered = recent_ids
+        .as_mut()
+        .is_some_and(|ids| ids.is_duplicate(&msg.id));
+    if redelivered || dedup.as_mut().is_some_and(|d| d.is_duplicate(msg)) {
+        metrics.duplicates_dropped.fetch_add(1, Ordering::Relaxed);
+        debug!(
+            "Dropping duplicate message {} from {} on {}",
+            msg.id, msg.sender, msg.channel
+        );
+        return false;
+    }
+    true
+}
+
+/// Self-reported state of a channel adapter
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", content = "reason", rename_all = "snake_case")]
+pub enum ChannelHealth {
+    Healthy,
+    /// Running, but something is wrong (e.g. a poll keeps failing)
+    Degraded(String),
+    /// Not able to receive or send at all
+    Unhealthy(String),
+}
+
+impl ChannelHealth {
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, Self::Healthy)
+    }
+}
+
+impl std::fmt::Display for ChannelHealth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Healthy => write!(f, "healthy"),
+            Self::Degraded(reason) => write!(f, "degraded: {}", reason),
+            Self::Unhealthy(reason) => write!(f, "unhealthy: {}", reason),
+        }
+    }
+}
+
+/// Health state shared between an adapter and its background tasks.
+/// Starts out `Unhealthy("not started")` until the adapter reports otherwise.
+#[derive(Debug, Clone)]
+pub struct HealthCell {
+    state: Arc<RwLock<ChannelHealth>>,
+}
+
+impl Default for HealthCell {
+    fn default() -> Self {
+        Self {
+            state: Arc::new(RwLock::new(ChannelHealth::Unhealthy(
+                "not started".to_string(),
+            ))),
+        }
+    }
+}
+
+impl HealthCell {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self) -> ChannelHealth {
+        self.state.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn set(&self, health: ChannelHealth) {
+        *self.state.write().unwrap_or_else(|e| e.into_inner()) = health;
+    }
+}
 
 /// Trait that all channel adapters implement
 #[async_trait]
 pub trait MessageChannel: Send + Sync {
     /// Start listening for messages, sending them to the provided sender
-    async fn start(&self, tx: mpsc::Sender<IncomingMessage>) -> Result<()>;
+    async fn start(&self, tx: IncomingSender) -> Result<()>;
 
     /// Send a message through this channel
     async fn send(&self, msg: OutgoingMessage) -> Result<()>;
 
+    /// Send a burst of messages through this channel, returning one result per message
+    /// in order. Channels that can deliver in bulk override this; by default each
+    /// message is sent on its own.
+    async fn send_batch(&self, msgs: Vec<OutgoingMessage>) -> Vec<Result<()>> {
+        let mut results = Vec::with_capacity(msgs.len());
+        for msg in msgs {
+            results.push(self.send(msg).await);
+        }
+        results
+    }
+
     /// Which channel type this adapter handles
     fn channel_type(&self) -> ChannelType;
+
+    /// Whether this channel does anything with messages of `kind`.
+    /// Broadcasts skip channels that would just drop the message.
+    fn supports_kind(&self, _kind: &MessageKind) -> bool {
+        true
+    }
+
+    /// Current health of this channel. Must not block: adapters report
+    /// state their background tasks have already recorded.
+    fn health(&self) -> ChannelHealth {
+        ChannelHealth::Healthy
+    }
 }
 
 /// Central message bus that routes messages between channels and the agent
 pub struct MessageBus {
     channels: HashMap<ChannelType, Box<dyn MessageChannel>>,
-    incoming_tx: mpsc::Sender<IncomingMessage>,
-    incoming_rx: mpsc::Receiver<IncomingMessage>,
+    incoming_tx: IncomingSender,
+    incoming_rx: IncomingQueue,
+    buffer_size: usize,
+    safe_mode: SafeMode,
+    dead_letters: DeadLetterQueue,
+    recent_ids: Option<RecentIds>,
+    dedup: Option<IntakeDedup>,
+    metrics: Arc<BusMetrics>,
+    send_retry: Option<RetryPolicy>,
+    send_rate_limit: Option<RateLimiter>,
 }
 
 impl MessageBus {
-    /// Create a new message bus with the specified buffer size for incoming messages
-    pub fn new(buffer_size: usize) -> Self {
-        let (tx, rx) = mpsc::channel(buffer_size);
-        info!("Created message bus with buffer size {}", buffer_size);
+    /// Create a new message bus with the specified buffer size for incoming messages,
+    /// keeping up to `dead_letter_capacity` undeliverable outgoing messages.
+    /// Redelivered message ids are dropped per `IdDedupConfig::default()`.
+    pub fn new(buffer_size: usize, dead_letter_capacity: usize) -> Self {
+        let (tx, rx) = incoming_queue(buffer_size);
+        info!(
+            "Created message bus with buffer size {} (dead-letter capacity {})",
+            buffer_size, dead_letter_capacity
+        );
         Self {
             channels: HashMap::new(),
             incoming_tx: tx,
             incoming_rx: rx,
+            buffer_size,
+            safe_mode: SafeMode::global().clone(),
+            dead_letters: DeadLetterQueue::new(dead_letter_capacity),
+            recent_ids: RecentIds::new(IdDedupConfig::default()),
+            dedup: None,
+            metrics: Arc::new(BusMetrics::default()),
+            send_retry: None,
+            send_rate_limit: None,
         }
     }
 
+    /// Change how many recent message ids are remembered to drop redeliveries,
+    /// and for how long (capacity 0 turns id dedup off)
+    pub fn with_id_dedup(mut self, config: IdDedupConfig) -> Self {
+        debug!("Id dedup: {} ids for {:?}", config.capacity, config.window);
+        self.recent_ids = RecentIds::new(config);
+        self
+    }
+
+    /// Drop duplicate incoming messages before they reach the agent (off by default)
+    pub fn with_intake_dedup(mut self, config: IntakeDedupConfig) -> Self {
+        info!(
+            "Intake dedup enabled ({:?}, window {:?})",
+            config.key, config.window
+        );
+        self.dedup = Some(IntakeDedup::new(config));
+        self
+    }
+
+    /// Wrap channels registered from now on in a `RetryingChannel` with `policy`
+    pub fn with_send_retry(mut self, policy: RetryPolicy) -> Self {
+        info!(
+            "Send retry enabled (up to {} retries, backoff {:?}..{:?})",
+            policy.max_retries, policy.initial_backoff, policy.max_backoff
+        );
+        self.send_retry = Some(policy);
+        self
+    }
+
+    /// Throttle sends on channels registered from now on with `limiter`'s
+    /// per-channel, per-recipient token buckets. Throttled sends fail with
+    /// `RetryAfter`, so they are retried after the wait when send retry is on.
+    pub fn with_send_rate_limit(mut self, limiter: RateLimiter) -> Self {
+        info!("Send rate limiting enabled");
+        self.send_rate_limit = Some(limiter);
+        self
+    }
+
+    /// Counters for this bus, shared with the receiver after `split`
+    pub fn metrics(&self) -> Arc<BusMetrics> {
+        self.metrics.clone()
+    }
+
+    /// Use a specific safe-mode flag instead of the process-wide one
+    pub fn with_safe_mode(mut self, safe_mode: SafeMode) -> Self {
+        self.safe_mode = safe_mode;
+        self
+    }
+
     /// Register a channel adapter with the bus
     pub fn register(&mut self, channel: Box<dyn MessageChannel>) {
         let channel_type = channel.channel_type();
         info!("Registering channel: {}", channel_type);
+        let channel = match &self.send_rate_limit {
+            Some(limiter) => Box::new(RateLimitedChannel::new(channel, limiter.clone())),
+            None => channel,
+        };
+        let channel = match &self.send_retry {
+            Some(policy) => Box::new(RetryingChannel::new(channel, policy.clone())),
+            None => channel,
+        };
         self.channels.insert(channel_type, channel);
     }
 
@@ -77,24 +533,70 @@ impl MessageBus {
         Ok(())
     }
 
-    /// Receive the next incoming message from any channel
-    /// Returns None if all channel senders have been dropped
-    pub async fn recv(&mut self) -> Option<IncomingMessage> {
-        self.incoming_rx.recv().await
+    /// Receive the next incoming message from any channel, high priority first
+    /// Returns `BusClosed` once no channel holds a sender anymore and nothing is queued
+    pub async fn recv(&mut self) -> Result<IncomingMessage, BusClosed> {
+        loop {
+            tokio::select! {
+                msg = self.incoming_rx.recv() => {
+                    let msg = msg.ok_or(BusClosed)?;
+                    if accept_incoming(&mut self.recent_ids, &mut self.dedup, &self.metrics, &msg) {
+                        return Ok(msg);
+                    }
+                }
+                _ = tokio::time::sleep(HEALTH_CHECK_INTERVAL) => {
+                    if !self.is_healthy() && self.incoming_rx.is_empty() {
+                        error!("{}", BusClosed);
+                        return Err(BusClosed);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether any channel still holds a live sender for incoming messages
+    pub fn is_healthy(&self) -> bool {
+        // The bus keeps one sender itself for handing out to channels
+        self.incoming_tx.normal.strong_count() > 1
     }
 
-    /// Send an outgoing message to the appropriate channel
+    /// Each registered channel's current health, for readiness probes
+    pub fn health_report(&self) -> HashMap<ChannelType, ChannelHealth> {
+        health_report(&self.channels)
+    }
+
+    /// Send an outgoing message to the appropriate channel.
+    /// Messages that can't be delivered are also kept in the dead-letter queue.
     pub async fn send(&self, msg: OutgoingMessage) -> Result<()> {
-        let channel_type = &msg.channel;
-        debug!("Routing outgoing message to channel: {}", channel_type);
+        route_outgoing(&self.channels, &self.safe_mode, &self.dead_letters, msg).await
+    }
 
-        let channel = self
-            .channels
-            .get(channel_type)
-            .ok_or_else(|| anyhow!("No channel registered for type: {}", channel_type))?;
+    /// Send a burst of queued messages, letting each channel deliver its share at once.
+    /// Returns one result per message, in order; failures are dead-lettered as with `send`.
+    pub async fn send_batch(&self, msgs: Vec<OutgoingMessage>) -> Vec<Result<()>> {
+        route_outgoing_batch(&self.channels, &self.safe_mode, &self.dead_letters, msgs).await
+    }
 
-        channel.send(msg).await?;
-        Ok(())
+    /// Send the same message through every registered channel that supports `kind`.
+    /// Returns each channel's result; failures are dead-lettered as with `send`.
+    pub async fn broadcast(
+        &self,
+        content: String,
+        kind: MessageKind,
+    ) -> Vec<(ChannelType, Result<()>)> {
+        broadcast_outgoing(
+            &self.channels,
+            &self.safe_mode,
+            &self.dead_letters,
+            content,
+            kind,
+        )
+        .await
+    }
+
+    /// Remove and return every undeliverable outgoing message, oldest first
+    pub fn drain_dead_letters(&self) -> Vec<DeadLetter> {
+        self.dead_letters.drain()
     }
 
     /// Get the number of registered channels
@@ -110,11 +612,67 @@ impl MessageBus {
     /// Split the bus into a receiver and a sender handle.
     /// This allows the receiver to be used in a select! loop while the sender
     /// is cloned into spawned tasks for routing responses.
-    pub fn split(self) -> (mpsc::Receiver<IncomingMessage>, BusSender) {
+    pub fn split(self) -> (BusReceiver, BusSender) {
         let sender = BusSender {
             channels: self.channels,
+            safe_mode: self.safe_mode,
+            dead_letters: self.dead_letters,
+        };
+        let receiver = BusReceiver {
+            rx: self.incoming_rx,
+            buffer_size: self.buffer_size,
+            recent_ids: self.recent_ids,
+            dedup: self.dedup,
+            metrics: self.metrics,
         };
-        (self.incoming_rx, sender)
+        (receiver, sender)
+    }
+}
+
+/// Receive-only handle for the message bus
+pub struct BusReceiver {
+    rx: IncomingQueue,
+    buffer_size: usize,
+    recent_ids: Option<RecentIds>,
+    dedup: Option<IntakeDedup>,
+    metrics: Arc<BusMetrics>,
+}
+
+impl BusReceiver {
+    /// Receive the next incoming message from any channel, high priority first
+    /// Returns `BusClosed` once every channel has dropped its sender and nothing is queued
+    pub async fn recv(&mut self) -> Result<IncomingMessage, BusClosed> {
+        loop {
+            match self.rx.recv().await {
+                Some(msg) => {
+                    if accept_incoming(&mut self.recent_ids, &mut self.dedup, &self.metrics, &msg) {
+                        return Ok(msg);
+                    }
+                }
+                None => {
+                    error!("{}", BusClosed);
+                    return Err(BusClosed);
+                }
+            }
+        }
+    }
+
+    /// Counters for the bus this receiver came from
+    pub fn metrics(&self) -> Arc<BusMetrics> {
+        self.metrics.clone()
+    }
+
+    /// Whether any channel still holds a live sender for incoming messages
+    pub fn is_healthy(&self) -> bool {
+        !self.rx.is_closed()
+    }
+
+    /// Restart every channel on a fresh incoming queue after the bus has closed
+    pub async fn restart(&mut self, sender: &BusSender) -> Result<()> {
+        let (tx, rx) = incoming_queue(self.buffer_size);
+        sender.start_all(tx).await?;
+        self.rx = rx;
+        Ok(())
     }
 }
 
@@ -122,27 +680,210 @@ impl MessageBus {
 /// Separated from the receiver to allow concurrent send/receive
 pub struct BusSender {
     channels: HashMap<ChannelType, Box<dyn MessageChannel>>,
+    safe_mode: SafeMode,
+    dead_letters: DeadLetterQueue,
 }
 
 impl BusSender {
-    /// Send an outgoing message to the appropriate channel
+    /// (Re)start every channel listener, delivering incoming messages to `tx`.
+    /// Succeeds if at least one channel started.
+    pub async fn start_all(&self, tx: IncomingSender) -> Result<()> {
+        let mut started = 0;
+        for (channel_type, channel) in &self.channels {
+            match channel.start(tx.clone()).await {
+                Ok(()) => {
+                    info!("Restarted channel: {}", channel_type);
+                    started += 1;
+                }
+                Err(e) => error!("Failed to restart channel {}: {}", channel_type, e),
+            }
+        }
+
+        if started == 0 {
+            return Err(anyhow!("No channels could be restarted"));
+        }
+        Ok(())
+    }
+
+    /// Send an outgoing message to the appropriate channel.
+    /// Messages that can't be delivered are also kept in the dead-letter queue.
     pub async fn send(&self, msg: OutgoingMessage) -> Result<()> {
-        let channel_type = &msg.channel;
-        debug!("Routing outgoing message to channel: {}", channel_type);
+        route_outgoing(&self.channels, &self.safe_mode, &self.dead_letters, msg).await
+    }
 
-        let channel = self
-            .channels
-            .get(channel_type)
-            .ok_or_else(|| anyhow!("No channel registered for type: {}", channel_type))?;
+    /// Send a burst of queued messages, letting each channel deliver its share at once.
+    /// Returns one result per message, in order; failures are dead-lettered as with `send`.
+    pub async fn send_batch(&self, msgs: Vec<OutgoingMessage>) -> Vec<Result<()>> {
+        route_outgoing_batch(&self.channels, &self.safe_mode, &self.dead_letters, msgs).await
+    }
 
-        channel.send(msg).await?;
-        Ok(())
+    /// Send the same message through every registered channel that supports `kind`.
+    /// Returns each channel's result; failures are dead-lettered as with `send`.
+    pub async fn broadcast(
+        &self,
+        content: String,
+        kind: MessageKind,
+    ) -> Vec<(ChannelType, Result<()>)> {
+        broadcast_outgoing(
+            &self.channels,
+            &self.safe_mode,
+            &self.dead_letters,
+            content,
+            kind,
+        )
+        .await
+    }
+
+    /// Remove and return every undeliverable outgoing message, oldest first
+    pub fn drain_dead_letters(&self) -> Vec<DeadLetter> {
+        self.dead_letters.drain()
     }
 
     /// Check if a specific channel type is registered
     pub fn has_channel(&self, channel_type: &ChannelType) -> bool {
         self.channels.contains_key(channel_type)
     }
+
+    /// Each registered channel's current health, for readiness probes
+    pub fn health_report(&self) -> HashMap<ChannelType, ChannelHealth> {
+        health_report(&self.channels)
+    }
+}
+
+fn health_report(
+    channels: &HashMap<ChannelType, Box<dyn MessageChannel>>,
+) -> HashMap<ChannelType, ChannelHealth> {
+    channels
+        .iter()
+        .map(|(channel_type, channel)| (channel_type.clone(), channel.health()))
+        .collect()
+}
+
+/// Deliver `msg` to its channel, dead-lettering it if that fails
+async fn route_outgoing(
+    channels: &HashMap<ChannelType, Box<dyn MessageChannel>>,
+    safe_mode: &SafeMode,
+    dead_letters: &DeadLetterQueue,
+    msg: OutgoingMessage,
+) -> Result<()> {
+    let channel_type = msg.channel.clone();
+    debug!("Routing outgoing message to channel: {}", channel_type);
+
+    let Some(channel) = channels.get(&channel_type) else {
+        let reason = format!("No channel registered for type: {}", channel_type);
+        // Internal messages never have a channel, so they aren't worth retrying
+        if channel_type != ChannelType::Internal {
+            dead_letters.push(msg, reason.clone());
+        }
+        return Err(anyhow!(reason));
+    };
+
+    if safe_mode.is_enabled() {
+        log_safe_mode_send(&msg);
+        return Ok(());
+    }
+
+    if let Err(e) = channel.send(msg.clone()).await {
+        dead_letters.push(msg, format!("Send via {} failed: {}", channel_type, e));
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Deliver a burst of messages, handing each run of same-channel messages to its
+/// channel in one `send_batch` call
+async fn route_outgoing_batch(
+    channels: &HashMap<ChannelType, Box<dyn MessageChannel>>,
+    safe_mode: &SafeMode,
+    dead_letters: &DeadLetterQueue,
+    msgs: Vec<OutgoingMessage>,
+) -> Vec<Result<()>> {
+    let mut results = Vec::with_capacity(msgs.len());
+    let mut msgs = msgs.into_iter().peekable();
+
+    while let Some(first) = msgs.next() {
+        let channel_type = first.channel.clone();
+        let mut run = vec![first];
+        while let Some(next) = msgs.next_if(|m| m.channel == channel_type) {
+            run.push(next);
+        }
+
+        let channel = match channels.get(&channel_type) {
+            Some(channel) if run.len() > 1 && !safe_mode.is_enabled() => channel,
+            _ => {
+                for msg in run {
+                    results.push(route_outgoing(channels, safe_mode, dead_letters, msg).await);
+                }
+                continue;
+            }
+        };
+
+        debug!(
+            "Routing {} outgoing messages to channel: {}",
+            run.len(),
+            channel_type
+        );
+        let mut outcomes = channel.send_batch(run.clone()).await.into_iter();
+        for msg in run {
+            let outcome = outcomes
+                .next()
+                .unwrap_or_else(|| Err(anyhow!("Channel returned no result for message")));
+            if let Err(e) = &outcome {
+                dead_letters.push(msg, format!("Send via {} failed: {}", channel_type, e));
+            }
+            results.push(outcome);
+        }
+    }
+
+    results
+}
+
+/// Send `content` to every channel that supports `kind`, one message per channel
+async fn broadcast_outgoing(
+    channels: &HashMap<ChannelType, Box<dyn MessageChannel>>,
+    safe_mode: &SafeMode,
+    dead_letters: &DeadLetterQueue,
+    content: String,
+    kind: MessageKind,
+) -> Vec<(ChannelType, Result<()>)> {
+    info!(
+        "Broadcasting {:?} message to {} channels",
+        kind,
+        channels.len()
+    );
+
+    let mut results = Vec::with_capacity(channels.len());
+    for (channel_type, channel) in channels {
+        if !channel.supports_kind(&kind) {
+            debug!(
+                "Skipping broadcast to {}: {:?} not supported",
+                channel_type, kind
+            );
+            continue;
+        }
+
+        let msg = OutgoingMessage {
+            content: content.clone(),
+            channel: channel_type.clone(),
+            reply_to: None,
+            thread_id: None,
+            kind: kind.clone(),
+        };
+        let result = route_outgoing(channels, safe_mode, dead_letters, msg).await;
+        if let Err(e) = &result {
+            warn!("Broadcast to {} failed: {}", channel_type, e);
+        }
+        results.push((channel_type.clone(), result));
+    }
+    results
+}
+
+/// Log an outgoing message that safe mode kept from being delivered
+fn log_safe_mode_send(msg: &OutgoingMessage) {
+    warn!(
+        "[SAFE MODE] would have sent to {}: {}",
+        msg.channel, msg.content
+    );
 }
 
 #[cfg(test)]
@@ -155,6 +896,7 @@ mod tests {
     struct MockChannel {
         channel_type: ChannelType,
         sent: Arc<AtomicBool>,
+        health: Option<HealthCell>,
     }
 
     impl MockChannel {
@@ -162,13 +904,14 @@ mod tests {
             Self {
                 channel_type,
                 sent: Arc::new(AtomicBool::new(false)),
+                health: None,
             }
         }
     }
 
     #[async_trait]
     impl MessageChannel for MockChannel {
-        async fn start(&self, _tx: mpsc::Sender<IncomingMessage>) -> Result<()> {
+        async fn start(&self, _tx: IncomingSender) -> Result<()> {
             Ok(())
         }
 
@@ -180,17 +923,24 @@ mod tests {
         fn channel_type(&self) -> ChannelType {
             self.channel_type.clone()
         }
+
+        fn health(&self) -> ChannelHealth {
+            match &self.health {
+                Some(cell) => cell.get(),
+                None => ChannelHealth::Healthy,
+            }
+        }
     }
 
     #[test]
     fn test_bus_creation() {
-        let bus = MessageBus::new(32);
+        let bus = MessageBus::new(32, 16);
         assert_eq!(bus.channel_count(), 0);
     }
 
     #[test]
     fn test_bus_register() {
-        let mut bus = MessageBus::new(32);
+        let mut bus = MessageBus::new(32, 16);
         bus.register(Box::new(MockChannel::new(ChannelType::Discord)));
         assert_eq!(bus.channel_count(), 1);
         assert!(bus.has_channel(&ChannelType::Discord));
@@ -199,7 +949,7 @@ mod tests {
 
     #[test]
     fn test_bus_register_multiple() {
-        let mut bus = MessageBus::new(32);
+        let mut bus = MessageBus::new(32, 16);
         bus.register(Box::new(MockChannel::new(ChannelType::Discord)));
         bus.register(Box::new(MockChannel::new(ChannelType::Slack)));
         bus.register(Box::new(MockChannel::new(ChannelType::IMessage)));
@@ -208,15 +958,43 @@ mod tests {
 
     #[tokio::test]
     async fn test_bus_start_all() {
-        let mut bus = MessageBus::new(32);
+        let mut bus = MessageBus::new(32, 16);
         bus.register(Box::new(MockChannel::new(ChannelType::Discord)));
         let result = bus.start_all().await;
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_health_report() {
+        let mut bus = MessageBus::new(32, 16);
+        let cell = HealthCell::new();
+        let mut slack = MockChannel::new(ChannelType::Slack);
+        slack.health = Some(cell.clone());
+        bus.register(Box::new(MockChannel::new(ChannelType::Discord)));
+        bus.register(Box::new(slack));
+
+        let report = bus.health_report();
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[&ChannelType::Discord], ChannelHealth::Healthy);
+        assert_eq!(
+            report[&ChannelType::Slack],
+            ChannelHealth::Unhealthy("not started".to_string())
+        );
+
+        cell.set(ChannelHealth::Degraded("poll failed".to_string()));
+        let (_rx, sender) = bus.split();
+        let report = sender.health_report();
+        assert_eq!(
+            report[&ChannelType::Slack].to_string(),
+            "degraded: poll failed"
+        );
+        cell.set(ChannelHealth::Healthy);
+        assert!(sender.health_report()[&ChannelType::Slack].is_healthy());
+    }
+
     #[tokio::test]
     async fn test_bus_split() {
-        let mut bus = MessageBus::new(32);
+        let mut bus = MessageBus::new(32, 16);
         bus.register(Box::new(MockChannel::new(ChannelType::Discord)));
         bus.start_all().await.unwrap();
 
@@ -227,7 +1005,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_bus_sender_send() {
-        let mut bus = MessageBus::new(32);
+        let mut bus = MessageBus::new(32, 16);
         let mock = MockChannel::new(ChannelType::Discord);
         let sent_flag = mock.sent.clone();
         bus.register(Box::new(mock));
@@ -239,15 +1017,39 @@ mod tests {
             content: "test".to_string(),
             channel: ChannelType::Discord,
             reply_to: None,
+            thread_id: None,
             kind: MessageKind::Response,
         };
         sender.send(msg).await.unwrap();
         assert!(sent_flag.load(Ordering::SeqCst));
     }
 
+    #[tokio::test]
+    async fn test_bus_safe_mode_suppresses_send() {
+        let mode = SafeMode::new();
+        let mut bus = MessageBus::new(32, 16).with_safe_mode(mode.clone());
+        let mock = MockChannel::new(ChannelType::Discord);
+        let sent_flag = mock.sent.clone();
+        bus.register(Box::new(mock));
+        bus.start_all().await.unwrap();
+
+        let (_rx, sender) = bus.split();
+        mode.enable();
+
+        let msg = OutgoingMessage {
+            content: "test".to_string(),
+            channel: ChannelType::Discord,
+            reply_to: None,
+            thread_id: None,
+            kind: MessageKind::Response,
+        };
+        sender.send(msg).await.unwrap();
+        assert!(!sent_flag.load(Ordering::SeqCst));
+    }
+
     #[tokio::test]
     async fn test_bus_sender_unknown_channel() {
-        let mut bus = MessageBus::new(32);
+        let mut bus = MessageBus::new(32, 16);
         bus.register(Box::new(MockChannel::new(ChannelType::Discord)));
         bus.start_all().await.unwrap();
 
@@ -257,15 +1059,468 @@ mod tests {
             content: "test".to_string(),
             channel: ChannelType::Slack,
             reply_to: None,
+            thread_id: None,
             kind: MessageKind::Response,
         };
         let result = sender.send(msg).await;
         assert!(result.is_err());
+
+        let dead = sender.drain_dead_letters();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].message.channel, ChannelType::Slack);
+        assert!(dead[0].reason.contains("No channel registered"));
+        assert!(sender.drain_dead_letters().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bus_internal_messages_not_dead_lettered() {
+        let mut bus = MessageBus::new(32, 16);
+        bus.register(Box::new(MockChannel::new(ChannelType::Discord)));
+        bus.start_all().await.unwrap();
+
+        let (_rx, sender) = bus.split();
+
+        let msg = OutgoingMessage {
+            content: "test".to_string(),
+            channel: ChannelType::Internal,
+            reply_to: None,
+            thread_id: None,
+            kind: MessageKind::Response,
+        };
+        assert!(sender.send(msg).await.is_err());
+        assert!(sender.drain_dead_letters().is_empty());
+    }
+
+    /// Channel whose sends always fail
+    struct FailingChannel;
+
+    #[async_trait]
+    impl MessageChannel for FailingChannel {
+        async fn start(&self, _tx: IncomingSender) -> Result<()> {
+            Ok(())
+        }
+
+        async fn send(&self, _msg: OutgoingMessage) -> Result<()> {
+            Err(anyhow!("connection reset"))
+        }
+
+        fn channel_type(&self) -> ChannelType {
+            ChannelType::Discord
+        }
+    }
+
+    /// Channel that records the size of each batch it is handed and fails "bad" messages
+    struct BatchingChannel {
+        batches: Arc<std::sync::Mutex<Vec<usize>>>,
+    }
+
+    #[async_trait]
+    impl MessageChannel for BatchingChannel {
+        async fn start(&self, _tx: IncomingSender) -> Result<()> {
+            Ok(())
+        }
+
+        async fn send(&self, msg: OutgoingMessage) -> Result<()> {
+            self.send_batch(vec![msg]).await.remove(0)
+        }
+
+        async fn send_batch(&self, msgs: Vec<OutgoingMessage>) -> Vec<Result<()>> {
+            self.batches.lock().unwrap().push(msgs.len());
+            msgs.iter()
+                .map(|m| {
+                    if m.content == "bad" {
+                        Err(anyhow!("rejected"))
+                    } else {
+                        Ok(())
+                    }
+                })
+                .collect()
+        }
+
+        fn channel_type(&self) -> ChannelType {
+            ChannelType::Reminders
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bus_send_batch_groups_runs_per_channel() {
+        let batches = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut bus = MessageBus::new(32, 16);
+        bus.register(Box::new(BatchingChannel {
+            batches: batches.clone(),
+        }));
+        bus.register(Box::new(MockChannel::new(ChannelType::Discord)));
+
+        let msg = |content: &str, channel: ChannelType| OutgoingMessage {
+            content: content.to_string(),
+            channel,
+            reply_to: None,
+            thread_id: None,
+            kind: MessageKind::Response,
+        };
+        let results = bus
+            .send_batch(vec![
+                msg("a", ChannelType::Reminders),
+                msg("bad", ChannelType::Reminders),
+                msg("c", ChannelType::Reminders),
+                msg("d", ChannelType::Discord),
+                msg("e", ChannelType::Reminders),
+            ])
+            .await;
+
+        assert_eq!(results.len(), 5);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        assert!(results[2].is_ok() && results[3].is_ok() && results[4].is_ok());
+        assert_eq!(*batches.lock().unwrap(), vec![3, 1]);
+
+        let dead = bus.drain_dead_letters();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].message.content, "bad");
+    }
+
+    #[tokio::test]
+    async fn test_bus_send_rate_limit() {
+        use crate::rate_limit::{BucketConfig, RetryAfter};
+
+        let batches = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let limiter = RateLimiter::for_sends().with_channel_limit(
+            ChannelType::Reminders,
+            BucketConfig::per(2, std::time::Duration::from_secs(3600)),
+        );
+        let mut bus = MessageBus::new(32, 16).with_send_rate_limit(limiter);
+        bus.register(Box::new(BatchingChannel {
+            batches: batches.clone(),
+        }));
+        bus.register(Box::new(MockChannel::new(ChannelType::Discord)));
+
+        let msg = |content: &str, channel: ChannelType, reply_to: Option<&str>| OutgoingMessage {
+            content: content.to_string(),
+            channel,
+            reply_to: reply_to.map(String::from),
+            thread_id: None,
+            kind: MessageKind::Response,
+        };
+        let results = bus
+            .send_batch(vec![
+                msg("a", ChannelType::Reminders, None),
+                msg("b", ChannelType::Reminders, None),
+                msg("c", ChannelType::Reminders, None),
+            ])
+            .await;
+
+        // Only the messages that got a token reach the channel
+        assert_eq!(*batches.lock().unwrap(), vec![2]);
+        assert!(results[0].is_ok() && results[1].is_ok());
+        let err = results[2].as_ref().unwrap_err();
+        assert!(err.downcast_ref::<RetryAfter>().is_some());
+        assert_eq!(bus.drain_dead_letters()[0].message.content, "c");
+
+        // Other recipients and unlimited channels aren't throttled
+        assert!(
+            bus.send(msg("d", ChannelType::Reminders, Some("reminder_list-2")))
+                .await
+                .is_ok()
+        );
+        assert!(bus.send(msg("e", ChannelType::Discord, None)).await.is_ok());
+        assert!(
+            bus.send(msg("f", ChannelType::Reminders, None))
+                .await
+                .is_err()
+        );
+    }
+
+    /// Channel that ignores acknowledgments
+    struct ResponsesOnlyChannel;
+
+    #[async_trait]
+    impl MessageChannel for ResponsesOnlyChannel {
+        async fn start(&self, _tx: IncomingSender) -> Result<()> {
+            Ok(())
+        }
+
+        async fn send(&self, _msg: OutgoingMessage) -> Result<()> {
+            Ok(())
+        }
+
+        fn channel_type(&self) -> ChannelType {
+            ChannelType::Notes
+        }
+
+        fn supports_kind(&self, kind: &MessageKind) -> bool {
+            *kind != MessageKind::Acknowledgment
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bus_broadcast_reports_per_channel() {
+        let mut bus = MessageBus::new(32, 16);
+        let mock = MockChannel::new(ChannelType::Slack);
+        let sent_flag = mock.sent.clone();
+        bus.register(Box::new(mock));
+        bus.register(Box::new(FailingChannel));
+        bus.register(Box::new(ResponsesOnlyChannel));
+        let (_rx, sender) = bus.split();
+
+        let mut results = sender
+            .broadcast("meepo is restarting".to_string(), MessageKind::Response)
+            .await;
+        results.sort_by_key(|(channel, _)| channel.to_string());
+        let outcome: Vec<_> = results
+            .iter()
+            .map(|(channel, result)| (channel.clone(), result.is_ok()))
+            .collect();
+        assert_eq!(
+            outcome,
+            vec![
+                (ChannelType::Discord, false),
+                (ChannelType::Notes, true),
+                (ChannelType::Slack, true),
+            ]
+        );
+        assert!(sent_flag.load(Ordering::SeqCst));
+        assert_eq!(sender.drain_dead_letters().len(), 1);
+
+        // Channels that don't handle acknowledgments are skipped, not failed
+        let results = sender
+            .broadcast("working...".to_string(), MessageKind::Acknowledgment)
+            .await;
+        assert_eq!(results.len(), 2);
+        assert!(
+            results
+                .iter()
+                .all(|(channel, _)| *channel != ChannelType::Notes)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bus_dead_letters_failed_sends_and_stays_bounded() {
+        let mut bus = MessageBus::new(32, 2);
+        bus.register(Box::new(FailingChannel));
+
+        for i in 0..3 {
+            let msg = OutgoingMessage {
+                content: format!("msg-{}", i),
+                channel: ChannelType::Discord,
+                reply_to: None,
+                thread_id: None,
+                kind: MessageKind::Response,
+            };
+            assert!(bus.send(msg).await.is_err());
+        }
+
+        // Oldest entry was dropped to stay within capacity
+        let dead = bus.drain_dead_letters();
+        assert_eq!(dead.len(), 2);
+        assert_eq!(dead[0].message.content, "msg-1");
+        assert_eq!(dead[1].message.content, "msg-2");
+        assert!(dead[0].reason.contains("connection reset"));
+    }
+
+    /// Channel whose listener delivers a single greeting and then exits
+    struct GreetingChannel;
+
+    #[async_trait]
+    impl MessageChannel for GreetingChannel {
+        async fn start(&self, tx: IncomingSender) -> Result<()> {
+            tokio::spawn(async move {
+                let _ = tx
+                    .send(IncomingMessage {
+                        id: "hello".to_string(),
+                        sender: "user".to_string(),
+                        content: "hello".to_string(),
+                        channel: ChannelType::Discord,
+                        timestamp: chrono::Utc::now(),
+                        priority: Priority::Normal,
+                        command: None,
+                        thread_id: None,
+                    })
+                    .await;
+            });
+            Ok(())
+        }
+
+        async fn send(&self, _msg: OutgoingMessage) -> Result<()> {
+            Ok(())
+        }
+
+        fn channel_type(&self) -> ChannelType {
+            ChannelType::Discord
+        }
+    }
+
+    #[test]
+    fn test_bus_is_healthy() {
+        let bus = MessageBus::new(32, 16);
+        assert!(!bus.is_healthy());
+
+        let _channel_tx = bus.incoming_tx.clone();
+        assert!(bus.is_healthy());
+    }
+
+    #[tokio::test]
+    async fn test_bus_receiver_reports_closed_and_restarts() {
+        // The restarted channel greets again under the same id
+        let mut bus = MessageBus::new(32, 16).with_id_dedup(IdDedupConfig {
+            capacity: 0,
+            window: Duration::ZERO,
+        });
+        bus.register(Box::new(GreetingChannel));
+        bus.start_all().await.unwrap();
+
+        let (mut rx, sender) = bus.split();
+        assert_eq!(rx.recv().await.unwrap().id, "hello");
+
+        // The only listener has exited: a distinct error instead of a silent None
+        assert_eq!(rx.recv().await.unwrap_err(), BusClosed);
+        assert!(!rx.is_healthy());
+
+        rx.restart(&sender).await.unwrap();
+        assert_eq!(rx.recv().await.unwrap().id, "hello");
+    }
+
+    fn incoming(id: &str, sender: &str, content: &str, channel: ChannelType) -> IncomingMessage {
+        IncomingMessage {
+            id: id.to_string(),
+            sender: sender.to_string(),
+            content: content.to_string(),
+            channel,
+            timestamp: chrono::Utc::now(),
+            priority: Priority::Normal,
+            command: None,
+            thread_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bus_recv_prefers_high_priority() {
+        let bus = MessageBus::new(32, 16);
+        let tx = bus.incoming_tx.clone();
+        let (mut rx, _sender) = bus.split();
+
+        for id in ["n1", "n2"] {
+            tx.send(incoming(id, "user", id, ChannelType::Slack))
+                .await
+                .unwrap();
+        }
+        for id in ["h1", "h2"] {
+            let mut msg = incoming(id, "system", id, ChannelType::Internal);
+            msg.priority = Priority::High;
+            tx.send(msg).await.unwrap();
+        }
+        tx.send(incoming("n3", "user", "n3", ChannelType::Slack))
+            .await
+            .unwrap();
+
+        let mut order = Vec::new();
+        for _ in 0..5 {
+            order.push(rx.recv().await.unwrap().id);
+        }
+        assert_eq!(order, ["h1", "h2", "n1", "n2", "n3"]);
+    }
+
+    #[tokio::test]
+    async fn test_bus_intake_dedup_drops_duplicates() {
+        let bus = MessageBus::new(32, 16).with_intake_dedup(IntakeDedupConfig {
+            window: Duration::from_secs(60),
+            key: DedupKey::SenderContent,
+        });
+        let tx = bus.incoming_tx.clone();
+        let (mut rx, _sender) = bus.split();
+
+        tx.send(incoming("1", "alice", "Hello  there", ChannelType::Slack))
+            .await
+            .unwrap();
+        // Redelivery and a cross-post with only whitespace/case differences
+        tx.send(incoming("2", "alice", "Hello there", ChannelType::Slack))
+            .await
+            .unwrap();
+        tx.send(incoming("3", "alice", "hello there ", ChannelType::Discord))
+            .await
+            .unwrap();
+        tx.send(incoming("4", "bob", "Hello there", ChannelType::Slack))
+            .await
+            .unwrap();
+
+        assert_eq!(rx.recv().await.unwrap().id, "1");
+        assert_eq!(rx.recv().await.unwrap().id, "4");
+        assert_eq!(rx.metrics().duplicates_dropped(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_bus_drops_redelivered_ids() {
+        let bus = MessageBus::new(32, 16);
+        let tx = bus.incoming_tx.clone();
+        let (mut rx, _sender) = bus.split();
+
+        tx.send(incoming("1", "alice", "first", ChannelType::Slack))
+            .await
+            .unwrap();
+        tx.send(incoming("1", "alice", "first", ChannelType::Slack))
+            .await
+            .unwrap();
+        tx.send(incoming("2", "alice", "first", ChannelType::Slack))
+            .await
+            .unwrap();
+
+        // Same id is dropped; same content under a new id is not (intake dedup is off)
+        assert_eq!(rx.recv().await.unwrap().id, "1");
+        assert_eq!(rx.recv().await.unwrap().id, "2");
+        assert_eq!(rx.metrics().duplicates_dropped(), 1);
+    }
+
+    #[test]
+    fn test_recent_ids_bounded_by_count_and_age() {
+        let mut ids = RecentIds::new(IdDedupConfig {
+            capacity: 2,
+            window: Duration::from_secs(60),
+        })
+        .unwrap();
+        assert!(!ids.is_duplicate("a"));
+        assert!(!ids.is_duplicate("b"));
+        assert!(ids.is_duplicate("a"));
+        // "b" is least recently seen, so "c" evicts it
+        assert!(!ids.is_duplicate("c"));
+        assert!(!ids.is_duplicate("b"));
+
+        let mut expired = RecentIds::new(IdDedupConfig {
+            capacity: 2,
+            window: Duration::ZERO,
+        })
+        .unwrap();
+        assert!(!expired.is_duplicate("a"));
+        assert!(!expired.is_duplicate("a"));
+
+        assert!(
+            RecentIds::new(IdDedupConfig {
+                capacity: 0,
+                window: Duration::from_secs(60),
+            })
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_intake_dedup_key_and_window() {
+        let mut per_channel = IntakeDedup::new(IntakeDedupConfig {
+            window: Duration::from_secs(60),
+            key: DedupKey::SenderContentChannel,
+        });
+        assert!(!per_channel.is_duplicate(&incoming("1", "a", "hi", ChannelType::Slack)));
+        assert!(!per_channel.is_duplicate(&incoming("2", "a", "hi", ChannelType::Discord)));
+        assert!(per_channel.is_duplicate(&incoming("3", "a", "hi", ChannelType::Slack)));
+
+        let mut expired = IntakeDedup::new(IntakeDedupConfig {
+            window: Duration::ZERO,
+            key: DedupKey::Content,
+        });
+        assert!(!expired.is_duplicate(&incoming("1", "a", "hi", ChannelType::Slack)));
+        assert!(!expired.is_duplicate(&incoming("2", "b", "hi", ChannelType::Slack)));
     }
 
     #[tokio::test]
     async fn test_bus_incoming_messages() {
-        let mut bus = MessageBus::new(32);
+        let mut bus = MessageBus::new(32, 16);
         let mock = MockChannel::new(ChannelType::Discord);
         bus.register(Box::new(mock));
 
@@ -282,6 +1537,9 @@ mod tests {
             content: "hello".to_string(),
             channel: ChannelType::Discord,
             timestamp: chrono::Utc::now(),
+            priority: Priority::Normal,
+            command: None,
+            thread_id: None,
         };
         tx.send(incoming).await.unwrap();
 
@@ -1,16 +1,15 @@
 //! Apple Contacts channel adapter using AppleScript polling
 
-use crate::bus::MessageChannel;
+use crate::bus::{IncomingSender, MessageChannel};
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use chrono::Utc;
-use meepo_core::types::{ChannelType, IncomingMessage, MessageKind, OutgoingMessage};
+use meepo_core::types::{ChannelType, IncomingMessage, MessageKind, OutgoingMessage, Priority};
 use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::process::Command;
 use tokio::sync::Mutex;
-use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
 /// Apple Contacts channel adapter that polls Contacts.app for contacts
@@ -48,7 +47,7 @@ impl ContactsChannel {
     }
 
     /// Poll Contacts.app for contacts in the configured group
-    async fn poll_contacts(&self, tx: &mpsc::Sender<IncomingMessage>) -> Result<()> {
+    async fn poll_contacts(&self, tx: &IncomingSender) -> Result<()> {
         let group = Self::escape_applescript(&self.group_name);
 
         let script = format!(
@@ -218,6 +217,9 @@ end tell
                 content,
                 channel: ChannelType::Contacts,
                 timestamp: Utc::now(),
+                priority: Priority::Normal,
+                command: None,
+                thread_id: None,
             };
 
             info!("New contact from Contacts.app: {}", display_name);
@@ -406,7 +408,7 @@ end tell
 
 #[async_trait]
 impl MessageChannel for ContactsChannel {
-    async fn start(&self, tx: mpsc::Sender<IncomingMessage>) -> Result<()> {
+    async fn start(&self, tx: IncomingSender) -> Result<()> {
         info!("Starting Contacts channel adapter");
         info!("Poll interval: {:?}", self.poll_interval);
         info!("Contacts group: {}", self.group_name);
@@ -456,6 +458,11 @@ impl MessageChannel for ContactsChannel {
     fn channel_type(&self) -> ChannelType {
         ChannelType::Contacts
     }
+
+    fn supports_kind(&self, kind: &MessageKind) -> bool {
+        // Acknowledgments are silently ignored
+        *kind != MessageKind::Acknowledgment
+    }
 }
 
 #[cfg(test)]
@@ -1,30 +1,188 @@
 //! Discord channel adapter using Serenity
 
-use crate::bus::MessageChannel;
+use crate::bus::{ChannelHealth, HealthCell, IncomingSender, MessageChannel};
 use crate::rate_limit::RateLimiter;
+use crate::retry::HttpStatusError;
+use crate::util::split_message;
 use anyhow::{Result, anyhow};
 use chrono::Utc;
 use dashmap::DashMap;
 use lru::LruCache;
-use meepo_core::types::{ChannelType, IncomingMessage, MessageKind, OutgoingMessage};
+use meepo_core::types::{
+    ChannelType, IncomingMessage, MessageKind, OutgoingMessage, Priority, SlashCommand,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use serenity::{
-    async_trait, gateway::GatewayError, model::gateway::Ready, model::prelude::*, prelude::*,
+    async_trait,
+    builder::{
+        Builder, CreateCommand, CreateCommandOption, CreateInteractionResponse,
+        CreateInteractionResponseFollowup, CreateInteractionResponseMessage, CreateMessage,
+        EditInteractionResponse,
+    },
+    gateway::GatewayError,
+    model::application::{
+        Command, CommandDataOption, CommandDataOptionValue, CommandOptionType, Interaction,
+    },
+    model::gateway::Ready,
+    model::prelude::*,
+    prelude::*,
 };
 use std::num::NonZeroUsize;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::mpsc;
 use tokio::sync::{Mutex, RwLock};
 use tracing::{debug, error, info, warn};
 
 const MAX_MESSAGE_CHANNELS: usize = 1000;
 const MAX_MESSAGE_SIZE: usize = 10_240;
+/// Discord rejects message content longer than this many characters
+const MAX_OUTGOING_LENGTH: usize = 2000;
+
+/// The Discord message a "discord_{id}" message id refers to, for reply references
+fn reply_reference(reply_to: &str) -> Option<MessageId> {
+    reply_to
+        .strip_prefix("discord_")?
+        .parse::<u64>()
+        .ok()
+        .filter(|id| *id != 0)
+        .map(MessageId::new)
+}
+
+/// A Discord thread is its own channel, so `thread_id` is that channel's id
+fn thread_channel(thread_id: &str) -> Option<ChannelId> {
+    thread_id
+        .parse::<u64>()
+        .ok()
+        .filter(|id| *id != 0)
+        .map(ChannelId::new)
+}
+
+/// A slash command to register with Discord on startup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscordSlashCommand {
+    /// Command name, lowercase (invoked as `/name`)
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub options: Vec<DiscordCommandOption>,
+}
+
+/// An argument of a slash command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscordCommandOption {
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub kind: DiscordOptionKind,
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Value type of a slash command option
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiscordOptionKind {
+    #[default]
+    String,
+    Integer,
+    Number,
+    Boolean,
+    User,
+    Channel,
+}
+
+impl DiscordSlashCommand {
+    fn to_builder(&self) -> CreateCommand {
+        self.options.iter().fold(
+            CreateCommand::new(&self.name).description(&self.description),
+            |command, option| {
+                let kind = match option.kind {
+                    DiscordOptionKind::String => CommandOptionType::String,
+                    DiscordOptionKind::Integer => CommandOptionType::Integer,
+                    DiscordOptionKind::Number => CommandOptionType::Number,
+                    DiscordOptionKind::Boolean => CommandOptionType::Boolean,
+                    DiscordOptionKind::User => CommandOptionType::User,
+                    DiscordOptionKind::Channel => CommandOptionType::Channel,
+                };
+                command.add_option(
+                    CreateCommandOption::new(kind, &option.name, &option.description)
+                        .required(option.required),
+                )
+            },
+        )
+    }
+}
+
+/// A deferred slash command invocation awaiting its reply
+#[derive(Debug, Clone)]
+struct PendingInteraction {
+    token: String,
+    /// Whether the deferred "thinking..." response has been replaced yet
+    responded: bool,
+}
+
+/// Convert command options to JSON, nesting subcommands as objects
+fn options_to_json(options: &[CommandDataOption]) -> serde_json::Map<String, Value> {
+    options
+        .iter()
+        .map(|option| {
+            let value = match &option.value {
+                CommandDataOptionValue::String(s) => Value::from(s.clone()),
+                CommandDataOptionValue::Integer(i) => Value::from(*i),
+                CommandDataOptionValue::Number(n) => Value::from(*n),
+                CommandDataOptionValue::Boolean(b) => Value::from(*b),
+                CommandDataOptionValue::SubCommand(nested)
+                | CommandDataOptionValue::SubCommandGroup(nested) => {
+                    Value::Object(options_to_json(nested))
+                }
+                CommandDataOptionValue::User(id) => Value::from(id.to_string()),
+                CommandDataOptionValue::Channel(id) => Value::from(id.to_string()),
+                CommandDataOptionValue::Role(id) => Value::from(id.to_string()),
+                CommandDataOptionValue::Mentionable(id) => Value::from(id.to_string()),
+                CommandDataOptionValue::Attachment(id) => Value::from(id.to_string()),
+                CommandDataOptionValue::Autocomplete { value, .. } => Value::from(value.clone()),
+                _ => Value::Null,
+            };
+            (option.name.clone(), value)
+        })
+        .collect()
+}
+
+/// Render a command as text for the agent, e.g. "/remind when: tomorrow what: call mom"
+fn command_content(command: &SlashCommand) -> String {
+    fn push_options(out: &mut String, options: &serde_json::Map<String, Value>) {
+        for (name, value) in options {
+            match value {
+                Value::Object(nested) => {
+                    out.push(' ');
+                    out.push_str(name);
+                    push_options(out, nested);
+                }
+                Value::String(s) => out.push_str(&format!(" {}: {}", name, s)),
+                other => out.push_str(&format!(" {}: {}", name, other)),
+            }
+        }
+    }
+
+    let mut content = format!("/{}", command.name);
+    push_options(&mut content, &command.options);
+    content
+}
+
+/// How a Discord user is shown as a message sender
+fn display_name(user: &User) -> String {
+    match user.discriminator {
+        Some(d) => format!("{}#{:04}", user.name, d),
+        None => user.name.clone(),
+    }
+}
 
 /// Type key for storing the incoming message sender in Serenity's TypeMap
 struct MessageSender;
 
 impl TypeMapKey for MessageSender {
-    type Value = mpsc::Sender<IncomingMessage>;
+    type Value = IncomingSender;
 }
 
 /// Type key for storing the user-to-channel mapping
@@ -55,6 +213,20 @@ impl TypeMapKey for RateLimiterKey {
     type Value = RateLimiter;
 }
 
+/// Type key for storing the slash commands to register
+struct SlashCommands;
+
+impl TypeMapKey for SlashCommands {
+    type Value = Vec<DiscordSlashCommand>;
+}
+
+/// Type key for storing message_id -> pending interaction for command replies (LRU-bounded)
+struct InteractionMap;
+
+impl TypeMapKey for InteractionMap {
+    type Value = Arc<Mutex<LruCache<String, PendingInteraction>>>;
+}
+
 /// Event handler for Discord messages
 struct DiscordHandler;
 
@@ -137,13 +309,14 @@ impl EventHandler for DiscordHandler {
         // Convert to IncomingMessage
         let incoming = IncomingMessage {
             id: msg_id,
-            sender: match msg.author.discriminator {
-                Some(d) => format!("{}#{:04}", msg.author.name, d),
-                None => msg.author.name.clone(),
-            },
+            sender: display_name(&msg.author),
             content: msg.content.clone(),
             channel: ChannelType::Discord,
             timestamp: Utc::now(),
+            // Only DMs reach this point, and those are addressed to meepo
+            priority: Priority::High,
+            command: None,
+            thread_id: None,
         };
 
         info!("Forwarding Discord message from {}", incoming.sender);
@@ -154,8 +327,133 @@ impl EventHandler for DiscordHandler {
         }
     }
 
-    async fn ready(&self, _ctx: Context, ready: Ready) {
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        let Interaction::Command(command) = interaction else {
+            return;
+        };
+
+        debug!(
+            "Received slash command /{} from user: {} ({})",
+            command.data.name, command.user.name, command.user.id
+        );
+
+        let data = ctx.data.read().await;
+        let allowed = data
+            .get::<AllowedUsers>()
+            .is_some_and(|users| users.contains(&command.user.id));
+        let rate_limited = data
+            .get::<RateLimiterKey>()
+            .is_some_and(|limiter| !limiter.check_and_record(&command.user.id.to_string()));
+
+        if !allowed || rate_limited {
+            drop(data);
+            if !allowed {
+                warn!(
+                    "Ignoring slash command from unauthorized user: {}",
+                    command.user.id
+                );
+            }
+            let reply = if allowed {
+                "You're sending commands too quickly. Try again shortly."
+            } else {
+                "You're not allowed to use this bot."
+            };
+            let response = CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(reply)
+                    .ephemeral(true),
+            );
+            if let Err(e) = command.create_response(&ctx.http, response).await {
+                warn!("Failed to reject Discord slash command: {}", e);
+            }
+            return;
+        }
+
+        // Discord fails the interaction unless it's acknowledged within 3 seconds,
+        // so defer right away; the reply replaces the "thinking..." placeholder
+        if let Err(e) = command.defer(&ctx.http).await {
+            error!(
+                "Failed to defer Discord slash command /{}: {}",
+                command.data.name, e
+            );
+            return;
+        }
+
+        let msg_id = format!("discord_{}", command.id);
+        if command.guild_id.is_none() {
+            data.get::<UserChannelMap>()
+                .expect("UserChannelMap not initialized")
+                .insert(command.user.id, command.channel_id);
+        }
+        data.get::<MessageChannelMap>()
+            .expect("MessageChannelMap not initialized")
+            .lock()
+            .await
+            .put(msg_id.clone(), command.channel_id);
+        data.get::<InteractionMap>()
+            .expect("InteractionMap not initialized")
+            .lock()
+            .await
+            .put(
+                msg_id.clone(),
+                PendingInteraction {
+                    token: command.token.clone(),
+                    responded: false,
+                },
+            );
+        let tx = data
+            .get::<MessageSender>()
+            .expect("MessageSender not initialized")
+            .clone();
+        drop(data);
+
+        let slash = SlashCommand {
+            name: command.data.name.clone(),
+            options: options_to_json(&command.data.options),
+        };
+        let incoming = IncomingMessage {
+            id: msg_id,
+            sender: display_name(&command.user),
+            content: command_content(&slash),
+            channel: ChannelType::Discord,
+            timestamp: Utc::now(),
+            priority: Priority::High,
+            command: Some(slash),
+            thread_id: None,
+        };
+
+        info!(
+            "Forwarding Discord slash command {} from {}",
+            incoming.content, incoming.sender
+        );
+
+        if let Err(e) = tx.send(incoming).await {
+            error!("Failed to send Discord slash command to bus: {}", e);
+        }
+    }
+
+    async fn ready(&self, ctx: Context, ready: Ready) {
         info!("Discord bot connected as {}", ready.user.name);
+
+        let commands = ctx
+            .data
+            .read()
+            .await
+            .get::<SlashCommands>()
+            .cloned()
+            .unwrap_or_default();
+        if commands.is_empty() {
+            return;
+        }
+
+        let builders = commands
+            .iter()
+            .map(DiscordSlashCommand::to_builder)
+            .collect();
+        match Command::set_global_commands(&ctx.http, builders).await {
+            Ok(registered) => info!("Registered {} Discord slash command(s)", registered.len()),
+            Err(e) => error!("Failed to register Discord slash commands: {}", e),
+        }
     }
 }
 
@@ -167,6 +465,11 @@ pub struct DiscordChannel {
     user_channel_map: Arc<DashMap<UserId, ChannelId>>,
     /// Maps message_id -> channel_id for reply-to tracking (LRU-bounded)
     message_channels: Arc<Mutex<LruCache<String, ChannelId>>>,
+    slash_commands: Vec<DiscordSlashCommand>,
+    /// Maps message_id -> deferred slash command awaiting a reply (LRU-bounded)
+    interactions: Arc<Mutex<LruCache<String, PendingInteraction>>>,
+    /// Gateway connection state, updated by the client task
+    health: HealthCell,
 }
 
 impl DiscordChannel {
@@ -184,7 +487,53 @@ impl DiscordChannel {
             message_channels: Arc::new(Mutex::new(LruCache::new(
                 NonZeroUsize::new(MAX_MESSAGE_CHANNELS).unwrap(),
             ))),
+            slash_commands: Vec::new(),
+            interactions: Arc::new(Mutex::new(LruCache::new(
+                NonZeroUsize::new(MAX_MESSAGE_CHANNELS).unwrap(),
+            ))),
+            health: HealthCell::new(),
+        }
+    }
+
+    /// Register these slash commands on startup; invocations arrive as
+    /// `IncomingMessage`s with `command` set
+    pub fn with_slash_commands(mut self, commands: Vec<DiscordSlashCommand>) -> Self {
+        self.slash_commands = commands;
+        self
+    }
+
+    /// Reply to a deferred slash command through its interaction token.
+    ///
+    /// The first reply replaces the "thinking..." placeholder; later ones are
+    /// follow-ups. Returns `Ok(false)` if `reply_to` isn't a pending command.
+    async fn reply_to_interaction(
+        &self,
+        http: &serenity::http::Http,
+        reply_to: &str,
+        content: &str,
+    ) -> Result<bool> {
+        let pending = self.interactions.lock().await.get(reply_to).cloned();
+        let Some(pending) = pending else {
+            return Ok(false);
+        };
+
+        if pending.responded {
+            CreateInteractionResponseFollowup::new()
+                .content(content)
+                .execute(http, (None, &pending.token))
+                .await
+        } else {
+            EditInteractionResponse::new()
+                .content(content)
+                .execute(http, &pending.token)
+                .await
+        }
+        .map_err(|e| anyhow!("Failed to reply to Discord slash command: {}", e))?;
+
+        if let Some(entry) = self.interactions.lock().await.get_mut(reply_to) {
+            entry.responded = true;
         }
+        Ok(true)
     }
 
     /// Parse user IDs from strings to UserId
@@ -201,6 +550,18 @@ impl DiscordChannel {
     }
 }
 
+/// Wrap a failed send, keeping the HTTP status so the retry layer can tell a
+/// Discord outage from a missing permission
+fn send_error(err: serenity::Error) -> anyhow::Error {
+    let context = format!("Failed to send Discord message: {}", err);
+    if let serenity::Error::Http(http_err) = &err
+        && let Some(status) = http_err.status_code()
+    {
+        return anyhow::Error::new(HttpStatusError(status.as_u16())).context(context);
+    }
+    anyhow::Error::new(err).context(context)
+}
+
 /// Check if a serenity error represents a fatal gateway condition that should not be retried
 fn is_fatal_gateway_error(err: &serenity::Error) -> bool {
     match err {
@@ -218,7 +579,7 @@ fn is_fatal_gateway_error(err: &serenity::Error) -> bool {
 
 #[async_trait]
 impl MessageChannel for DiscordChannel {
-    async fn start(&self, tx: mpsc::Sender<IncomingMessage>) -> Result<()> {
+    async fn start(&self, tx: IncomingSender) -> Result<()> {
         info!("Starting Discord channel adapter");
 
         // Parse user IDs
@@ -229,7 +590,10 @@ impl MessageChannel for DiscordChannel {
         let token = self.token.clone();
         let user_channel_map = self.user_channel_map.clone();
         let message_channels = self.message_channels.clone();
+        let slash_commands = self.slash_commands.clone();
+        let interactions = self.interactions.clone();
         let http_arc = self.http.clone();
+        let health = self.health.clone();
 
         // Spawn the Discord client in a background task with retry logic
         tokio::spawn(async move {
@@ -256,9 +620,14 @@ impl MessageChannel for DiscordChannel {
                             error!(
                                 "Check your DISCORD_BOT_TOKEN and bot settings at https://discord.com/developers/applications"
                             );
+                            health.set(ChannelHealth::Unhealthy(format!("fatal error: {}", e)));
                             break;
                         }
                         error!("Failed to create Discord client: {}", e);
+                        health.set(ChannelHealth::Degraded(format!(
+                            "failed to create client: {}",
+                            e
+                        )));
                         warn!("Retrying in {:?}...", backoff);
                         tokio::time::sleep(backoff).await;
                         backoff = (backoff * 2).min(max_backoff);
@@ -277,6 +646,8 @@ impl MessageChannel for DiscordChannel {
                     data.insert::<MessageChannelMap>(message_channels.clone());
                     data.insert::<AllowedUsers>(user_ids.clone());
                     data.insert::<RateLimiterKey>(RateLimiter::new(10, Duration::from_secs(60)));
+                    data.insert::<SlashCommands>(slash_commands.clone());
+                    data.insert::<InteractionMap>(interactions.clone());
                 }
 
                 // Store HTTP client for sending messages
@@ -286,9 +657,11 @@ impl MessageChannel for DiscordChannel {
                 }
 
                 // Start the client
+                health.set(ChannelHealth::Healthy);
                 match client.start().await {
                     Ok(_) => {
                         info!("Discord client stopped cleanly");
+                        health.set(ChannelHealth::Unhealthy("client stopped".to_string()));
                         break;
                     }
                     Err(e) => {
@@ -297,9 +670,11 @@ impl MessageChannel for DiscordChannel {
                             error!(
                                 "Check your DISCORD_BOT_TOKEN and bot settings at https://discord.com/developers/applications"
                             );
+                            health.set(ChannelHealth::Unhealthy(format!("fatal error: {}", e)));
                             break;
                         }
                         error!("Discord client error: {}", e);
+                        health.set(ChannelHealth::Degraded(format!("gateway error: {}", e)));
                         warn!("Retrying in {:?}...", backoff);
                         tokio::time::sleep(backoff).await;
                         backoff = (backoff * 2).min(max_backoff);
@@ -320,6 +695,28 @@ impl MessageChannel for DiscordChannel {
             .as_ref()
             .ok_or_else(|| anyhow!("Discord channel not started yet"))?;
 
+        // Replies to slash commands go through the interaction, which already
+        // shows a "thinking..." indicator for acknowledgments
+        if let Some(reply_to) = &msg.reply_to
+            && self.interactions.lock().await.contains(reply_to)
+        {
+            if msg.kind == MessageKind::Acknowledgment {
+                return Ok(());
+            }
+            match self
+                .reply_to_interaction(http, reply_to, &msg.content)
+                .await
+            {
+                Ok(true) => {
+                    info!("Discord slash command reply sent for {}", reply_to);
+                    return Ok(());
+                }
+                Ok(false) => {}
+                // Tokens expire after 15 minutes; fall back to a plain message
+                Err(e) => warn!("{}, sending as a regular message", e),
+            }
+        }
+
         // Look up channel from reply_to if present
         let channel_id = if let Some(reply_to) = &msg.reply_to {
             let mut lru = self.message_channels.lock().await;
@@ -346,6 +743,15 @@ impl MessageChannel for DiscordChannel {
         let channel_id =
             channel_id.ok_or_else(|| anyhow!("No Discord users have messaged the bot yet"))?;
 
+        // An explicit thread wins; otherwise reply to the original message in place
+        let (channel_id, reference) = match msg.thread_id.as_deref().and_then(thread_channel) {
+            Some(thread) => (thread, None),
+            None => (
+                channel_id,
+                msg.reply_to.as_deref().and_then(reply_reference),
+            ),
+        };
+
         // Handle acknowledgment: show native "is typing..." indicator
         if msg.kind == MessageKind::Acknowledgment {
             debug!("Sending Discord typing indicator to channel {}", channel_id);
@@ -355,16 +761,26 @@ impl MessageChannel for DiscordChannel {
             return Ok(());
         }
 
-        // Normal response: send text message
+        // Normal response: send text message, the first part referencing the original if known
         debug!("Sending Discord message");
-        channel_id
-            .say(http, &msg.content)
-            .await
-            .map_err(|e| anyhow!("Failed to send Discord message: {}", e))?;
+        let chunks = split_message(&msg.content, MAX_OUTGOING_LENGTH);
+        for (i, chunk) in chunks.iter().enumerate() {
+            let mut message = CreateMessage::new().content(chunk);
+            if let Some(message_id) = reference
+                && i == 0
+            {
+                message = message.reference_message((channel_id, message_id));
+            }
+            channel_id
+                .send_message(http, message)
+                .await
+                .map_err(send_error)?;
+        }
 
         info!(
-            "Discord message sent successfully to channel {}",
-            channel_id
+            "Discord message sent successfully to channel {} ({} part(s))",
+            channel_id,
+            chunks.len()
         );
         Ok(())
     }
@@ -372,6 +788,10 @@ impl MessageChannel for DiscordChannel {
     fn channel_type(&self) -> ChannelType {
         ChannelType::Discord
     }
+
+    fn health(&self) -> ChannelHealth {
+        self.health.get()
+    }
 }
 
 #[cfg(test)]
@@ -401,6 +821,70 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_reply_reference_and_thread_channel() {
+        assert_eq!(
+            reply_reference("discord_1234567890"),
+            Some(MessageId::new(1234567890))
+        );
+        assert_eq!(reply_reference("slack_D1_123.456"), None);
+        assert_eq!(reply_reference("discord_not-a-number"), None);
+        assert_eq!(reply_reference("discord_0"), None);
+
+        assert_eq!(thread_channel("987654321"), Some(ChannelId::new(987654321)));
+        assert_eq!(thread_channel("thread-abc"), None);
+    }
+
+    #[test]
+    fn test_slash_command_options_and_content() {
+        // Type 1 = subcommand, 3 = string, 4 = integer, 5 = boolean
+        let options: Vec<CommandDataOption> = serde_json::from_value(serde_json::json!([
+            {"name": "add", "type": 1, "options": [
+                {"name": "what", "type": 3, "value": "call mom 📞"},
+                {"name": "minutes", "type": 4, "value": 30},
+                {"name": "urgent", "type": 5, "value": true}
+            ]}
+        ]))
+        .unwrap();
+
+        let command = SlashCommand {
+            name: "remind".to_string(),
+            options: options_to_json(&options),
+        };
+        assert_eq!(command.options["add"]["what"], "call mom 📞");
+        assert_eq!(command.options["add"]["minutes"], 30);
+        assert_eq!(
+            command_content(&command),
+            "/remind add minutes: 30 urgent: true what: call mom 📞"
+        );
+
+        let bare = SlashCommand {
+            name: "status".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(command_content(&bare), "/status");
+    }
+
+    #[test]
+    fn test_slash_command_config_builds_registration() {
+        let config: DiscordSlashCommand = serde_json::from_value(serde_json::json!({
+            "name": "ask",
+            "description": "Ask Meepo something",
+            "options": [
+                {"name": "question", "description": "What to ask", "required": true},
+                {"name": "private", "description": "Only you see it", "kind": "boolean"}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(config.options[0].kind, DiscordOptionKind::String);
+
+        let json = serde_json::to_value(config.to_builder()).unwrap();
+        assert_eq!(json["name"], "ask");
+        assert_eq!(json["options"][0]["type"], 3);
+        assert_eq!(json["options"][0]["required"], true);
+        assert_eq!(json["options"][1]["type"], 5);
+    }
+
     #[test]
     fn test_parse_empty_user_ids() {
         let channel = DiscordChannel::new("token".to_string(), vec![]);
@@ -1,18 +1,17 @@
 //! Email channel adapter using Mail.app AppleScript polling
 
-use crate::bus::MessageChannel;
+use crate::bus::{IncomingSender, MessageChannel};
 use crate::rate_limit::RateLimiter;
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use chrono::Utc;
 use lru::LruCache;
-use meepo_core::types::{ChannelType, IncomingMessage, MessageKind, OutgoingMessage};
+use meepo_core::types::{ChannelType, IncomingMessage, MessageKind, OutgoingMessage, Priority};
 use std::num::NonZeroUsize;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::process::Command;
 use tokio::sync::Mutex;
-use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
 const MAX_EMAIL_SENDERS: usize = 500;
@@ -57,7 +56,7 @@ impl EmailChannel {
     }
 
     /// Poll Mail.app for unread emails matching the subject prefix
-    async fn poll_emails(&self, tx: &mpsc::Sender<IncomingMessage>) -> Result<()> {
+    async fn poll_emails(&self, tx: &IncomingSender) -> Result<()> {
         let prefix = Self::escape_applescript(&self.subject_prefix);
 
         let script = format!(
@@ -185,6 +184,9 @@ end tell
                 content,
                 channel: ChannelType::Email,
                 timestamp: Utc::now(),
+                priority: Priority::Normal,
+                command: None,
+                thread_id: Some(subject.clone()),
             };
 
             info!("New email from {}: {}", sender, stripped_subject);
@@ -197,6 +199,19 @@ end tell
         Ok(())
     }
 
+    /// The (subject, sender) a message replies to: `reply_to` picks the original
+    /// email, and `thread_id`, if set, is the subject of the thread to reply into
+    async fn reply_context(&self, msg: &OutgoingMessage) -> Option<(String, String)> {
+        let reply_to = msg.reply_to.as_ref()?;
+        let lru = self.message_senders.lock().await;
+        let meta = lru.peek(reply_to)?;
+        let subject = msg
+            .thread_id
+            .clone()
+            .unwrap_or_else(|| meta.subject.clone());
+        Some((subject, meta.sender.clone()))
+    }
+
     /// Reply to an email using Mail.app threading
     async fn reply_to_email(
         &self,
@@ -239,7 +254,7 @@ end tell
             Command::new("osascript").arg("-e").arg(&script).output(),
         )
         .await
-        .map_err(|_| anyhow!("Email reply timed out"))?
+        .map_err(|e| anyhow::Error::new(e).context("Email reply timed out"))?
         .map_err(|e| anyhow!("Failed to run osascript: {}", e))?;
 
         if output.status.success() {
@@ -255,7 +270,7 @@ end tell
 
 #[async_trait]
 impl MessageChannel for EmailChannel {
-    async fn start(&self, tx: mpsc::Sender<IncomingMessage>) -> Result<()> {
+    async fn start(&self, tx: IncomingSender) -> Result<()> {
         info!("Starting Email channel adapter");
         info!("Poll interval: {:?}", self.poll_interval);
         info!("Subject prefix: {}", self.subject_prefix);
@@ -291,32 +306,25 @@ impl MessageChannel for EmailChannel {
     }
 
     async fn send(&self, msg: OutgoingMessage) -> Result<()> {
-        if let Some(reply_to) = &msg.reply_to {
-            let lru = self.message_senders.lock().await;
-            if let Some(meta) = lru.peek(reply_to) {
-                let subject = meta.subject.clone();
-                let sender = meta.sender.clone();
-                drop(lru);
-
-                // Handle acknowledgment: send auto-reply
-                if msg.kind == MessageKind::Acknowledgment {
-                    debug!("Sending email acknowledgment to {}", sender);
-                    if let Err(e) = self
-                        .reply_to_email(
-                            &subject,
-                            &sender,
-                            "Your message has been received. Working on a response...",
-                        )
-                        .await
-                    {
-                        warn!("Failed to send email acknowledgment: {}", e);
-                    }
-                    return Ok(());
+        if let Some((subject, sender)) = self.reply_context(&msg).await {
+            // Handle acknowledgment: send auto-reply
+            if msg.kind == MessageKind::Acknowledgment {
+                debug!("Sending email acknowledgment to {}", sender);
+                if let Err(e) = self
+                    .reply_to_email(
+                        &subject,
+                        &sender,
+                        "Your message has been received. Working on a response...",
+                    )
+                    .await
+                {
+                    warn!("Failed to send email acknowledgment: {}", e);
                 }
-
-                // Normal response
-                return self.reply_to_email(&subject, &sender, &msg.content).await;
+                return Ok(());
             }
+
+            // Normal response
+            return self.reply_to_email(&subject, &sender, &msg.content).await;
         }
 
         // Acknowledgments without reply context are silently ignored
@@ -379,6 +387,45 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_reply_context_maps_ids_to_subject() {
+        let channel = EmailChannel::new(Duration::from_secs(10), "[meepo]".to_string());
+        channel.message_senders.lock().await.put(
+            "email_123".to_string(),
+            EmailMeta {
+                sender: "user@example.com".to_string(),
+                subject: "[meepo] test subject".to_string(),
+            },
+        );
+
+        let mut msg = OutgoingMessage {
+            content: "test reply".to_string(),
+            channel: ChannelType::Email,
+            reply_to: Some("email_123".to_string()),
+            thread_id: None,
+            kind: MessageKind::Response,
+        };
+        assert_eq!(
+            channel.reply_context(&msg).await,
+            Some((
+                "[meepo] test subject".to_string(),
+                "user@example.com".to_string()
+            ))
+        );
+
+        msg.thread_id = Some("[meepo] weekly plan".to_string());
+        assert_eq!(
+            channel.reply_context(&msg).await,
+            Some((
+                "[meepo] weekly plan".to_string(),
+                "user@example.com".to_string()
+            ))
+        );
+
+        msg.reply_to = Some("email_unknown".to_string());
+        assert_eq!(channel.reply_context(&msg).await, None);
+    }
+
     #[tokio::test]
     async fn test_send_without_context_fails() {
         let channel = EmailChannel::new(Duration::from_secs(10), "[meepo]".to_string());
@@ -387,6 +434,7 @@ mod tests {
             content: "test reply".to_string(),
             channel: ChannelType::Email,
             reply_to: None,
+            thread_id: None,
             kind: MessageKind::Response,
         };
 
@@ -1,19 +1,18 @@
 //! iMessage channel adapter using SQLite polling and AppleScript
 
-use crate::bus::MessageChannel;
+use crate::bus::{ChannelHealth, HealthCell, IncomingSender, MessageChannel};
 use crate::rate_limit::RateLimiter;
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use chrono::Utc;
 use lru::LruCache;
-use meepo_core::types::{ChannelType, IncomingMessage, MessageKind, OutgoingMessage};
+use meepo_core::types::{ChannelType, IncomingMessage, MessageKind, OutgoingMessage, Priority};
 use rusqlite::{Connection, params};
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::process::Command;
-use tokio::sync::mpsc;
 use tokio::sync::{Mutex, RwLock};
 use tracing::{debug, error, info, warn};
 
@@ -23,6 +22,27 @@ const MAX_MESSAGE_SIZE: usize = 10_240;
 /// Acknowledgment text sent by Meepo (used to skip echo/auto-reply loops)
 const ACK_TEXT: &str = "On it, thinking...";
 
+/// How often chat.db is re-read while waiting for a delivery receipt
+const DELIVERY_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Wait before resending a message Messages.app marked as not delivered
+const DELIVERY_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Delivery state of a sent iMessage, as recorded in chat.db
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    /// Delivered to the recipient's device
+    Delivered,
+    /// Delivered and read by the recipient
+    Read,
+    /// Sent but still undelivered when we stopped waiting
+    Pending,
+    /// Messages.app reported an error ("Not Delivered")
+    Failed,
+    /// Not confirmed: confirmation is off, chat.db is unreadable, or the message wasn't found
+    Unknown,
+}
+
 /// iMessage channel adapter
 pub struct IMessageChannel {
     poll_interval: Duration,
@@ -32,6 +52,10 @@ pub struct IMessageChannel {
     /// Maps message_id -> sender contact for reply-to tracking (LRU-bounded)
     message_senders: Arc<Mutex<LruCache<String, String>>>,
     rate_limiter: RateLimiter,
+    /// How long to wait for a delivery receipt after sending (None = fire-and-forget)
+    delivery_timeout: Option<Duration>,
+    /// Outcome of the most recent chat.db poll
+    health: HealthCell,
 }
 
 impl IMessageChannel {
@@ -61,9 +85,18 @@ impl IMessageChannel {
                 NonZeroUsize::new(MAX_MESSAGE_SENDERS).unwrap(),
             ))),
             rate_limiter: RateLimiter::new(10, Duration::from_secs(60)),
+            delivery_timeout: None,
+            health: HealthCell::new(),
         }
     }
 
+    /// Confirm delivery of responses by watching chat.db for up to `timeout`,
+    /// resending once if Messages.app reports the message as not delivered
+    pub fn with_delivery_confirmation(mut self, timeout: Duration) -> Self {
+        self.delivery_timeout = Some(timeout);
+        self
+    }
+
     /// Normalize phone number for comparison (remove +, -, spaces, etc.)
     fn normalize_contact(contact: &str) -> String {
         contact
@@ -82,7 +115,7 @@ impl IMessageChannel {
     }
 
     /// Poll the iMessage database for new messages
-    async fn poll_messages(&self, tx: &mpsc::Sender<IncomingMessage>) -> Result<()> {
+    async fn poll_messages(&self, tx: &IncomingSender) -> Result<()> {
         // Open read-only connection to chat.db
         // Note: We open a fresh connection on each poll rather than maintaining a persistent connection
         // because: (1) Messages.app may lock the database, so a stale connection could fail,
@@ -200,6 +233,9 @@ impl IMessageChannel {
                 content: content.clone(),
                 channel: ChannelType::IMessage,
                 timestamp,
+                priority: Priority::Normal,
+                command: None,
+                thread_id: None,
             };
 
             info!("Forwarding iMessage from {} ({} chars)", handle, content.len());
@@ -258,8 +294,7 @@ end tell"#,
                 .output(),
         )
         .await
-        .map_err(|_| anyhow!("iMessage send timed out after 30 seconds"))?
-        ?;
+        .map_err(|e| anyhow::Error::new(e).context("iMessage send timed out after 30 seconds"))??;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -270,6 +305,124 @@ end tell"#,
         Ok(())
     }
 
+    /// Send a message and, if delivery confirmation is enabled, wait for its receipt
+    ///
+    /// A message Messages.app marks as not delivered is resent once after a
+    /// short delay. If chat.db can't be read the message is still sent, with a
+    /// warning, and the status is `Unknown`.
+    pub async fn send_with_confirmation(
+        &self,
+        recipient: &str,
+        message: &str,
+    ) -> Result<DeliveryStatus> {
+        let Some(timeout) = self.delivery_timeout else {
+            self.send_imessage(recipient, message).await?;
+            return Ok(DeliveryStatus::Unknown);
+        };
+
+        let mut status = DeliveryStatus::Unknown;
+        for attempt in 0..2 {
+            if attempt > 0 {
+                warn!(
+                    "iMessage to {} was not delivered, retrying in {:?}",
+                    recipient, DELIVERY_RETRY_DELAY
+                );
+                tokio::time::sleep(DELIVERY_RETRY_DELAY).await;
+            }
+
+            let before = match self.max_message_rowid() {
+                Ok(rowid) => rowid,
+                Err(e) => {
+                    warn!(
+                        "Cannot read {:?} to confirm delivery ({}), sending without confirmation",
+                        self.db_path, e
+                    );
+                    self.send_imessage(recipient, message).await?;
+                    return Ok(DeliveryStatus::Unknown);
+                }
+            };
+            self.send_imessage(recipient, message).await?;
+            status = self.wait_for_delivery(before, message, timeout).await;
+            if status != DeliveryStatus::Failed {
+                break;
+            }
+        }
+
+        debug!("iMessage to {} delivery status: {:?}", recipient, status);
+        Ok(status)
+    }
+
+    fn open_db(&self) -> rusqlite::Result<Connection> {
+        Connection::open_with_flags(&self.db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+    }
+
+    fn max_message_rowid(&self) -> rusqlite::Result<i64> {
+        self.open_db()?
+            .query_row("SELECT COALESCE(MAX(ROWID), 0) FROM message", [], |row| {
+                row.get(0)
+            })
+    }
+
+    /// Poll chat.db until the message sent after `after_rowid` is delivered,
+    /// fails, or `timeout` runs out
+    async fn wait_for_delivery(
+        &self,
+        after_rowid: i64,
+        text: &str,
+        timeout: Duration,
+    ) -> DeliveryStatus {
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut last_seen = None;
+        loop {
+            match self
+                .open_db()
+                .and_then(|conn| Self::query_delivery_status(&conn, after_rowid, text))
+            {
+                Ok(Some(DeliveryStatus::Pending)) => last_seen = Some(DeliveryStatus::Pending),
+                Ok(Some(status)) => return status,
+                Ok(None) => {}
+                Err(e) => {
+                    warn!("Cannot read delivery status from chat.db: {}", e);
+                    return DeliveryStatus::Unknown;
+                }
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return last_seen.unwrap_or(DeliveryStatus::Unknown);
+            }
+            tokio::time::sleep(DELIVERY_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Delivery state of the newest outgoing message with `text` after `after_rowid`,
+    /// or None if it hasn't reached chat.db yet
+    fn query_delivery_status(
+        conn: &Connection,
+        after_rowid: i64,
+        text: &str,
+    ) -> rusqlite::Result<Option<DeliveryStatus>> {
+        let row = conn.query_row(
+            "SELECT is_delivered, is_read, error FROM message
+             WHERE is_from_me = 1 AND ROWID > ?1 AND text = ?2
+             ORDER BY ROWID DESC LIMIT 1",
+            params![after_rowid, text],
+            |row| {
+                Ok((
+                    row.get::<_, bool>(0)?,
+                    row.get::<_, bool>(1)?,
+                    row.get::<_, i64>(2)?,
+                ))
+            },
+        );
+        match row {
+            Ok((_, _, error)) if error != 0 => Ok(Some(DeliveryStatus::Failed)),
+            Ok((_, true, _)) => Ok(Some(DeliveryStatus::Read)),
+            Ok((true, _, _)) => Ok(Some(DeliveryStatus::Delivered)),
+            Ok(_) => Ok(Some(DeliveryStatus::Pending)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
     /// After sending an ack, bump the ROWID watermark so the poller
     /// skips any auto-reply that arrives in response to our ack.
     async fn bump_watermark_after_send(&self) {
@@ -300,7 +453,7 @@ end tell"#,
 
 #[async_trait]
 impl MessageChannel for IMessageChannel {
-    async fn start(&self, tx: mpsc::Sender<IncomingMessage>) -> Result<()> {
+    async fn start(&self, tx: IncomingSender) -> Result<()> {
         info!("Starting iMessage channel adapter");
         info!("Database path: {:?}", self.db_path);
         info!("Poll interval: {:?}", self.poll_interval);
@@ -319,6 +472,9 @@ impl MessageChannel for IMessageChannel {
         let allowed_contacts = self.allowed_contacts.clone();
         let message_senders = self.message_senders.clone();
         let rate_limiter = self.rate_limiter.clone();
+        let delivery_timeout = self.delivery_timeout;
+        let health = self.health.clone();
+        health.set(ChannelHealth::Healthy);
 
         // Create a new channel instance for the task
         let channel = IMessageChannel {
@@ -328,6 +484,8 @@ impl MessageChannel for IMessageChannel {
             last_rowid,
             message_senders,
             rate_limiter,
+            delivery_timeout,
+            health,
         };
 
         // Spawn polling task
@@ -339,8 +497,15 @@ impl MessageChannel for IMessageChannel {
                 interval.tick().await;
                 debug!("Polling iMessage database");
 
-                if let Err(e) = channel.poll_messages(&tx).await {
-                    error!("Error polling iMessage database: {}", e);
+                match channel.poll_messages(&tx).await {
+                    Ok(()) => channel.health.set(ChannelHealth::Healthy),
+                    Err(e) => {
+                        error!("Error polling iMessage database: {}", e);
+                        channel.health.set(ChannelHealth::Degraded(format!(
+                            "chat.db poll failed: {}",
+                            e
+                        )));
+                    }
                 }
             }
         });
@@ -386,14 +551,32 @@ impl MessageChannel for IMessageChannel {
         }
 
         // Normal response
-        self.send_imessage(&recipient, &msg.content).await?;
-        info!("iMessage sent successfully to {}", recipient);
-        Ok(())
+        match self
+            .send_with_confirmation(&recipient, &msg.content)
+            .await?
+        {
+            DeliveryStatus::Failed => Err(anyhow!(
+                "iMessage to {} was not delivered after retrying",
+                recipient
+            )),
+            DeliveryStatus::Unknown => {
+                info!("iMessage sent successfully to {}", recipient);
+                Ok(())
+            }
+            status => {
+                info!("iMessage sent to {} ({:?})", recipient, status);
+                Ok(())
+            }
+        }
     }
 
     fn channel_type(&self) -> ChannelType {
         ChannelType::IMessage
     }
+
+    fn health(&self) -> ChannelHealth {
+        self.health.get()
+    }
 }
 
 #[cfg(test)]
@@ -493,6 +676,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_query_delivery_status() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE message (ROWID INTEGER PRIMARY KEY, text TEXT, is_from_me INTEGER,
+                 is_delivered INTEGER, is_read INTEGER, error INTEGER);
+             INSERT INTO message VALUES (1, 'hello', 1, 1, 1, 0);
+             INSERT INTO message VALUES (2, 'hello', 0, 0, 0, 0);",
+        )
+        .unwrap();
+        let status = |after| IMessageChannel::query_delivery_status(&conn, after, "hello").unwrap();
+
+        assert_eq!(status(0), Some(DeliveryStatus::Read));
+        // Only outgoing messages newer than the watermark count
+        assert_eq!(status(1), None);
+
+        conn.execute("INSERT INTO message VALUES (3, 'hello', 1, 0, 0, 0)", [])
+            .unwrap();
+        assert_eq!(status(1), Some(DeliveryStatus::Pending));
+        conn.execute("UPDATE message SET is_delivered = 1 WHERE ROWID = 3", [])
+            .unwrap();
+        assert_eq!(status(1), Some(DeliveryStatus::Delivered));
+        conn.execute("UPDATE message SET error = 22 WHERE ROWID = 3", [])
+            .unwrap();
+        assert_eq!(status(1), Some(DeliveryStatus::Failed));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_delivery_unreadable_db() {
+        let channel = IMessageChannel::new(
+            Duration::from_secs(3),
+            vec![],
+            Some(PathBuf::from("/nonexistent/chat.db")),
+        )
+        .with_delivery_confirmation(Duration::from_secs(5));
+        assert!(channel.max_message_rowid().is_err());
+        assert_eq!(
+            channel
+                .wait_for_delivery(0, "hello", Duration::from_secs(5))
+                .await,
+            DeliveryStatus::Unknown
+        );
+    }
+
     #[test]
     fn test_channel_type() {
         let channel = IMessageChannel::new(Duration::from_secs(3), vec![], None);
@@ -1,7 +1,7 @@
 //! Channel adapters and message bus for meepo
 //!
 //! This crate provides the message routing infrastructure and channel-specific
-//! adapters for Discord, iMessage, and Slack.
+//! adapters for Discord, iMessage, Slack, and Telegram.
 
 pub mod alexa;
 pub mod bus;
@@ -17,12 +17,20 @@ pub mod notes;
 pub mod rate_limit;
 #[cfg(target_os = "macos")]
 pub mod reminders;
+pub mod retry;
 pub mod slack;
+pub mod telegram;
+pub mod util;
+pub mod watcher_bridge;
 
 // Re-export main types
 pub use alexa::AlexaChannel;
-pub use bus::{MessageBus, MessageChannel};
-pub use discord::DiscordChannel;
+pub use bus::{
+    BusClosed, BusMetrics, BusReceiver, BusSender, ChannelHealth, DeadLetter, DeadLetterQueue,
+    DedupKey, HealthCell, IdDedupConfig, IncomingSender, IntakeDedupConfig, MessageBus,
+    MessageChannel,
+};
+pub use discord::{DiscordChannel, DiscordCommandOption, DiscordOptionKind, DiscordSlashCommand};
 #[cfg(target_os = "macos")]
 pub use email::EmailChannel;
 #[cfg(target_os = "macos")]
@@ -31,7 +39,13 @@ pub use imessage::IMessageChannel;
 pub use contacts::ContactsChannel;
 #[cfg(target_os = "macos")]
 pub use notes::NotesChannel;
-pub use rate_limit::RateLimiter;
+pub use rate_limit::{
+    BucketConfig, Clock, RateLimitedChannel, RateLimiter, RetryAfter, SystemClock,
+};
 #[cfg(target_os = "macos")]
 pub use reminders::RemindersChannel;
+pub use retry::{HttpStatusError, RetryPolicy, RetryingChannel, is_transient};
 pub use slack::SlackChannel;
+pub use telegram::TelegramChannel;
+pub use util::{split_message, split_message_by};
+pub use watcher_bridge::{UnknownReplyChannel, WatcherBridge, reply_channel_type};
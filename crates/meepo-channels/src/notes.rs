@@ -1,16 +1,15 @@
 //! Apple Notes channel adapter using AppleScript polling
 
-use crate::bus::MessageChannel;
+use crate::bus::{IncomingSender, MessageChannel};
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use chrono::Utc;
-use meepo_core::types::{ChannelType, IncomingMessage, MessageKind, OutgoingMessage};
+use meepo_core::types::{ChannelType, IncomingMessage, MessageKind, OutgoingMessage, Priority};
 use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::process::Command;
 use tokio::sync::Mutex;
-use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
 /// Apple Notes channel adapter that polls Notes.app for new notes
@@ -46,7 +45,7 @@ impl NotesChannel {
     }
 
     /// Poll Notes.app for notes in the configured folder whose name starts with the tag prefix
-    async fn poll_notes(&self, tx: &mpsc::Sender<IncomingMessage>) -> Result<()> {
+    async fn poll_notes(&self, tx: &IncomingSender) -> Result<()> {
         let folder = Self::escape_applescript(&self.folder_name);
         let prefix = Self::escape_applescript(&self.tag_prefix);
 
@@ -163,6 +162,9 @@ end tell
                 content,
                 channel: ChannelType::Notes,
                 timestamp: Utc::now(),
+                priority: Priority::Normal,
+                command: None,
+                thread_id: None,
             };
 
             info!("New note from Notes.app: {}", name);
@@ -255,7 +257,7 @@ end tell
 
 #[async_trait]
 impl MessageChannel for NotesChannel {
-    async fn start(&self, tx: mpsc::Sender<IncomingMessage>) -> Result<()> {
+    async fn start(&self, tx: IncomingSender) -> Result<()> {
         info!("Starting Notes channel adapter");
         info!("Poll interval: {:?}", self.poll_interval);
         info!("Notes folder: {}", self.folder_name);
@@ -310,6 +312,11 @@ impl MessageChannel for NotesChannel {
     fn channel_type(&self) -> ChannelType {
         ChannelType::Notes
     }
+
+    fn supports_kind(&self, kind: &MessageKind) -> bool {
+        // Acknowledgments are silently ignored
+        *kind != MessageKind::Acknowledgment
+    }
 }
 
 #[cfg(test)]
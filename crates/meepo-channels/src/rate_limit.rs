@@ -1,15 +1,93 @@
-//! Per-sender rate limiting for channel adapters
+//! Rate limiting for channel adapters
+//!
+//! [`RateLimiter`] throttles incoming messages per sender with a sliding
+//! window, and outgoing traffic with token buckets keyed by
+//! `(ChannelType, recipient)` whose capacity and refill rate are configured
+//! per channel (Slack, Discord, and email tolerate very different rates).
+//! At most [`MAX_BUCKETS`] buckets are kept; the least recently used is
+//! dropped first, which at worst hands an idle recipient a full bucket.
+//! [`RateLimitedChannel`] applies those buckets to a channel's sends; the bus
+//! wraps every registered channel in one when a limiter is configured.
 
+use crate::bus::{ChannelHealth, IncomingSender, MessageChannel};
+use anyhow::Result;
+use async_trait::async_trait;
 use dashmap::DashMap;
-use std::collections::VecDeque;
-use std::sync::Arc;
+use lru::LruCache;
+use meepo_core::types::{ChannelType, MessageKind, OutgoingMessage};
+use std::collections::{HashMap, VecDeque};
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tracing::warn;
 
-/// A sliding-window rate limiter that tracks per-sender message counts.
+/// Upper bound on outgoing token buckets kept at once
+pub const MAX_BUCKETS: usize = 4096;
+
+/// Source of the current time, swappable so refill can be tested without sleeping
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+/// The real monotonic clock
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// How long to wait before a throttled send may be retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryAfter(pub Duration);
+
+impl std::fmt::Display for RetryAfter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "rate limited, retry after {:?}", self.0)
+    }
+}
+
+impl std::error::Error for RetryAfter {}
+
+/// Token bucket parameters for one channel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BucketConfig {
+    /// Maximum burst size (tokens in a full bucket)
+    pub capacity: u32,
+    /// Tokens added back per second
+    pub refill_per_sec: f64,
+}
+
+impl BucketConfig {
+    pub fn new(capacity: u32, refill_per_sec: f64) -> Self {
+        Self {
+            capacity,
+            refill_per_sec,
+        }
+    }
+
+    /// `capacity` sends per `period`, refilled smoothly
+    pub fn per(capacity: u32, period: Duration) -> Self {
+        Self::new(capacity, capacity as f64 / period.as_secs_f64())
+    }
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Rate limiter for channel traffic.
+///
+/// Incoming: each sender gets their own sliding window of timestamps. When a
+/// new message arrives, expired timestamps are pruned and the count is checked
+/// against the limit.
 ///
-/// Each sender gets their own window of timestamps. When a new message arrives,
-/// expired timestamps are pruned and the count is checked against the limit.
+/// Outgoing: each `(channel, recipient)` pair gets a token bucket using that
+/// channel's [`BucketConfig`] (or the default). Channels with no limit
+/// configured are never throttled. Clones share all state.
 #[derive(Clone)]
 pub struct RateLimiter {
     /// Per-sender sliding windows of message timestamps
@@ -18,6 +96,13 @@ pub struct RateLimiter {
     max_messages: usize,
     /// Duration of the sliding window
     window_duration: Duration,
+    /// Per-(channel, recipient) token buckets
+    buckets: Arc<Mutex<LruCache<(ChannelType, String), Bucket>>>,
+    /// Bucket parameters per channel
+    channel_limits: Arc<HashMap<ChannelType, BucketConfig>>,
+    /// Bucket parameters for channels without their own entry
+    default_limit: Option<BucketConfig>,
+    clock: Arc<dyn Clock>,
 }
 
 impl RateLimiter {
@@ -31,7 +116,84 @@ impl RateLimiter {
             windows: Arc::new(DashMap::new()),
             max_messages,
             window_duration,
+            buckets: Arc::new(Mutex::new(LruCache::new(
+                NonZeroUsize::new(MAX_BUCKETS).expect("MAX_BUCKETS is non-zero"),
+            ))),
+            channel_limits: Arc::new(HashMap::new()),
+            default_limit: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// A limiter used only for outgoing sends: no bucket limits until one is
+    /// set with [`with_channel_limit`](Self::with_channel_limit) or
+    /// [`with_default_limit`](Self::with_default_limit)
+    pub fn for_sends() -> Self {
+        Self::new(usize::MAX, Duration::ZERO)
+    }
+
+    /// Set the token bucket used for recipients on `channel`
+    pub fn with_channel_limit(mut self, channel: ChannelType, limit: BucketConfig) -> Self {
+        Arc::make_mut(&mut self.channel_limits).insert(channel, limit);
+        self
+    }
+
+    /// Set the token bucket for channels without their own limit
+    pub fn with_default_limit(mut self, limit: BucketConfig) -> Self {
+        self.default_limit = Some(limit);
+        self
+    }
+
+    /// Use a custom clock (for tests)
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Take a token for sending to `recipient` on `channel`.
+    ///
+    /// Returns `Err(RetryAfter)` with the time until a token is available when
+    /// the bucket is empty. Refused checks don't consume anything.
+    pub fn check(&self, channel: &ChannelType, recipient: &str) -> Result<(), RetryAfter> {
+        let Some(limit) = self
+            .channel_limits
+            .get(channel)
+            .copied()
+            .or(self.default_limit)
+        else {
+            return Ok(());
+        };
+
+        let now = self.clock.now();
+        let capacity = limit.capacity as f64;
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let bucket =
+            buckets.get_or_insert_mut((channel.clone(), recipient.to_string()), || Bucket {
+                tokens: capacity,
+                updated: now,
+            });
+
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * limit.refill_per_sec).min(capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(());
         }
+
+        // A rate too slow for the wait to fit in a Duration never refills
+        let wait = if limit.capacity == 0 || limit.refill_per_sec <= 0.0 {
+            Duration::MAX
+        } else {
+            Duration::try_from_secs_f64((1.0 - bucket.tokens) / limit.refill_per_sec)
+                .unwrap_or(Duration::MAX)
+        };
+        warn!(
+            "Rate limit exceeded for {} recipient '{}' (capacity {}, {}/s); retry after {:?}",
+            channel, recipient, limit.capacity, limit.refill_per_sec, wait,
+        );
+        Err(RetryAfter(wait))
     }
 
     /// Check if a message from the given sender should be allowed.
@@ -39,7 +201,7 @@ impl RateLimiter {
     /// Returns `true` if the message is within rate limits, `false` if it should be dropped.
     /// Automatically records the message timestamp if allowed.
     pub fn check_and_record(&self, sender: &str) -> bool {
-        let now = Instant::now();
+        let now = self.clock.now();
         let cutoff = now - self.window_duration;
 
         let mut entry = self.windows.entry(sender.to_string()).or_default();
@@ -66,9 +228,114 @@ impl RateLimiter {
     }
 }
 
+/// The recipient a message's bucket is keyed by. Replies are keyed by the
+/// conversation of the message they answer: ids shaped like
+/// `slack_{channel}_{ts}` or `telegram_{chat}_{id}` drop the per-message
+/// suffix, other ids are used whole. Messages without `reply_to` go to the
+/// channel's default destination and share its bucket.
+fn recipient(msg: &OutgoingMessage) -> &str {
+    let Some(reply_to) = msg.reply_to.as_deref() else {
+        return "";
+    };
+    match reply_to.split('_').count() {
+        3.. => reply_to
+            .rsplit_once('_')
+            .map_or(reply_to, |(conversation, _)| conversation),
+        _ => reply_to,
+    }
+}
+
+/// A channel whose sends each take a token from a [`RateLimiter`] bucket.
+/// Throttled sends fail with [`RetryAfter`] without reaching the channel;
+/// `RetryingChannel` waits that long before trying again.
+pub struct RateLimitedChannel {
+    inner: Box<dyn MessageChannel>,
+    limiter: RateLimiter,
+}
+
+impl RateLimitedChannel {
+    pub fn new(inner: Box<dyn MessageChannel>, limiter: RateLimiter) -> Self {
+        Self { inner, limiter }
+    }
+}
+
+#[async_trait]
+impl MessageChannel for RateLimitedChannel {
+    async fn start(&self, tx: IncomingSender) -> Result<()> {
+        self.inner.start(tx).await
+    }
+
+    async fn send(&self, msg: OutgoingMessage) -> Result<()> {
+        self.limiter
+            .check(&self.inner.channel_type(), recipient(&msg))?;
+        self.inner.send(msg).await
+    }
+
+    /// Hand the messages that got a token to the inner channel in one batch;
+    /// the rest fail with `RetryAfter`
+    async fn send_batch(&self, msgs: Vec<OutgoingMessage>) -> Vec<Result<()>> {
+        let channel_type = self.inner.channel_type();
+        let mut results: Vec<Option<Result<()>>> = Vec::with_capacity(msgs.len());
+        let mut allowed = Vec::new();
+        for msg in msgs {
+            match self.limiter.check(&channel_type, recipient(&msg)) {
+                Ok(()) => {
+                    allowed.push(msg);
+                    results.push(None);
+                }
+                Err(wait) => results.push(Some(Err(wait.into()))),
+            }
+        }
+
+        let mut sent = self.inner.send_batch(allowed).await.into_iter();
+        results
+            .into_iter()
+            .map(|result| {
+                result.unwrap_or_else(|| {
+                    sent.next().unwrap_or_else(|| {
+                        Err(anyhow::anyhow!("Channel returned no result for message"))
+                    })
+                })
+            })
+            .collect()
+    }
+
+    fn channel_type(&self) -> ChannelType {
+        self.inner.channel_type()
+    }
+
+    fn supports_kind(&self, kind: &MessageKind) -> bool {
+        self.inner.supports_kind(kind)
+    }
+
+    fn health(&self) -> ChannelHealth {
+        self.inner.health()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Mutex;
+
+    /// Clock that only moves when told to
+    struct MockClock(Mutex<Instant>);
+
+    impl MockClock {
+        fn new() -> Arc<Self> {
+            Arc::new(Self(Mutex::new(Instant::now())))
+        }
+
+        fn advance(&self, by: Duration) {
+            *self.0.lock().unwrap() += by;
+        }
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> Instant {
+            *self.0.lock().unwrap()
+        }
+    }
 
     #[test]
     fn test_allows_within_limit() {
@@ -127,4 +394,139 @@ mod tests {
         assert!(limiter2.check_and_record("user1"));
         assert!(!limiter.check_and_record("user1"));
     }
+
+    #[test]
+    fn test_bucket_refills_over_time() {
+        let clock = MockClock::new();
+        let limiter = RateLimiter::new(10, Duration::from_secs(60))
+            .with_channel_limit(ChannelType::Slack, BucketConfig::new(2, 1.0))
+            .with_clock(clock.clone());
+
+        assert!(limiter.check(&ChannelType::Slack, "C1").is_ok());
+        assert!(limiter.check(&ChannelType::Slack, "C1").is_ok());
+        assert_eq!(
+            limiter.check(&ChannelType::Slack, "C1"),
+            Err(RetryAfter(Duration::from_secs(1)))
+        );
+
+        // Half a token: still throttled, with half the wait
+        clock.advance(Duration::from_millis(500));
+        assert_eq!(
+            limiter.check(&ChannelType::Slack, "C1"),
+            Err(RetryAfter(Duration::from_millis(500)))
+        );
+
+        clock.advance(Duration::from_millis(500));
+        assert!(limiter.check(&ChannelType::Slack, "C1").is_ok());
+        assert!(limiter.check(&ChannelType::Slack, "C1").is_err());
+
+        // Refill is capped at capacity
+        clock.advance(Duration::from_secs(60));
+        assert!(limiter.check(&ChannelType::Slack, "C1").is_ok());
+        assert!(limiter.check(&ChannelType::Slack, "C1").is_ok());
+        assert!(limiter.check(&ChannelType::Slack, "C1").is_err());
+    }
+
+    #[test]
+    fn test_tiny_refill_rate_waits_forever() {
+        let limiter = RateLimiter::for_sends()
+            .with_channel_limit(ChannelType::Slack, BucketConfig::new(1, 1e-300))
+            .with_clock(MockClock::new());
+
+        assert!(limiter.check(&ChannelType::Slack, "C1").is_ok());
+        assert_eq!(
+            limiter.check(&ChannelType::Slack, "C1"),
+            Err(RetryAfter(Duration::MAX))
+        );
+    }
+
+    #[test]
+    fn test_buckets_per_channel_and_recipient() {
+        let clock = MockClock::new();
+        let limiter = RateLimiter::new(10, Duration::from_secs(60))
+            .with_channel_limit(
+                ChannelType::Email,
+                BucketConfig::per(1, Duration::from_secs(60)),
+            )
+            .with_channel_limit(ChannelType::Discord, BucketConfig::new(3, 1.0))
+            .with_clock(clock.clone());
+
+        assert!(limiter.check(&ChannelType::Email, "a@example.com").is_ok());
+        let RetryAfter(wait) = limiter
+            .check(&ChannelType::Email, "a@example.com")
+            .unwrap_err();
+        assert_eq!(wait, Duration::from_secs(60));
+
+        // Another recipient on the same channel has its own bucket
+        assert!(limiter.check(&ChannelType::Email, "b@example.com").is_ok());
+
+        // Discord has a different capacity
+        for _ in 0..3 {
+            assert!(
+                limiter
+                    .check(&ChannelType::Discord, "a@example.com")
+                    .is_ok()
+            );
+        }
+        assert!(
+            limiter
+                .check(&ChannelType::Discord, "a@example.com")
+                .is_err()
+        );
+
+        // Unconfigured channels are unlimited
+        for _ in 0..100 {
+            assert!(limiter.check(&ChannelType::Slack, "C1").is_ok());
+        }
+    }
+
+    #[test]
+    fn test_recipient_is_the_conversation() {
+        let msg = |reply_to: Option<&str>, thread_id: Option<&str>| OutgoingMessage {
+            content: "hi".to_string(),
+            channel: ChannelType::Slack,
+            reply_to: reply_to.map(str::to_string),
+            thread_id: thread_id.map(str::to_string),
+            kind: MessageKind::Response,
+        };
+
+        assert_eq!(recipient(&msg(Some("slack_C1_1700.1"), None)), "slack_C1");
+        assert_eq!(
+            recipient(&msg(Some("slack_C1_1700.2"), Some("1700.1"))),
+            "slack_C1"
+        );
+        assert_eq!(recipient(&msg(Some("telegram_42_7"), None)), "telegram_42");
+        assert_eq!(recipient(&msg(Some("discord_123"), None)), "discord_123");
+        assert_eq!(recipient(&msg(None, Some("1700.1"))), "");
+    }
+
+    #[test]
+    fn test_buckets_are_bounded() {
+        let limiter = RateLimiter::for_sends()
+            .with_default_limit(BucketConfig::new(1, 0.0))
+            .with_clock(MockClock::new());
+
+        assert!(limiter.check(&ChannelType::Slack, "first").is_ok());
+        assert!(limiter.check(&ChannelType::Slack, "first").is_err());
+        for i in 0..MAX_BUCKETS {
+            assert!(limiter.check(&ChannelType::Slack, &i.to_string()).is_ok());
+        }
+        assert_eq!(limiter.buckets.lock().unwrap().len(), MAX_BUCKETS);
+
+        // The oldest bucket was evicted, so its recipient starts over
+        assert!(limiter.check(&ChannelType::Slack, "first").is_ok());
+    }
+
+    #[test]
+    fn test_default_limit_applies_to_unconfigured_channels() {
+        let limiter = RateLimiter::new(10, Duration::from_secs(60))
+            .with_default_limit(BucketConfig::new(1, 0.5))
+            .with_clock(MockClock::new());
+
+        assert!(limiter.check(&ChannelType::Slack, "C1").is_ok());
+        assert_eq!(
+            limiter.check(&ChannelType::Slack, "C1"),
+            Err(RetryAfter(Duration::from_secs(2)))
+        );
+    }
 }
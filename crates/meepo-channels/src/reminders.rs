@@ -1,34 +1,344 @@
 //! Apple Reminders channel adapter using AppleScript polling
 
-use crate::bus::MessageChannel;
+use crate::bus::{ChannelHealth, HealthCell, IncomingSender, MessageChannel};
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use chrono::Utc;
-use meepo_core::types::{ChannelType, IncomingMessage, MessageKind, OutgoingMessage};
+use meepo_core::types::{ChannelType, IncomingMessage, MessageKind, OutgoingMessage, Priority};
+use rusqlite::{Connection, params};
 use std::collections::HashSet;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::process::Command;
 use tokio::sync::Mutex;
-use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
+/// How an outgoing reply is split into a reminder title and body
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TitleMode {
+    /// First line becomes the title, the remaining lines the body
+    #[default]
+    FirstLine,
+    /// First sentence becomes the title, the rest the body
+    FirstSentence,
+    /// A leading `title:` line supplies the title; otherwise behaves like `FirstLine`
+    TitlePrefix,
+}
+
+impl TitleMode {
+    /// Parse a title mode from config (e.g., "first_line", "first_sentence", "title_prefix")
+    pub fn from_string(s: &str) -> Self {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "first_sentence" | "sentence" => Self::FirstSentence,
+            "title_prefix" | "prefix" => Self::TitlePrefix,
+            _ => Self::FirstLine,
+        }
+    }
+}
+
+/// Formatting options applied when replies are written to Reminders.app
+#[derive(Debug, Clone)]
+pub struct ReplyFormat {
+    pub title_mode: TitleMode,
+    /// Keep the full content in the body and use the title only as a short summary
+    pub full_body: bool,
+    /// Convert markdown to clean text before creating the reminder
+    pub strip_markdown: bool,
+    /// Titles longer than this (in chars) are truncated with an ellipsis
+    pub max_title_chars: usize,
+}
+
+impl Default for ReplyFormat {
+    fn default() -> Self {
+        Self {
+            title_mode: TitleMode::default(),
+            full_body: false,
+            strip_markdown: true,
+            max_title_chars: 80,
+        }
+    }
+}
+
+impl ReplyFormat {
+    /// Split reply content into a `(title, body)` pair for a new reminder
+    pub fn apply(&self, content: &str) -> (String, String) {
+        let text = if self.strip_markdown {
+            strip_markdown(content)
+        } else {
+            content.trim().to_string()
+        };
+
+        // A `title:` line is consumed entirely, so it never repeats in the body
+        let prefixed = if self.title_mode == TitleMode::TitlePrefix {
+            split_title_prefix(&text)
+        } else {
+            None
+        };
+
+        let has_prefix = prefixed.is_some();
+
+        let (title, rest) = match prefixed {
+            Some(split) => split,
+            None => match self.title_mode {
+                TitleMode::FirstSentence => split_first_sentence(&text),
+                _ => split_first_line(&text),
+            },
+        };
+
+        let body = if self.full_body && !has_prefix {
+            text
+        } else {
+            rest
+        };
+
+        (truncate_title(&title, self.max_title_chars), body)
+    }
+}
+
+fn split_first_line(text: &str) -> (String, String) {
+    match text.split_once('\n') {
+        Some((first, rest)) => (first.trim().to_string(), rest.trim().to_string()),
+        None => (text.trim().to_string(), String::new()),
+    }
+}
+
+fn split_first_sentence(text: &str) -> (String, String) {
+    let first_line_end = text.find('\n').unwrap_or(text.len());
+    let boundary = text[..first_line_end]
+        .char_indices()
+        .find(|&(i, c)| {
+            matches!(c, '.' | '!' | '?')
+                && text[i + c.len_utf8()..]
+                    .chars()
+                    .next()
+                    .is_none_or(char::is_whitespace)
+        })
+        .map(|(i, c)| i + c.len_utf8());
+
+    match boundary {
+        Some(end) => {
+            let title = text[..end].trim().trim_end_matches('.').to_string();
+            (title, text[end..].trim().to_string())
+        }
+        None => split_first_line(text),
+    }
+}
+
+fn split_title_prefix(text: &str) -> Option<(String, String)> {
+    let (first, rest) = text.split_once('\n').unwrap_or((text, ""));
+    let first = first.trim();
+    let lower = first.to_lowercase();
+    if !lower.starts_with("title:") {
+        return None;
+    }
+    let title = first["title:".len()..].trim();
+    if title.is_empty() {
+        return None;
+    }
+    Some((title.to_string(), rest.trim().to_string()))
+}
+
+fn truncate_title(title: &str, max_chars: usize) -> String {
+    if max_chars == 0 || title.chars().count() <= max_chars {
+        return title.to_string();
+    }
+    let truncated: String = title.chars().take(max_chars.saturating_sub(1)).collect();
+    format!("{}…", truncated.trim_end())
+}
+
+/// Convert markdown to plain text while keeping its line structure.
+///
+/// Headings, emphasis markers, inline code ticks and code fences are removed,
+/// bullets become `•`, and links are rendered as `text (url)`.
+pub fn strip_markdown(s: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+
+    for raw in s.lines() {
+        let line = raw.trim_end();
+        let trimmed = line.trim_start();
+
+        // Drop code fences and horizontal rules, keep everything else
+        if trimmed.starts_with("```") {
+            continue;
+        }
+        if trimmed.len() >= 3
+            && ['-', '*', '_']
+                .iter()
+                .any(|&m| trimmed.chars().all(|c| c == m || c == ' '))
+        {
+            continue;
+        }
+
+        let indent = &line[..line.len() - trimmed.len()];
+        let mut text = trimmed.to_string();
+
+        if let Some(rest) = text.strip_prefix('>') {
+            text = rest.trim_start().to_string();
+        }
+        if text.starts_with('#') {
+            let stripped = text.trim_start_matches('#');
+            if stripped.is_empty() || stripped.starts_with(' ') {
+                text = stripped.trim_start().to_string();
+            }
+        }
+        for bullet in ["- ", "* ", "+ "] {
+            if let Some(rest) = text.strip_prefix(bullet) {
+                text = format!("• {}", rest);
+                break;
+            }
+        }
+
+        text = strip_links(&text)
+            .replace("**", "")
+            .replace("__", "")
+            .replace('`', "");
+
+        // Collapse runs of blank lines to a single separator
+        if text.is_empty() && lines.last().is_none_or(|l| l.is_empty()) {
+            continue;
+        }
+        lines.push(format!("{}{}", indent, text));
+    }
+
+    lines.join("\n").trim().to_string()
+}
+
+/// Rewrite `[text](url)` links as `text (url)`
+fn strip_links(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(open) = rest.find('[') {
+        let after = &rest[open + 1..];
+        let parsed = after.find("](").and_then(|close| {
+            let url_part = &after[close + 2..];
+            url_part
+                .find(')')
+                .map(|end| (&after[..close], &url_part[..end], &url_part[end + 1..]))
+        });
+
+        match parsed {
+            Some((text, url, remainder)) => {
+                out.push_str(&rest[..open]);
+                if text == url || url.is_empty() {
+                    out.push_str(text);
+                } else {
+                    out.push_str(&format!("{} ({})", text, url));
+                }
+                rest = remainder;
+            }
+            None => {
+                out.push_str(&rest[..open + 1]);
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Small SQLite file recording which reminders have been processed, so they
+/// survive restarts without being marked completed
+pub struct SeenReminderStore {
+    conn: std::sync::Mutex<Connection>,
+}
+
+impl SeenReminderStore {
+    /// Open (or create) the store at `path`
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)
+            .map_err(|e| anyhow!("Failed to open reminder store {:?}: {}", path, e))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS seen_reminders (
+                id TEXT PRIMARY KEY,
+                list_name TEXT NOT NULL,
+                seen_at TEXT NOT NULL
+            )",
+        )?;
+        Ok(Self {
+            conn: std::sync::Mutex::new(conn),
+        })
+    }
+
+    /// All reminder IDs recorded so far
+    pub fn ids(&self) -> Result<HashSet<String>> {
+        let conn = self.conn.lock().map_err(|_| anyhow!("lock poisoned"))?;
+        let mut stmt = conn.prepare("SELECT id FROM seen_reminders")?;
+        let ids = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<HashSet<String>>>()?;
+        Ok(ids)
+    }
+
+    /// Record a reminder as processed
+    pub fn insert(&self, id: &str, list_name: &str) -> Result<()> {
+        let conn = self.conn.lock().map_err(|_| anyhow!("lock poisoned"))?;
+        conn.execute(
+            "INSERT OR IGNORE INTO seen_reminders (id, list_name, seen_at) VALUES (?1, ?2, ?3)",
+            params![id, list_name, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+}
+
 /// Apple Reminders channel adapter that polls Reminders.app for new items
-/// in a designated list and creates reminders from outgoing messages.
+/// in one or more lists and creates reminders from outgoing messages.
 pub struct RemindersChannel {
     poll_interval: Duration,
+    /// List that replies are written to
     list_name: String,
+    /// Lists polled for incoming reminders
+    watched_lists: Vec<String>,
+    reply_format: ReplyFormat,
     /// Tracks reminder IDs we've already processed to avoid duplicates
     seen_ids: Arc<Mutex<HashSet<String>>>,
+    /// When set, processed reminders are recorded here and left incomplete
+    seen_store: Option<Arc<SeenReminderStore>>,
+    /// Outcome of the most recent poll across all watched lists
+    health: HealthCell,
 }
 
 impl RemindersChannel {
     pub fn new(poll_interval: Duration, list_name: String) -> Self {
         Self {
             poll_interval,
+            watched_lists: vec![list_name.clone()],
             list_name,
+            reply_format: ReplyFormat::default(),
             seen_ids: Arc::new(Mutex::new(HashSet::new())),
+            seen_store: None,
+            health: HealthCell::new(),
+        }
+    }
+
+    /// Override how outgoing replies are turned into reminders
+    pub fn with_reply_format(mut self, reply_format: ReplyFormat) -> Self {
+        self.reply_format = reply_format;
+        self
+    }
+
+    /// Poll these lists instead of only the reply list
+    pub fn with_watched_lists(mut self, lists: Vec<String>) -> Self {
+        if !lists.is_empty() {
+            self.watched_lists = lists;
         }
+        self
+    }
+
+    /// Leave picked-up reminders incomplete and remember them in a SQLite
+    /// file at `path` instead, so they aren't reprocessed after a restart
+    pub fn with_seen_store(mut self, path: &Path) -> Result<Self> {
+        let store = SeenReminderStore::open(path)?;
+        let ids = store.ids()?;
+        debug!(
+            "Loaded {} processed reminder IDs from {:?}",
+            ids.len(),
+            path
+        );
+        self.seen_ids = Arc::new(Mutex::new(ids));
+        self.seen_store = Some(Arc::new(store));
+        Ok(self)
     }
 
     /// Sanitize a string for safe use in AppleScript.
@@ -42,16 +352,33 @@ impl RemindersChannel {
             .collect()
     }
 
-    /// Poll Reminders.app for incomplete reminders in the configured list
-    async fn poll_reminders(&self, tx: &mpsc::Sender<IncomingMessage>) -> Result<()> {
-        let list = Self::escape_applescript(&self.list_name);
+    /// Poll Reminders.app for incomplete reminders in every watched list
+    async fn poll_reminders(&self, tx: &IncomingSender) -> Result<()> {
+        let mut failures = Vec::new();
+        for list_name in &self.watched_lists {
+            if let Err(e) = self.poll_list(list_name, tx).await {
+                warn!("Failed to poll Reminders list '{}': {}", list_name, e);
+                failures.push(e.to_string());
+            }
+        }
+        self.health.set(if failures.is_empty() {
+            ChannelHealth::Healthy
+        } else {
+            ChannelHealth::Degraded(failures.join("; "))
+        });
+        Ok(())
+    }
+
+    /// Poll one Reminders list for incomplete reminders
+    async fn poll_list(&self, list_name: &str, tx: &IncomingSender) -> Result<()> {
+        let list = Self::escape_applescript(list_name);
 
         let script = format!(
             r#"
 tell application "Reminders"
     try
         if not (exists list "{list}") then
-            return ""
+            return "NO_LIST"
         end if
         set output to ""
         set targetList to list "{list}"
@@ -88,15 +415,17 @@ end tell
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            warn!("Reminders.app poll failed: {}", stderr);
-            return Ok(());
+            return Err(anyhow!("osascript failed: {}", stderr.trim()));
         }
 
         let stdout = String::from_utf8_lossy(&output.stdout);
-        if stdout.trim().is_empty() || stdout.starts_with("ERROR:") {
-            if stdout.starts_with("ERROR:") {
-                warn!("Reminders.app error: {}", stdout);
-            }
+        if stdout.trim() == "NO_LIST" {
+            return Err(anyhow!("list '{}' not found", list_name));
+        }
+        if let Some(err) = stdout.strip_prefix("ERROR:") {
+            return Err(anyhow!("Reminders.app error: {}", err.trim()));
+        }
+        if stdout.trim().is_empty() {
             return Ok(());
         }
 
@@ -149,15 +478,25 @@ end tell
                 content,
                 channel: ChannelType::Reminders,
                 timestamp: Utc::now(),
+                priority: Priority::Normal,
+                command: None,
+                thread_id: None,
             };
 
-            info!("New reminder from Reminders.app: {}", name);
+            info!("New reminder from Reminders.app ({}): {}", list_name, name);
 
             if let Err(e) = tx.send(incoming).await {
                 error!("Failed to send reminder message to bus: {}", e);
             }
 
-            // Mark the reminder as completed so it doesn't get picked up again
+            // Either remember the reminder persistently or mark it completed
+            // so it doesn't get picked up again
+            if let Some(store) = &self.seen_store {
+                if let Err(e) = store.insert(&id, list_name) {
+                    warn!("Failed to record processed reminder: {}", e);
+                }
+                continue;
+            }
             let complete_script = format!(
                 r#"
 tell application "Reminders"
@@ -170,7 +509,6 @@ tell application "Reminders"
     end try
 end tell
 "#,
-                list = Self::escape_applescript(&self.list_name),
                 id = Self::escape_applescript(&id),
             );
 
@@ -231,23 +569,135 @@ end tell
             Err(anyhow!("Failed to create reminder: {}", stderr))
         }
     }
+
+    /// Create several reminders in a single AppleScript `tell` block.
+    /// Returns one result per `(name, body)` item, in order, so one failure
+    /// doesn't lose the rest.
+    pub async fn create_reminders(&self, items: &[(String, String)]) -> Vec<Result<()>> {
+        if items.is_empty() {
+            return Vec::new();
+        }
+
+        let safe_list = Self::escape_applescript(&self.list_name);
+        let make_items: String = items
+            .iter()
+            .map(|(name, body)| {
+                format!(
+                    r#"
+            try
+                make new reminder with properties {{name:"{}", body:"{}"}}
+                set output to output & "OK" & linefeed
+            on error errMsg
+                set output to output & "ERROR: " & errMsg & linefeed
+            end try"#,
+                    Self::escape_applescript(name),
+                    Self::escape_applescript(body)
+                )
+            })
+            .collect();
+
+        let script = format!(
+            r#"
+tell application "Reminders"
+    try
+        if not (exists list "{safe_list}") then
+            make new list with properties {{name:"{safe_list}"}}
+        end if
+    on error errMsg
+        return "ERROR: " & errMsg
+    end try
+    set output to ""
+    tell list "{safe_list}"{make_items}
+    end tell
+    return output
+end tell
+"#
+        );
+
+        let output = match tokio::time::timeout(
+            Duration::from_secs(30 + 2 * items.len() as u64),
+            Command::new("osascript").arg("-e").arg(&script).output(),
+        )
+        .await
+        {
+            Ok(Ok(output)) => output,
+            Ok(Err(e)) => {
+                return batch_error(items.len(), &format!("Failed to run osascript: {}", e));
+            }
+            Err(_) => return batch_error(items.len(), "Reminders batch create timed out"),
+        };
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return batch_error(
+                items.len(),
+                &format!("Failed to create reminders: {}", stderr),
+            );
+        }
+
+        let results = parse_batch_output(&String::from_utf8_lossy(&output.stdout), items.len());
+        let created = results.iter().filter(|r| r.is_ok()).count();
+        info!("Created {}/{} reminders in one batch", created, items.len());
+        results
+    }
+}
+
+/// The same error for every item of a batch that failed as a whole
+fn batch_error(count: usize, reason: &str) -> Vec<Result<()>> {
+    (0..count).map(|_| Err(anyhow!("{}", reason))).collect()
+}
+
+/// Turn the per-item "OK" / "ERROR: ..." lines of a batch script into results
+fn parse_batch_output(stdout: &str, count: usize) -> Vec<Result<()>> {
+    let lines: Vec<&str> = stdout
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+
+    // A single error line means the batch failed before any item ran
+    if lines.len() != count {
+        let reason = match lines.as_slice() {
+            [line] if line.starts_with("ERROR:") => format!("Reminders.app error: {}", line),
+            _ => format!(
+                "Unexpected Reminders.app output for {} items: {}",
+                count,
+                stdout.trim()
+            ),
+        };
+        return batch_error(count, &reason);
+    }
+
+    lines
+        .into_iter()
+        .map(|line| {
+            if line == "OK" {
+                Ok(())
+            } else {
+                Err(anyhow!("Reminders.app error: {}", line))
+            }
+        })
+        .collect()
 }
 
 #[async_trait]
 impl MessageChannel for RemindersChannel {
-    async fn start(&self, tx: mpsc::Sender<IncomingMessage>) -> Result<()> {
+    async fn start(&self, tx: IncomingSender) -> Result<()> {
         info!("Starting Reminders channel adapter");
         info!("Poll interval: {:?}", self.poll_interval);
-        info!("Reminders list: {}", self.list_name);
-
-        let poll_interval = self.poll_interval;
-        let list_name = self.list_name.clone();
-        let seen_ids = self.seen_ids.clone();
+        info!("Reminders lists: {}", self.watched_lists.join(", "));
+        if self.seen_store.is_some() {
+            info!("Processed reminders are tracked, not completed");
+        }
 
         let channel = RemindersChannel {
-            poll_interval,
-            list_name,
-            seen_ids,
+            poll_interval: self.poll_interval,
+            list_name: self.list_name.clone(),
+            watched_lists: self.watched_lists.clone(),
+            reply_format: self.reply_format.clone(),
+            seen_ids: self.seen_ids.clone(),
+            seen_store: self.seen_store.clone(),
+            health: self.health.clone(),
         };
 
         tokio::spawn(async move {
@@ -275,18 +725,45 @@ impl MessageChannel for RemindersChannel {
             return Ok(());
         }
 
-        // Extract a title from the first line of content, rest becomes body
-        let (title, body) = match msg.content.split_once('\n') {
-            Some((first, rest)) => (first.trim().to_string(), rest.trim().to_string()),
-            None => (msg.content.clone(), String::new()),
-        };
+        let (title, body) = self.reply_format.apply(&msg.content);
 
         self.create_reminder(&title, &body).await
     }
 
+    async fn send_batch(&self, msgs: Vec<OutgoingMessage>) -> Vec<Result<()>> {
+        // Acknowledgments are skipped, everything else goes into one script
+        let items: Vec<(String, String)> = msgs
+            .iter()
+            .filter(|m| m.kind != MessageKind::Acknowledgment)
+            .map(|m| self.reply_format.apply(&m.content))
+            .collect();
+        let mut created = self.create_reminders(&items).await.into_iter();
+
+        msgs.iter()
+            .map(|m| {
+                if m.kind == MessageKind::Acknowledgment {
+                    Ok(())
+                } else {
+                    created
+                        .next()
+                        .unwrap_or_else(|| Err(anyhow!("Missing reminder result")))
+                }
+            })
+            .collect()
+    }
+
     fn channel_type(&self) -> ChannelType {
         ChannelType::Reminders
     }
+
+    fn health(&self) -> ChannelHealth {
+        self.health.get()
+    }
+
+    fn supports_kind(&self, kind: &MessageKind) -> bool {
+        // Acknowledgments are silently ignored
+        *kind != MessageKind::Acknowledgment
+    }
 }
 
 #[cfg(test)]
@@ -311,6 +788,126 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_title_mode_from_string() {
+        assert_eq!(
+            TitleMode::from_string("first_sentence"),
+            TitleMode::FirstSentence
+        );
+        assert_eq!(
+            TitleMode::from_string("title-prefix"),
+            TitleMode::TitlePrefix
+        );
+        assert_eq!(TitleMode::from_string("bogus"), TitleMode::FirstLine);
+    }
+
+    #[test]
+    fn test_reply_format_first_line() {
+        let format = ReplyFormat::default();
+        let (title, body) = format.apply("## Groceries\n\n- milk\n- **eggs**");
+        assert_eq!(title, "Groceries");
+        assert_eq!(body, "• milk\n• eggs");
+    }
+
+    #[test]
+    fn test_reply_format_first_sentence() {
+        let format = ReplyFormat {
+            title_mode: TitleMode::FirstSentence,
+            ..Default::default()
+        };
+        let (title, body) = format.apply("Call Bob at 3.5pm. He wants the v1.2 report.\nThanks!");
+        assert_eq!(title, "Call Bob at 3.5pm");
+        assert_eq!(body, "He wants the v1.2 report.\nThanks!");
+    }
+
+    #[test]
+    fn test_reply_format_title_prefix() {
+        let format = ReplyFormat {
+            title_mode: TitleMode::TitlePrefix,
+            full_body: true,
+            ..Default::default()
+        };
+        let (title, body) = format.apply("Title: Trip plan\nDay 1: fly\n\nDay 2: hike");
+        assert_eq!(title, "Trip plan");
+        assert_eq!(body, "Day 1: fly\n\nDay 2: hike");
+
+        // Without a prefix line, fall back to first line
+        let (title, _) = format.apply("Just a note");
+        assert_eq!(title, "Just a note");
+    }
+
+    #[test]
+    fn test_reply_format_full_body_truncates_title() {
+        let format = ReplyFormat {
+            full_body: true,
+            max_title_chars: 10,
+            ..Default::default()
+        };
+        let (title, body) = format.apply("A very long first paragraph\nsecond line");
+        assert_eq!(title, "A very lo…");
+        assert_eq!(body, "A very long first paragraph\nsecond line");
+    }
+
+    #[test]
+    fn test_parse_batch_output() {
+        let results = parse_batch_output("OK\nERROR: bad name\nOK\n", 3);
+        assert!(results[0].is_ok());
+        assert!(
+            results[1]
+                .as_ref()
+                .unwrap_err()
+                .to_string()
+                .contains("bad name")
+        );
+        assert!(results[2].is_ok());
+
+        // Failing before any item ran fails the whole batch
+        let results = parse_batch_output("ERROR: Reminders got an error\n", 2);
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.is_err()));
+    }
+
+    #[test]
+    fn test_strip_markdown() {
+        let md = "# Heading\n\n\n> quoted `code`\n\n```rust\nlet x = 1;\n```\n---\nSee [docs](https://x.y) and __this__";
+        assert_eq!(
+            strip_markdown(md),
+            "Heading\n\nquoted code\n\nlet x = 1;\nSee docs (https://x.y) and this"
+        );
+    }
+
+    #[test]
+    fn test_watched_lists() {
+        let channel = RemindersChannel::new(Duration::from_secs(10), "Meepo".to_string());
+        assert_eq!(channel.watched_lists, vec!["Meepo"]);
+
+        let channel = channel.with_watched_lists(vec!["Inbox".to_string(), "Work".to_string()]);
+        assert_eq!(channel.watched_lists, vec!["Inbox", "Work"]);
+        assert_eq!(channel.list_name, "Meepo");
+    }
+
+    #[tokio::test]
+    async fn test_seen_store_survives_restart() {
+        let path = std::env::temp_dir().join(format!("reminders_seen_{}.db", uuid::Uuid::new_v4()));
+
+        let channel = RemindersChannel::new(Duration::from_secs(10), "Meepo".to_string())
+            .with_seen_store(&path)
+            .unwrap();
+        let store = channel.seen_store.clone().unwrap();
+        store.insert("x-apple-reminder://1", "Meepo").unwrap();
+        store.insert("x-apple-reminder://1", "Meepo").unwrap();
+        drop(channel);
+
+        let restarted = RemindersChannel::new(Duration::from_secs(10), "Meepo".to_string())
+            .with_seen_store(&path)
+            .unwrap();
+        let seen = restarted.seen_ids.lock().await;
+        assert_eq!(seen.len(), 1);
+        assert!(seen.contains("x-apple-reminder://1"));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
     #[tokio::test]
     async fn test_seen_ids_dedup() {
         let channel = RemindersChannel::new(Duration::from_secs(10), "Meepo".to_string());
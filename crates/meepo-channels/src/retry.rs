@@ -0,0 +1,419 @@
+//! Retry decorator for channel sends
+//!
+//! [`RetryingChannel`] wraps any [`MessageChannel`] and retries failed sends
+//! with exponential backoff, so a network blip or a busy Mail.app doesn't
+//! drop the reply. Only transient errors (timeouts, dropped connections, HTTP
+//! 5xx and 429) are retried; anything else fails straight away. Only the last
+//! error is surfaced once retries run out.
+//!
+//! A retry resends the whole message. Channels that split long replies into
+//! chunks can repeat the chunks that went out before the failure, which is
+//! why retries are off by default.
+
+use crate::bus::{ChannelHealth, IncomingSender, MessageChannel};
+use crate::rate_limit::RetryAfter;
+use anyhow::Result;
+use async_trait::async_trait;
+use meepo_core::types::{ChannelType, MessageKind, OutgoingMessage};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// An HTTP error status returned by a channel's API.
+/// Kept as a typed error so [`is_transient`] can tell a 503 from a 403.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HttpStatusError(pub u16);
+
+impl std::fmt::Display for HttpStatusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "HTTP status {}", self.0)
+    }
+}
+
+impl std::error::Error for HttpStatusError {}
+
+/// Whether a failed send is worth retrying: timeouts, dropped connections,
+/// rate limits and server errors are; bad requests and permission errors aren't
+pub fn is_transient(err: &anyhow::Error) -> bool {
+    let transient_status = |status: u16| status == 429 || (500..600).contains(&status);
+    err.chain().any(|cause| {
+        if cause.is::<RetryAfter>() || cause.is::<tokio::time::error::Elapsed>() {
+            return true;
+        }
+        if let Some(HttpStatusError(status)) = cause.downcast_ref::<HttpStatusError>() {
+            return transient_status(*status);
+        }
+        if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+            return e.is_timeout()
+                || e.is_connect()
+                || e.status().is_some_and(|s| transient_status(s.as_u16()));
+        }
+        if let Some(e) = cause.downcast_ref::<std::io::Error>() {
+            return matches!(
+                e.kind(),
+                std::io::ErrorKind::TimedOut
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::BrokenPipe
+                    | std::io::ErrorKind::Interrupted
+            );
+        }
+        false
+    })
+}
+
+/// How `RetryingChannel` retries a failed send
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt (0 = never retry)
+    pub max_retries: u32,
+    /// Wait before the first retry; doubled for each one after
+    pub initial_backoff: Duration,
+    /// Upper bound on any single wait
+    pub max_backoff: Duration,
+    /// Message kinds that are sent once and never retried
+    pub no_retry_kinds: Vec<MessageKind>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+            no_retry_kinds: Vec::new(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: u32, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        Self {
+            max_retries,
+            initial_backoff,
+            max_backoff,
+            no_retry_kinds: Vec::new(),
+        }
+    }
+
+    /// Send messages of `kind` once, without retrying
+    pub fn without_retry_for(mut self, kind: MessageKind) -> Self {
+        if !self.no_retry_kinds.contains(&kind) {
+            self.no_retry_kinds.push(kind);
+        }
+        self
+    }
+
+    /// How many retries a message of `kind` gets
+    pub fn retries_for(&self, kind: &MessageKind) -> u32 {
+        if self.no_retry_kinds.contains(kind) {
+            0
+        } else {
+            self.max_retries
+        }
+    }
+
+    /// Wait before retry number `retry` (starting at 1)
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    /// Wait after `err`: a rate limiter's `RetryAfter` wins over the backoff
+    /// when it asks for longer, but never beyond `max_backoff`
+    fn delay_after(&self, retry: u32, err: &anyhow::Error) -> Duration {
+        let backoff = self.backoff(retry);
+        match err.downcast_ref::<RetryAfter>() {
+            Some(RetryAfter(wait)) => backoff.max(*wait).min(self.max_backoff),
+            None => backoff,
+        }
+    }
+}
+
+/// A channel whose sends are retried according to a [`RetryPolicy`].
+/// Everything other than sending is passed straight through.
+pub struct RetryingChannel {
+    inner: Box<dyn MessageChannel>,
+    policy: RetryPolicy,
+}
+
+impl RetryingChannel {
+    pub fn new(inner: Box<dyn MessageChannel>, policy: RetryPolicy) -> Self {
+        Self { inner, policy }
+    }
+
+    pub fn policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+
+    /// Retry `msg` after its first attempt failed with `err`, for as long as
+    /// the failures are transient
+    async fn retry(&self, msg: OutgoingMessage, mut err: anyhow::Error) -> Result<()> {
+        let retries = self.policy.retries_for(&msg.kind);
+        let mut retry = 0;
+        while retry < retries && is_transient(&err) {
+            retry += 1;
+            let delay = self.policy.delay_after(retry, &err);
+            warn!(
+                "{} send failed ({}); retry {}/{} in {:?}",
+                self.inner.channel_type(),
+                err,
+                retry,
+                retries,
+                delay
+            );
+            tokio::time::sleep(delay).await;
+            match self.inner.send(msg.clone()).await {
+                Ok(()) => {
+                    debug!(
+                        "{} send succeeded on retry {}",
+                        self.inner.channel_type(),
+                        retry
+                    );
+                    return Ok(());
+                }
+                Err(e) => err = e,
+            }
+        }
+        if retry > 0 {
+            return Err(err.context(format!("giving up after {} retries", retry)));
+        }
+        Err(err)
+    }
+}
+
+#[async_trait]
+impl MessageChannel for RetryingChannel {
+    async fn start(&self, tx: IncomingSender) -> Result<()> {
+        self.inner.start(tx).await
+    }
+
+    async fn send(&self, msg: OutgoingMessage) -> Result<()> {
+        match self.inner.send(msg.clone()).await {
+            Ok(()) => Ok(()),
+            Err(e) => self.retry(msg, e).await,
+        }
+    }
+
+    /// Let the inner channel deliver the batch in bulk, then retry only the
+    /// messages that failed, one at a time
+    async fn send_batch(&self, msgs: Vec<OutgoingMessage>) -> Vec<Result<()>> {
+        let results = self.inner.send_batch(msgs.clone()).await;
+        let mut retried = Vec::with_capacity(results.len());
+        for (msg, result) in msgs.into_iter().zip(results) {
+            retried.push(match result {
+                Ok(()) => Ok(()),
+                Err(e) => self.retry(msg, e).await,
+            });
+        }
+        retried
+    }
+
+    fn channel_type(&self) -> ChannelType {
+        self.inner.channel_type()
+    }
+
+    fn supports_kind(&self, kind: &MessageKind) -> bool {
+        self.inner.supports_kind(kind)
+    }
+
+    fn health(&self) -> ChannelHealth {
+        self.inner.health()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Fails the first `failures` sends, then succeeds
+    struct FlakyChannel {
+        failures: u32,
+        attempts: Arc<AtomicU32>,
+        error: fn(u32) -> anyhow::Error,
+    }
+
+    fn timeout(attempt: u32) -> anyhow::Error {
+        anyhow!(std::io::Error::new(
+            std::io::ErrorKind::TimedOut,
+            format!("network blip {}", attempt)
+        ))
+    }
+
+    fn forbidden(_attempt: u32) -> anyhow::Error {
+        anyhow::Error::new(HttpStatusError(403)).context("missing permissions")
+    }
+
+    #[async_trait]
+    impl MessageChannel for FlakyChannel {
+        async fn start(&self, _tx: IncomingSender) -> Result<()> {
+            Ok(())
+        }
+
+        async fn send(&self, _msg: OutgoingMessage) -> Result<()> {
+            let attempt = self.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+            if attempt <= self.failures {
+                Err((self.error)(attempt))
+            } else {
+                Ok(())
+            }
+        }
+
+        fn channel_type(&self) -> ChannelType {
+            ChannelType::Slack
+        }
+
+        fn health(&self) -> ChannelHealth {
+            ChannelHealth::Degraded("flaky".to_string())
+        }
+    }
+
+    fn flaky(failures: u32, policy: RetryPolicy) -> (RetryingChannel, Arc<AtomicU32>) {
+        failing_with(timeout, failures, policy)
+    }
+
+    fn failing_with(
+        error: fn(u32) -> anyhow::Error,
+        failures: u32,
+        policy: RetryPolicy,
+    ) -> (RetryingChannel, Arc<AtomicU32>) {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let inner = FlakyChannel {
+            failures,
+            attempts: attempts.clone(),
+            error,
+        };
+        (RetryingChannel::new(Box::new(inner), policy), attempts)
+    }
+
+    fn fast_policy(max_retries: u32) -> RetryPolicy {
+        RetryPolicy::new(
+            max_retries,
+            Duration::from_millis(1),
+            Duration::from_millis(5),
+        )
+    }
+
+    fn message(kind: MessageKind) -> OutgoingMessage {
+        OutgoingMessage {
+            content: "hello".to_string(),
+            channel: ChannelType::Slack,
+            reply_to: None,
+            kind,
+            thread_id: None,
+        }
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let policy = RetryPolicy::new(5, Duration::from_millis(100), Duration::from_millis(350));
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(350));
+        assert_eq!(policy.backoff(40), Duration::from_millis(350));
+
+        let throttled = anyhow::Error::new(RetryAfter(Duration::from_millis(300)));
+        assert_eq!(
+            policy.delay_after(1, &throttled),
+            Duration::from_millis(300)
+        );
+        let throttled = anyhow::Error::new(RetryAfter(Duration::from_secs(60)));
+        assert_eq!(
+            policy.delay_after(1, &throttled),
+            Duration::from_millis(350)
+        );
+    }
+
+    #[test]
+    fn test_is_transient() {
+        assert!(is_transient(&timeout(1)));
+        assert!(is_transient(&anyhow::Error::new(HttpStatusError(503))));
+        assert!(is_transient(
+            &anyhow::Error::new(HttpStatusError(429)).context("Slack API HTTP error")
+        ));
+        assert!(is_transient(&anyhow::Error::new(RetryAfter(
+            Duration::from_secs(1)
+        ))));
+        assert!(!is_transient(&forbidden(1)));
+        assert!(!is_transient(&anyhow::Error::new(HttpStatusError(400))));
+        assert!(!is_transient(&anyhow!(
+            "Slack API error: channel_not_found"
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_default_policy_does_not_retry() {
+        let (channel, attempts) = flaky(1, RetryPolicy::default());
+        assert!(channel.send(message(MessageKind::Response)).await.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_permanent_errors_are_not_retried() {
+        let (channel, attempts) = failing_with(forbidden, 10, fast_policy(3));
+        let err = channel
+            .send(message(MessageKind::Response))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "missing permissions");
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retries_until_success() {
+        let (channel, attempts) = flaky(2, fast_policy(3));
+        channel.send(message(MessageKind::Response)).await.unwrap();
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(channel.channel_type(), ChannelType::Slack);
+        assert_eq!(
+            channel.health(),
+            ChannelHealth::Degraded("flaky".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_surfaces_last_error_after_exhaustion() {
+        let (channel, attempts) = flaky(10, fast_policy(2));
+        let err = channel
+            .send(message(MessageKind::Response))
+            .await
+            .unwrap_err();
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(err.to_string(), "giving up after 2 retries");
+        assert_eq!(err.root_cause().to_string(), "network blip 3");
+    }
+
+    #[tokio::test]
+    async fn test_no_retry_for_acknowledgments() {
+        let policy = fast_policy(3).without_retry_for(MessageKind::Acknowledgment);
+        let (channel, attempts) = flaky(1, policy);
+        let err = channel
+            .send(message(MessageKind::Acknowledgment))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "network blip 1");
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+        // Responses on the same channel are still retried
+        channel.send(message(MessageKind::Response)).await.unwrap();
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_send_batch_retries_failures() {
+        let (channel, attempts) = flaky(1, fast_policy(1));
+        let results = channel
+            .send_batch(vec![
+                message(MessageKind::Response),
+                message(MessageKind::Response),
+            ])
+            .await;
+        assert!(results.iter().all(|r| r.is_ok()));
+        // First message failed once and was retried; second went through
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+}
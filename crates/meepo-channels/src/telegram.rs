@@ -0,0 +1,478 @@
+//! Telegram channel adapter using Bot API long polling
+
+use crate::bus::{ChannelHealth, HealthCell, IncomingSender, MessageChannel};
+use crate::rate_limit::RateLimiter;
+use crate::retry::HttpStatusError;
+use crate::util::split_message_by;
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use chrono::Utc;
+use meepo_core::types::{ChannelType, IncomingMessage, MessageKind, OutgoingMessage, Priority};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::{debug, error, info, warn};
+
+const API_BASE: &str = "https://api.telegram.org";
+const MAX_MESSAGE_SIZE: usize = 10_240;
+/// Telegram rejects messages longer than this many UTF-16 code units
+const MAX_OUTGOING_LENGTH: usize = 4096;
+
+/// Split a "telegram_{chat_id}_{message_id}" message id into its chat and message ids
+fn parse_message_id(id: &str) -> Option<(&str, &str)> {
+    let (chat_id, message_id) = id.strip_prefix("telegram_")?.rsplit_once('_')?;
+    (!chat_id.is_empty() && !message_id.is_empty()).then_some((chat_id, message_id))
+}
+
+/// Split `text` into chunks Telegram will accept, measured in UTF-16 units
+fn split_message(text: &str, limit: usize) -> Vec<String> {
+    split_message_by(text, limit, |s| s.encode_utf16().count())
+}
+
+/// Telegram channel adapter using Bot API long polling
+pub struct TelegramChannel {
+    bot_token: String,
+    /// How long each getUpdates request waits for new messages
+    poll_timeout: Duration,
+    /// Telegram chat IDs allowed to interact with the agent.
+    /// The channel refuses to start when this is empty.
+    allowed_chats: Vec<String>,
+    /// Most recent chat that messaged us, used when a reply has no target
+    last_chat_id: Arc<RwLock<Option<String>>>,
+    api_base: String,
+    /// Outcome of the most recent getUpdates call
+    health: HealthCell,
+}
+
+impl TelegramChannel {
+    /// Create a new Telegram channel adapter
+    ///
+    /// # Arguments
+    /// * `bot_token` - Bot token from @BotFather
+    /// * `poll_timeout` - Long-poll timeout for getUpdates
+    /// * `allowed_chats` - Telegram chat IDs allowed to interact (must not be empty)
+    pub fn new(bot_token: String, poll_timeout: Duration, allowed_chats: Vec<String>) -> Self {
+        Self {
+            bot_token,
+            poll_timeout,
+            allowed_chats,
+            last_chat_id: Arc::new(RwLock::new(None)),
+            api_base: API_BASE.to_string(),
+            health: HealthCell::new(),
+        }
+    }
+
+    /// Use a different Bot API server (e.g. a self-hosted telegram-bot-api)
+    pub fn with_api_base(mut self, api_base: impl Into<String>) -> Self {
+        self.api_base = api_base.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Call a Bot API method, returning its `result` field.
+    /// The URL carries the bot token, so it's stripped from transport errors.
+    async fn api_call(
+        client: &reqwest::Client,
+        api_base: &str,
+        token: &str,
+        method: &str,
+        body: &serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        let url = format!("{}/bot{}/{}", api_base, token, method);
+        let response = client
+            .post(&url)
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| e.without_url())?;
+        let status = response.status();
+        let result: serde_json::Value = response.json().await.map_err(|e| e.without_url())?;
+
+        if result.get("ok").and_then(|v| v.as_bool()) != Some(true) {
+            let description = result
+                .get("description")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown");
+            return Err(
+                anyhow::Error::new(HttpStatusError(status.as_u16())).context(format!(
+                    "Telegram {} error ({}): {}",
+                    method, status, description
+                )),
+            );
+        }
+
+        Ok(result.get("result").cloned().unwrap_or_default())
+    }
+
+    /// Send one chunk with Markdown formatting, falling back to plain text
+    /// if Telegram can't parse the entities (e.g. a chunk split mid-span)
+    async fn send_chunk(
+        &self,
+        client: &reqwest::Client,
+        chat_id: &str,
+        thread_id: Option<&str>,
+        text: &str,
+    ) -> Result<()> {
+        let mut body = serde_json::json!({
+            "chat_id": chat_id,
+            "text": text,
+            "parse_mode": "Markdown",
+        });
+        if let Some(thread_id) = thread_id.and_then(|t| t.parse::<i64>().ok()) {
+            body["message_thread_id"] = serde_json::Value::from(thread_id);
+        }
+
+        match Self::api_call(
+            client,
+            &self.api_base,
+            &self.bot_token,
+            "sendMessage",
+            &body,
+        )
+        .await
+        {
+            Ok(_) => Ok(()),
+            Err(e) if e.to_string().contains("can't parse entities") => {
+                debug!("Telegram rejected Markdown, resending as plain text: {}", e);
+                if let Some(obj) = body.as_object_mut() {
+                    obj.remove("parse_mode");
+                }
+                Self::api_call(
+                    client,
+                    &self.api_base,
+                    &self.bot_token,
+                    "sendMessage",
+                    &body,
+                )
+                .await
+                .map(|_| ())
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[async_trait]
+impl MessageChannel for TelegramChannel {
+    async fn start(&self, tx: IncomingSender) -> Result<()> {
+        info!("Starting Telegram channel adapter");
+
+        if self.bot_token.is_empty() {
+            return Err(anyhow!("Telegram bot token is empty"));
+        }
+        // Anyone who finds the bot could otherwise drive the agent
+        if self.allowed_chats.is_empty() {
+            return Err(anyhow!(
+                "Telegram allowed_chats is empty; list the chat IDs that may use the bot"
+            ));
+        }
+
+        // Requests must outlive the long-poll timeout
+        let client = reqwest::Client::builder()
+            .timeout(self.poll_timeout + Duration::from_secs(30))
+            .build()?;
+
+        // Verify token before spawning the polling task
+        let me = Self::api_call(
+            &client,
+            &self.api_base,
+            &self.bot_token,
+            "getMe",
+            &serde_json::json!({}),
+        )
+        .await?;
+        let username = me
+            .get("username")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown");
+        info!("Telegram bot authenticated as @{}", username);
+
+        let token = self.bot_token.clone();
+        let api_base = self.api_base.clone();
+        let poll_timeout = self.poll_timeout.as_secs();
+        let allowed_chats = self.allowed_chats.clone();
+        let last_chat_id = self.last_chat_id.clone();
+        let rate_limiter = RateLimiter::new(10, Duration::from_secs(60));
+        let health = self.health.clone();
+        health.set(ChannelHealth::Healthy);
+
+        tokio::spawn(async move {
+            info!("Telegram polling task started");
+            let mut offset: Option<i64> = None;
+
+            loop {
+                let mut body = serde_json::json!({
+                    "timeout": poll_timeout,
+                    "allowed_updates": ["message"],
+                });
+                if let Some(offset) = offset {
+                    body["offset"] = serde_json::Value::from(offset);
+                }
+
+                let updates = match Self::api_call(&client, &api_base, &token, "getUpdates", &body)
+                    .await
+                {
+                    Ok(updates) => {
+                        health.set(ChannelHealth::Healthy);
+                        updates
+                    }
+                    Err(e) => {
+                        error!("Error polling Telegram for updates: {}", e);
+                        health.set(ChannelHealth::Degraded(format!("getUpdates failed: {}", e)));
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                        continue;
+                    }
+                };
+
+                let Some(updates) = updates.as_array() else {
+                    continue;
+                };
+
+                for update in updates {
+                    // Acknowledge every update, even ones we skip, so it isn't redelivered
+                    if let Some(update_id) = update.get("update_id").and_then(|v| v.as_i64()) {
+                        offset = Some(offset.map_or(update_id + 1, |o| o.max(update_id + 1)));
+                    }
+
+                    let Some(message) = update.get("message") else {
+                        continue;
+                    };
+                    let Some(chat_id) = message
+                        .get("chat")
+                        .and_then(|c| c.get("id"))
+                        .and_then(|v| v.as_i64())
+                        .map(|id| id.to_string())
+                    else {
+                        continue;
+                    };
+                    let message_id = message
+                        .get("message_id")
+                        .and_then(|v| v.as_i64())
+                        .unwrap_or_default();
+                    let text = message.get("text").and_then(|v| v.as_str()).unwrap_or("");
+                    // Private chats are addressed to meepo; groups are ambient
+                    let priority = match message
+                        .get("chat")
+                        .and_then(|c| c.get("type"))
+                        .and_then(|v| v.as_str())
+                    {
+                        Some("private") => Priority::High,
+                        _ => Priority::Normal,
+                    };
+                    let thread_id = message
+                        .get("message_thread_id")
+                        .and_then(|v| v.as_i64())
+                        .map(|id| id.to_string());
+
+                    if !allowed_chats.contains(&chat_id) {
+                        debug!(
+                            "Ignoring Telegram message from unauthorized chat: {}",
+                            chat_id
+                        );
+                        continue;
+                    }
+
+                    // Skip non-text messages (stickers, photos without captions, ...)
+                    if text.is_empty() {
+                        continue;
+                    }
+
+                    if text.len() > MAX_MESSAGE_SIZE {
+                        warn!(
+                            "Dropping oversized Telegram message from {} ({} bytes, limit {} bytes)",
+                            chat_id,
+                            text.len(),
+                            MAX_MESSAGE_SIZE,
+                        );
+                        continue;
+                    }
+
+                    if !rate_limiter.check_and_record(&chat_id) {
+                        continue;
+                    }
+
+                    *last_chat_id.write().await = Some(chat_id.clone());
+
+                    let incoming = IncomingMessage {
+                        id: format!("telegram_{}_{}", chat_id, message_id),
+                        sender: chat_id.clone(),
+                        content: text.to_string(),
+                        channel: ChannelType::Telegram,
+                        timestamp: Utc::now(),
+                        priority,
+                        command: None,
+                        thread_id,
+                    };
+
+                    info!(
+                        "Forwarding Telegram message from chat {} ({} chars)",
+                        chat_id,
+                        text.len()
+                    );
+
+                    if let Err(e) = tx.send(incoming).await {
+                        error!("Failed to send Telegram message to bus: {}", e);
+                    }
+                }
+            }
+        });
+
+        info!("Telegram channel adapter started");
+        Ok(())
+    }
+
+    async fn send(&self, msg: OutgoingMessage) -> Result<()> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()?;
+
+        let chat_id = match msg.reply_to.as_deref().and_then(parse_message_id) {
+            Some((chat_id, _)) => chat_id.to_string(),
+            None => self
+                .last_chat_id
+                .read()
+                .await
+                .clone()
+                .ok_or_else(|| anyhow!("No Telegram chat available for sending"))?,
+        };
+
+        // Acknowledgment: show "typing..." rather than posting a placeholder
+        if msg.kind == MessageKind::Acknowledgment {
+            let body = serde_json::json!({ "chat_id": chat_id, "action": "typing" });
+            if let Err(e) = Self::api_call(
+                &client,
+                &self.api_base,
+                &self.bot_token,
+                "sendChatAction",
+                &body,
+            )
+            .await
+            {
+                warn!("Failed to send Telegram typing indicator: {}", e);
+            }
+            return Ok(());
+        }
+
+        let chunks = split_message(&msg.content, MAX_OUTGOING_LENGTH);
+        for chunk in &chunks {
+            self.send_chunk(&client, &chat_id, msg.thread_id.as_deref(), chunk)
+                .await?;
+        }
+
+        info!(
+            "Telegram message sent successfully ({} part(s))",
+            chunks.len()
+        );
+        Ok(())
+    }
+
+    fn channel_type(&self) -> ChannelType {
+        ChannelType::Telegram
+    }
+
+    fn health(&self) -> ChannelHealth {
+        self.health.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_telegram_channel_creation() {
+        let channel =
+            TelegramChannel::new("123:abc".to_string(), Duration::from_secs(30), Vec::new())
+                .with_api_base("http://localhost:8081/");
+        assert!(matches!(channel.channel_type(), ChannelType::Telegram));
+        assert_eq!(channel.api_base, "http://localhost:8081");
+    }
+
+    #[tokio::test]
+    async fn test_telegram_empty_token() {
+        let channel = TelegramChannel::new(String::new(), Duration::from_secs(30), Vec::new());
+        let (tx, _rx) = crate::bus::incoming_queue(10);
+        assert!(channel.start(tx).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_telegram_requires_allowed_chats() {
+        let channel =
+            TelegramChannel::new("123:abc".to_string(), Duration::from_secs(30), Vec::new());
+        let (tx, _rx) = crate::bus::incoming_queue(10);
+        let err = channel.start(tx).await.unwrap_err();
+        assert!(err.to_string().contains("allowed_chats"));
+    }
+
+    #[tokio::test]
+    async fn test_telegram_errors_hide_token() {
+        let client = reqwest::Client::new();
+        let err = TelegramChannel::api_call(
+            &client,
+            "http://127.0.0.1:1",
+            "123:secret-token",
+            "getMe",
+            &serde_json::json!({}),
+        )
+        .await
+        .unwrap_err();
+        assert!(!format!("{:?}", err).contains("secret-token"));
+        assert!(!err.to_string().contains("secret-token"));
+    }
+
+    #[tokio::test]
+    async fn test_telegram_send_no_chat() {
+        let channel =
+            TelegramChannel::new("123:abc".to_string(), Duration::from_secs(30), Vec::new());
+        let msg = OutgoingMessage {
+            content: "test".to_string(),
+            channel: ChannelType::Telegram,
+            reply_to: None,
+            thread_id: None,
+            kind: MessageKind::Response,
+        };
+        assert!(channel.send(msg).await.is_err()); // Nobody has messaged us yet
+    }
+
+    #[test]
+    fn test_parse_message_id() {
+        assert_eq!(parse_message_id("telegram_12345_67"), Some(("12345", "67")));
+        // Group chat ids are negative
+        assert_eq!(
+            parse_message_id("telegram_-1001234567890_42"),
+            Some(("-1001234567890", "42"))
+        );
+        assert_eq!(parse_message_id("slack_D123_1.2"), None);
+        assert_eq!(parse_message_id("telegram_12345"), None);
+    }
+
+    #[test]
+    fn test_split_message_prefers_line_breaks() {
+        assert_eq!(split_message("short", 4096), vec!["short"]);
+        assert!(split_message("", 4096).is_empty());
+
+        let text = "first line\nsecond line\nthird";
+        assert_eq!(
+            split_message(text, 15),
+            vec!["first line", "second line", "third"]
+        );
+        assert_eq!(split_message("one two three", 8), vec!["one two", "three"]);
+        assert_eq!(split_message("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+    }
+
+    #[test]
+    fn test_split_message_counts_utf16_units() {
+        // Each emoji is two UTF-16 units, so only two fit in a limit of 5
+        let chunks = split_message("😀😀😀😀", 5);
+        assert_eq!(chunks, vec!["😀😀", "😀😀"]);
+
+        let long = "日本語".repeat(2000);
+        let chunks = split_message(&long, MAX_OUTGOING_LENGTH);
+        assert_eq!(chunks.len(), 2);
+        assert!(
+            chunks
+                .iter()
+                .all(|c| c.encode_utf16().count() <= MAX_OUTGOING_LENGTH)
+        );
+        assert_eq!(chunks.concat(), long);
+    }
+}
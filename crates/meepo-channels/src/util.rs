@@ -0,0 +1,314 @@
+//! Helpers shared by channel adapters
+
+/// Split `content` into messages of at most `max_len` characters
+///
+/// Breaks fall on paragraph boundaries where possible, then line breaks,
+/// then sentence ends, then spaces; a word is only cut when it is longer
+/// than `max_len` on its own. A fenced code block that has to be split is
+/// closed at the end of each part and reopened (with its language tag) at
+/// the start of the next, so every part renders as code.
+pub fn split_message(content: &str, max_len: usize) -> Vec<String> {
+    split_message_by(content, max_len, |s| s.chars().count())
+}
+
+/// `split_message` with a custom length measure, for platforms that count
+/// something other than characters (e.g. Telegram's UTF-16 code units)
+pub fn split_message_by(
+    content: &str,
+    max_len: usize,
+    measure: impl Fn(&str) -> usize,
+) -> Vec<String> {
+    let content = content.trim();
+    if content.is_empty() {
+        return Vec::new();
+    }
+    if max_len == 0 || measure(content) <= max_len {
+        return vec![content.to_string()];
+    }
+
+    let mut packer = Packer::new(max_len, &measure, PARAGRAPH_BREAK);
+    for block in blocks(content) {
+        let pieces = match &block {
+            Block::Prose(text) => split_prose(text, max_len, &measure, 0),
+            Block::Code { open, lines } => split_code(open, lines, max_len, &measure),
+        };
+        for piece in pieces {
+            packer.push(piece);
+        }
+    }
+    packer.finish()
+}
+
+const PARAGRAPH_BREAK: &str = "\n\n";
+const FENCE: &str = "```";
+
+/// A paragraph of prose or a complete fenced code block
+enum Block<'a> {
+    Prose(String),
+    Code { open: &'a str, lines: Vec<&'a str> },
+}
+
+impl Block<'_> {
+    fn render(open: &str, lines: &[&str]) -> String {
+        if lines.is_empty() {
+            format!("{}\n{}", open, FENCE)
+        } else {
+            format!("{}\n{}\n{}", open, lines.join("\n"), FENCE)
+        }
+    }
+}
+
+fn blocks(content: &str) -> Vec<Block<'_>> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut lines = content.lines();
+
+    let flush = |paragraph: &mut Vec<&str>, blocks: &mut Vec<Block>| {
+        if !paragraph.is_empty() {
+            blocks.push(Block::Prose(paragraph.join("\n")));
+            paragraph.clear();
+        }
+    };
+
+    while let Some(line) = lines.next() {
+        if line.trim_start().starts_with(FENCE) {
+            flush(&mut paragraph, &mut blocks);
+            let body = lines
+                .by_ref()
+                .take_while(|l| !l.trim_start().starts_with(FENCE))
+                .collect();
+            blocks.push(Block::Code {
+                open: line.trim(),
+                lines: body,
+            });
+        } else if line.trim().is_empty() {
+            flush(&mut paragraph, &mut blocks);
+        } else {
+            paragraph.push(line);
+        }
+    }
+    flush(&mut paragraph, &mut blocks);
+    blocks
+}
+
+/// Split prose by lines, then sentences, then words, then characters,
+/// going finer only for pieces that are still too long
+fn split_prose(
+    text: &str,
+    max_len: usize,
+    measure: &dyn Fn(&str) -> usize,
+    level: usize,
+) -> Vec<String> {
+    if measure(text) <= max_len {
+        return vec![text.to_string()];
+    }
+    let (units, separator): (Vec<&str>, &str) = match level {
+        0 => (text.lines().collect(), "\n"),
+        1 => (sentences(text), " "),
+        2 => (text.split_whitespace().collect(), " "),
+        _ => return hard_split(text, max_len, measure),
+    };
+
+    let mut packer = Packer::new(max_len, measure, separator);
+    for unit in units.into_iter().filter(|u| !u.trim().is_empty()) {
+        for piece in split_prose(unit, max_len, measure, level + 1) {
+            packer.push(piece);
+        }
+    }
+    packer.finish()
+}
+
+/// Split a code block between lines, fencing each part
+fn split_code(
+    open: &str,
+    lines: &[&str],
+    max_len: usize,
+    measure: &dyn Fn(&str) -> usize,
+) -> Vec<String> {
+    let whole = Block::render(open, lines);
+    if measure(&whole) <= max_len {
+        return vec![whole];
+    }
+    // Room left for code once the fences and their line breaks are counted
+    let overhead = measure(&Block::render(open, &[""]));
+    let Some(budget) = max_len.checked_sub(overhead).filter(|&b| b > 0) else {
+        return split_prose(&whole, max_len, measure, 0);
+    };
+
+    let mut packer = Packer::new(budget, measure, "\n");
+    for line in lines {
+        if measure(line) <= budget {
+            packer.push(line.to_string());
+        } else {
+            for piece in hard_split(line, budget, measure) {
+                packer.push(piece);
+            }
+        }
+    }
+    packer
+        .finish()
+        .into_iter()
+        .map(|part| Block::render(open, &[part.as_str()]))
+        .collect()
+}
+
+/// Sentences of `text`, each ending at `.`, `!` or `?` followed by whitespace
+fn sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if matches!(c, '.' | '!' | '?') && chars.peek().is_some_and(|(_, n)| n.is_whitespace()) {
+            let end = i + c.len_utf8();
+            sentences.push(text[start..end].trim());
+            start = end;
+        }
+    }
+    sentences.push(text[start..].trim());
+    sentences
+}
+
+/// Cut `text` into pieces of at most `max_len` regardless of word boundaries
+fn hard_split(text: &str, max_len: usize, measure: &dyn Fn(&str) -> usize) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    let mut current_len = 0;
+    let mut buf = [0; 4];
+    for c in text.chars() {
+        let len = measure(c.encode_utf8(&mut buf));
+        if current_len + len > max_len && !current.is_empty() {
+            pieces.push(std::mem::take(&mut current));
+            current_len = 0;
+        }
+        current.push(c);
+        current_len += len;
+    }
+    if !current.is_empty() {
+        pieces.push(current);
+    }
+    pieces
+}
+
+/// Greedily joins pieces with a separator into parts no longer than `max_len`
+struct Packer<'a> {
+    max_len: usize,
+    measure: &'a dyn Fn(&str) -> usize,
+    separator: &'a str,
+    parts: Vec<String>,
+    current: String,
+    current_len: usize,
+}
+
+impl<'a> Packer<'a> {
+    fn new(max_len: usize, measure: &'a dyn Fn(&str) -> usize, separator: &'a str) -> Self {
+        Self {
+            max_len,
+            measure,
+            separator,
+            parts: Vec::new(),
+            current: String::new(),
+            current_len: 0,
+        }
+    }
+
+    fn push(&mut self, piece: String) {
+        let len = (self.measure)(&piece);
+        if self.current.is_empty() {
+            self.current = piece;
+            self.current_len = len;
+            return;
+        }
+        let joined_len = self.current_len + (self.measure)(self.separator) + len;
+        if joined_len <= self.max_len {
+            self.current.push_str(self.separator);
+            self.current.push_str(&piece);
+            self.current_len = joined_len;
+        } else {
+            self.parts.push(std::mem::replace(&mut self.current, piece));
+            self.current_len = len;
+        }
+    }
+
+    fn finish(mut self) -> Vec<String> {
+        if !self.current.is_empty() {
+            self.parts.push(self.current);
+        }
+        self.parts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_message_short_and_empty() {
+        assert_eq!(split_message("hello", 10), vec!["hello"]);
+        assert!(split_message("  \n ", 10).is_empty());
+    }
+
+    #[test]
+    fn test_split_message_prefers_paragraphs_then_sentences() {
+        let text = "First paragraph here.\n\nSecond one. It has two sentences.";
+        assert_eq!(
+            split_message(text, 25),
+            vec![
+                "First paragraph here.",
+                "Second one.",
+                "It has two sentences."
+            ]
+        );
+        assert_eq!(
+            split_message(text, 60),
+            vec!["First paragraph here.\n\nSecond one. It has two sentences."]
+        );
+        assert_eq!(
+            split_message("alpha beta gamma delta", 11),
+            vec!["alpha beta", "gamma delta"]
+        );
+    }
+
+    #[test]
+    fn test_split_message_word_longer_than_max() {
+        let chunks = split_message("tiny supercalifragilistic end", 10);
+        assert_eq!(chunks, vec!["tiny", "supercalif", "ragilistic", "end"]);
+        assert!(chunks.iter().all(|c| c.chars().count() <= 10));
+    }
+
+    #[test]
+    fn test_split_message_code_block_spanning_limit() {
+        let text = "Here is the fix:\n\n```rust\nlet a = 1;\nlet b = 2;\nlet c = 3;\n```\n\nDone.";
+        let chunks = split_message(text, 30);
+        assert_eq!(
+            chunks,
+            vec![
+                "Here is the fix:",
+                "```rust\nlet a = 1;\n```",
+                "```rust\nlet b = 2;\n```",
+                "```rust\nlet c = 3;\n```\n\nDone.",
+            ]
+        );
+        assert!(chunks.iter().all(|c| c.chars().count() <= 30));
+        // Every part has balanced fences
+        assert!(chunks.iter().all(|c| c.matches(FENCE).count() % 2 == 0));
+    }
+
+    #[test]
+    fn test_split_message_keeps_code_block_whole_when_it_fits() {
+        let text = "Intro line that is long enough.\n\n```\nfn main() {\n\n}\n```";
+        assert_eq!(
+            split_message(text, 40),
+            vec![
+                "Intro line that is long enough.",
+                "```\nfn main() {\n\n}\n```"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_message_by_custom_measure() {
+        // Each emoji is two UTF-16 units
+        let chunks = split_message_by("😀😀😀", 4, |s| s.encode_utf16().count());
+        assert_eq!(chunks, vec!["😀😀", "😀"]);
+    }
+}
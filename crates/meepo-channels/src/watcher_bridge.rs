@@ -0,0 +1,204 @@
+//! Delivers watcher events straight to the channel each watcher replies on
+//!
+//! [`WatcherBridge`] consumes the `WatcherEvent` stream from
+//! `meepo-scheduler`, looks up the emitting watcher's `reply_channel`, and
+//! sends the event text through a [`BusSender`] without involving the agent.
+
+use crate::bus::BusSender;
+use anyhow::{Context, Result, anyhow};
+use meepo_core::types::{ChannelType, MessageKind, OutgoingMessage};
+use meepo_scheduler::{WatcherEvent, get_watcher_by_id};
+use rusqlite::Connection;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use tracing::{debug, error, info};
+
+/// A watcher's `reply_channel` doesn't name any channel
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "unknown reply channel '{0}' (expected discord, slack, telegram, imessage, email, alexa, reminders, notes, contacts, or internal)"
+)]
+pub struct UnknownReplyChannel(pub String);
+
+/// Map a watcher's `reply_channel` to the channel it names.
+///
+/// Anything after the first `-`, `:` or `#` is a destination label within the
+/// channel and is ignored, so "slack-alerts" and "slack:#finance" both map to
+/// Slack. Unlike `ChannelType::from_string`, names that match no channel are
+/// an error rather than silently becoming `Internal`.
+pub fn reply_channel_type(reply_channel: &str) -> Result<ChannelType, UnknownReplyChannel> {
+    let name = reply_channel
+        .split(['-', ':', '#'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+    match name.as_str() {
+        "discord" => Ok(ChannelType::Discord),
+        "slack" => Ok(ChannelType::Slack),
+        "telegram" => Ok(ChannelType::Telegram),
+        "imessage" => Ok(ChannelType::IMessage),
+        "email" => Ok(ChannelType::Email),
+        "alexa" => Ok(ChannelType::Alexa),
+        "reminders" => Ok(ChannelType::Reminders),
+        "notes" => Ok(ChannelType::Notes),
+        "contacts" => Ok(ChannelType::Contacts),
+        "internal" => Ok(ChannelType::Internal),
+        _ => Err(UnknownReplyChannel(reply_channel.to_string())),
+    }
+}
+
+/// Turns watcher events into outgoing messages on each watcher's reply channel
+pub struct WatcherBridge {
+    sender: Arc<BusSender>,
+    db: Arc<Mutex<Connection>>,
+}
+
+impl WatcherBridge {
+    /// `db` is the scheduler database the watchers are persisted in
+    pub fn new(sender: Arc<BusSender>, db: Arc<Mutex<Connection>>) -> Self {
+        Self { sender, db }
+    }
+
+    /// Build the message for `event`, addressed to its watcher's reply channel
+    pub fn to_outgoing(&self, event: &WatcherEvent) -> Result<OutgoingMessage> {
+        let watcher = {
+            let conn = self
+                .db
+                .lock()
+                .map_err(|_| anyhow!("scheduler database lock poisoned"))?;
+            get_watcher_by_id(&conn, &event.watcher_id)?
+        }
+        .with_context(|| format!("watcher {} not found", event.watcher_id))?;
+        let channel = reply_channel_type(&watcher.reply_channel)
+            .with_context(|| format!("watcher {}", event.watcher_id))?;
+
+        Ok(OutgoingMessage {
+            content: event.to_string(),
+            channel,
+            reply_to: None,
+            thread_id: None,
+            kind: MessageKind::Response,
+        })
+    }
+
+    /// Deliver one event to its watcher's reply channel
+    pub async fn forward(&self, event: &WatcherEvent) -> Result<()> {
+        let msg = self.to_outgoing(event)?;
+        debug!(
+            "Delivering {} from watcher {} to {}",
+            event.kind(),
+            event.watcher_id,
+            msg.channel
+        );
+        self.sender.send(msg).await
+    }
+
+    /// Deliver every event from `rx` until the stream ends, logging failures
+    pub async fn run(self, mut rx: mpsc::UnboundedReceiver<WatcherEvent>) {
+        info!("Watcher bridge started");
+        while let Some(event) = rx.recv().await {
+            if let Err(e) = self.forward(&event).await {
+                error!(
+                    "Failed to deliver {} from watcher {}: {:#}",
+                    event.kind(),
+                    event.watcher_id,
+                    e
+                );
+            }
+        }
+        info!("Watcher bridge stopped");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::{IncomingSender, MessageBus, MessageChannel};
+    use async_trait::async_trait;
+    use meepo_scheduler::{Watcher, WatcherKind, init_watcher_tables, save_watcher};
+
+    /// Records what it was asked to send
+    struct RecordingChannel {
+        sent: Arc<Mutex<Vec<OutgoingMessage>>>,
+    }
+
+    #[async_trait]
+    impl MessageChannel for RecordingChannel {
+        async fn start(&self, _tx: IncomingSender) -> Result<()> {
+            Ok(())
+        }
+
+        async fn send(&self, msg: OutgoingMessage) -> Result<()> {
+            self.sent.lock().unwrap().push(msg);
+            Ok(())
+        }
+
+        fn channel_type(&self) -> ChannelType {
+            ChannelType::Slack
+        }
+    }
+
+    fn watcher(reply_channel: &str) -> Watcher {
+        Watcher::new(
+            WatcherKind::OneShot {
+                at: chrono::Utc::now(),
+                task: "stand-up".to_string(),
+            },
+            "Remind me".to_string(),
+            reply_channel.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_reply_channel_type() {
+        assert_eq!(reply_channel_type("slack"), Ok(ChannelType::Slack));
+        assert_eq!(reply_channel_type("Slack-alerts"), Ok(ChannelType::Slack));
+        assert_eq!(reply_channel_type("discord:#ops"), Ok(ChannelType::Discord));
+        assert_eq!(reply_channel_type("iMessage"), Ok(ChannelType::IMessage));
+        assert_eq!(reply_channel_type("internal"), Ok(ChannelType::Internal));
+        assert_eq!(
+            reply_channel_type("webhook"),
+            Err(UnknownReplyChannel("webhook".to_string()))
+        );
+        assert!(reply_channel_type("").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_forward_routes_to_reply_channel() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_watcher_tables(&conn).unwrap();
+        let slack_watcher = watcher("slack-alerts");
+        let webhook_watcher = watcher("webhook");
+        save_watcher(&conn, &slack_watcher).unwrap();
+        save_watcher(&conn, &webhook_watcher).unwrap();
+
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let mut bus = MessageBus::new(8, 8);
+        bus.register(Box::new(RecordingChannel { sent: sent.clone() }));
+        let (_rx, sender) = bus.split();
+        let bridge = WatcherBridge::new(Arc::new(sender), Arc::new(Mutex::new(conn)));
+
+        let event = WatcherEvent::task(slack_watcher.id.clone(), "stand-up".to_string());
+        bridge.forward(&event).await.unwrap();
+        let sent = sent.lock().unwrap().clone();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].channel, ChannelType::Slack);
+        assert_eq!(sent[0].content, event.to_string());
+
+        let err = bridge
+            .forward(&WatcherEvent::task(
+                webhook_watcher.id.clone(),
+                "x".to_string(),
+            ))
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("unknown reply channel 'webhook'"));
+
+        let err = bridge
+            .forward(&WatcherEvent::task("missing".to_string(), "x".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "watcher missing not found");
+    }
+}
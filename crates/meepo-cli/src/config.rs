@@ -14,6 +14,10 @@ pub struct MeepoConfig {
     pub memory: MemoryConfig,
     #[serde(default)]
     pub filesystem: FilesystemConfig,
+    #[serde(default)]
+    pub commands: CommandsConfig,
+    #[serde(default)]
+    pub tool_cache: ToolCacheConfig,
     #[serde(default = "default_orchestrator_config")]
     pub orchestrator: OrchestratorConfig,
     #[serde(default = "default_autonomy_config")]
@@ -32,6 +36,10 @@ pub struct MeepoConfig {
     pub usage: UsageCliConfig,
     #[serde(default)]
     pub gateway: GatewayConfig,
+    #[serde(default)]
+    pub imap: ImapConfig,
+    #[serde(default)]
+    pub linux: LinuxConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,6 +50,9 @@ pub struct AgentConfig {
     pub system_prompt_file: String,
     #[serde(default = "default_memory_file")]
     pub memory_file: String,
+    /// Launch the mail and calendar apps at startup so the first call is fast
+    #[serde(default)]
+    pub prewarm_providers: bool,
 }
 
 fn default_system_prompt_file() -> String {
@@ -193,6 +204,8 @@ impl std::fmt::Debug for TavilyConfig {
 pub struct ChannelsConfig {
     pub discord: DiscordConfig,
     pub slack: SlackConfig,
+    #[serde(default)]
+    pub telegram: TelegramConfig,
     pub imessage: IMessageConfig,
     #[serde(default)]
     pub email: EmailConfig,
@@ -204,6 +217,142 @@ pub struct ChannelsConfig {
     pub notes: NotesConfig,
     #[serde(default)]
     pub contacts: ContactsConfig,
+    /// How many times to restart channels after every listener has died (0 = never)
+    #[serde(default = "default_max_channel_restarts")]
+    pub max_restarts: u32,
+    #[serde(default)]
+    pub dedup: ChannelDedupConfig,
+    #[serde(default)]
+    pub retry: ChannelRetryConfig,
+    #[serde(default)]
+    pub rate_limit: ChannelRateLimitConfig,
+}
+
+fn default_max_channel_restarts() -> u32 {
+    3
+}
+
+/// Drop duplicate incoming messages (redeliveries, cross-posts) before the agent sees them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelDedupConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// How long a message fingerprint is remembered
+    #[serde(default = "default_dedup_window_secs")]
+    pub window_secs: u64,
+    /// "sender_content", "sender_content_channel", or "content"
+    #[serde(default)]
+    pub key: meepo_channels::DedupKey,
+}
+
+fn default_dedup_window_secs() -> u64 {
+    60
+}
+
+impl Default for ChannelDedupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window_secs: default_dedup_window_secs(),
+            key: meepo_channels::DedupKey::default(),
+        }
+    }
+}
+
+/// Retry failed outgoing sends with exponential backoff before dead-lettering them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelRetryConfig {
+    /// Retries after the first attempt (0 = never retry). Only transient
+    /// errors are retried, and a retry resends every chunk of a long reply.
+    #[serde(default = "default_retry_max_retries")]
+    pub max_retries: u32,
+    #[serde(default = "default_retry_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    #[serde(default = "default_retry_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+    /// Also retry "Thinking..." acknowledgments
+    #[serde(default)]
+    pub retry_acknowledgments: bool,
+}
+
+fn default_retry_max_retries() -> u32 {
+    0
+}
+
+fn default_retry_initial_backoff_ms() -> u64 {
+    500
+}
+
+fn default_retry_max_backoff_ms() -> u64 {
+    10_000
+}
+
+impl Default for ChannelRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: default_retry_max_retries(),
+            initial_backoff_ms: default_retry_initial_backoff_ms(),
+            max_backoff_ms: default_retry_max_backoff_ms(),
+            retry_acknowledgments: false,
+        }
+    }
+}
+
+impl ChannelRetryConfig {
+    pub fn policy(&self) -> meepo_channels::RetryPolicy {
+        let policy = meepo_channels::RetryPolicy::new(
+            self.max_retries,
+            std::time::Duration::from_millis(self.initial_backoff_ms),
+            std::time::Duration::from_millis(self.max_backoff_ms),
+        );
+        if self.retry_acknowledgments {
+            policy
+        } else {
+            policy.without_retry_for(meepo_core::types::MessageKind::Acknowledgment)
+        }
+    }
+}
+
+/// Throttle outgoing sends with a token bucket per channel and recipient
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChannelRateLimitConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Bucket for channels without their own entry (none = unlimited)
+    #[serde(default)]
+    pub default: Option<SendBucketConfig>,
+    /// Buckets per channel, keyed by channel name ("slack", "discord", "email", ...)
+    #[serde(default)]
+    pub channels: std::collections::HashMap<meepo_core::types::ChannelType, SendBucketConfig>,
+}
+
+/// `capacity` sends per `per_secs` seconds, refilled smoothly
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SendBucketConfig {
+    pub capacity: u32,
+    pub per_secs: u64,
+}
+
+impl SendBucketConfig {
+    fn bucket(&self) -> meepo_channels::BucketConfig {
+        meepo_channels::BucketConfig::per(
+            self.capacity,
+            std::time::Duration::from_secs(self.per_secs.max(1)),
+        )
+    }
+}
+
+impl ChannelRateLimitConfig {
+    pub fn limiter(&self) -> meepo_channels::RateLimiter {
+        let mut limiter = meepo_channels::RateLimiter::for_sends();
+        if let Some(default) = &self.default {
+            limiter = limiter.with_default_limit(default.bucket());
+        }
+        for (channel, bucket) in &self.channels {
+            limiter = limiter.with_channel_limit(channel.clone(), bucket.bucket());
+        }
+        limiter
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -214,6 +363,9 @@ pub struct DiscordConfig {
     pub token: String,
     #[serde(default)]
     pub allowed_users: Vec<String>,
+    /// Slash commands registered on startup
+    #[serde(default)]
+    pub slash_commands: Vec<meepo_channels::DiscordSlashCommand>,
 }
 
 impl std::fmt::Debug for DiscordConfig {
@@ -222,6 +374,7 @@ impl std::fmt::Debug for DiscordConfig {
             .field("enabled", &self.enabled)
             .field("token", &mask_secret(&self.token))
             .field("allowed_users", &self.allowed_users)
+            .field("slash_commands", &self.slash_commands)
             .finish()
     }
 }
@@ -249,6 +402,46 @@ impl std::fmt::Debug for SlackConfig {
     }
 }
 
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TelegramConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub bot_token: String,
+    /// How long each getUpdates long poll waits for new messages
+    #[serde(default = "default_telegram_poll_timeout")]
+    pub poll_timeout_secs: u64,
+    /// Chat IDs allowed to talk to the agent; the channel won't start while this is empty
+    #[serde(default)]
+    pub allowed_chats: Vec<String>,
+}
+
+fn default_telegram_poll_timeout() -> u64 {
+    30
+}
+
+impl Default for TelegramConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bot_token: String::new(),
+            poll_timeout_secs: default_telegram_poll_timeout(),
+            allowed_chats: Vec::new(),
+        }
+    }
+}
+
+impl std::fmt::Debug for TelegramConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TelegramConfig")
+            .field("enabled", &self.enabled)
+            .field("bot_token", &mask_secret(&self.bot_token))
+            .field("poll_timeout_secs", &self.poll_timeout_secs)
+            .field("allowed_chats", &self.allowed_chats)
+            .finish()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IMessageConfig {
     #[serde(default)]
@@ -257,6 +450,9 @@ pub struct IMessageConfig {
     pub poll_interval_secs: u64,
     #[serde(default)]
     pub allowed_contacts: Vec<String>,
+    /// Seconds to wait for a delivery receipt in chat.db after sending (0 = don't confirm)
+    #[serde(default)]
+    pub delivery_timeout_secs: u64,
 }
 
 fn default_poll_interval() -> u64 {
@@ -337,6 +533,21 @@ pub struct RemindersConfig {
     pub poll_interval_secs: u64,
     #[serde(default = "default_reminders_list_name")]
     pub list_name: String,
+    /// Lists to poll for incoming reminders (empty = just `list_name`)
+    #[serde(default)]
+    pub watch_lists: Vec<String>,
+    /// Leave picked-up reminders incomplete and track them in ~/.meepo/reminders_seen.db
+    #[serde(default)]
+    pub keep_incomplete: bool,
+    /// How replies are split into title and body: "first_line", "first_sentence", or "title_prefix"
+    #[serde(default = "default_reminders_title_mode")]
+    pub title_mode: String,
+    /// Keep the full reply in the body and use the title only as a short summary
+    #[serde(default)]
+    pub full_body: bool,
+    /// Convert markdown replies to clean text
+    #[serde(default = "default_true")]
+    pub strip_markdown: bool,
 }
 
 fn default_reminders_poll_interval() -> u64 {
@@ -347,12 +558,21 @@ fn default_reminders_list_name() -> String {
     "Meepo".to_string()
 }
 
+fn default_reminders_title_mode() -> String {
+    "first_line".to_string()
+}
+
 impl Default for RemindersConfig {
     fn default() -> Self {
         Self {
             enabled: false,
             poll_interval_secs: default_reminders_poll_interval(),
             list_name: default_reminders_list_name(),
+            watch_lists: Vec::new(),
+            keep_incomplete: false,
+            title_mode: default_reminders_title_mode(),
+            full_body: false,
+            strip_markdown: true,
         }
     }
 }
@@ -424,6 +644,40 @@ impl Default for ContactsConfig {
 pub struct KnowledgeConfig {
     pub db_path: String,
     pub tantivy_path: String,
+    #[serde(default)]
+    pub relations: RelationVocabularyConfig,
+}
+
+/// Controlled vocabulary for relationship types written to the knowledge graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelationVocabularyConfig {
+    /// Allowed relation types; empty means no restriction
+    #[serde(default)]
+    pub types: Vec<String>,
+    /// Variant -> canonical type (e.g. "coworker" = "works_with")
+    #[serde(default)]
+    pub synonyms: std::collections::HashMap<String, String>,
+    /// "reject" or "snap" (use the most similar allowed type)
+    #[serde(default)]
+    pub unknown: meepo_knowledge::UnknownRelation,
+    /// Minimum trigram similarity for snapping
+    #[serde(default = "default_relation_snap_threshold")]
+    pub snap_threshold: f32,
+}
+
+fn default_relation_snap_threshold() -> f32 {
+    meepo_knowledge::DEFAULT_SNAP_THRESHOLD
+}
+
+impl Default for RelationVocabularyConfig {
+    fn default() -> Self {
+        Self {
+            types: Vec::new(),
+            synonyms: std::collections::HashMap::new(),
+            unknown: meepo_knowledge::UnknownRelation::default(),
+            snap_threshold: default_relation_snap_threshold(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -433,6 +687,28 @@ pub struct WatchersConfig {
     #[serde(default = "default_min_poll")]
     pub min_poll_interval_secs: u64,
     pub active_hours: ActiveHours,
+    /// Days of watcher execution history to keep
+    #[serde(default = "default_history_retention_days")]
+    pub history_retention_days: u32,
+    /// Random spread applied to polling intervals (0.0 = none, 0.1 = ±10%)
+    #[serde(default)]
+    pub jitter_fraction: f64,
+    /// Failed polls in a row before a watcher is auto-disabled (0 = never)
+    #[serde(default = "default_max_consecutive_failures")]
+    pub max_consecutive_failures: u32,
+    /// Polls allowed to run at once across all watchers; the rest wait (0 = no cap)
+    #[serde(default = "default_max_concurrent_polls")]
+    pub max_concurrent_polls: usize,
+    /// Channel to alert when a watcher is auto-disabled (e.g. "slack"); unset = log only
+    #[serde(default)]
+    pub alert_channel: Option<String>,
+    /// Send each watcher event straight to the watcher's reply channel
+    /// instead of handing it to the agent
+    #[serde(default)]
+    pub direct_delivery: bool,
+    /// TOML or JSON file of watcher definitions, synced into the database at startup
+    #[serde(default)]
+    pub file: Option<String>,
 }
 
 fn default_max_concurrent() -> usize {
@@ -443,6 +719,18 @@ fn default_min_poll() -> u64 {
     30
 }
 
+fn default_history_retention_days() -> u32 {
+    30
+}
+
+fn default_max_consecutive_failures() -> u32 {
+    0
+}
+
+fn default_max_concurrent_polls() -> usize {
+    4
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActiveHours {
     pub start: String,
@@ -474,6 +762,17 @@ fn default_workspace() -> String {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryConfig {
     pub workspace: String,
+    /// Recent turns per channel kept in memory and injected into the prompt in place of
+    /// stored history once a channel has some (0 = always use stored history)
+    #[serde(default = "default_conversation_window")]
+    pub conversation_window: usize,
+    /// Persist the per-channel conversation window to the workspace across restarts
+    #[serde(default)]
+    pub persist_conversations: bool,
+}
+
+fn default_conversation_window() -> usize {
+    0
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -494,6 +793,81 @@ impl Default for FilesystemConfig {
     }
 }
 
+/// Settings for the `run_command` tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandsConfig {
+    /// Program names `run_command` may execute (bare names, resolved via PATH)
+    #[serde(default = "default_allowed_commands")]
+    pub allowed: Vec<String>,
+    /// Maximum run time per command in seconds
+    #[serde(default = "default_command_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_allowed_commands() -> Vec<String> {
+    meepo_core::tools::system::DEFAULT_ALLOWED_COMMANDS
+        .iter()
+        .map(|c| c.to_string())
+        .collect()
+}
+
+fn default_command_timeout_secs() -> u64 {
+    30
+}
+
+impl Default for CommandsConfig {
+    fn default() -> Self {
+        Self {
+            allowed: default_allowed_commands(),
+            timeout_secs: default_command_timeout_secs(),
+        }
+    }
+}
+
+/// Short-lived caching of read-only tool results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolCacheConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// How long a cached result stays valid, in seconds
+    #[serde(default = "default_tool_cache_ttl_secs")]
+    pub ttl_secs: u64,
+    /// Maximum cached results per tool
+    #[serde(default = "default_tool_cache_max_entries")]
+    pub max_entries: usize,
+    /// Tools whose results are cached (only read-only tools are ever cached).
+    /// A call to any other side-effecting tool clears the cache, since it may have changed the data.
+    #[serde(default = "default_cached_tools")]
+    pub tools: Vec<String>,
+}
+
+fn default_tool_cache_ttl_secs() -> u64 {
+    60
+}
+
+fn default_tool_cache_max_entries() -> usize {
+    128
+}
+
+fn default_cached_tools() -> Vec<String> {
+    vec![
+        "read_calendar".to_string(),
+        "read_emails".to_string(),
+        "search_knowledge".to_string(),
+    ]
+}
+
+impl Default for ToolCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl_secs: default_tool_cache_ttl_secs(),
+            max_entries: default_tool_cache_max_entries(),
+            tools: default_cached_tools(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrchestratorConfig {
     #[serde(default = "default_max_concurrent_subtasks")]
@@ -750,6 +1124,76 @@ impl Default for BrowserConfig {
     }
 }
 
+// ── IMAP Email Config ───────────────────────────────────────────
+
+/// Mail server backing the email tools and email watchers. When enabled it
+/// replaces Mail.app/Outlook, and it is the only email option on Linux.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct ImapConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// e.g. "imaps://imap.example.com"
+    #[serde(default)]
+    pub imap_url: String,
+    /// e.g. "smtps://smtp.example.com:465" or "smtp://smtp.example.com:587" (STARTTLS)
+    #[serde(default)]
+    pub smtp_url: String,
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    pub password: String,
+    /// From address for outgoing mail (defaults to username)
+    #[serde(default)]
+    pub from: Option<String>,
+}
+
+impl std::fmt::Debug for ImapConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ImapConfig")
+            .field("enabled", &self.enabled)
+            .field("imap_url", &self.imap_url)
+            .field("smtp_url", &self.smtp_url)
+            .field("username", &self.username)
+            .field("password", &mask_secret(&self.password))
+            .field("from", &self.from)
+            .finish()
+    }
+}
+
+// ── Linux Platform Config ───────────────────────────────────────
+
+/// Calendar server backing the calendar tools on Linux, where there is no
+/// local Calendar app to script
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LinuxConfig {
+    #[serde(default)]
+    pub calendar: LinuxCalendarConfig,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct LinuxCalendarConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// CalDAV calendar collection URL
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    pub password: String,
+}
+
+impl std::fmt::Debug for LinuxCalendarConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LinuxCalendarConfig")
+            .field("enabled", &self.enabled)
+            .field("url", &self.url)
+            .field("username", &self.username)
+            .field("password", &mask_secret(&self.password))
+            .finish()
+    }
+}
+
 // ── Gateway Config ──────────────────────────────────────────────
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -844,7 +1288,7 @@ impl Default for UsageCliConfig {
 pub struct NotificationsConfig {
     #[serde(default)]
     pub enabled: bool,
-    /// Channel to send notifications to (e.g., "imessage", "discord", "slack")
+    /// Channel to send notifications to (e.g., "imessage", "discord", "slack", "telegram")
     #[serde(default = "default_notify_channel")]
     pub channel: String,
     /// Notify when a background task starts
@@ -1013,6 +1457,28 @@ impl MeepoConfig {
             );
         }
 
+        if !config.channels.telegram.bot_token.is_empty()
+            && !config.channels.telegram.bot_token.contains("${")
+        {
+            warn!(
+                "Telegram bot token is hardcoded in config file. For security, use environment variables: bot_token = \"${{TELEGRAM_BOT_TOKEN}}\""
+            );
+        }
+
+        if !config.imap.password.is_empty() && !config.imap.password.contains("${") {
+            warn!(
+                "Email password is hardcoded in config file. For security, use environment variables: password = \"${{EMAIL_PASSWORD}}\""
+            );
+        }
+
+        if !config.linux.calendar.password.is_empty()
+            && !config.linux.calendar.password.contains("${")
+        {
+            warn!(
+                "CalDAV password is hardcoded in config file. For security, use environment variables: password = \"${{CALDAV_PASSWORD}}\""
+            );
+        }
+
         Ok(config)
     }
 }
@@ -1027,11 +1493,14 @@ const ALLOWED_ENV_VARS: &[&str] = &[
     "TAVILY_API_KEY",
     "DISCORD_BOT_TOKEN",
     "SLACK_BOT_TOKEN",
+    "TELEGRAM_BOT_TOKEN",
     "A2A_AUTH_TOKEN",
     "OPENCLAW_A2A_TOKEN",
     "GITHUB_TOKEN",
     "MEEPO_GATEWAY_TOKEN",
     "ELEVENLABS_API_KEY",
+    "EMAIL_PASSWORD",
+    "CALDAV_PASSWORD",
     "HOME",
     "USER",
 ];
@@ -32,7 +32,11 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Start the Meepo daemon
-    Start,
+    Start {
+        /// Safe mode: only read-only tools run and channels only receive
+        #[arg(long)]
+        safe: bool,
+    },
 
     /// Stop a running Meepo daemon
     Stop,
@@ -120,7 +124,7 @@ async fn main() -> Result<()> {
         Commands::Init => cmd_init().await,
         Commands::Setup => cmd_setup().await,
         Commands::Config => cmd_config(&cli.config).await,
-        Commands::Start => cmd_start(&cli.config).await,
+        Commands::Start { safe } => cmd_start(&cli.config, safe).await,
         Commands::Stop => cmd_stop().await,
         Commands::Ask { message } => cmd_ask(&cli.config, &message).await,
         Commands::McpServer => cmd_mcp_server(&cli.config).await,
@@ -284,7 +288,7 @@ async fn cmd_setup() -> Result<()> {
         // ── 4a: Accessibility ───────────────────────────────────
         setup_substep(
             "4a",
-            "Accessibility (for UI automation: click_element, type_text, read_screen)",
+            "Accessibility (for UI automation: find_element, click_element, click_at, type_text, read_screen)",
         );
         println!("  This lets Meepo read and interact with UI elements on screen.");
         println!();
@@ -922,10 +926,15 @@ async fn cmd_config(config_path: &Option<PathBuf>) -> Result<()> {
     Ok(())
 }
 
-async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
+async fn cmd_start(config_path: &Option<PathBuf>, safe: bool) -> Result<()> {
     let cfg = MeepoConfig::load(config_path)?;
     info!("Starting Meepo daemon...");
 
+    if safe {
+        meepo_core::SafeMode::global().enable();
+        warn!("Safe mode enabled: only read-only tools run and channel sends are disabled");
+    }
+
     let cancel = CancellationToken::new();
 
     // Initialize knowledge database and graph
@@ -944,8 +953,13 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
 
     // Use the graph's internal DB to avoid duplicate SQLite connections to the same file
     let db = knowledge_graph.db();
+    db.set_relation_vocabulary(relation_vocabulary(&cfg.knowledge.relations));
     info!("Knowledge database and Tantivy index initialized");
 
+    // Initialize scheduler database (kept alive for runtime persistence)
+    let sched_db = Arc::new(std::sync::Mutex::new(rusqlite::Connection::open(&db_path)?));
+    meepo_scheduler::persistence::init_watcher_tables(&sched_db.lock().unwrap())?;
+
     // Load SOUL and MEMORY
     let workspace = shellexpand(&cfg.memory.workspace);
     let soul = meepo_knowledge::load_soul(workspace.join(&cfg.agent.system_prompt_file))
@@ -1070,44 +1084,62 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
     let (bg_task_tx, mut bg_task_rx) =
         tokio::sync::mpsc::channel::<meepo_core::tools::autonomous::BackgroundTaskCommand>(100);
 
+    // An IMAP account replaces the platform mail app for email tools and watchers
+    let imap_email = configure_imap_email(&cfg.imap);
+
     // Build tool registry
     let mut registry = meepo_core::tools::ToolRegistry::new();
     // Email, calendar, and UI automation tools require macOS or Windows platform support
+    // (Linux gets email and calendar from configured servers, registered below)
     #[cfg(any(target_os = "macos", target_os = "windows"))]
     {
-        registry.register(Arc::new(meepo_core::tools::macos::ReadEmailsTool::new()));
-        registry.register(Arc::new(meepo_core::tools::macos::ReadCalendarTool::new()));
-        registry.register(Arc::new(meepo_core::tools::macos::SendEmailTool::new()));
-        registry.register(Arc::new(meepo_core::tools::macos::CreateEventTool::new()));
+        registry.register(Arc::new(meepo_core::tools::macos::ReadEmailsTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::ReadCalendarTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::ListCalendarsTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::SendEmailTool::new(
+            cfg.filesystem.allowed_directories.clone(),
+        )))?;
+        registry.register(Arc::new(meepo_core::tools::macos::CreateEventTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::UpdateEventTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::DeleteEventTool::new()))?;
         registry.register(Arc::new(
             meepo_core::tools::accessibility::ReadScreenTool::new(),
-        ));
+        ))?;
+        registry.register(Arc::new(
+            meepo_core::tools::accessibility::FindElementTool::new(),
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::accessibility::ClickElementTool::new(),
-        ));
+        ))?;
+        registry.register(Arc::new(
+            meepo_core::tools::accessibility::ClickAtTool::new(),
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::accessibility::TypeTextTool::new(),
-        ));
-    }
-    // Clipboard and app launcher are cross-platform (arboard + open crates)
-    registry.register(Arc::new(meepo_core::tools::macos::OpenAppTool::new()));
-    registry.register(Arc::new(meepo_core::tools::macos::GetClipboardTool::new()));
-    // macOS-only tools: Reminders, Notes, Notifications, Screen Capture, Music, Contacts
+        ))?;
+    }
+    // Clipboard, app launcher, notifications, and screen capture are cross-platform
+    // (arboard, open and notify-rust crates, the desktop screenshot tool; AppleScript/screencapture on macOS)
+    registry.register(Arc::new(meepo_core::tools::macos::OpenAppTool::new()))?;
+    registry.register(Arc::new(meepo_core::tools::macos::GetClipboardTool::new()))?;
+    registry.register(Arc::new(meepo_core::tools::macos::SetClipboardTool::new()))?;
+    registry.register(Arc::new(
+        meepo_core::tools::macos::SendNotificationTool::new(),
+    ))?;
+    registry.register(Arc::new(meepo_core::tools::macos::ScreenCaptureTool::new()))?;
+    // macOS-only tools: Reminders, Notes, Music, Contacts
     #[cfg(target_os = "macos")]
     {
-        registry.register(Arc::new(meepo_core::tools::macos::ListRemindersTool::new()));
-        registry.register(Arc::new(meepo_core::tools::macos::CreateReminderTool::new()));
-        registry.register(Arc::new(meepo_core::tools::macos::ListNotesTool::new()));
-        registry.register(Arc::new(meepo_core::tools::macos::CreateNoteTool::new()));
-        registry.register(Arc::new(
-            meepo_core::tools::macos::SendNotificationTool::new(),
-        ));
-        registry.register(Arc::new(meepo_core::tools::macos::ScreenCaptureTool::new()));
+        registry.register(Arc::new(meepo_core::tools::macos::ListRemindersTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::CreateReminderTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::ListNotesTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::CreateNoteTool::new()))?;
         registry.register(Arc::new(
             meepo_core::tools::macos::GetCurrentTrackTool::new(),
-        ));
-        registry.register(Arc::new(meepo_core::tools::macos::MusicControlTool::new()));
-        registry.register(Arc::new(meepo_core::tools::macos::SearchContactsTool::new()));
+        ))?;
+        registry.register(Arc::new(meepo_core::tools::macos::MusicControlTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::SearchContactsTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::LookupContactTool::new()))?;
     }
     // Browser automation tools (macOS: Safari/Chrome via AppleScript)
     #[cfg(target_os = "macos")]
@@ -1115,37 +1147,37 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
         let browser = &cfg.browser.default_browser;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserListTabsTool::new(browser),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserOpenTabTool::new(browser),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserCloseTabTool::new(browser),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserSwitchTabTool::new(browser),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserGetPageContentTool::new(browser),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserExecuteJsTool::new(browser),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserClickElementTool::new(browser),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserFillFormTool::new(browser),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserNavigateTool::new(browser),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserGetUrlTool::new(browser),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserScreenshotTool::new(browser),
-        ));
+        ))?;
         info!("Registered browser tools (browser: {})", browser);
     }
     let code_config = meepo_core::tools::code::CodeToolConfig {
@@ -1155,42 +1187,60 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
     };
     registry.register(Arc::new(meepo_core::tools::code::WriteCodeTool::new(
         code_config.clone(),
-    )));
+    )))?;
     registry.register(Arc::new(meepo_core::tools::code::MakePrTool::new(
         code_config.clone(),
-    )));
+    )))?;
     registry.register(Arc::new(meepo_core::tools::code::ReviewPrTool::new(
         code_config.clone(),
-    )));
+    )))?;
     registry.register(Arc::new(meepo_core::tools::code::SpawnClaudeCodeTool::new(
         code_config.clone(),
         db.clone(),
         bg_task_tx.clone(),
-    )));
+    )))?;
     registry.register(Arc::new(meepo_core::tools::memory::RememberTool::new(
         db.clone(),
-    )));
+    )))?;
     registry.register(Arc::new(meepo_core::tools::memory::RecallTool::new(
         db.clone(),
-    )));
+    )))?;
     // Use KnowledgeGraph for SearchKnowledgeTool to enable Tantivy full-text search
     registry.register(Arc::new(
         meepo_core::tools::memory::SearchKnowledgeTool::with_graph(knowledge_graph.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(meepo_core::tools::memory::LinkEntitiesTool::new(
         db.clone(),
-    )));
+    )))?;
+    registry.register(Arc::new(
+        meepo_core::tools::memory::UpdateEntityTool::with_graph(knowledge_graph.clone()),
+    ))?;
+    registry.register(Arc::new(meepo_core::tools::rag::GetRelatedTool::new(
+        db.clone(),
+    )))?;
+    registry.register(Arc::new(meepo_core::tools::memory::ForgetTool::with_graph(
+        knowledge_graph.clone(),
+    )))?;
+    registry.register(Arc::new(
+        meepo_core::tools::memory::FindDuplicatesTool::new(db.clone()),
+    ))?;
+    registry.register(Arc::new(
+        meepo_core::tools::memory::MergeEntitiesTool::with_graph(knowledge_graph.clone()),
+    ))?;
     // RAG-enhanced tools: GraphRAG-powered recall and document ingestion
     registry.register(Arc::new(meepo_core::tools::rag::SmartRecallTool::new(
         knowledge_graph.clone(),
         db.clone(),
-    )));
-    registry.register(Arc::new(
-        meepo_core::tools::rag::IngestDocumentTool::new(knowledge_graph.clone()),
-    ));
-    registry.register(Arc::new(meepo_core::tools::system::RunCommandTool));
-    registry.register(Arc::new(meepo_core::tools::system::ReadFileTool));
-    registry.register(Arc::new(meepo_core::tools::system::WriteFileTool));
+    )))?;
+    registry.register(Arc::new(meepo_core::tools::rag::IngestDocumentTool::new(
+        knowledge_graph.clone(),
+    )))?;
+    registry.register(Arc::new(meepo_core::tools::system::RunCommandTool::new(
+        cfg.commands.allowed.clone(),
+        cfg.commands.timeout_secs,
+    )))?;
+    registry.register(Arc::new(meepo_core::tools::system::ReadFileTool))?;
+    registry.register(Arc::new(meepo_core::tools::system::WriteFileTool))?;
     // Filesystem access tools — validate configured directories exist
     for dir in &cfg.filesystem.allowed_directories {
         let expanded = shellexpand(dir);
@@ -1206,198 +1256,232 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
         meepo_core::tools::filesystem::ListDirectoryTool::new(
             cfg.filesystem.allowed_directories.clone(),
         ),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::filesystem::SearchFilesTool::new(
             cfg.filesystem.allowed_directories.clone(),
         ),
-    ));
+    ))?;
     // BrowseUrlTool with optional Tavily extract
     if let Some(ref tavily) = tavily_client {
         registry.register(Arc::new(
             meepo_core::tools::system::BrowseUrlTool::with_tavily(tavily.clone()),
-        ));
+        ))?;
     } else {
-        registry.register(Arc::new(meepo_core::tools::system::BrowseUrlTool::new()));
+        registry.register(Arc::new(meepo_core::tools::system::BrowseUrlTool::new()))?;
     }
     // Register web_search tool if Tavily is available
     if let Some(ref tavily) = tavily_client {
         registry.register(Arc::new(meepo_core::tools::search::WebSearchTool::new(
             tavily.clone(),
-        )));
+        )))?;
     }
     registry.register(Arc::new(
         meepo_core::tools::watchers::CreateWatcherTool::new(db.clone(), watcher_command_tx.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::watchers::ListWatchersTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::watchers::CancelWatcherTool::new(db.clone(), watcher_command_tx.clone()),
-    ));
+    ))?;
+    registry.register(Arc::new(
+        meepo_core::tools::watchers::WatcherStateTool::new(sched_db.clone()),
+    ))?;
+    registry.register(Arc::new(
+        meepo_core::tools::watchers::PauseWatcherTool::new(sched_db.clone(), watcher_command_tx.clone()),
+    ))?;
+    registry.register(Arc::new(
+        meepo_core::tools::watchers::ResumeWatcherTool::new(sched_db.clone(), watcher_command_tx.clone()),
+    ))?;
+    registry.register(Arc::new(
+        meepo_core::tools::watchers::PreviewWatcherScheduleTool,
+    ))?;
     // Autonomous agent management tools
     registry.register(Arc::new(
         meepo_core::tools::autonomous::SpawnBackgroundTaskTool::new(db.clone(), bg_task_tx.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::autonomous::AgentStatusTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(meepo_core::tools::autonomous::StopTaskTool::new(
         db.clone(),
         watcher_command_tx.clone(),
         bg_task_tx.clone(),
-    )));
+    )))?;
     // ── Lifestyle Integration Tools ──────────────────────────────
     // Phase 1: Email Intelligence (macOS/Windows only — needs email provider)
     #[cfg(any(target_os = "macos", target_os = "windows"))]
     {
         registry.register(Arc::new(
             meepo_core::tools::lifestyle::email_intelligence::EmailTriageTool::new(db.clone()),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::lifestyle::email_intelligence::EmailDraftReplyTool::new(db.clone()),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::lifestyle::email_intelligence::EmailSummarizeThreadTool::new(),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::lifestyle::email_intelligence::EmailUnsubscribeTool::new(),
-        ));
+        ))?;
     }
     // Phase 1: Smart Calendar (macOS/Windows only — needs calendar provider)
     #[cfg(any(target_os = "macos", target_os = "windows"))]
     {
         registry.register(Arc::new(
             meepo_core::tools::lifestyle::calendar::FindFreeTimeTool::new(),
-        ));
+        ))?;
+        registry.register(Arc::new(
+            meepo_core::tools::lifestyle::calendar::FindFreeSlotsTool::new(),
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::lifestyle::calendar::ScheduleMeetingTool::new(),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::lifestyle::calendar::RescheduleEventTool::new(),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::lifestyle::calendar::DailyBriefingTool::new(db.clone()),
-        ));
+        ))?;
+        registry.register(Arc::new(
+            meepo_core::tools::lifestyle::travel::ExtractItineraryTool::new(db.clone()),
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::lifestyle::calendar::WeeklyReviewTool::new(db.clone()),
-        ));
-    }
+        ))?;
+    }
+    // Email and calendar on Linux go through configured IMAP/SMTP and CalDAV servers
+    #[cfg(target_os = "linux")]
+    register_linux_platform_tools(
+        &mut registry,
+        &cfg.linux,
+        &cfg.filesystem.allowed_directories,
+        &db,
+    )?;
     // Phase 1: Deep Research (cross-platform — uses Tavily + knowledge graph)
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::research::ResearchTopicTool::new(
             tavily_client.clone(),
             db.clone(),
         ),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::research::CompileReportTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::research::TrackTopicTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::research::FactCheckTool::new(
             tavily_client.clone(),
             db.clone(),
         ),
-    ));
+    ))?;
     // Phase 2: SMS/iMessage Autopilot (cross-platform — send_sms is macOS only at runtime)
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::sms::SendSmsTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::sms::SetAutoReplyTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::sms::MessageSummaryTool::new(db.clone()),
-    ));
+    ))?;
     // Phase 2: Task & Project Manager (cross-platform — knowledge graph)
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::tasks::CreateTaskTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::tasks::ListTasksTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::tasks::UpdateTaskTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::tasks::CompleteTaskTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::tasks::ProjectStatusTool::new(db.clone()),
-    ));
+    ))?;
     // Phase 2: News & Content Curator (cross-platform — Tavily + knowledge graph)
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::news::TrackFeedTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::news::UntrackFeedTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::news::SummarizeArticleTool::new(
             tavily_client.clone(),
             db.clone(),
         ),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::news::ContentDigestTool::new(
             tavily_client.clone(),
             db.clone(),
         ),
-    ));
+    ))?;
+    registry.register(Arc::new(
+        meepo_core::tools::lifestyle::news::CurateNewsTool::new(db.clone()),
+    ))?;
     // Phase 3: Finance & Expense Tracker (cross-platform — knowledge graph)
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::finance::LogExpenseTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::finance::SpendingSummaryTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::finance::BudgetCheckTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::finance::ParseReceiptTool::new(db.clone()),
-    ));
+    ))?;
+    registry.register(Arc::new(
+        meepo_core::tools::lifestyle::finance::ImportTransactionsTool::new(db.clone()),
+    ))?;
     // Phase 3: Health & Habit Tracker (cross-platform — knowledge graph)
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::health::LogHabitTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::health::HabitStreakTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::health::HabitReportTool::new(db.clone()),
-    ));
+    ))?;
     // Phase 3: Travel & Commute Assistant (cross-platform — Tavily + knowledge graph)
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::travel::GetWeatherTool::new(tavily_client.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::travel::GetDirectionsTool::new(tavily_client.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::travel::FlightStatusTool::new(
             tavily_client.clone(),
             db.clone(),
         ),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::travel::PackingListTool::new(db.clone()),
-    ));
+    ))?;
     // Phase 3: Social & Relationship Manager (cross-platform — knowledge graph)
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::social::RelationshipSummaryTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::social::SuggestFollowupsTool::new(db.clone()),
-    ));
+    ))?;
     // ── Canvas / A2UI Tools ─────────────────────────────────────
-    registry.register(Arc::new(meepo_core::tools::canvas::CanvasPushTool::new()));
-    registry.register(Arc::new(meepo_core::tools::canvas::CanvasResetTool::new()));
-    registry.register(Arc::new(meepo_core::tools::canvas::CanvasEvalTool::new()));
-    registry.register(Arc::new(meepo_core::tools::canvas::CanvasSnapshotTool::new()));
+    registry.register(Arc::new(meepo_core::tools::canvas::CanvasPushTool::new()))?;
+    registry.register(Arc::new(meepo_core::tools::canvas::CanvasResetTool::new()))?;
+    registry.register(Arc::new(meepo_core::tools::canvas::CanvasEvalTool::new()))?;
+    registry.register(Arc::new(
+        meepo_core::tools::canvas::CanvasSnapshotTool::new(),
+    ))?;
     info!("Registered {} tools (including lifestyle integrations)", registry.len());
 
     // Initialize progress channel for sub-agent orchestrator
@@ -1426,7 +1510,7 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
             orchestrator.clone(),
             registry_slot.clone(),
         ),
-    ));
+    ))?;
     info!(
         "Registered delegate_tasks tool (total: {} tools)",
         registry.len()
@@ -1450,7 +1534,9 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
                 Ok(tools) => {
                     let count = tools.len();
                     for tool in tools {
-                        registry.register(tool);
+                        if let Err(e) = registry.register(tool) {
+                            warn!("MCP client '{}': {}", client_cfg.name, e);
+                        }
                     }
                     info!(
                         "MCP client '{}': registered {} tools",
@@ -1483,7 +1569,7 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
             })
             .collect();
 
-        registry.register(Arc::new(meepo_a2a::DelegateToAgentTool::new(peers)));
+        registry.register(Arc::new(meepo_a2a::DelegateToAgentTool::new(peers)))?;
         info!(
             "A2A: registered delegate_to_agent tool ({} peer agents)",
             cfg.a2a.agents.len()
@@ -1497,7 +1583,9 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
             Ok(skill_tools) => {
                 let count = skill_tools.len();
                 for tool in skill_tools {
-                    registry.register(tool);
+                    if let Err(e) = registry.register(tool) {
+                        warn!("Skills: {}", e);
+                    }
                 }
                 info!(
                     "Skills: loaded {} tools from {}",
@@ -1537,7 +1625,7 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
         let tracker = Arc::new(meepo_core::usage::UsageTracker::new(db.clone(), usage_config));
         registry.register(Arc::new(
             meepo_core::tools::usage_stats::GetUsageStatsTool::new(tracker.clone()),
-        ));
+        ))?;
         info!("Usage tracking enabled");
         Some(tracker)
     } else {
@@ -1545,6 +1633,7 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
         None
     };
 
+    apply_tool_cache(&mut registry, &cfg.tool_cache);
     info!("Total tools registered: {}", registry.len());
 
     // Initialize agent
@@ -1564,21 +1653,73 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
     if let Some(ref tracker) = usage_tracker {
         agent = agent.with_usage_tracker(tracker.clone());
     }
+    if cfg.memory.conversation_window > 0 {
+        let mut store = meepo_core::ConversationStore::new(cfg.memory.conversation_window);
+        if cfg.memory.persist_conversations {
+            store = store.with_persistence(workspace.join("conversations.json"))?;
+        }
+        agent = agent.with_conversation_store(Arc::new(store), cfg.memory.conversation_window);
+    }
     let agent = Arc::new(agent);
 
-    // Initialize watcher scheduler
-    let (watcher_event_tx, mut watcher_event_rx) = tokio::sync::mpsc::unbounded_channel();
-    let watcher_runner = Arc::new(tokio::sync::Mutex::new(
-        meepo_scheduler::runner::WatcherRunner::new(watcher_event_tx),
-    ));
-
-    // Initialize scheduler database (kept alive for runtime persistence)
-    let sched_db = Arc::new(std::sync::Mutex::new(rusqlite::Connection::open(&db_path)?));
     let watchers = {
         let conn = sched_db.lock().unwrap();
-        meepo_scheduler::persistence::init_watcher_tables(&conn)?;
+        if let Some(file) = &cfg.watchers.file {
+            meepo_scheduler::sync_watchers_from_file(&conn, &shellexpand(file))?;
+        }
+        meepo_scheduler::persistence::prune_watcher_executions(
+            &conn,
+            cfg.watchers.history_retention_days,
+        )?;
         meepo_scheduler::persistence::get_active_watchers(&conn)?
     }; // conn dropped here before any await
+
+    // Initialize watcher scheduler
+    let (watcher_event_tx, mut watcher_event_rx) = tokio::sync::mpsc::unbounded_channel();
+    let watcher_config = meepo_scheduler::runner::WatcherConfig {
+        history_retention_days: cfg.watchers.history_retention_days,
+        jitter_fraction: cfg.watchers.jitter_fraction,
+        max_consecutive_failures: cfg.watchers.max_consecutive_failures,
+        max_concurrent_polls: cfg.watchers.max_concurrent_polls,
+        ..Default::default()
+    };
+    let (watcher_alert_tx, mut watcher_alert_rx) = tokio::sync::mpsc::unbounded_channel();
+    let watcher_lifecycle: Arc<dyn meepo_scheduler::WatcherLifecycleListener> =
+        match &cfg.watchers.alert_channel {
+            Some(channel) => Arc::new(ChannelAlertListener {
+                channel: meepo_channels::reply_channel_type(channel)
+                    .context("Invalid watchers.alert_channel")?,
+                tx: watcher_alert_tx,
+            }),
+            None => Arc::new(meepo_scheduler::LoggingLifecycleListener),
+        };
+    let mut watcher_runner =
+        meepo_scheduler::runner::WatcherRunner::with_config(watcher_event_tx, watcher_config)
+            .with_execution_db(sched_db.clone())
+            .with_state_db(sched_db.clone())
+            .with_lifecycle_listener(watcher_lifecycle);
+    if imap_email {
+        // Email watchers poll the IMAP account instead of Mail.app
+        watcher_runner = watcher_runner.with_email_source(Arc::new(
+            meepo_core::platform::imap::ImapEmailProvider::from_configured()?,
+        ));
+    } else if cfg!(target_os = "windows") {
+        // No built-in Outlook polling; watch through the email provider's structured reads
+        watcher_runner = watcher_runner.with_email_source(Arc::new(
+            meepo_core::platform::ProviderEmailSource::new(
+                meepo_core::platform::create_email_provider()?,
+            ),
+        ));
+    }
+    match meepo_core::platform::create_calendar_provider() {
+        Ok(provider) => {
+            watcher_runner = watcher_runner.with_calendar_source(Arc::new(
+                meepo_core::platform::ProviderCalendarSource::new(provider),
+            ));
+        }
+        Err(e) => warn!("Calendar watchers disabled: {}", e),
+    }
+    let watcher_runner = Arc::new(tokio::sync::Mutex::new(watcher_runner));
     {
         let runner = watcher_runner.lock().await;
         for w in watchers {
@@ -1590,14 +1731,27 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
     info!("Watcher scheduler initialized");
 
     // Initialize message bus
-    let mut bus = meepo_channels::bus::MessageBus::new(256);
+    let mut bus = meepo_channels::bus::MessageBus::new(256, 128);
+    if cfg.channels.dedup.enabled {
+        bus = bus.with_intake_dedup(meepo_channels::IntakeDedupConfig {
+            window: std::time::Duration::from_secs(cfg.channels.dedup.window_secs),
+            key: cfg.channels.dedup.key,
+        });
+    }
+    if cfg.channels.retry.max_retries > 0 {
+        bus = bus.with_send_retry(cfg.channels.retry.policy());
+    }
+    if cfg.channels.rate_limit.enabled {
+        bus = bus.with_send_rate_limit(cfg.channels.rate_limit.limiter());
+    }
 
     // Register Discord channel if enabled
     if cfg.channels.discord.enabled {
         let discord = meepo_channels::discord::DiscordChannel::new(
             shellexpand_str(&cfg.channels.discord.token),
             cfg.channels.discord.allowed_users.clone(),
-        );
+        )
+        .with_slash_commands(cfg.channels.discord.slash_commands.clone());
         bus.register(Box::new(discord));
         info!("Discord channel registered");
     }
@@ -1605,11 +1759,16 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
     // Register iMessage channel if enabled (macOS only)
     #[cfg(target_os = "macos")]
     if cfg.channels.imessage.enabled {
-        let imessage = meepo_channels::imessage::IMessageChannel::new(
+        let mut imessage = meepo_channels::imessage::IMessageChannel::new(
             std::time::Duration::from_secs(cfg.channels.imessage.poll_interval_secs),
             cfg.channels.imessage.allowed_contacts.clone(),
             None,
         );
+        if cfg.channels.imessage.delivery_timeout_secs > 0 {
+            imessage = imessage.with_delivery_confirmation(std::time::Duration::from_secs(
+                cfg.channels.imessage.delivery_timeout_secs,
+            ));
+        }
         bus.register(Box::new(imessage));
         info!("iMessage channel registered");
     }
@@ -1629,6 +1788,21 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
         info!("Slack channel registered");
     }
 
+    // Register Telegram channel if enabled
+    if cfg.channels.telegram.enabled && cfg.channels.telegram.allowed_chats.is_empty() {
+        warn!(
+            "[channels.telegram] is enabled but allowed_chats is empty — Telegram channel disabled"
+        );
+    } else if cfg.channels.telegram.enabled {
+        let telegram = meepo_channels::telegram::TelegramChannel::new(
+            shellexpand_str(&cfg.channels.telegram.bot_token),
+            std::time::Duration::from_secs(cfg.channels.telegram.poll_timeout_secs),
+            cfg.channels.telegram.allowed_chats.clone(),
+        );
+        bus.register(Box::new(telegram));
+        info!("Telegram channel registered");
+    }
+
     // Register Email channel if enabled (macOS only — uses Mail.app)
     #[cfg(target_os = "macos")]
     if cfg.channels.email.enabled {
@@ -1659,10 +1833,24 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
     // Register Reminders channel if enabled (macOS only)
     #[cfg(target_os = "macos")]
     if cfg.channels.reminders.enabled {
-        let reminders = meepo_channels::reminders::RemindersChannel::new(
+        let reply_format = meepo_channels::reminders::ReplyFormat {
+            title_mode: meepo_channels::reminders::TitleMode::from_string(
+                &cfg.channels.reminders.title_mode,
+            ),
+            full_body: cfg.channels.reminders.full_body,
+            strip_markdown: cfg.channels.reminders.strip_markdown,
+            ..Default::default()
+        };
+        let mut reminders = meepo_channels::reminders::RemindersChannel::new(
             std::time::Duration::from_secs(cfg.channels.reminders.poll_interval_secs),
             cfg.channels.reminders.list_name.clone(),
-        );
+        )
+        .with_reply_format(reply_format)
+        .with_watched_lists(cfg.channels.reminders.watch_lists.clone());
+        if cfg.channels.reminders.keep_incomplete {
+            reminders =
+                reminders.with_seen_store(&config::config_dir().join("reminders_seen.db"))?;
+        }
         bus.register(Box::new(reminders));
         info!("Reminders channel registered");
     }
@@ -1706,12 +1894,28 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
     bus.start_all().await?;
     info!("All message channels started");
 
+    // Launch Mail/Calendar in the background so the first real call is fast
+    if cfg.agent.prewarm_providers {
+        tokio::spawn(meepo_core::platform::prewarm_all());
+    }
+
     println!("Meepo is running. Press Ctrl+C to stop.");
 
     // Split bus into receiver + sender for concurrent use
+    let bus_metrics = bus.metrics();
     let (mut incoming_rx, bus_sender) = bus.split();
     let bus_sender = Arc::new(bus_sender);
 
+    // Deliver watcher auto-disable alerts
+    let bus_sender_for_alerts = bus_sender.clone();
+    tokio::spawn(async move {
+        while let Some(msg) = watcher_alert_rx.recv().await {
+            if let Err(e) = bus_sender_for_alerts.send(msg).await {
+                warn!("Failed to send watcher alert: {}", e);
+            }
+        }
+    });
+
     // ── Autonomous Loop ─────────────────────────────────────────
     let bus_sender_for_progress = bus_sender.clone();
 
@@ -1724,31 +1928,50 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
     // Forward incoming bus messages to the autonomous loop
     let wake_clone = wake.clone();
     let cancel_clone = cancel.clone();
+    let bus_sender_for_restart = bus_sender.clone();
+    let max_channel_restarts = cfg.channels.max_restarts;
     let bus_to_loop = tokio::spawn(async move {
+        let mut restarts = 0;
         loop {
             tokio::select! {
                 _ = cancel_clone.cancelled() => break,
                 msg = incoming_rx.recv() => {
                     match msg {
-                        Some(incoming) => {
+                        Ok(incoming) => {
                             info!("Message from {} via {}: {}",
                                 incoming.sender,
                                 incoming.channel,
-                                &incoming.content[..incoming.content.len().min(100)]);
+                                meepo_core::truncate_chars(&incoming.content, 100));
                             if loop_msg_tx.send(incoming).await.is_err() {
                                 break;
                             }
                             wake_clone.notify_one();
                         }
-                        None => break,
+                        Err(e) => {
+                            if restarts >= max_channel_restarts {
+                                error!("{} — giving up after {} restart(s); Meepo will no longer receive messages", e, restarts);
+                                break;
+                            }
+                            restarts += 1;
+                            warn!("{} — restarting channels (attempt {}/{})", e, restarts, max_channel_restarts);
+                            tokio::time::sleep(std::time::Duration::from_secs(5 * restarts as u64)).await;
+                            if let Err(e) = incoming_rx.restart(&bus_sender_for_restart).await {
+                                error!("Failed to restart channels: {}", e);
+                            }
+                        }
                     }
                 }
             }
         }
     });
 
-    // Forward watcher events to the autonomous loop
+    // Forward watcher events to the autonomous loop, or straight to their
+    // reply channels when direct delivery is on
     let (loop_watcher_tx, loop_watcher_rx) = tokio::sync::mpsc::unbounded_channel();
+    let watcher_bridge = cfg
+        .watchers
+        .direct_delivery
+        .then(|| meepo_channels::WatcherBridge::new(bus_sender.clone(), sched_db.clone()));
     let cancel_clone2 = cancel.clone();
     let wake_clone2 = wake.clone();
     let watcher_to_loop = tokio::spawn(async move {
@@ -1758,9 +1981,15 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
                 event = watcher_event_rx.recv() => {
                     match event {
                         Some(ev) => {
-                            info!("Watcher event: {} from {}", ev.kind, ev.watcher_id);
-                            let _ = loop_watcher_tx.send(ev);
-                            wake_clone2.notify_one();
+                            info!("Watcher event: {} from {}", ev.kind(), ev.watcher_id);
+                            if let Some(bridge) = &watcher_bridge {
+                                if let Err(e) = bridge.forward(&ev).await {
+                                    error!("Failed to deliver watcher event: {:#}", e);
+                                }
+                            } else {
+                                let _ = loop_watcher_tx.send(ev);
+                                wake_clone2.notify_one();
+                            }
                         }
                         None => break,
                     }
@@ -1803,10 +2032,20 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
                 resp = loop_resp_rx.recv() => {
                     match resp {
                         Some(msg) => {
-                            let channel = msg.channel.clone();
-                            if let Err(e) = bus_sender.send(msg).await {
+                            // Send whatever else is already queued along with it, so
+                            // channels like Reminders can create a burst in one go
+                            let mut burst = vec![msg];
+                            while let Ok(more) = loop_resp_rx.try_recv() {
+                                burst.push(more);
+                            }
+                            let channels: Vec<_> =
+                                burst.iter().map(|m| m.channel.clone()).collect();
+                            let results = bus_sender.send_batch(burst).await;
+                            for (channel, result) in channels.into_iter().zip(results) {
                                 // Internal channel has no handler — this is expected
-                                if channel != meepo_core::types::ChannelType::Internal {
+                                if let Err(e) = result
+                                    && channel != meepo_core::types::ChannelType::Internal
+                                {
                                     error!("Failed to route response to {}: {}", channel, e);
                                 }
                             }
@@ -1833,35 +2072,10 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
                             use meepo_core::tools::watchers::WatcherCommand;
                             match command {
                                 WatcherCommand::Create { id, kind, config, action, reply_channel } => {
-                                    // Map the tool's kind string to WatcherKind's serde tag variant name
-                                    let type_tag = match kind.as_str() {
-                                        "email" => "EmailWatch",
-                                        "calendar" => "CalendarWatch",
-                                        "github" => "GitHubWatch",
-                                        "file" => "FileWatch",
-                                        "message" => "MessageWatch",
-                                        "scheduled" | "time" => "Scheduled",
-                                        "oneshot" => "OneShot",
-                                        other => {
-                                            error!("Unknown watcher kind: {}", other);
-                                            return;
-                                        }
-                                    };
-                                    // Inject the "type" tag into config for serde deserialization
-                                    let config_with_type = match config {
-                                        serde_json::Value::Object(mut map) => {
-                                            map.insert("type".to_string(), serde_json::Value::String(type_tag.to_string()));
-                                            serde_json::Value::Object(map)
-                                        }
-                                        _ => {
-                                            error!("Watcher config is not a JSON object");
-                                            return;
-                                        }
-                                    };
-                                    let watcher_kind: meepo_scheduler::watcher::WatcherKind = match serde_json::from_value(config_with_type) {
+                                    let watcher_kind = match meepo_scheduler::watcher::WatcherKind::from_config(&kind, config) {
                                         Ok(k) => k,
                                         Err(e) => {
-                                            error!("Failed to deserialize watcher kind: {}", e);
+                                            error!("Failed to create watcher: {:#}", e);
                                             return;
                                         }
                                     };
@@ -1871,8 +2085,13 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
                                         action,
                                         reply_channel,
                                         active: true,
+                                        paused: false,
                                         created_at: chrono::Utc::now(),
                                     };
+                                    if let Err(e) = watcher.validate() {
+                                        error!("Rejected watcher {}: {:#}", watcher.id, e);
+                                        return;
+                                    }
                                     if let Ok(conn) = sched_db.lock()
                                         && let Err(e) = meepo_scheduler::persistence::save_watcher(&conn, &watcher)
                                     {
@@ -1892,6 +2111,12 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
                                         error!("Failed to stop watcher {}: {}", id, e);
                                     }
                                 }
+                                WatcherCommand::Pause { id } => {
+                                    runner.lock().await.pause_watcher(&id).await;
+                                }
+                                WatcherCommand::Resume { id } => {
+                                    runner.lock().await.resume_watcher(&id).await;
+                                }
                                 WatcherCommand::List => {}
                             }
                         });
@@ -1956,6 +2181,9 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
                                     content: description.clone(),
                                     channel: meepo_core::types::ChannelType::from_string(&reply_channel_clone),
                                     timestamp: chrono::Utc::now(),
+                                    priority: meepo_core::types::Priority::Normal,
+                                    command: None,
+                                    thread_id: None,
                                 };
 
                                 let result = tokio::select! {
@@ -1974,13 +2202,14 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
                                         notifier.notify(meepo_core::notifications::NotifyEvent::TaskCompleted {
                                             task_id: id_clone.clone(),
                                             description: description.clone(),
-                                            result_preview: response.content[..response.content.len().min(500)].to_string(),
+                                            result_preview: meepo_core::truncate_chars(&response.content, 500).to_string(),
                                         }).await;
                                         // Also send to reply_channel
                                         let notify_msg = meepo_core::types::OutgoingMessage {
                                             content: format!("Background task [{}] completed:\n{}", id_clone, response.content),
                                             channel: meepo_core::types::ChannelType::from_string(&reply_channel_clone),
                                             reply_to: None,
+                                            thread_id: None,
                                             kind: meepo_core::types::MessageKind::Response,
                                         };
                                         let _ = bus.send(notify_msg).await;
@@ -2001,6 +2230,7 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
                                                 content: format!("Background task [{}] failed: {}", id_clone, err_msg),
                                                 channel: meepo_core::types::ChannelType::from_string(&reply_channel_clone),
                                                 reply_to: None,
+                                                thread_id: None,
                                                 kind: meepo_core::types::MessageKind::Response,
                                             };
                                             let _ = bus.send(notify_msg).await;
@@ -2059,6 +2289,7 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
                                             content: format!("Claude Code task [{}] failed: {}", id, err_msg),
                                             channel: meepo_core::types::ChannelType::from_string(&reply_channel),
                                             reply_to: None,
+                                            thread_id: None,
                                             kind: meepo_core::types::MessageKind::Response,
                                         };
                                         let _ = bus.send(notify).await;
@@ -2084,8 +2315,9 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
                                                 }
                                                 let stdout = String::from_utf8_lossy(&stdout_buf);
                                                 // Truncate to 10K chars for DB storage
-                                                let result = if stdout.len() > 10_000 {
-                                                    format!("{}...\n[truncated, {} total chars]", &stdout[..10_000], stdout.len())
+                                                let truncated = meepo_core::truncate_chars(&stdout, 10_000);
+                                                let result = if truncated.len() < stdout.len() {
+                                                    format!("{}...\n[truncated, {} total chars]", truncated, stdout.chars().count())
                                                 } else {
                                                     stdout.to_string()
                                                 };
@@ -2113,12 +2345,13 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
                                         notifier.notify(meepo_core::notifications::NotifyEvent::TaskCompleted {
                                             task_id: id.clone(),
                                             description: format!("Claude Code: {}", &task),
-                                            result_preview: output[..output.len().min(500)].to_string(),
+                                            result_preview: meepo_core::truncate_chars(&output, 500).to_string(),
                                         }).await;
                                         let notify = meepo_core::types::OutgoingMessage {
                                             content: format!("Claude Code task [{}] completed:\n{}", id, output),
                                             channel: meepo_core::types::ChannelType::from_string(&reply_channel),
                                             reply_to: None,
+                                            thread_id: None,
                                             kind: meepo_core::types::MessageKind::Response,
                                         };
                                         let _ = bus.send(notify).await;
@@ -2139,6 +2372,7 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
                                                 content: format!("Claude Code task [{}] failed: {}", id, err_msg),
                                                 channel: meepo_core::types::ChannelType::from_string(&reply_channel),
                                                 reply_to: None,
+                                                thread_id: None,
                                                 kind: meepo_core::types::MessageKind::Response,
                                             };
                                             let _ = bus.send(notify).await;
@@ -2171,7 +2405,7 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
                 _ = cancel_clone5.cancelled() => break,
                 progress = progress_rx.recv() => {
                     if let Some(msg) = progress {
-                        info!("Sub-agent progress for {}: {}", msg.channel, &msg.content[..msg.content.len().min(100)]);
+                        info!("Sub-agent progress for {}: {}", msg.channel, meepo_core::truncate_chars(&msg.content, 100));
                         let _ = bus_sender_for_progress.send(msg).await;
                     }
                 }
@@ -2390,8 +2624,21 @@ async fn cmd_start(config_path: &Option<PathBuf>) -> Result<()> {
         let _ = dt.await;
     }
 
-    // Stop all watchers
-    watcher_runner.lock().await.stop_all().await;
+    // Stop all watchers, letting in-flight polls finish before aborting
+    let watcher_metrics = watcher_runner.lock().await.metrics();
+    watcher_runner.lock().await.shutdown_and_wait().await;
+    info!(
+        "Watcher polls: {} run, {} waited for a free slot",
+        watcher_metrics.polls_started(),
+        watcher_metrics.polls_queued()
+    );
+
+    if cfg.channels.dedup.enabled {
+        info!(
+            "Dropped {} duplicate incoming messages",
+            bus_metrics.duplicates_dropped()
+        );
+    }
 
     println!("Meepo stopped.");
     Ok(())
@@ -2684,6 +2931,9 @@ async fn cmd_mcp_server(config_path: &Option<PathBuf>) -> Result<()> {
             .context("Failed to initialize knowledge graph")?,
     );
     let db = knowledge_graph.db();
+    db.set_relation_vocabulary(relation_vocabulary(&cfg.knowledge.relations));
+    let sched_db = Arc::new(std::sync::Mutex::new(rusqlite::Connection::open(&db_path)?));
+    meepo_scheduler::persistence::init_watcher_tables(&sched_db.lock().unwrap())?;
 
     // Tavily client (optional)
     let tavily_client = cfg
@@ -2698,41 +2948,55 @@ async fn cmd_mcp_server(config_path: &Option<PathBuf>) -> Result<()> {
     let (watcher_command_tx, _watcher_command_rx) =
         tokio::sync::mpsc::channel::<meepo_core::tools::watchers::WatcherCommand>(100);
 
+    configure_imap_email(&cfg.imap);
     let mut registry = meepo_core::tools::ToolRegistry::new();
 
     #[cfg(any(target_os = "macos", target_os = "windows"))]
     {
-        registry.register(Arc::new(meepo_core::tools::macos::ReadEmailsTool::new()));
-        registry.register(Arc::new(meepo_core::tools::macos::ReadCalendarTool::new()));
-        registry.register(Arc::new(meepo_core::tools::macos::SendEmailTool::new()));
-        registry.register(Arc::new(meepo_core::tools::macos::CreateEventTool::new()));
+        registry.register(Arc::new(meepo_core::tools::macos::ReadEmailsTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::ReadCalendarTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::ListCalendarsTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::SendEmailTool::new(
+            cfg.filesystem.allowed_directories.clone(),
+        )))?;
+        registry.register(Arc::new(meepo_core::tools::macos::CreateEventTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::UpdateEventTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::DeleteEventTool::new()))?;
         registry.register(Arc::new(
             meepo_core::tools::accessibility::ReadScreenTool::new(),
-        ));
+        ))?;
+        registry.register(Arc::new(
+            meepo_core::tools::accessibility::FindElementTool::new(),
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::accessibility::ClickElementTool::new(),
-        ));
+        ))?;
+        registry.register(Arc::new(
+            meepo_core::tools::accessibility::ClickAtTool::new(),
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::accessibility::TypeTextTool::new(),
-        ));
+        ))?;
     }
-    registry.register(Arc::new(meepo_core::tools::macos::OpenAppTool::new()));
-    registry.register(Arc::new(meepo_core::tools::macos::GetClipboardTool::new()));
+    registry.register(Arc::new(meepo_core::tools::macos::OpenAppTool::new()))?;
+    registry.register(Arc::new(meepo_core::tools::macos::GetClipboardTool::new()))?;
+    registry.register(Arc::new(meepo_core::tools::macos::SetClipboardTool::new()))?;
+    registry.register(Arc::new(
+        meepo_core::tools::macos::SendNotificationTool::new(),
+    ))?;
+    registry.register(Arc::new(meepo_core::tools::macos::ScreenCaptureTool::new()))?;
     #[cfg(target_os = "macos")]
     {
-        registry.register(Arc::new(meepo_core::tools::macos::ListRemindersTool::new()));
-        registry.register(Arc::new(meepo_core::tools::macos::CreateReminderTool::new()));
-        registry.register(Arc::new(meepo_core::tools::macos::ListNotesTool::new()));
-        registry.register(Arc::new(meepo_core::tools::macos::CreateNoteTool::new()));
-        registry.register(Arc::new(
-            meepo_core::tools::macos::SendNotificationTool::new(),
-        ));
-        registry.register(Arc::new(meepo_core::tools::macos::ScreenCaptureTool::new()));
+        registry.register(Arc::new(meepo_core::tools::macos::ListRemindersTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::CreateReminderTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::ListNotesTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::CreateNoteTool::new()))?;
         registry.register(Arc::new(
             meepo_core::tools::macos::GetCurrentTrackTool::new(),
-        ));
-        registry.register(Arc::new(meepo_core::tools::macos::MusicControlTool::new()));
-        registry.register(Arc::new(meepo_core::tools::macos::SearchContactsTool::new()));
+        ))?;
+        registry.register(Arc::new(meepo_core::tools::macos::MusicControlTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::SearchContactsTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::LookupContactTool::new()))?;
     }
     // Browser automation tools for ask command
     #[cfg(target_os = "macos")]
@@ -2740,37 +3004,37 @@ async fn cmd_mcp_server(config_path: &Option<PathBuf>) -> Result<()> {
         let browser = &cfg.browser.default_browser;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserListTabsTool::new(browser),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserOpenTabTool::new(browser),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserCloseTabTool::new(browser),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserSwitchTabTool::new(browser),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserGetPageContentTool::new(browser),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserExecuteJsTool::new(browser),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserClickElementTool::new(browser),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserFillFormTool::new(browser),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserNavigateTool::new(browser),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserGetUrlTool::new(browser),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::browser::BrowserScreenshotTool::new(browser),
-        ));
+        ))?;
     }
     let code_config = meepo_core::tools::code::CodeToolConfig {
         claude_code_path: shellexpand_str(&cfg.code.claude_code_path),
@@ -2779,185 +3043,236 @@ async fn cmd_mcp_server(config_path: &Option<PathBuf>) -> Result<()> {
     };
     registry.register(Arc::new(meepo_core::tools::code::WriteCodeTool::new(
         code_config.clone(),
-    )));
+    )))?;
     registry.register(Arc::new(meepo_core::tools::code::MakePrTool::new(
         code_config.clone(),
-    )));
+    )))?;
     registry.register(Arc::new(meepo_core::tools::code::ReviewPrTool::new(
         code_config,
-    )));
+    )))?;
     registry.register(Arc::new(meepo_core::tools::memory::RememberTool::new(
         db.clone(),
-    )));
+    )))?;
     registry.register(Arc::new(meepo_core::tools::memory::RecallTool::new(
         db.clone(),
-    )));
+    )))?;
     registry.register(Arc::new(
         meepo_core::tools::memory::SearchKnowledgeTool::with_graph(knowledge_graph.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(meepo_core::tools::memory::LinkEntitiesTool::new(
         db.clone(),
-    )));
-    registry.register(Arc::new(meepo_core::tools::system::RunCommandTool));
-    registry.register(Arc::new(meepo_core::tools::system::ReadFileTool));
-    registry.register(Arc::new(meepo_core::tools::system::WriteFileTool));
+    )))?;
+    registry.register(Arc::new(
+        meepo_core::tools::memory::UpdateEntityTool::with_graph(knowledge_graph.clone()),
+    ))?;
+    registry.register(Arc::new(meepo_core::tools::rag::GetRelatedTool::new(
+        db.clone(),
+    )))?;
+    registry.register(Arc::new(meepo_core::tools::memory::ForgetTool::with_graph(
+        knowledge_graph.clone(),
+    )))?;
+    registry.register(Arc::new(
+        meepo_core::tools::memory::FindDuplicatesTool::new(db.clone()),
+    ))?;
+    registry.register(Arc::new(
+        meepo_core::tools::memory::MergeEntitiesTool::with_graph(knowledge_graph.clone()),
+    ))?;
+    registry.register(Arc::new(meepo_core::tools::system::RunCommandTool::new(
+        cfg.commands.allowed.clone(),
+        cfg.commands.timeout_secs,
+    )))?;
+    registry.register(Arc::new(meepo_core::tools::system::ReadFileTool))?;
+    registry.register(Arc::new(meepo_core::tools::system::WriteFileTool))?;
     registry.register(Arc::new(
         meepo_core::tools::filesystem::ListDirectoryTool::new(
             cfg.filesystem.allowed_directories.clone(),
         ),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::filesystem::SearchFilesTool::new(
             cfg.filesystem.allowed_directories.clone(),
         ),
-    ));
+    ))?;
     if let Some(ref tavily) = tavily_client {
         registry.register(Arc::new(
             meepo_core::tools::system::BrowseUrlTool::with_tavily(tavily.clone()),
-        ));
+        ))?;
         registry.register(Arc::new(meepo_core::tools::search::WebSearchTool::new(
             tavily.clone(),
-        )));
+        )))?;
     } else {
-        registry.register(Arc::new(meepo_core::tools::system::BrowseUrlTool::new()));
+        registry.register(Arc::new(meepo_core::tools::system::BrowseUrlTool::new()))?;
     }
     registry.register(Arc::new(
         meepo_core::tools::watchers::CreateWatcherTool::new(db.clone(), watcher_command_tx.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::watchers::ListWatchersTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::watchers::CancelWatcherTool::new(db.clone(), watcher_command_tx.clone()),
-    ));
+    ))?;
+    registry.register(Arc::new(
+        meepo_core::tools::watchers::WatcherStateTool::new(sched_db.clone()),
+    ))?;
+    registry.register(Arc::new(
+        meepo_core::tools::watchers::PauseWatcherTool::new(sched_db.clone(), watcher_command_tx.clone()),
+    ))?;
+    registry.register(Arc::new(
+        meepo_core::tools::watchers::ResumeWatcherTool::new(sched_db.clone(), watcher_command_tx.clone()),
+    ))?;
+    registry.register(Arc::new(
+        meepo_core::tools::watchers::PreviewWatcherScheduleTool,
+    ))?;
     // Autonomous tools — agent_status works in MCP mode, spawn/stop won't have handlers
     registry.register(Arc::new(
         meepo_core::tools::autonomous::AgentStatusTool::new(db.clone()),
-    ));
+    ))?;
 
     // ── Lifestyle Integration Tools (MCP mode) ──────────────────
     #[cfg(any(target_os = "macos", target_os = "windows"))]
     {
         registry.register(Arc::new(
             meepo_core::tools::lifestyle::email_intelligence::EmailTriageTool::new(db.clone()),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::lifestyle::email_intelligence::EmailDraftReplyTool::new(db.clone()),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::lifestyle::email_intelligence::EmailSummarizeThreadTool::new(),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::lifestyle::email_intelligence::EmailUnsubscribeTool::new(),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::lifestyle::calendar::FindFreeTimeTool::new(),
-        ));
+        ))?;
+        registry.register(Arc::new(
+            meepo_core::tools::lifestyle::calendar::FindFreeSlotsTool::new(),
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::lifestyle::calendar::ScheduleMeetingTool::new(),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::lifestyle::calendar::RescheduleEventTool::new(),
-        ));
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::lifestyle::calendar::DailyBriefingTool::new(db.clone()),
-        ));
+        ))?;
+        registry.register(Arc::new(
+            meepo_core::tools::lifestyle::travel::ExtractItineraryTool::new(db.clone()),
+        ))?;
         registry.register(Arc::new(
             meepo_core::tools::lifestyle::calendar::WeeklyReviewTool::new(db.clone()),
-        ));
+        ))?;
     }
+    #[cfg(target_os = "linux")]
+    register_linux_platform_tools(
+        &mut registry,
+        &cfg.linux,
+        &cfg.filesystem.allowed_directories,
+        &db,
+    )?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::research::ResearchTopicTool::new(tavily_client.clone(), db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::research::CompileReportTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::research::TrackTopicTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::research::FactCheckTool::new(tavily_client.clone(), db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::sms::SendSmsTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::sms::SetAutoReplyTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::sms::MessageSummaryTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::tasks::CreateTaskTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::tasks::ListTasksTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::tasks::UpdateTaskTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::tasks::CompleteTaskTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::tasks::ProjectStatusTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::news::TrackFeedTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::news::UntrackFeedTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::news::SummarizeArticleTool::new(tavily_client.clone(), db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::news::ContentDigestTool::new(tavily_client.clone(), db.clone()),
-    ));
+    ))?;
+    registry.register(Arc::new(
+        meepo_core::tools::lifestyle::news::CurateNewsTool::new(db.clone()),
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::finance::LogExpenseTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::finance::SpendingSummaryTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::finance::BudgetCheckTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::finance::ParseReceiptTool::new(db.clone()),
-    ));
+    ))?;
+    registry.register(Arc::new(
+        meepo_core::tools::lifestyle::finance::ImportTransactionsTool::new(db.clone()),
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::health::LogHabitTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::health::HabitStreakTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::health::HabitReportTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::travel::GetWeatherTool::new(tavily_client.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::travel::GetDirectionsTool::new(tavily_client.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::travel::FlightStatusTool::new(tavily_client.clone(), db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::travel::PackingListTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::social::RelationshipSummaryTool::new(db.clone()),
-    ));
+    ))?;
     registry.register(Arc::new(
         meepo_core::tools::lifestyle::social::SuggestFollowupsTool::new(db.clone()),
-    ));
+    ))?;
     // ── Canvas / A2UI Tools (MCP mode) ──────────────────────────────
-    registry.register(Arc::new(meepo_core::tools::canvas::CanvasPushTool::new()));
-    registry.register(Arc::new(meepo_core::tools::canvas::CanvasResetTool::new()));
-    registry.register(Arc::new(meepo_core::tools::canvas::CanvasEvalTool::new()));
-    registry.register(Arc::new(meepo_core::tools::canvas::CanvasSnapshotTool::new()));
+    registry.register(Arc::new(meepo_core::tools::canvas::CanvasPushTool::new()))?;
+    registry.register(Arc::new(meepo_core::tools::canvas::CanvasResetTool::new()))?;
+    registry.register(Arc::new(meepo_core::tools::canvas::CanvasEvalTool::new()))?;
+    registry.register(Arc::new(
+        meepo_core::tools::canvas::CanvasSnapshotTool::new(),
+    ))?;
 
     // ── Usage Tracker (MCP mode) ────────────────────────────────────
     if cfg.usage.enabled {
@@ -2983,7 +3298,7 @@ async fn cmd_mcp_server(config_path: &Option<PathBuf>) -> Result<()> {
         let tracker = Arc::new(meepo_core::usage::UsageTracker::new(db.clone(), usage_config));
         registry.register(Arc::new(
             meepo_core::tools::usage_stats::GetUsageStatsTool::new(tracker),
-        ));
+        ))?;
     }
 
     // Load skills if enabled
@@ -2991,11 +3306,14 @@ async fn cmd_mcp_server(config_path: &Option<PathBuf>) -> Result<()> {
         let skills_dir = shellexpand(&cfg.skills.dir);
         if let Ok(skill_tools) = meepo_core::skills::load_skills(&skills_dir) {
             for tool in skill_tools {
-                registry.register(tool);
+                if let Err(e) = registry.register(tool) {
+                    warn!("Skills: {}", e);
+                }
             }
         }
     }
 
+    apply_tool_cache(&mut registry, &cfg.tool_cache);
     let registry = Arc::new(registry);
     info!("MCP server: {} tools available", registry.len());
 
@@ -3304,6 +3622,180 @@ fn copy_dir_recursive(src: &std::path::Path, dst: &std::path::Path) -> Result<()
     Ok(())
 }
 
+/// Posts a message to a channel when a watcher is auto-disabled, so broken
+/// watchers get noticed; everything else is just logged
+struct ChannelAlertListener {
+    channel: meepo_core::types::ChannelType,
+    tx: tokio::sync::mpsc::UnboundedSender<meepo_core::types::OutgoingMessage>,
+}
+
+impl meepo_scheduler::WatcherLifecycleListener for ChannelAlertListener {
+    fn on_started(&self, watcher: &meepo_scheduler::Watcher) {
+        meepo_scheduler::LoggingLifecycleListener.on_started(watcher);
+    }
+
+    fn on_stopped(&self, watcher_id: &str) {
+        meepo_scheduler::LoggingLifecycleListener.on_stopped(watcher_id);
+    }
+
+    fn on_fired(&self, watcher_id: &str, detail: &str) {
+        meepo_scheduler::LoggingLifecycleListener.on_fired(watcher_id, detail);
+    }
+
+    fn on_error(&self, watcher_id: &str, error: &str, consecutive_failures: u32) {
+        meepo_scheduler::LoggingLifecycleListener.on_error(watcher_id, error, consecutive_failures);
+    }
+
+    fn on_auto_disabled(&self, watcher_id: &str, reason: &str) {
+        meepo_scheduler::LoggingLifecycleListener.on_auto_disabled(watcher_id, reason);
+        let msg = meepo_core::types::OutgoingMessage {
+            content: format!(
+                "⚠️ Watcher {} was auto-disabled ({}). Fix it and recreate it to resume.",
+                watcher_id, reason
+            ),
+            channel: self.channel.clone(),
+            reply_to: None,
+            thread_id: None,
+            kind: meepo_core::types::MessageKind::Response,
+        };
+        if self.tx.send(msg).is_err() {
+            warn!("Watcher alert channel closed; dropping auto-disable alert");
+        }
+    }
+
+    fn on_paused(&self, watcher_id: &str) {
+        meepo_scheduler::LoggingLifecycleListener.on_paused(watcher_id);
+    }
+}
+
+fn relation_vocabulary(
+    cfg: &config::RelationVocabularyConfig,
+) -> meepo_knowledge::RelationVocabulary {
+    cfg.synonyms.iter().fold(
+        meepo_knowledge::RelationVocabulary::new(&cfg.types)
+            .with_unknown(cfg.unknown)
+            .with_snap_threshold(cfg.snap_threshold),
+        |vocab, (variant, canonical)| vocab.with_synonym(variant, canonical),
+    )
+}
+
+/// Point the email provider at the `[imap]` account, if enabled. Returns whether it was.
+fn configure_imap_email(imap: &config::ImapConfig) -> bool {
+    if !imap.enabled {
+        return false;
+    }
+    if imap.imap_url.is_empty() || imap.smtp_url.is_empty() {
+        warn!("[imap] is enabled but imap_url or smtp_url is empty — using the platform mail app");
+        return false;
+    }
+    meepo_core::platform::imap::configure(meepo_core::platform::imap::ImapSmtpConfig {
+        imap_url: imap.imap_url.clone(),
+        smtp_url: imap.smtp_url.clone(),
+        username: imap.username.clone(),
+        password: shellexpand_str(&imap.password),
+        from: imap.from.clone(),
+    });
+    info!("Email tools using IMAP account {}", imap.username);
+    true
+}
+
+/// Configure the CalDAV provider from `[linux]` and register the email and
+/// calendar tools for whichever of IMAP and CalDAV is set up
+#[cfg(target_os = "linux")]
+fn register_linux_platform_tools(
+    registry: &mut meepo_core::tools::ToolRegistry,
+    linux: &config::LinuxConfig,
+    allowed_dirs: &[String],
+    db: &Arc<meepo_knowledge::KnowledgeDb>,
+) -> Result<()> {
+    use meepo_core::platform::linux;
+    use meepo_core::tools::lifestyle::{calendar, email_intelligence, travel};
+
+    let email_ready = meepo_core::platform::imap::is_configured();
+    if email_ready {
+        registry.register(Arc::new(meepo_core::tools::macos::ReadEmailsTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::SendEmailTool::new(
+            allowed_dirs.to_vec(),
+        )))?;
+        registry.register(Arc::new(email_intelligence::EmailTriageTool::new(
+            db.clone(),
+        )))?;
+        registry.register(Arc::new(email_intelligence::EmailDraftReplyTool::new(
+            db.clone(),
+        )))?;
+        registry.register(Arc::new(email_intelligence::EmailSummarizeThreadTool::new()))?;
+        registry.register(Arc::new(email_intelligence::EmailUnsubscribeTool::new()))?;
+    }
+
+    let calendar_cfg = &linux.calendar;
+    let calendar_ready = calendar_cfg.enabled && !calendar_cfg.url.is_empty();
+    if calendar_ready {
+        linux::configure_calendar(linux::CalDavConfig {
+            url: calendar_cfg.url.clone(),
+            username: calendar_cfg.username.clone(),
+            password: shellexpand_str(&calendar_cfg.password),
+        });
+        registry.register(Arc::new(meepo_core::tools::macos::ReadCalendarTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::ListCalendarsTool::new()))?;
+        registry.register(Arc::new(meepo_core::tools::macos::CreateEventTool::new()))?;
+        registry.register(Arc::new(calendar::FindFreeTimeTool::new()))?;
+        registry.register(Arc::new(calendar::FindFreeSlotsTool::new()))?;
+        registry.register(Arc::new(calendar::ScheduleMeetingTool::new()))?;
+        registry.register(Arc::new(calendar::RescheduleEventTool::new()))?;
+        registry.register(Arc::new(calendar::WeeklyReviewTool::new(db.clone())))?;
+        info!("Linux calendar tools enabled (CalDAV)");
+    } else if calendar_cfg.enabled {
+        warn!("[linux.calendar] is enabled but url is empty — calendar tools disabled");
+    }
+
+    // The daily briefing and itinerary extraction read both the calendar and the inbox
+    if email_ready && calendar_ready {
+        registry.register(Arc::new(calendar::DailyBriefingTool::new(db.clone())))?;
+        registry.register(Arc::new(travel::ExtractItineraryTool::new(db.clone())))?;
+    }
+    Ok(())
+}
+
+/// Wrap the `[tool_cache]` tools so identical calls within the TTL reuse the last result
+fn apply_tool_cache(
+    registry: &mut meepo_core::tools::ToolRegistry,
+    cache: &config::ToolCacheConfig,
+) {
+    if !cache.enabled || cache.ttl_secs == 0 {
+        return;
+    }
+    let ttl = std::time::Duration::from_secs(cache.ttl_secs);
+    let generation = meepo_core::tools::CacheGeneration::new();
+    for name in &cache.tools {
+        let wrapped = registry.wrap(name, |inner| {
+            Arc::new(
+                meepo_core::tools::CachingTool::new(inner, ttl, cache.max_entries)
+                    .with_generation(generation.clone()),
+            )
+        });
+        if wrapped {
+            info!("Caching {} results for {}s", name, cache.ttl_secs);
+        }
+    }
+    // Many tools write to the calendar, mail or knowledge graph (create_calendar_event,
+    // remember, log_expense, ...), so a call to any side-effecting tool drops every
+    // cached read. Read-only tools leave the cache alone.
+    let writers: Vec<String> = meepo_core::tools::ToolExecutor::list_tools(registry)
+        .into_iter()
+        .map(|tool| tool.name)
+        .filter(|name| !cache.tools.contains(name))
+        .filter(|name| registry.get(name).is_some_and(|tool| !tool.read_only()))
+        .collect();
+    for name in &writers {
+        registry.wrap(name, |inner| {
+            Arc::new(meepo_core::tools::InvalidatingTool::new(
+                inner,
+                generation.clone(),
+            ))
+        });
+    }
+}
+
 // Utility: expand ~ and env vars in paths
 fn shellexpand(s: &str) -> PathBuf {
     let expanded = shellexpand_str(s);
@@ -3342,3 +3834,93 @@ fn shellexpand_str(s: &str) -> String {
     }
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use meepo_core::tools::{ToolExecutor, ToolHandler, ToolRegistry};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Counts calls so cache hits are observable
+    struct CountingTool {
+        name: &'static str,
+        read_only: bool,
+        calls: AtomicUsize,
+    }
+
+    impl CountingTool {
+        fn new(name: &'static str, read_only: bool) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                read_only,
+                calls: AtomicUsize::new(0),
+            })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ToolHandler for CountingTool {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn description(&self) -> &str {
+            "Counts calls"
+        }
+
+        fn input_schema(&self) -> serde_json::Value {
+            meepo_core::tools::json_schema(serde_json::json!({}), vec![])
+        }
+
+        fn read_only(&self) -> bool {
+            self.read_only
+        }
+
+        async fn execute(&self, _input: serde_json::Value) -> Result<String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok("ok".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_only_side_effecting_tools_clear_the_tool_cache() {
+        let reader = CountingTool::new("read_calendar", true);
+        let mut registry = ToolRegistry::new();
+        registry.register(reader.clone()).unwrap();
+        registry
+            .register(CountingTool::new("list_watchers", true))
+            .unwrap();
+        registry
+            .register(CountingTool::new("create_calendar_event", false))
+            .unwrap();
+        let cache = config::ToolCacheConfig {
+            enabled: true,
+            ttl_secs: 60,
+            max_entries: 10,
+            tools: vec!["read_calendar".to_string()],
+        };
+        apply_tool_cache(&mut registry, &cache);
+
+        let input = serde_json::json!({});
+        registry
+            .execute("read_calendar", input.clone())
+            .await
+            .unwrap();
+        registry
+            .execute("list_watchers", input.clone())
+            .await
+            .unwrap();
+        registry
+            .execute("read_calendar", input.clone())
+            .await
+            .unwrap();
+        assert_eq!(reader.calls.load(Ordering::SeqCst), 1);
+
+        registry
+            .execute("create_calendar_event", input.clone())
+            .await
+            .unwrap();
+        registry.execute("read_calendar", input).await.unwrap();
+        assert_eq!(reader.calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -6,6 +6,7 @@ use tracing::{debug, info};
 
 use crate::api::ApiClient;
 use crate::context::build_system_prompt;
+use crate::conversation::ConversationStore;
 use crate::middleware::MiddlewareChain;
 use crate::query_router::{self, QueryRouterConfig, RetrievalStrategy};
 use crate::summarization::{self, SummarizationConfig};
@@ -14,7 +15,7 @@ use crate::tools::{ToolExecutor, ToolRegistry};
 use crate::types::{IncomingMessage, MessageKind, OutgoingMessage};
 use crate::usage::{UsageSource, UsageTracker};
 
-use meepo_knowledge::KnowledgeDb;
+use meepo_knowledge::{KnowledgeDb, MemoryEntry, parse_memory, select_relevant_memory};
 
 /// Maximum context size in bytes to prevent multi-MB context strings.
 const MAX_CONTEXT_SIZE: usize = 100_000;
@@ -36,6 +37,12 @@ pub struct Agent {
     tool_selector_config: ToolSelectorConfig,
     /// Usage tracker for cost monitoring
     usage_tracker: Option<Arc<UsageTracker>>,
+    /// Short-term per-channel conversation memory (replaces DB history loading when set)
+    conversations: Option<Arc<ConversationStore>>,
+    /// How many recent turns to inject from the conversation store
+    conversation_window: usize,
+    /// Parsed MEMORY.md and entry cap when only query-relevant memory is injected
+    selective_memory: Option<(Vec<MemoryEntry>, usize)>,
 }
 
 impl Agent {
@@ -58,6 +65,9 @@ impl Agent {
             summarization_config: SummarizationConfig::default(),
             tool_selector_config: ToolSelectorConfig::default(),
             usage_tracker: None,
+            conversations: None,
+            conversation_window: 0,
+            selective_memory: None,
         }
     }
 
@@ -91,6 +101,21 @@ impl Agent {
         self
     }
 
+    /// Inject the last `window` turns of the message's channel from `store` into the
+    /// prompt, in place of the stored history once the store has turns for that channel
+    pub fn with_conversation_store(mut self, store: Arc<ConversationStore>, window: usize) -> Self {
+        self.conversations = Some(store);
+        self.conversation_window = window;
+        self
+    }
+
+    /// Inject only the `max_entries` MEMORY.md entries most relevant to each message
+    /// instead of the whole file
+    pub fn with_selective_memory(mut self, max_entries: usize) -> Self {
+        self.selective_memory = Some((parse_memory(&self.memory), max_entries));
+        self
+    }
+
     /// Handle an incoming message and generate a response
     pub async fn handle_message(&self, msg: IncomingMessage) -> Result<OutgoingMessage> {
         info!(
@@ -127,7 +152,13 @@ impl Agent {
         let context = self.load_context(&msg, &strategy).await?;
 
         // Build system prompt
-        let system_prompt = build_system_prompt(&self.soul, &self.memory, &context);
+        let memory = match &self.selective_memory {
+            Some((entries, max_entries)) => {
+                select_relevant_memory(entries, &msg.content, *max_entries)
+            }
+            None => self.memory.clone(),
+        };
+        let system_prompt = build_system_prompt(&self.soul, &memory, &context);
 
         // Get tool definitions (with optional LLM selection)
         let all_tools = self.tools.list_tools();
@@ -157,6 +188,7 @@ impl Agent {
                         ),
                         channel: msg.channel,
                         reply_to: Some(msg.id),
+                        thread_id: msg.thread_id,
                         kind: MessageKind::Response,
                     });
                 }
@@ -206,6 +238,16 @@ impl Agent {
             .await
             .context("Failed to store response")?;
 
+        if let Some(store) = &self.conversations {
+            store.record(
+                &msg.channel,
+                msg.thread_id.as_deref(),
+                &msg.sender,
+                &msg.content,
+                &response_text,
+            );
+        }
+
         info!(
             "Generated response ({} chars, {} tokens)",
             response_text.len(),
@@ -216,6 +258,7 @@ impl Agent {
             content: response_text,
             channel: msg.channel,
             reply_to: Some(msg.id),
+            thread_id: msg.thread_id,
             kind: MessageKind::Response,
         })
     }
@@ -233,8 +276,23 @@ impl Agent {
         let mut context = String::new();
         let mut truncated = false;
 
-        // Add recent conversation history from this channel (with summarization)
-        if strategy.load_history {
+        // Short-term memory of this channel from the conversation store, trimmed to a
+        // quarter of the context budget. The store only caches what this process has
+        // seen, so stored history is used while it has nothing for the channel yet.
+        let cached_turns = match &self.conversations {
+            Some(store) if strategy.load_history => store.recent_context_within(
+                &msg.channel,
+                msg.thread_id.as_deref(),
+                self.conversation_window,
+                MAX_CONTEXT_SIZE / 4,
+            ),
+            _ => String::new(),
+        };
+
+        if !cached_turns.is_empty() {
+            context.push_str(&cached_turns);
+        } else if strategy.load_history {
+            // Add recent conversation history from this channel (with summarization)
             let recent = self
                 .db
                 .get_recent_conversations(Some(&msg.channel.to_string()), 30)
@@ -326,7 +384,8 @@ impl Agent {
 
         // Final truncation guard: hard-cap the string if it still exceeds the limit
         if context.len() > MAX_CONTEXT_SIZE {
-            context.truncate(MAX_CONTEXT_SIZE);
+            // Byte budget, so cut at the nearest char boundary below it
+            context.truncate(context.floor_char_boundary(MAX_CONTEXT_SIZE));
             context.push_str("\n[Context truncated]");
             truncated = true;
         }
@@ -414,6 +473,9 @@ mod tests {
             content: "Hello meepo".to_string(),
             channel: ChannelType::Internal,
             timestamp: Utc::now(),
+            priority: crate::types::Priority::Normal,
+            command: None,
+            thread_id: None,
         };
 
         let strategy = RetrievalStrategy {
@@ -429,4 +491,88 @@ mod tests {
         // Context is a String — load_context should succeed without panic
         assert!(context.len() <= 100_000, "Context unexpectedly large");
     }
+
+    #[tokio::test]
+    async fn test_conversation_store_falls_back_to_stored_history() {
+        let (agent, _temp) = create_test_agent();
+        let store = Arc::new(ConversationStore::new(10));
+        let agent = agent.with_conversation_store(store.clone(), 10);
+        agent
+            .db
+            .insert_conversation("slack", "alice", "from before the restart", None)
+            .await
+            .unwrap();
+
+        let msg = IncomingMessage {
+            id: "test-1".to_string(),
+            sender: "alice".to_string(),
+            content: "Hello meepo".to_string(),
+            channel: ChannelType::Slack,
+            timestamp: Utc::now(),
+            priority: crate::types::Priority::Normal,
+            command: None,
+            thread_id: None,
+        };
+        let strategy = RetrievalStrategy {
+            complexity: query_router::QueryComplexity::SingleStep,
+            search_knowledge: false,
+            search_web: false,
+            load_history: true,
+            graph_expand: false,
+            corrective_rag: false,
+            knowledge_limit: 5,
+        };
+
+        // Empty store: stored history is used
+        let context = agent.load_context(&msg, &strategy).await.unwrap();
+        assert!(context.contains("from before the restart"));
+
+        // Once the store has turns for the channel, they replace it
+        store.record(&ChannelType::Slack, None, "alice", "hi", "hello there");
+        let context = agent.load_context(&msg, &strategy).await.unwrap();
+        assert!(context.contains("hello there"));
+        assert!(!context.contains("from before the restart"));
+    }
+
+    #[tokio::test]
+    async fn test_conversation_store_is_per_thread() {
+        let (agent, _temp) = create_test_agent();
+        let store = Arc::new(ConversationStore::new(10));
+        let agent = agent.with_conversation_store(store.clone(), 10);
+        store.record(
+            &ChannelType::Slack,
+            Some("1700.1"),
+            "alice",
+            "hi",
+            "in the thread",
+        );
+
+        let msg = |thread: Option<&str>| IncomingMessage {
+            id: "test-1".to_string(),
+            sender: "alice".to_string(),
+            content: "Hello meepo".to_string(),
+            channel: ChannelType::Slack,
+            timestamp: Utc::now(),
+            priority: crate::types::Priority::Normal,
+            command: None,
+            thread_id: thread.map(str::to_string),
+        };
+        let strategy = RetrievalStrategy {
+            complexity: query_router::QueryComplexity::SingleStep,
+            search_knowledge: false,
+            search_web: false,
+            load_history: true,
+            graph_expand: false,
+            corrective_rag: false,
+            knowledge_limit: 5,
+        };
+
+        let context = agent
+            .load_context(&msg(Some("1700.1")), &strategy)
+            .await
+            .unwrap();
+        assert!(context.contains("in the thread"));
+        let context = agent.load_context(&msg(None), &strategy).await.unwrap();
+        assert!(!context.contains("in the thread"));
+    }
 }
@@ -178,8 +178,7 @@ impl ApiClient {
                             }
                         };
 
-                        if result_content.len() > MAX_TOOL_OUTPUT {
-                            result_content.truncate(MAX_TOOL_OUTPUT);
+                        if crate::text::truncate_string(&mut result_content, MAX_TOOL_OUTPUT) {
                             result_content.push_str("\n[Output truncated]");
                         }
 
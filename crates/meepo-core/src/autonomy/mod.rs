@@ -17,7 +17,8 @@ use tracing::{debug, error, info, warn};
 
 use crate::agent::Agent;
 use crate::notifications::{NotificationService, NotifyEvent};
-use crate::types::{ChannelType, IncomingMessage, MessageKind, OutgoingMessage};
+use crate::text::truncate_chars;
+use crate::types::{ChannelType, IncomingMessage, MessageKind, OutgoingMessage, Priority};
 use meepo_knowledge::KnowledgeDb;
 use meepo_scheduler::WatcherEvent;
 
@@ -246,14 +247,16 @@ impl AutonomousLoop {
         }
     }
 
-    /// Drain all pending inputs from channels without blocking
+    /// Drain all pending inputs from channels without blocking. High-priority
+    /// user messages come first, the rest in arrival order.
     fn drain_inputs(&mut self) -> Vec<LoopInput> {
-        let mut inputs = Vec::new();
-
-        // Drain user messages
+        let mut messages = Vec::new();
         while let Ok(msg) = self.message_rx.try_recv() {
-            inputs.push(LoopInput::UserMessage(msg));
+            messages.push(msg);
         }
+        // Stable, so each priority level stays FIFO
+        messages.sort_by_key(|msg| msg.priority != Priority::High);
+        let mut inputs: Vec<LoopInput> = messages.into_iter().map(LoopInput::UserMessage).collect();
 
         // Drain watcher events
         while let Ok(event) = self.watcher_rx.try_recv() {
@@ -334,6 +337,9 @@ impl AutonomousLoop {
             content: prompt,
             channel: ChannelType::Internal,
             timestamp: now,
+            priority: Priority::Normal,
+            command: None,
+            thread_id: None,
         };
 
         match self.agent.handle_message(msg).await {
@@ -413,6 +419,9 @@ impl AutonomousLoop {
             content: prompt,
             channel: ChannelType::Internal,
             timestamp: chrono::Utc::now(),
+            priority: Priority::Normal,
+            command: None,
+            thread_id: None,
         };
 
         match self.agent.handle_message(msg).await {
@@ -446,7 +455,7 @@ impl AutonomousLoop {
                                 info!(
                                     "Executing goal action for {}: {}",
                                     action.goal_id,
-                                    &action_prompt[..action_prompt.len().min(100)]
+                                    truncate_chars(action_prompt, 100)
                                 );
 
                                 // Notify about autonomous action
@@ -455,7 +464,7 @@ impl AutonomousLoop {
                                         description: format!(
                                             "Goal [{}]: {}",
                                             action.goal_id,
-                                            &action_prompt[..action_prompt.len().min(200)]
+                                            truncate_chars(action_prompt, 200)
                                         ),
                                     })
                                     .await;
@@ -466,6 +475,9 @@ impl AutonomousLoop {
                                     content: action_prompt.clone(),
                                     channel: ChannelType::Internal,
                                     timestamp: chrono::Utc::now(),
+                                    priority: Priority::Normal,
+                                    command: None,
+                                    thread_id: None,
                                 };
 
                                 if let Err(e) = self.agent.handle_message(action_msg).await {
@@ -506,6 +518,7 @@ impl AutonomousLoop {
                 content: String::new(), // each channel decides what to show
                 channel: msg.channel.clone(),
                 reply_to: Some(msg.id.clone()),
+                thread_id: msg.thread_id.clone(),
                 kind: MessageKind::Acknowledgment,
             };
             let _ = self.response_tx.send(ack).await;
@@ -534,14 +547,15 @@ impl AutonomousLoop {
     async fn handle_watcher_event(&self, event: WatcherEvent) {
         info!(
             "Processing watcher event: {} from {}",
-            event.kind, event.watcher_id
+            event.kind(),
+            event.watcher_id
         );
 
         // Notify user that a watcher triggered
         self.notifier
             .notify(NotifyEvent::WatcherTriggered {
                 watcher_id: event.watcher_id.clone(),
-                kind: event.kind.clone(),
+                kind: event.kind(),
                 payload: event.payload.to_string(),
             })
             .await;
@@ -561,12 +575,9 @@ impl AutonomousLoop {
 
         // Build prompt with the watcher's action context
         let content = if action.is_empty() {
-            format!("Watcher {} triggered: {}", event.watcher_id, event.payload)
+            event.to_string()
         } else {
-            format!(
-                "Watcher {} triggered: {}\nYour requested action: {}",
-                event.watcher_id, event.payload, action
-            )
+            format!("{}\nYour requested action: {}", event, action)
         };
 
         let msg = IncomingMessage {
@@ -575,6 +586,9 @@ impl AutonomousLoop {
             content,
             channel: reply_channel.clone(),
             timestamp: chrono::Utc::now(),
+            priority: Priority::Normal,
+            command: None,
+            thread_id: None,
         };
 
         match self.agent.handle_message(msg).await {
@@ -591,7 +605,8 @@ impl AutonomousLoop {
                     .notify(NotifyEvent::Error {
                         context: format!(
                             "Handling watcher event {} from {}",
-                            event.kind, event.watcher_id
+                            event.kind(),
+                            event.watcher_id
                         ),
                         error: e.to_string(),
                     })
@@ -672,6 +687,9 @@ mod tests {
                 content: "hello".into(),
                 channel: ChannelType::Discord,
                 timestamp: chrono::Utc::now(),
+                priority: Priority::Normal,
+                command: None,
+                thread_id: None,
             })
             .await
             .unwrap();
@@ -698,6 +716,63 @@ mod tests {
         assert_eq!(inputs.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_drain_inputs_high_priority_first() {
+        let (agent, db, _tmp) = setup();
+        let (msg_tx, msg_rx) = mpsc::channel(16);
+        let (_, watcher_rx) = mpsc::unbounded_channel();
+        let (resp_tx, _) = mpsc::channel(16);
+        let notifier = NotificationService::disabled(resp_tx.clone());
+
+        for (id, priority) in [
+            ("normal-1", Priority::Normal),
+            ("high", Priority::High),
+            ("normal-2", Priority::Normal),
+        ] {
+            msg_tx
+                .send(IncomingMessage {
+                    id: id.into(),
+                    sender: "user".into(),
+                    content: "hello".into(),
+                    channel: ChannelType::Slack,
+                    timestamp: chrono::Utc::now(),
+                    priority,
+                    command: None,
+                    thread_id: None,
+                })
+                .await
+                .unwrap();
+        }
+
+        let mut loop_ = AutonomousLoop::new(
+            agent,
+            db,
+            AutonomyConfig {
+                enabled: true,
+                tick_interval_secs: 30,
+                max_goals: 50,
+                send_acknowledgments: true,
+                daily_plan_hour: 7,
+                max_calls_per_minute: 10,
+            },
+            msg_rx,
+            watcher_rx,
+            resp_tx,
+            notifier,
+            AutonomousLoop::create_wake_handle(),
+        );
+
+        let ids: Vec<String> = loop_
+            .drain_inputs()
+            .into_iter()
+            .map(|input| match input {
+                LoopInput::UserMessage(msg) => msg.id,
+                LoopInput::WatcherEvent(_) => unreachable!(),
+            })
+            .collect();
+        assert_eq!(ids, ["high", "normal-1", "normal-2"]);
+    }
+
     #[test]
     fn test_rate_limiter_allows_within_limit() {
         let mut limiter = RateLimiter::new(3, Duration::from_secs(60));
@@ -2,6 +2,14 @@
 
 use tracing::debug;
 
+/// Rough characters-per-token ratio for English text, used in place of a real tokenizer
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Estimate how many tokens `text` costs (about four characters per token, rounded up)
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
 /// Build complete system prompt from components
 pub fn build_system_prompt(soul: &str, memory: &str, extra_context: &str) -> String {
     let mut prompt = String::new();
@@ -45,6 +53,97 @@ pub fn build_system_prompt(soul: &str, memory: &str, extra_context: &str) -> Str
     prompt
 }
 
+/// Build a system prompt whose estimated size stays within `max_tokens`
+///
+/// SOUL, the timestamp and the instructions are always kept in full. MEMORY is
+/// fitted next, dropping its oldest (first) lines, and the extra context gets
+/// whatever budget is left, dropping its last lines. A section that had to be
+/// cut carries a note saying how much was left out.
+pub fn build_system_prompt_budgeted(
+    soul: &str,
+    memory: &str,
+    extra_context: &str,
+    max_tokens: usize,
+) -> String {
+    let mut remaining =
+        max_tokens.saturating_sub(estimate_tokens(&build_system_prompt(soul, "", "")));
+    let memory = fit_section("MEMORY", memory, Keep::Tail, &mut remaining);
+    let extra_context = fit_section("CONTEXT", extra_context, Keep::Head, &mut remaining);
+    build_system_prompt(soul, &memory, &extra_context)
+}
+
+/// Which end of a section survives truncation
+#[derive(Clone, Copy)]
+enum Keep {
+    Head,
+    Tail,
+}
+
+/// Cut `text` down to whole lines that fit in `remaining` tokens, including its
+/// `# {name}` header, and deduct what was used
+fn fit_section(name: &str, text: &str, keep: Keep, remaining: &mut usize) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+    let overhead = estimate_tokens(&format!("# {}\n\n\n\n", name));
+    let full = overhead + estimate_tokens(text);
+    if full <= *remaining {
+        *remaining -= full;
+        return text.to_string();
+    }
+
+    let lines: Vec<&str> = text.lines().collect();
+    let total = lines.len();
+    let note = |omitted: usize| {
+        format!(
+            "[{} truncated to fit the context window: {} of {} lines omitted]",
+            name, omitted, total
+        )
+    };
+    // Reserve room for the longest possible note so the final one always fits
+    let note_chars = note(total).chars().count() + 1;
+    let Some(budget) = remaining.checked_sub(overhead) else {
+        return String::new();
+    };
+    if note_chars.div_ceil(CHARS_PER_TOKEN) > budget {
+        return String::new();
+    }
+
+    let kept = match keep {
+        Keep::Head => lines_that_fit(lines.iter(), note_chars, budget),
+        Keep::Tail => lines_that_fit(lines.iter().rev(), note_chars, budget),
+    };
+
+    let note = note(total - kept);
+    let fitted = match keep {
+        Keep::Head => format!("{}\n{}", lines[..kept].join("\n"), note),
+        Keep::Tail => format!("{}\n{}", note, lines[total - kept..].join("\n")),
+    };
+    debug!(
+        "Truncated {} to {} of {} lines to fit the prompt budget",
+        name, kept, total
+    );
+    *remaining -= overhead + estimate_tokens(&fitted);
+    fitted.trim().to_string()
+}
+
+/// Count how many of `lines`, taken in order, fit in `budget` tokens after `chars` already used
+fn lines_that_fit<'a>(
+    lines: impl Iterator<Item = &'a &'a str>,
+    mut chars: usize,
+    budget: usize,
+) -> usize {
+    let mut kept = 0;
+    for line in lines {
+        chars += line.chars().count() + 1;
+        if chars.div_ceil(CHARS_PER_TOKEN) > budget {
+            break;
+        }
+        kept += 1;
+    }
+    kept
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,6 +163,61 @@ mod tests {
         assert!(prompt.contains("Rust"));
     }
 
+    #[test]
+    fn test_build_system_prompt_budgeted_fits() {
+        let soul = "I am meepo";
+        let memory = "The user likes Rust";
+        let context = "Recent conversation about async programming";
+
+        let budgeted = build_system_prompt_budgeted(soul, memory, context, 10_000);
+        assert!(budgeted.contains(memory));
+        assert!(budgeted.contains(context));
+        assert!(!budgeted.contains("truncated"));
+    }
+
+    #[test]
+    fn test_build_system_prompt_budgeted_truncates() {
+        let soul = "I am meepo";
+        let memory: String = (1..=200)
+            .map(|i| format!("- memory entry {}\n", i))
+            .collect();
+        let context: String = (1..=200).map(|i| format!("context line {}\n", i)).collect();
+        let max_tokens = estimate_tokens(&build_system_prompt(soul, "", "")) + 300;
+
+        let prompt = build_system_prompt_budgeted(soul, &memory, &context, max_tokens);
+        assert!(estimate_tokens(&prompt) <= max_tokens);
+        assert!(prompt.contains("I am meepo"));
+        assert!(prompt.contains("INSTRUCTIONS"));
+
+        // The newest memory entries survive, the oldest are dropped
+        assert!(prompt.contains("- memory entry 200\n"));
+        assert!(!prompt.contains("- memory entry 1\n"));
+        assert!(prompt.contains("[MEMORY truncated to fit the context window:"));
+
+        // Memory takes priority, so there is no room left for the context
+        assert!(!prompt.contains("context line 1\n"));
+        assert!(!prompt.contains("# CONTEXT"));
+    }
+
+    #[test]
+    fn test_build_system_prompt_budgeted_keeps_context_head() {
+        let context: String = (1..=100).map(|i| format!("context line {}\n", i)).collect();
+        let max_tokens = estimate_tokens(&build_system_prompt("", "", "")) + 100;
+
+        let prompt = build_system_prompt_budgeted("", "", &context, max_tokens);
+        assert!(estimate_tokens(&prompt) <= max_tokens);
+        assert!(prompt.contains("context line 1\n"));
+        assert!(!prompt.contains("context line 100"));
+        assert!(prompt.contains("[CONTEXT truncated to fit the context window:"));
+    }
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+    }
+
     #[test]
     fn test_build_system_prompt_empty() {
         let prompt = build_system_prompt("", "", "");
@@ -0,0 +1,291 @@
+//! Short-term conversational memory scoped per channel and thread
+//!
+//! `ConversationStore` keeps the last few incoming/outgoing exchanges for each
+//! channel (and optionally each thread within it), so the agent can see what
+//! was just said in the same conversation without the caller managing history.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::{debug, warn};
+
+use crate::types::ChannelType;
+
+/// One incoming message and the agent's reply to it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConversationTurn {
+    pub sender: String,
+    pub incoming: String,
+    pub outgoing: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl ConversationTurn {
+    fn format(&self) -> String {
+        format!(
+            "{}: {}\nmeepo: {}\n",
+            self.sender, self.incoming, self.outgoing
+        )
+    }
+}
+
+/// Persisted form of the store: one entry per channel/thread
+#[derive(Serialize, Deserialize)]
+struct StoredThread {
+    channel: String,
+    thread: Option<String>,
+    turns: Vec<ConversationTurn>,
+}
+
+type ThreadKey = (String, Option<String>);
+
+/// Bounded per-channel/thread record of recent conversation turns
+pub struct ConversationStore {
+    max_turns: usize,
+    threads: Mutex<HashMap<ThreadKey, VecDeque<ConversationTurn>>>,
+    persist_path: Option<PathBuf>,
+}
+
+impl ConversationStore {
+    /// Create an in-memory store keeping at most `max_turns` turns per thread
+    pub fn new(max_turns: usize) -> Self {
+        Self {
+            max_turns: max_turns.max(1),
+            threads: Mutex::new(HashMap::new()),
+            persist_path: None,
+        }
+    }
+
+    /// Persist the store as JSON at `path`, loading any turns already saved there
+    pub fn with_persistence(mut self, path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        if path.exists() {
+            let threads = load_threads(&path, self.max_turns)?;
+            debug!(
+                "Loaded {} conversation thread(s) from {}",
+                threads.len(),
+                path.display()
+            );
+            self.threads = Mutex::new(threads);
+        }
+        self.persist_path = Some(path);
+        Ok(self)
+    }
+
+    /// Record an exchange, evicting the oldest turn once the thread is full
+    pub fn record(
+        &self,
+        channel: &ChannelType,
+        thread: Option<&str>,
+        sender: &str,
+        incoming: &str,
+        outgoing: &str,
+    ) {
+        let turn = ConversationTurn {
+            sender: sender.to_string(),
+            incoming: incoming.to_string(),
+            outgoing: outgoing.to_string(),
+            timestamp: Utc::now(),
+        };
+
+        let Ok(mut threads) = self.threads.lock() else {
+            warn!("Conversation store lock poisoned");
+            return;
+        };
+        let turns = threads.entry(key(channel, thread)).or_default();
+        turns.push_back(turn);
+        while turns.len() > self.max_turns {
+            turns.pop_front();
+        }
+
+        if let Some(path) = &self.persist_path
+            && let Err(e) = save_threads(path, &threads)
+        {
+            warn!("Failed to persist conversation store: {}", e);
+        }
+    }
+
+    /// The last `n` turns of a thread, oldest first
+    pub fn recent(
+        &self,
+        channel: &ChannelType,
+        thread: Option<&str>,
+        n: usize,
+    ) -> Vec<ConversationTurn> {
+        let Ok(threads) = self.threads.lock() else {
+            return Vec::new();
+        };
+        threads
+            .get(&key(channel, thread))
+            .map(|turns| {
+                let skip = turns.len().saturating_sub(n);
+                turns.iter().skip(skip).cloned().collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Format the last `n` turns of a thread for injection into the prompt context
+    pub fn recent_context(&self, channel: &ChannelType, thread: Option<&str>, n: usize) -> String {
+        self.recent_context_within(channel, thread, n, usize::MAX)
+    }
+
+    /// Like `recent_context`, but drops the oldest turns until the text fits in
+    /// `max_chars`. Returns an empty string if not even the latest turn fits.
+    pub fn recent_context_within(
+        &self,
+        channel: &ChannelType,
+        thread: Option<&str>,
+        n: usize,
+        max_chars: usize,
+    ) -> String {
+        const HEADER: &str = "## Recent Conversation\n\n";
+
+        let turns = self.recent(channel, thread, n);
+        let formatted: Vec<String> = turns.iter().map(ConversationTurn::format).collect();
+
+        // Keep the newest turns that fit in the budget
+        let mut used = HEADER.len();
+        let mut start = formatted.len();
+        while start > 0 && used + formatted[start - 1].len() <= max_chars {
+            start -= 1;
+            used += formatted[start].len();
+        }
+
+        if start == formatted.len() {
+            return String::new();
+        }
+        if start > 0 {
+            debug!("Trimmed {} older conversation turn(s) to fit budget", start);
+        }
+
+        let mut context = String::from(HEADER);
+        for turn in &formatted[start..] {
+            context.push_str(turn);
+        }
+        context
+    }
+}
+
+fn key(channel: &ChannelType, thread: Option<&str>) -> ThreadKey {
+    (channel.to_string(), thread.map(str::to_string))
+}
+
+fn load_threads(
+    path: &Path,
+    max_turns: usize,
+) -> Result<HashMap<ThreadKey, VecDeque<ConversationTurn>>> {
+    let data = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read conversation store {}", path.display()))?;
+    let stored: Vec<StoredThread> =
+        serde_json::from_str(&data).context("Failed to parse conversation store")?;
+
+    Ok(stored
+        .into_iter()
+        .map(|t| {
+            let skip = t.turns.len().saturating_sub(max_turns);
+            let turns = t.turns.into_iter().skip(skip).collect();
+            ((t.channel, t.thread), turns)
+        })
+        .collect())
+}
+
+fn save_threads(
+    path: &Path,
+    threads: &HashMap<ThreadKey, VecDeque<ConversationTurn>>,
+) -> Result<()> {
+    let stored: Vec<StoredThread> = threads
+        .iter()
+        .map(|((channel, thread), turns)| StoredThread {
+            channel: channel.clone(),
+            thread: thread.clone(),
+            turns: turns.iter().cloned().collect(),
+        })
+        .collect();
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let data = serde_json::to_string(&stored)?;
+    std::fs::write(path, data)
+        .with_context(|| format!("Failed to write conversation store {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounded_and_scoped() {
+        let store = ConversationStore::new(2);
+        store.record(&ChannelType::Discord, None, "alice", "one", "1");
+        store.record(&ChannelType::Discord, None, "alice", "two", "2");
+        store.record(&ChannelType::Discord, None, "alice", "three", "3");
+        store.record(&ChannelType::Slack, Some("t1"), "bob", "hi", "hello");
+
+        let turns = store.recent(&ChannelType::Discord, None, 10);
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns[0].incoming, "two");
+        assert_eq!(turns[1].incoming, "three");
+
+        assert!(store.recent(&ChannelType::Slack, None, 10).is_empty());
+        assert_eq!(store.recent(&ChannelType::Slack, Some("t1"), 10).len(), 1);
+    }
+
+    #[test]
+    fn test_recent_context_trims_to_budget() {
+        let store = ConversationStore::new(10);
+        store.record(
+            &ChannelType::Discord,
+            None,
+            "alice",
+            "first question",
+            "first answer",
+        );
+        store.record(
+            &ChannelType::Discord,
+            None,
+            "alice",
+            "second question",
+            "second answer",
+        );
+
+        let full = store.recent_context(&ChannelType::Discord, None, 10);
+        assert!(full.starts_with("## Recent Conversation"));
+        assert!(full.contains("alice: first question\nmeepo: first answer"));
+
+        // Budget only fits the header plus the newest turn
+        let trimmed = store.recent_context_within(&ChannelType::Discord, None, 10, full.len() - 1);
+        assert!(!trimmed.contains("first question"));
+        assert!(trimmed.contains("second question"));
+
+        assert!(
+            store
+                .recent_context_within(&ChannelType::Discord, None, 10, 10)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_persistence_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("conversations.json");
+
+        let store = ConversationStore::new(5).with_persistence(&path).unwrap();
+        store.record(
+            &ChannelType::IMessage,
+            Some("chat-1"),
+            "carol",
+            "ping",
+            "pong",
+        );
+        drop(store);
+
+        let reloaded = ConversationStore::new(5).with_persistence(&path).unwrap();
+        let turns = reloaded.recent(&ChannelType::IMessage, Some("chat-1"), 5);
+        assert_eq!(turns.len(), 1);
+        assert_eq!(turns[0].outgoing, "pong");
+    }
+}
@@ -6,11 +6,13 @@
 //! - Comprehensive tool system with macOS integration, code execution, memory, and more
 //! - Context loading from SOUL and MEMORY files
 //! - Integration with knowledge graph and watcher scheduler
+//! - Safe mode for observe-only runs
 
 pub mod agent;
 pub mod api;
 pub mod autonomy;
 pub mod context;
+pub mod conversation;
 pub mod corrective_rag;
 pub mod middleware;
 pub mod notifications;
@@ -18,9 +20,11 @@ pub mod orchestrator;
 pub mod platform;
 pub mod providers;
 pub mod query_router;
+pub mod safe_mode;
 pub mod skills;
 pub mod summarization;
 pub mod tavily;
+pub mod text;
 pub mod tool_selector;
 pub mod tools;
 pub mod types;
@@ -30,7 +34,8 @@ pub mod usage;
 pub use agent::Agent;
 pub use api::{ApiClient, ApiMessage, ApiResponse, ContentBlock, MessageContent, ToolDefinition};
 pub use autonomy::{AutonomousLoop, AutonomyConfig};
-pub use context::build_system_prompt;
+pub use context::{build_system_prompt, build_system_prompt_budgeted, estimate_tokens};
+pub use conversation::{ConversationStore, ConversationTurn};
 pub use corrective_rag::CorrectiveRagConfig;
 pub use middleware::{AgentMiddleware, MiddlewareChain, MiddlewareContext};
 pub use notifications::{NotificationService, NotifyConfig, NotifyEvent};
@@ -40,7 +45,9 @@ pub use orchestrator::{
 };
 pub use providers::{ChatMessage, ChatResponse, LlmProvider, ModelRouter};
 pub use query_router::{QueryComplexity, QueryRouterConfig, RetrievalStrategy};
+pub use safe_mode::SafeMode;
 pub use summarization::SummarizationConfig;
+pub use text::{truncate_chars, truncate_string};
 pub use tool_selector::ToolSelectorConfig;
 pub use tools::{ToolExecutor, ToolHandler, ToolRegistry};
 pub use types::{ChannelType, IncomingMessage, MessageKind, OutgoingMessage};
@@ -318,14 +318,12 @@ impl AgentMiddleware for ToolOutputTruncationMiddleware {
         mut result: String,
         _ctx: &MiddlewareContext,
     ) -> Result<String> {
-        if result.len() > self.max_chars {
+        let original_len = result.len();
+        if crate::text::truncate_string(&mut result, self.max_chars) {
             debug!(
-                "[truncation] Truncating {} output from {} to {} chars",
-                tool_name,
-                result.len(),
-                self.max_chars
+                "[truncation] Truncating {} output from {} bytes to {} chars",
+                tool_name, original_len, self.max_chars
             );
-            result.truncate(self.max_chars);
             result.push_str("\n[Output truncated]");
         }
         Ok(result)
@@ -8,6 +8,7 @@ use chrono::{NaiveTime, Utc};
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
+use crate::text::truncate_chars;
 use crate::types::{ChannelType, MessageKind, OutgoingMessage};
 
 /// Which kind of event triggered this notification
@@ -143,6 +144,7 @@ impl NotificationService {
             content,
             channel: self.config.channel.clone(),
             reply_to: None,
+            thread_id: None,
             kind: MessageKind::Response,
         };
 
@@ -192,7 +194,7 @@ impl NotificationService {
                 format!(
                     "🤖 Starting background task\n[{}] {}",
                     task_id,
-                    truncate(description, 200)
+                    truncate_chars(description, 200)
                 )
             }
             NotifyEvent::TaskCompleted {
@@ -203,8 +205,8 @@ impl NotificationService {
                 format!(
                     "✅ Task completed\n[{}] {}\n\nResult: {}",
                     task_id,
-                    truncate(description, 150),
-                    truncate(result_preview, 300)
+                    truncate_chars(description, 150),
+                    truncate_chars(result_preview, 300)
                 )
             }
             NotifyEvent::TaskFailed {
@@ -215,8 +217,8 @@ impl NotificationService {
                 format!(
                     "❌ Task failed\n[{}] {}\n\nError: {}",
                     task_id,
-                    truncate(description, 150),
-                    truncate(error, 200)
+                    truncate_chars(description, 150),
+                    truncate_chars(error, 200)
                 )
             }
             NotifyEvent::WatcherTriggered {
@@ -228,20 +230,20 @@ impl NotificationService {
                     "👁 Watcher triggered\n[{}] {}\n{}",
                     watcher_id,
                     kind,
-                    truncate(payload, 300)
+                    truncate_chars(payload, 300)
                 )
             }
             NotifyEvent::AutonomousAction { description } => {
                 format!(
                     "🧠 Taking autonomous action\n{}",
-                    truncate(description, 400)
+                    truncate_chars(description, 400)
                 )
             }
             NotifyEvent::Error { context, error } => {
                 format!(
                     "⚠️ Error: {}\n{}",
-                    truncate(context, 100),
-                    truncate(error, 300)
+                    truncate_chars(context, 100),
+                    truncate_chars(error, 300)
                 )
             }
             NotifyEvent::BudgetWarning {
@@ -275,28 +277,15 @@ impl NotificationService {
     }
 }
 
-/// Truncate a string to max_len, appending "..." if truncated
-fn truncate(s: &str, max_len: usize) -> &str {
-    if s.len() <= max_len {
-        s
-    } else {
-        // Find a safe char boundary
-        let mut end = max_len;
-        while end > 0 && !s.is_char_boundary(end) {
-            end -= 1;
-        }
-        &s[..end]
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_truncate() {
-        assert_eq!(truncate("hello", 10), "hello");
-        assert_eq!(truncate("hello world", 5), "hello");
+        assert_eq!(truncate_chars("hello", 10), "hello");
+        assert_eq!(truncate_chars("hello world", 5), "hello");
+        assert_eq!(truncate_chars("任务完成了", 2), "任务");
     }
 
     #[test]
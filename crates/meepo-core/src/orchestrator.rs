@@ -221,6 +221,7 @@ impl TaskOrchestrator {
             content: message.to_string(),
             channel: channel.clone(),
             reply_to: reply_to.clone(),
+            thread_id: None,
             kind: MessageKind::Response,
         };
         if let Err(e) = self.progress_tx.send(msg).await {
@@ -332,6 +333,7 @@ impl TaskOrchestrator {
                     content: format!("Started {} background tasks...", task_count),
                     channel: channel.clone(),
                     reply_to: reply_to.clone(),
+                    thread_id: None,
                     kind: MessageKind::Response,
                 })
                 .await;
@@ -364,6 +366,7 @@ impl TaskOrchestrator {
                                 content: update,
                                 channel: channel.clone(),
                                 reply_to: reply_to.clone(),
+                                thread_id: None,
                                 kind: MessageKind::Response,
                             })
                             .await;
@@ -375,6 +378,7 @@ impl TaskOrchestrator {
                                 content: format!("A background task panicked: {}", e),
                                 channel: channel.clone(),
                                 reply_to: reply_to.clone(),
+                                thread_id: None,
                                 kind: MessageKind::Response,
                             })
                             .await;
@@ -394,6 +398,7 @@ impl TaskOrchestrator {
                     content: format!("All background tasks complete:\n\n{}", summary),
                     channel: channel.clone(),
                     reply_to: reply_to.clone(),
+                    thread_id: None,
                     kind: MessageKind::Response,
                 })
                 .await;
@@ -444,7 +449,7 @@ mod tests {
     pub fn make_registry_with_tools(names: &[&str]) -> Arc<ToolRegistry> {
         let mut registry = ToolRegistry::new();
         for name in names {
-            registry.register(Arc::new(DummyTool::new(name)));
+            registry.register(Arc::new(DummyTool::new(name))).unwrap();
         }
         Arc::new(registry)
     }
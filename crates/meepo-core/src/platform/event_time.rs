@@ -0,0 +1,191 @@
+//! Event time parsing shared by the calendar providers
+//!
+//! Times are resolved to an absolute timestamp in Rust before any script is
+//! built: ISO 8601 and a few fixed formats first, then natural language
+//! ("tomorrow 3pm", "friday 10:30") via `interim`. Calendar scripts only
+//! ever see exact dates, never free text they might misread or reject.
+
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+
+/// Exact formats tried before natural language, read in the local timezone
+const FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %H:%M",
+    "%B %d, %Y %I:%M %p",
+    "%B %d, %Y at %I:%M %p",
+    "%A, %B %d, %Y at %I:%M:%S %p",
+];
+
+/// Resolve an event time given by the user or agent to the local timezone
+pub fn resolve_event_time(input: &str) -> Result<DateTime<Local>> {
+    parse_event_time(input, &Local::now())
+}
+
+/// Resolve `input` relative to `now`, in `now`'s timezone. Input that names a
+/// day but no time of day ("tomorrow", "next week") is rejected rather than
+/// guessed, with an error the agent can turn into a question for the user.
+pub fn parse_event_time<Tz: TimeZone>(input: &str, now: &DateTime<Tz>) -> Result<DateTime<Tz>> {
+    let trimmed = input.trim();
+    let tz = now.timezone();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(trimmed) {
+        return Ok(dt.with_timezone(&tz));
+    }
+    if let Some(naive) = FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(trimmed, format).ok())
+    {
+        return tz
+            .from_local_datetime(&naive)
+            .earliest()
+            .ok_or_else(|| anyhow!("'{}' does not exist in the local timezone", trimmed));
+    }
+
+    let words = natural_words(trimmed);
+    let parsed = interim::parse_date_string(&words.join(" "), now.clone(), interim::Dialect::Us)
+        .map_err(|_| {
+            anyhow!(
+                "Could not understand the time '{}'; ask the user to clarify, or use a form \
+                 like \"tomorrow 3pm\", \"friday 10:30\", or 2025-03-14T15:00",
+                trimmed
+            )
+        })?;
+    if !words.iter().any(|word| is_time_of_day(word)) {
+        return Err(anyhow!(
+            "'{}' doesn't say what time of day; ask the user for a time, e.g. \"{} 3pm\"",
+            trimmed,
+            trimmed
+        ));
+    }
+    Ok(parsed)
+}
+
+/// Normalise free text for `interim`: lowercase (it only handles lowercase),
+/// no commas or filler words, 12-hour times as 24-hour ones (it reads a bare
+/// "12pm" as midnight), and a leading time moved after the day, since
+/// "3pm tomorrow" would otherwise silently mean today
+fn natural_words(input: &str) -> Vec<String> {
+    let lowered = input.to_lowercase().replace(',', " ");
+    let mut words: Vec<String> = Vec::new();
+    for word in lowered.split_whitespace() {
+        let word = match word {
+            "at" | "on" => continue,
+            "noon" => "12pm",
+            "midnight" => "12am",
+            word => word,
+        };
+        match words.last_mut() {
+            Some(last) if matches!(word, "am" | "pm") && is_clock(last) => last.push_str(word),
+            _ => words.push(word.to_string()),
+        }
+    }
+    for word in &mut words {
+        if let Some(clock) = to_24_hour(word) {
+            *word = clock;
+        }
+    }
+    if words.len() > 1 && is_time_of_day(&words[0]) {
+        words.rotate_left(1);
+    }
+    words
+}
+
+/// "3pm" -> "15:00", "12:30am" -> "00:30"; `None` for anything else
+fn to_24_hour(word: &str) -> Option<String> {
+    let (clock, pm) = match word.strip_suffix("pm") {
+        Some(clock) => (clock, true),
+        None => (word.strip_suffix("am")?, false),
+    };
+    let (hour, minute) = match clock.split_once([':', '.']) {
+        Some((hour, minute)) => (hour.parse::<u32>().ok()?, minute.parse::<u32>().ok()?),
+        None => (clock.parse::<u32>().ok()?, 0),
+    };
+    if !(1..=12).contains(&hour) || minute > 59 {
+        return None;
+    }
+    let hour = match (hour, pm) {
+        (12, false) => 0,
+        (12, true) => 12,
+        (hour, true) => hour + 12,
+        (hour, false) => hour,
+    };
+    Some(format!("{:02}:{:02}", hour, minute))
+}
+
+/// "15", "3:30", "9.15"
+fn is_clock(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_ascii_digit())
+        && word
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == ':' || c == '.')
+}
+
+/// "3pm", "10:30", "9.15am"; a bare number is a day, not a time
+fn is_time_of_day(word: &str) -> bool {
+    match word.strip_suffix("am").or_else(|| word.strip_suffix("pm")) {
+        Some(clock) => is_clock(clock),
+        None => is_clock(word) && word.contains(':'),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    /// Wednesday 12 March 2025, 10:30 at UTC+1
+    fn now() -> DateTime<FixedOffset> {
+        FixedOffset::east_opt(3600)
+            .unwrap()
+            .with_ymd_and_hms(2025, 3, 12, 10, 30, 0)
+            .unwrap()
+    }
+
+    fn parse(input: &str) -> String {
+        parse_event_time(input, &now())
+            .unwrap()
+            .format("%Y-%m-%d %H:%M %:z")
+            .to_string()
+    }
+
+    #[test]
+    fn test_parse_natural_language() {
+        assert_eq!(parse("tomorrow 3pm"), "2025-03-13 15:00 +01:00");
+        assert_eq!(parse("Tomorrow at 3 PM"), "2025-03-13 15:00 +01:00");
+        assert_eq!(parse("3pm tomorrow"), "2025-03-13 15:00 +01:00");
+        assert_eq!(parse("friday 10:30"), "2025-03-14 10:30 +01:00");
+        assert_eq!(parse("Friday, noon"), "2025-03-14 12:00 +01:00");
+        assert_eq!(parse("mar 14 3pm"), "2025-03-14 15:00 +01:00");
+        assert_eq!(parse("friday 12am"), "2025-03-14 00:00 +01:00");
+        assert_eq!(parse("friday 12:30pm"), "2025-03-14 12:30 +01:00");
+    }
+
+    #[test]
+    fn test_parse_exact_formats() {
+        assert_eq!(parse("2025-03-14T15:00"), "2025-03-14 15:00 +01:00");
+        assert_eq!(parse("2025-03-14 15:00:00"), "2025-03-14 15:00 +01:00");
+        // An explicit offset is kept as the same instant
+        assert_eq!(parse("2025-03-14T15:00:00Z"), "2025-03-14 16:00 +01:00");
+        assert_eq!(parse("March 14, 2025 3:00 PM"), "2025-03-14 15:00 +01:00");
+    }
+
+    #[test]
+    fn test_parse_rejects_garbage_and_ambiguity() {
+        let err = parse_event_time("blah blah", &now()).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Could not understand the time 'blah blah'")
+        );
+        assert!(parse_event_time("", &now()).is_err());
+
+        let err = parse_event_time("tomorrow", &now()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "'tomorrow' doesn't say what time of day; ask the user for a time, e.g. \"tomorrow 3pm\""
+        );
+        assert!(parse_event_time("next week", &now()).is_err());
+        assert!(parse_event_time("2025-03-14", &now()).is_err());
+    }
+}
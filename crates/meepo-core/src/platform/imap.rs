@@ -0,0 +1,1205 @@
+//! IMAP/SMTP email provider
+//!
+//! Talks to a mail server directly instead of scripting a local mail app, so
+//! it works headless and on any OS. Reading uses IMAP with server-side
+//! SEARCH; sending uses SMTP with `In-Reply-To`/`References` for threading.
+//! Both go through the `curl` CLI, which speaks IMAP(S) and SMTP(S). The
+//! account is set once at startup via [`configure`]; when present,
+//! [`create_email_provider`](super::create_email_provider) prefers it over
+//! the platform's mail app.
+
+use anyhow::{Context, Result, anyhow, bail};
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chrono::Local;
+use meepo_scheduler::{EmailSource, PolledEmail};
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::RwLock;
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, warn};
+
+use super::mime::{self, AttachmentInfo, header, parse_headers};
+use super::{
+    EMAIL_BODY_CHARS, EMAIL_PREVIEW_CHARS, EmailPage, EmailProvider, EmailReadOptions,
+    EmailSummary, PageInfo, ReplyTarget, format_email_page, validate_attachments,
+};
+use crate::text::{html_to_text, truncate_chars};
+
+/// Maximum time for a single curl invocation
+const CURL_TIMEOUT_SECS: u64 = 60;
+
+/// Mailbox drafts are appended to
+const DRAFTS_MAILBOX: &str = "Drafts";
+
+/// IMAP/SMTP account used by [`ImapEmailProvider`]
+#[derive(Debug, Clone)]
+pub struct ImapSmtpConfig {
+    /// e.g. `imaps://imap.example.com` (port optional)
+    pub imap_url: String,
+    /// e.g. `smtps://smtp.example.com:465` or `smtp://smtp.example.com:587` (STARTTLS)
+    pub smtp_url: String,
+    pub username: String,
+    pub password: String,
+    /// From address for outgoing mail; defaults to `username`
+    pub from: Option<String>,
+}
+
+static CONFIG: RwLock<Option<ImapSmtpConfig>> = RwLock::new(None);
+
+/// Set the IMAP/SMTP account used by email providers created afterwards
+pub fn configure(config: ImapSmtpConfig) {
+    *CONFIG.write().unwrap_or_else(|e| e.into_inner()) = Some(config);
+}
+
+/// Whether an account has been set with [`configure`]
+pub fn is_configured() -> bool {
+    CONFIG.read().unwrap_or_else(|e| e.into_inner()).is_some()
+}
+
+/// Run curl with credentials passed on stdin (never on the command line,
+/// where other users could read them), returning stdout
+async fn run_curl(username: &str, password: &str, args: &[String]) -> Result<Vec<u8>> {
+    let mut child = tokio::process::Command::new("curl")
+        .args(["--silent", "--show-error", "--ssl-reqd", "--config", "-"])
+        .arg("--max-time")
+        .arg(CURL_TIMEOUT_SECS.to_string())
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .context("Failed to run curl (is it installed?)")?;
+
+    let credentials = format!(
+        "user = \"{}\"\n",
+        curl_config_escape(&format!("{}:{}", username, password))
+    );
+    let mut stdin = child.stdin.take().context("curl stdin unavailable")?;
+    stdin.write_all(credentials.as_bytes()).await?;
+    drop(stdin);
+
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("curl failed: {}", stderr.trim());
+    }
+    Ok(output.stdout)
+}
+
+/// Escape a value for a double-quoted curl config string
+fn curl_config_escape(s: &str) -> String {
+    s.chars()
+        .filter(|c| *c != '\r' && *c != '\n')
+        .flat_map(|c| match c {
+            '\\' | '"' => vec!['\\', c],
+            _ => vec![c],
+        })
+        .collect()
+}
+
+/// Quote a string for an IMAP command, dropping characters that would end the line
+fn imap_quote(s: &str) -> String {
+    let escaped: String = s
+        .chars()
+        .filter(|c| *c != '\r' && *c != '\n')
+        .flat_map(|c| match c {
+            '\\' | '"' => vec!['\\', c],
+            _ => vec![c],
+        })
+        .collect();
+    format!("\"{}\"", escaped)
+}
+
+/// Percent-encode a mailbox name for an IMAP URL path
+fn imap_url_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Map the tool-level mailbox names onto conventional IMAP folder names
+fn imap_mailbox(mailbox: &str) -> &str {
+    match mailbox.to_lowercase().as_str() {
+        "sent" => "Sent",
+        "drafts" => "Drafts",
+        "trash" => "Trash",
+        "archive" => "Archive",
+        _ => "INBOX",
+    }
+}
+
+/// SEARCH criteria for the read_emails `search` term (run server-side, so large
+/// mailboxes aren't downloaded to filter them)
+fn search_criteria(search: Option<&str>) -> String {
+    match search {
+        Some(term) => format!("OR FROM {} SUBJECT {}", imap_quote(term), imap_quote(term)),
+        None => "ALL".to_string(),
+    }
+}
+
+/// Message sequence numbers from an untagged `* SEARCH` response
+fn parse_search_response(response: &str) -> Vec<u64> {
+    response
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("* SEARCH"))
+        .flat_map(|ids| ids.split_whitespace().filter_map(|id| id.parse().ok()))
+        .collect()
+}
+
+/// Compress a list of sequence numbers into an IMAP sequence set ("3,5,7")
+fn sequence_set(ids: &[u64]) -> String {
+    ids.iter().map(u64::to_string).collect::<Vec<_>>().join(",")
+}
+
+/// Encode a header value as an RFC 2047 word if it isn't plain ASCII
+fn encode_header(value: &str) -> String {
+    if value.is_ascii() {
+        value.to_string()
+    } else {
+        format!("=?UTF-8?B?{}?=", BASE64.encode(value.as_bytes()))
+    }
+}
+
+/// Strip CR/LF so user input can't inject extra headers
+fn header_safe(value: &str) -> String {
+    value.replace(['\r', '\n'], " ")
+}
+
+/// The bare address from "Name <addr@example.com>" or "addr@example.com"
+fn bare_address(address: &str) -> &str {
+    match (address.find('<'), address.rfind('>')) {
+        (Some(start), Some(end)) if start < end => address[start + 1..end].trim(),
+        _ => address.trim(),
+    }
+}
+
+/// Email provider backed by an IMAP server for reading and SMTP for sending
+pub struct ImapEmailProvider {
+    config: ImapSmtpConfig,
+}
+
+impl ImapEmailProvider {
+    pub fn new(config: ImapSmtpConfig) -> Self {
+        Self { config }
+    }
+
+    /// Provider for the account set with [`configure`]
+    pub fn from_configured() -> Result<Self> {
+        CONFIG
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .map(Self::new)
+            .ok_or_else(|| anyhow!("No IMAP/SMTP account configured ([imap] in config)"))
+    }
+
+    fn mailbox_url(&self, mailbox: &str) -> String {
+        format!(
+            "{}/{}",
+            self.config.imap_url.trim_end_matches('/'),
+            imap_url_encode(mailbox)
+        )
+    }
+
+    async fn curl(&self, args: &[String]) -> Result<Vec<u8>> {
+        run_curl(&self.config.username, &self.config.password, args).await
+    }
+
+    /// Run a SEARCH in `mailbox`, returning matching sequence numbers in ascending order
+    async fn search(&self, mailbox: &str, criteria: &str) -> Result<Vec<u64>> {
+        let output = self
+            .curl(&[
+                self.mailbox_url(mailbox),
+                "--request".to_string(),
+                format!("SEARCH {}", criteria),
+            ])
+            .await?;
+        Ok(parse_search_response(&String::from_utf8_lossy(&output)))
+    }
+
+    /// Fetch one body section for each message in a single connection
+    async fn fetch_sections(&self, mailbox: &str, requests: &[(u64, &str)]) -> Result<Vec<String>> {
+        // Each transfer's output is terminated by a unique marker so bodies can be split apart
+        let marker = format!("--meepo-{}--", uuid::Uuid::new_v4());
+        let mut args = vec!["--write-out".to_string(), format!("\n{}\n", marker)];
+        let base = self.mailbox_url(mailbox);
+        for (id, section) in requests {
+            args.push(format!(
+                "{};MAILINDEX={};SECTION={}",
+                base,
+                id,
+                // Sections may carry URL parameters (";PARTIAL="), so only spaces are encoded
+                section.replace(' ', "%20")
+            ));
+        }
+
+        let output = self.curl(&args).await?;
+        let output = String::from_utf8_lossy(&output);
+        Ok(output
+            .split(&format!("\n{}\n", marker))
+            .take(requests.len())
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Message-ID and References of the message being replied to: the one with the given
+    /// Message-ID in the inbox, sent or archive mailboxes, or else the newest inbox message
+    /// whose subject contains the given text
+    async fn thread_headers(&self, target: ReplyTarget<'_>) -> Result<Option<(String, String)>> {
+        let (mailboxes, criteria): (&[&str], String) = match target {
+            ReplyTarget::MessageId(id) => (
+                &["INBOX", "Sent", "Archive"],
+                format!("HEADER Message-ID {}", imap_quote(&format!("<{}>", id))),
+            ),
+            ReplyTarget::Subject(subject) => {
+                (&["INBOX"], format!("SUBJECT {}", imap_quote(subject)))
+            }
+        };
+        let mut found = None;
+        for &mailbox in mailboxes {
+            match self.search(mailbox, &criteria).await {
+                Ok(ids) => {
+                    if let Some(&id) = ids.last() {
+                        found = Some((mailbox, id));
+                        break;
+                    }
+                }
+                // Not every server has Sent or Archive mailboxes
+                Err(e) if mailbox != "INBOX" => debug!("Skipping {}: {}", mailbox, e),
+                Err(e) => return Err(e),
+            }
+        }
+        let Some((mailbox, id)) = found else {
+            return Ok(None);
+        };
+        let sections = self
+            .fetch_sections(mailbox, &[(id, "HEADER.FIELDS (MESSAGE-ID REFERENCES)")])
+            .await?;
+        let headers = parse_headers(sections.first().map(String::as_str).unwrap_or(""));
+        let message_id = header(&headers, "message-id");
+        if message_id.is_empty() {
+            return Ok(None);
+        }
+        let references = match header(&headers, "references") {
+            "" => message_id.to_string(),
+            refs => format!("{} {}", refs, message_id),
+        };
+        Ok(Some((message_id.to_string(), references)))
+    }
+
+    /// Sequence numbers of up to `limit` messages matching `criteria` after skipping the
+    /// `offset` newest (newest first), which of those are currently unread, and how many
+    /// messages match in all
+    async fn newest_ids(
+        &self,
+        mailbox: &str,
+        criteria: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<u64>, Vec<u64>, usize)> {
+        let mut ids = self.search(mailbox, criteria).await?;
+        let total = ids.len();
+        // Highest sequence numbers are the newest messages
+        ids.reverse();
+        let ids: Vec<u64> = ids.into_iter().skip(offset).take(limit).collect();
+        if ids.is_empty() {
+            return Ok((ids, Vec::new(), total));
+        }
+        let unseen = self
+            .search(mailbox, "UNSEEN")
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|id| ids.contains(id))
+            .collect();
+        Ok((ids, unseen, total))
+    }
+
+    /// Fetching a body marks the message `\Seen`; clear it again on messages that were unread
+    async fn restore_unseen(&self, mailbox: &str, unseen: &[u64]) {
+        if unseen.is_empty() {
+            return;
+        }
+        if let Err(e) = self
+            .curl(&[
+                self.mailbox_url(mailbox),
+                "--request".to_string(),
+                format!("STORE {} -FLAGS (\\Seen)", sequence_set(unseen)),
+            ])
+            .await
+        {
+            warn!("Failed to restore unread flags after reading emails: {}", e);
+        }
+    }
+
+    /// The newest `limit` messages in `mailbox` matching the SEARCH `criteria`, newest first
+    async fn fetch_messages(
+        &self,
+        mailbox: &str,
+        criteria: &str,
+        limit: usize,
+        include_body: bool,
+    ) -> Result<Vec<PolledEmail>> {
+        let (emails, _) = self
+            .fetch_page(mailbox, criteria, 0, limit, include_body)
+            .await?;
+        Ok(emails)
+    }
+
+    /// Like [`fetch_messages`](Self::fetch_messages) after skipping the `offset` newest,
+    /// along with how many messages match `criteria` in all
+    async fn fetch_page(
+        &self,
+        mailbox: &str,
+        criteria: &str,
+        offset: usize,
+        limit: usize,
+        include_body: bool,
+    ) -> Result<(Vec<PolledEmail>, usize)> {
+        let (ids, unseen, total) = self.newest_ids(mailbox, criteria, offset, limit).await?;
+        if ids.is_empty() {
+            return Ok((Vec::new(), total));
+        }
+
+        let per_message = if include_body { 2 } else { 1 };
+        let requests: Vec<(u64, &str)> = ids
+            .iter()
+            .flat_map(|id| {
+                [
+                    (*id, "HEADER.FIELDS (FROM SUBJECT DATE MESSAGE-ID)"),
+                    (*id, "1;PARTIAL=0.2048"),
+                ]
+                .into_iter()
+                .take(per_message)
+            })
+            .collect();
+        let sections = self.fetch_sections(mailbox, &requests).await?;
+        self.restore_unseen(mailbox, &unseen).await;
+
+        let emails = sections
+            .chunks(per_message)
+            .map(|parts| {
+                let headers = parse_headers(&parts[0]);
+                PolledEmail {
+                    message_id: header(&headers, "message-id").to_string(),
+                    from: header(&headers, "from").to_string(),
+                    subject: header(&headers, "subject").to_string(),
+                    date: header(&headers, "date").to_string(),
+                    body: parts
+                        .get(1)
+                        .map(|body| body.split_whitespace().collect::<Vec<_>>().join(" ")),
+                }
+            })
+            .collect();
+        Ok((emails, total))
+    }
+
+    /// MIME structure of each message, from the server's BODYSTRUCTURE (nothing is downloaded)
+    async fn body_structures(
+        &self,
+        mailbox: &str,
+        ids: &[u64],
+    ) -> Result<Vec<(u64, Vec<BodyPart>)>> {
+        let output = self
+            .curl(&[
+                self.mailbox_url(mailbox),
+                "--request".to_string(),
+                format!("FETCH {} BODYSTRUCTURE", sequence_set(ids)),
+            ])
+            .await?;
+        Ok(parse_bodystructure_response(&String::from_utf8_lossy(
+            &output,
+        )))
+    }
+
+    /// Read with HTML bodies and/or attachment listings, decoding the chosen body part
+    async fn read_rich(
+        &self,
+        mailbox: &str,
+        criteria: &str,
+        offset: usize,
+        limit: usize,
+        options: EmailReadOptions,
+    ) -> Result<Vec<String>> {
+        let (ids, unseen, _) = self.newest_ids(mailbox, criteria, offset, limit).await?;
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let structures = self.body_structures(mailbox, &ids).await?;
+        let part_of = |id: u64| {
+            structures
+                .iter()
+                .find(|(seq, _)| *seq == id)
+                .map(|(_, parts)| parts.as_slice())
+                .unwrap_or(&[])
+        };
+
+        // Fetch only the text part to show, never attachments
+        let bodies: Vec<Option<&BodyPart>> = ids
+            .iter()
+            .map(|id| choose_body_part(part_of(*id), options.prefer_html))
+            .collect();
+        let sections: Vec<String> = bodies
+            .iter()
+            .map(|part| match part {
+                Some(part) => format!("{};PARTIAL=0.262144", part.section),
+                None => "1;PARTIAL=0.2048".to_string(),
+            })
+            .collect();
+        let requests: Vec<(u64, &str)> = ids
+            .iter()
+            .zip(&sections)
+            .flat_map(|(id, section)| {
+                [
+                    (*id, "HEADER.FIELDS (FROM SUBJECT DATE)"),
+                    (*id, section.as_str()),
+                ]
+            })
+            .collect();
+        let fetched = self.fetch_sections(mailbox, &requests).await?;
+        self.restore_unseen(mailbox, &unseen).await;
+
+        Ok(ids
+            .iter()
+            .zip(bodies)
+            .zip(fetched.chunks(2))
+            .map(|((id, part), fetched)| {
+                let headers = parse_headers(&fetched[0]);
+                let raw = fetched.get(1).map(String::as_str).unwrap_or("");
+                let (body, is_html) = match part {
+                    Some(part) => (
+                        mime::decode_charset(
+                            &mime::decode_transfer_encoding(raw, &part.encoding),
+                            &part.charset,
+                        ),
+                        part.mime_type == "text/html",
+                    ),
+                    None => (raw.to_string(), false),
+                };
+                let attachments: Vec<AttachmentInfo> = part_of(*id)
+                    .iter()
+                    .filter(|part| part.is_attachment)
+                    .map(|part| AttachmentInfo {
+                        name: part
+                            .filename
+                            .clone()
+                            .unwrap_or_else(|| "(unnamed)".to_string()),
+                        mime_type: part.mime_type.clone(),
+                        size: Some(part.size),
+                    })
+                    .collect();
+                mime::format_rich_email(
+                    header(&headers, "from"),
+                    header(&headers, "subject"),
+                    header(&headers, "date"),
+                    &body,
+                    is_html,
+                    options
+                        .include_attachments
+                        .then_some(attachments.as_slice()),
+                )
+            })
+            .collect())
+    }
+}
+
+/// A node of an IMAP response (parenthesized list, string, atom, or NIL)
+#[derive(Debug, Clone, PartialEq)]
+enum Sexp {
+    List(Vec<Sexp>),
+    Str(String),
+    Atom(String),
+    Nil,
+}
+
+impl Sexp {
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Sexp::Str(s) | Sexp::Atom(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+/// Parse one IMAP value from the start of `input`, returning it and the remainder
+fn parse_sexp(input: &str) -> Option<(Sexp, &str)> {
+    let input = input.trim_start();
+    let mut chars = input.char_indices();
+    match chars.next()?.1 {
+        '(' => {
+            let mut items = Vec::new();
+            let mut rest = &input[1..];
+            loop {
+                rest = rest.trim_start();
+                if let Some(after) = rest.strip_prefix(')') {
+                    return Some((Sexp::List(items), after));
+                }
+                let (item, after) = parse_sexp(rest)?;
+                items.push(item);
+                rest = after;
+            }
+        }
+        '"' => {
+            let mut value = String::new();
+            let mut escaped = false;
+            for (i, c) in chars {
+                match c {
+                    _ if escaped => {
+                        value.push(c);
+                        escaped = false;
+                    }
+                    '\\' => escaped = true,
+                    '"' => return Some((Sexp::Str(value), &input[i + 1..])),
+                    _ => value.push(c),
+                }
+            }
+            None
+        }
+        '{' => {
+            // Literal: {n}\r\n followed by n bytes
+            let close = input.find('}')?;
+            let len: usize = input[1..close].parse().ok()?;
+            let data = input[close + 1..].trim_start_matches(['\r', '\n']);
+            let value = data.get(..len)?;
+            Some((Sexp::Str(value.to_string()), &data[len..]))
+        }
+        _ => {
+            let end = input
+                .find(|c: char| c.is_whitespace() || c == '(' || c == ')')
+                .unwrap_or(input.len());
+            let atom = &input[..end];
+            let value = if atom.eq_ignore_ascii_case("NIL") {
+                Sexp::Nil
+            } else {
+                Sexp::Atom(atom.to_string())
+            };
+            Some((value, &input[end..]))
+        }
+    }
+}
+
+/// A leaf MIME part described by BODYSTRUCTURE
+#[derive(Debug, Clone, PartialEq)]
+struct BodyPart {
+    /// IMAP section number ("1", "2.1", ...)
+    section: String,
+    /// Lowercase "type/subtype"
+    mime_type: String,
+    charset: String,
+    encoding: String,
+    /// Encoded size in bytes
+    size: u64,
+    filename: Option<String>,
+    is_attachment: bool,
+}
+
+/// Value of `key` in an IMAP parameter list like `("CHARSET" "utf-8" "NAME" "x.pdf")`
+fn sexp_param(list: Option<&Sexp>, key: &str) -> Option<String> {
+    let Some(Sexp::List(items)) = list else {
+        return None;
+    };
+    items
+        .chunks(2)
+        .find(|pair| {
+            pair[0]
+                .as_str()
+                .is_some_and(|k| k.eq_ignore_ascii_case(key))
+        })
+        .and_then(|pair| pair.get(1)?.as_str())
+        .map(mime::decode_encoded_words)
+}
+
+/// Flatten a BODYSTRUCTURE into its leaf parts; `section` is this node's section ("" at the top)
+fn flatten_bodystructure(node: &[Sexp], section: &str, parts: &mut Vec<BodyPart>) {
+    if let Some(Sexp::List(_)) = node.first() {
+        // Multipart: child bodies come first, then the subtype and extensions
+        for (i, child) in node.iter().enumerate() {
+            let Sexp::List(child) = child else {
+                break;
+            };
+            let child_section = if section.is_empty() {
+                (i + 1).to_string()
+            } else {
+                format!("{}.{}", section, i + 1)
+            };
+            flatten_bodystructure(child, &child_section, parts);
+        }
+        return;
+    }
+
+    let field = |i: usize| node.get(i).and_then(Sexp::as_str).unwrap_or("");
+    let media_type = field(0).to_lowercase();
+    let mime_type = format!("{}/{}", media_type, field(1).to_lowercase());
+    // Extension fields follow the type-specific ones
+    let disposition_index = match mime_type.as_str() {
+        "message/rfc822" => 11,
+        _ if media_type == "text" => 9,
+        _ => 8,
+    };
+    let (disposition, disposition_params) = match node.get(disposition_index) {
+        Some(Sexp::List(d)) => (
+            d.first()
+                .and_then(Sexp::as_str)
+                .unwrap_or("")
+                .to_lowercase(),
+            d.get(1),
+        ),
+        _ => (String::new(), None),
+    };
+    let filename =
+        sexp_param(disposition_params, "FILENAME").or_else(|| sexp_param(node.get(2), "NAME"));
+    let is_attachment = disposition == "attachment"
+        || filename.is_some()
+        || (media_type != "text" && media_type != "multipart");
+
+    parts.push(BodyPart {
+        section: if section.is_empty() {
+            "1".to_string()
+        } else {
+            section.to_string()
+        },
+        charset: sexp_param(node.get(2), "CHARSET").unwrap_or_else(|| "utf-8".to_string()),
+        encoding: field(5).to_lowercase(),
+        size: field(6).parse().unwrap_or(0),
+        mime_type,
+        filename,
+        is_attachment,
+    });
+}
+
+/// Leaf parts per message from `* n FETCH (BODYSTRUCTURE (...))` lines
+fn parse_bodystructure_response(response: &str) -> Vec<(u64, Vec<BodyPart>)> {
+    let mut messages = Vec::new();
+    for line in response.split("\n* ").map(|l| l.trim_start_matches("* ")) {
+        let Some((seq, rest)) = line.split_once(' ') else {
+            continue;
+        };
+        let (Ok(seq), Some(rest)) = (seq.parse::<u64>(), rest.trim_start().strip_prefix("FETCH"))
+        else {
+            continue;
+        };
+        let Some((Sexp::List(items), _)) = parse_sexp(rest) else {
+            continue;
+        };
+        let structure = items
+            .iter()
+            .position(|item| {
+                item.as_str()
+                    .is_some_and(|s| s.eq_ignore_ascii_case("BODYSTRUCTURE"))
+            })
+            .and_then(|i| items.get(i + 1));
+        if let Some(Sexp::List(node)) = structure {
+            let mut parts = Vec::new();
+            flatten_bodystructure(node, "", &mut parts);
+            messages.push((seq, parts));
+        }
+    }
+    messages
+}
+
+/// Summary of a message read with its body preview
+fn email_summary(email: PolledEmail) -> EmailSummary {
+    EmailSummary {
+        preview: truncate_chars(email.body.as_deref().unwrap_or(""), EMAIL_PREVIEW_CHARS)
+            .to_string(),
+        message_id: email.message_id,
+        from: email.from,
+        subject: email.subject,
+        date: email.date,
+    }
+}
+
+/// The inline text part to show as the body, preferring HTML if asked
+fn choose_body_part(parts: &[BodyPart], prefer_html: bool) -> Option<&BodyPart> {
+    let inline = |mime_type: &str| {
+        parts
+            .iter()
+            .find(|part| !part.is_attachment && part.mime_type == mime_type)
+    };
+    let (first, second) = if prefer_html {
+        ("text/html", "text/plain")
+    } else {
+        ("text/plain", "text/html")
+    };
+    inline(first).or_else(|| inline(second))
+}
+
+#[async_trait]
+impl EmailProvider for ImapEmailProvider {
+    async fn read_emails_structured(
+        &self,
+        limit: u64,
+        mailbox: &str,
+        search: Option<&str>,
+    ) -> Result<Vec<EmailSummary>> {
+        let mailbox = imap_mailbox(mailbox);
+        debug!("Reading {} emails via IMAP ({})", limit, mailbox);
+
+        let emails = self
+            .fetch_messages(mailbox, &search_criteria(search), limit as usize, true)
+            .await?;
+        Ok(emails.into_iter().map(email_summary).collect())
+    }
+
+    /// Reads only the page's messages, and counts the matches with the same SEARCH
+    async fn read_emails_page(
+        &self,
+        limit: u64,
+        offset: u64,
+        mailbox: &str,
+        search: Option<&str>,
+    ) -> Result<EmailPage> {
+        let mailbox = imap_mailbox(mailbox);
+        debug!(
+            "Reading {} emails at offset {} via IMAP ({})",
+            limit, offset, mailbox
+        );
+
+        let (emails, total) = self
+            .fetch_page(
+                mailbox,
+                &search_criteria(search),
+                offset as usize,
+                limit as usize,
+                true,
+            )
+            .await?;
+        let emails: Vec<EmailSummary> = emails.into_iter().map(email_summary).collect();
+        Ok(EmailPage {
+            page: PageInfo::of_total(offset, emails.len() as u64, total as u64),
+            emails,
+        })
+    }
+
+    async fn read_emails_with_options(
+        &self,
+        limit: u64,
+        offset: u64,
+        mailbox: &str,
+        search: Option<&str>,
+        options: EmailReadOptions,
+    ) -> Result<String> {
+        if options.is_plain() {
+            let page = self
+                .read_emails_page(limit, offset, mailbox, search)
+                .await?;
+            return Ok(format_email_page(mailbox, &page));
+        }
+        let mailbox = imap_mailbox(mailbox);
+        debug!(
+            "Reading {} emails at offset {} via IMAP ({}) with {:?}",
+            limit, offset, mailbox, options
+        );
+
+        let emails = self
+            .read_rich(
+                mailbox,
+                &search_criteria(search),
+                offset as usize,
+                limit as usize,
+                options,
+            )
+            .await?;
+        if emails.is_empty() {
+            return Ok(format!("No emails found in {}", mailbox));
+        }
+        Ok(emails.concat())
+    }
+
+    async fn read_email_body(&self, message_id: &str, mailbox: &str) -> Result<String> {
+        let mailbox = imap_mailbox(mailbox);
+        let id = message_id
+            .trim()
+            .trim_start_matches('<')
+            .trim_end_matches('>');
+        let criteria = format!("HEADER Message-ID {}", imap_quote(&format!("<{}>", id)));
+        let (ids, unseen, _) = self.newest_ids(mailbox, &criteria, 0, 1).await?;
+        let Some(&seq) = ids.first() else {
+            bail!("No message with Message-ID <{}> in {}", id, mailbox);
+        };
+
+        let structures = self.body_structures(mailbox, &[seq]).await?;
+        let parts = structures
+            .iter()
+            .find(|(found, _)| *found == seq)
+            .map(|(_, parts)| parts.as_slice())
+            .unwrap_or(&[]);
+        let part = choose_body_part(parts, false);
+        let section = format!(
+            "{};PARTIAL=0.262144",
+            part.map_or("1", |part| part.section.as_str())
+        );
+        let fetched = self.fetch_sections(mailbox, &[(seq, &section)]).await?;
+        self.restore_unseen(mailbox, &unseen).await;
+
+        let raw = fetched.first().map(String::as_str).unwrap_or("");
+        let body = match part {
+            Some(part) => {
+                let text = mime::decode_charset(
+                    &mime::decode_transfer_encoding(raw, &part.encoding),
+                    &part.charset,
+                );
+                if part.mime_type == "text/html" {
+                    html_to_text(&text)
+                } else {
+                    text
+                }
+            }
+            None => raw.to_string(),
+        };
+        Ok(truncate_chars(body.trim(), EMAIL_BODY_CHARS).to_string())
+    }
+
+    async fn send_email(
+        &self,
+        to: &str,
+        subject: &str,
+        body: &str,
+        cc: Option<&str>,
+        in_reply_to: Option<&str>,
+        attachments: &[PathBuf],
+    ) -> Result<String> {
+        validate_attachments(attachments)?;
+        let attachments = read_attachments(attachments).await?;
+        let from = self
+            .config
+            .from
+            .clone()
+            .unwrap_or_else(|| self.config.username.clone());
+
+        let thread = match in_reply_to {
+            Some(in_reply_to) => {
+                let target = ReplyTarget::parse(in_reply_to);
+                debug!("Replying to email: {:?}", target);
+                self.thread_headers(target).await.unwrap_or_else(|e| {
+                    warn!("Failed to look up email to reply to: {}", e);
+                    None
+                })
+            }
+            None => None,
+        };
+
+        let message = build_message(&from, to, cc, subject, body, thread.as_ref(), &attachments);
+        let recipients: Vec<&str> = to
+            .split(',')
+            .chain(cc.into_iter().flat_map(|cc| cc.split(',')))
+            .map(bare_address)
+            .filter(|addr| !addr.is_empty())
+            .collect();
+        if recipients.is_empty() {
+            bail!("No recipients given");
+        }
+
+        let path = std::env::temp_dir().join(format!("meepo-mail-{}.eml", uuid::Uuid::new_v4()));
+        tokio::fs::write(&path, &message).await?;
+
+        let mut args = vec![
+            self.config.smtp_url.clone(),
+            "--mail-from".to_string(),
+            bare_address(&from).to_string(),
+        ];
+        for recipient in &recipients {
+            args.push("--mail-rcpt".to_string());
+            args.push(recipient.to_string());
+        }
+        args.push("--upload-file".to_string());
+        args.push(path.to_string_lossy().to_string());
+
+        let result = self.curl(&args).await;
+        if let Err(e) = tokio::fs::remove_file(&path).await {
+            warn!(
+                "Failed to remove temporary message {}: {}",
+                path.display(),
+                e
+            );
+        }
+        result?;
+
+        Ok(if thread.is_some() {
+            format!("Reply sent to {} (threaded)", to)
+        } else {
+            format!("Email sent to {}", to)
+        })
+    }
+
+    /// Appends the message to the Drafts mailbox over IMAP instead of sending it
+    async fn create_draft(
+        &self,
+        to: &str,
+        subject: &str,
+        body: &str,
+        cc: Option<&str>,
+        in_reply_to: Option<&str>,
+        attachments: &[PathBuf],
+    ) -> Result<String> {
+        validate_attachments(attachments)?;
+        let attachments = read_attachments(attachments).await?;
+        let from = self
+            .config
+            .from
+            .clone()
+            .unwrap_or_else(|| self.config.username.clone());
+        let thread = match in_reply_to {
+            Some(in_reply_to) => self
+                .thread_headers(ReplyTarget::parse(in_reply_to))
+                .await
+                .unwrap_or_else(|e| {
+                    warn!("Failed to look up email to reply to: {}", e);
+                    None
+                }),
+            None => None,
+        };
+
+        let message = build_message(&from, to, cc, subject, body, thread.as_ref(), &attachments);
+        let path = std::env::temp_dir().join(format!("meepo-draft-{}.eml", uuid::Uuid::new_v4()));
+        tokio::fs::write(&path, &message).await?;
+
+        debug!("Saving draft to {} via IMAP", DRAFTS_MAILBOX);
+        let args = vec![
+            self.mailbox_url(DRAFTS_MAILBOX),
+            "--upload-file".to_string(),
+            path.to_string_lossy().to_string(),
+        ];
+        let result = self.curl(&args).await;
+        if let Err(e) = tokio::fs::remove_file(&path).await {
+            warn!("Failed to remove temporary draft {}: {}", path.display(), e);
+        }
+        result?;
+
+        Ok(format!(
+            "Draft to {} saved to {} (not sent)",
+            to, DRAFTS_MAILBOX
+        ))
+    }
+}
+
+#[async_trait]
+impl EmailSource for ImapEmailProvider {
+    async fn recent_emails(&self, limit: usize, include_body: bool) -> Result<Vec<PolledEmail>> {
+        self.fetch_messages("INBOX", "ALL", limit, include_body)
+            .await
+    }
+}
+
+/// A file to attach to an outgoing message
+struct OutgoingAttachment {
+    filename: String,
+    content_type: String,
+    data: Vec<u8>,
+}
+
+async fn read_attachments(paths: &[PathBuf]) -> Result<Vec<OutgoingAttachment>> {
+    let mut attachments = Vec::with_capacity(paths.len());
+    for path in paths {
+        let data = tokio::fs::read(path)
+            .await
+            .with_context(|| format!("Failed to read attachment {}", path.display()))?;
+        attachments.push(OutgoingAttachment {
+            filename: path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| "attachment".to_string()),
+            content_type: mime_guess::from_path(path)
+                .first_or_octet_stream()
+                .to_string(),
+            data,
+        });
+    }
+    Ok(attachments)
+}
+
+/// Build an RFC 5322 message: plain text, or multipart/mixed when there are attachments
+fn build_message(
+    from: &str,
+    to: &str,
+    cc: Option<&str>,
+    subject: &str,
+    body: &str,
+    thread: Option<&(String, String)>,
+    attachments: &[OutgoingAttachment],
+) -> String {
+    let domain = bare_address(from)
+        .rsplit_once('@')
+        .map(|(_, domain)| domain)
+        .unwrap_or("localhost");
+
+    let mut headers = vec![
+        format!("From: {}", header_safe(from)),
+        format!("To: {}", header_safe(to)),
+    ];
+    if let Some(cc) = cc {
+        headers.push(format!("Cc: {}", header_safe(cc)));
+    }
+    headers.push(format!("Subject: {}", encode_header(&header_safe(subject))));
+    headers.push(format!("Date: {}", Local::now().to_rfc2822()));
+    headers.push(format!("Message-ID: <{}@{}>", uuid::Uuid::new_v4(), domain));
+    if let Some((message_id, references)) = thread {
+        headers.push(format!("In-Reply-To: {}", header_safe(message_id)));
+        headers.push(format!("References: {}", header_safe(references)));
+    }
+    headers.push("MIME-Version: 1.0".to_string());
+
+    let text_headers = "Content-Type: text/plain; charset=utf-8\r\nContent-Transfer-Encoding: 8bit";
+    let body = body.replace("\r\n", "\n").replace('\n', "\r\n");
+    if attachments.is_empty() {
+        return format!(
+            "{}\r\n{}\r\n\r\n{}\r\n",
+            headers.join("\r\n"),
+            text_headers,
+            body
+        );
+    }
+
+    let boundary = format!("meepo-{}", uuid::Uuid::new_v4().simple());
+    headers.push(format!(
+        "Content-Type: multipart/mixed; boundary=\"{}\"",
+        boundary
+    ));
+    let mut message = format!(
+        "{}\r\n\r\n--{}\r\n{}\r\n\r\n{}\r\n",
+        headers.join("\r\n"),
+        boundary,
+        text_headers,
+        body
+    );
+    for attachment in attachments {
+        let filename = encode_header(&header_safe(&attachment.filename).replace('"', "'"));
+        let encoded = BASE64.encode(&attachment.data);
+        // Base64 is ASCII, so splitting the bytes keeps whole characters
+        let lines: Vec<&str> = encoded
+            .as_bytes()
+            .chunks(76)
+            .map(|line| std::str::from_utf8(line).unwrap_or_default())
+            .collect();
+        message.push_str(&format!(
+            "--{}\r\nContent-Type: {}; name=\"{}\"\r\nContent-Disposition: attachment; filename=\"{}\"\r\nContent-Transfer-Encoding: base64\r\n\r\n{}\r\n",
+            boundary,
+            attachment.content_type,
+            filename,
+            filename,
+            lines.join("\r\n")
+        ));
+    }
+    message.push_str(&format!("--{}--\r\n", boundary));
+    message
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_imap_helpers() {
+        assert_eq!(imap_quote(r#"say "hi"\now"#), r#""say \"hi\"\\now""#);
+        assert_eq!(imap_quote("a\r\nb"), "\"ab\"");
+        assert_eq!(curl_config_escape("me:p\"w\\d"), "me:p\\\"w\\\\d");
+        assert_eq!(imap_mailbox("Sent"), "Sent");
+        assert_eq!(imap_mailbox("whatever"), "INBOX");
+        assert_eq!(imap_url_encode("Archive 2024"), "Archive%202024");
+        assert_eq!(parse_search_response("* SEARCH 2 5 9\r\n"), vec![2, 5, 9]);
+        assert!(parse_search_response("* SEARCH\r\n").is_empty());
+        assert_eq!(sequence_set(&[9, 5, 2]), "9,5,2");
+    }
+
+    #[test]
+    fn test_parse_bodystructure() {
+        let response = "* 7 FETCH (UID 42 BODYSTRUCTURE (((\"TEXT\" \"PLAIN\" (\"CHARSET\" \"utf-8\") NIL NIL \"7BIT\" 12 1 NIL NIL NIL NIL)\
+            (\"TEXT\" \"HTML\" (\"CHARSET\" \"iso-8859-1\") NIL NIL \"QUOTED-PRINTABLE\" 80 2 NIL NIL NIL NIL) \"ALTERNATIVE\")\
+            (\"APPLICATION\" \"PDF\" (\"NAME\" \"x.pdf\") NIL NIL \"BASE64\" 4096 NIL (\"ATTACHMENT\" (\"FILENAME\" {10}\r\nreport.pdf)) NIL NIL) \"MIXED\"))\r\n\
+            * 8 FETCH (BODYSTRUCTURE (\"TEXT\" \"PLAIN\" NIL NIL NIL \"BASE64\" 20 1 NIL NIL NIL NIL))\r\n";
+        let messages = parse_bodystructure_response(response);
+        assert_eq!(messages.len(), 2);
+
+        let (seq, parts) = &messages[0];
+        assert_eq!(*seq, 7);
+        let sections: Vec<&str> = parts.iter().map(|p| p.section.as_str()).collect();
+        assert_eq!(sections, vec!["1.1", "1.2", "2"]);
+        assert_eq!(parts[1].mime_type, "text/html");
+        assert_eq!(parts[1].charset, "iso-8859-1");
+        assert_eq!(parts[1].encoding, "quoted-printable");
+        assert!(!parts[1].is_attachment);
+        assert_eq!(parts[2].filename.as_deref(), Some("report.pdf"));
+        assert_eq!(parts[2].size, 4096);
+        assert!(parts[2].is_attachment);
+
+        assert_eq!(choose_body_part(parts, true).unwrap().section, "1.2");
+        assert_eq!(choose_body_part(parts, false).unwrap().section, "1.1");
+
+        let (seq, parts) = &messages[1];
+        assert_eq!(*seq, 8);
+        assert_eq!(parts[0].section, "1");
+        assert_eq!(parts[0].charset, "utf-8");
+        assert_eq!(
+            choose_body_part(parts, true).unwrap().mime_type,
+            "text/plain"
+        );
+    }
+
+    #[test]
+    fn test_build_message() {
+        let thread = (
+            "<abc@example.com>".to_string(),
+            "<abc@example.com>".to_string(),
+        );
+        let message = build_message(
+            "Me <me@example.com>",
+            "you@example.com",
+            Some("boss@example.com"),
+            "Grüße\r\nBcc: evil@example.com",
+            "line one\nline two",
+            Some(&thread),
+            &[],
+        );
+        let (head, body) = message.split_once("\r\n\r\n").unwrap();
+        assert!(head.contains("To: you@example.com\r\n"));
+        assert!(head.contains("Cc: boss@example.com\r\n"));
+        assert!(head.contains("Subject: =?UTF-8?B?"));
+        assert!(!head.contains("\r\nBcc:"));
+        assert!(head.contains("In-Reply-To: <abc@example.com>\r\n"));
+        assert!(head.contains("@example.com>\r\nIn-Reply-To"));
+        assert_eq!(body, "line one\r\nline two\r\n");
+        assert_eq!(bare_address("Me <me@example.com>"), "me@example.com");
+        assert_eq!(bare_address(" me@example.com "), "me@example.com");
+    }
+
+    #[test]
+    fn test_build_message_with_attachments() {
+        let attachment = OutgoingAttachment {
+            filename: "report.pdf".to_string(),
+            content_type: "application/pdf".to_string(),
+            data: vec![b'x'; 100],
+        };
+        let message = build_message(
+            "me@example.com",
+            "you@example.com",
+            None,
+            "Report",
+            "See attached",
+            None,
+            &[attachment],
+        );
+        let (head, rest) = message.split_once("\r\n\r\n").unwrap();
+        let boundary = head
+            .split("boundary=\"")
+            .nth(1)
+            .and_then(|b| b.strip_suffix('"'))
+            .unwrap();
+        assert!(boundary.starts_with("meepo-"));
+        assert!(rest.starts_with(&format!("--{}\r\nContent-Type: text/plain", boundary)));
+        assert!(rest.contains("\r\n\r\nSee attached\r\n"));
+        assert!(rest.contains(
+            "Content-Type: application/pdf; name=\"report.pdf\"\r\nContent-Disposition: attachment; filename=\"report.pdf\""
+        ));
+        // 100 bytes encode to 136 base64 characters, wrapped at 76
+        let encoded = BASE64.encode([b'x'; 100]);
+        assert!(rest.contains(&format!("{}\r\n{}\r\n", &encoded[..76], &encoded[76..])));
+        assert!(rest.ends_with(&format!("--{}--\r\n", boundary)));
+    }
+}
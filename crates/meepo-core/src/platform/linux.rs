@@ -0,0 +1,891 @@
+//! Linux platform implementations
+//!
+//! Linux has no scriptable calendar app, so the calendar provider talks to a
+//! CalDAV server over HTTP instead. The calendar is supplied once at startup
+//! via [`configure_calendar`]; until then the factory reports the provider as
+//! unavailable. Email on Linux uses the cross-platform IMAP provider
+//! ([`super::imap`]).
+
+use anyhow::{Result, anyhow, bail};
+use async_trait::async_trait;
+use chrono::{
+    DateTime, Datelike, Days, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday,
+};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::Duration;
+use tracing::debug;
+
+use super::event_time::resolve_event_time;
+use super::{CalendarProvider, EventDetails, EventSummary, calendar_not_found_error};
+
+/// CalDAV calendar collection used by [`LinuxCalendarProvider`]
+#[derive(Debug, Clone)]
+pub struct CalDavConfig {
+    /// URL of the calendar collection, e.g. `https://dav.example.com/calendars/me/personal/`
+    pub url: String,
+    pub username: String,
+    pub password: String,
+}
+
+static CALENDAR_CONFIG: RwLock<Option<CalDavConfig>> = RwLock::new(None);
+
+/// Set the CalDAV calendar used by calendar providers created afterwards
+pub fn configure_calendar(config: CalDavConfig) {
+    *CALENDAR_CONFIG.write().unwrap_or_else(|e| e.into_inner()) = Some(config);
+}
+
+/// A VEVENT reduced to what the calendar tools display
+#[derive(Debug, Clone, PartialEq)]
+struct CalendarEvent {
+    uid: String,
+    summary: String,
+    start: String,
+    end: String,
+    location: String,
+    /// DTSTART digits, for chronological sorting
+    sort_key: String,
+    /// DTSTART as an instant; `None` when its TZID couldn't be resolved
+    start_at: Option<DateTime<Utc>>,
+    end_at: Option<DateTime<Utc>>,
+    all_day: bool,
+}
+
+/// Calendar provider backed by a CalDAV calendar collection
+pub struct LinuxCalendarProvider {
+    config: CalDavConfig,
+    client: reqwest::Client,
+}
+
+impl LinuxCalendarProvider {
+    pub fn new(config: CalDavConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()?;
+        Ok(Self { config, client })
+    }
+
+    /// Provider for the calendar set with [`configure_calendar`]
+    pub fn from_configured() -> Result<Self> {
+        let config = CALENDAR_CONFIG
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .ok_or_else(|| {
+                anyhow!("Calendar on Linux requires a CalDAV calendar ([linux.calendar] in config)")
+            })?;
+        Self::new(config)
+    }
+
+    fn collection_url(&self) -> String {
+        format!("{}/", self.config.url.trim_end_matches('/'))
+    }
+
+    /// Name of the configured calendar: the last path segment of its URL
+    fn calendar_name(&self) -> String {
+        collection_name(&self.config.url)
+    }
+
+    /// Only the configured calendar exists; reject any other name
+    fn check_calendar_name(&self, calendar_name: Option<&str>) -> Result<()> {
+        match calendar_name {
+            Some(name) if !name.trim().eq_ignore_ascii_case(&self.calendar_name()) => {
+                Err(calendar_not_found_error(name, &[self.calendar_name()]))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Last path segment of a collection URL (`.../calendars/me/personal/` -> `personal`)
+fn collection_name(url: &str) -> String {
+    url.trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .replace("%20", " ")
+}
+
+impl LinuxCalendarProvider {
+    /// Events of the next `days_ahead` days, in start order
+    async fn fetch_events(&self, days_ahead: u64) -> Result<Vec<CalendarEvent>> {
+        debug!(
+            "Reading calendar events for next {} days via CalDAV",
+            days_ahead
+        );
+        let start = Utc::now();
+        let end = start + chrono::Duration::days(days_ahead as i64);
+        let range = format!(
+            r#"start="{}" end="{}""#,
+            start.format("%Y%m%dT%H%M%SZ"),
+            end.format("%Y%m%dT%H%M%SZ")
+        );
+        // Ask the server to expand recurring events into individual instances
+        let query = format!(
+            r#"<?xml version="1.0" encoding="utf-8" ?>
+<C:calendar-query xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">
+  <D:prop>
+    <C:calendar-data><C:expand {range}/></C:calendar-data>
+  </D:prop>
+  <C:filter>
+    <C:comp-filter name="VCALENDAR">
+      <C:comp-filter name="VEVENT">
+        <C:time-range {range}/>
+      </C:comp-filter>
+    </C:comp-filter>
+  </C:filter>
+</C:calendar-query>"#
+        );
+
+        let response = self
+            .client
+            .request(
+                reqwest::Method::from_bytes(b"REPORT")?,
+                self.collection_url(),
+            )
+            .basic_auth(&self.config.username, Some(&self.config.password))
+            .header("Depth", "1")
+            .header(
+                reqwest::header::CONTENT_TYPE,
+                "application/xml; charset=utf-8",
+            )
+            .body(query)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            bail!("CalDAV REPORT failed: HTTP {}", response.status());
+        }
+
+        let mut events = parse_calendar_events(&response.text().await?);
+        events.sort_by(|a, b| a.sort_key.cmp(&b.sort_key));
+        Ok(events)
+    }
+}
+
+#[async_trait]
+impl CalendarProvider for LinuxCalendarProvider {
+    async fn read_events(&self, days_ahead: u64, calendar_name: Option<&str>) -> Result<String> {
+        self.check_calendar_name(calendar_name)?;
+        let events = self.fetch_events(days_ahead).await?;
+        if events.is_empty() {
+            return Ok(format!("No events in the next {} days", days_ahead));
+        }
+
+        let mut output = String::new();
+        for event in events {
+            output.push_str(&format!("Event: {}\n", event.summary));
+            output.push_str(&format!("Start: {}\n", event.start));
+            output.push_str(&format!("End: {}\n", event.end));
+            if !event.location.is_empty() {
+                output.push_str(&format!("Location: {}\n", event.location));
+            }
+            output.push_str("---\n");
+        }
+        Ok(output)
+    }
+
+    async fn read_events_structured(
+        &self,
+        days_ahead: u64,
+        calendar_name: Option<&str>,
+    ) -> Result<Vec<EventSummary>> {
+        self.check_calendar_name(calendar_name)?;
+        let calendar = self.calendar_name();
+        self.fetch_events(days_ahead)
+            .await?
+            .into_iter()
+            .map(|event| event_summary(event, &calendar))
+            .collect()
+    }
+
+    async fn list_calendars(&self) -> Result<String> {
+        Ok(self.calendar_name())
+    }
+
+    async fn create_event(
+        &self,
+        summary: &str,
+        start_time: &str,
+        duration_minutes: u64,
+        calendar_name: Option<&str>,
+        details: &EventDetails,
+    ) -> Result<String> {
+        debug!("Creating calendar event: {}", summary);
+        self.check_calendar_name(calendar_name)?;
+        details.validate()?;
+        let start = resolve_event_time(start_time)?.with_timezone(&Utc);
+        let end = match &details.end_time {
+            Some(end) => resolve_event_time(end)?.with_timezone(&Utc),
+            None => start + chrono::Duration::minutes(duration_minutes as i64),
+        };
+        if end <= start {
+            bail!("The end time must be after the start time");
+        }
+        let uid = uuid::Uuid::new_v4().to_string();
+        let ics = build_event_ics(&uid, summary, start, end, details, &self.config.username);
+
+        let response = self
+            .client
+            .put(format!("{}{}.ics", self.collection_url(), uid))
+            .basic_auth(&self.config.username, Some(&self.config.password))
+            .header(
+                reqwest::header::CONTENT_TYPE,
+                "text/calendar; charset=utf-8",
+            )
+            .header(reqwest::header::IF_NONE_MATCH, "*")
+            .body(ics)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            bail!("CalDAV PUT failed: HTTP {}", response.status());
+        }
+
+        Ok(format!(
+            "Event created successfully: {} at {}",
+            summary,
+            start.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+        ))
+    }
+}
+
+/// The structured form of a parsed event, or an error naming the event when
+/// its start time couldn't be pinned down
+fn event_summary(event: CalendarEvent, calendar: &str) -> Result<EventSummary> {
+    let start = event.start_at.ok_or_else(|| {
+        anyhow!(
+            "Can't read the start time of '{}' ({}); the calendar data doesn't define its time zone",
+            event.summary,
+            event.start
+        )
+    })?;
+    Ok(EventSummary {
+        id: event.uid,
+        title: event.summary,
+        calendar: Some(calendar.to_string()),
+        start,
+        end: event.end_at,
+        all_day: event.all_day,
+    })
+}
+
+/// A minimal VCALENDAR containing one event
+/// A single VEVENT. Attendees get RSVP invitations from servers that do CalDAV
+/// scheduling, which needs `organizer` (the account's address) as ORGANIZER.
+fn build_event_ics(
+    uid: &str,
+    summary: &str,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    details: &EventDetails,
+    organizer: &str,
+) -> String {
+    let stamp = |dt: DateTime<Utc>| dt.format("%Y%m%dT%H%M%SZ").to_string();
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//meepo//EN".to_string(),
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{}", uid),
+        format!("DTSTAMP:{}", stamp(Utc::now())),
+        format!("DTSTART:{}", stamp(start)),
+        format!("DTEND:{}", stamp(end)),
+        format!("SUMMARY:{}", ics_escape(summary)),
+    ];
+    if let Some(location) = &details.location {
+        lines.push(format!("LOCATION:{}", ics_escape(location)));
+    }
+    if let Some(notes) = &details.notes {
+        lines.push(format!("DESCRIPTION:{}", ics_escape(notes)));
+    }
+    if !details.attendees.is_empty() && organizer.contains('@') {
+        lines.push(format!("ORGANIZER:mailto:{}", organizer));
+    }
+    for attendee in &details.attendees {
+        lines.push(format!(
+            "ATTENDEE;ROLE=REQ-PARTICIPANT;PARTSTAT=NEEDS-ACTION;RSVP=TRUE:mailto:{}",
+            attendee
+        ));
+    }
+    lines.push("END:VEVENT".to_string());
+    lines.push("END:VCALENDAR".to_string());
+    lines.join("\r\n") + "\r\n"
+}
+
+fn ics_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+fn ics_unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') | Some('N') => out.push('\n'),
+                Some(other) => out.push(other),
+                None => out.push('\\'),
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Undo XML escaping in calendar-data text
+fn xml_unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let Some(semi) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..semi];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// One STANDARD or DAYLIGHT observance of a VTIMEZONE
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Observance {
+    /// Local time the observance first took effect
+    start: Option<NaiveDateTime>,
+    /// UTC offset while the observance is in effect, in seconds
+    offset: i64,
+    /// Yearly onset as (month, nth weekday of the month, weekday); a negative
+    /// nth counts from the end of the month
+    rule: Option<(u32, i32, Weekday)>,
+    /// Last local time the rule applies
+    until: Option<NaiveDateTime>,
+}
+
+impl Observance {
+    /// The latest onset of this observance at or before local time `time`
+    fn last_onset(&self, time: NaiveDateTime) -> Option<NaiveDateTime> {
+        let start = self.start.filter(|start| *start <= time)?;
+        let Some((month, nth, weekday)) = self.rule else {
+            return Some(start);
+        };
+        let recurring = [time.year(), time.year() - 1]
+            .into_iter()
+            .filter_map(|year| nth_weekday(year, month, nth, weekday))
+            .map(|day| day.and_time(start.time()))
+            .find(|onset| {
+                *onset <= time && *onset >= start && self.until.is_none_or(|until| *onset <= until)
+            });
+        Some(recurring.unwrap_or(start))
+    }
+}
+
+/// The `nth` `weekday` of a month; negative `nth` counts back from its end
+fn nth_weekday(year: i32, month: u32, nth: i32, weekday: Weekday) -> Option<NaiveDate> {
+    if nth > 0 {
+        return NaiveDate::from_weekday_of_month_opt(year, month, weekday, u8::try_from(nth).ok()?);
+    }
+    let next_month = match month {
+        12 => NaiveDate::from_ymd_opt(year + 1, 1, 1),
+        _ => NaiveDate::from_ymd_opt(year, month + 1, 1),
+    }?;
+    let last = next_month.pred_opt()?;
+    let back = (7 + last.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+    let day = last.checked_sub_days(Days::new(
+        u64::from(back) + 7 * u64::try_from(-nth - 1).ok()?,
+    ))?;
+    (day.month() == month).then_some(day)
+}
+
+/// A `FREQ=YEARLY;BYMONTH=3;BYDAY=-1SU` rule as (month, nth, weekday) and its UNTIL.
+/// Other recurrences aren't used by time zone definitions in practice.
+fn parse_observance_rule(rrule: &str) -> Option<((u32, i32, Weekday), Option<NaiveDateTime>)> {
+    let parts: HashMap<&str, &str> = rrule
+        .split(';')
+        .filter_map(|part| part.split_once('='))
+        .collect();
+    if parts.get("FREQ") != Some(&"YEARLY") {
+        return None;
+    }
+    let month = parts.get("BYMONTH")?.parse().ok()?;
+    let byday = parts.get("BYDAY")?;
+    let (nth, day) = byday.split_at(byday.len().checked_sub(2)?);
+    let nth = match nth.trim_start_matches('+') {
+        "" => 1,
+        nth => nth.parse().ok()?,
+    };
+    let weekday = match day {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    };
+    let until = parts
+        .get("UNTIL")
+        .and_then(|until| parse_ics_datetime(until));
+    Some(((month, nth, weekday), until))
+}
+
+/// A `+0100` / `-0530` UTC offset, in seconds
+fn parse_utc_offset(offset: &str) -> Option<i64> {
+    let (sign, digits) = match offset.trim().split_at_checked(1)? {
+        ("+", digits) => (1, digits),
+        ("-", digits) => (-1, digits),
+        _ => return None,
+    };
+    if !matches!(digits.len(), 4 | 6) || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let field = |range: std::ops::Range<usize>| digits.get(range).map_or(Ok(0), str::parse::<i64>);
+    let (hours, minutes, seconds) = (field(0..2).ok()?, field(2..4).ok()?, field(4..6).ok()?);
+    Some(sign * (hours * 3600 + minutes * 60 + seconds))
+}
+
+/// A DATE-TIME value without its `Z`, as a naive date and time
+fn parse_ics_datetime(value: &str) -> Option<NaiveDateTime> {
+    let value = value.strip_suffix('Z').unwrap_or(value);
+    NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y%m%d")
+                .ok()
+                .map(|date| date.and_time(NaiveTime::MIN))
+        })
+}
+
+/// The instant of local time `time` in zone `tzid`, using the VTIMEZONE
+/// definitions sent with the event. `None` for zones that weren't defined.
+fn resolve_zoned_time(
+    zones: &HashMap<String, Vec<Observance>>,
+    tzid: &str,
+    time: NaiveDateTime,
+) -> Option<DateTime<Utc>> {
+    let offset = match zones.get(tzid) {
+        Some(observances) => {
+            observances
+                .iter()
+                .filter_map(|observance| Some((observance.last_onset(time)?, observance.offset)))
+                .max_by_key(|(onset, _)| *onset)?
+                .1
+        }
+        None if matches!(tzid, "UTC" | "Etc/UTC" | "GMT" | "Etc/GMT") => 0,
+        None => return None,
+    };
+    Some(Utc.from_utc_datetime(&(time - chrono::Duration::seconds(offset))))
+}
+
+/// The instant a DTSTART/DTEND property names, and whether it is a whole-day
+/// DATE. Floating times are local by definition; TZID times are looked up in
+/// `zones`, giving `None` when the zone is unknown.
+fn ics_instant(
+    params: &str,
+    value: &str,
+    zones: &HashMap<String, Vec<Observance>>,
+) -> (Option<DateTime<Utc>>, bool) {
+    if params.contains("VALUE=DATE")
+        && let Ok(date) = NaiveDate::parse_from_str(value, "%Y%m%d")
+    {
+        let midnight = Local
+            .from_local_datetime(&date.and_time(NaiveTime::MIN))
+            .earliest()
+            .map(|local| local.with_timezone(&Utc));
+        return (midnight, true);
+    }
+    let Some(time) = parse_ics_datetime(value) else {
+        return (None, false);
+    };
+    let instant = if value.ends_with('Z') {
+        Some(Utc.from_utc_datetime(&time))
+    } else {
+        match params
+            .split(';')
+            .find_map(|param| param.strip_prefix("TZID="))
+        {
+            Some(tzid) => resolve_zoned_time(zones, tzid.trim_matches('"'), time),
+            None => Local
+                .from_local_datetime(&time)
+                .earliest()
+                .map(|local| local.with_timezone(&Utc)),
+        }
+    };
+    (instant, false)
+}
+
+/// Human-readable form of a DTSTART/DTEND property
+fn format_ics_time(params: &str, value: &str) -> String {
+    if params.contains("VALUE=DATE")
+        && let Ok(date) = NaiveDate::parse_from_str(value, "%Y%m%d")
+    {
+        return format!("{} (all day)", date.format("%Y-%m-%d"));
+    }
+    let (naive, utc) = match value.strip_suffix('Z') {
+        Some(v) => (v, true),
+        None => (value, false),
+    };
+    let Ok(dt) = NaiveDateTime::parse_from_str(naive, "%Y%m%dT%H%M%S") else {
+        return value.to_string();
+    };
+    if utc {
+        return Utc
+            .from_utc_datetime(&dt)
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M")
+            .to_string();
+    }
+    match params
+        .split(';')
+        .find_map(|param| param.strip_prefix("TZID="))
+    {
+        Some(tzid) => format!(
+            "{} ({})",
+            dt.format("%Y-%m-%d %H:%M"),
+            tzid.trim_matches('"')
+        ),
+        None => dt.format("%Y-%m-%d %H:%M").to_string(),
+    }
+}
+
+/// A DTSTART/DTEND property as (params, value), not yet resolved to an instant
+type RawTime<'a> = Option<(&'a str, &'a str)>;
+
+/// Extract the events from a CalDAV multistatus response
+fn parse_calendar_events(xml: &str) -> Vec<CalendarEvent> {
+    let mut events = Vec::new();
+    let mut rest = xml;
+
+    while let Some(start) = rest.find("BEGIN:VCALENDAR") {
+        let Some(len) = rest[start..].find("END:VCALENDAR") else {
+            break;
+        };
+        let calendar = xml_unescape(&rest[start..start + len]);
+        rest = &rest[start + len..];
+
+        // Unfold continuation lines before splitting properties
+        let unfolded = calendar
+            .replace("\r\n", "\n")
+            .replace("\n ", "")
+            .replace("\n\t", "");
+
+        let mut components: Vec<&str> = Vec::new();
+        // Events with their raw DTSTART/DTEND (params, value), resolved once the
+        // calendar's VTIMEZONEs, which may come after them, have been read
+        let mut parsed: Vec<(CalendarEvent, RawTime, RawTime)> = Vec::new();
+        let mut event: Option<(CalendarEvent, RawTime, RawTime)> = None;
+        let mut zones: HashMap<String, Vec<Observance>> = HashMap::new();
+        let mut zone: Option<(String, Vec<Observance>)> = None;
+        for line in unfolded.lines() {
+            let Some((name_params, value)) = line.split_once(':') else {
+                continue;
+            };
+            let (name, params) = name_params.split_once(';').unwrap_or((name_params, ""));
+
+            match name {
+                "BEGIN" => {
+                    components.push(value);
+                    match (value, components.len()) {
+                        ("VEVENT", 2) => {
+                            event = Some((
+                                CalendarEvent {
+                                    uid: String::new(),
+                                    summary: String::new(),
+                                    start: String::new(),
+                                    end: String::new(),
+                                    location: String::new(),
+                                    sort_key: String::new(),
+                                    start_at: None,
+                                    end_at: None,
+                                    all_day: false,
+                                },
+                                None,
+                                None,
+                            ))
+                        }
+                        ("VTIMEZONE", 2) => zone = Some((String::new(), Vec::new())),
+                        ("STANDARD" | "DAYLIGHT", 3) => {
+                            if let Some((_, observances)) = zone.as_mut() {
+                                observances.push(Observance::default());
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                "END" => {
+                    components.pop();
+                    match (value, components.len()) {
+                        ("VEVENT", 1) => parsed.extend(event.take()),
+                        ("VTIMEZONE", 1) => {
+                            if let Some((tzid, observances)) = zone.take() {
+                                zones.insert(tzid, observances);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                // Only properties of the event itself, not nested alarms
+                _ if components.last() == Some(&"VEVENT") => {
+                    let Some((event, dtstart, dtend)) = event.as_mut() else {
+                        continue;
+                    };
+                    match name {
+                        "UID" => event.uid = value.to_string(),
+                        "SUMMARY" => event.summary = ics_unescape(value),
+                        "LOCATION" => event.location = ics_unescape(value),
+                        "DTSTART" => {
+                            event.start = format_ics_time(params, value);
+                            event.sort_key = value.chars().filter(char::is_ascii_digit).collect();
+                            *dtstart = Some((params, value));
+                        }
+                        "DTEND" => {
+                            event.end = format_ics_time(params, value);
+                            *dtend = Some((params, value));
+                        }
+                        _ => {}
+                    }
+                }
+                _ if components.last() == Some(&"VTIMEZONE") => {
+                    if let (Some((tzid, _)), "TZID") = (zone.as_mut(), name) {
+                        *tzid = value.to_string();
+                    }
+                }
+                _ if matches!(components.last(), Some(&"STANDARD" | &"DAYLIGHT")) => {
+                    let Some(observance) = zone
+                        .as_mut()
+                        .and_then(|(_, observances)| observances.last_mut())
+                    else {
+                        continue;
+                    };
+                    match name {
+                        "DTSTART" => observance.start = parse_ics_datetime(value),
+                        "TZOFFSETTO" => observance.offset = parse_utc_offset(value).unwrap_or(0),
+                        "RRULE" => {
+                            if let Some((rule, until)) = parse_observance_rule(value) {
+                                observance.rule = Some(rule);
+                                observance.until = until;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for (mut event, dtstart, dtend) in parsed {
+            if let Some((params, value)) = dtstart {
+                (event.start_at, event.all_day) = ics_instant(params, value, &zones);
+            }
+            if let Some((params, value)) = dtend {
+                event.end_at = ics_instant(params, value, &zones).0;
+            }
+            events.push(event);
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_calendar_events() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:cal="urn:ietf:params:xml:ns:caldav">
+ <d:response><d:propstat><d:prop><cal:calendar-data>BEGIN:VCALENDAR
+BEGIN:VEVENT
+SUMMARY:Dentist &amp; checkup
+DTSTART;TZID=Europe/Berlin:20250314T150000
+DTEND;TZID=Europe/Berlin:20250314T160000
+LOCATION:Main St\, Suite 4
+BEGIN:VALARM
+SUMMARY:Alarm text
+END:VALARM
+END:VEVENT
+END:VCALENDAR
+</cal:calendar-data></d:prop></d:propstat></d:response>
+ <d:response><d:propstat><d:prop><cal:calendar-data>BEGIN:VCALENDAR
+BEGIN:VEVENT
+SUMMARY:Team offsite with a very long title that the server
+ folded
+DTSTART;VALUE=DATE:20250310
+DTEND;VALUE=DATE:20250311
+END:VEVENT
+END:VCALENDAR
+</cal:calendar-data></d:prop></d:propstat></d:response>
+</d:multistatus>"#;
+
+        let mut events = parse_calendar_events(xml);
+        events.sort_by(|a, b| a.sort_key.cmp(&b.sort_key));
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0].summary,
+            "Team offsite with a very long title that the serverfolded"
+        );
+        assert_eq!(events[0].start, "2025-03-10 (all day)");
+        assert_eq!(events[1].summary, "Dentist & checkup");
+        assert_eq!(events[1].start, "2025-03-14 15:00 (Europe/Berlin)");
+        assert_eq!(events[1].end, "2025-03-14 16:00 (Europe/Berlin)");
+        assert_eq!(events[1].location, "Main St, Suite 4");
+    }
+
+    #[test]
+    fn test_zoned_times_resolve_to_utc() {
+        let xml = "BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:summer
+SUMMARY:Summer call
+DTSTART;TZID=Europe/Berlin:20250710T090000
+DTEND;TZID=Europe/Berlin:20250710T093000
+END:VEVENT
+BEGIN:VTIMEZONE
+TZID:Europe/Berlin
+BEGIN:DAYLIGHT
+TZOFFSETFROM:+0100
+TZOFFSETTO:+0200
+DTSTART:19810329T020000
+RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=-1SU
+END:DAYLIGHT
+BEGIN:STANDARD
+TZOFFSETFROM:+0200
+TZOFFSETTO:+0100
+DTSTART:19961027T030000
+RRULE:FREQ=YEARLY;BYMONTH=10;BYDAY=-1SU
+END:STANDARD
+END:VTIMEZONE
+BEGIN:VEVENT
+UID:winter
+SUMMARY:Winter call
+DTSTART;TZID=Europe/Berlin:20250314T150000
+END:VEVENT
+BEGIN:VEVENT
+UID:utc
+SUMMARY:UTC call
+DTSTART:20250314T150000Z
+END:VEVENT
+BEGIN:VEVENT
+UID:unknown
+SUMMARY:Mystery call
+DTSTART;TZID=Mars/Olympus:20250314T150000
+END:VEVENT
+END:VCALENDAR";
+        let at = |mo, d, h, mi| Utc.with_ymd_and_hms(2025, mo, d, h, mi, 0).unwrap();
+        let events = parse_calendar_events(xml);
+        let summaries: Vec<Result<EventSummary>> = events
+            .into_iter()
+            .map(|event| event_summary(event, "personal"))
+            .collect();
+
+        let summer = summaries[0].as_ref().unwrap();
+        assert_eq!(summer.id, "summer");
+        assert_eq!(summer.start, at(7, 10, 7, 0));
+        assert_eq!(summer.end, Some(at(7, 10, 7, 30)));
+        assert_eq!(summer.calendar.as_deref(), Some("personal"));
+        assert_eq!(summaries[1].as_ref().unwrap().start, at(3, 14, 14, 0));
+        assert_eq!(summaries[2].as_ref().unwrap().start, at(3, 14, 15, 0));
+        let err = summaries[3].as_ref().unwrap_err().to_string();
+        assert!(err.contains("Mystery call"), "{}", err);
+    }
+
+    #[test]
+    fn test_nth_weekday() {
+        let day = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(
+            nth_weekday(2025, 3, -1, Weekday::Sun),
+            Some(day(2025, 3, 30))
+        );
+        assert_eq!(
+            nth_weekday(2025, 11, 1, Weekday::Sun),
+            Some(day(2025, 11, 2))
+        );
+        assert_eq!(
+            nth_weekday(2025, 12, -1, Weekday::Wed),
+            Some(day(2025, 12, 31))
+        );
+        assert_eq!(nth_weekday(2025, 2, 5, Weekday::Mon), None);
+        assert_eq!(parse_utc_offset("-0530"), Some(-(5 * 3600 + 30 * 60)));
+        assert_eq!(parse_utc_offset("0100"), None);
+    }
+
+    #[test]
+    fn test_event_ics_and_start_time() {
+        let start = resolve_event_time("2025-03-14T15:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let end = start + chrono::Duration::minutes(30);
+        let ics = build_event_ics(
+            "uid-1",
+            "Lunch; with Bob, Alice",
+            start,
+            end,
+            &EventDetails::default(),
+            "me@example.com",
+        );
+        assert!(ics.contains("DTSTART:20250314T150000Z\r\n"));
+        assert!(ics.contains("DTEND:20250314T153000Z\r\n"));
+        assert!(ics.contains("SUMMARY:Lunch\\; with Bob\\, Alice\r\n"));
+        assert!(!ics.contains("ORGANIZER"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+
+        let details = EventDetails {
+            attendees: vec!["bob@example.com".to_string()],
+            location: Some("Cafe, 2nd floor".to_string()),
+            notes: Some("Bring slides".to_string()),
+            end_time: None,
+        };
+        let ics = build_event_ics("uid-2", "Lunch", start, end, &details, "me@example.com");
+        assert!(ics.contains("LOCATION:Cafe\\, 2nd floor\r\n"));
+        assert!(ics.contains("DESCRIPTION:Bring slides\r\n"));
+        assert!(ics.contains("ORGANIZER:mailto:me@example.com\r\n"));
+        assert!(ics.contains("RSVP=TRUE:mailto:bob@example.com\r\n"));
+    }
+
+    #[test]
+    fn test_calendar_name() {
+        let provider = LinuxCalendarProvider::new(CalDavConfig {
+            url: "https://dav.example.com/calendars/me/Work%20Stuff/".to_string(),
+            username: String::new(),
+            password: String::new(),
+        })
+        .unwrap();
+        assert_eq!(provider.calendar_name(), "Work Stuff");
+        assert!(provider.check_calendar_name(None).is_ok());
+        assert!(provider.check_calendar_name(Some("work stuff")).is_ok());
+        let err = provider.check_calendar_name(Some("Personal")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "No calendar named 'Personal' was found. Available calendars: Work Stuff"
+        );
+    }
+}
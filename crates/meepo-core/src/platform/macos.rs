@@ -1,16 +1,33 @@
 //! macOS platform implementations using AppleScript
 
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Local, Timelike};
 use tokio::process::Command;
+use tokio::sync::oneshot;
 use tracing::{debug, warn};
 
+use super::event_time::resolve_event_time;
 use super::{
-    BrowserCookie, BrowserProvider, BrowserTab, CalendarProvider, ContactsProvider, EmailProvider,
-    MusicProvider, NotesProvider, NotificationProvider, PageContent, RemindersProvider,
-    ScreenCaptureProvider, UiAutomation,
+    BrowserCookie, BrowserProvider, BrowserTab, CalendarProvider, Contact, ContactsProvider,
+    DEFAULT_SCRIPT_TIMEOUT, EMAIL_BODY_CHARS, EMAIL_PREVIEW_CHARS, EmailPage, EmailProvider,
+    EmailReadOptions, EmailSummary, EventChanges, EventDetails, EventSummary, MAX_LISTED_ELEMENTS,
+    MusicProvider, NotesProvider, NotificationProvider, PageContent, PageInfo, RemindersProvider,
+    ReplyTarget, ScreenCaptureProvider, UiAutomation, UiElement, Urgency, VALID_ELEMENT_TYPES,
+    automation_denied_error, calendar_not_found_error, check_automation_permission,
+    default_screenshot_path, event_not_found_error, format_email_page, listed_element_types, mime,
+    parse_contact_records, parse_display_size, parse_email_summary_records, parse_event_records,
+    parse_ui_element_records, record_automation_permission, take_unique_emails,
+    validate_attachments, validate_click_point, validate_screenshot_path,
 };
 
+/// AppleScript error code for Apple Events the user hasn't allowed (errAEEventNotPermitted)
+const NOT_PERMITTED_ERROR_CODE: &str = "-1743";
+
 /// Sanitize a string for safe use in AppleScript
 fn sanitize_applescript_string(input: &str) -> String {
     input
@@ -22,77 +39,212 @@ fn sanitize_applescript_string(input: &str) -> String {
         .collect()
 }
 
-/// Validate screenshot output path to prevent writing to sensitive locations
-fn validate_screenshot_path(path: &str) -> Result<()> {
-    if path.contains("..") {
-        return Err(anyhow::anyhow!(
-            "Screenshot path contains '..' which is not allowed"
-        ));
+/// How long the first queued script waits for concurrent scripts to join its batch
+const BATCH_WINDOW: Duration = Duration::from_millis(10);
+
+/// Separates per-script results in a batch's output
+const BATCH_SEP: char = '\u{4}';
+
+/// A script waiting to run in the next osascript batch
+struct PendingScript {
+    script: String,
+    timeout: Duration,
+    reply: oneshot::Sender<Result<String>>,
+}
+
+static PENDING_SCRIPTS: LazyLock<Mutex<Vec<PendingScript>>> =
+    LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// Run an AppleScript with the default timeout
+async fn run_applescript(script: &str) -> Result<String> {
+    run_applescript_with_timeout(script, DEFAULT_SCRIPT_TIMEOUT).await
+}
+
+/// Run an AppleScript. Scripts started concurrently (e.g. a briefing reading mail and
+/// calendar at once) share one osascript process, which may run for the longest of
+/// their timeouts; each caller still gives up after its own.
+async fn run_applescript_with_timeout(script: &str, timeout: Duration) -> Result<String> {
+    let (reply, result) = oneshot::channel();
+    let starts_batch = {
+        let mut pending = PENDING_SCRIPTS.lock().unwrap_or_else(|e| e.into_inner());
+        pending.push(PendingScript {
+            script: script.to_string(),
+            timeout,
+            reply,
+        });
+        pending.len() == 1
+    };
+    if starts_batch {
+        // Spawned so a cancelled caller doesn't take the rest of the batch with it
+        tokio::spawn(async {
+            tokio::time::sleep(BATCH_WINDOW).await;
+            let batch =
+                std::mem::take(&mut *PENDING_SCRIPTS.lock().unwrap_or_else(|e| e.into_inner()));
+            run_batch(batch).await;
+        });
+    }
+    tokio::time::timeout(timeout + BATCH_WINDOW, result)
+        .await
+        .map_err(|_| timed_out_error(timeout))?
+        .map_err(|_| anyhow::anyhow!("AppleScript batch was dropped before it finished"))?
+}
+
+/// The error for a script that ran longer than `timeout`
+fn timed_out_error(timeout: Duration) -> anyhow::Error {
+    if timeout.subsec_millis() == 0 {
+        anyhow::anyhow!(
+            "AppleScript execution timed out after {} seconds",
+            timeout.as_secs()
+        )
+    } else {
+        anyhow::anyhow!(
+            "AppleScript execution timed out after {} ms",
+            timeout.as_millis()
+        )
     }
+}
 
-    let path_buf = std::path::PathBuf::from(path);
+/// Run queued scripts in one osascript process and hand each caller its own result
+async fn run_batch(mut batch: Vec<PendingScript>) {
+    if batch.len() == 1 {
+        let pending = batch.remove(0);
+        let output = spawn_osascript(&pending.script, pending.timeout).await;
+        let _ = pending.reply.send(output);
+        return;
+    }
 
-    // Resolve parent directory to check location
-    let check_path = if let Some(parent) = path_buf.parent() {
-        if parent.as_os_str().is_empty() || !parent.exists() {
-            path_buf.clone()
-        } else {
-            parent
-                .canonicalize()
-                .unwrap_or_else(|_| parent.to_path_buf())
-                .join(path_buf.file_name().unwrap_or_default())
+    debug!(
+        "Running {} AppleScripts in one osascript process",
+        batch.len()
+    );
+    let scripts: Vec<&str> = batch.iter().map(|p| p.script.as_str()).collect();
+    let timeout = batch
+        .iter()
+        .map(|p| p.timeout)
+        .max()
+        .unwrap_or(DEFAULT_SCRIPT_TIMEOUT);
+    let output = spawn_osascript(&batch_script(&scripts), timeout).await;
+    match output.and_then(|out| split_batch_output(&out, batch.len())) {
+        Ok(results) => {
+            for (pending, result) in batch.into_iter().zip(results) {
+                let _ = pending.reply.send(result);
+            }
         }
-    } else {
-        path_buf.clone()
-    };
+        // A syntax error in one script stops the whole batch from compiling, and nothing
+        // has run yet, so run them separately to fail only the faulty one
+        Err(e) if is_compile_error(&e) => {
+            debug!("AppleScript batch failed to compile, running scripts separately");
+            for pending in batch {
+                tokio::spawn(async move {
+                    let output = spawn_osascript(&pending.script, pending.timeout).await;
+                    let _ = pending.reply.send(output);
+                });
+            }
+        }
+        Err(e) => {
+            let message = e.to_string();
+            for pending in batch {
+                let _ = pending.reply.send(Err(anyhow::anyhow!("{}", message)));
+            }
+        }
+    }
+}
+
+/// Whether osascript rejected a script before running it (errOSASyntaxError and friends)
+fn is_compile_error(e: &anyhow::Error) -> bool {
+    let message = e.to_string();
+    message.contains("syntax error") || message.contains("(-2741)") || message.contains("(-2740)")
+}
 
-    let home_dir =
-        dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
-    let temp_dir = std::env::temp_dir()
-        .canonicalize()
-        .unwrap_or_else(|_| std::env::temp_dir());
+/// Combine scripts into one: each becomes a handler, run by a second handler that
+/// returns `ok:` and its text result or `error:` and the failure, so one failing
+/// script doesn't abort the others. The runner restores AppleScript's text item
+/// delimiters afterwards, so a script that changes them can't affect the next one.
+/// A script that returns nothing, or something with no text form (a tab, a window),
+/// succeeds with empty output, as it would when run on its own.
+fn batch_script(scripts: &[&str]) -> String {
+    let mut combined = String::new();
+    for (i, script) in scripts.iter().enumerate() {
+        combined.push_str(&format!(
+            r#"on meepo_part_{i}()
+{script}
+end meepo_part_{i}
+
+on meepo_run_{i}()
+    set savedDelimiters to AppleScript's text item delimiters
+    set hasResult to false
+    try
+        set partResult to meepo_part_{i}()
+        try
+            partResult
+            set hasResult to true
+        end try
+    on error errMsg number errNum
+        set AppleScript's text item delimiters to savedDelimiters
+        return "error:execution error: " & errMsg & " (" & errNum & ")"
+    end try
+    set AppleScript's text item delimiters to savedDelimiters
+    if not hasResult then return "ok:"
+    if class of partResult is text then return "ok:" & partResult
+    try
+        return "ok:" & (partResult as text)
+    end try
+    return "ok:"
+end meepo_run_{i}
 
-    let is_in_home = check_path.starts_with(&home_dir);
-    let is_in_temp = check_path.starts_with(&temp_dir);
+"#
+        ));
+    }
+    combined.push_str("set sep to ASCII character 4\nset output to \"\"\n");
+    for i in 0..scripts.len() {
+        combined.push_str(&format!("set output to output & meepo_run_{i}() & sep\n"));
+    }
+    combined.push_str("return output\n");
+    combined
+}
 
-    if !is_in_home && !is_in_temp {
+/// Split a batch's output into per-script results, shaped like separate osascript runs
+fn split_batch_output(output: &str, count: usize) -> Result<Vec<Result<String>>> {
+    let parts: Vec<&str> = output.split(BATCH_SEP).collect();
+    // osascript's trailing newline follows the last separator
+    if parts.len() != count + 1 {
         return Err(anyhow::anyhow!(
-            "Screenshot path '{}' must be within home or temp directory",
-            path
+            "AppleScript batch returned {} results for {} scripts",
+            parts.len() - 1,
+            count
         ));
     }
+    parts[..count]
+        .iter()
+        .map(|part| {
+            if let Some(out) = part.strip_prefix("ok:") {
+                Ok(Ok(format!("{}\n", out)))
+            } else if let Some(error) = part.strip_prefix("error:") {
+                warn!("AppleScript failed: {}", error);
+                Ok(Err(anyhow::anyhow!("AppleScript failed: {}", error)))
+            } else {
+                Err(anyhow::anyhow!("Malformed AppleScript batch result"))
+            }
+        })
+        .collect()
+}
 
-    // Block system directories even if under home
-    let system_dirs = [
-        "/etc",
-        "/bin",
-        "/sbin",
-        "/usr/bin",
-        "/usr/sbin",
-        "/System",
-        "/Library",
-    ];
-    for sys_dir in &system_dirs {
-        if check_path.starts_with(sys_dir) {
-            return Err(anyhow::anyhow!(
-                "Screenshot path cannot target system directory '{}'",
-                sys_dir
-            ));
-        }
-    }
+/// Run one osascript process, giving up after `timeout`
+async fn spawn_osascript(script: &str, timeout: Duration) -> Result<String> {
+    run_osascript(&["-e", script], timeout).await
+}
 
-    Ok(())
+/// Run a JavaScript for Automation script, for the few things only its
+/// Objective-C bridge reaches (CoreGraphics events and display geometry)
+async fn run_jxa(script: &str) -> Result<String> {
+    run_osascript(&["-l", "JavaScript", "-e", script], DEFAULT_SCRIPT_TIMEOUT).await
 }
 
-/// Run an AppleScript with 30 second timeout
-async fn run_applescript(script: &str) -> Result<String> {
-    let output = tokio::time::timeout(
-        std::time::Duration::from_secs(30),
-        Command::new("osascript").arg("-e").arg(script).output(),
-    )
-    .await
-    .map_err(|_| anyhow::anyhow!("AppleScript execution timed out after 30 seconds"))?
-    .context("Failed to execute osascript")?;
+async fn run_osascript(args: &[&str], timeout: Duration) -> Result<String> {
+    let output = tokio::time::timeout(timeout, Command::new("osascript").args(args).output())
+        .await
+        .map_err(|_| timed_out_error(timeout))?
+        .context("Failed to execute osascript")?;
 
     if output.status.success() {
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
@@ -103,44 +255,187 @@ async fn run_applescript(script: &str) -> Result<String> {
     }
 }
 
-pub struct MacOsEmailProvider;
+/// Run an AppleScript that controls `app`, tracking whether automation is permitted.
+/// Fails fast without launching osascript if permission was denied recently.
+async fn run_app_applescript(app: &str, script: &str, timeout: Duration) -> Result<String> {
+    check_automation_permission(app)?;
 
-#[async_trait]
-impl EmailProvider for MacOsEmailProvider {
-    async fn read_emails(&self, limit: u64, mailbox: &str, search: Option<&str>) -> Result<String> {
-        let safe_mailbox = match mailbox.to_lowercase().as_str() {
-            "inbox" => "inbox",
-            "sent" => "sent mailbox",
-            "drafts" => "drafts",
-            "trash" => "trash",
-            _ => "inbox",
+    let result = run_applescript_with_timeout(script, timeout).await;
+    if is_automation_denial(&result) {
+        record_automation_permission(app, false);
+        return Err(automation_denied_error(app));
+    }
+    if result.is_ok() {
+        record_automation_permission(app, true);
+    }
+    result
+}
+
+/// Whether a script run was refused because automating the app isn't permitted.
+/// Only error text is inspected: osascript's stderr, or the "Error: ..." most
+/// scripts return from their `on error` handler. Regular output is data (an
+/// email can mention -1743) and never counts as a denial.
+fn is_automation_denial(result: &Result<String>) -> bool {
+    let denied = |text: &str| {
+        text.contains(NOT_PERMITTED_ERROR_CODE)
+            || text.contains("Not authorized to send Apple events")
+    };
+    match result {
+        Ok(output) => output
+            .trim_start()
+            .strip_prefix("Error: ")
+            .is_some_and(denied),
+        Err(e) => denied(&e.to_string()),
+    }
+}
+
+/// Mail.app mailbox and `whose` clause for a read_emails request
+fn mail_message_filter(mailbox: &str, search: Option<&str>) -> (&'static str, String) {
+    let safe_mailbox = match mailbox.to_lowercase().as_str() {
+        "inbox" => "inbox",
+        "sent" => "sent mailbox",
+        "drafts" => "drafts",
+        "trash" => "trash",
+        _ => "inbox",
+    };
+    let filter_clause = if let Some(term) = search {
+        let safe_term = sanitize_applescript_string(term);
+        format!(
+            r#" whose (subject contains "{}" or sender contains "{}")"#,
+            safe_term, safe_term
+        )
+    } else {
+        String::new()
+    };
+    (safe_mailbox, filter_clause)
+}
+
+/// AppleScript that appends the mailboxes called `mailbox` to the list `sources`.
+/// Special mailboxes are account-independent; any other name is looked up in
+/// every account, and accounts without it are skipped.
+fn mail_mailbox_sources(mailbox: &str) -> String {
+    let special = match mailbox.to_lowercase().as_str() {
+        "inbox" => Some("inbox"),
+        "sent" => Some("sent mailbox"),
+        "drafts" => Some("drafts mailbox"),
+        "trash" => Some("trash mailbox"),
+        "junk" => Some("junk mailbox"),
+        _ => None,
+    };
+    match special {
+        Some(special) => format!(
+            r#"try
+            set end of sources to {}
+        end try"#,
+            special
+        ),
+        None => format!(
+            r#"repeat with acct in every account
+            try
+                set end of sources to (mailbox "{}" of acct)
+            end try
+        end repeat"#,
+            sanitize_applescript_string(mailbox)
+        ),
+    }
+}
+
+/// AppleScript that counts the messages matching a read and sets `firstIdx`/`lastIdx`
+/// to the 1-based range of the requested page, clamped to what exists. Mail.app
+/// errors on ranges past the last message, so callers only read when
+/// `firstIdx <= lastIdx`.
+fn mail_page_range(safe_mailbox: &str, filter_clause: &str, offset: u64, limit: u64) -> String {
+    format!(
+        r#"set total to count of (messages of {safe_mailbox}{filter_clause})
+        set firstIdx to {first}
+        set lastIdx to {last}
+        if lastIdx > total then set lastIdx to total"#,
+        first = offset.saturating_add(1),
+        last = offset.saturating_add(limit),
+    )
+}
+
+/// Parse the output of [`MacOsEmailProvider::read_emails_page`]: the number of
+/// matching messages, then the usual summary records
+fn parse_counted_email_records(output: &str) -> Result<(u64, Vec<EmailSummary>)> {
+    let trimmed = output.trim_start();
+    if trimmed.starts_with("Error:") {
+        return Err(anyhow::anyhow!("{}", trimmed.trim_end()));
+    }
+    let (total, records) = trimmed
+        .split_once(mime::RECORD_SEP)
+        .unwrap_or((trimmed, ""));
+    let total = total
+        .trim()
+        .parse()
+        .with_context(|| format!("Unexpected Mail.app output: {}", total.trim()))?;
+    Ok((total, parse_email_summary_records(records)?))
+}
+
+/// Parse records from [`MacOsEmailProvider::read_emails_across`]: an age in
+/// seconds, then the usual summary fields. Returns them newest first.
+fn parse_aged_email_records(output: &str) -> Result<Vec<EmailSummary>> {
+    let trimmed = output.trim_start();
+    if trimmed.starts_with("Error:") {
+        return Err(anyhow::anyhow!("{}", trimmed.trim_end()));
+    }
+    let mut aged = Vec::new();
+    for record in output.split(mime::RECORD_SEP) {
+        let Some((age, summary)) = record.split_once(mime::FIELD_SEP) else {
+            continue;
         };
-        let filter_clause = if let Some(term) = search {
-            let safe_term = sanitize_applescript_string(term);
-            format!(
-                r#" whose (subject contains "{}" or sender contains "{}")"#,
-                safe_term, safe_term
-            )
-        } else {
-            String::new()
+        let Ok(age) = age.trim().parse::<i64>() else {
+            continue;
         };
+        for email in parse_email_summary_records(summary)? {
+            aged.push((age, email));
+        }
+    }
+    aged.sort_by_key(|(age, _)| *age);
+    Ok(aged.into_iter().map(|(_, email)| email).collect())
+}
+
+pub struct MacOsEmailProvider {
+    /// How long each Mail.app script may run
+    timeout: Duration,
+}
+
+impl MacOsEmailProvider {
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+impl Default for MacOsEmailProvider {
+    fn default() -> Self {
+        Self::new(DEFAULT_SCRIPT_TIMEOUT)
+    }
+}
+
+#[async_trait]
+impl EmailProvider for MacOsEmailProvider {
+    async fn read_emails_structured(
+        &self,
+        limit: u64,
+        mailbox: &str,
+        search: Option<&str>,
+    ) -> Result<Vec<EmailSummary>> {
+        let (safe_mailbox, filter_clause) = mail_message_filter(mailbox, search);
         debug!("Reading {} emails from Mail.app ({})", limit, mailbox);
         let script = format!(
             r#"
 tell application "Mail"
     try
-        set msgs to (messages 1 thru {} of {}{})
+        set rs to ASCII character 30
+        set fs to ASCII character 31
+        set msgs to (messages 1 thru {limit} of {safe_mailbox}{filter_clause})
         set output to ""
         repeat with m in msgs
             set msgBody to content of m
-            if length of msgBody > 500 then
-                set msgBody to text 1 thru 500 of msgBody
+            if length of msgBody > {max_chars} then
+                set msgBody to text 1 thru {max_chars} of msgBody
             end if
-            set output to output & "From: " & (sender of m) & "\n"
-            set output to output & "Subject: " & (subject of m) & "\n"
-            set output to output & "Date: " & (date received of m as string) & "\n"
-            set output to output & "Preview: " & msgBody & "\n"
-            set output to output & "---\n"
+            set output to output & (message id of m) & fs & (sender of m) & fs & (subject of m) & fs & (date received of m as string) & fs & msgBody & rs
         end repeat
         return output
     on error errMsg
@@ -148,9 +443,237 @@ tell application "Mail"
     end try
 end tell
 "#,
-            limit, safe_mailbox, filter_clause
+            max_chars = EMAIL_PREVIEW_CHARS,
         );
-        run_applescript(&script).await
+        let output = run_app_applescript("Mail", &script, self.timeout).await?;
+        parse_email_summary_records(&output)
+    }
+
+    /// Counts the matching messages and reads only `messages offset+1 thru
+    /// offset+limit`, so paging deep into a mailbox doesn't read what's skipped
+    async fn read_emails_page(
+        &self,
+        limit: u64,
+        offset: u64,
+        mailbox: &str,
+        search: Option<&str>,
+    ) -> Result<EmailPage> {
+        let (safe_mailbox, filter_clause) = mail_message_filter(mailbox, search);
+        debug!(
+            "Reading {} emails at offset {} from Mail.app ({})",
+            limit, offset, mailbox
+        );
+        let script = format!(
+            r#"
+tell application "Mail"
+    try
+        set rs to ASCII character 30
+        set fs to ASCII character 31
+        {range}
+        set output to (total as string) & rs
+        if firstIdx is less than or equal to lastIdx then
+            set msgs to (messages firstIdx thru lastIdx of {safe_mailbox}{filter_clause})
+            repeat with m in msgs
+                set msgBody to content of m
+                if length of msgBody > {max_chars} then
+                    set msgBody to text 1 thru {max_chars} of msgBody
+                end if
+                set output to output & (message id of m) & fs & (sender of m) & fs & (subject of m) & fs & (date received of m as string) & fs & msgBody & rs
+            end repeat
+        end if
+        return output
+    on error errMsg
+        return "Error: " & errMsg
+    end try
+end tell
+"#,
+            range = mail_page_range(safe_mailbox, &filter_clause, offset, limit),
+            max_chars = EMAIL_PREVIEW_CHARS,
+        );
+        let output = run_app_applescript("Mail", &script, self.timeout).await?;
+        let (total, emails) = parse_counted_email_records(&output)?;
+        Ok(EmailPage {
+            page: PageInfo::of_total(offset, emails.len() as u64, total),
+            emails,
+        })
+    }
+
+    /// Reads every mailbox in one script. Each record is prefixed with the
+    /// message's age in seconds, which sorts reliably where Mail's
+    /// locale-formatted dates don't.
+    async fn read_emails_across(
+        &self,
+        limit: u64,
+        mailboxes: &[&str],
+        search: Option<&str>,
+    ) -> Result<Vec<EmailSummary>> {
+        let (_, filter_clause) = mail_message_filter("inbox", search);
+        debug!("Reading {} emails from Mail.app ({:?})", limit, mailboxes);
+        let sources = mailboxes
+            .iter()
+            .map(|mailbox| mail_mailbox_sources(mailbox))
+            .collect::<Vec<_>>()
+            .join("\n        ");
+        let script = format!(
+            r#"
+tell application "Mail"
+    try
+        set rs to ASCII character 30
+        set fs to ASCII character 31
+        set now to current date
+        set sources to {{}}
+        {sources}
+        set output to ""
+        repeat with mbox in sources
+            try
+                set msgs to (every message of mbox{filter_clause})
+                set n to count of msgs
+                if n > {limit} then set n to {limit}
+                repeat with i from 1 to n
+                    set m to item i of msgs
+                    set msgBody to content of m
+                    if length of msgBody > {max_chars} then
+                        set msgBody to text 1 thru {max_chars} of msgBody
+                    end if
+                    set output to output & ((now - (date received of m)) as integer) & fs & (message id of m) & fs & (sender of m) & fs & (subject of m) & fs & (date received of m as string) & fs & msgBody & rs
+                end repeat
+            end try
+        end repeat
+        return output
+    on error errMsg
+        return "Error: " & errMsg
+    end try
+end tell
+"#,
+            max_chars = EMAIL_PREVIEW_CHARS,
+        );
+        let output = run_app_applescript("Mail", &script, self.timeout).await?;
+        let emails = parse_aged_email_records(&output)?;
+        Ok(take_unique_emails(emails, limit))
+    }
+
+    async fn read_emails_with_options(
+        &self,
+        limit: u64,
+        offset: u64,
+        mailbox: &str,
+        search: Option<&str>,
+        options: EmailReadOptions,
+    ) -> Result<String> {
+        if options.is_plain() {
+            let page = self
+                .read_emails_page(limit, offset, mailbox, search)
+                .await?;
+            return Ok(format_email_page(mailbox, &page));
+        }
+        let (safe_mailbox, filter_clause) = mail_message_filter(mailbox, search);
+        debug!(
+            "Reading {} emails at offset {} from Mail.app ({}) with {:?}",
+            limit, offset, mailbox, options
+        );
+        // Mail.app has no HTML accessor; the raw source is parsed on our side
+        let html_clause = if options.prefer_html {
+            "set msgHtml to source of m"
+        } else {
+            "set msgHtml to \"\""
+        };
+        let attachments_clause = if options.include_attachments {
+            r#"repeat with a in mail attachments of m
+                set msgAttachments to msgAttachments & (name of a) & af & (MIME type of a) & af & (file size of a) & asep
+            end repeat"#
+        } else {
+            ""
+        };
+        let script = format!(
+            r#"
+tell application "Mail"
+    try
+        set rs to ASCII character 30
+        set fs to ASCII character 31
+        set asep to ASCII character 29
+        set af to ASCII character 28
+        {range}
+        set output to ""
+        if firstIdx is less than or equal to lastIdx then
+            set msgs to (messages firstIdx thru lastIdx of {safe_mailbox}{filter_clause})
+            repeat with m in msgs
+                set msgBody to content of m
+                if length of msgBody > {max_chars} then
+                    set msgBody to text 1 thru {max_chars} of msgBody
+                end if
+                {html_clause}
+                set msgAttachments to ""
+                try
+                    {attachments_clause}
+                end try
+                set output to output & (sender of m) & fs & (subject of m) & fs & (date received of m as string) & fs & msgBody & fs & msgHtml & fs & msgAttachments & rs
+            end repeat
+        end if
+        return output
+    on error errMsg
+        return "Error: " & errMsg
+    end try
+end tell
+"#,
+            range = mail_page_range(safe_mailbox, &filter_clause, offset, limit),
+            max_chars = mime::RICH_BODY_CHARS,
+        );
+        let output = run_app_applescript("Mail", &script, self.timeout).await?;
+        if output.trim_start().starts_with("Error:") {
+            return Ok(output);
+        }
+        Ok(mime::format_script_records(
+            &output,
+            true,
+            options.include_attachments,
+        ))
+    }
+
+    async fn read_email_body(&self, message_id: &str, mailbox: &str) -> Result<String> {
+        // Only ids that pass ReplyTarget's check go into the script, so none need escaping
+        let ReplyTarget::MessageId(id) = ReplyTarget::parse(message_id) else {
+            anyhow::bail!("'{}' is not a Message-ID", message_id);
+        };
+        let (safe_mailbox, _) = mail_message_filter(mailbox, None);
+        debug!("Reading the body of <{}> from Mail.app ({})", id, mailbox);
+        // The body is prefixed so text that happens to start with "Error:" isn't taken as one
+        let script = format!(
+            r#"
+tell application "Mail"
+    try
+        set found to (every message of {safe_mailbox} whose message id is "{id}")
+        if (count of found) = 0 then
+            return "Error: No message with Message-ID <{id}> in {mailbox}"
+        end if
+        set msgBody to content of item 1 of found
+        if length of msgBody > {max_chars} then
+            set msgBody to text 1 thru {max_chars} of msgBody
+        end if
+        return "Body:" & msgBody
+    on error errMsg
+        return "Error: " & errMsg
+    end try
+end tell
+"#,
+            mailbox = safe_mailbox,
+            max_chars = EMAIL_BODY_CHARS,
+        );
+        let output = run_app_applescript("Mail", &script, self.timeout).await?;
+        match output.trim_start().strip_prefix("Body:") {
+            Some(body) => Ok(body.trim().to_string()),
+            None => Err(anyhow::anyhow!("{}", output.trim())),
+        }
+    }
+
+    async fn prewarm(&self) -> Result<()> {
+        debug!("Prewarming Mail.app");
+        run_app_applescript(
+            "Mail",
+            r#"tell application "Mail" to count of accounts"#,
+            self.timeout,
+        )
+        .await
+        .map(|_| ())
     }
 
     async fn send_email(
@@ -160,96 +683,267 @@ end tell
         body: &str,
         cc: Option<&str>,
         in_reply_to: Option<&str>,
+        attachments: &[PathBuf],
     ) -> Result<String> {
-        let safe_to = sanitize_applescript_string(to);
-        let safe_subject = sanitize_applescript_string(subject);
-        let safe_body = sanitize_applescript_string(body);
+        validate_attachments(attachments)?;
+        let script = compose_email_script(to, subject, body, cc, in_reply_to, attachments, true);
+        run_app_applescript("Mail", &script, self.timeout).await
+    }
 
-        let script = if let Some(reply_subject) = in_reply_to {
-            let safe_reply_subject = sanitize_applescript_string(reply_subject);
-            debug!("Replying to email with subject: {}", reply_subject);
-            format!(
-                r#"
+    async fn create_draft(
+        &self,
+        to: &str,
+        subject: &str,
+        body: &str,
+        cc: Option<&str>,
+        in_reply_to: Option<&str>,
+        attachments: &[PathBuf],
+    ) -> Result<String> {
+        validate_attachments(attachments)?;
+        let script = compose_email_script(to, subject, body, cc, in_reply_to, attachments, false);
+        run_app_applescript("Mail", &script, self.timeout).await
+    }
+}
+
+/// Script that composes a message in Mail.app and either sends it or, when
+/// `deliver` is false, leaves it open in a compose window and saves it to Drafts
+fn compose_email_script(
+    to: &str,
+    subject: &str,
+    body: &str,
+    cc: Option<&str>,
+    in_reply_to: Option<&str>,
+    attachments: &[PathBuf],
+    deliver: bool,
+) -> String {
+    let safe_to = sanitize_applescript_string(to);
+    let safe_subject = sanitize_applescript_string(subject);
+    let safe_body = sanitize_applescript_string(body);
+    // Drafts stay open in a compose window; Mail autosaves those, so a failed
+    // explicit save isn't an error
+    let finish = |message: &str| {
+        let action = if deliver {
+            format!("send {}", message)
+        } else {
+            format!("try\n                save {}\n            end try", message)
+        };
+        if attachments.is_empty() {
+            return action;
+        }
+        let mut script = String::new();
+        for path in attachments {
+            script.push_str(&format!(
+                "tell content of {} to make new attachment with properties {{file name:(POSIX file \"{}\")}} at after the last paragraph\n            ",
+                message,
+                sanitize_applescript_string(&path.to_string_lossy())
+            ));
+        }
+        // Mail attaches files asynchronously; sending straight away can drop them
+        format!("{}delay 1\n            {}", script, action)
+    };
+
+    if let Some(in_reply_to) = in_reply_to {
+        let find_original = match ReplyTarget::parse(in_reply_to) {
+            ReplyTarget::MessageId(id) => {
+                debug!("Replying to email with Message-ID: {}", id);
+                // Mail stores ids without angle brackets; check the inbox first, then
+                // every other mailbox so replies to archived or sent mail still thread
+                format!(
+                    r#"set targetMsgs to (every message of inbox whose message id is "{id}")
+        if (count of targetMsgs) = 0 then
+            repeat with acct in every account
+                repeat with mbox in every mailbox of acct
+                    set targetMsgs to (every message of mbox whose message id is "{id}")
+                    if (count of targetMsgs) > 0 then exit repeat
+                end repeat
+                if (count of targetMsgs) > 0 then exit repeat
+            end repeat
+        end if"#
+                )
+            }
+            ReplyTarget::Subject(reply_subject) => {
+                debug!("Replying to email with subject: {}", reply_subject);
+                format!(
+                    r#"set targetMsgs to (every message of inbox whose subject contains "{}")"#,
+                    sanitize_applescript_string(reply_subject)
+                )
+            }
+        };
+        let (threaded, unthreaded) = if deliver {
+            (
+                "Reply sent (threaded)",
+                "Email sent (no original found for threading)",
+            )
+        } else {
+            (
+                "Draft reply saved (threaded)",
+                "Draft saved (no original found for threading)",
+            )
+        };
+        format!(
+            r#"
 tell application "Mail"
     try
-        set targetMsgs to (every message of inbox whose subject contains "{}")
+        {}
         if (count of targetMsgs) > 0 then
             set originalMsg to item 1 of targetMsgs
             set replyMsg to reply originalMsg with opening window
             set content of replyMsg to "{}"
-            send replyMsg
-            return "Reply sent (threaded)"
+            {}
+            return "{}"
         else
             set newMessage to make new outgoing message with properties {{subject:"{}", content:"{}", visible:true}}
             tell newMessage
                 make new to recipient at end of to recipients with properties {{address:"{}"}}
-                send
             end tell
-            return "Email sent (no original found for threading)"
+            {}
+            return "{}"
         end if
     on error errMsg
         return "Error: " & errMsg
     end try
 end tell
 "#,
-                safe_reply_subject, safe_body, safe_subject, safe_body, safe_to
-            )
-        } else {
-            debug!("Sending new email to: {}", to);
-            let cc_block = if let Some(cc_addr) = cc {
-                let safe_cc = sanitize_applescript_string(cc_addr);
-                format!(
-                    r#"
-                make new cc recipient at end of cc recipients with properties {{address:"{}"}}"#,
-                    safe_cc
-                )
-            } else {
-                String::new()
-            };
+            find_original,
+            safe_body,
+            finish("replyMsg"),
+            threaded,
+            safe_subject,
+            safe_body,
+            safe_to,
+            finish("newMessage"),
+            unthreaded
+        )
+    } else {
+        debug!("Composing new email to: {}", to);
+        let cc_block = if let Some(cc_addr) = cc {
+            let safe_cc = sanitize_applescript_string(cc_addr);
             format!(
                 r#"
+            make new cc recipient at end of cc recipients with properties {{address:"{}"}}"#,
+                safe_cc
+            )
+        } else {
+            String::new()
+        };
+        let done = if deliver {
+            "Email sent successfully"
+        } else {
+            "Draft saved to Drafts (not sent)"
+        };
+        format!(
+            r#"
 tell application "Mail"
     try
         set newMessage to make new outgoing message with properties {{subject:"{}", content:"{}", visible:true}}
         tell newMessage
             make new to recipient at end of to recipients with properties {{address:"{}"}}{}
-            send
         end tell
-        return "Email sent successfully"
+        {}
+        return "{}"
     on error errMsg
         return "Error: " & errMsg
     end try
 end tell
 "#,
-                safe_subject, safe_body, safe_to, cc_block
-            )
-        };
-        run_applescript(&script).await
+            safe_subject,
+            safe_body,
+            safe_to,
+            cc_block,
+            finish("newMessage"),
+            done
+        )
+    }
+}
+
+pub struct MacOsCalendarProvider {
+    /// How long each Calendar.app script may run
+    timeout: Duration,
+}
+
+impl MacOsCalendarProvider {
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+impl Default for MacOsCalendarProvider {
+    fn default() -> Self {
+        Self::new(DEFAULT_SCRIPT_TIMEOUT)
+    }
+}
+
+#[async_trait]
+impl CalendarProvider for MacOsCalendarProvider {
+    async fn read_events(&self, days_ahead: u64, calendar_name: Option<&str>) -> Result<String> {
+        debug!(
+            "Reading calendar events for next {} days ({})",
+            days_ahead,
+            calendar_name.unwrap_or("all calendars")
+        );
+        let script = format!(
+            r#"
+tell application "Calendar"
+    try
+        {select}
+        set startDate to current date
+        set endDate to (current date) + ({days_ahead} * days)
+        set output to ""
+        repeat with cal in targetCals
+            set calName to name of cal
+            set theEvents to (every event of cal whose start date is greater than or equal to startDate and start date is less than or equal to endDate)
+            repeat with evt in theEvents
+                set output to output & "Calendar: " & calName & "\n"
+                set output to output & "Event: " & (summary of evt) & "\n"
+                set output to output & "ID: " & (uid of evt) & "\n"
+                set output to output & "Start: " & (start date of evt as string) & "\n"
+                set output to output & "End: " & (end date of evt as string) & "\n"
+                set output to output & "---\n"
+            end repeat
+        end repeat
+        return output
+    on error errMsg
+        return "Error: " & errMsg
+    end try
+end tell
+"#,
+            select = select_calendars_script(calendar_name),
+        );
+        let output = run_app_applescript("Calendar", &script, self.timeout).await?;
+        calendar_result(calendar_name, output)
     }
-}
-
-pub struct MacOsCalendarProvider;
 
-#[async_trait]
-impl CalendarProvider for MacOsCalendarProvider {
-    async fn read_events(&self, days_ahead: u64) -> Result<String> {
-        debug!("Reading calendar events for next {} days", days_ahead);
+    /// Dates go out as numeric local components, never `as string`, whose
+    /// wording follows the system language
+    async fn read_events_structured(
+        &self,
+        days_ahead: u64,
+        calendar_name: Option<&str>,
+    ) -> Result<Vec<EventSummary>> {
+        debug!(
+            "Reading structured calendar events for next {} days ({})",
+            days_ahead,
+            calendar_name.unwrap_or("all calendars")
+        );
         let script = format!(
             r#"
+on localStamp(d)
+    return ((year of d) as string) & " " & ((month of d as integer) as string) & " " & ((day of d) as string) & " " & ((time of d) as string)
+end localStamp
+
 tell application "Calendar"
     try
+        {select}
+        set rs to ASCII character 30
+        set fs to ASCII character 31
         set startDate to current date
-        set endDate to (current date) + ({} * days)
+        set endDate to (current date) + ({days_ahead} * days)
         set output to ""
-        repeat with cal in calendars
+        repeat with cal in targetCals
             set calName to name of cal
             set theEvents to (every event of cal whose start date is greater than or equal to startDate and start date is less than or equal to endDate)
             repeat with evt in theEvents
-                set output to output & "Calendar: " & calName & "\n"
-                set output to output & "Event: " & (summary of evt) & "\n"
-                set output to output & "Start: " & (start date of evt as string) & "\n"
-                set output to output & "End: " & (end date of evt as string) & "\n"
-                set output to output & "---\n"
+                set output to output & (uid of evt) & fs & (summary of evt) & fs & calName & fs & ((allday event of evt) as string) & fs & (my localStamp(start date of evt)) & fs & (my localStamp(end date of evt)) & rs
             end repeat
         end repeat
         return output
@@ -258,9 +952,36 @@ tell application "Calendar"
     end try
 end tell
 "#,
-            days_ahead
+            select = select_calendars_script(calendar_name),
         );
-        run_applescript(&script).await
+        let output = run_app_applescript("Calendar", &script, self.timeout).await?;
+        parse_event_records(&calendar_result(calendar_name, output)?)
+    }
+
+    async fn list_calendars(&self) -> Result<String> {
+        debug!("Listing calendars");
+        let script = r#"
+tell application "Calendar"
+    try
+        set AppleScript's text item delimiters to linefeed
+        return (name of every calendar) as string
+    on error errMsg
+        return "Error: " & errMsg
+    end try
+end tell
+"#;
+        run_app_applescript("Calendar", script, self.timeout).await
+    }
+
+    async fn prewarm(&self) -> Result<()> {
+        debug!("Prewarming Calendar.app");
+        run_app_applescript(
+            "Calendar",
+            r#"tell application "Calendar" to count of calendars"#,
+            self.timeout,
+        )
+        .await
+        .map(|_| ())
     }
 
     async fn create_event(
@@ -268,64 +989,279 @@ end tell
         summary: &str,
         start_time: &str,
         duration_minutes: u64,
+        calendar_name: Option<&str>,
+        details: &EventDetails,
     ) -> Result<String> {
         debug!("Creating calendar event: {}", summary);
+        details.validate()?;
         let safe_summary = sanitize_applescript_string(summary);
-        let safe_start_time = sanitize_applescript_string(start_time);
+        let start_date = applescript_date("startDate", &resolve_event_time(start_time)?);
+        let end_date = match &details.end_time {
+            Some(end) => applescript_date("endDate", &resolve_event_time(end)?),
+            None => format!(
+                "set endDate to startDate + ({} * minutes)",
+                duration_minutes
+            ),
+        };
+        let mut properties = format!(
+            r#"summary:"{}", start date:startDate, end date:endDate"#,
+            safe_summary
+        );
+        if let Some(location) = &details.location {
+            properties.push_str(&format!(
+                r#", location:"{}""#,
+                sanitize_applescript_string(location)
+            ));
+        }
+        if let Some(notes) = &details.notes {
+            properties.push_str(&format!(
+                r#", description:"{}""#,
+                sanitize_applescript_string(notes)
+            ));
+        }
+        // Calendar sends the invitations through the calendar's account once
+        // attendees are on a saved event
+        let attendees: String = details
+            .attendees
+            .iter()
+            .map(|address| {
+                format!(
+                    r#"
+                make new attendee at end of attendees with properties {{email:"{}"}}"#,
+                    sanitize_applescript_string(address)
+                )
+            })
+            .collect();
+        let invited = if details.attendees.is_empty() {
+            String::new()
+        } else {
+            format!(
+                " & \" (invitations sent to {})\"",
+                sanitize_applescript_string(&details.attendees.join(", "))
+            )
+        };
+
         let script = format!(
             r#"
 tell application "Calendar"
     try
-        set startDate to date "{}"
-        set endDate to startDate + ({} * minutes)
-        set targetCal to first calendar
+        {select}
+        {start_date}
+        {end_date}
+        if endDate is less than or equal to startDate then
+            return "Error: the end time must be after the start time"
+        end if
+        set targetCal to item 1 of targetCals
         tell targetCal
-            make new event with properties {{summary:"{}", start date:startDate, end date:endDate}}
+            set newEvent to make new event with properties {{{properties}}}
+            tell newEvent{attendees}
+            end tell
         end tell
-        return "Event created successfully in calendar: " & (name of targetCal)
+        return "Event created successfully in calendar: " & (name of targetCal){invited}
     on error errMsg
         return "Error: " & errMsg
     end try
 end tell
 "#,
-            safe_start_time, duration_minutes, safe_summary
+            select = select_calendars_script(calendar_name),
         );
-        run_applescript(&script).await
+        let output = run_app_applescript("Calendar", &script, self.timeout).await?;
+        calendar_result(calendar_name, output)
+    }
+
+    async fn update_event(&self, event_id: &str, changes: &EventChanges) -> Result<String> {
+        debug!("Updating calendar event: {}", event_id);
+        if changes.is_empty() {
+            return Err(anyhow::anyhow!("No changes given for event '{}'", event_id));
+        }
+        let mut edits = Vec::new();
+        if let Some(summary) = &changes.summary {
+            edits.push(format!(
+                r#"set summary of targetEvent to "{}""#,
+                sanitize_applescript_string(summary)
+            ));
+        }
+        if let Some(location) = &changes.location {
+            edits.push(format!(
+                r#"set location of targetEvent to "{}""#,
+                sanitize_applescript_string(location)
+            ));
+        }
+        if let Some(notes) = &changes.notes {
+            edits.push(format!(
+                r#"set description of targetEvent to "{}""#,
+                sanitize_applescript_string(notes)
+            ));
+        }
+        if changes.start_time.is_some() || changes.duration_minutes.is_some() {
+            let new_start = match &changes.start_time {
+                Some(start) => applescript_date("newStart", &resolve_event_time(start)?),
+                None => "set newStart to oldStart".to_string(),
+            };
+            let new_duration = match changes.duration_minutes {
+                Some(minutes) => format!("{} * minutes", minutes),
+                None => "(end date of targetEvent) - oldStart".to_string(),
+            };
+            // Set the bound that keeps start before end at every step
+            edits.push(format!(
+                r#"set oldStart to start date of targetEvent
+            {new_start}
+            set newEnd to newStart + ({new_duration})
+            if newStart > oldStart then
+                set end date of targetEvent to newEnd
+                set start date of targetEvent to newStart
+            else
+                set start date of targetEvent to newStart
+                set end date of targetEvent to newEnd
+            end if"#
+            ));
+        }
+
+        let script = format!(
+            r#"
+tell application "Calendar"
+    try
+        {find}
+        {edits}
+        return "Event updated: " & (summary of targetEvent) & " (" & (start date of targetEvent as string) & ")"
+    on error errMsg
+        return "Error: " & errMsg
+    end try
+end tell
+"#,
+            find = find_event_script(event_id),
+            edits = edits.join("\n        "),
+        );
+        let output = run_app_applescript("Calendar", &script, self.timeout).await?;
+        event_result(event_id, output)
+    }
+
+    async fn delete_event(&self, event_id: &str) -> Result<String> {
+        debug!("Deleting calendar event: {}", event_id);
+        let script = format!(
+            r#"
+tell application "Calendar"
+    try
+        {find}
+        set eventSummary to summary of targetEvent
+        delete targetEvent
+        return "Event deleted: " & eventSummary
+    on error errMsg
+        return "Error: " & errMsg
+    end try
+end tell
+"#,
+            find = find_event_script(event_id),
+        );
+        let output = run_app_applescript("Calendar", &script, self.timeout).await?;
+        event_result(event_id, output)
+    }
+}
+
+/// Prefix of Calendar script output when the named calendar doesn't exist
+/// (followed by the available names, one per line)
+const CALENDAR_NOT_FOUND: &str = "CALENDAR_NOT_FOUND:";
+
+/// AppleScript that sets `var` to `time`, built from its components because
+/// `date "..."` parses text by the system locale and rejects most formats
+fn applescript_date(var: &str, time: &DateTime<Local>) -> String {
+    // Day 1 first, so moving to a shorter month can't overflow into the next
+    format!(
+        "set {var} to current date
+        set day of {var} to 1
+        set year of {var} to {}
+        set month of {var} to {}
+        set day of {var} to {}
+        set time of {var} to {}",
+        time.year(),
+        time.month(),
+        time.day(),
+        time.num_seconds_from_midnight()
+    )
+}
+
+/// AppleScript (inside `tell application "Calendar"`) that sets `targetCals` to the
+/// calendar named `calendar_name`, or to every calendar when `None`
+fn select_calendars_script(calendar_name: Option<&str>) -> String {
+    match calendar_name {
+        None => "set targetCals to calendars".to_string(),
+        Some(name) => format!(
+            r#"set targetCals to (every calendar whose name is "{}")
+        if (count of targetCals) is 0 then
+            set AppleScript's text item delimiters to linefeed
+            return "{}" & linefeed & ((name of every calendar) as string)
+        end if"#,
+            sanitize_applescript_string(name),
+            CALENDAR_NOT_FOUND
+        ),
+    }
+}
+
+/// Turn the unknown-calendar marker from a Calendar script into a readable error
+fn calendar_result(calendar_name: Option<&str>, output: String) -> Result<String> {
+    match output.trim().strip_prefix(CALENDAR_NOT_FOUND) {
+        Some(names) => {
+            let available: Vec<String> = names
+                .lines()
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(String::from)
+                .collect();
+            Err(calendar_not_found_error(
+                calendar_name.unwrap_or_default(),
+                &available,
+            ))
+        }
+        None => Ok(output),
+    }
+}
+
+/// Output of the Calendar scripts when the targeted event doesn't exist
+const EVENT_NOT_FOUND: &str = "EVENT_NOT_FOUND";
+
+/// AppleScript (inside `tell application "Calendar"`) that sets `targetEvent`
+/// to the event whose uid is `event_id`, or returns [`EVENT_NOT_FOUND`]
+fn find_event_script(event_id: &str) -> String {
+    format!(
+        r#"set targetEvent to missing value
+        repeat with cal in calendars
+            set matches to (every event of cal whose uid is "{}")
+            if (count of matches) > 0 then
+                set targetEvent to item 1 of matches
+                exit repeat
+            end if
+        end repeat
+        if targetEvent is missing value then return "{}""#,
+        sanitize_applescript_string(event_id),
+        EVENT_NOT_FOUND
+    )
+}
+
+/// Turn the not-found marker from an event script into a readable error
+fn event_result(event_id: &str, output: String) -> Result<String> {
+    if output.trim() == EVENT_NOT_FOUND {
+        Err(event_not_found_error(event_id))
+    } else {
+        Ok(output)
     }
 }
 
-/// Allowlist of valid UI element types for macOS accessibility
-const VALID_ELEMENT_TYPES: &[&str] = &[
-    "button",
-    "checkbox",
-    "radio button",
-    "text field",
-    "text area",
-    "pop up button",
-    "menu item",
-    "menu button",
-    "slider",
-    "tab group",
-    "table",
-    "outline",
-    "list",
-    "scroll area",
-    "group",
-    "window",
-    "sheet",
-    "toolbar",
-    "static text",
-    "image",
-    "link",
-    "cell",
-    "row",
-    "column",
-    "combo box",
-    "incrementor",
-    "relevance indicator",
-];
-
-pub struct MacOsUiAutomation;
+pub struct MacOsUiAutomation {
+    /// How long each System Events script may run
+    timeout: Duration,
+}
+
+impl MacOsUiAutomation {
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+impl Default for MacOsUiAutomation {
+    fn default() -> Self {
+        Self::new(DEFAULT_SCRIPT_TIMEOUT)
+    }
+}
 
 #[async_trait]
 impl UiAutomation for MacOsUiAutomation {
@@ -347,7 +1283,57 @@ tell application "System Events"
     end try
 end tell
 "#;
-        run_applescript(script).await
+        run_applescript_with_timeout(script, self.timeout).await
+    }
+
+    async fn list_elements(&self, element_type: Option<&str>) -> Result<Vec<UiElement>> {
+        let wanted = listed_element_types(element_type)?;
+        debug!("Listing {:?} elements of the front window", wanted);
+        let wanted = wanted
+            .iter()
+            .map(|t| format!("\"{}\"", t))
+            .collect::<Vec<_>>()
+            .join(", ");
+        // `entire contents` walks the whole window; the class name ("button",
+        // "radio button", ...) is what click_element takes as the type
+        let script = format!(
+            r#"
+tell application "System Events"
+    try
+        set rs to ASCII character 30
+        set fs to ASCII character 31
+        set wanted to {{{wanted}}}
+        set frontApp to first application process whose frontmost is true
+        set elems to entire contents of front window of frontApp
+        set output to ""
+        set found to 0
+        repeat with e in elems
+            try
+                set elemRole to (class of e) as string
+                if wanted contains elemRole then
+                    set elemName to name of e
+                    if elemName is missing value then set elemName to description of e
+                    if elemName is missing value then set elemName to ""
+                    set isEnabled to true
+                    try
+                        set isEnabled to enabled of e
+                    end try
+                    set output to output & elemRole & fs & elemName & fs & isEnabled & rs
+                    set found to found + 1
+                    if found = {max} then exit repeat
+                end if
+            end try
+        end repeat
+        return output
+    on error errMsg
+        return "Error: " & errMsg
+    end try
+end tell
+"#,
+            max = MAX_LISTED_ELEMENTS,
+        );
+        let output = run_applescript_with_timeout(&script, self.timeout).await?;
+        parse_ui_element_records(&output)
     }
 
     async fn click_element(&self, element_name: &str, element_type: &str) -> Result<String> {
@@ -375,27 +1361,70 @@ end tell
 "#,
             element_type, safe_element_name
         );
-        run_applescript(&script).await
+        run_applescript_with_timeout(&script, self.timeout).await
     }
 
-    async fn type_text(&self, text: &str) -> Result<String> {
-        debug!("Typing text ({} chars)", text.len());
-        let safe_text = sanitize_applescript_string(text);
+    async fn click_at(&self, x: i32, y: i32) -> Result<String> {
+        let size = run_jxa(
+            r#"
+ObjC.import('CoreGraphics');
+var bounds = $.CGDisplayBounds($.CGMainDisplayID());
+bounds.size.width + ',' + bounds.size.height
+"#,
+        )
+        .await?;
+        validate_click_point(x, y, parse_display_size(&size)?)?;
+        debug!("Clicking at ({}, {})", x, y);
+        // Move first so apps that track hover see the pointer arrive
         let script = format!(
             r#"
+ObjC.import('CoreGraphics');
+var point = {{x: {x}, y: {y}}};
+[$.kCGEventMouseMoved, $.kCGEventLeftMouseDown, $.kCGEventLeftMouseUp].forEach(function (type) {{
+    $.CGEventPost($.kCGHIDEventTap, $.CGEventCreateMouseEvent(null, type, point, $.kCGMouseButtonLeft));
+    delay(0.05);
+}});
+'Clicked at ({x}, {y})'
+"#
+        );
+        run_jxa(&script).await
+    }
+
+    async fn type_text(&self, text: &str) -> Result<String> {
+        debug!("Typing text ({} chars)", text.len());
+        run_applescript_with_timeout(&type_text_script(text), self.timeout).await
+    }
+}
+
+/// Script that types `text` into the focused app. `sanitize_applescript_string` flattens
+/// newlines, so each line is typed on its own with a `keystroke return` between them.
+fn type_text_script(text: &str) -> String {
+    let normalized = text.replace("\r\n", "\n").replace('\r', "\n");
+    let mut keystrokes = Vec::new();
+    for (i, line) in normalized.split('\n').enumerate() {
+        if i > 0 {
+            keystrokes.push("keystroke return".to_string());
+        }
+        if !line.is_empty() {
+            keystrokes.push(format!(
+                "keystroke \"{}\"",
+                sanitize_applescript_string(line)
+            ));
+        }
+    }
+    format!(
+        r#"
 tell application "System Events"
     try
-        keystroke "{}"
+        {}
         return "Text typed successfully"
     on error errMsg
         return "Error: " & errMsg
     end try
 end tell
 "#,
-            safe_text.replace('\n', "\" & return & \"")
-        );
-        run_applescript(&script).await
-    }
+        keystrokes.join("\n        ")
+    )
 }
 
 pub struct MacOsRemindersProvider;
@@ -583,14 +1612,18 @@ impl NotificationProvider for MacOsNotificationProvider {
         title: &str,
         message: &str,
         sound: Option<&str>,
+        urgency: Urgency,
     ) -> Result<String> {
         let safe_title = sanitize_applescript_string(title);
         let safe_message = sanitize_applescript_string(message);
-        let sound_clause = if let Some(s) = sound {
-            let safe_sound = sanitize_applescript_string(s);
-            format!(r#" sound name "{}""#, safe_sound)
-        } else {
-            r#" sound name "default""#.to_string()
+        // `display notification` has no urgency levels; low-urgency ones are silent
+        let sound_clause = match (sound, urgency) {
+            (Some(s), _) => {
+                let safe_sound = sanitize_applescript_string(s);
+                format!(r#" sound name "{}""#, safe_sound)
+            }
+            (None, Urgency::Low) => String::new(),
+            (None, _) => r#" sound name "default""#.to_string(),
         };
         debug!("Sending notification: {}", title);
         let script = format!(
@@ -604,35 +1637,110 @@ impl NotificationProvider for MacOsNotificationProvider {
 
 pub struct MacOsScreenCaptureProvider;
 
+impl MacOsScreenCaptureProvider {
+    /// Run `screencapture -x [-R x,y,w,h] <path>`
+    async fn screencapture(region: Option<&str>, path: &str) -> Result<()> {
+        let mut cmd = Command::new("screencapture");
+        cmd.arg("-x"); // silent (no shutter sound)
+        if let Some(region) = region {
+            cmd.arg("-R").arg(region);
+        }
+        let output =
+            tokio::time::timeout(std::time::Duration::from_secs(10), cmd.arg(path).output())
+                .await
+                .map_err(|_| anyhow::anyhow!("Screen capture timed out"))?
+                .context("Failed to run screencapture")?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            let error = String::from_utf8_lossy(&output.stderr);
+            Err(anyhow::anyhow!("Screen capture failed: {}", error))
+        }
+    }
+
+    /// Bounds of the first window whose title contains `title`, as "x,y,w,h"
+    async fn window_region(title: &str) -> Result<String> {
+        let safe_title = sanitize_applescript_string(title);
+        let script = format!(
+            r#"
+tell application "System Events"
+    repeat with proc in (application processes whose visible is true)
+        repeat with w in windows of proc
+            if name of w contains "{}" then
+                set {{x, y}} to position of w
+                set {{wd, ht}} to size of w
+                return (x as text) & "," & (y as text) & "," & (wd as text) & "," & (ht as text)
+            end if
+        end repeat
+    end repeat
+    return "WINDOW_NOT_FOUND"
+end tell
+"#,
+            safe_title
+        );
+        let region = run_applescript(&script).await?.trim().to_string();
+        if region == "WINDOW_NOT_FOUND" || region.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No visible window with a title containing '{}'",
+                title
+            ));
+        }
+        Ok(region)
+    }
+
+    /// Capture to a scratch file and return its bytes
+    async fn capture_png(region: Option<&str>) -> Result<Vec<u8>> {
+        let path = default_screenshot_path();
+        Self::screencapture(region, &path).await?;
+        let png = tokio::fs::read(&path)
+            .await
+            .context("Failed to read captured screenshot")?;
+        let _ = tokio::fs::remove_file(&path).await;
+        Ok(png)
+    }
+}
+
 #[async_trait]
 impl ScreenCaptureProvider for MacOsScreenCaptureProvider {
+    async fn capture_screen_png(&self) -> Result<Vec<u8>> {
+        debug!("Capturing screen");
+        Self::capture_png(None).await
+    }
+
+    async fn capture_window_png(&self, title: &str) -> Result<Vec<u8>> {
+        debug!("Capturing window '{}'", title);
+        let region = Self::window_region(title).await?;
+        Self::capture_png(Some(&region)).await
+    }
+
+    // screencapture writes .jpg and .pdf as well, so let it write the file directly
     async fn capture_screen(&self, path: Option<&str>) -> Result<String> {
-        let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
         let output_path = path
-            .map(|p| p.to_string())
-            .unwrap_or_else(|| format!("/tmp/meepo-screenshot-{}.png", timestamp));
+            .map(String::from)
+            .unwrap_or_else(default_screenshot_path);
 
         // Validate output path to prevent writing to sensitive locations
         validate_screenshot_path(&output_path)?;
 
         debug!("Capturing screen to {}", output_path);
-        let output = tokio::time::timeout(
-            std::time::Duration::from_secs(10),
-            Command::new("screencapture")
-                .arg("-x") // silent (no shutter sound)
-                .arg(&output_path)
-                .output(),
-        )
-        .await
-        .map_err(|_| anyhow::anyhow!("Screen capture timed out"))?
-        .context("Failed to run screencapture")?;
+        Self::screencapture(None, &output_path).await?;
+        Ok(format!("Screenshot saved to {}", output_path))
+    }
 
-        if output.status.success() {
-            Ok(format!("Screenshot saved to {}", output_path))
-        } else {
-            let error = String::from_utf8_lossy(&output.stderr);
-            Err(anyhow::anyhow!("Screen capture failed: {}", error))
-        }
+    async fn capture_window(&self, title: &str, path: Option<&str>) -> Result<String> {
+        let output_path = path
+            .map(String::from)
+            .unwrap_or_else(default_screenshot_path);
+        validate_screenshot_path(&output_path)?;
+
+        debug!("Capturing window '{}' to {}", title, output_path);
+        let region = Self::window_region(title).await?;
+        Self::screencapture(Some(&region), &output_path).await?;
+        Ok(format!(
+            "Screenshot of '{}' saved to {}",
+            title, output_path
+        ))
     }
 }
 
@@ -741,6 +1849,42 @@ end tell
         );
         run_applescript(&script).await
     }
+
+    async fn lookup(&self, name: &str) -> Result<Vec<Contact>> {
+        let safe_name = sanitize_applescript_string(name);
+        debug!("Looking up contacts named: {}", name);
+        let script = format!(
+            r#"
+tell application "Contacts"
+    set rs to ASCII character 30
+    set fs to ASCII character 31
+    set vs to ASCII character 29
+    set results to (every person whose name contains "{}")
+    set maxResults to 20
+    if (count of results) < maxResults then
+        set maxResults to (count of results)
+    end if
+    set output to ""
+    repeat with i from 1 to maxResults
+        set p to item i of results
+        set output to output & (name of p) & fs
+        repeat with e in (emails of p)
+            set output to output & (value of e) & vs
+        end repeat
+        set output to output & fs
+        repeat with ph in (phones of p)
+            set output to output & (value of ph) & vs
+        end repeat
+        set output to output & rs
+    end repeat
+    return output
+end tell
+"#,
+            safe_name
+        );
+        let output = run_app_applescript("Contacts", &script, DEFAULT_SCRIPT_TIMEOUT).await?;
+        Ok(parse_contact_records(&output))
+    }
 }
 
 /// Safari browser automation via AppleScript
@@ -1254,6 +2398,16 @@ mod tests {
         assert_eq!(sanitize_applescript_string(with_control), "testtext");
     }
 
+    #[test]
+    fn test_type_text_script_types_newlines_as_returns() {
+        let script = type_text_script("Hi \"Bob\",\r\n\nThanks");
+        assert!(script.contains(
+            "keystroke \"Hi \\\"Bob\\\",\"\n        keystroke return\n        \
+             keystroke return\n        keystroke \"Thanks\""
+        ));
+        assert!(!type_text_script("one line").contains("keystroke return"));
+    }
+
     #[test]
     fn test_sanitize_prevents_injection() {
         let attack = "test\"; do shell script \"rm -rf /\" --\"";
@@ -1261,4 +2415,131 @@ mod tests {
         assert!(!safe.contains('\n'));
         assert!(safe.contains("\\\""));
     }
+
+    #[test]
+    fn test_timed_out_error() {
+        assert_eq!(
+            timed_out_error(Duration::from_secs(30)).to_string(),
+            "AppleScript execution timed out after 30 seconds"
+        );
+        assert_eq!(
+            timed_out_error(Duration::from_millis(1500)).to_string(),
+            "AppleScript execution timed out after 1500 ms"
+        );
+    }
+
+    #[test]
+    fn test_batch_script_wraps_each_script() {
+        let script = batch_script(&["return 1", "tell application \"Mail\" to count of accounts"]);
+        assert!(script.contains("on meepo_part_0()\nreturn 1\nend meepo_part_0"));
+        assert!(script.contains("set partResult to meepo_part_1()"));
+        assert!(script.contains("set output to output & meepo_run_1() & sep\n"));
+        // Each runner puts the delimiters back and never coerces a result to empty text
+        assert_eq!(script.matches("to savedDelimiters").count(), 4);
+        assert!(!script.contains("set partResult to \"\""));
+        assert!(script.ends_with("return output\n"));
+    }
+
+    #[tokio::test]
+    async fn test_batch_script_without_result() {
+        let (first, first_result) = oneshot::channel();
+        let (second, second_result) = oneshot::channel();
+        let pending = |script: &str, reply| PendingScript {
+            script: script.to_string(),
+            timeout: DEFAULT_SCRIPT_TIMEOUT,
+            reply,
+        };
+        // A script that returns nothing must not abort the batch or fail its neighbour
+        run_batch(vec![
+            pending("set x to 1\nreturn", first),
+            pending("return \"hello\"", second),
+        ])
+        .await;
+        assert_eq!(first_result.await.unwrap().unwrap(), "\n");
+        assert_eq!(second_result.await.unwrap().unwrap(), "hello\n");
+    }
+
+    #[test]
+    fn test_automation_denial_only_from_errors() {
+        let stderr = Err(anyhow::anyhow!(
+            "AppleScript failed: execution error: Not authorized to send Apple events to Mail. (-1743)"
+        ));
+        assert!(is_automation_denial(&stderr));
+        assert!(is_automation_denial(&Ok(
+            "Error: Not authorized to send Apple events to Mail.".to_string()
+        )));
+
+        // Successful output that merely mentions the error code is data
+        assert!(!is_automation_denial(&Ok(
+            "From: ops@example.com\nSubject: AppleEvent error -1743 again".to_string()
+        )));
+        assert!(!is_automation_denial(&Err(anyhow::anyhow!(
+            "AppleScript failed: execution error: Mail got an error (-600)"
+        ))));
+    }
+
+    #[test]
+    fn test_split_batch_output() {
+        let output = "ok:first\u{4}error:execution error: Not authorized (-1743)\u{4}ok:\u{4}\n";
+        let results = split_batch_output(output, 3).unwrap();
+        assert_eq!(results[0].as_ref().unwrap(), "first\n");
+        assert!(
+            results[1]
+                .as_ref()
+                .unwrap_err()
+                .to_string()
+                .contains("-1743")
+        );
+        assert_eq!(results[2].as_ref().unwrap(), "\n");
+
+        assert!(split_batch_output(output, 2).is_err());
+        assert!(split_batch_output("garbage\u{4}\n", 1).is_err());
+    }
+
+    #[test]
+    fn test_applescript_date() {
+        use chrono::TimeZone;
+        let time = Local.with_ymd_and_hms(2025, 2, 28, 15, 30, 0).unwrap();
+        let script = applescript_date("startDate", &time);
+        assert!(script.starts_with("set startDate to current date\n"));
+        assert!(script.contains("set day of startDate to 1\n"));
+        assert!(script.contains("set month of startDate to 2\n"));
+        assert!(script.contains("set day of startDate to 28\n"));
+        assert!(script.ends_with("set time of startDate to 55800"));
+    }
+
+    #[test]
+    fn test_parse_aged_email_records() {
+        let output = "3600\u{1f}<old@x>\u{1f}Ann\u{1f}Lunch\u{1f}Monday\u{1f}Noon?\u{1e}\
+                      60\u{1f}<new@x>\u{1f}Bob\u{1f}Re: Lunch\u{1f}Monday\u{1f}Sure\u{1e}\
+                      120\u{1f}<new@x>\u{1f}Bob\u{1f}Re: Lunch\u{1f}Monday\u{1f}Sure\u{1e}\n";
+        let emails = parse_aged_email_records(output).unwrap();
+        let ids: Vec<&str> = emails.iter().map(|e| e.message_id.as_str()).collect();
+        assert_eq!(ids, vec!["<new@x>", "<new@x>", "<old@x>"]);
+        assert_eq!(emails[2].from, "Ann");
+        assert_eq!(take_unique_emails(emails, 10).len(), 2);
+        assert!(parse_aged_email_records("Error: no such mailbox").is_err());
+
+        assert!(mail_mailbox_sources("Sent").contains("set end of sources to sent mailbox"));
+        assert!(mail_mailbox_sources("Archive").contains(r#"(mailbox "Archive" of acct)"#));
+    }
+
+    #[test]
+    fn test_parse_counted_email_records() {
+        let output = "134\u{1e}<a@x>\u{1f}Ann\u{1f}Lunch\u{1f}Monday\u{1f}Noon?\u{1e}\n";
+        let (total, emails) = parse_counted_email_records(output).unwrap();
+        assert_eq!(total, 134);
+        assert_eq!(emails.len(), 1);
+        assert_eq!(emails[0].message_id, "<a@x>");
+
+        // Past the last message only the count comes back
+        let (total, emails) = parse_counted_email_records("7\u{1e}").unwrap();
+        assert_eq!((total, emails.len()), (7, 0));
+        assert!(parse_counted_email_records("Error: no such mailbox").is_err());
+
+        let range = mail_page_range("inbox", "", 20, 10);
+        assert!(range.contains("set total to count of (messages of inbox)"));
+        assert!(range.contains("set firstIdx to 21"));
+        assert!(range.contains("set lastIdx to 30"));
+    }
 }
@@ -0,0 +1,468 @@
+//! MIME helpers shared by the email providers
+//!
+//! Just enough RFC 2045/2047 handling to read real-world mail: header
+//! unfolding and encoded words, transfer and charset decoding, pulling the
+//! HTML part out of a raw message, and rendering attachment listings.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+
+use crate::text::{html_to_text, truncate_chars};
+
+/// Characters of body text shown per email when richer output is requested
+pub const RICH_BODY_CHARS: usize = 2000;
+
+/// An attachment as listed (not downloaded) when reading email
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttachmentInfo {
+    pub name: String,
+    /// MIME type, empty when the mail app doesn't report one
+    pub mime_type: String,
+    /// Size in bytes (encoded size for IMAP, where that's all the server reports)
+    pub size: Option<u64>,
+}
+
+/// Unfold and split raw header lines into (lowercase name, decoded value) pairs
+pub fn parse_headers(raw: &str) -> Vec<(String, String)> {
+    let mut headers: Vec<(String, String)> = Vec::new();
+    for line in raw.lines() {
+        let line = line.trim_end_matches('\r');
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_lowercase(), value.trim().to_string()));
+        }
+    }
+    headers
+        .into_iter()
+        .map(|(name, value)| (name, decode_encoded_words(&value)))
+        .collect()
+}
+
+/// Value of header `name` (lowercase), or "" if absent
+pub fn header<'a>(headers: &'a [(String, String)], name: &str) -> &'a str {
+    headers
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, v)| v.as_str())
+        .unwrap_or("")
+}
+
+/// Decode RFC 2047 encoded words (`=?UTF-8?B?...?=`) in a header value
+pub fn decode_encoded_words(value: &str) -> String {
+    let mut out = String::new();
+    let mut rest = value;
+    let mut last_was_word = false;
+
+    while let Some(start) = rest.find("=?") {
+        let decoded = rest[start + 2..]
+            .split_once("?=")
+            .and_then(|(word, after)| {
+                let mut parts = word.splitn(3, '?');
+                let (charset, encoding, text) = (parts.next()?, parts.next()?, parts.next()?);
+                let bytes = match encoding.to_ascii_uppercase().as_str() {
+                    "B" => BASE64.decode(text).ok()?,
+                    "Q" => decode_q(text),
+                    _ => return None,
+                };
+                Some((decode_charset(&bytes, charset), after))
+            });
+
+        match decoded {
+            Some((text, after)) => {
+                // Whitespace between adjacent encoded words is not part of the text
+                let between = &rest[..start];
+                if !(last_was_word && between.trim().is_empty()) {
+                    out.push_str(between);
+                }
+                out.push_str(&text);
+                rest = after;
+                last_was_word = true;
+            }
+            None => {
+                out.push_str(&rest[..start + 2]);
+                rest = &rest[start + 2..];
+                last_was_word = false;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Decode the "Q" encoding of RFC 2047 (quoted-printable with `_` for space)
+fn decode_q(text: &str) -> Vec<u8> {
+    decode_quoted_printable(&text.replace('_', " "))
+}
+
+/// Decode a quoted-printable body, including soft line breaks
+pub fn decode_quoted_printable(text: &str) -> Vec<u8> {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'=' {
+            // "=\r\n" or "=\n" joins lines
+            if bytes.get(i + 1) == Some(&b'\n') {
+                i += 2;
+                continue;
+            }
+            if bytes.get(i + 1..i + 3) == Some(b"\r\n") {
+                i += 3;
+                continue;
+            }
+            if let Some(b) = bytes
+                .get(i + 1..i + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                out.push(b);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    out
+}
+
+/// Undo a Content-Transfer-Encoding, tolerating bodies cut off mid-way
+pub fn decode_transfer_encoding(body: &str, encoding: &str) -> Vec<u8> {
+    match encoding.trim().to_lowercase().as_str() {
+        "base64" => {
+            let mut clean: String = body
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '='))
+                .collect();
+            // A partial fetch may end mid-quantum; decode what's complete
+            clean.truncate(clean.len() / 4 * 4);
+            BASE64.decode(clean.as_bytes()).unwrap_or_default()
+        }
+        "quoted-printable" => decode_quoted_printable(body),
+        _ => body.as_bytes().to_vec(),
+    }
+}
+
+/// Text from bytes in `charset`; unknown charsets are read as UTF-8
+pub fn decode_charset(bytes: &[u8], charset: &str) -> String {
+    match charset.trim().to_lowercase().as_str() {
+        "iso-8859-1" | "latin1" | "latin-1" | "windows-1252" | "cp1252" => {
+            bytes.iter().map(|&b| b as char).collect()
+        }
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+/// Split a Content-Type value into the lowercase MIME type and its parameters
+pub fn parse_content_type(value: &str) -> (String, Vec<(String, String)>) {
+    let mut parts = value.split(';');
+    let mime_type = parts.next().unwrap_or("").trim().to_lowercase();
+    let params = parts
+        .filter_map(|param| {
+            let (key, value) = param.split_once('=')?;
+            Some((
+                key.trim().to_lowercase(),
+                value.trim().trim_matches('"').to_string(),
+            ))
+        })
+        .collect();
+    (mime_type, params)
+}
+
+fn param<'a>(params: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    params
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+/// Split a raw message or part into header block and body
+fn split_head_body(raw: &str) -> (&str, &str) {
+    let crlf = raw.find("\r\n\r\n").map(|i| (i, 4));
+    let lf = raw.find("\n\n").map(|i| (i, 2));
+    match [crlf, lf].into_iter().flatten().min_by_key(|(i, _)| *i) {
+        Some((i, len)) => (&raw[..i], &raw[i + len..]),
+        None => (raw, ""),
+    }
+}
+
+/// The parts of a multipart body delimited by `boundary`
+fn split_multipart<'a>(body: &'a str, boundary: &str) -> Vec<&'a str> {
+    let delimiter = format!("--{}", boundary);
+    let mut parts = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut offset = 0;
+    for line in body.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        if trimmed.starts_with(&delimiter) {
+            if let Some(s) = start {
+                parts.push(&body[s..offset]);
+            }
+            if trimmed[delimiter.len()..].starts_with("--") {
+                return parts;
+            }
+            start = Some(offset + line.len());
+        }
+        offset += line.len();
+    }
+    if let Some(s) = start {
+        parts.push(&body[s..]);
+    }
+    parts
+}
+
+/// The decoded `text/html` part of a raw RFC 5322 message, if it has one
+pub fn html_part(raw: &str) -> Option<String> {
+    let (head, body) = split_head_body(raw);
+    let headers = parse_headers(head);
+    let (mime_type, params) = parse_content_type(header(&headers, "content-type"));
+
+    if mime_type.starts_with("multipart/") {
+        let boundary = param(&params, "boundary")?;
+        return split_multipart(body, boundary)
+            .into_iter()
+            .find_map(html_part);
+    }
+    if mime_type != "text/html" {
+        return None;
+    }
+    let encoding = header(&headers, "content-transfer-encoding");
+    match encoding.trim().to_lowercase().as_str() {
+        "base64" | "quoted-printable" => Some(decode_charset(
+            &decode_transfer_encoding(body, encoding),
+            param(&params, "charset").unwrap_or("utf-8"),
+        )),
+        // 7bit/8bit text has already been decoded by whoever produced `raw`
+        _ => Some(body.to_string()),
+    }
+}
+
+/// Human-readable byte count ("512 B", "12.3 KB", "4.0 MB")
+pub fn format_size(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    let b = bytes as f64;
+    if b < KB {
+        format!("{} B", bytes)
+    } else if b < KB * KB {
+        format!("{:.1} KB", b / KB)
+    } else {
+        format!("{:.1} MB", b / (KB * KB))
+    }
+}
+
+/// `Attachments:` section of a read_emails entry
+pub fn format_attachments(attachments: &[AttachmentInfo]) -> String {
+    if attachments.is_empty() {
+        return "Attachments: none\n".to_string();
+    }
+    let mut out = String::from("Attachments:\n");
+    for attachment in attachments {
+        let details: Vec<String> = [
+            Some(attachment.mime_type.clone()).filter(|m| !m.is_empty()),
+            attachment.size.map(format_size),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if details.is_empty() {
+            out.push_str(&format!("  - {}\n", attachment.name));
+        } else {
+            out.push_str(&format!(
+                "  - {} ({})\n",
+                attachment.name,
+                details.join(", ")
+            ));
+        }
+    }
+    out
+}
+
+/// One read_emails entry with a longer body (HTML converted to text when `is_html`)
+/// and, if given, the attachment list
+pub fn format_rich_email(
+    from: &str,
+    subject: &str,
+    date: &str,
+    body: &str,
+    is_html: bool,
+    attachments: Option<&[AttachmentInfo]>,
+) -> String {
+    let text = if is_html {
+        html_to_text(body)
+    } else {
+        body.to_string()
+    };
+    let mut out = format!("From: {}\nSubject: {}\nDate: {}\n", from, subject, date);
+    out.push_str(&format!(
+        "Preview: {}\n",
+        truncate_chars(text.trim(), RICH_BODY_CHARS)
+    ));
+    if let Some(attachments) = attachments {
+        out.push_str(&format_attachments(attachments));
+    }
+    out.push_str("---\n");
+    out
+}
+
+/// Separators for the records Mail.app/Outlook scripts print for rich reads
+/// (ASCII record/unit/group/file separators never appear in mail text)
+pub const RECORD_SEP: char = '\u{1e}';
+pub const FIELD_SEP: char = '\u{1f}';
+pub const ATTACHMENT_SEP: char = '\u{1d}';
+pub const ATTACHMENT_FIELD_SEP: char = '\u{1c}';
+
+/// Format script output for rich reads. Each record holds from, subject, date, plain
+/// text, HTML (a raw message source when `html_is_source`) and attachments as
+/// `name`, `mime type` and `size` fields.
+pub fn format_script_records(
+    output: &str,
+    html_is_source: bool,
+    include_attachments: bool,
+) -> String {
+    let mut out = String::new();
+    for record in output.split(RECORD_SEP) {
+        let fields: Vec<&str> = record.split(FIELD_SEP).collect();
+        if fields.len() < 4 {
+            continue;
+        }
+        let field = |i: usize| fields.get(i).copied().unwrap_or("");
+        let html = if html_is_source {
+            html_part(field(4))
+        } else {
+            Some(field(4).to_string())
+        }
+        .filter(|html| !html.trim().is_empty());
+
+        let attachments: Vec<AttachmentInfo> = field(5)
+            .split(ATTACHMENT_SEP)
+            .filter(|a| !a.trim().is_empty())
+            .map(|a| {
+                let mut parts = a.split(ATTACHMENT_FIELD_SEP);
+                AttachmentInfo {
+                    name: parts.next().unwrap_or("").trim().to_string(),
+                    mime_type: parts.next().unwrap_or("").trim().to_string(),
+                    size: parts.next().and_then(|s| s.trim().parse().ok()),
+                }
+            })
+            .collect();
+
+        let (body, is_html) = match &html {
+            Some(html) => (html.as_str(), true),
+            None => (field(3), false),
+        };
+        out.push_str(&format_rich_email(
+            field(0).trim(),
+            field(1),
+            field(2),
+            body,
+            is_html,
+            include_attachments.then_some(attachments.as_slice()),
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_headers_decodes_encoded_words() {
+        let raw = "From: =?UTF-8?B?SsO2cmc=?= <jorg@example.com>\r\n\
+                   Subject: =?utf-8?Q?Caf=C3=A9_menu?=\r\n =?UTF-8?B?IGZvciB0b2RheQ==?=\r\n\
+                   Date: Mon, 1 Jan 2024 10:00:00 +0000\r\n\r\n";
+        let headers = parse_headers(raw);
+        assert_eq!(header(&headers, "from"), "Jörg <jorg@example.com>");
+        assert_eq!(header(&headers, "subject"), "Café menu for today");
+        assert_eq!(header(&headers, "date"), "Mon, 1 Jan 2024 10:00:00 +0000");
+        assert_eq!(header(&headers, "cc"), "");
+        assert_eq!(decode_encoded_words("=?iso-8859-1?Q?caf=E9?="), "café");
+    }
+
+    #[test]
+    fn test_decode_transfer_encoding() {
+        assert_eq!(
+            decode_transfer_encoding("Caf=C3=A9 au =\r\nlait=3D", "quoted-printable"),
+            "Café au lait=".as_bytes()
+        );
+        assert_eq!(
+            decode_transfer_encoding("aGVsbG8g\r\nd29ybGQ=\r\n", "base64"),
+            b"hello world"
+        );
+        // Cut off mid-quantum by a partial fetch
+        assert_eq!(
+            decode_transfer_encoding("aGVsbG8gd29y", "base64"),
+            b"hello wor"
+        );
+        assert_eq!(decode_transfer_encoding("aGVsbG8gd2", "base64"), b"hello ");
+        assert_eq!(decode_transfer_encoding("plain", "7bit"), b"plain");
+    }
+
+    #[test]
+    fn test_html_part() {
+        let raw = "From: a@b.c\r\n\
+                   Content-Type: multipart/mixed; boundary=\"outer\"\r\n\r\n\
+                   --outer\r\n\
+                   Content-Type: multipart/alternative; boundary=inner\r\n\r\n\
+                   --inner\r\n\
+                   Content-Type: text/plain\r\n\r\n\
+                   plain text\r\n\
+                   --inner\r\n\
+                   Content-Type: text/html; charset=utf-8\r\n\
+                   Content-Transfer-Encoding: quoted-printable\r\n\r\n\
+                   <p>Caf=C3=A9 <b>menu</b></p>\r\n\
+                   --inner--\r\n\
+                   --outer\r\n\
+                   Content-Type: application/pdf; name=menu.pdf\r\n\r\n\
+                   JVBERi0=\r\n\
+                   --outer--\r\n";
+        assert_eq!(html_part(raw).unwrap().trim(), "<p>Café <b>menu</b></p>");
+        assert_eq!(html_part("Content-Type: text/plain\n\nhello"), None);
+    }
+
+    #[test]
+    fn test_format_attachments() {
+        assert_eq!(format_attachments(&[]), "Attachments: none\n");
+        let listing = format_attachments(&[
+            AttachmentInfo {
+                name: "report.pdf".to_string(),
+                mime_type: "application/pdf".to_string(),
+                size: Some(126_976),
+            },
+            AttachmentInfo {
+                name: "notes.txt".to_string(),
+                mime_type: String::new(),
+                size: None,
+            },
+        ]);
+        assert_eq!(
+            listing,
+            "Attachments:\n  - report.pdf (application/pdf, 124.0 KB)\n  - notes.txt\n"
+        );
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
+    }
+
+    #[test]
+    fn test_format_script_records() {
+        let output = format!(
+            "\nAnn <ann@example.com>{f}Menu{f}Monday{f}plain body{f}<p>Hi <b>there</b></p>{f}menu.pdf{af}application/pdf{af}2048{a}notes.txt{af}{af}{r}\
+             Bob{f}Hello{f}Tuesday{f}just text{f}{f}{r}\n",
+            f = FIELD_SEP,
+            r = RECORD_SEP,
+            a = ATTACHMENT_SEP,
+            af = ATTACHMENT_FIELD_SEP,
+        );
+        assert_eq!(
+            format_script_records(&output, false, true),
+            "From: Ann <ann@example.com>\nSubject: Menu\nDate: Monday\nPreview: Hi there\n\
+             Attachments:\n  - menu.pdf (application/pdf, 2.0 KB)\n  - notes.txt\n---\n\
+             From: Bob\nSubject: Hello\nDate: Tuesday\nPreview: just text\nAttachments: none\n---\n"
+        );
+        assert!(!format_script_records(&output, false, false).contains("Attachments"));
+    }
+}
@@ -3,19 +3,496 @@
 //! Provides trait definitions and platform-specific implementations.
 //! On macOS: AppleScript-based implementations.
 //! On Windows: PowerShell/COM-based implementations.
+//! On Linux: CalDAV calendar, configured at startup.
+//! Everywhere: IMAP/SMTP email, preferred over the mail app when configured.
 
+pub mod event_time;
+pub mod imap;
+#[cfg(target_os = "linux")]
+pub mod linux;
 #[cfg(target_os = "macos")]
 pub mod macos;
+pub mod mime;
 #[cfg(target_os = "windows")]
 pub mod windows;
 
 use anyhow::Result;
 use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// How long a denied automation permission fails calls fast before it is re-checked
+const PERMISSION_DENIED_RECHECK: Duration = Duration::from_secs(60);
+
+/// Last observed automation permission per app, shared by every provider instance
+static AUTOMATION_PERMISSIONS: LazyLock<Mutex<HashMap<String, (bool, Instant)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Whether automation of `app` was last seen as granted (`None` if never checked)
+pub fn automation_permission(app: &str) -> Option<bool> {
+    AUTOMATION_PERMISSIONS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(app)
+        .map(|(granted, _)| *granted)
+}
+
+/// Record the outcome of a call that needed automation permission for `app`
+pub fn record_automation_permission(app: &str, granted: bool) {
+    let mut permissions = AUTOMATION_PERMISSIONS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let previous = permissions.insert(app.to_string(), (granted, Instant::now()));
+    if !granted && previous.is_none_or(|(was_granted, _)| was_granted) {
+        warn!("Automation permission for {} is not granted", app);
+    }
+}
+
+/// Fail fast if automation of `app` was denied recently
+pub fn check_automation_permission(app: &str) -> Result<()> {
+    let permissions = AUTOMATION_PERMISSIONS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    match permissions.get(app) {
+        Some((false, checked_at)) if checked_at.elapsed() < PERMISSION_DENIED_RECHECK => {
+            Err(automation_denied_error(app))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Error returned when the OS refuses to let meepo automate `app`
+pub fn automation_denied_error(app: &str) -> anyhow::Error {
+    anyhow::anyhow!(
+        "Meepo is not allowed to control {}. Grant access in System Settings > \
+         Privacy & Security > Automation, then try again.",
+        app
+    )
+}
+
+/// Default time a single platform script (AppleScript) may run
+pub const DEFAULT_SCRIPT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Construction options for the email, calendar, and UI automation providers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProviderOptions {
+    /// How long each AppleScript call may run before it fails with a timeout (macOS)
+    pub script_timeout: Duration,
+}
+
+impl Default for ProviderOptions {
+    fn default() -> Self {
+        Self {
+            script_timeout: DEFAULT_SCRIPT_TIMEOUT,
+        }
+    }
+}
+
+impl ProviderOptions {
+    pub fn with_script_timeout(mut self, timeout: Duration) -> Self {
+        self.script_timeout = timeout;
+        self
+    }
+}
+
+/// Extra detail to include when reading emails
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmailReadOptions {
+    /// Use the HTML part (converted to text) as the body when a message has one
+    pub prefer_html: bool,
+    /// List each message's attachments (name, MIME type, size) without downloading them
+    pub include_attachments: bool,
+}
+
+impl EmailReadOptions {
+    /// Whether these are the defaults, i.e. a plain preview read
+    pub fn is_plain(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Body characters kept in an [`EmailSummary`] preview
+pub const EMAIL_PREVIEW_CHARS: usize = 500;
+
+/// Body characters returned by [`EmailProvider::read_email_body`]
+pub const EMAIL_BODY_CHARS: usize = 20_000;
+
+/// Deepest `offset` a mailbox read pages to; reading past it would mean listing
+/// tens of thousands of messages to show ten
+pub const MAX_EMAIL_OFFSET: u64 = 10_000;
+
+/// Mailboxes `read_emails` searches when asked to look everywhere
+pub const ALL_MAILBOXES: &[&str] = &["inbox", "sent", "archive"];
+
+/// Largest combined size of the files attached to one outgoing email
+pub const MAX_ATTACHMENT_BYTES: u64 = 20 * 1024 * 1024;
+
+/// Check that every attachment is an existing file and that together they
+/// fit under [`MAX_ATTACHMENT_BYTES`]. Providers call this before composing
+/// anything, so a bad path fails the send instead of half-sending it.
+pub fn validate_attachments(attachments: &[PathBuf]) -> Result<()> {
+    let mut total = 0u64;
+    for path in attachments {
+        let metadata = std::fs::metadata(path)
+            .map_err(|e| anyhow::anyhow!("Attachment {} not found: {}", path.display(), e))?;
+        if !metadata.is_file() {
+            anyhow::bail!("Attachment {} is not a file", path.display());
+        }
+        total += metadata.len();
+    }
+    if total > MAX_ATTACHMENT_BYTES {
+        anyhow::bail!(
+            "Attachments total {:.1} MB, over the {} MB limit",
+            total as f64 / (1024.0 * 1024.0),
+            MAX_ATTACHMENT_BYTES / (1024 * 1024)
+        );
+    }
+    Ok(())
+}
+
+/// One message from a mailbox read
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct EmailSummary {
+    /// Message-ID header (or the mail app's own id when it hides the header); may be empty
+    pub message_id: String,
+    pub from: String,
+    pub subject: String,
+    pub date: String,
+    /// Start of the body text, at most [`EMAIL_PREVIEW_CHARS`] characters
+    pub preview: String,
+}
+
+/// Format summaries as `read_emails` presents them to the agent
+pub fn format_email_summaries(mailbox: &str, emails: &[EmailSummary]) -> String {
+    if emails.is_empty() {
+        return format!("No emails found in {}", mailbox);
+    }
+    emails
+        .iter()
+        .map(|email| {
+            let id_line = if email.message_id.is_empty() {
+                String::new()
+            } else {
+                format!("Message-ID: {}\n", email.message_id)
+            };
+            format!(
+                "From: {}\nSubject: {}\nDate: {}\n{}Preview: {}\n---\n",
+                email.from, email.subject, email.date, id_line, email.preview
+            )
+        })
+        .collect()
+}
+
+/// Where a page of results sits in the full list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageInfo {
+    /// Items skipped before this page
+    pub offset: u64,
+    /// Items on this page
+    pub count: u64,
+    /// Items in the full list, when the provider can count them
+    pub total: Option<u64>,
+    /// Whether items follow this page
+    pub has_more: bool,
+}
+
+impl PageInfo {
+    /// Page info for `count` items starting at `offset` out of a known `total`
+    pub fn of_total(offset: u64, count: u64, total: u64) -> Self {
+        Self {
+            offset,
+            count,
+            total: Some(total),
+            has_more: offset.saturating_add(count) < total,
+        }
+    }
+
+    /// Footer telling the agent which `noun`s it got and how to get the next page
+    pub fn footer(&self, noun: &str) -> String {
+        let range = if self.count == 0 {
+            format!("No {} at offset {}", noun, self.offset)
+        } else {
+            format!(
+                "Showing {} {}-{}",
+                noun,
+                self.offset.saturating_add(1),
+                self.offset.saturating_add(self.count)
+            )
+        };
+        let total = match self.total {
+            Some(total) => format!(" of {}", total),
+            None => String::new(),
+        };
+        if self.has_more {
+            format!(
+                "{}{}. More available: use offset={} for the next page.",
+                range,
+                total,
+                self.offset.saturating_add(self.count)
+            )
+        } else {
+            format!("{}{}. No more {}.", range, total, noun)
+        }
+    }
+}
+
+/// One page of a mailbox read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailPage {
+    pub emails: Vec<EmailSummary>,
+    pub page: PageInfo,
+}
+
+impl EmailPage {
+    /// Cut a page out of `emails`, the newest messages read with room for at least one
+    /// past the page (`offset + limit + 1`), so whether more exist can be told without a count
+    pub fn from_window(emails: Vec<EmailSummary>, offset: u64, limit: u64) -> Self {
+        let has_more = emails.len() as u64 > offset.saturating_add(limit);
+        let emails: Vec<EmailSummary> = emails
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect();
+        let page = PageInfo {
+            offset,
+            count: emails.len() as u64,
+            total: None,
+            has_more,
+        };
+        Self { emails, page }
+    }
+}
+
+/// Format a page as `read_emails` presents it: the summaries, then where the page sits
+pub fn format_email_page(mailbox: &str, page: &EmailPage) -> String {
+    if page.emails.is_empty() && page.page.offset == 0 {
+        return format_email_summaries(mailbox, &page.emails);
+    }
+    format!(
+        "{}{}",
+        format_email_summaries(mailbox, &page.emails),
+        page.page.footer("emails")
+    )
+}
+
+/// Parse a date as mail and calendar apps print it: RFC 2822 (IMAP headers),
+/// RFC 3339, or a few common local formats, which are taken to be in the local timezone
+fn parse_display_date(date: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+    // Newer macOS versions put a narrow no-break space before AM/PM
+    let date = date.trim().replace('\u{202f}', " ");
+    let date = date.as_str();
+    if let Ok(parsed) = DateTime::parse_from_rfc2822(date) {
+        return Some(parsed.with_timezone(&Utc));
+    }
+    if let Ok(parsed) = DateTime::parse_from_rfc3339(date) {
+        return Some(parsed.with_timezone(&Utc));
+    }
+    [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%m/%d/%Y %I:%M:%S %p",
+        "%m/%d/%Y %H:%M:%S",
+        "%A, %B %d, %Y at %I:%M:%S %p",
+        "%A, %d %B %Y at %H:%M:%S",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(date, format).ok())
+    .and_then(|naive| Local.from_local_datetime(&naive).earliest())
+    .map(|local| local.with_timezone(&Utc))
+}
+
+/// Keep the first copy of each message (by Message-ID) and at most `limit` messages
+fn take_unique_emails(
+    emails: impl IntoIterator<Item = EmailSummary>,
+    limit: u64,
+) -> Vec<EmailSummary> {
+    let mut seen = std::collections::HashSet::new();
+    emails
+        .into_iter()
+        .filter(|email| email.message_id.is_empty() || seen.insert(email.message_id.clone()))
+        .take(limit as usize)
+        .collect()
+}
+
+/// Merge messages read from several mailboxes: newest first (messages whose date
+/// can't be parsed go last, in their original order), duplicates dropped, at most
+/// `limit` in total
+pub fn merge_email_summaries(emails: Vec<EmailSummary>, limit: u64) -> Vec<EmailSummary> {
+    let mut dated: Vec<_> = emails
+        .into_iter()
+        .map(|email| (parse_display_date(&email.date), email))
+        .collect();
+    dated.sort_by(|(a, _), (b, _)| match (a, b) {
+        (Some(a), Some(b)) => b.cmp(a),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+    take_unique_emails(dated.into_iter().map(|(_, email)| email), limit)
+}
+
+/// Parse email records printed by a mail script: records end with `mime::RECORD_SEP`
+/// and hold message id, sender, subject, date and body separated by `mime::FIELD_SEP`.
+/// Scripts report their own failures as output starting with "Error:".
+pub fn parse_email_summary_records(output: &str) -> Result<Vec<EmailSummary>> {
+    let trimmed = output.trim_start();
+    if trimmed.starts_with("Error:") {
+        return Err(anyhow::anyhow!("{}", trimmed.trim_end()));
+    }
+    Ok(output
+        .split(mime::RECORD_SEP)
+        .filter_map(|record| {
+            let fields: Vec<&str> = record.split(mime::FIELD_SEP).collect();
+            if fields.len() < 5 {
+                return None;
+            }
+            Some(EmailSummary {
+                message_id: fields[0].trim().to_string(),
+                from: fields[1].trim().to_string(),
+                subject: fields[2].trim().to_string(),
+                date: fields[3].trim().to_string(),
+                preview: crate::text::truncate_chars(fields[4].trim(), EMAIL_PREVIEW_CHARS)
+                    .to_string(),
+            })
+        })
+        .collect())
+}
+
+/// The message an `in_reply_to` value refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplyTarget<'a> {
+    /// A Message-ID, without angle brackets
+    MessageId(&'a str),
+    /// Subject text, for when no id is available
+    Subject(&'a str),
+}
+
+impl<'a> ReplyTarget<'a> {
+    /// Treat `in_reply_to` as a Message-ID (`<id@host>` or a bare `id@host`) when it looks
+    /// like one, otherwise as a subject. Ids with quotes or shell metacharacters are never
+    /// taken as ids, so they can go into scripts unescaped.
+    pub fn parse(in_reply_to: &'a str) -> Self {
+        let value = in_reply_to.trim();
+        let id = value
+            .strip_prefix('<')
+            .and_then(|v| v.strip_suffix('>'))
+            .unwrap_or(value);
+        let looks_like_id = id
+            .split_once('@')
+            .is_some_and(|(local, domain)| !local.is_empty() && !domain.is_empty())
+            && id.chars().all(|c| {
+                c.is_ascii_graphic() && !matches!(c, '"' | '\'' | '`' | '$' | '\\' | '<' | '>')
+            });
+        if looks_like_id {
+            Self::MessageId(id)
+        } else {
+            Self::Subject(value)
+        }
+    }
+}
 
 /// Email provider for reading and sending emails
 #[async_trait]
 pub trait EmailProvider: Send + Sync {
-    async fn read_emails(&self, limit: u64, mailbox: &str, search: Option<&str>) -> Result<String>;
+    /// The newest `limit` messages in `mailbox` (optionally matching `search`), newest first
+    async fn read_emails_structured(
+        &self,
+        limit: u64,
+        mailbox: &str,
+        search: Option<&str>,
+    ) -> Result<Vec<EmailSummary>>;
+
+    /// The newest `limit` messages across `mailboxes` (optionally matching `search`),
+    /// merged newest first. Mailboxes that can't be read, e.g. because the account
+    /// has no such mailbox, are skipped; it's only an error if none can be.
+    async fn read_emails_across(
+        &self,
+        limit: u64,
+        mailboxes: &[&str],
+        search: Option<&str>,
+    ) -> Result<Vec<EmailSummary>> {
+        let mut emails = Vec::new();
+        let mut any_read = false;
+        let mut last_error = None;
+        for mailbox in mailboxes {
+            match self.read_emails_structured(limit, mailbox, search).await {
+                Ok(found) => {
+                    any_read = true;
+                    emails.extend(found);
+                }
+                Err(e) => {
+                    warn!("Skipping mailbox {}: {}", mailbox, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        match last_error {
+            Some(e) if !any_read => Err(e),
+            _ => Ok(merge_email_summaries(emails, limit)),
+        }
+    }
+
+    /// Up to `limit` messages in `mailbox`, skipping the `offset` newest, newest first.
+    /// By default this reads one message past the page to tell whether more exist;
+    /// providers that can count matching messages cheaply also report the total.
+    async fn read_emails_page(
+        &self,
+        limit: u64,
+        offset: u64,
+        mailbox: &str,
+        search: Option<&str>,
+    ) -> Result<EmailPage> {
+        let window = offset.saturating_add(limit).saturating_add(1);
+        let emails = self.read_emails_structured(window, mailbox, search).await?;
+        Ok(EmailPage::from_window(emails, offset, limit))
+    }
+
+    /// [`read_emails_structured`](Self::read_emails_structured) formatted for the agent
+    async fn read_emails(&self, limit: u64, mailbox: &str, search: Option<&str>) -> Result<String> {
+        let emails = self.read_emails_structured(limit, mailbox, search).await?;
+        Ok(format_email_summaries(mailbox, &emails))
+    }
+
+    /// Like [`read_emails`](Self::read_emails) skipping the `offset` newest messages,
+    /// with HTML bodies and/or attachment listings per `options`. Providers that can't
+    /// do either return the plain read.
+    async fn read_emails_with_options(
+        &self,
+        limit: u64,
+        offset: u64,
+        mailbox: &str,
+        search: Option<&str>,
+        options: EmailReadOptions,
+    ) -> Result<String> {
+        let _ = options;
+        let page = self
+            .read_emails_page(limit, offset, mailbox, search)
+            .await?;
+        Ok(format_email_page(mailbox, &page))
+    }
+
+    /// Plain-text body of the message in `mailbox` whose id is `message_id` (as in
+    /// [`EmailSummary::message_id`]), up to [`EMAIL_BODY_CHARS`] characters, for
+    /// callers that need more than the preview
+    async fn read_email_body(&self, message_id: &str, mailbox: &str) -> Result<String> {
+        let _ = (message_id, mailbox);
+        Err(anyhow::anyhow!(
+            "Reading full message bodies is not supported by this email provider"
+        ))
+    }
+
+    /// Launch the mail app and check automation permission with a cheap no-op,
+    /// so the first real call doesn't pay the startup cost
+    async fn prewarm(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Send a message with `attachments` (files on disk) attached; see
+    /// [`validate_attachments`] for what's accepted
     async fn send_email(
         &self,
         to: &str,
@@ -23,25 +500,346 @@ pub trait EmailProvider: Send + Sync {
         body: &str,
         cc: Option<&str>,
         in_reply_to: Option<&str>,
+        attachments: &[PathBuf],
     ) -> Result<String>;
+
+    /// Compose the same message as [`send_email`](Self::send_email) but save it
+    /// to Drafts for the user to review and send themselves
+    async fn create_draft(
+        &self,
+        to: &str,
+        subject: &str,
+        body: &str,
+        cc: Option<&str>,
+        in_reply_to: Option<&str>,
+        attachments: &[PathBuf],
+    ) -> Result<String> {
+        let _ = (to, subject, body, cc, in_reply_to, attachments);
+        Err(anyhow::anyhow!(
+            "Saving drafts is not supported by this email provider"
+        ))
+    }
+}
+
+/// Error returned when no calendar event has the given identifier
+pub fn event_not_found_error(event_id: &str) -> anyhow::Error {
+    anyhow::anyhow!(
+        "No calendar event with ID '{}' was found. It may have been deleted; \
+         use read_calendar to get current event IDs.",
+        event_id
+    )
 }
 
-/// Calendar provider for reading and creating events
+/// Error returned when no calendar is named `name`
+pub fn calendar_not_found_error(name: &str, available: &[String]) -> anyhow::Error {
+    if available.is_empty() {
+        anyhow::anyhow!("No calendar named '{}' was found", name)
+    } else {
+        anyhow::anyhow!(
+            "No calendar named '{}' was found. Available calendars: {}",
+            name,
+            available.join(", ")
+        )
+    }
+}
+
+/// Optional extras for a new calendar event
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventDetails {
+    /// Email addresses to invite
+    pub attendees: Vec<String>,
+    pub location: Option<String>,
+    pub notes: Option<String>,
+    /// Explicit end time, used instead of the duration when set
+    pub end_time: Option<String>,
+}
+
+impl EventDetails {
+    /// Reject attendee entries that aren't plausible email addresses, naming them all
+    pub fn validate(&self) -> Result<()> {
+        let invalid: Vec<&str> = self
+            .attendees
+            .iter()
+            .map(String::as_str)
+            .filter(|address| !is_plausible_email(address))
+            .collect();
+        if invalid.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(
+                "Invalid attendee email address(es): {}",
+                invalid.join(", ")
+            ))
+        }
+    }
+}
+
+/// `local@domain.tld` with no whitespace, quotes, or angle brackets
+fn is_plausible_email(address: &str) -> bool {
+    let Some((local, domain)) = address.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !domain.contains('@')
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !address
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '<' | '>' | ',' | ';'))
+}
+
+/// Fields to change on an existing calendar event (`None` leaves a field as is)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventChanges {
+    pub summary: Option<String>,
+    /// New start time; the event keeps its duration unless `duration_minutes` is set
+    pub start_time: Option<String>,
+    pub duration_minutes: Option<u64>,
+    pub location: Option<String>,
+    pub notes: Option<String>,
+}
+
+impl EventChanges {
+    /// Whether no field would change
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Daily window, in local time, in which free slots may be proposed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkingHours {
+    pub start: chrono::NaiveTime,
+    pub end: chrono::NaiveTime,
+    /// Propose no slots on Saturdays and Sundays
+    pub skip_weekends: bool,
+}
+
+impl Default for WorkingHours {
+    fn default() -> Self {
+        Self {
+            start: chrono::NaiveTime::from_hms_opt(9, 0, 0).expect("valid time"),
+            end: chrono::NaiveTime::from_hms_opt(17, 0, 0).expect("valid time"),
+            skip_weekends: true,
+        }
+    }
+}
+
+/// A free stretch of calendar time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeSlot {
+    pub start: chrono::DateTime<chrono::Utc>,
+    pub end: chrono::DateTime<chrono::Utc>,
+}
+
+/// One event from a structured calendar read, with times in UTC
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventSummary {
+    /// Provider identifier of the event (may be empty)
+    pub id: String,
+    pub title: String,
+    /// Calendar the event belongs to, when the provider reports it
+    pub calendar: Option<String>,
+    pub start: chrono::DateTime<chrono::Utc>,
+    /// `None` when the provider gave no end time
+    pub end: Option<chrono::DateTime<chrono::Utc>>,
+    /// All-day events start at local midnight and last whole days
+    pub all_day: bool,
+}
+
+/// Parse the event records a structured calendar script prints: id, title,
+/// calendar, all-day flag ("true"/"false"), start and end, separated by
+/// [`mime::FIELD_SEP`], one record per [`mime::RECORD_SEP`]. Times are RFC 3339
+/// or a local `year month day seconds-since-midnight` stamp, so nothing
+/// depends on how the system formats dates. Any record that can't be read
+/// fails the whole read.
+pub fn parse_event_records(output: &str) -> Result<Vec<EventSummary>> {
+    let trimmed = output.trim_start();
+    if trimmed.starts_with("Error:") {
+        return Err(anyhow::anyhow!("{}", trimmed.trim_end()));
+    }
+    output
+        .split(mime::RECORD_SEP)
+        .filter(|record| !record.trim().is_empty())
+        .map(|record| {
+            let fields: Vec<&str> = record.split(mime::FIELD_SEP).map(str::trim).collect();
+            let [id, title, calendar, all_day, start, end] = fields[..] else {
+                return Err(anyhow::anyhow!(
+                    "Malformed calendar record ({} fields): {}",
+                    fields.len(),
+                    record.trim()
+                ));
+            };
+            let start = parse_record_time(start).ok_or_else(|| {
+                anyhow::anyhow!("Can't read the start time of '{}': {}", title, start)
+            })?;
+            let end = match end {
+                "" => None,
+                end => Some(parse_record_time(end).ok_or_else(|| {
+                    anyhow::anyhow!("Can't read the end time of '{}': {}", title, end)
+                })?),
+            };
+            Ok(EventSummary {
+                id: id.to_string(),
+                title: title.to_string(),
+                calendar: (!calendar.is_empty()).then(|| calendar.to_string()),
+                start,
+                end,
+                all_day: all_day.eq_ignore_ascii_case("true"),
+            })
+        })
+        .collect()
+}
+
+/// An RFC 3339 time, or a `year month day seconds` stamp in the local time zone
+fn parse_record_time(time: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    use chrono::{Local, NaiveDate, NaiveTime, TimeZone, Utc};
+    if let Ok(parsed) = chrono::DateTime::parse_from_rfc3339(time) {
+        return Some(parsed.with_timezone(&Utc));
+    }
+    let parts: Vec<u32> = time
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .ok()?;
+    let [year, month, day, seconds] = parts[..] else {
+        return None;
+    };
+    let date = NaiveDate::from_ymd_opt(i32::try_from(year).ok()?, month, day)?;
+    let time = NaiveTime::from_num_seconds_from_midnight_opt(seconds, 0)?;
+    Local
+        .from_local_datetime(&date.and_time(time))
+        .earliest()
+        .map(|local| local.with_timezone(&Utc))
+}
+
+impl TimeSlot {
+    pub fn minutes(&self) -> i64 {
+        (self.end - self.start).num_minutes()
+    }
+}
+
+/// Calendar provider for reading, creating, updating, and deleting events
 #[async_trait]
 pub trait CalendarProvider: Send + Sync {
-    async fn read_events(&self, days_ahead: u64) -> Result<String>;
+    /// Upcoming events, each with the `ID:` used by [`update_event`](Self::update_event)
+    /// and [`delete_event`](Self::delete_event). Reads every calendar unless
+    /// `calendar_name` picks one.
+    async fn read_events(&self, days_ahead: u64, calendar_name: Option<&str>) -> Result<String>;
+
+    /// Upcoming events as typed records with UTC times, for callers that compute
+    /// with them (watchers, free-slot search). Fails rather than guess when an
+    /// event's time can't be read exactly.
+    async fn read_events_structured(
+        &self,
+        days_ahead: u64,
+        calendar_name: Option<&str>,
+    ) -> Result<Vec<EventSummary>> {
+        let _ = (days_ahead, calendar_name);
+        Err(anyhow::anyhow!(
+            "Structured event reads are not supported by this calendar provider"
+        ))
+    }
+
+    /// Names of the available calendars, one per line
+    async fn list_calendars(&self) -> Result<String>;
+
+    /// Launch the calendar app and check automation permission with a cheap no-op,
+    /// so the first real call doesn't pay the startup cost
+    async fn prewarm(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Create an event in `calendar_name`, or the provider's default calendar.
+    /// Lasts `duration_minutes` unless `details` gives an end time; any attendees
+    /// are invited.
     async fn create_event(
         &self,
         summary: &str,
         start_time: &str,
         duration_minutes: u64,
+        calendar_name: Option<&str>,
+        details: &EventDetails,
     ) -> Result<String>;
+
+    /// Apply `changes` to the event with identifier `event_id`
+    async fn update_event(&self, event_id: &str, changes: &EventChanges) -> Result<String> {
+        let _ = (event_id, changes);
+        Err(anyhow::anyhow!(
+            "Updating events is not supported by this calendar provider"
+        ))
+    }
+
+    /// Delete the event with identifier `event_id`
+    async fn delete_event(&self, event_id: &str) -> Result<String> {
+        let _ = event_id;
+        Err(anyhow::anyhow!(
+            "Deleting events is not supported by this calendar provider"
+        ))
+    }
+
+    /// Free stretches of at least `duration_minutes` inside `working_hours`
+    /// over the next `within_days` days (today included), earliest first.
+    /// By default this reads every calendar with
+    /// [`read_events_structured`](Self::read_events_structured) and computes the
+    /// gaps between events; all-day events don't count as busy. Fails when the
+    /// events can't be read exactly rather than guessing at free time.
+    async fn find_free_slots(
+        &self,
+        duration_minutes: u64,
+        within_days: u64,
+        working_hours: &WorkingHours,
+    ) -> Result<Vec<TimeSlot>> {
+        let events = self.read_events_structured(within_days, None).await?;
+        Ok(free_slots(
+            &busy_times(&events),
+            chrono::Local::now(),
+            duration_minutes,
+            within_days,
+            working_hours,
+        ))
+    }
 }
 
 /// Clipboard provider for reading clipboard contents
 #[async_trait]
 pub trait ClipboardProvider: Send + Sync {
+    /// Clipboard text; fails with a clear message when it holds an image or other non-text
     async fn get_clipboard(&self) -> Result<String>;
+
+    async fn set_clipboard(&self, text: &str) -> Result<()>;
+
+    /// The image on the clipboard
+    async fn get_image(&self) -> Result<ClipboardImage>;
+
+    async fn set_image(&self, image: &ClipboardImage) -> Result<()>;
+}
+
+/// An image on the clipboard as raw pixels
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardImage {
+    pub width: usize,
+    pub height: usize,
+    /// Row-major RGBA, 4 bytes per pixel
+    pub rgba: Vec<u8>,
+}
+
+impl ClipboardImage {
+    /// Check that `rgba` holds exactly `width * height` pixels
+    pub fn validate(&self) -> Result<()> {
+        let expected = self.width * self.height * 4;
+        if self.width == 0 || self.height == 0 || self.rgba.len() != expected {
+            return Err(anyhow::anyhow!(
+                "Invalid clipboard image: {}x{} needs {} RGBA bytes, got {}",
+                self.width,
+                self.height,
+                expected,
+                self.rgba.len()
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// Application launcher
@@ -50,11 +848,145 @@ pub trait AppLauncher: Send + Sync {
     async fn open_app(&self, app_name: &str) -> Result<String>;
 }
 
+/// Allowlist of UI element types, named as System Events names them
+pub const VALID_ELEMENT_TYPES: &[&str] = &[
+    "button",
+    "checkbox",
+    "radio button",
+    "text field",
+    "text area",
+    "pop up button",
+    "menu item",
+    "menu button",
+    "slider",
+    "tab group",
+    "table",
+    "outline",
+    "list",
+    "scroll area",
+    "group",
+    "window",
+    "sheet",
+    "toolbar",
+    "static text",
+    "image",
+    "link",
+    "cell",
+    "row",
+    "column",
+    "combo box",
+    "incrementor",
+    "relevance indicator",
+];
+
+/// Element types listed when no type is asked for: the ones worth clicking
+pub const CLICKABLE_ELEMENT_TYPES: &[&str] = &[
+    "button",
+    "checkbox",
+    "radio button",
+    "pop up button",
+    "menu button",
+    "combo box",
+    "text field",
+    "link",
+];
+
+/// Most elements `list_elements` returns, so a busy window can't flood the context
+pub const MAX_LISTED_ELEMENTS: usize = 200;
+
+/// The canonical (lowercase) form of `element_type`, if it's in [`VALID_ELEMENT_TYPES`]
+pub fn normalize_element_type(element_type: &str) -> Result<&'static str> {
+    VALID_ELEMENT_TYPES
+        .iter()
+        .find(|valid| valid.eq_ignore_ascii_case(element_type))
+        .copied()
+        .ok_or_else(|| anyhow::anyhow!("Invalid element type: {}", element_type))
+}
+
+/// Types `list_elements` should return for an optional `element_type` filter
+pub fn listed_element_types(element_type: Option<&str>) -> Result<Vec<&'static str>> {
+    match element_type {
+        Some(element_type) => Ok(vec![normalize_element_type(element_type)?]),
+        None => Ok(CLICKABLE_ELEMENT_TYPES.to_vec()),
+    }
+}
+
+/// An element of the frontmost window, as found by [`UiAutomation::list_elements`]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct UiElement {
+    /// Name to pass to `click_element`; may be empty for unlabeled elements
+    pub name: String,
+    /// One of [`VALID_ELEMENT_TYPES`]
+    pub role: String,
+    pub enabled: bool,
+}
+
+/// Parse element records printed by a UI script: role, name and enabled flag
+/// separated by `mime::FIELD_SEP`, each record ending with `mime::RECORD_SEP`
+pub fn parse_ui_element_records(output: &str) -> Result<Vec<UiElement>> {
+    let trimmed = output.trim_start();
+    if trimmed.starts_with("Error:") {
+        return Err(anyhow::anyhow!("{}", trimmed.trim_end()));
+    }
+    Ok(output
+        .split(mime::RECORD_SEP)
+        .filter_map(|record| {
+            let mut fields = record.split(mime::FIELD_SEP);
+            let role = fields.next()?.trim();
+            let name = fields.next()?.trim();
+            let enabled = fields.next()?.trim();
+            Some(UiElement {
+                name: name.to_string(),
+                role: role.to_string(),
+                enabled: !enabled.eq_ignore_ascii_case("false"),
+            })
+        })
+        .collect())
+}
+
+/// Parse a display size printed by a UI script as "width,height"
+pub fn parse_display_size(output: &str) -> Result<(u32, u32)> {
+    let parse = |value: &str| value.trim().parse::<f64>().ok().filter(|v| *v >= 1.0);
+    output
+        .trim()
+        .split_once(',')
+        .and_then(|(width, height)| Some((parse(width)? as u32, parse(height)? as u32)))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Could not read the main display size from '{}'",
+                output.trim()
+            )
+        })
+}
+
+/// Check that (`x`, `y`) lies on a main display of `size`, measured from its
+/// top-left corner
+pub fn validate_click_point(x: i32, y: i32, (width, height): (u32, u32)) -> Result<()> {
+    let on_display = |value: i32, extent: u32| u32::try_from(value).is_ok_and(|v| v < extent);
+    if on_display(x, width) && on_display(y, height) {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Point ({}, {}) is outside the main display (x 0-{}, y 0-{})",
+            x,
+            y,
+            width - 1,
+            height - 1
+        ))
+    }
+}
+
 /// UI automation for accessibility
 #[async_trait]
 pub trait UiAutomation: Send + Sync {
     async fn read_screen(&self) -> Result<String>;
+    /// Elements of the frontmost window, at most [`MAX_LISTED_ELEMENTS`]. With
+    /// `element_type`, only that type; otherwise [`CLICKABLE_ELEMENT_TYPES`].
+    async fn list_elements(&self, element_type: Option<&str>) -> Result<Vec<UiElement>>;
     async fn click_element(&self, element_name: &str, element_type: &str) -> Result<String>;
+    /// Left-click at (`x`, `y`) on the main display, measured from its top-left
+    /// corner; points off the display are rejected before anything is clicked
+    async fn click_at(&self, x: i32, y: i32) -> Result<String>;
     async fn type_text(&self, text: &str) -> Result<String>;
 }
 
@@ -86,13 +1018,147 @@ pub trait NotificationProvider: Send + Sync {
         title: &str,
         message: &str,
         sound: Option<&str>,
+        urgency: Urgency,
     ) -> Result<String>;
 }
 
+/// How insistently a desktop notification should be shown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Urgency {
+    /// Shown quietly (no sound unless one is asked for)
+    Low,
+    #[default]
+    Normal,
+    /// Stays on screen until dismissed where the desktop supports it
+    Critical,
+}
+
+impl std::str::FromStr for Urgency {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "low" => Ok(Self::Low),
+            "normal" => Ok(Self::Normal),
+            "critical" => Ok(Self::Critical),
+            other => Err(anyhow::anyhow!(
+                "Invalid urgency '{}' (expected low, normal, or critical)",
+                other
+            )),
+        }
+    }
+}
+
 /// Screen capture provider
 #[async_trait]
 pub trait ScreenCaptureProvider: Send + Sync {
-    async fn capture_screen(&self, path: Option<&str>) -> Result<String>;
+    /// The main display as PNG bytes
+    async fn capture_screen_png(&self) -> Result<Vec<u8>>;
+
+    /// The first window whose title contains `title`, as PNG bytes
+    async fn capture_window_png(&self, title: &str) -> Result<Vec<u8>>;
+
+    /// Save a screenshot of the main display to `path` (default: a temp file)
+    async fn capture_screen(&self, path: Option<&str>) -> Result<String> {
+        let png = self.capture_screen_png().await?;
+        save_screenshot(path, &png).await
+    }
+
+    /// Save a screenshot of a window to `path` (default: a temp file)
+    async fn capture_window(&self, title: &str, path: Option<&str>) -> Result<String> {
+        let png = self.capture_window_png(title).await?;
+        save_screenshot(path, &png).await
+    }
+}
+
+/// Default screenshot location: a timestamped PNG in the temp directory
+pub(crate) fn default_screenshot_path() -> String {
+    std::env::temp_dir()
+        .join(format!(
+            "meepo-screenshot-{}.png",
+            chrono::Utc::now().format("%Y%m%d_%H%M%S_%3f")
+        ))
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Write PNG bytes to `path` (validated) or the default location, returning a status line
+async fn save_screenshot(path: Option<&str>, png: &[u8]) -> Result<String> {
+    let output_path = path
+        .map(String::from)
+        .unwrap_or_else(default_screenshot_path);
+    if !output_path.to_lowercase().ends_with(".png") {
+        return Err(anyhow::anyhow!(
+            "Screenshots are saved as PNG; use a path ending in .png"
+        ));
+    }
+    validate_screenshot_path(&output_path)?;
+    tokio::fs::write(&output_path, png)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to save screenshot to {}: {}", output_path, e))?;
+    Ok(format!("Screenshot saved to {}", output_path))
+}
+
+/// Validate screenshot output path to prevent writing to sensitive locations
+pub(crate) fn validate_screenshot_path(path: &str) -> Result<()> {
+    if path.contains("..") {
+        return Err(anyhow::anyhow!(
+            "Screenshot path contains '..' which is not allowed"
+        ));
+    }
+
+    let path_buf = std::path::PathBuf::from(path);
+
+    // Resolve parent directory to check location
+    let check_path = if let Some(parent) = path_buf.parent() {
+        if parent.as_os_str().is_empty() || !parent.exists() {
+            path_buf.clone()
+        } else {
+            parent
+                .canonicalize()
+                .unwrap_or_else(|_| parent.to_path_buf())
+                .join(path_buf.file_name().unwrap_or_default())
+        }
+    } else {
+        path_buf.clone()
+    };
+
+    let home_dir =
+        dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
+    let temp_dir = std::env::temp_dir()
+        .canonicalize()
+        .unwrap_or_else(|_| std::env::temp_dir());
+
+    let is_in_home = check_path.starts_with(&home_dir);
+    let is_in_temp = check_path.starts_with(&temp_dir);
+
+    if !is_in_home && !is_in_temp {
+        return Err(anyhow::anyhow!(
+            "Screenshot path '{}' must be within home or temp directory",
+            path
+        ));
+    }
+
+    // Block system directories even if under home
+    let system_dirs = [
+        "/etc",
+        "/bin",
+        "/sbin",
+        "/usr/bin",
+        "/usr/sbin",
+        "/System",
+        "/Library",
+    ];
+    for sys_dir in &system_dirs {
+        if check_path.starts_with(sys_dir) {
+            return Err(anyhow::anyhow!(
+                "Screenshot path cannot target system directory '{}'",
+                sys_dir
+            ));
+        }
+    }
+
+    Ok(())
 }
 
 /// Music control provider (Apple Music / Spotify)
@@ -102,10 +1168,51 @@ pub trait MusicProvider: Send + Sync {
     async fn control_playback(&self, action: &str) -> Result<String>;
 }
 
+/// A person from the address book
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Contact {
+    pub name: String,
+    pub emails: Vec<String>,
+    pub phones: Vec<String>,
+}
+
 /// Contacts provider for searching contacts
 #[async_trait]
 pub trait ContactsProvider: Send + Sync {
     async fn search_contacts(&self, query: &str) -> Result<String>;
+
+    /// All contacts whose name contains `name`, so ambiguous names yield every candidate
+    async fn lookup(&self, name: &str) -> Result<Vec<Contact>>;
+}
+
+/// Parse contact records printed by a contacts script: records end with
+/// `mime::RECORD_SEP`, fields (name, emails, phones) are split by `mime::FIELD_SEP`,
+/// and values within a field by `mime::ATTACHMENT_SEP`.
+pub fn parse_contact_records(output: &str) -> Vec<Contact> {
+    let values = |field: Option<&str>| -> Vec<String> {
+        field
+            .unwrap_or_default()
+            .split(mime::ATTACHMENT_SEP)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(String::from)
+            .collect()
+    };
+    output
+        .split(mime::RECORD_SEP)
+        .filter_map(|record| {
+            let mut fields = record.split(mime::FIELD_SEP);
+            let name = fields.next()?.trim();
+            if name.is_empty() {
+                return None;
+            }
+            Some(Contact {
+                name: name.to_string(),
+                emails: values(fields.next()),
+                phones: values(fields.next()),
+            })
+        })
+        .collect()
 }
 
 /// Browser tab metadata
@@ -156,10 +1263,27 @@ pub trait BrowserProvider: Send + Sync {
 }
 
 /// Create platform email provider
+///
+/// A configured IMAP account takes precedence over the platform mail app.
 pub fn create_email_provider() -> Result<Box<dyn EmailProvider>> {
+    create_email_provider_with_options(&ProviderOptions::default())
+}
+
+/// Create platform email provider with non-default options
+pub fn create_email_provider_with_options(
+    options: &ProviderOptions,
+) -> Result<Box<dyn EmailProvider>> {
+    if imap::is_configured() {
+        return Ok(Box::new(imap::ImapEmailProvider::from_configured()?));
+    }
+    // Only the AppleScript providers take options so far
+    #[cfg(not(target_os = "macos"))]
+    let _ = options;
     #[cfg(target_os = "macos")]
     {
-        Ok(Box::new(macos::MacOsEmailProvider))
+        Ok(Box::new(macos::MacOsEmailProvider::new(
+            options.script_timeout,
+        )))
     }
     #[cfg(target_os = "windows")]
     {
@@ -167,26 +1291,230 @@ pub fn create_email_provider() -> Result<Box<dyn EmailProvider>> {
     }
     #[cfg(not(any(target_os = "macos", target_os = "windows")))]
     {
-        Err(anyhow::anyhow!("Email provider not available on this platform"))
+        Err(anyhow::anyhow!(
+            "Email provider not available on this platform without an IMAP account ([imap] in config)"
+        ))
     }
 }
 
 /// Create platform calendar provider
 pub fn create_calendar_provider() -> Result<Box<dyn CalendarProvider>> {
+    create_calendar_provider_with_options(&ProviderOptions::default())
+}
+
+/// Create platform calendar provider with non-default options
+pub fn create_calendar_provider_with_options(
+    options: &ProviderOptions,
+) -> Result<Box<dyn CalendarProvider>> {
+    // Only the AppleScript providers take options so far
+    #[cfg(not(target_os = "macos"))]
+    let _ = options;
     #[cfg(target_os = "macos")]
     {
-        Ok(Box::new(macos::MacOsCalendarProvider))
+        Ok(Box::new(macos::MacOsCalendarProvider::new(
+            options.script_timeout,
+        )))
     }
     #[cfg(target_os = "windows")]
     {
         Ok(Box::new(windows::WindowsCalendarProvider))
     }
-    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    #[cfg(target_os = "linux")]
+    {
+        Ok(Box::new(linux::LinuxCalendarProvider::from_configured()?))
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
     {
         Err(anyhow::anyhow!("Calendar provider not available on this platform"))
     }
 }
 
+/// Prewarm the email and calendar providers concurrently.
+/// Failures are logged rather than returned; platforms without the providers are skipped.
+pub async fn prewarm_all() {
+    let email = async {
+        match create_email_provider() {
+            Ok(provider) => Some(provider.prewarm().await),
+            Err(_) => None,
+        }
+    };
+    let calendar = async {
+        match create_calendar_provider() {
+            Ok(provider) => Some(provider.prewarm().await),
+            Err(_) => None,
+        }
+    };
+    let started = Instant::now();
+    let (email, calendar) = tokio::join!(email, calendar);
+
+    for (name, result) in [("email", email), ("calendar", calendar)] {
+        match result {
+            Some(Ok(())) => info!("Prewarmed {} provider", name),
+            Some(Err(e)) => warn!("Failed to prewarm {} provider: {}", name, e),
+            None => {}
+        }
+    }
+    info!("Provider prewarm finished in {:?}", started.elapsed());
+}
+
+/// Lets email watchers poll a platform email provider (e.g. Outlook) through its
+/// structured reads, so they dedup by message id
+pub struct ProviderEmailSource {
+    provider: Box<dyn EmailProvider>,
+}
+
+impl ProviderEmailSource {
+    pub fn new(provider: Box<dyn EmailProvider>) -> Self {
+        Self { provider }
+    }
+}
+
+#[async_trait]
+impl meepo_scheduler::EmailSource for ProviderEmailSource {
+    async fn recent_emails(
+        &self,
+        limit: usize,
+        include_body: bool,
+    ) -> Result<Vec<meepo_scheduler::PolledEmail>> {
+        let emails = self
+            .provider
+            .read_emails_structured(limit as u64, "inbox", None)
+            .await?;
+        Ok(emails
+            .into_iter()
+            .map(|email| meepo_scheduler::PolledEmail {
+                message_id: email.message_id,
+                from: email.from,
+                subject: email.subject,
+                date: email.date,
+                body: include_body.then_some(email.preview),
+            })
+            .collect())
+    }
+}
+
+/// Lets calendar watchers poll a platform calendar provider through its
+/// structured reads, so event times arrive as exact UTC instants
+pub struct ProviderCalendarSource {
+    provider: Box<dyn CalendarProvider>,
+}
+
+impl ProviderCalendarSource {
+    pub fn new(provider: Box<dyn CalendarProvider>) -> Self {
+        Self { provider }
+    }
+}
+
+#[async_trait]
+impl meepo_scheduler::CalendarSource for ProviderCalendarSource {
+    async fn upcoming_events(
+        &self,
+        days_ahead: u64,
+        calendar: Option<&str>,
+    ) -> Result<Vec<meepo_scheduler::PolledCalendarEvent>> {
+        let events = self
+            .provider
+            .read_events_structured(days_ahead, calendar)
+            .await?;
+        Ok(events
+            .into_iter()
+            .map(|event| meepo_scheduler::PolledCalendarEvent {
+                id: event.id,
+                title: event.title,
+                start: event.start,
+                all_day: event.all_day,
+            })
+            .collect())
+    }
+}
+
+/// How long an event without an end time is assumed to last
+const DEFAULT_EVENT_MINUTES: i64 = 60;
+
+/// Busy intervals of `events`, sorted by start. All-day events are left out.
+fn busy_times(
+    events: &[EventSummary],
+) -> Vec<(chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>)> {
+    let mut busy: Vec<_> = events
+        .iter()
+        .filter(|event| !event.all_day)
+        .map(|event| {
+            let end = event
+                .end
+                .filter(|end| *end > event.start)
+                .unwrap_or(event.start + chrono::Duration::minutes(DEFAULT_EVENT_MINUTES));
+            (event.start, end)
+        })
+        .collect();
+    busy.sort();
+    busy
+}
+
+/// Gaps of at least `duration_minutes` between `busy` intervals (sorted by
+/// start) inside the working hours of each of `within_days` days from `now`,
+/// in `now`'s time zone. Slots start no earlier than `now` rounded up to the
+/// next quarter hour.
+fn free_slots<Tz: chrono::TimeZone>(
+    busy: &[(chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>)],
+    now: chrono::DateTime<Tz>,
+    duration_minutes: u64,
+    within_days: u64,
+    working_hours: &WorkingHours,
+) -> Vec<TimeSlot> {
+    use chrono::{Datelike, Days, Utc, Weekday};
+
+    let duration = chrono::Duration::minutes(duration_minutes as i64);
+    let quarter = 15 * 60;
+    let now_secs = now.timestamp();
+    let earliest = chrono::DateTime::<Utc>::from_timestamp(
+        now_secs + (quarter - now_secs.rem_euclid(quarter)) % quarter,
+        0,
+    )
+    .unwrap_or_else(|| now.with_timezone(&Utc));
+    let tz = now.timezone();
+    let today = now.date_naive();
+
+    let mut slots = Vec::new();
+    for offset in 0..within_days {
+        let Some(day) = today.checked_add_days(Days::new(offset)) else {
+            break;
+        };
+        if working_hours.skip_weekends && matches!(day.weekday(), Weekday::Sat | Weekday::Sun) {
+            continue;
+        }
+        let (Some(open), Some(close)) = (
+            tz.from_local_datetime(&day.and_time(working_hours.start))
+                .earliest(),
+            tz.from_local_datetime(&day.and_time(working_hours.end))
+                .latest(),
+        ) else {
+            continue;
+        };
+        let close = close.with_timezone(&Utc);
+        let mut cursor = open.with_timezone(&Utc).max(earliest);
+
+        for (start, end) in busy {
+            if *end <= cursor || *start >= close {
+                continue;
+            }
+            if *start - cursor >= duration {
+                slots.push(TimeSlot {
+                    start: cursor,
+                    end: *start,
+                });
+            }
+            cursor = cursor.max(*end);
+        }
+        if close - cursor >= duration {
+            slots.push(TimeSlot {
+                start: cursor,
+                end: close,
+            });
+        }
+    }
+    slots
+}
+
 /// Create cross-platform clipboard provider
 pub fn create_clipboard_provider() -> Box<dyn ClipboardProvider> {
     Box::new(CrossPlatformClipboard)
@@ -199,9 +1527,21 @@ pub fn create_app_launcher() -> Box<dyn AppLauncher> {
 
 /// Create platform UI automation provider
 pub fn create_ui_automation() -> Result<Box<dyn UiAutomation>> {
+    create_ui_automation_with_options(&ProviderOptions::default())
+}
+
+/// Create platform UI automation provider with non-default options
+pub fn create_ui_automation_with_options(
+    options: &ProviderOptions,
+) -> Result<Box<dyn UiAutomation>> {
+    // Only the AppleScript providers take options so far
+    #[cfg(not(target_os = "macos"))]
+    let _ = options;
     #[cfg(target_os = "macos")]
     {
-        Ok(Box::new(macos::MacOsUiAutomation))
+        Ok(Box::new(macos::MacOsUiAutomation::new(
+            options.script_timeout,
+        )))
     }
     #[cfg(target_os = "windows")]
     {
@@ -237,7 +1577,7 @@ pub fn create_notes_provider() -> Result<Box<dyn NotesProvider>> {
     }
 }
 
-/// Create platform notification provider (macOS only)
+/// Create platform notification provider (AppleScript on macOS, `notify-rust` elsewhere)
 pub fn create_notification_provider() -> Result<Box<dyn NotificationProvider>> {
     #[cfg(target_os = "macos")]
     {
@@ -245,11 +1585,12 @@ pub fn create_notification_provider() -> Result<Box<dyn NotificationProvider>> {
     }
     #[cfg(not(target_os = "macos"))]
     {
-        Err(anyhow::anyhow!("Notification provider is only available on macOS"))
+        Ok(Box::new(CrossPlatformNotificationProvider))
     }
 }
 
-/// Create platform screen capture provider (macOS only)
+/// Create platform screen capture provider (`screencapture` on macOS, the desktop's
+/// screenshot tool elsewhere)
 pub fn create_screen_capture_provider() -> Result<Box<dyn ScreenCaptureProvider>> {
     #[cfg(target_os = "macos")]
     {
@@ -257,7 +1598,7 @@ pub fn create_screen_capture_provider() -> Result<Box<dyn ScreenCaptureProvider>
     }
     #[cfg(not(target_os = "macos"))]
     {
-        Err(anyhow::anyhow!("Screen capture provider is only available on macOS"))
+        Ok(Box::new(CrossPlatformScreenCapture))
     }
 }
 
@@ -327,16 +1668,243 @@ pub struct CrossPlatformClipboard;
 impl ClipboardProvider for CrossPlatformClipboard {
     async fn get_clipboard(&self) -> Result<String> {
         tokio::task::spawn_blocking(|| {
-            let mut clipboard = arboard::Clipboard::new()
-                .map_err(|e| anyhow::anyhow!("Failed to access clipboard: {}", e))?;
-            clipboard
-                .get_text()
-                .map_err(|e| anyhow::anyhow!("Failed to read clipboard: {}", e))
+            with_clipboard(|clipboard| match clipboard.get_text() {
+                Ok(text) => Ok(text),
+                Err(arboard::Error::ContentNotAvailable) => match clipboard.get_image() {
+                    Ok(image) => Err(anyhow::anyhow!(
+                        "Clipboard contains non-text content (an image, {}x{})",
+                        image.width,
+                        image.height
+                    )),
+                    Err(_) => Err(anyhow::anyhow!(
+                        "Clipboard is empty or contains non-text content"
+                    )),
+                },
+                Err(e) => Err(anyhow::anyhow!("Failed to read clipboard: {}", e)),
+            })
+        })
+        .await?
+    }
+
+    async fn set_clipboard(&self, text: &str) -> Result<()> {
+        let text = text.to_string();
+        tokio::task::spawn_blocking(move || {
+            with_clipboard(|clipboard| {
+                clipboard
+                    .set_text(text)
+                    .map_err(|e| anyhow::anyhow!("Failed to write clipboard: {}", e))
+            })
+        })
+        .await?
+    }
+
+    async fn get_image(&self) -> Result<ClipboardImage> {
+        tokio::task::spawn_blocking(|| {
+            let image = with_clipboard(|clipboard| {
+                clipboard.get_image().map_err(|e| match e {
+                    arboard::Error::ContentNotAvailable => {
+                        anyhow::anyhow!("Clipboard does not contain an image")
+                    }
+                    e => anyhow::anyhow!("Failed to read clipboard image: {}", e),
+                })
+            })?;
+            Ok(ClipboardImage {
+                width: image.width,
+                height: image.height,
+                rgba: image.bytes.into_owned(),
+            })
+        })
+        .await?
+    }
+
+    async fn set_image(&self, image: &ClipboardImage) -> Result<()> {
+        image.validate()?;
+        let image = image.clone();
+        tokio::task::spawn_blocking(move || {
+            with_clipboard(|clipboard| {
+                clipboard
+                    .set_image(arboard::ImageData {
+                        width: image.width,
+                        height: image.height,
+                        bytes: image.rgba.into(),
+                    })
+                    .map_err(|e| anyhow::anyhow!("Failed to write clipboard image: {}", e))
+            })
         })
         .await?
     }
 }
 
+/// The clipboard handle shared by every read and write. On Linux the process that
+/// wrote the clipboard serves its content to other apps until the handle is dropped,
+/// so a handle opened per write would take the content with it.
+static CLIPBOARD: LazyLock<Mutex<Option<arboard::Clipboard>>> =
+    LazyLock::new(|| Mutex::new(None));
+
+/// Run `f` with the shared clipboard handle, opening it on first use
+fn with_clipboard<T>(f: impl FnOnce(&mut arboard::Clipboard) -> Result<T>) -> Result<T> {
+    let mut shared = CLIPBOARD.lock().unwrap_or_else(|e| e.into_inner());
+    let mut clipboard = match shared.take() {
+        Some(clipboard) => clipboard,
+        None => arboard::Clipboard::new()
+            .map_err(|e| anyhow::anyhow!("Failed to access clipboard: {}", e))?,
+    };
+    let result = f(&mut clipboard);
+    *shared = Some(clipboard);
+    result
+}
+
+/// Desktop notifications via `notify-rust` (D-Bus on Linux, toast notifications on Windows)
+#[cfg(not(target_os = "macos"))]
+pub struct CrossPlatformNotificationProvider;
+
+#[cfg(not(target_os = "macos"))]
+#[async_trait]
+impl NotificationProvider for CrossPlatformNotificationProvider {
+    async fn send_notification(
+        &self,
+        title: &str,
+        message: &str,
+        sound: Option<&str>,
+        urgency: Urgency,
+    ) -> Result<String> {
+        let mut notification = notify_rust::Notification::new();
+        notification
+            .appname("Meepo")
+            .summary(title)
+            .body(message)
+            .urgency(match urgency {
+                Urgency::Low => notify_rust::Urgency::Low,
+                Urgency::Normal => notify_rust::Urgency::Normal,
+                Urgency::Critical => notify_rust::Urgency::Critical,
+            });
+        if let Some(sound) = sound {
+            notification.sound_name(sound);
+        }
+        // Showing blocks on D-Bus / COM
+        tokio::task::spawn_blocking(move || {
+            notification
+                .show()
+                .map(|_| ())
+                .map_err(|e| anyhow::anyhow!("Failed to show notification: {}", e))
+        })
+        .await??;
+        Ok("Notification sent".to_string())
+    }
+}
+
+/// Screen capture through the desktop's own screenshot tool: grim (Wayland),
+/// gnome-screenshot, spectacle, scrot or ImageMagick's `import` (X11) on Linux,
+/// and PowerShell with System.Drawing on Windows
+#[cfg(not(target_os = "macos"))]
+pub struct CrossPlatformScreenCapture;
+
+/// Commands that write a PNG of the whole screen to `path`, in order of preference
+#[cfg(not(target_os = "macos"))]
+fn screenshot_commands(path: &str) -> Vec<(&'static str, Vec<String>)> {
+    #[cfg(target_os = "windows")]
+    {
+        let script = format!(
+            "Add-Type -AssemblyName System.Windows.Forms,System.Drawing; \
+             $b = [System.Windows.Forms.SystemInformation]::VirtualScreen; \
+             $bmp = New-Object System.Drawing.Bitmap $b.Width, $b.Height; \
+             [System.Drawing.Graphics]::FromImage($bmp).CopyFromScreen($b.Left, $b.Top, 0, 0, $bmp.Size); \
+             $bmp.Save('{}', [System.Drawing.Imaging.ImageFormat]::Png)",
+            path.replace('\'', "''")
+        );
+        vec![(
+            "powershell",
+            vec![
+                "-NoProfile".to_string(),
+                "-NonInteractive".to_string(),
+                "-Command".to_string(),
+                script,
+            ],
+        )]
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let args = |flags: &[&str]| {
+            flags
+                .iter()
+                .map(|f| f.to_string())
+                .chain(std::iter::once(path.to_string()))
+                .collect::<Vec<_>>()
+        };
+        vec![
+            ("grim", args(&[])),
+            ("gnome-screenshot", args(&["-f"])),
+            ("spectacle", args(&["-b", "-n", "-o"])),
+            ("scrot", args(&["-o"])),
+            ("import", args(&["-window", "root"])),
+        ]
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+impl CrossPlatformScreenCapture {
+    /// Run the first available screenshot command, writing a PNG to `path`
+    async fn capture_to(path: &std::path::Path) -> Result<()> {
+        let path_str = path.to_string_lossy();
+        let mut failures = Vec::new();
+        for (program, args) in screenshot_commands(&path_str) {
+            let output = match tokio::process::Command::new(program)
+                .args(&args)
+                .output()
+                .await
+            {
+                Ok(output) => output,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => {
+                    failures.push(format!("{}: {}", program, e));
+                    continue;
+                }
+            };
+            if output.status.success() && path.exists() {
+                tracing::debug!("Captured screen with {}", program);
+                return Ok(());
+            }
+            failures.push(format!(
+                "{}: {}",
+                program,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        if failures.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No screenshot tool found; install grim (Wayland) or scrot (X11)"
+            ));
+        }
+        Err(anyhow::anyhow!(
+            "Screen capture failed ({})",
+            failures.join("; ")
+        ))
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+#[async_trait]
+impl ScreenCaptureProvider for CrossPlatformScreenCapture {
+    async fn capture_screen_png(&self) -> Result<Vec<u8>> {
+        let path = std::env::temp_dir().join(format!("meepo-capture-{}.png", uuid::Uuid::new_v4()));
+        let png = match Self::capture_to(&path).await {
+            Ok(()) => tokio::fs::read(&path)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to read captured screenshot: {}", e)),
+            Err(e) => Err(e),
+        };
+        let _ = tokio::fs::remove_file(&path).await;
+        png
+    }
+
+    async fn capture_window_png(&self, title: &str) -> Result<Vec<u8>> {
+        Err(anyhow::anyhow!(
+            "Capturing a single window ('{}') is only supported on macOS; capture the whole screen instead",
+            title
+        ))
+    }
+}
+
 /// Cross-platform app launcher
 pub struct CrossPlatformAppLauncher;
 
@@ -397,6 +1965,561 @@ mod tests {
         let _ui = create_ui_automation().unwrap();
     }
 
+    #[test]
+    fn test_automation_permission_cache() {
+        let app = "MeepoTestApp";
+        assert_eq!(automation_permission(app), None);
+        assert!(check_automation_permission(app).is_ok());
+
+        record_automation_permission(app, false);
+        assert_eq!(automation_permission(app), Some(false));
+        let err = check_automation_permission(app).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("not allowed to control MeepoTestApp")
+        );
+
+        record_automation_permission(app, true);
+        assert_eq!(automation_permission(app), Some(true));
+        assert!(check_automation_permission(app).is_ok());
+    }
+
+    #[test]
+    fn test_clipboard_image_validate() {
+        let image = ClipboardImage {
+            width: 2,
+            height: 1,
+            rgba: vec![0; 8],
+        };
+        assert!(image.validate().is_ok());
+        let short = ClipboardImage {
+            rgba: vec![0; 7],
+            ..image
+        };
+        assert_eq!(
+            short.validate().unwrap_err().to_string(),
+            "Invalid clipboard image: 2x1 needs 8 RGBA bytes, got 7"
+        );
+    }
+
+    #[test]
+    fn test_event_details_validate() {
+        let details = EventDetails {
+            attendees: vec![
+                "ann@example.com".to_string(),
+                "bob.smith+x@mail.co.uk".to_string(),
+            ],
+            ..Default::default()
+        };
+        assert!(details.validate().is_ok());
+
+        let details = EventDetails {
+            attendees: vec![
+                "ann@example.com".to_string(),
+                "bob".to_string(),
+                "carol@localhost".to_string(),
+                "dave @example.com".to_string(),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            details.validate().unwrap_err().to_string(),
+            "Invalid attendee email address(es): bob, carol@localhost, dave @example.com"
+        );
+    }
+
+    #[test]
+    fn test_parse_email_summary_records() {
+        let output = "<a@x>\u{1f}Ann <ann@x.com>\u{1f}Lunch\u{1f}Monday\u{1f}See you at noon\u{1e}\
+                      \u{1f}Bob\u{1f}Re: Lunch\u{1f}Tuesday\u{1f}\u{1e}\n";
+        let emails = parse_email_summary_records(output).unwrap();
+        assert_eq!(emails.len(), 2);
+        assert_eq!(emails[0].message_id, "<a@x>");
+        assert_eq!(emails[0].from, "Ann <ann@x.com>");
+        assert_eq!(emails[0].preview, "See you at noon");
+        assert_eq!(emails[1].message_id, "");
+        assert_eq!(emails[1].subject, "Re: Lunch");
+
+        assert_eq!(
+            format_email_summaries("inbox", &emails[..1]),
+            "From: Ann <ann@x.com>\nSubject: Lunch\nDate: Monday\nMessage-ID: <a@x>\n\
+             Preview: See you at noon\n---\n"
+        );
+        assert_eq!(
+            format_email_summaries("inbox", &[]),
+            "No emails found in inbox"
+        );
+
+        let err = parse_email_summary_records("Error: Mail got an error").unwrap_err();
+        assert_eq!(err.to_string(), "Error: Mail got an error");
+    }
+
+    #[test]
+    fn test_page_info_footer() {
+        assert_eq!(
+            PageInfo::of_total(10, 10, 134).footer("emails"),
+            "Showing emails 11-20 of 134. More available: use offset=20 for the next page."
+        );
+        assert_eq!(
+            PageInfo::of_total(0, 3, 3).footer("events"),
+            "Showing events 1-3 of 3. No more events."
+        );
+        assert_eq!(
+            PageInfo::of_total(40, 0, 12).footer("emails"),
+            "No emails at offset 40 of 12. No more emails."
+        );
+
+        let email = |id: &str| EmailSummary {
+            message_id: id.to_string(),
+            ..Default::default()
+        };
+        let window: Vec<_> = ["<1@x>", "<2@x>", "<3@x>", "<4@x>"]
+            .into_iter()
+            .map(email)
+            .collect();
+        let page = EmailPage::from_window(window.clone(), 1, 2);
+        let ids: Vec<&str> = page.emails.iter().map(|e| e.message_id.as_str()).collect();
+        assert_eq!(ids, vec!["<2@x>", "<3@x>"]);
+        assert!(page.page.has_more);
+        assert_eq!(page.page.total, None);
+        assert!(
+            format_email_page("inbox", &page)
+                .ends_with("Showing emails 2-3. More available: use offset=3 for the next page.")
+        );
+
+        let page = EmailPage::from_window(window.clone(), 2, 5);
+        assert_eq!(page.page.count, 2);
+        assert!(!page.page.has_more);
+        // An absurd offset reads nothing instead of overflowing
+        let page = EmailPage::from_window(window, u64::MAX, 10);
+        assert_eq!(page.page.count, 0);
+        assert!(!page.page.has_more);
+        assert_eq!(
+            format_email_page("inbox", &EmailPage::from_window(Vec::new(), 0, 10)),
+            "No emails found in inbox"
+        );
+    }
+
+    #[test]
+    fn test_merge_email_summaries() {
+        let email = |id: &str, date: &str| EmailSummary {
+            message_id: id.to_string(),
+            date: date.to_string(),
+            ..Default::default()
+        };
+        let merged = merge_email_summaries(
+            vec![
+                email("<a@x>", "Mon, 3 Jun 2024 09:00:00 +0000"),
+                email("<b@x>", "someday"),
+                email("<c@x>", "Tue, 4 Jun 2024 09:00:00 +0000"),
+                email("<a@x>", "Mon, 3 Jun 2024 09:00:00 +0000"),
+                email("<d@x>", "2024-06-03T10:00:00Z"),
+            ],
+            10,
+        );
+        let ids: Vec<&str> = merged.iter().map(|e| e.message_id.as_str()).collect();
+        assert_eq!(ids, vec!["<c@x>", "<d@x>", "<a@x>", "<b@x>"]);
+        assert_eq!(merge_email_summaries(merged, 2).len(), 2);
+        assert!(parse_display_date("6/3/2024 9:05:00 AM").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_read_emails_across_skips_missing_mailboxes() {
+        struct TwoMailboxes;
+
+        #[async_trait]
+        impl EmailProvider for TwoMailboxes {
+            async fn read_emails_structured(
+                &self,
+                _limit: u64,
+                mailbox: &str,
+                _search: Option<&str>,
+            ) -> Result<Vec<EmailSummary>> {
+                let date = match mailbox {
+                    "inbox" => "Mon, 3 Jun 2024 09:00:00 +0000",
+                    "sent" => "Tue, 4 Jun 2024 09:00:00 +0000",
+                    _ => anyhow::bail!("no mailbox {}", mailbox),
+                };
+                Ok(vec![EmailSummary {
+                    message_id: format!("<{}@x>", mailbox),
+                    date: date.to_string(),
+                    ..Default::default()
+                }])
+            }
+
+            async fn send_email(
+                &self,
+                _to: &str,
+                _subject: &str,
+                _body: &str,
+                _cc: Option<&str>,
+                _in_reply_to: Option<&str>,
+                _attachments: &[PathBuf],
+            ) -> Result<String> {
+                unimplemented!()
+            }
+        }
+
+        // The default page read asks for one past the page
+        let page = TwoMailboxes
+            .read_emails_page(10, 0, "inbox", None)
+            .await
+            .unwrap();
+        assert_eq!(page.emails.len(), 1);
+        assert!(!page.page.has_more);
+
+        let emails = TwoMailboxes
+            .read_emails_across(10, ALL_MAILBOXES, None)
+            .await
+            .unwrap();
+        let ids: Vec<&str> = emails.iter().map(|e| e.message_id.as_str()).collect();
+        assert_eq!(ids, vec!["<sent@x>", "<inbox@x>"]);
+
+        let err = TwoMailboxes
+            .read_emails_across(10, &["archive"], None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "no mailbox archive");
+    }
+
+    #[test]
+    fn test_click_point_validation() {
+        assert_eq!(parse_display_size("1440,900\n").unwrap(), (1440, 900));
+        assert_eq!(parse_display_size("1512.0, 982.0").unwrap(), (1512, 982));
+        assert!(parse_display_size("Error: no display").is_err());
+        assert!(parse_display_size("0,0").is_err());
+
+        assert!(validate_click_point(0, 0, (1440, 900)).is_ok());
+        assert!(validate_click_point(1439, 899, (1440, 900)).is_ok());
+        assert_eq!(
+            validate_click_point(1440, 10, (1440, 900))
+                .unwrap_err()
+                .to_string(),
+            "Point (1440, 10) is outside the main display (x 0-1439, y 0-899)"
+        );
+        assert!(validate_click_point(-1, 10, (1440, 900)).is_err());
+        assert!(validate_click_point(10, 900, (1440, 900)).is_err());
+    }
+
+    #[test]
+    fn test_list_elements_helpers() {
+        assert_eq!(
+            normalize_element_type("Radio Button").unwrap(),
+            "radio button"
+        );
+        assert_eq!(
+            normalize_element_type("widget").unwrap_err().to_string(),
+            "Invalid element type: widget"
+        );
+        assert_eq!(listed_element_types(Some("LINK")).unwrap(), vec!["link"]);
+        assert_eq!(listed_element_types(None).unwrap(), CLICKABLE_ELEMENT_TYPES);
+        assert!(
+            CLICKABLE_ELEMENT_TYPES
+                .iter()
+                .all(|t| VALID_ELEMENT_TYPES.contains(t))
+        );
+
+        let output = "button\u{1f}Save\u{1f}true\u{1e}checkbox\u{1f}\u{1f}False\u{1e}\n";
+        let elements = parse_ui_element_records(output).unwrap();
+        assert_eq!(
+            elements,
+            vec![
+                UiElement {
+                    name: "Save".to_string(),
+                    role: "button".to_string(),
+                    enabled: true,
+                },
+                UiElement {
+                    name: String::new(),
+                    role: "checkbox".to_string(),
+                    enabled: false,
+                },
+            ]
+        );
+        assert!(parse_ui_element_records("Error: no window").is_err());
+    }
+
+    #[test]
+    fn test_reply_target() {
+        assert_eq!(
+            ReplyTarget::parse("<CA+abc.123@mail.gmail.com>"),
+            ReplyTarget::MessageId("CA+abc.123@mail.gmail.com")
+        );
+        assert_eq!(
+            ReplyTarget::parse(" 1234.5678@example.com "),
+            ReplyTarget::MessageId("1234.5678@example.com")
+        );
+        assert_eq!(
+            ReplyTarget::parse("Lunch on Friday"),
+            ReplyTarget::Subject("Lunch on Friday")
+        );
+        // Subjects that mention an address aren't ids
+        assert_eq!(
+            ReplyTarget::parse("Invoice for bob@example.com"),
+            ReplyTarget::Subject("Invoice for bob@example.com")
+        );
+        assert_eq!(ReplyTarget::parse("<@x>"), ReplyTarget::Subject("<@x>"));
+        assert_eq!(
+            ReplyTarget::parse("a\"b@x.com"),
+            ReplyTarget::Subject("a\"b@x.com")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_provider_email_source() {
+        use meepo_scheduler::EmailSource;
+
+        struct FakeMail;
+
+        #[async_trait]
+        impl EmailProvider for FakeMail {
+            async fn read_emails_structured(
+                &self,
+                limit: u64,
+                mailbox: &str,
+                _search: Option<&str>,
+            ) -> Result<Vec<EmailSummary>> {
+                assert_eq!((limit, mailbox), (5, "inbox"));
+                Ok(vec![EmailSummary {
+                    message_id: "<1@x>".to_string(),
+                    preview: "hello".to_string(),
+                    ..Default::default()
+                }])
+            }
+
+            async fn send_email(
+                &self,
+                _to: &str,
+                _subject: &str,
+                _body: &str,
+                _cc: Option<&str>,
+                _in_reply_to: Option<&str>,
+                _attachments: &[PathBuf],
+            ) -> Result<String> {
+                unimplemented!()
+            }
+        }
+
+        let source = ProviderEmailSource::new(Box::new(FakeMail));
+        let emails = source.recent_emails(5, false).await.unwrap();
+        assert_eq!(emails[0].dedup_key(), "<1@x>");
+        assert_eq!(emails[0].body, None);
+        let emails = source.recent_emails(5, true).await.unwrap();
+        assert_eq!(emails[0].body.as_deref(), Some("hello"));
+    }
+
+    #[test]
+    fn test_parse_event_records() {
+        use chrono::{Local, TimeZone, Utc};
+
+        let record = |fields: &str| fields.replace('|', "\u{1f}") + "\u{1e}";
+        let output = record("evt-1|Standup|Work|false|2026 10 16 34200|2026 10 16 35100")
+            + &record("evt-2|Call||false|2026-10-20T12:00:00Z|")
+            + &record("evt-3|Holiday|Home|true|2026 10 19 0|2026 10 20 0");
+        let events = parse_event_records(&output).unwrap();
+
+        let local = |d, h, mi| {
+            Local
+                .with_ymd_and_hms(2026, 10, d, h, mi, 0)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].id, "evt-1");
+        assert_eq!(events[0].calendar.as_deref(), Some("Work"));
+        assert_eq!(events[0].start, local(16, 9, 30));
+        assert_eq!(events[0].end, Some(local(16, 9, 45)));
+        assert!(!events[0].all_day);
+        assert_eq!(
+            events[1].start,
+            Utc.with_ymd_and_hms(2026, 10, 20, 12, 0, 0).unwrap()
+        );
+        assert_eq!(events[1].calendar, None);
+        assert_eq!(events[1].end, None);
+        assert!(events[2].all_day);
+        assert_eq!(events[2].start, local(19, 0, 0));
+
+        assert!(parse_event_records("").unwrap().is_empty());
+        assert!(parse_event_records("Error: Calendar got an error").is_err());
+
+        // One unreadable record fails the read instead of being dropped
+        let bad = output + &record("evt-4|Mystery||false|Friday at 9|");
+        let err = parse_event_records(&bad).unwrap_err().to_string();
+        assert!(err.contains("Mystery"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_provider_calendar_source_uses_structured_reads() {
+        use chrono::{TimeZone, Utc};
+        use meepo_scheduler::CalendarSource;
+
+        struct FakeCalendar {
+            structured: bool,
+        }
+
+        #[async_trait]
+        impl CalendarProvider for FakeCalendar {
+            async fn read_events(&self, _days: u64, _calendar: Option<&str>) -> Result<String> {
+                Ok("Event: Standup\nStart: Freitag, 16. Oktober 2026 um 09:30:00\n---\n".into())
+            }
+
+            async fn read_events_structured(
+                &self,
+                _days: u64,
+                _calendar: Option<&str>,
+            ) -> Result<Vec<EventSummary>> {
+                if !self.structured {
+                    return Err(anyhow::anyhow!("not supported"));
+                }
+                Ok(vec![EventSummary {
+                    id: "evt-1".into(),
+                    title: "Standup".into(),
+                    calendar: Some("Work".into()),
+                    start: Utc.with_ymd_and_hms(2026, 10, 16, 7, 30, 0).unwrap(),
+                    end: None,
+                    all_day: false,
+                }])
+            }
+
+            async fn list_calendars(&self) -> Result<String> {
+                Ok("Work".into())
+            }
+
+            async fn create_event(
+                &self,
+                _summary: &str,
+                _start_time: &str,
+                _duration_minutes: u64,
+                _calendar_name: Option<&str>,
+                _details: &EventDetails,
+            ) -> Result<String> {
+                Err(anyhow::anyhow!("not used"))
+            }
+        }
+
+        let source = ProviderCalendarSource::new(Box::new(FakeCalendar { structured: true }));
+        let events = source.upcoming_events(1, None).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].id, "evt-1");
+        assert_eq!(
+            events[0].start,
+            Utc.with_ymd_and_hms(2026, 10, 16, 7, 30, 0).unwrap()
+        );
+        assert!(!events[0].all_day);
+
+        // The display listing is never scraped as a fallback
+        let source = ProviderCalendarSource::new(Box::new(FakeCalendar { structured: false }));
+        assert!(source.upcoming_events(1, None).await.is_err());
+    }
+
+    #[test]
+    fn test_busy_times() {
+        use chrono::{TimeZone, Utc};
+
+        let at = |d, h, mi| Utc.with_ymd_and_hms(2026, 10, d, h, mi, 0).unwrap();
+        let event = |id: &str, start, end, all_day| EventSummary {
+            id: id.to_string(),
+            title: id.to_string(),
+            calendar: None,
+            start,
+            end,
+            all_day,
+        };
+        let events = vec![
+            event("review", at(20, 14, 0), Some(at(20, 15, 30)), false),
+            event("standup", at(20, 7, 30), None, false),
+            event("holiday", at(21, 0, 0), Some(at(22, 0, 0)), true),
+        ];
+        assert_eq!(
+            busy_times(&events),
+            vec![
+                (at(20, 7, 30), at(20, 8, 30)),
+                (at(20, 14, 0), at(20, 15, 30))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_free_slots() {
+        use chrono::{TimeZone, Utc};
+
+        let at = |d, h, mi| Utc.with_ymd_and_hms(2026, 10, d, h, mi, 0).unwrap();
+        // Friday the 16th, 10:05; the 17th and 18th are a weekend
+        let now = at(16, 10, 5);
+        let busy = vec![
+            (at(16, 11, 0), at(16, 12, 0)),
+            (at(16, 11, 30), at(16, 16, 40)),
+            (at(19, 8, 0), at(19, 9, 45)),
+        ];
+        let hours = WorkingHours::default();
+
+        let slots = free_slots(&busy, now, 30, 4, &hours);
+        let spans: Vec<_> = slots.iter().map(|s| (s.start, s.end)).collect();
+        assert_eq!(
+            spans,
+            vec![
+                (at(16, 10, 15), at(16, 11, 0)),
+                (at(19, 9, 45), at(19, 17, 0)),
+            ]
+        );
+        assert_eq!(slots[0].minutes(), 45);
+
+        // Longer meetings don't fit before 11:00, and weekends can be included
+        let all_week = WorkingHours {
+            skip_weekends: false,
+            ..hours
+        };
+        let slots = free_slots(&busy, now, 60, 3, &all_week);
+        assert_eq!(slots.len(), 2);
+        assert_eq!(slots[0].start, at(17, 9, 0));
+        assert_eq!(slots[1].start, at(18, 9, 0));
+    }
+
+    #[test]
+    fn test_validate_attachments() {
+        let dir = tempfile::tempdir().unwrap();
+        let small = dir.path().join("notes.txt");
+        std::fs::write(&small, "hello").unwrap();
+        assert!(validate_attachments(&[]).is_ok());
+        assert!(validate_attachments(std::slice::from_ref(&small)).is_ok());
+
+        let missing = dir.path().join("missing.pdf");
+        let err = validate_attachments(&[small.clone(), missing]).unwrap_err();
+        assert!(err.to_string().contains("missing.pdf not found"));
+        let err = validate_attachments(&[dir.path().to_path_buf()]).unwrap_err();
+        assert!(err.to_string().ends_with("is not a file"));
+
+        let big = dir.path().join("big.bin");
+        std::fs::File::create(&big)
+            .unwrap()
+            .set_len(MAX_ATTACHMENT_BYTES)
+            .unwrap();
+        let err = validate_attachments(&[big, small]).unwrap_err();
+        assert!(err.to_string().contains("over the 20 MB limit"));
+    }
+
+    #[test]
+    fn test_provider_options() {
+        let options = ProviderOptions::default();
+        assert_eq!(options.script_timeout, DEFAULT_SCRIPT_TIMEOUT);
+        let options = options.with_script_timeout(Duration::from_secs(120));
+        assert_eq!(options.script_timeout, Duration::from_secs(120));
+    }
+
+    #[test]
+    fn test_parse_contact_records() {
+        let output = "Alice Smith\u{1f}alice@work.com\u{1d}alice@home.net\u{1d}\u{1f}555-0100\u{1d}\u{1e}\
+                      Alice Jones\u{1f}\u{1f}\u{1e}\n";
+        let contacts = parse_contact_records(output);
+        assert_eq!(contacts.len(), 2);
+        assert_eq!(contacts[0].name, "Alice Smith");
+        assert_eq!(contacts[0].emails, vec!["alice@work.com", "alice@home.net"]);
+        assert_eq!(contacts[0].phones, vec!["555-0100"]);
+        assert_eq!(contacts[1].name, "Alice Jones");
+        assert!(contacts[1].emails.is_empty());
+        assert!(parse_contact_records("").is_empty());
+    }
+
     #[cfg(target_os = "macos")]
     #[test]
     fn test_macos_providers_create() {
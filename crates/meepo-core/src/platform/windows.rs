@@ -2,10 +2,18 @@
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use std::path::PathBuf;
 use tokio::process::Command;
 use tracing::{debug, warn};
 
-use super::{CalendarProvider, EmailProvider, UiAutomation};
+use super::event_time::resolve_event_time;
+use super::{
+    CalendarProvider, EMAIL_BODY_CHARS, EMAIL_PREVIEW_CHARS, EmailProvider, EmailReadOptions,
+    EmailSummary, EventChanges, EventDetails, EventSummary, MAX_LISTED_ELEMENTS, ReplyTarget,
+    UiAutomation, UiElement, calendar_not_found_error, event_not_found_error, format_email_page,
+    listed_element_types, mime, parse_display_size, parse_email_summary_records,
+    parse_event_records, parse_ui_element_records, validate_attachments, validate_click_point,
+};
 
 /// Sanitize a string for safe use in PowerShell
 /// Escapes backticks, dollar signs, double/single quotes, and control characters
@@ -63,48 +71,123 @@ async fn run_powershell(script: &str) -> Result<String> {
     }
 }
 
+/// Outlook default-folder id and item filter for a read_emails request
+fn outlook_message_filter(mailbox: &str, search: Option<&str>) -> (&'static str, String) {
+    let folder = match mailbox.to_lowercase().as_str() {
+        "inbox" => "6",
+        "sent" => "5",
+        "drafts" => "16",
+        "trash" => "3",
+        _ => "6",
+    };
+    let filter_clause = if let Some(term) = search {
+        let safe_term = sanitize_powershell_string(term);
+        format!(
+            r#"$items = $items | Where-Object {{ $_.Subject -like "*{}*" -or $_.SenderName -like "*{}*" }}"#,
+            safe_term, safe_term
+        )
+    } else {
+        String::new()
+    };
+    (folder, filter_clause)
+}
+
 pub struct WindowsEmailProvider;
 
 #[async_trait]
 impl EmailProvider for WindowsEmailProvider {
-    async fn read_emails(&self, limit: u64, mailbox: &str, search: Option<&str>) -> Result<String> {
+    async fn read_emails_structured(
+        &self,
+        limit: u64,
+        mailbox: &str,
+        search: Option<&str>,
+    ) -> Result<Vec<EmailSummary>> {
         debug!("Reading {} emails from Outlook ({})", limit, mailbox);
-        let folder = match mailbox.to_lowercase().as_str() {
-            "inbox" => "6",
-            "sent" => "5",
-            "drafts" => "16",
-            "trash" => "3",
-            _ => "6",
+        let (folder, filter_clause) = outlook_message_filter(mailbox, search);
+        let max_chars = EMAIL_PREVIEW_CHARS;
+        // PR_INTERNET_MESSAGE_ID; items without one (e.g. drafts) fall back to the EntryID
+        let script = format!(
+            r#"
+try {{
+    $rs = [char]30; $fs = [char]31
+    $outlook = New-Object -ComObject Outlook.Application
+    $namespace = $outlook.GetNamespace("MAPI")
+    $folder = $namespace.GetDefaultFolder({folder})
+    $items = $folder.Items
+    $items.Sort("[ReceivedTime]", $true)
+    {filter_clause}
+    $count = [Math]::Min($items.Count, {limit})
+    $output = ""
+    for ($i = 1; $i -le $count; $i++) {{
+        $msg = $items.Item($i)
+        $body = $msg.Body
+        if ($body.Length -gt {max_chars}) {{ $body = $body.Substring(0, {max_chars}) }}
+        $id = $msg.EntryID
+        try {{ $id = $msg.PropertyAccessor.GetProperty("http://schemas.microsoft.com/mapi/proptag/0x1035001F") }} catch {{ }}
+        $output += "$id$fs$($msg.SenderName) <$($msg.SenderEmailAddress)>$fs$($msg.Subject)$fs$($msg.ReceivedTime)$fs$body$rs"
+    }}
+    Write-Output $output
+}} catch {{
+    Write-Error "Error reading emails: $_"
+}}
+"#
+        );
+        let output = run_powershell(&script).await?;
+        parse_email_summary_records(&output)
+    }
+
+    async fn read_emails_with_options(
+        &self,
+        limit: u64,
+        offset: u64,
+        mailbox: &str,
+        search: Option<&str>,
+        options: EmailReadOptions,
+    ) -> Result<String> {
+        if options.is_plain() {
+            let page = self
+                .read_emails_page(limit, offset, mailbox, search)
+                .await?;
+            return Ok(format_email_page(mailbox, &page));
+        }
+        debug!(
+            "Reading {} emails at offset {} from Outlook ({}) with {:?}",
+            limit, offset, mailbox, options
+        );
+        let (folder, filter_clause) = outlook_message_filter(mailbox, search);
+        let html_clause = if options.prefer_html {
+            "$html = $msg.HTMLBody; if ($html.Length -gt 262144) { $html = $html.Substring(0, 262144) }"
+        } else {
+            "$html = \"\""
         };
-        let filter_clause = if let Some(term) = search {
-            let safe_term = sanitize_powershell_string(term);
-            format!(
-                r#"$items = $items | Where-Object {{ $_.Subject -like "*{}*" -or $_.SenderName -like "*{}*" }}"#,
-                safe_term, safe_term
-            )
+        // Outlook doesn't expose attachment MIME types, so only name and size are listed
+        let attachments_clause = if options.include_attachments {
+            "foreach ($a in $msg.Attachments) { $attachments += \"$($a.FileName)$af$af$($a.Size)$asep\" }"
         } else {
-            String::new()
+            ""
         };
+        let max_chars = mime::RICH_BODY_CHARS;
+        let (first, end) = (offset.saturating_add(1), offset.saturating_add(limit));
         let script = format!(
             r#"
 try {{
+    $rs = [char]30; $fs = [char]31; $asep = [char]29; $af = [char]28
     $outlook = New-Object -ComObject Outlook.Application
     $namespace = $outlook.GetNamespace("MAPI")
     $folder = $namespace.GetDefaultFolder({folder})
     $items = $folder.Items
     $items.Sort("[ReceivedTime]", $true)
     {filter_clause}
-    $count = [Math]::Min($items.Count, {limit})
+    $count = [Math]::Min($items.Count, {end})
     $output = ""
-    for ($i = 1; $i -le $count; $i++) {{
+    for ($i = {first}; $i -le $count; $i++) {{
         $msg = $items.Item($i)
         $body = $msg.Body
-        if ($body.Length -gt 500) {{ $body = $body.Substring(0, 500) }}
-        $output += "From: $($msg.SenderName) <$($msg.SenderEmailAddress)>`n"
-        $output += "Subject: $($msg.Subject)`n"
-        $output += "Date: $($msg.ReceivedTime)`n"
-        $output += "Preview: $body`n"
-        $output += "---`n"
+        if ($body.Length -gt {max_chars}) {{ $body = $body.Substring(0, {max_chars}) }}
+        {html_clause}
+        $attachments = ""
+        {attachments_clause}
+        $output += "$($msg.SenderName) <$($msg.SenderEmailAddress)>$fs$($msg.Subject)$fs$($msg.ReceivedTime)$fs$body$fs$html$fs$attachments$rs"
     }}
     Write-Output $output
 }} catch {{
@@ -112,7 +195,42 @@ try {{
 }}
 "#
         );
-        run_powershell(&script).await
+        let output = run_powershell(&script).await?;
+        Ok(mime::format_script_records(
+            &output,
+            false,
+            options.include_attachments,
+        ))
+    }
+
+    async fn read_email_body(&self, message_id: &str, mailbox: &str) -> Result<String> {
+        debug!(
+            "Reading the body of {} from Outlook ({})",
+            message_id, mailbox
+        );
+        let (folder, _) = outlook_message_filter(mailbox, None);
+        let safe_id = sanitize_powershell_string(message_id);
+        let max_chars = EMAIL_BODY_CHARS;
+        // Ids come from PR_INTERNET_MESSAGE_ID, or the EntryID for items without one
+        let script = format!(
+            r#"
+try {{
+    $outlook = New-Object -ComObject Outlook.Application
+    $namespace = $outlook.GetNamespace("MAPI")
+    $folder = $namespace.GetDefaultFolder({folder})
+    $msg = $folder.Items.Find("@SQL=""http://schemas.microsoft.com/mapi/proptag/0x1035001F"" = '{safe_id}'")
+    if ($msg -eq $null) {{ try {{ $msg = $namespace.GetItemFromID("{safe_id}") }} catch {{ }} }}
+    if ($msg -eq $null) {{ throw "No message with Message-ID {safe_id}" }}
+    $body = $msg.Body
+    if ($body.Length -gt {max_chars}) {{ $body = $body.Substring(0, {max_chars}) }}
+    Write-Output $body
+}} catch {{
+    Write-Error "Error reading email: $_"
+}}
+"#
+        );
+        let output = run_powershell(&script).await?;
+        Ok(output.trim().to_string())
     }
 
     async fn send_email(
@@ -122,49 +240,141 @@ try {{
         body: &str,
         cc: Option<&str>,
         in_reply_to: Option<&str>,
+        attachments: &[PathBuf],
     ) -> Result<String> {
-        let safe_to = sanitize_powershell_string(to);
-        let safe_subject = sanitize_powershell_string(subject);
-        let safe_body = sanitize_powershell_string(body);
-        let script = if let Some(reply_subject) = in_reply_to {
-            let safe_reply = sanitize_powershell_string(reply_subject);
-            debug!("Replying to email with subject: {}", reply_subject);
-            format!(
-                r#"
+        validate_attachments(attachments)?;
+        let script = compose_email_script(to, subject, body, cc, in_reply_to, attachments, true);
+        run_powershell(&script).await
+    }
+
+    async fn create_draft(
+        &self,
+        to: &str,
+        subject: &str,
+        body: &str,
+        cc: Option<&str>,
+        in_reply_to: Option<&str>,
+        attachments: &[PathBuf],
+    ) -> Result<String> {
+        validate_attachments(attachments)?;
+        let script = compose_email_script(to, subject, body, cc, in_reply_to, attachments, false);
+        run_powershell(&script).await
+    }
+}
+
+/// Script that composes a message in Outlook and either sends it or, when
+/// `deliver` is false, saves it to Drafts
+fn compose_email_script(
+    to: &str,
+    subject: &str,
+    body: &str,
+    cc: Option<&str>,
+    in_reply_to: Option<&str>,
+    attachments: &[PathBuf],
+    deliver: bool,
+) -> String {
+    // One `Attachments.Add` line per file for the item in `$item`
+    let attach = |item: &str, indent: usize| -> String {
+        attachments
+            .iter()
+            .map(|path| {
+                format!(
+                    "{:indent$}${}.Attachments.Add(\"{}\") | Out-Null\n",
+                    "",
+                    item,
+                    sanitize_powershell_string(&path.to_string_lossy())
+                )
+            })
+            .collect()
+    };
+    let (verb, threaded, unthreaded, done) = if deliver {
+        (
+            "Send",
+            "Reply sent (threaded)",
+            "Email sent (no original found for threading)",
+            "Email sent successfully",
+        )
+    } else {
+        (
+            "Save",
+            "Draft reply saved (threaded)",
+            "Draft saved (no original found for threading)",
+            "Draft saved to Drafts (not sent)",
+        )
+    };
+    let safe_to = sanitize_powershell_string(to);
+    let safe_subject = sanitize_powershell_string(subject);
+    let safe_body = sanitize_powershell_string(body);
+    if let Some(in_reply_to) = in_reply_to {
+        let find_original = match ReplyTarget::parse(in_reply_to) {
+            ReplyTarget::MessageId(id) => {
+                debug!("Replying to email with Message-ID: {}", id);
+                // PR_INTERNET_MESSAGE_ID keeps the angle brackets. Check the inbox first,
+                // then walk every folder of every store.
+                format!(
+                    r#"$filter = '@SQL="http://schemas.microsoft.com/mapi/proptag/0x1035001F" = ''<{id}>'''
+    function Find-Original($folder) {{
+        try {{ $hit = $folder.Items.Find($filter) }} catch {{ $hit = $null }}
+        if ($hit -ne $null) {{ return $hit }}
+        foreach ($sub in $folder.Folders) {{
+            $hit = Find-Original $sub
+            if ($hit -ne $null) {{ return $hit }}
+        }}
+        return $null
+    }}
+    $found = $inbox.Items.Find($filter)
+    if ($found -eq $null) {{
+        foreach ($store in $namespace.Stores) {{
+            $found = Find-Original $store.GetRootFolder()
+            if ($found -ne $null) {{ break }}
+        }}
+    }}"#
+                )
+            }
+            ReplyTarget::Subject(reply_subject) => {
+                debug!("Replying to email with subject: {}", reply_subject);
+                let safe_reply = sanitize_powershell_string(reply_subject);
+                format!("$found = $inbox.Items.Find(\"[Subject] = '{safe_reply}'\")")
+            }
+        };
+        let reply_attachments = attach("reply", 8);
+        let mail_attachments = attach("mail", 8);
+        format!(
+            r#"
 try {{
     $outlook = New-Object -ComObject Outlook.Application
     $namespace = $outlook.GetNamespace("MAPI")
     $inbox = $namespace.GetDefaultFolder(6)
-    $items = $inbox.Items
-    $found = $items.Find("[Subject] = '{safe_reply}'")
+    {find_original}
     if ($found -ne $null) {{
         $reply = $found.Reply()
         $reply.Body = "{safe_body}" + "`n`n" + $reply.Body
-        $reply.Send()
-        Write-Output "Reply sent (threaded)"
+{reply_attachments}        $reply.{verb}()
+        Write-Output "{threaded}"
     }} else {{
         $mail = $outlook.CreateItem(0)
         $mail.To = "{safe_to}"
         $mail.Subject = "{safe_subject}"
         $mail.Body = "{safe_body}"
-        $mail.Send()
-        Write-Output "Email sent (no original found for threading)"
+{mail_attachments}        $mail.{verb}()
+        Write-Output "{unthreaded}"
     }}
 }} catch {{
     Write-Error "Error sending email: $_"
 }}
 "#
-            )
+        )
+    } else {
+        debug!("Composing new email to: {}", to);
+        let cc_line = if let Some(cc_addr) = cc {
+            let safe_cc = sanitize_powershell_string(cc_addr);
+            format!("    $mail.CC = \"{safe_cc}\"")
         } else {
-            debug!("Sending new email to: {}", to);
-            let cc_line = if let Some(cc_addr) = cc {
-                let safe_cc = sanitize_powershell_string(cc_addr);
-                format!("    $mail.CC = \"{safe_cc}\"")
-            } else {
-                String::new()
-            };
-            format!(
-                r#"
+            String::new()
+        };
+        let mail_attachments = attach("mail", 4);
+        format!(
+            r#"
 try {{
     $outlook = New-Object -ComObject Outlook.Application
     $mail = $outlook.CreateItem(0)
@@ -172,52 +382,123 @@ try {{
     $mail.Subject = "{safe_subject}"
     $mail.Body = "{safe_body}"
 {cc_line}
-    $mail.Send()
-    Write-Output "Email sent successfully"
+{mail_attachments}    $mail.{verb}()
+    Write-Output "{done}"
 }} catch {{
     Write-Error "Error sending email: $_"
 }}
 "#
-            )
-        };
-        run_powershell(&script).await
+        )
     }
 }
 
+/// An event time resolved in Rust, as local ISO 8601 for `[DateTime]::Parse`,
+/// which would otherwise read free text by the system locale
+fn powershell_time(input: &str) -> Result<String> {
+    Ok(resolve_event_time(input)?
+        .format("%Y-%m-%dT%H:%M:%S")
+        .to_string())
+}
+
 pub struct WindowsCalendarProvider;
 
 #[async_trait]
 impl CalendarProvider for WindowsCalendarProvider {
-    async fn read_events(&self, days_ahead: u64) -> Result<String> {
+    async fn read_events(&self, days_ahead: u64, calendar_name: Option<&str>) -> Result<String> {
         debug!(
-            "Reading calendar events for next {} days from Outlook",
-            days_ahead
+            "Reading calendar events for next {} days from Outlook ({})",
+            days_ahead,
+            calendar_name.unwrap_or("all calendars")
         );
         let script = format!(
             r#"
 try {{
-    $outlook = New-Object -ComObject Outlook.Application
-    $namespace = $outlook.GetNamespace("MAPI")
-    $calendar = $namespace.GetDefaultFolder(9)
-    $items = $calendar.Items
-    $items.IncludeRecurrences = $true
-    $items.Sort("[Start]")
+    {select}
     $start = (Get-Date).ToString("g")
     $end = (Get-Date).AddDays({days_ahead}).ToString("g")
     $restrict = "[Start] >= '$start' AND [Start] <= '$end'"
-    $filtered = $items.Restrict($restrict)
     $output = ""
-    foreach ($evt in $filtered) {{
-        $output += "Event: $($evt.Subject)`n"
-        $output += "Start: $($evt.Start)`n"
-        $output += "End: $($evt.End)`n"
-        $output += "---`n"
+    foreach ($calendar in $targets) {{
+        $items = $calendar.Items
+        $items.IncludeRecurrences = $true
+        $items.Sort("[Start]")
+        $filtered = $items.Restrict($restrict)
+        foreach ($evt in $filtered) {{
+            $output += "Calendar: $($calendar.Name)`n"
+            $output += "Event: $($evt.Subject)`n"
+            $output += "ID: $($evt.EntryID)`n"
+            $output += "Start: $($evt.Start)`n"
+            $output += "End: $($evt.End)`n"
+            $output += "---`n"
+        }}
     }}
     Write-Output $output
 }} catch {{
     Write-Error "Error reading calendar: $_"
 }}
-"#
+"#,
+            select = select_calendars_script(calendar_name),
+        );
+        let output = run_powershell(&script).await?;
+        calendar_result(calendar_name, output)
+    }
+
+    /// Times come from Outlook's StartUTC/EndUTC in an invariant format, so they
+    /// don't depend on the system's regional settings
+    async fn read_events_structured(
+        &self,
+        days_ahead: u64,
+        calendar_name: Option<&str>,
+    ) -> Result<Vec<EventSummary>> {
+        debug!(
+            "Reading structured calendar events for next {} days from Outlook ({})",
+            days_ahead,
+            calendar_name.unwrap_or("all calendars")
+        );
+        let script = format!(
+            r#"
+try {{
+    {select}
+    $rs = [char]30; $fs = [char]31
+    $utc = "yyyy-MM-dd'T'HH:mm:ss'Z'"
+    $invariant = [Globalization.CultureInfo]::InvariantCulture
+    $start = (Get-Date).ToString("g")
+    $end = (Get-Date).AddDays({days_ahead}).ToString("g")
+    $restrict = "[Start] >= '$start' AND [Start] <= '$end'"
+    $output = ""
+    foreach ($calendar in $targets) {{
+        $items = $calendar.Items
+        $items.IncludeRecurrences = $true
+        $items.Sort("[Start]")
+        $filtered = $items.Restrict($restrict)
+        foreach ($evt in $filtered) {{
+            $allDay = if ($evt.AllDayEvent) {{ "true" }} else {{ "false" }}
+            $output += "$($evt.EntryID)$fs$($evt.Subject)$fs$($calendar.Name)$fs$allDay$fs$($evt.StartUTC.ToString($utc, $invariant))$fs$($evt.EndUTC.ToString($utc, $invariant))$rs"
+        }}
+    }}
+    Write-Output $output
+}} catch {{
+    Write-Error "Error reading calendar: $_"
+}}
+"#,
+            select = select_calendars_script(calendar_name),
+        );
+        let output = run_powershell(&script).await?;
+        parse_event_records(&calendar_result(calendar_name, output)?)
+    }
+
+    async fn list_calendars(&self) -> Result<String> {
+        debug!("Listing Outlook calendars");
+        let script = format!(
+            r#"
+try {{
+    {select}
+    Write-Output (($calendars | ForEach-Object {{ $_.Name }}) -join "`n")
+}} catch {{
+    Write-Error "Error listing calendars: $_"
+}}
+"#,
+            select = select_calendars_script(None),
         );
         run_powershell(&script).await
     }
@@ -227,29 +508,255 @@ try {{
         summary: &str,
         start_time: &str,
         duration_minutes: u64,
+        calendar_name: Option<&str>,
+        details: &EventDetails,
     ) -> Result<String> {
         debug!("Creating calendar event: {}", summary);
+        details.validate()?;
         let safe_summary = sanitize_powershell_string(summary);
-        let safe_start = sanitize_powershell_string(start_time);
+        let start = powershell_time(start_time)?;
+        let select = select_calendars_script(calendar_name);
+        // Without a name, the event goes to the default calendar
+        let target = if calendar_name.is_some() {
+            "$targets[0]"
+        } else {
+            "$default"
+        };
+        let mut fields = vec![match &details.end_time {
+            Some(end) => format!(
+                r#"$appt.End = [DateTime]::Parse("{}")
+    if ($appt.End -le $appt.Start) {{ throw "the end time must be after the start time" }}"#,
+                powershell_time(end)?
+            ),
+            None => format!("$appt.Duration = {}", duration_minutes),
+        }];
+        if let Some(location) = &details.location {
+            fields.push(format!(
+                r#"$appt.Location = "{}""#,
+                sanitize_powershell_string(location)
+            ));
+        }
+        if let Some(notes) = &details.notes {
+            fields.push(format!(
+                r#"$appt.Body = "{}""#,
+                sanitize_powershell_string(notes)
+            ));
+        }
+        // A meeting with recipients is sent as invitations rather than just saved
+        let save = if details.attendees.is_empty() {
+            "$appt.Save()".to_string()
+        } else {
+            let recipients: Vec<String> = details
+                .attendees
+                .iter()
+                .map(|address| {
+                    format!(
+                        r#"[void]$appt.Recipients.Add("{}")"#,
+                        sanitize_powershell_string(address)
+                    )
+                })
+                .collect();
+            format!(
+                "$appt.MeetingStatus = 1\n    {}\n    [void]$appt.Recipients.ResolveAll()\n    $appt.Save()\n    $appt.Send()",
+                recipients.join("\n    ")
+            )
+        };
+        let fields = fields.join("\n    ");
         let script = format!(
             r#"
 try {{
-    $outlook = New-Object -ComObject Outlook.Application
-    $appt = $outlook.CreateItem(1)
+    {select}
+    $appt = {target}.Items.Add(1)
     $appt.Subject = "{safe_summary}"
-    $appt.Start = [DateTime]::Parse("{safe_start}")
-    $appt.Duration = {duration_minutes}
-    $appt.Save()
-    Write-Output "Event created successfully"
+    $appt.Start = [DateTime]::Parse("{start}")
+    {fields}
+    {save}
+    Write-Output "Event created successfully in calendar: $({target}.Name)"
 }} catch {{
     Write-Error "Error creating event: $_"
 }}
 "#
         );
-        run_powershell(&script).await
+        let output = run_powershell(&script).await?;
+        calendar_result(calendar_name, output)
+    }
+
+    async fn update_event(&self, event_id: &str, changes: &EventChanges) -> Result<String> {
+        debug!("Updating calendar event: {}", event_id);
+        if changes.is_empty() {
+            return Err(anyhow::anyhow!("No changes given for event '{}'", event_id));
+        }
+        let mut edits = Vec::new();
+        if let Some(summary) = &changes.summary {
+            edits.push(format!(
+                r#"$appt.Subject = "{}""#,
+                sanitize_powershell_string(summary)
+            ));
+        }
+        if let Some(location) = &changes.location {
+            edits.push(format!(
+                r#"$appt.Location = "{}""#,
+                sanitize_powershell_string(location)
+            ));
+        }
+        if let Some(notes) = &changes.notes {
+            edits.push(format!(
+                r#"$appt.Body = "{}""#,
+                sanitize_powershell_string(notes)
+            ));
+        }
+        // Outlook keeps the duration when Start moves
+        if let Some(start) = &changes.start_time {
+            edits.push(format!(
+                r#"$appt.Start = [DateTime]::Parse("{}")"#,
+                powershell_time(start)?
+            ));
+        }
+        if let Some(minutes) = changes.duration_minutes {
+            edits.push(format!("$appt.Duration = {}", minutes));
+        }
+
+        let script = format!(
+            r#"
+try {{
+    {find}
+    {edits}
+    $appt.Save()
+    Write-Output "Event updated: $($appt.Subject) ($($appt.Start))"
+}} catch {{
+    Write-Error "Error updating event: $_"
+}}
+"#,
+            find = find_appointment_script(event_id),
+            edits = edits.join("\n    "),
+        );
+        let output = run_powershell(&script).await?;
+        appointment_result(event_id, output)
+    }
+
+    async fn delete_event(&self, event_id: &str) -> Result<String> {
+        debug!("Deleting calendar event: {}", event_id);
+        let script = format!(
+            r#"
+try {{
+    {find}
+    $subject = $appt.Subject
+    $appt.Delete()
+    Write-Output "Event deleted: $subject"
+}} catch {{
+    Write-Error "Error deleting event: $_"
+}}
+"#,
+            find = find_appointment_script(event_id),
+        );
+        let output = run_powershell(&script).await?;
+        appointment_result(event_id, output)
     }
 }
 
+/// Prefix of Outlook script output when the named calendar doesn't exist
+/// (followed by the available names, one per line)
+const CALENDAR_NOT_FOUND: &str = "CALENDAR_NOT_FOUND:";
+
+/// PowerShell that sets `$default` to the default calendar folder, `$calendars` to it
+/// and its subfolders, and `$targets` to the calendar named `calendar_name` (all of
+/// them when `None`), printing [`CALENDAR_NOT_FOUND`] and stopping if there's no match
+fn select_calendars_script(calendar_name: Option<&str>) -> String {
+    let mut script = r#"$outlook = New-Object -ComObject Outlook.Application
+    $namespace = $outlook.GetNamespace("MAPI")
+    $default = $namespace.GetDefaultFolder(9)
+    $calendars = @($default) + @($default.Folders)"#
+        .to_string();
+    match calendar_name {
+        None => script.push_str("\n    $targets = $calendars"),
+        Some(name) => script.push_str(&format!(
+            r#"
+    $targets = @($calendars | Where-Object {{ $_.Name -eq "{}" }})
+    if ($targets.Count -eq 0) {{
+        Write-Output ("{}`n" + (($calendars | ForEach-Object {{ $_.Name }}) -join "`n"))
+        return
+    }}"#,
+            sanitize_powershell_string(name),
+            CALENDAR_NOT_FOUND
+        )),
+    }
+    script
+}
+
+/// Turn the unknown-calendar marker from an Outlook script into a readable error
+fn calendar_result(calendar_name: Option<&str>, output: String) -> Result<String> {
+    match output.trim().strip_prefix(CALENDAR_NOT_FOUND) {
+        Some(names) => {
+            let available: Vec<String> = names
+                .lines()
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(String::from)
+                .collect();
+            Err(calendar_not_found_error(
+                calendar_name.unwrap_or_default(),
+                &available,
+            ))
+        }
+        None => Ok(output),
+    }
+}
+
+/// Output of the Outlook scripts when the targeted appointment doesn't exist
+const EVENT_NOT_FOUND: &str = "EVENT_NOT_FOUND";
+
+/// PowerShell that sets `$appt` to the Outlook item with EntryID `event_id`,
+/// or prints [`EVENT_NOT_FOUND`] and stops
+fn find_appointment_script(event_id: &str) -> String {
+    format!(
+        r#"$outlook = New-Object -ComObject Outlook.Application
+    $namespace = $outlook.GetNamespace("MAPI")
+    $appt = $null
+    try {{ $appt = $namespace.GetItemFromID("{}") }} catch {{ }}
+    if ($appt -eq $null) {{ Write-Output "{}"; return }}"#,
+        sanitize_powershell_string(event_id),
+        EVENT_NOT_FOUND
+    )
+}
+
+/// Turn the not-found marker from an appointment script into a readable error
+fn appointment_result(event_id: &str, output: String) -> Result<String> {
+    if output.trim() == EVENT_NOT_FOUND {
+        Err(event_not_found_error(event_id))
+    } else {
+        Ok(output)
+    }
+}
+
+/// UI Automation control types and the element type each one is listed as
+const CONTROL_TYPE_ROLES: &[(&str, &str)] = &[
+    ("Button", "button"),
+    ("CheckBox", "checkbox"),
+    ("RadioButton", "radio button"),
+    ("Edit", "text field"),
+    ("Document", "text area"),
+    ("ComboBox", "combo box"),
+    ("MenuItem", "menu item"),
+    ("SplitButton", "menu button"),
+    ("Slider", "slider"),
+    ("Tab", "tab group"),
+    ("Table", "table"),
+    ("DataGrid", "table"),
+    ("Tree", "outline"),
+    ("List", "list"),
+    ("Group", "group"),
+    ("Window", "window"),
+    ("ToolBar", "toolbar"),
+    ("Text", "static text"),
+    ("Image", "image"),
+    ("Hyperlink", "link"),
+    ("DataItem", "row"),
+    ("ListItem", "row"),
+    ("TreeItem", "row"),
+    ("HeaderItem", "column"),
+    ("Spinner", "incrementor"),
+];
+
 pub struct WindowsUiAutomation;
 
 #[async_trait]
@@ -272,6 +779,59 @@ try {
         run_powershell(script).await
     }
 
+    async fn list_elements(&self, element_type: Option<&str>) -> Result<Vec<UiElement>> {
+        let wanted = listed_element_types(element_type)?;
+        debug!("Listing {:?} elements via UI Automation", wanted);
+        let roles = CONTROL_TYPE_ROLES
+            .iter()
+            .map(|(control_type, role)| format!("\"{}\" = \"{}\"", control_type, role))
+            .collect::<Vec<_>>()
+            .join("; ");
+        let wanted = wanted
+            .iter()
+            .map(|t| format!("\"{}\"", t))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let max = MAX_LISTED_ELEMENTS;
+        // The focused element's top-level ancestor is the foreground window
+        let script = format!(
+            r#"
+Add-Type -AssemblyName UIAutomationClient
+Add-Type -AssemblyName UIAutomationTypes
+try {{
+    $rs = [char]30; $fs = [char]31
+    $rootElement = [System.Windows.Automation.AutomationElement]::RootElement
+    $walker = [System.Windows.Automation.TreeWalker]::ControlViewWalker
+    $window = [System.Windows.Automation.AutomationElement]::FocusedElement
+    $parent = $walker.GetParent($window)
+    while ($parent -ne $null -and -not $parent.Equals($rootElement)) {{
+        $window = $parent
+        $parent = $walker.GetParent($window)
+    }}
+    $roles = @{{ {roles} }}
+    $wanted = @({wanted})
+    $output = ""
+    $found = 0
+    $all = $window.FindAll([System.Windows.Automation.TreeScope]::Descendants, [System.Windows.Automation.Condition]::TrueCondition)
+    foreach ($e in $all) {{
+        $controlType = $e.Current.ControlType.ProgrammaticName -replace '^ControlType\.', ''
+        $role = $roles[$controlType]
+        if ($role -and $wanted -contains $role) {{
+            $output += "$role$fs$($e.Current.Name)$fs$($e.Current.IsEnabled)$rs"
+            $found++
+            if ($found -ge {max}) {{ break }}
+        }}
+    }}
+    Write-Output $output
+}} catch {{
+    Write-Error "Error listing elements: $_"
+}}
+"#
+        );
+        let output = run_powershell(&script).await?;
+        parse_ui_element_records(&output)
+    }
+
     async fn click_element(&self, element_name: &str, element_type: &str) -> Result<String> {
         debug!("Clicking {} element: {}", element_type, element_name);
         let safe_name = sanitize_powershell_string(element_name);
@@ -300,6 +860,41 @@ try {{
         run_powershell(&script).await
     }
 
+    async fn click_at(&self, x: i32, y: i32) -> Result<String> {
+        let size = run_powershell(
+            r#"
+Add-Type -AssemblyName System.Windows.Forms
+$bounds = [System.Windows.Forms.Screen]::PrimaryScreen.Bounds
+Write-Output "$($bounds.Width),$($bounds.Height)"
+"#,
+        )
+        .await?;
+        validate_click_point(x, y, parse_display_size(&size)?)?;
+        debug!("Clicking at ({}, {})", x, y);
+        let script = format!(
+            r#"
+Add-Type @'
+using System;
+using System.Runtime.InteropServices;
+public static class MeepoMouse {{
+    [DllImport("user32.dll")] public static extern bool SetCursorPos(int x, int y);
+    [DllImport("user32.dll")] public static extern void mouse_event(uint flags, uint dx, uint dy, uint data, UIntPtr extra);
+}}
+'@
+try {{
+    [MeepoMouse]::SetCursorPos({x}, {y}) | Out-Null
+    # MOUSEEVENTF_LEFTDOWN, then MOUSEEVENTF_LEFTUP
+    [MeepoMouse]::mouse_event(0x0002, 0, 0, 0, [UIntPtr]::Zero)
+    [MeepoMouse]::mouse_event(0x0004, 0, 0, 0, [UIntPtr]::Zero)
+    Write-Output "Clicked at ({x}, {y})"
+}} catch {{
+    Write-Error "Error clicking at ({x}, {y}): $_"
+}}
+"#
+        );
+        run_powershell(&script).await
+    }
+
     async fn type_text(&self, text: &str) -> Result<String> {
         debug!("Typing text ({} chars)", text.len());
         // First escape SendKeys meta-characters, then escape for PowerShell string embedding
@@ -348,6 +943,15 @@ mod tests {
         assert!(!safe.contains("\";"));
     }
 
+    #[test]
+    fn test_control_type_roles_are_valid_element_types() {
+        assert!(
+            CONTROL_TYPE_ROLES
+                .iter()
+                .all(|(_, role)| crate::platform::VALID_ELEMENT_TYPES.contains(role))
+        );
+    }
+
     #[test]
     fn test_sanitize_sendkeys_string() {
         // SendKeys meta-characters should be wrapped in braces
@@ -0,0 +1,80 @@
+//! Safe mode — a global observe-only switch
+//!
+//! When enabled, only tools marked read-only (`ToolHandler::read_only`) run;
+//! every other tool returns a "[SAFE MODE] would have..." message instead of
+//! acting, and channel sends are logged instead of
+//! delivered. Intended for first-time setup and debugging (`meepo start --safe`).
+
+use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock};
+
+static GLOBAL: LazyLock<SafeMode> = LazyLock::new(SafeMode::new);
+
+/// Shared safe-mode flag. Clones observe the same state.
+#[derive(Debug, Clone, Default)]
+pub struct SafeMode {
+    enabled: Arc<AtomicBool>,
+}
+
+impl SafeMode {
+    /// Create a new, disabled flag independent of the global one
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The process-wide flag consulted by tool registries and the message bus
+    pub fn global() -> &'static SafeMode {
+        &GLOBAL
+    }
+
+    pub fn enable(&self) {
+        self.enabled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn disable(&self) {
+        self.enabled.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+}
+
+/// Whether the process-wide safe mode is enabled
+pub fn is_enabled() -> bool {
+    SafeMode::global().is_enabled()
+}
+
+/// Build the message returned in place of running a tool that isn't read-only
+pub fn would_have(tool_name: &str, input: &Value) -> String {
+    format!(
+        "[SAFE MODE] would have run `{}` with input: {}. No action was taken.",
+        tool_name, input
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clones_share_state() {
+        let mode = SafeMode::new();
+        let clone = mode.clone();
+        assert!(!clone.is_enabled());
+
+        mode.enable();
+        assert!(clone.is_enabled());
+
+        clone.disable();
+        assert!(!mode.is_enabled());
+    }
+
+    #[test]
+    fn test_would_have_message() {
+        let msg = would_have("open_app", &serde_json::json!({"app_name": "Safari"}));
+        assert!(msg.starts_with("[SAFE MODE] would have run `open_app`"));
+        assert!(msg.contains("Safari"));
+    }
+}
@@ -0,0 +1,212 @@
+//! Unicode-safe string helpers
+//!
+//! Slicing a `&str` by byte index (`&s[..n]`, `String::truncate`) panics when
+//! `n` lands inside a multibyte character, which non-ASCII email bodies, CJK
+//! text, and emoji hit routinely. Output capping should go through these.
+//! Also home to [`html_to_text`] for turning HTML bodies into readable text,
+//! and [`normalize_words`] for matching keywords on word boundaries.
+
+/// The longest prefix of `s` containing at most `max_chars` characters.
+///
+/// Never splits a character, so it is safe on any input.
+pub fn truncate_chars(s: &str, max_chars: usize) -> &str {
+    match s.char_indices().nth(max_chars) {
+        Some((end, _)) => &s[..end],
+        None => s,
+    }
+}
+
+/// Truncate `s` in place to at most `max_chars` characters.
+///
+/// Returns whether anything was cut, so callers can append a marker.
+pub fn truncate_string(s: &mut String, max_chars: usize) -> bool {
+    let len = truncate_chars(s, max_chars).len();
+    let truncated = len < s.len();
+    s.truncate(len);
+    truncated
+}
+
+/// Lowercase alphanumeric words joined by single spaces.
+///
+/// Punctuation and hyphens become word breaks, so "Check-in:" and "check in"
+/// compare equal; pad with spaces to test for a whole-word phrase.
+pub fn normalize_words(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Readable plain text from an HTML document.
+///
+/// Drops tags along with `<script>`, `<style>` and `<head>` content, turns
+/// block elements into line breaks, decodes entities, and collapses runs of
+/// whitespace. Good enough for email bodies; not a full HTML parser.
+pub fn html_to_text(html: &str) -> String {
+    const SKIPPED: [&str; 4] = ["script", "style", "head", "title"];
+    const BLOCKS: [&str; 17] = [
+        "br",
+        "p",
+        "div",
+        "tr",
+        "table",
+        "ul",
+        "ol",
+        "h1",
+        "h2",
+        "h3",
+        "h4",
+        "h5",
+        "h6",
+        "hr",
+        "blockquote",
+        "pre",
+        "section",
+    ];
+
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    // Source line breaks are just whitespace in HTML; only tags break lines
+    let push_text = |text: &mut String, raw: &str| {
+        text.push_str(&decode_html_entities(raw).replace(['\r', '\n'], " "));
+    };
+    while let Some(open) = rest.find('<') {
+        push_text(&mut text, &rest[..open]);
+        rest = &rest[open..];
+        let Some(close) = rest.find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = &rest[1..close];
+        rest = &rest[close + 1..];
+
+        let closing = tag.starts_with('/');
+        let name: String = tag
+            .trim_start_matches('/')
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+
+        if !closing && SKIPPED.contains(&name.as_str()) {
+            // Skip to the matching close tag (case-insensitive)
+            let end_tag = format!("</{}", name);
+            match rest.to_ascii_lowercase().find(&end_tag) {
+                Some(end) => {
+                    rest = &rest[end..];
+                    rest = rest.find('>').map_or("", |gt| &rest[gt + 1..]);
+                }
+                None => rest = "",
+            }
+        } else if name == "li" && !closing {
+            text.push_str("\n- ");
+        } else if BLOCKS.contains(&name.as_str()) || name == "li" {
+            text.push('\n');
+        } else if name == "td" || name == "th" {
+            text.push(' ');
+        }
+    }
+    push_text(&mut text, rest);
+
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Decode named (common) and numeric HTML character references
+fn decode_html_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').filter(|&semi| semi <= 10).and_then(|semi| {
+            let entity = &rest[1..semi];
+            let c = match entity {
+                "amp" => '&',
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                    .and_then(char::from_u32)?,
+            };
+            Some((c, semi))
+        });
+        match decoded {
+            Some((c, semi)) => {
+                out.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_chars_ascii() {
+        assert_eq!(truncate_chars("hello", 10), "hello");
+        assert_eq!(truncate_chars("hello world", 5), "hello");
+        assert_eq!(truncate_chars("hello", 0), "");
+        assert_eq!(truncate_chars("", 3), "");
+    }
+
+    #[test]
+    fn test_truncate_chars_multibyte_boundary() {
+        // Each of these is more than one byte, so a byte cap of 5 would panic or split
+        assert_eq!(truncate_chars("日本語のテキスト", 3), "日本語");
+        assert_eq!(truncate_chars("héllo wörld", 5), "héllo");
+        assert_eq!(truncate_chars("👋🌍🎉✨", 2), "👋🌍");
+        assert_eq!(truncate_chars("ab😀cd", 3), "ab😀");
+        assert_eq!(truncate_chars("ab😀cd", 2), "ab");
+        assert_eq!(truncate_chars("日本語", 3), "日本語");
+    }
+
+    #[test]
+    fn test_truncate_string_in_place() {
+        let mut s = "メールの本文 📧 with trailing text".to_string();
+        assert!(truncate_string(&mut s, 8));
+        assert_eq!(s, "メールの本文 📧");
+
+        let mut short = "ok".to_string();
+        assert!(!truncate_string(&mut short, 10));
+        assert_eq!(short, "ok");
+    }
+
+    #[test]
+    fn test_normalize_words() {
+        assert_eq!(normalize_words("Check-in:  3:00 PM"), "check in 3 00 pm");
+        assert_eq!(normalize_words("Zürich—Genève!"), "zürich genève");
+        assert_eq!(normalize_words(" -- "), "");
+    }
+
+    #[test]
+    fn test_html_to_text() {
+        let html = r#"<html><head><title>Newsletter</title><style>p { color: red }</style></head>
+<body><p>Hello&nbsp;<b>Jörg</b>,</p><p>Your order   ships
+today &amp; arrives soon.</p><ul><li>Item one</li><li>Item &#x32;</li></ul>
+<script>alert("x")</script><div>Fish &lt;3 chips &unknown; end</div><br>Bye</body></html>"#;
+        assert_eq!(
+            html_to_text(html),
+            "Hello Jörg,\nYour order ships today & arrives soon.\n- Item one\n- Item 2\nFish <3 chips &unknown; end\nBye"
+        );
+        assert_eq!(html_to_text("plain <unterminated"), "plain");
+    }
+}
@@ -103,7 +103,10 @@ fn select_heuristic(
     }
     if lower.contains("calendar") || lower.contains("schedule") || lower.contains("meeting") {
         relevant_prefixes.push("read_calendar");
+        relevant_prefixes.push("list_calendars");
         relevant_prefixes.push("create_calendar");
+        relevant_prefixes.push("update_calendar");
+        relevant_prefixes.push("delete_calendar");
     }
     if lower.contains("remind") {
         relevant_prefixes.push("list_reminder");
@@ -149,6 +152,9 @@ fn select_heuristic(
         relevant_prefixes.push("create_watcher");
         relevant_prefixes.push("list_watcher");
         relevant_prefixes.push("cancel_watcher");
+        relevant_prefixes.push("pause_watcher");
+        relevant_prefixes.push("resume_watcher");
+        relevant_prefixes.push("preview_watcher_schedule");
     }
     if lower.contains("command")
         || lower.contains("terminal")
@@ -157,17 +163,25 @@ fn select_heuristic(
     {
         relevant_prefixes.push("run_command");
     }
-    if lower.contains("screen") || lower.contains("click") || lower.contains("type") {
+    if lower.contains("screen")
+        || lower.contains("click")
+        || lower.contains("type")
+        || lower.contains("window")
+    {
         relevant_prefixes.push("screen_capture");
         relevant_prefixes.push("read_screen");
+        relevant_prefixes.push("find_element");
         relevant_prefixes.push("click_element");
+        relevant_prefixes.push("click_at");
         relevant_prefixes.push("type_text");
     }
-    if lower.contains("contact") {
+    if lower.contains("contact") || lower.contains("email") {
         relevant_prefixes.push("search_contacts");
+        relevant_prefixes.push("lookup_contact");
     }
     if lower.contains("clipboard") || lower.contains("paste") || lower.contains("copy") {
         relevant_prefixes.push("get_clipboard");
+        relevant_prefixes.push("set_clipboard");
     }
     if lower.contains("notification") || lower.contains("notify") || lower.contains("alert") {
         relevant_prefixes.push("send_notification");
@@ -186,6 +200,17 @@ fn select_heuristic(
         relevant_prefixes.push("recall");
         relevant_prefixes.push("search_knowledge");
         relevant_prefixes.push("link_entities");
+        relevant_prefixes.push("update_entity");
+    }
+    if lower.contains("related") || lower.contains("connected") || lower.contains("relationship") {
+        relevant_prefixes.push("get_related");
+    }
+    if lower.contains("forget") || lower.contains("delete") {
+        relevant_prefixes.push("forget");
+    }
+    if lower.contains("duplicate") || lower.contains("merge") {
+        relevant_prefixes.push("find_duplicates");
+        relevant_prefixes.push("merge_entities");
     }
     if lower.contains("ingest") || lower.contains("index") || lower.contains("document") {
         relevant_prefixes.push("ingest_");
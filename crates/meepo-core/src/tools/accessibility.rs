@@ -10,38 +10,7 @@ use serde_json::Value;
 use tracing::debug;
 
 use super::{ToolHandler, json_schema};
-use crate::platform::UiAutomation;
-
-/// Allowlist of valid UI element types
-const VALID_ELEMENT_TYPES: &[&str] = &[
-    "button",
-    "checkbox",
-    "radio button",
-    "text field",
-    "text area",
-    "pop up button",
-    "menu item",
-    "menu button",
-    "slider",
-    "tab group",
-    "table",
-    "outline",
-    "list",
-    "scroll area",
-    "group",
-    "window",
-    "sheet",
-    "toolbar",
-    "static text",
-    "image",
-    "link",
-    "cell",
-    "row",
-    "column",
-    "combo box",
-    "incrementor",
-    "relevance indicator",
-];
+use crate::platform::{UiAutomation, UiElement, normalize_element_type};
 
 /// Read screen information (focused app and window)
 pub struct ReadScreenTool {
@@ -77,6 +46,10 @@ impl ToolHandler for ReadScreenTool {
         json_schema(serde_json::json!({}), vec![])
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, _input: Value) -> Result<String> {
         debug!("Reading screen information");
         self.provider.read_screen().await
@@ -140,10 +113,7 @@ impl ToolHandler for ClickElementTool {
             .unwrap_or("button");
 
         // Input validation: validate element_type against allowlist and normalize to canonical lowercase form
-        let element_type_normalized = VALID_ELEMENT_TYPES
-            .iter()
-            .find(|&&valid| valid.eq_ignore_ascii_case(element_type))
-            .ok_or_else(|| anyhow::anyhow!("Invalid element type: {}", element_type))?;
+        let element_type_normalized = normalize_element_type(element_type)?;
 
         debug!(
             "Clicking {} element: {}",
@@ -155,6 +125,156 @@ impl ToolHandler for ClickElementTool {
     }
 }
 
+/// List elements of the frontmost window with their names, roles and state
+pub struct FindElementTool {
+    provider: Box<dyn UiAutomation>,
+}
+
+impl Default for FindElementTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FindElementTool {
+    pub fn new() -> Self {
+        Self {
+            provider: crate::platform::create_ui_automation()
+                .expect("UI automation not available on this platform"),
+        }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for FindElementTool {
+    fn name(&self) -> &str {
+        "find_element"
+    }
+
+    fn description(&self) -> &str {
+        "List UI elements in the frontmost window with their names, types, and whether they are \
+         enabled. Use before click_element to find the exact name and type to click."
+    }
+
+    fn input_schema(&self) -> Value {
+        json_schema(
+            serde_json::json!({
+                "element_type": {
+                    "type": "string",
+                    "description": "Only list elements of this type, e.g. 'button', 'text field', 'menu item' (default: all clickable types)"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "Only list elements whose name contains this text (case-insensitive)"
+                }
+            }),
+            vec![],
+        )
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        let element_type = input
+            .get("element_type")
+            .and_then(|v| v.as_str())
+            .map(normalize_element_type)
+            .transpose()?;
+        let name = input.get("name").and_then(|v| v.as_str());
+
+        debug!("Finding {} elements", element_type.unwrap_or("clickable"));
+        let elements = self.provider.list_elements(element_type).await?;
+        Ok(format_elements(&elements, name))
+    }
+}
+
+/// One line per element, keeping only names containing `name` if given
+fn format_elements(elements: &[UiElement], name: Option<&str>) -> String {
+    let needle = name.map(str::to_lowercase);
+    let lines: Vec<String> = elements
+        .iter()
+        .filter(|e| {
+            needle
+                .as_deref()
+                .is_none_or(|n| e.name.to_lowercase().contains(n))
+        })
+        .map(|e| {
+            let state = if e.enabled { "" } else { " (disabled)" };
+            format!("{} \"{}\"{}", e.role, e.name, state)
+        })
+        .collect();
+    if lines.is_empty() {
+        return "No matching elements found in the frontmost window".to_string();
+    }
+    format!("Found {} element(s):\n{}", lines.len(), lines.join("\n"))
+}
+
+/// Click at screen coordinates, for elements with no accessible name
+pub struct ClickAtTool {
+    provider: Box<dyn UiAutomation>,
+}
+
+impl Default for ClickAtTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClickAtTool {
+    pub fn new() -> Self {
+        Self {
+            provider: crate::platform::create_ui_automation()
+                .expect("UI automation not available on this platform"),
+        }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for ClickAtTool {
+    fn name(&self) -> &str {
+        "click_at"
+    }
+
+    fn description(&self) -> &str {
+        "Click at a point on the main display, measured from its top-left corner. Use when \
+         click_element can't target an element by name, e.g. after locating it in a screenshot. \
+         On macOS coordinates are in points: halve pixel positions from a Retina screenshot."
+    }
+
+    fn input_schema(&self) -> Value {
+        json_schema(
+            serde_json::json!({
+                "x": {
+                    "type": "integer",
+                    "description": "Horizontal position, from the left edge of the main display"
+                },
+                "y": {
+                    "type": "integer",
+                    "description": "Vertical position, from the top edge of the main display"
+                }
+            }),
+            vec!["x", "y"],
+        )
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        let coordinate = |key: &str| -> Result<i32> {
+            let value = input
+                .get(key)
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing '{}' parameter", key))?;
+            i32::try_from(value)
+                .map_err(|_| anyhow::anyhow!("'{}' is out of range: {}", key, value))
+        };
+        let (x, y) = (coordinate("x")?, coordinate("y")?);
+
+        debug!("Clicking at ({}, {})", x, y);
+        self.provider.click_at(x, y).await
+    }
+}
+
 /// Type text using keyboard simulation
 pub struct TypeTextTool {
     provider: Box<dyn UiAutomation>,
@@ -190,7 +310,7 @@ impl ToolHandler for TypeTextTool {
             serde_json::json!({
                 "text": {
                     "type": "string",
-                    "description": "Text to type"
+                    "description": "Text to type. Newlines are typed as Return key presses"
                 }
             }),
             vec!["text"],
@@ -244,6 +364,32 @@ mod tests {
         assert!(schema.get("properties").is_some());
     }
 
+    #[test]
+    fn test_format_elements() {
+        let element = |role: &str, name: &str, enabled| UiElement {
+            name: name.to_string(),
+            role: role.to_string(),
+            enabled,
+        };
+        let elements = vec![
+            element("button", "Save", true),
+            element("button", "Save As…", false),
+            element("text field", "Search", true),
+        ];
+        assert_eq!(
+            format_elements(&elements, None),
+            "Found 3 element(s):\nbutton \"Save\"\nbutton \"Save As…\" (disabled)\ntext field \"Search\""
+        );
+        assert_eq!(
+            format_elements(&elements, Some("save")),
+            "Found 2 element(s):\nbutton \"Save\"\nbutton \"Save As…\" (disabled)"
+        );
+        assert_eq!(
+            format_elements(&elements, Some("cancel")),
+            "No matching elements found in the frontmost window"
+        );
+    }
+
     #[tokio::test]
     async fn test_click_element_missing_params() {
         let tool = ClickElementTool::new();
@@ -8,6 +8,7 @@ use tokio::sync::mpsc;
 use tracing::debug;
 
 use super::{ToolHandler, json_schema};
+use crate::text::truncate_chars;
 use meepo_knowledge::KnowledgeDb;
 
 /// Commands for background task management
@@ -143,6 +144,10 @@ impl ToolHandler for AgentStatusTool {
         json_schema(serde_json::json!({}), vec![])
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, _input: Value) -> Result<String> {
         let mut output = String::new();
 
@@ -203,8 +208,9 @@ impl ToolHandler for AgentStatusTool {
                     .result
                     .as_deref()
                     .map(|r| {
-                        if r.len() > 80 {
-                            format!("{}...", &r[..80])
+                        let preview = truncate_chars(r, 80);
+                        if preview.len() < r.len() {
+                            format!("{}...", preview)
                         } else {
                             r.to_string()
                         }
@@ -39,6 +39,10 @@ impl ToolHandler for BrowserListTabsTool {
         json_schema(serde_json::json!({}), vec![])
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, _input: Value) -> Result<String> {
         debug!("Listing browser tabs");
         let tabs = self.provider.list_tabs().await?;
@@ -243,6 +247,10 @@ impl ToolHandler for BrowserGetPageContentTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let tab_id = input.get("tab_id").and_then(|v| v.as_str());
 
@@ -577,6 +585,10 @@ impl ToolHandler for BrowserGetUrlTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let tab_id = input.get("tab_id").and_then(|v| v.as_str());
         debug!("Getting browser URL");
@@ -624,6 +636,10 @@ impl ToolHandler for BrowserScreenshotTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let path = input.get("path").and_then(|v| v.as_str());
         let tab_id = input.get("tab_id").and_then(|v| v.as_str());
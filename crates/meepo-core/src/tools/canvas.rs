@@ -230,6 +230,10 @@ impl ToolHandler for CanvasSnapshotTool {
         json_schema(serde_json::json!({}), vec![])
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, _input: Value) -> Result<String> {
         debug!("Canvas snapshot requested");
         Ok(serde_json::json!({
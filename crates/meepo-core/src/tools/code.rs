@@ -11,6 +11,7 @@ use tracing::{debug, warn};
 
 use super::autonomous::BackgroundTaskCommand;
 use super::{ToolHandler, json_schema};
+use crate::text::truncate_chars;
 use meepo_knowledge::KnowledgeDb;
 
 /// Configuration for Claude Code CLI tools, plumbed from [code] config section
@@ -599,6 +600,10 @@ impl ToolHandler for ReviewPrTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let repo = input
             .get("repo")
@@ -750,7 +755,7 @@ impl ToolHandler for SpawnClaudeCodeTool {
         }
 
         let task_id = format!("t-{}", uuid::Uuid::new_v4());
-        let description = format!("Claude Code: {}", &task[..task.len().min(100)]);
+        let description = format!("Claude Code: {}", truncate_chars(task, 100));
 
         debug!(
             "Spawning Claude Code background task {}: {}",
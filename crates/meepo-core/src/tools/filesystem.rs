@@ -11,7 +11,7 @@ use super::{ToolHandler, json_schema};
 /// Validate that a path is within one of the allowed directories.
 /// Uses canonicalize() to resolve symlinks and ".." — the canonical path
 /// must start with one of the pre-canonicalized allowed directories.
-fn validate_allowed_path(path: &str, allowed_dirs: &[PathBuf]) -> Result<PathBuf> {
+pub(crate) fn validate_allowed_path(path: &str, allowed_dirs: &[PathBuf]) -> Result<PathBuf> {
     let expanded = shellexpand(path);
     let canonical = expanded
         .canonicalize()
@@ -29,6 +29,17 @@ fn validate_allowed_path(path: &str, allowed_dirs: &[PathBuf]) -> Result<PathBuf
     ))
 }
 
+/// Expand and canonicalize configured directories for [`validate_allowed_path`]
+pub(crate) fn resolve_allowed_dirs(allowed_dirs: &[String]) -> Vec<PathBuf> {
+    allowed_dirs
+        .iter()
+        .map(|d| {
+            let expanded = shellexpand(d);
+            expanded.canonicalize().unwrap_or(expanded)
+        })
+        .collect()
+}
+
 fn shellexpand(s: &str) -> PathBuf {
     let mut result = s.to_string();
     if result.starts_with("~/")
@@ -47,13 +58,7 @@ pub struct ListDirectoryTool {
 impl ListDirectoryTool {
     pub fn new(allowed_dirs: Vec<String>) -> Self {
         Self {
-            allowed_dirs: allowed_dirs
-                .iter()
-                .map(|d| {
-                    let expanded = shellexpand(d);
-                    expanded.canonicalize().unwrap_or(expanded)
-                })
-                .collect(),
+            allowed_dirs: resolve_allowed_dirs(&allowed_dirs),
         }
     }
 }
@@ -88,6 +93,10 @@ impl ToolHandler for ListDirectoryTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let path_str = input
             .get("path")
@@ -210,13 +219,7 @@ pub struct SearchFilesTool {
 impl SearchFilesTool {
     pub fn new(allowed_dirs: Vec<String>) -> Self {
         Self {
-            allowed_dirs: allowed_dirs
-                .iter()
-                .map(|d| {
-                    let expanded = shellexpand(d);
-                    expanded.canonicalize().unwrap_or(expanded)
-                })
-                .collect(),
+            allowed_dirs: resolve_allowed_dirs(&allowed_dirs),
         }
     }
 }
@@ -255,6 +258,10 @@ impl ToolHandler for SearchFilesTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let path_str = input
             .get("path")
@@ -9,7 +9,7 @@ use serde_json::Value;
 use std::sync::Arc;
 use tracing::debug;
 
-use crate::platform::{CalendarProvider, ContactsProvider, EmailProvider};
+use crate::platform::{CalendarProvider, ContactsProvider, EmailProvider, TimeSlot, WorkingHours};
 use crate::tools::{ToolHandler, json_schema};
 use meepo_knowledge::KnowledgeDb;
 
@@ -69,6 +69,10 @@ impl ToolHandler for FindFreeTimeTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let days_ahead = input
             .get("days_ahead")
@@ -93,7 +97,7 @@ impl ToolHandler for FindFreeTimeTool {
             days_ahead, min_duration, work_start, work_end
         );
 
-        let events = self.provider.read_events(days_ahead).await?;
+        let events = self.provider.read_events(days_ahead, None).await?;
 
         Ok(format!(
             "Calendar events (next {} days):\n\n{}\n\n\
@@ -110,6 +114,188 @@ impl ToolHandler for FindFreeTimeTool {
     }
 }
 
+/// Propose meeting times by computing the gaps between calendar events
+pub struct FindFreeSlotsTool {
+    provider: Box<dyn CalendarProvider>,
+}
+
+impl FindFreeSlotsTool {
+    pub fn new() -> Self {
+        Self {
+            provider: crate::platform::create_calendar_provider()
+                .expect("Calendar provider not available on this platform"),
+        }
+    }
+}
+
+impl Default for FindFreeSlotsTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse an `HH:MM` working-hours bound
+fn parse_hhmm(value: &str, param: &str) -> Result<chrono::NaiveTime> {
+    chrono::NaiveTime::parse_from_str(value.trim(), "%H:%M")
+        .map_err(|_| anyhow::anyhow!("Invalid '{}': expected HH:MM, got '{}'", param, value))
+}
+
+/// `45 min`, `2h`, or `7h 15m`
+fn format_minutes(minutes: i64) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{} min", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h {}m", h, m),
+    }
+}
+
+/// One slot per line in local time, e.g. `- Mon Oct 19, 09:45-17:00 (7h 15m free)`
+fn format_slot(slot: &TimeSlot) -> String {
+    let start = slot.start.with_timezone(&chrono::Local);
+    let end = slot.end.with_timezone(&chrono::Local);
+    format!(
+        "- {}, {}-{} ({} free)",
+        start.format("%a %b %-d"),
+        start.format("%H:%M"),
+        end.format("%H:%M"),
+        format_minutes(slot.minutes())
+    )
+}
+
+#[async_trait]
+impl ToolHandler for FindFreeSlotsTool {
+    fn name(&self) -> &str {
+        "find_free_slots"
+    }
+
+    fn description(&self) -> &str {
+        "Find open slots in the calendar for a meeting of a given length. Computes the gaps \
+         between events within working hours over the coming days and returns the earliest \
+         candidates, ready to propose or pass to create_calendar_event."
+    }
+
+    fn input_schema(&self) -> Value {
+        json_schema(
+            serde_json::json!({
+                "duration_minutes": {
+                    "type": "number",
+                    "description": "Length of the meeting in minutes (default: 30)"
+                },
+                "within_days": {
+                    "type": "number",
+                    "description": "Number of days to search, starting today (default: 5, max: 30)"
+                },
+                "working_hours_start": {
+                    "type": "string",
+                    "description": "Start of working hours in HH:MM format (default: '09:00')"
+                },
+                "working_hours_end": {
+                    "type": "string",
+                    "description": "End of working hours in HH:MM format (default: '17:00')"
+                },
+                "skip_weekends": {
+                    "type": "boolean",
+                    "description": "Leave out Saturdays and Sundays (default: true)"
+                },
+                "max_slots": {
+                    "type": "number",
+                    "description": "Maximum number of slots to return (default: 5, max: 20)"
+                }
+            }),
+            vec![],
+        )
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        let duration_minutes = input
+            .get("duration_minutes")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(30)
+            .clamp(5, 24 * 60);
+        let within_days = input
+            .get("within_days")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(5)
+            .clamp(1, 30);
+        let max_slots = input
+            .get("max_slots")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(5)
+            .clamp(1, 20) as usize;
+        let defaults = WorkingHours::default();
+        let working_hours = WorkingHours {
+            start: match input.get("working_hours_start").and_then(|v| v.as_str()) {
+                Some(start) => parse_hhmm(start, "working_hours_start")?,
+                None => defaults.start,
+            },
+            end: match input.get("working_hours_end").and_then(|v| v.as_str()) {
+                Some(end) => parse_hhmm(end, "working_hours_end")?,
+                None => defaults.end,
+            },
+            skip_weekends: input
+                .get("skip_weekends")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(defaults.skip_weekends),
+        };
+        if working_hours.end <= working_hours.start {
+            return Err(anyhow::anyhow!(
+                "working_hours_end must be after working_hours_start"
+            ));
+        }
+
+        debug!(
+            "Finding {} min slots over {} days ({}-{})",
+            duration_minutes, within_days, working_hours.start, working_hours.end
+        );
+
+        let slots = self
+            .provider
+            .find_free_slots(duration_minutes, within_days, &working_hours)
+            .await?;
+
+        let window = format!(
+            "{}-{}{}",
+            working_hours.start.format("%H:%M"),
+            working_hours.end.format("%H:%M"),
+            if working_hours.skip_weekends {
+                ", weekdays"
+            } else {
+                ""
+            }
+        );
+        if slots.is_empty() {
+            return Ok(format!(
+                "No free slots of {} in the next {} day(s) ({}).",
+                format_minutes(duration_minutes as i64),
+                within_days,
+                window
+            ));
+        }
+
+        let mut output = format!(
+            "Free slots for {} in the next {} day(s) ({}):\n",
+            format_minutes(duration_minutes as i64),
+            within_days,
+            window
+        );
+        for slot in slots.iter().take(max_slots) {
+            output.push_str(&format_slot(slot));
+            output.push('\n');
+        }
+        if slots.len() > max_slots {
+            output.push_str(&format!(
+                "{} more slot(s) available; raise max_slots to see them.\n",
+                slots.len() - max_slots
+            ));
+        }
+        Ok(output)
+    }
+}
+
 /// Schedule a meeting with smart time finding
 pub struct ScheduleMeetingTool {
     calendar: Box<dyn CalendarProvider>,
@@ -217,7 +403,7 @@ impl ToolHandler for ScheduleMeetingTool {
         );
 
         // Get current calendar to find availability
-        let events = self.calendar.read_events(7).await?;
+        let events = self.calendar.read_events(7, None).await?;
 
         // Look up attendee contact info if contacts provider is available
         let mut attendee_info = Vec::new();
@@ -342,7 +528,7 @@ impl ToolHandler for RescheduleEventTool {
         debug!("Rescheduling '{}' to {}", event_title, new_time);
 
         // Read current calendar to find the event and check conflicts
-        let events = self.provider.read_events(14).await?;
+        let events = self.provider.read_events(14, None).await?;
 
         Ok(format!(
             "Reschedule Request:\n\
@@ -419,6 +605,10 @@ impl ToolHandler for DailyBriefingTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let include_emails = input
             .get("include_emails")
@@ -431,15 +621,16 @@ impl ToolHandler for DailyBriefingTool {
 
         debug!("Generating daily briefing");
 
-        // Get today's calendar
-        let calendar = self.calendar.read_events(1).await?;
-
-        // Get recent emails
-        let emails = if include_emails {
-            self.email.read_emails(10, "inbox", None).await?
-        } else {
-            "Email summary skipped.".to_string()
+        // Get today's calendar and recent emails together (on macOS they share one osascript run)
+        let emails = async {
+            if include_emails {
+                self.email.read_emails(10, "inbox", None).await
+            } else {
+                Ok("Email summary skipped.".to_string())
+            }
         };
+        let (calendar, emails) = tokio::join!(self.calendar.read_events(1, None), emails);
+        let (calendar, emails) = (calendar?, emails?);
 
         // Get pending tasks from knowledge graph
         let tasks = if include_tasks {
@@ -533,11 +724,15 @@ impl ToolHandler for WeeklyReviewTool {
         json_schema(serde_json::json!({}), vec![])
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, _input: Value) -> Result<String> {
         debug!("Generating weekly review");
 
         // Get next week's calendar
-        let upcoming = self.calendar.read_events(7).await?;
+        let upcoming = self.calendar.read_events(7, None).await?;
 
         // Get completed actions from action log
         let actions = self
@@ -594,6 +789,128 @@ impl ToolHandler for WeeklyReviewTool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::platform::EventDetails;
+    use std::sync::Mutex;
+
+    /// Calendar whose free slots are fixed, recording the request it was given
+    #[derive(Default)]
+    struct FixedSlotsCalendar {
+        slots: Vec<TimeSlot>,
+        requests: Arc<Mutex<Vec<(u64, u64, WorkingHours)>>>,
+    }
+
+    #[async_trait]
+    impl CalendarProvider for FixedSlotsCalendar {
+        async fn read_events(&self, _: u64, _: Option<&str>) -> Result<String> {
+            Ok(String::new())
+        }
+
+        async fn list_calendars(&self) -> Result<String> {
+            Ok(String::new())
+        }
+
+        async fn create_event(
+            &self,
+            _: &str,
+            _: &str,
+            _: u64,
+            _: Option<&str>,
+            _: &EventDetails,
+        ) -> Result<String> {
+            Ok(String::new())
+        }
+
+        async fn find_free_slots(
+            &self,
+            duration_minutes: u64,
+            within_days: u64,
+            working_hours: &WorkingHours,
+        ) -> Result<Vec<TimeSlot>> {
+            self.requests
+                .lock()
+                .unwrap()
+                .push((duration_minutes, within_days, *working_hours));
+            Ok(self.slots.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_find_free_slots_tool() {
+        use chrono::{Local, TimeZone, Utc};
+
+        let local = |d, h, mi| {
+            Local
+                .with_ymd_and_hms(2026, 10, d, h, mi, 0)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        let calendar = FixedSlotsCalendar {
+            slots: vec![
+                TimeSlot {
+                    start: local(19, 9, 45),
+                    end: local(19, 17, 0),
+                },
+                TimeSlot {
+                    start: local(20, 9, 0),
+                    end: local(20, 9, 45),
+                },
+            ],
+            ..Default::default()
+        };
+        let requests = calendar.requests.clone();
+        let tool = FindFreeSlotsTool {
+            provider: Box::new(calendar),
+        };
+
+        let output = tool
+            .execute(serde_json::json!({
+                "duration_minutes": 45,
+                "working_hours_start": "08:30",
+                "skip_weekends": false,
+                "max_slots": 1
+            }))
+            .await
+            .unwrap();
+        assert_eq!(
+            output,
+            "Free slots for 45 min in the next 5 day(s) (08:30-17:00):\n\
+             - Mon Oct 19, 09:45-17:00 (7h 15m free)\n\
+             1 more slot(s) available; raise max_slots to see them.\n"
+        );
+        let (duration, days, hours) = requests.lock().unwrap()[0];
+        assert_eq!((duration, days), (45, 5));
+        assert_eq!(
+            hours.start,
+            chrono::NaiveTime::from_hms_opt(8, 30, 0).unwrap()
+        );
+        assert!(!hours.skip_weekends);
+
+        let err = tool
+            .execute(serde_json::json!({"working_hours_start": "18:00"}))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("must be after"));
+        assert!(
+            tool.execute(serde_json::json!({"working_hours_end": "5pm"}))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_free_slots_tool_none_free() {
+        let tool = FindFreeSlotsTool {
+            provider: Box::new(FixedSlotsCalendar::default()),
+        };
+        let output = tool
+            .execute(serde_json::json!({"duration_minutes": 120, "within_days": 2}))
+            .await
+            .unwrap();
+        assert_eq!(
+            output,
+            "No free slots of 2h in the next 2 day(s) (09:00-17:00, weekdays)."
+        );
+    }
 
     #[cfg(any(target_os = "macos", target_os = "windows"))]
     #[test]
@@ -9,7 +9,7 @@ use serde_json::Value;
 use std::sync::Arc;
 use tracing::debug;
 
-use crate::platform::EmailProvider;
+use crate::platform::{EmailProvider, format_email_summaries};
 use crate::tools::{ToolHandler, json_schema};
 use meepo_knowledge::KnowledgeDb;
 
@@ -76,7 +76,10 @@ impl ToolHandler for EmailTriageTool {
         debug!("Triaging {} emails from last {} hours", limit, since_hours);
 
         // Read recent emails
-        let emails = self.provider.read_emails(limit, "inbox", None).await?;
+        let emails = self
+            .provider
+            .read_emails_structured(limit, "inbox", None)
+            .await?;
 
         // Store triage results in knowledge graph for future reference
         let _ = self
@@ -85,7 +88,7 @@ impl ToolHandler for EmailTriageTool {
                 &format!("email_triage_{}", chrono::Utc::now().format("%Y%m%d_%H%M")),
                 "email_triage",
                 Some(serde_json::json!({
-                    "count": limit,
+                    "count": emails.len(),
                     "since_hours": since_hours,
                     "timestamp": chrono::Utc::now().to_rfc3339(),
                 })),
@@ -101,7 +104,9 @@ impl ToolHandler for EmailTriageTool {
              - NEWSLETTER: Subscriptions and marketing\n\
              - SPAM: Unwanted or suspicious\n\n\
              For each ACTION REQUIRED email, identify the specific action needed.",
-            since_hours, limit, emails
+            since_hours,
+            emails.len(),
+            format_email_summaries("inbox", &emails)
         ))
     }
 }
@@ -279,6 +284,10 @@ impl ToolHandler for EmailSummarizeThreadTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let subject = input
             .get("subject")
@@ -1,14 +1,18 @@
 //! Finance & Expense Tracker tools
 //!
-//! Track expenses, parse receipt emails, monitor spending, and check budgets.
-//! All data stored in the knowledge graph as entities.
+//! Track expenses, parse receipt emails, import bank statements, monitor spending, and
+//! check budgets. All data stored in the knowledge graph as entities.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
+use chrono::NaiveDate;
 use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use tracing::debug;
 
+use crate::text::truncate_chars;
+use crate::tools::system::validate_file_path;
 use crate::tools::{ToolHandler, json_schema};
 use meepo_knowledge::KnowledgeDb;
 
@@ -212,6 +216,10 @@ impl ToolHandler for SpendingSummaryTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let period = input
             .get("period")
@@ -517,6 +525,10 @@ impl ToolHandler for ParseReceiptTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let text = input
             .get("text")
@@ -544,7 +556,7 @@ impl ToolHandler for ParseReceiptTool {
              5. **Payment Method** — if mentioned\n\
              6. **Items** — line items if available\n\n\
              {}",
-            &text[..text.len().min(10_000)],
+            truncate_chars(text, 10_000),
             if auto_log {
                 "Then automatically log the expense using log_expense."
             } else {
@@ -554,6 +566,976 @@ impl ToolHandler for ParseReceiptTool {
     }
 }
 
+/// Maximum size of a transaction CSV accepted by `import_transactions`
+const MAX_CSV_BYTES: usize = 5 * 1024 * 1024;
+
+/// Date formats tried in order when an import doesn't name one, after the slash
+/// formats for the file's [`DateOrder`]
+const TRANSACTION_DATE_FORMATS: &[&str] = &[
+    "%Y-%m-%d",
+    "%Y/%m/%d",
+    "%d.%m.%Y",
+    "%d-%b-%Y",
+    "%d %b %Y",
+    "%b %d, %Y",
+    "%B %d, %Y",
+];
+
+/// Slash date formats; two-digit years are tried first so `%Y` never reads "24" as year 24
+const MONTH_FIRST_DATE_FORMATS: &[&str] = &["%m/%d/%y", "%m/%d/%Y"];
+const DAY_FIRST_DATE_FORMATS: &[&str] = &["%d/%m/%y", "%d/%m/%Y"];
+
+/// Whether slash dates (`03/04/2024`) put the month or the day first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DateOrder {
+    MonthFirst,
+    DayFirst,
+}
+
+impl DateOrder {
+    fn formats(self) -> &'static [&'static str] {
+        match self {
+            DateOrder::MonthFirst => MONTH_FIRST_DATE_FORMATS,
+            DateOrder::DayFirst => DAY_FIRST_DATE_FORMATS,
+        }
+    }
+}
+
+/// Keyword rules applied after any caller-supplied rules. Checked in order, so more
+/// specific keywords ("uber eats") come before broader ones ("uber").
+const DEFAULT_CATEGORY_RULES: &[(&str, &[&str])] = &[
+    (
+        "dining",
+        &[
+            "uber eats",
+            "doordash",
+            "grubhub",
+            "restaurant",
+            "cafe",
+            "coffee",
+            "starbucks",
+            "pizza",
+        ],
+    ),
+    (
+        "groceries",
+        &[
+            "grocery",
+            "whole foods",
+            "trader joe",
+            "safeway",
+            "kroger",
+            "aldi",
+            "market",
+        ],
+    ),
+    (
+        "transport",
+        &[
+            "uber", "lyft", "shell", "chevron", "exxon", "fuel", "parking", "transit",
+        ],
+    ),
+    ("travel", &["airline", "hotel", "airbnb", "expedia"]),
+    (
+        "shopping",
+        &["amazon", "target", "walmart", "best buy", "ikea"],
+    ),
+    (
+        "entertainment",
+        &["netflix", "spotify", "hulu", "cinema", "steam"],
+    ),
+    (
+        "bills",
+        &[
+            "electric",
+            "utility",
+            "comcast",
+            "verizon",
+            "insurance",
+            "internet",
+        ],
+    ),
+    (
+        "health",
+        &["pharmacy", "cvs", "walgreens", "clinic", "dental", "gym"],
+    ),
+];
+
+/// Header names recognised for each column when no mapping is given
+const DATE_HEADERS: &[&str] = &[
+    "date",
+    "transaction date",
+    "trans. date",
+    "posted date",
+    "posting date",
+    "post date",
+];
+const DESCRIPTION_HEADERS: &[&str] = &[
+    "description",
+    "payee",
+    "merchant",
+    "name",
+    "memo",
+    "details",
+];
+const AMOUNT_HEADERS: &[&str] = &["amount", "transaction amount"];
+const DEBIT_HEADERS: &[&str] = &["debit", "withdrawal", "withdrawals", "money out"];
+const CREDIT_HEADERS: &[&str] = &["credit", "deposit", "deposits", "money in"];
+
+/// A column picked out by header name or zero-based index
+#[derive(Debug, Clone, PartialEq)]
+enum ColumnRef {
+    Index(usize),
+    Name(String),
+}
+
+impl ColumnRef {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Number(n) => n.as_u64().map(|i| Self::Index(i as usize)),
+            Value::String(s) if !s.trim().is_empty() => Some(Self::Name(s.trim().to_string())),
+            _ => None,
+        }
+    }
+}
+
+/// One CSV row, normalised so that a negative amount is money leaving the account
+#[derive(Debug, Clone, PartialEq)]
+struct Transaction {
+    date: NaiveDate,
+    description: String,
+    amount: f64,
+    category: String,
+}
+
+impl Transaction {
+    fn is_spend(&self) -> bool {
+        self.amount < 0.0
+    }
+
+    fn month(&self) -> String {
+        self.date.format("%Y-%m").to_string()
+    }
+
+    fn entity_name(&self) -> String {
+        format!(
+            "transaction:{}:{}:{}:{:.2}",
+            self.category, self.date, self.description, self.amount
+        )
+    }
+
+    /// Money out is stored as an `expense` so spending_summary and budget_check
+    /// count it; money in is kept as a `transaction`
+    fn entity_type(&self) -> &'static str {
+        if self.is_spend() {
+            "expense"
+        } else {
+            "transaction"
+        }
+    }
+
+    fn metadata(&self) -> Value {
+        serde_json::json!({
+            "amount": self.amount.abs(),
+            "signed_amount": self.amount,
+            "direction": if self.is_spend() { "debit" } else { "credit" },
+            "category": self.category,
+            "description": self.description,
+            "vendor": self.description,
+            "date": self.date.to_string(),
+            "month": self.month(),
+            "source": "csv_import",
+            "created_at": chrono::Utc::now().to_rfc3339(),
+        })
+    }
+}
+
+/// Transactions parsed from a CSV, plus a reason for every row that was skipped
+#[derive(Debug, Default)]
+struct ParsedTransactions {
+    transactions: Vec<Transaction>,
+    skipped: Vec<String>,
+}
+
+/// Resolved column positions for one CSV
+#[derive(Debug, Clone, Copy)]
+struct ColumnIndexes {
+    date: usize,
+    description: usize,
+    amount: Option<usize>,
+    debit: Option<usize>,
+    credit: Option<usize>,
+}
+
+/// Parsing options for `import_transactions`
+#[derive(Debug, Clone)]
+struct TransactionImport {
+    delimiter: char,
+    has_header: bool,
+    date_format: Option<String>,
+    /// '.' for amounts like 1,234.56, ',' for 1.234,56
+    decimal_separator: char,
+    /// Credit-card exports usually list purchases as positive amounts
+    positive_is_spend: bool,
+    date_column: Option<ColumnRef>,
+    description_column: Option<ColumnRef>,
+    amount_column: Option<ColumnRef>,
+    debit_column: Option<ColumnRef>,
+    credit_column: Option<ColumnRef>,
+    /// (category, lowercase keywords), first match wins
+    rules: Vec<(String, Vec<String>)>,
+}
+
+impl Default for TransactionImport {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            has_header: true,
+            date_format: None,
+            decimal_separator: '.',
+            positive_is_spend: false,
+            date_column: None,
+            description_column: None,
+            amount_column: None,
+            debit_column: None,
+            credit_column: None,
+            rules: DEFAULT_CATEGORY_RULES
+                .iter()
+                .map(|(category, keywords)| {
+                    (
+                        category.to_string(),
+                        keywords.iter().map(|k| k.to_string()).collect(),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TransactionImport {
+    fn from_input(input: &Value) -> Result<Self> {
+        let mut import = Self::default();
+
+        if let Some(delimiter) = input.get("delimiter").and_then(|v| v.as_str()) {
+            let mut chars = delimiter.chars();
+            import.delimiter = match (chars.next(), chars.next()) {
+                (Some(c), None) => c,
+                _ if delimiter == "\\t" || delimiter.eq_ignore_ascii_case("tab") => '\t',
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "'delimiter' must be a single character (or 'tab')"
+                    ));
+                }
+            };
+        }
+        if let Some(has_header) = input.get("has_header").and_then(|v| v.as_bool()) {
+            import.has_header = has_header;
+        }
+        import.date_format = input
+            .get("date_format")
+            .and_then(|v| v.as_str())
+            .filter(|f| !f.trim().is_empty())
+            .map(str::to_string);
+        import.decimal_separator = match input
+            .get("decimal_separator")
+            .and_then(|v| v.as_str())
+            .unwrap_or(".")
+        {
+            "." => '.',
+            "," => ',',
+            other => {
+                return Err(anyhow::anyhow!(
+                    "Unknown decimal_separator '{}'. Use '.' or ','",
+                    other
+                ));
+            }
+        };
+        import.positive_is_spend = match input
+            .get("sign_convention")
+            .and_then(|v| v.as_str())
+            .unwrap_or("negative_is_spend")
+        {
+            "negative_is_spend" => false,
+            "positive_is_spend" => true,
+            other => {
+                return Err(anyhow::anyhow!(
+                    "Unknown sign_convention '{}'. Use negative_is_spend or positive_is_spend",
+                    other
+                ));
+            }
+        };
+
+        if let Some(columns) = input.get("columns").and_then(|v| v.as_object()) {
+            let column = |key: &str| columns.get(key).and_then(ColumnRef::from_value);
+            import.date_column = column("date");
+            import.description_column = column("description");
+            import.amount_column = column("amount");
+            import.debit_column = column("debit");
+            import.credit_column = column("credit");
+        }
+
+        if let Some(rules) = input.get("rules").and_then(|v| v.as_array()) {
+            let mut custom = Vec::with_capacity(rules.len());
+            for rule in rules {
+                let category = rule
+                    .get("category")
+                    .and_then(|v| v.as_str())
+                    .filter(|c| !c.trim().is_empty())
+                    .ok_or_else(|| anyhow::anyhow!("Each rule needs a 'category'"))?;
+                let keywords: Vec<String> = rule
+                    .get("keywords")
+                    .and_then(|v| v.as_array())
+                    .map(|kws| {
+                        kws.iter()
+                            .filter_map(|k| k.as_str())
+                            .map(|k| k.trim().to_lowercase())
+                            .filter(|k| !k.is_empty())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                if keywords.is_empty() {
+                    return Err(anyhow::anyhow!(
+                        "Rule for '{}' needs at least one keyword",
+                        category
+                    ));
+                }
+                custom.push((category.trim().to_lowercase(), keywords));
+            }
+            custom.append(&mut import.rules);
+            import.rules = custom;
+        }
+
+        Ok(import)
+    }
+
+    fn parse(&self, text: &str) -> Result<ParsedTransactions> {
+        let mut records = parse_csv(text, self.delimiter);
+        let headers = if self.has_header && !records.is_empty() {
+            Some(records.remove(0))
+        } else {
+            None
+        };
+        let headers = headers.as_deref();
+
+        let columns = ColumnIndexes {
+            date: resolve_column(self.date_column.as_ref(), headers, DATE_HEADERS)?
+                .ok_or_else(|| anyhow::anyhow!("Could not find a date column; set columns.date"))?,
+            description: resolve_column(
+                self.description_column.as_ref(),
+                headers,
+                DESCRIPTION_HEADERS,
+            )?
+            .ok_or_else(|| {
+                anyhow::anyhow!("Could not find a description column; set columns.description")
+            })?,
+            amount: resolve_column(self.amount_column.as_ref(), headers, AMOUNT_HEADERS)?,
+            debit: resolve_column(self.debit_column.as_ref(), headers, DEBIT_HEADERS)?,
+            credit: resolve_column(self.credit_column.as_ref(), headers, CREDIT_HEADERS)?,
+        };
+        if columns.amount.is_none() && columns.debit.is_none() && columns.credit.is_none() {
+            return Err(anyhow::anyhow!(
+                "Could not find an amount column; set columns.amount or columns.debit/columns.credit"
+            ));
+        }
+
+        let order = if self.date_format.is_some() {
+            DateOrder::MonthFirst
+        } else {
+            let dates: Vec<&str> = records
+                .iter()
+                .filter_map(|record| record.get(columns.date))
+                .map(|date| date.trim())
+                .collect();
+            slash_date_order(&dates)?
+        };
+
+        let mut parsed = ParsedTransactions::default();
+        let first_row = if self.has_header { 2 } else { 1 };
+        for (i, record) in records.iter().enumerate() {
+            match self.parse_record(record, &columns, order) {
+                Ok(transaction) => parsed.transactions.push(transaction),
+                Err(e) => parsed.skipped.push(format!("row {}: {}", i + first_row, e)),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    fn parse_record(
+        &self,
+        record: &[String],
+        columns: &ColumnIndexes,
+        order: DateOrder,
+    ) -> Result<Transaction> {
+        let cell = |idx: Option<usize>| {
+            idx.and_then(|i| record.get(i))
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+        };
+
+        let date = parse_transaction_date(
+            cell(Some(columns.date)).unwrap_or_default(),
+            self.date_format.as_deref(),
+            order,
+        )?;
+        let description = cell(Some(columns.description))
+            .unwrap_or("unknown")
+            .to_string();
+        let amount = match cell(columns.amount) {
+            Some(raw) => {
+                let value = parse_amount(raw, self.decimal_separator)?;
+                if self.positive_is_spend {
+                    -value
+                } else {
+                    value
+                }
+            }
+            None => {
+                let amount = |raw| parse_amount(raw, self.decimal_separator);
+                let debit = cell(columns.debit).map(amount).transpose()?;
+                let credit = cell(columns.credit).map(amount).transpose()?;
+                if debit.is_none() && credit.is_none() {
+                    return Err(anyhow::anyhow!("Missing amount"));
+                }
+                credit.unwrap_or(0.0).abs() - debit.unwrap_or(0.0).abs()
+            }
+        };
+        let category = self.categorize(&description, amount < 0.0);
+
+        Ok(Transaction {
+            date,
+            description,
+            amount,
+            category,
+        })
+    }
+
+    /// Pick the first rule whose keyword appears in the description. Unmatched money
+    /// out is "other"; unmatched money in is "income".
+    fn categorize(&self, description: &str, is_spend: bool) -> String {
+        let description = description.to_lowercase();
+        self.rules
+            .iter()
+            .find(|(_, keywords)| keywords.iter().any(|k| description.contains(k.as_str())))
+            .map(|(category, _)| category.clone())
+            .unwrap_or_else(|| if is_spend { "other" } else { "income" }.to_string())
+    }
+}
+
+/// Split CSV text into records, honouring double-quoted fields (which may contain the
+/// delimiter, `""` escapes and newlines). Blank lines are dropped.
+fn parse_csv(text: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c != '"' {
+                field.push(c);
+            } else if chars.peek() == Some(&'"') {
+                chars.next();
+                field.push('"');
+            } else {
+                in_quotes = false;
+            }
+        } else if c == '"' {
+            in_quotes = true;
+        } else if c == delimiter {
+            record.push(std::mem::take(&mut field));
+        } else if c == '\n' || c == '\r' {
+            if c == '\r' && chars.peek() == Some(&'\n') {
+                chars.next();
+            }
+            record.push(std::mem::take(&mut field));
+            let finished = std::mem::take(&mut record);
+            if finished.iter().any(|f| !f.trim().is_empty()) {
+                records.push(finished);
+            }
+        } else {
+            field.push(c);
+        }
+    }
+
+    record.push(field);
+    if record.iter().any(|f| !f.trim().is_empty()) {
+        records.push(record);
+    }
+    records
+}
+
+/// Resolve a column reference to an index. Without an explicit reference, fall back to
+/// the first header matching one of `candidates` (case-insensitive).
+fn resolve_column(
+    column: Option<&ColumnRef>,
+    headers: Option<&[String]>,
+    candidates: &[&str],
+) -> Result<Option<usize>> {
+    let find = |name: &str| {
+        headers.and_then(|h| {
+            h.iter()
+                .position(|header| header.trim().eq_ignore_ascii_case(name))
+        })
+    };
+
+    match column {
+        Some(ColumnRef::Index(idx)) => Ok(Some(*idx)),
+        Some(ColumnRef::Name(name)) => find(name).map(Some).ok_or_else(|| match headers {
+            Some(h) => anyhow::anyhow!("Column '{}' not found in header: {}", name, h.join(", ")),
+            None => anyhow::anyhow!(
+                "Column '{}' given by name but the CSV has no header row; use an index",
+                name
+            ),
+        }),
+        None => Ok(candidates.iter().find_map(|c| find(c))),
+    }
+}
+
+/// Parse an amount such as `-1,234.50`, `$12.00`, `(42.10)` or `12.50-`. Parentheses and
+/// a trailing minus both mean negative. With a `decimal` separator of ',' amounts read
+/// like `1.234,50`. Grouping separators must split off thousands, so an amount written
+/// in the other convention (`12,50` when `decimal` is '.') is rejected, not misread.
+fn parse_amount(raw: &str, decimal: char) -> Result<f64> {
+    let mut value = raw.trim();
+    let mut negative = false;
+    if let Some(inner) = value.strip_prefix('(').and_then(|v| v.strip_suffix(')')) {
+        negative = true;
+        value = inner;
+    }
+    if let Some(rest) = value.strip_suffix('-') {
+        negative = !negative;
+        value = rest;
+    }
+
+    let group = if decimal == ',' { '.' } else { ',' };
+    let cleaned: String = value
+        .chars()
+        .filter(|c| !matches!(c, '$' | '€' | '£' | '+') && !c.is_whitespace())
+        .collect();
+    let (whole, fraction) = match cleaned.split_once(decimal) {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (cleaned.as_str(), None),
+    };
+    if whole.split(group).skip(1).any(|digits| digits.len() != 3)
+        || fraction.is_some_and(|f| f.contains(group))
+    {
+        return Err(anyhow::anyhow!(
+            "Ambiguous amount '{}'; set 'decimal_separator' to match the file",
+            raw
+        ));
+    }
+    let number = match fraction {
+        Some(fraction) => format!("{}.{}", whole.replace(group, ""), fraction),
+        None => whole.replace(group, ""),
+    };
+    let amount: f64 = number
+        .parse()
+        .ok()
+        .filter(|a: &f64| a.is_finite())
+        .ok_or_else(|| anyhow::anyhow!("Invalid amount '{}'", raw))?;
+
+    Ok(if negative { -amount } else { amount })
+}
+
+/// How the slash dates among `dates` are ordered: day-first when some date's first
+/// number is over 12, month-first when some date's second number is. Fails when the
+/// dates disagree, or when none settles it and some date reads differently either way.
+fn slash_date_order(dates: &[&str]) -> Result<DateOrder> {
+    let mut day_first = None;
+    let mut month_first = None;
+    let mut ambiguous = None;
+    for date in dates {
+        let date_part = date.split(['T', ' ']).next().unwrap_or_default();
+        let parts: Vec<&str> = date_part.split('/').collect();
+        let [first, second, _] = parts.as_slice() else {
+            continue;
+        };
+        if first.len() > 2 {
+            continue;
+        }
+        let (Ok(first), Ok(second)) = (first.parse::<u32>(), second.parse::<u32>()) else {
+            continue;
+        };
+        if first > 12 {
+            day_first.get_or_insert(*date);
+        } else if second > 12 {
+            month_first.get_or_insert(*date);
+        } else if first != second {
+            ambiguous.get_or_insert(*date);
+        }
+    }
+
+    match (day_first, month_first, ambiguous) {
+        (Some(day), Some(month), _) => Err(anyhow::anyhow!(
+            "Dates '{}' (day first) and '{}' (month first) disagree; pass 'date_format'",
+            day,
+            month
+        )),
+        (Some(_), None, _) => Ok(DateOrder::DayFirst),
+        (None, Some(_), _) | (None, None, None) => Ok(DateOrder::MonthFirst),
+        (None, None, Some(date)) => Err(anyhow::anyhow!(
+            "Can't tell whether dates like '{}' are day or month first; pass 'date_format' \
+             ('%d/%m/%Y' or '%m/%d/%Y')",
+            date
+        )),
+    }
+}
+
+/// Parse a transaction date with the given strftime format, or by trying the common
+/// export formats with slash dates read in `order`. A trailing time of day
+/// (`2024-01-05T10:00:00`) is ignored.
+fn parse_transaction_date(raw: &str, format: Option<&str>, order: DateOrder) -> Result<NaiveDate> {
+    let raw = raw.trim();
+    if let Some(format) = format {
+        return NaiveDate::parse_from_str(raw, format)
+            .or_else(|_| chrono::NaiveDateTime::parse_from_str(raw, format).map(|dt| dt.date()))
+            .with_context(|| format!("Date '{}' does not match format '{}'", raw, format));
+    }
+
+    let date_part = raw.split(['T', ' ']).next().filter(|part| *part != raw);
+    for candidate in std::iter::once(raw).chain(date_part) {
+        for format in order.formats().iter().chain(TRANSACTION_DATE_FORMATS) {
+            if let Ok(date) = NaiveDate::parse_from_str(candidate, format) {
+                return Ok(date);
+            }
+        }
+    }
+
+    Err(anyhow::anyhow!(
+        "Unrecognized date '{}'; pass 'date_format'",
+        raw
+    ))
+}
+
+/// Render spend totals by category and by month. Only money leaving the account counts
+/// as spend; incoming amounts are totalled separately.
+fn spend_summary(transactions: &[Transaction]) -> String {
+    let mut by_category: HashMap<&str, f64> = HashMap::new();
+    let mut by_month: BTreeMap<String, HashMap<&str, f64>> = BTreeMap::new();
+    let mut total = 0.0;
+    let mut income = 0.0;
+
+    for tx in transactions {
+        if !tx.is_spend() {
+            income += tx.amount;
+            continue;
+        }
+        let spent = -tx.amount;
+        total += spent;
+        *by_category.entry(&tx.category).or_insert(0.0) += spent;
+        *by_month
+            .entry(tx.month())
+            .or_default()
+            .entry(&tx.category)
+            .or_insert(0.0) += spent;
+    }
+
+    let sorted = |totals: &HashMap<&str, f64>| {
+        let mut list: Vec<(String, f64)> =
+            totals.iter().map(|(c, a)| (c.to_string(), *a)).collect();
+        list.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        list
+    };
+
+    let mut output = format!("## Spending by Category (total ${:.2})\n", total);
+    if by_category.is_empty() {
+        output.push_str("  No spending found.\n");
+    }
+    for (category, amount) in sorted(&by_category) {
+        let pct = if total > 0.0 {
+            (amount / total * 100.0) as u32
+        } else {
+            0
+        };
+        output.push_str(&format!("  - {}: ${:.2} ({}%)\n", category, amount, pct));
+    }
+
+    output.push_str("\n## Spending by Month\n");
+    if by_month.is_empty() {
+        output.push_str("  No spending found.\n");
+    }
+    for (month, categories) in &by_month {
+        let month_total: f64 = categories.values().sum();
+        output.push_str(&format!("### {} (${:.2})\n", month, month_total));
+        for (category, amount) in sorted(categories) {
+            output.push_str(&format!("  - {}: ${:.2}\n", category, amount));
+        }
+    }
+
+    if income > 0.0 {
+        output.push_str(&format!("\n## Income\n  - Total: ${:.2}\n", income));
+    }
+    output
+}
+
+/// Import transactions from a bank or credit-card CSV export
+pub struct ImportTransactionsTool {
+    db: Arc<KnowledgeDb>,
+}
+
+impl ImportTransactionsTool {
+    pub fn new(db: Arc<KnowledgeDb>) -> Self {
+        Self { db }
+    }
+
+    /// Store transactions as entities linked to their category (money out as `expense`,
+    /// money in as `transaction`), and roll spend up into one `monthly_spending` entity
+    /// per category and month. Returns (stored, duplicates).
+    async fn store(&self, transactions: &[Transaction]) -> Result<(usize, usize)> {
+        let mut occurrences: HashMap<String, usize> = HashMap::new();
+        let mut category_ids: HashMap<String, String> = HashMap::new();
+        let mut monthly: HashMap<(String, String), (f64, u64)> = HashMap::new();
+        let mut stored = 0;
+        let mut duplicates = 0;
+
+        for tx in transactions {
+            // Identical rows (two coffees on the same day) get numbered so that they
+            // are kept, while re-importing the same file still finds them as duplicates
+            let base = tx.entity_name();
+            let seen = occurrences.entry(base.clone()).or_insert(0);
+            *seen += 1;
+            let name = if *seen == 1 {
+                base
+            } else {
+                format!("{} #{}", base, seen)
+            };
+
+            let existing = self
+                .db
+                .search_entities(&name, Some(tx.entity_type()))
+                .await?;
+            if existing.iter().any(|e| e.name == name) {
+                duplicates += 1;
+                continue;
+            }
+            let tx_id = self
+                .db
+                .insert_entity(&name, tx.entity_type(), Some(tx.metadata()))
+                .await?;
+
+            let cat_id = match category_ids.get(&tx.category) {
+                Some(id) => id.clone(),
+                None => {
+                    let categories = self
+                        .db
+                        .search_entities(&tx.category, Some("expense_category"))
+                        .await
+                        .unwrap_or_default();
+                    let id = match categories
+                        .iter()
+                        .find(|c| c.name.eq_ignore_ascii_case(&tx.category))
+                    {
+                        Some(existing) => existing.id.clone(),
+                        None => {
+                            self.db
+                                .insert_entity(&tx.category, "expense_category", None)
+                                .await?
+                        }
+                    };
+                    category_ids.insert(tx.category.clone(), id.clone());
+                    id
+                }
+            };
+            let _ = self
+                .db
+                .insert_relationship(&tx_id, &cat_id, "categorized_as", None)
+                .await;
+
+            if tx.is_spend() {
+                let entry = monthly
+                    .entry((tx.category.clone(), tx.month()))
+                    .or_insert((0.0, 0));
+                entry.0 += -tx.amount;
+                entry.1 += 1;
+            }
+            stored += 1;
+        }
+
+        for ((category, month), (amount, count)) in monthly {
+            let name = format!("spending:{}:{}", category, month);
+            let existing = self
+                .db
+                .search_entities(&name, Some("monthly_spending"))
+                .await?
+                .into_iter()
+                .find(|e| e.name == name);
+            let (prev_total, prev_count) = existing
+                .as_ref()
+                .and_then(|e| e.metadata.as_ref())
+                .map(|m| {
+                    (
+                        m.get("total").and_then(|v| v.as_f64()).unwrap_or(0.0),
+                        m.get("transactions").and_then(|v| v.as_u64()).unwrap_or(0),
+                    )
+                })
+                .unwrap_or((0.0, 0));
+            let metadata = serde_json::json!({
+                "category": category,
+                "month": month,
+                "total": ((prev_total + amount) * 100.0).round() / 100.0,
+                "transactions": prev_count + count,
+                "updated_at": chrono::Utc::now().to_rfc3339(),
+            });
+
+            match existing {
+                Some(entity) => {
+                    self.db
+                        .update_entity(&entity.id, None, None, Some(metadata))
+                        .await?;
+                }
+                None => {
+                    self.db
+                        .insert_entity(&name, "monthly_spending", Some(metadata))
+                        .await?;
+                }
+            }
+        }
+
+        Ok((stored, duplicates))
+    }
+}
+
+#[async_trait]
+impl ToolHandler for ImportTransactionsTool {
+    fn name(&self) -> &str {
+        "import_transactions"
+    }
+
+    fn description(&self) -> &str {
+        "Import transactions from a bank or credit-card CSV export. Maps columns by header name \
+         or index, categorizes each transaction with keyword rules, stores them in the knowledge \
+         graph (spending as expenses seen by spending_summary and budget_check, with \
+         per-category monthly totals named 'spending:<category>:<YYYY-MM>'), and returns \
+         spending by category and month."
+    }
+
+    fn input_schema(&self) -> Value {
+        json_schema(
+            serde_json::json!({
+                "csv": {
+                    "type": "string",
+                    "description": "CSV text to import (use this or 'path')"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Path to a CSV file to import (use this or 'csv')"
+                },
+                "columns": {
+                    "type": "object",
+                    "description": "Column mapping by header name or zero-based index, e.g. {\"date\": \"Posted Date\", \"description\": \"Payee\", \"amount\": 3}. Keys: date, description, amount, debit, credit. Common headers are detected automatically."
+                },
+                "sign_convention": {
+                    "type": "string",
+                    "description": "How the amount column signs spending: negative_is_spend (bank accounts, default) or positive_is_spend (most credit cards). Ignored for separate debit/credit columns."
+                },
+                "date_format": {
+                    "type": "string",
+                    "description": "strftime date format, e.g. '%d/%m/%Y'. Default: detect ISO and named-month dates, and whether slash dates are day or month first (files where that can't be told apart need this)."
+                },
+                "decimal_separator": {
+                    "type": "string",
+                    "description": "Decimal separator in amounts: '.' (1,234.56, default) or ',' (1.234,56)"
+                },
+                "delimiter": {
+                    "type": "string",
+                    "description": "Field delimiter: a single character or 'tab' (default: ',')"
+                },
+                "has_header": {
+                    "type": "boolean",
+                    "description": "Whether the first row is a header (default: true)"
+                },
+                "rules": {
+                    "type": "array",
+                    "description": "Extra categorization rules checked before the built-in ones, e.g. [{\"category\": \"groceries\", \"keywords\": [\"corner store\"]}]"
+                },
+                "store": {
+                    "type": "boolean",
+                    "description": "Store transactions in the knowledge graph (default: true; false previews the summary only)"
+                }
+            }),
+            vec![],
+        )
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        let csv = input.get("csv").and_then(|v| v.as_str());
+        let path = input.get("path").and_then(|v| v.as_str());
+        let store = input.get("store").and_then(|v| v.as_bool()).unwrap_or(true);
+
+        let text = match (csv, path) {
+            (Some(csv), _) => csv.to_string(),
+            (None, Some(path)) => {
+                let validated_path = validate_file_path(path, false)?;
+                tokio::fs::read_to_string(&validated_path)
+                    .await
+                    .with_context(|| format!("Failed to read CSV: {}", validated_path.display()))?
+            }
+            (None, None) => return Err(anyhow::anyhow!("Provide either 'csv' or 'path'")),
+        };
+        if text.len() > MAX_CSV_BYTES {
+            return Err(anyhow::anyhow!("CSV too large (max 5MB)"));
+        }
+
+        let import = TransactionImport::from_input(&input)?;
+        let parsed = import.parse(&text)?;
+        debug!(
+            "Parsed {} transactions ({} rows skipped)",
+            parsed.transactions.len(),
+            parsed.skipped.len()
+        );
+
+        if parsed.transactions.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No transactions could be parsed{}",
+                if parsed.skipped.is_empty() {
+                    String::new()
+                } else {
+                    format!(
+                        ":\n{}",
+                        parsed
+                            .skipped
+                            .iter()
+                            .take(5)
+                            .cloned()
+                            .collect::<Vec<_>>()
+                            .join("\n")
+                    )
+                }
+            ));
+        }
+
+        let mut output = format!(
+            "# Transaction Import\n\n- Parsed: {} transactions\n",
+            parsed.transactions.len()
+        );
+        if store {
+            let (stored, duplicates) = self.store(&parsed.transactions).await?;
+            output.push_str(&format!(
+                "- Stored: {} new ({} already imported)\n",
+                stored, duplicates
+            ));
+        } else {
+            output.push_str("- Stored: no (preview only)\n");
+        }
+        if !parsed.skipped.is_empty() {
+            output.push_str(&format!("- Skipped rows: {}\n", parsed.skipped.len()));
+            for reason in parsed.skipped.iter().take(5) {
+                output.push_str(&format!("  - {}\n", reason));
+            }
+        }
+        output.push('\n');
+        output.push_str(&spend_summary(&parsed.transactions));
+
+        Ok(output)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -604,4 +1586,276 @@ mod tests {
             .await;
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_parse_csv_quoted_fields() {
+        let records = parse_csv(
+            "Date,Description,Amount\r\n2024-01-05,\"Joe's \"\"Cafe\"\", Main St\",-4.50\n\n2024-01-06,\"Multi\nline\",1\n",
+            ',',
+        );
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[1][1], "Joe's \"Cafe\", Main St");
+        assert_eq!(records[2][1], "Multi\nline");
+    }
+
+    #[test]
+    fn test_parse_amount_formats() {
+        assert_eq!(parse_amount("-1,234.50", '.').unwrap(), -1234.5);
+        assert_eq!(parse_amount("$12.00", '.').unwrap(), 12.0);
+        assert_eq!(parse_amount("(42.10)", '.').unwrap(), -42.1);
+        assert_eq!(parse_amount("12.50-", '.').unwrap(), -12.5);
+        assert!(parse_amount("abc", '.').is_err());
+        assert!(parse_amount("NaN", '.').is_err());
+
+        // Decimal commas are read only when asked for, never guessed
+        assert_eq!(parse_amount("-1.234,50", ',').unwrap(), -1234.5);
+        assert_eq!(parse_amount("€ 12,5", ',').unwrap(), 12.5);
+        assert_eq!(parse_amount("1 234,50", ',').unwrap(), 1234.5);
+        assert!(parse_amount("12,50", '.').is_err());
+        assert!(parse_amount("1,234.50", ',').is_err());
+    }
+
+    #[test]
+    fn test_parse_transaction_date_formats() {
+        let expected = NaiveDate::from_ymd_opt(2024, 3, 7).unwrap();
+        for raw in [
+            "2024-03-07",
+            "2024/03/07",
+            "03/07/2024",
+            "03/07/24",
+            "07.03.2024",
+            "07-Mar-2024",
+            "Mar 7, 2024",
+            "2024-03-07T09:30:00Z",
+            "2024-03-07 09:30",
+        ] {
+            assert_eq!(
+                parse_transaction_date(raw, None, DateOrder::MonthFirst).unwrap(),
+                expected,
+                "{}",
+                raw
+            );
+        }
+        assert_eq!(
+            parse_transaction_date("07/03/2024", None, DateOrder::DayFirst).unwrap(),
+            expected
+        );
+        let formatted =
+            parse_transaction_date("07/03/2024", Some("%d/%m/%Y"), DateOrder::MonthFirst);
+        assert_eq!(formatted.unwrap(), expected);
+        assert!(parse_transaction_date("not a date", None, DateOrder::MonthFirst).is_err());
+    }
+
+    #[test]
+    fn test_slash_date_order() {
+        assert_eq!(
+            slash_date_order(&["03/07/2024", "25/07/2024"]).unwrap(),
+            DateOrder::DayFirst
+        );
+        assert_eq!(
+            slash_date_order(&["03/07/2024", "07/25/2024"]).unwrap(),
+            DateOrder::MonthFirst
+        );
+        assert_eq!(
+            slash_date_order(&["2024-07-03", "01/01/2024"]).unwrap(),
+            DateOrder::MonthFirst
+        );
+        let ambiguous = slash_date_order(&["03/07/2024", "04/07/2024"]).unwrap_err();
+        assert!(ambiguous.to_string().contains("date_format"));
+        assert!(slash_date_order(&["25/07/2024", "07/25/2024"]).is_err());
+
+        // A day-first file is read day-first throughout
+        let parsed = TransactionImport::default()
+            .parse(
+                "Date,Description,Amount\n\
+                 03/07/2024,Cafe,-4.00\n\
+                 25/07/2024,Shell,-50.00\n",
+            )
+            .unwrap();
+        assert_eq!(
+            parsed.transactions[0].date,
+            NaiveDate::from_ymd_opt(2024, 7, 3).unwrap()
+        );
+        assert!(
+            TransactionImport::default()
+                .parse("Date,Description,Amount\n03/07/2024,Cafe,-4.00\n")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_bank_export_with_detected_headers() {
+        let import = TransactionImport::default();
+        let parsed = import
+            .parse(
+                "Posted Date,Payee,Amount\n\
+                 2024-01-03,WHOLE FOODS #123,-52.10\n\
+                 2024-01-04,Uber Eats order,-18.00\n\
+                 2024-01-05,ACME PAYROLL,2500.00\n\
+                 bad date,Somewhere,-1.00\n",
+            )
+            .unwrap();
+
+        assert_eq!(parsed.transactions.len(), 3);
+        assert_eq!(parsed.transactions[0].category, "groceries");
+        assert_eq!(parsed.transactions[0].amount, -52.10);
+        assert_eq!(parsed.transactions[1].category, "dining");
+        assert_eq!(parsed.transactions[2].category, "income");
+        assert_eq!(parsed.skipped.len(), 1);
+        assert!(parsed.skipped[0].starts_with("row 5:"));
+    }
+
+    #[test]
+    fn test_parse_credit_card_export_with_mapping() {
+        let import = TransactionImport::from_input(&serde_json::json!({
+            "has_header": false,
+            "delimiter": ";",
+            "sign_convention": "positive_is_spend",
+            "date_format": "%d/%m/%Y",
+            "columns": {"date": 0, "description": 2, "amount": 1},
+            "rules": [{"category": "pets", "keywords": ["Petco"]}]
+        }))
+        .unwrap();
+        let parsed = import
+            .parse("15/02/2024;30.00;PETCO STORE\n16/02/2024;-10.00;Refund\n")
+            .unwrap();
+
+        assert_eq!(parsed.transactions.len(), 2);
+        assert_eq!(
+            parsed.transactions[0].date,
+            NaiveDate::from_ymd_opt(2024, 2, 15).unwrap()
+        );
+        assert_eq!(parsed.transactions[0].amount, -30.0);
+        assert_eq!(parsed.transactions[0].category, "pets");
+        assert_eq!(parsed.transactions[1].amount, 10.0);
+    }
+
+    #[test]
+    fn test_parse_debit_credit_columns() {
+        let parsed = TransactionImport::default()
+            .parse("Date,Description,Debit,Credit\n01/10/2024,Netflix,15.99,\n01/31/2024,Refund,,5.00\n")
+            .unwrap();
+
+        assert_eq!(parsed.transactions[0].amount, -15.99);
+        assert_eq!(parsed.transactions[0].category, "entertainment");
+        assert_eq!(parsed.transactions[1].amount, 5.0);
+    }
+
+    #[test]
+    fn test_import_options_validation() {
+        assert!(
+            TransactionImport::from_input(&serde_json::json!({"sign_convention": "up"})).is_err()
+        );
+        assert!(TransactionImport::from_input(&serde_json::json!({"delimiter": ",,"})).is_err());
+        assert!(
+            TransactionImport::from_input(&serde_json::json!({"decimal_separator": "'"})).is_err()
+        );
+        assert!(
+            TransactionImport::default()
+                .parse("When,What\n2024-01-01,x\n")
+                .is_err()
+        );
+        let missing = TransactionImport::from_input(&serde_json::json!({
+            "columns": {"date": "Booked"}
+        }))
+        .unwrap()
+        .parse("Date,Description,Amount\n2024-01-01,x,-1\n");
+        assert!(missing.unwrap_err().to_string().contains("Booked"));
+    }
+
+    #[test]
+    fn test_spend_summary_by_category_and_month() {
+        let parsed = TransactionImport::default()
+            .parse(
+                "Date,Description,Amount\n\
+                 2024-01-03,Safeway,-40.00\n\
+                 2024-01-20,Shell,-60.00\n\
+                 2024-02-02,Safeway,-25.00\n\
+                 2024-02-03,Paycheck,1000.00\n",
+            )
+            .unwrap();
+        let summary = spend_summary(&parsed.transactions);
+
+        assert!(summary.contains("## Spending by Category (total $125.00)"));
+        assert!(summary.contains("  - groceries: $65.00 (52%)"));
+        assert!(summary.contains("### 2024-01 ($100.00)"));
+        assert!(summary.contains("### 2024-02 ($25.00)"));
+        assert!(summary.contains("  - Total: $1000.00"));
+    }
+
+    #[tokio::test]
+    async fn test_import_transactions_stores_and_dedupes() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(KnowledgeDb::new(dir.path().join("test.db")).unwrap());
+        let tool = ImportTransactionsTool::new(db.clone());
+        let csv = "Date,Description,Amount\n\
+                   2024-01-03,Trader Joe's,-30.00\n\
+                   2024-01-03,Trader Joe's,-30.00\n\
+                   2024-01-09,Kroger,-12.50\n";
+
+        let first = tool.execute(serde_json::json!({"csv": csv})).await.unwrap();
+        assert!(first.contains("- Stored: 3 new (0 already imported)"));
+
+        let second = tool.execute(serde_json::json!({"csv": csv})).await.unwrap();
+        assert!(second.contains("- Stored: 0 new (3 already imported)"));
+
+        let totals = db
+            .search_entities("spending:groceries", Some("monthly_spending"))
+            .await
+            .unwrap();
+        assert_eq!(totals.len(), 1);
+        let meta = totals[0].metadata.as_ref().unwrap();
+        assert_eq!(meta["total"].as_f64().unwrap(), 72.5);
+        assert_eq!(meta["transactions"].as_u64().unwrap(), 3);
+
+        let expenses = db
+            .search_entities("groceries", Some("expense"))
+            .await
+            .unwrap();
+        assert_eq!(expenses.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_imported_spending_shows_in_summary() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(KnowledgeDb::new(dir.path().join("test.db")).unwrap());
+        let csv = "Date,Description,Amount
+                   2024-01-03,Safeway,-40.00
+                   2024-01-05,Paycheck,1000.00
+";
+        ImportTransactionsTool::new(db.clone())
+            .execute(serde_json::json!({"csv": csv}))
+            .await
+            .unwrap();
+
+        let summary = SpendingSummaryTool::new(db.clone())
+            .execute(serde_json::json!({}))
+            .await
+            .unwrap();
+        assert!(summary.contains("- Total: $40.00"), "{}", summary);
+        assert!(summary.contains("  - groceries: $40.00 (100%)"));
+        assert!(summary.contains("  - Safeway: $40.00"));
+    }
+
+    #[tokio::test]
+    async fn test_import_transactions_preview_does_not_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(KnowledgeDb::new(dir.path().join("test.db")).unwrap());
+        let tool = ImportTransactionsTool::new(db.clone());
+
+        let output = tool
+            .execute(serde_json::json!({
+                "csv": "Date,Description,Amount\n2024-01-03,Costco,-99.00\n",
+                "store": false
+            }))
+            .await
+            .unwrap();
+        assert!(output.contains("preview only"));
+        assert!(
+            db.search_entities("", Some("expense"))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
 }
@@ -241,6 +241,10 @@ impl ToolHandler for HabitStreakTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let habit = input.get("habit").and_then(|v| v.as_str());
 
@@ -348,6 +352,10 @@ impl ToolHandler for HabitReportTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let period = input
             .get("period")
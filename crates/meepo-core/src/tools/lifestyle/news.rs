@@ -5,13 +5,21 @@
 
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures_util::{StreamExt, stream};
+use lru::LruCache;
 use serde_json::Value;
-use std::sync::Arc;
-use tracing::debug;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
 
 use crate::tavily::TavilyClient;
+use crate::text::{html_to_text, normalize_words, truncate_chars};
 use crate::tools::{ToolHandler, json_schema};
 use meepo_knowledge::KnowledgeDb;
+use meepo_scheduler::{FeedContent, FeedFetch, FeedState, fetch_public_feed};
 
 /// Track a content feed or news source
 pub struct TrackFeedTool {
@@ -293,13 +301,13 @@ impl ToolHandler for SummarizeArticleTool {
                     let resp = reqwest::get(url).await?;
                     let text = resp.text().await?;
                     // Truncate to reasonable size
-                    text[..text.len().min(50_000)].to_string()
+                    truncate_chars(&text, 50_000).to_string()
                 }
             }
         } else {
             let resp = reqwest::get(url).await?;
             let text = resp.text().await?;
-            text[..text.len().min(50_000)].to_string()
+            truncate_chars(&text, 50_000).to_string()
         };
 
         // Store in knowledge graph
@@ -324,7 +332,7 @@ impl ToolHandler for SummarizeArticleTool {
              2. **Key Takeaways** — 3-5 bullet points\n\
              3. **Relevance** — why this matters",
             url,
-            &content[..content.len().min(30_000)],
+            truncate_chars(&content, 30_000),
             length
         ))
     }
@@ -369,6 +377,10 @@ impl ToolHandler for ContentDigestTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let max_items = input
             .get("max_items_per_feed")
@@ -463,9 +475,577 @@ impl ToolHandler for ContentDigestTool {
     }
 }
 
+/// Preference key holding the news interest profile (keyword -> weight)
+const NEWS_INTERESTS_KEY: &str = "news_interests";
+
+/// How long a fetched feed is reused before asking the server again
+const FEED_CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Upper bound on feeds fetched in one `curate_news` call
+const MAX_CURATED_FEEDS: usize = 50;
+
+/// Feeds fetched at the same time by one `curate_news` call
+const FEED_FETCH_CONCURRENCY: usize = 8;
+
+/// How long one `curate_news` call waits for its feeds; slower ones are reported
+const CURATE_FETCH_DEADLINE: Duration = Duration::from_secs(45);
+
+/// Feeds kept in the cache; the least recently used is dropped first
+const MAX_CACHED_FEEDS: usize = 200;
+
+/// Query parameters that only track where a click came from
+const TRACKING_PARAMS: &[&str] = &["ref", "fbclid", "gclid", "mc_cid", "mc_eid"];
+
+/// A feed to curate from, with an optional per-feed keyword filter
+#[derive(Debug, Clone, PartialEq)]
+struct CuratedFeed {
+    name: Option<String>,
+    url: String,
+    filter_keywords: Vec<String>,
+}
+
+/// A fetched feed and the validators needed to re-fetch it conditionally
+#[derive(Debug, Clone)]
+struct CachedFeed {
+    state: FeedState,
+    content: FeedContent,
+    fetched_at: Instant,
+}
+
+/// A story gathered from one or more feeds
+#[derive(Debug, Clone, PartialEq)]
+struct Story {
+    title: String,
+    link: Option<String>,
+    summary: Option<String>,
+    published: Option<DateTime<Utc>>,
+    source: String,
+    /// Other feeds that carried the same story
+    also_in: Vec<String>,
+    score: f64,
+    matched: Vec<String>,
+}
+
+/// A URL reduced to what identifies the article: no scheme, `www.`, fragment,
+/// trailing slash or tracking parameters
+fn normalize_url(link: &str) -> Option<String> {
+    let url = url::Url::parse(link.trim()).ok()?;
+    let host = url.host_str()?.to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    let query: Vec<String> = url
+        .query_pairs()
+        .filter(|(key, _)| !key.starts_with("utm_") && !TRACKING_PARAMS.iter().any(|p| key == p))
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect();
+
+    let mut normalized = format!("{}{}", host, url.path().trim_end_matches('/'));
+    if !query.is_empty() {
+        normalized.push('?');
+        normalized.push_str(&query.join("&"));
+    }
+    Some(normalized)
+}
+
+/// Merge stories sharing a normalized title or URL, keeping the first copy and
+/// noting the other sources that carried it
+fn dedup_stories(stories: Vec<Story>) -> Vec<Story> {
+    let mut unique: Vec<Story> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for story in stories {
+        let keys: Vec<String> = [
+            Some(normalize_words(&story.title))
+                .filter(|t| !t.is_empty())
+                .map(|t| format!("title:{}", t)),
+            story
+                .link
+                .as_deref()
+                .and_then(normalize_url)
+                .map(|u| format!("url:{}", u)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        match keys.iter().find_map(|k| index.get(k).copied()) {
+            Some(i) => {
+                let existing = &mut unique[i];
+                if existing.source != story.source && !existing.also_in.contains(&story.source) {
+                    existing.also_in.push(story.source);
+                }
+                for key in keys {
+                    index.entry(key).or_insert(i);
+                }
+            }
+            None => {
+                for key in keys {
+                    index.insert(key, unique.len());
+                }
+                unique.push(story);
+            }
+        }
+    }
+
+    unique
+}
+
+/// Parse an interest profile given as `["rust", "ai"]` (weight 1 each) or
+/// `{"rust": 2, "ai": 1}`
+fn parse_interests(value: &Value) -> Result<Vec<(String, f64)>> {
+    let entries: Vec<(String, f64)> = match value {
+        Value::Array(keywords) => keywords
+            .iter()
+            .filter_map(|k| k.as_str())
+            .map(|k| (k.to_string(), 1.0))
+            .collect(),
+        Value::Object(weights) => weights
+            .iter()
+            .map(|(k, w)| {
+                w.as_f64()
+                    .filter(|w| w.is_finite())
+                    .map(|w| (k.clone(), w))
+                    .ok_or_else(|| anyhow::anyhow!("Interest weight for '{}' must be a number", k))
+            })
+            .collect::<Result<_>>()?,
+        _ => {
+            return Err(anyhow::anyhow!(
+                "'interests' must be a list of keywords or an object of keyword weights"
+            ));
+        }
+    };
+
+    let mut interests: Vec<(String, f64)> = Vec::new();
+    for (keyword, weight) in entries {
+        let keyword = normalize_words(&keyword);
+        if keyword.is_empty() || interests.iter().any(|(k, _)| *k == keyword) {
+            continue;
+        }
+        interests.push((keyword, weight));
+    }
+    Ok(interests)
+}
+
+/// Score a story against the interest profile. A keyword in the title counts double
+/// its weight; one only in the summary counts its weight once.
+fn score_story(story: &Story, interests: &[(String, f64)]) -> (f64, Vec<String>) {
+    let title = format!(" {} ", normalize_words(&story.title));
+    let summary = format!(
+        " {} ",
+        normalize_words(story.summary.as_deref().unwrap_or(""))
+    );
+
+    let mut score = 0.0;
+    let mut matched = Vec::new();
+    for (keyword, weight) in interests {
+        let needle = format!(" {} ", keyword);
+        if title.contains(&needle) {
+            score += weight * 2.0;
+        } else if summary.contains(&needle) {
+            score += weight;
+        } else {
+            continue;
+        }
+        matched.push(keyword.clone());
+    }
+    (score, matched)
+}
+
+/// Fetch RSS feeds, dedup stories, and rank them by the user's interests
+pub struct CurateNewsTool {
+    db: Arc<KnowledgeDb>,
+    cache: Mutex<LruCache<String, CachedFeed>>,
+    cache_ttl: Duration,
+    fetch_deadline: Duration,
+}
+
+impl CurateNewsTool {
+    pub fn new(db: Arc<KnowledgeDb>) -> Self {
+        let capacity = NonZeroUsize::new(MAX_CACHED_FEEDS).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            db,
+            cache: Mutex::new(LruCache::new(capacity)),
+            cache_ttl: FEED_CACHE_TTL,
+            fetch_deadline: CURATE_FETCH_DEADLINE,
+        }
+    }
+
+    /// Active `rss` feeds registered with `track_feed`. Untracking stores a newer
+    /// inactive copy, so only the most recent entity per name counts.
+    async fn tracked_feeds(&self) -> Result<Vec<CuratedFeed>> {
+        let entities = self.db.search_entities("", Some("tracked_feed")).await?;
+        let mut seen = std::collections::HashSet::new();
+        let mut feeds = Vec::new();
+
+        for entity in entities {
+            if !seen.insert(entity.name.clone()) {
+                continue;
+            }
+            let Some(meta) = entity.metadata else {
+                continue;
+            };
+            let active = meta.get("active").and_then(|a| a.as_bool()).unwrap_or(true);
+            let source_type = meta.get("source_type").and_then(|s| s.as_str());
+            let source = meta.get("source").and_then(|s| s.as_str()).unwrap_or("");
+            if !active || source_type != Some("rss") {
+                continue;
+            }
+            feeds.push(CuratedFeed {
+                name: Some(entity.name),
+                url: source.to_string(),
+                filter_keywords: meta
+                    .get("filter_keywords")
+                    .and_then(|k| k.as_array())
+                    .map(|arr| {
+                        arr.iter()
+                            .filter_map(|k| k.as_str())
+                            .map(normalize_words)
+                            .filter(|k| !k.is_empty())
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            });
+        }
+
+        Ok(feeds)
+    }
+
+    /// The stored interest profile, if one has been saved
+    async fn saved_interests(&self) -> Result<Vec<(String, f64)>> {
+        let prefs = self.db.get_preferences(Some("news")).await?;
+        match prefs.iter().find(|p| p.key == NEWS_INTERESTS_KEY) {
+            Some(pref) => parse_interests(&pref.value),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Feed content, reusing the cached copy while it is fresh and otherwise asking
+    /// the server with the cached validators. A stale copy is served if the server
+    /// can't be reached.
+    async fn load_feed(&self, url: &str, refresh: bool) -> Result<FeedContent> {
+        let cached = self
+            .cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(url)
+            .cloned();
+        if let Some(cached) = &cached
+            && !refresh
+            && cached.fetched_at.elapsed() < self.cache_ttl
+        {
+            debug!("Feed {} served from cache", url);
+            return Ok(cached.content.clone());
+        }
+
+        let mut state = cached.as_ref().map(|c| c.state.clone()).unwrap_or_default();
+        let content = match (fetch_public_feed(url, &mut state).await, cached) {
+            (Ok(FeedFetch::Fetched(content)), _) => content,
+            (Ok(FeedFetch::NotModified), Some(cached)) => cached.content,
+            (Ok(FeedFetch::NotModified), None) => {
+                return Err(anyhow::anyhow!("Server replied 304 with nothing cached"));
+            }
+            (Ok(FeedFetch::Unavailable(status)), None) => {
+                return Err(anyhow::anyhow!("Server returned {}", status));
+            }
+            (Err(e), None) => return Err(e),
+            (Ok(FeedFetch::Unavailable(_)) | Err(_), Some(cached)) => {
+                warn!("Feed {} unavailable, serving cached copy", url);
+                return Ok(cached.content);
+            }
+        };
+
+        self.cache.lock().unwrap_or_else(|e| e.into_inner()).put(
+            url.to_string(),
+            CachedFeed {
+                state,
+                content: content.clone(),
+                fetched_at: Instant::now(),
+            },
+        );
+        Ok(content)
+    }
+}
+
+#[async_trait]
+impl ToolHandler for CurateNewsTool {
+    fn name(&self) -> &str {
+        "curate_news"
+    }
+
+    fn description(&self) -> &str {
+        "Curate top news stories from RSS feeds. Fetches the tracked RSS feeds (or the given \
+         feed URLs), merges duplicate stories, ranks them by the saved keyword-interest profile, \
+         and returns the top stories with source and link. Feeds are cached and re-fetched \
+         with ETag/Last-Modified checks."
+    }
+
+    fn input_schema(&self) -> Value {
+        json_schema(
+            serde_json::json!({
+                "feeds": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "RSS/Atom feed URLs to curate (default: active feeds tracked with source_type 'rss')"
+                },
+                "top_n": {
+                    "type": "number",
+                    "description": "Number of stories to return (default: 10, max: 50)"
+                },
+                "interests": {
+                    "description": "Interest keywords for ranking, as a list (['rust', 'ai']) or weights ({'rust': 2, 'ai': 1}). Default: the saved profile"
+                },
+                "save_interests": {
+                    "type": "boolean",
+                    "description": "Save 'interests' as the profile used by future calls (default: false)"
+                },
+                "refresh": {
+                    "type": "boolean",
+                    "description": "Re-check feeds even if fetched in the last 15 minutes (default: false)"
+                }
+            }),
+            vec![],
+        )
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        let top_n = input
+            .get("top_n")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(10)
+            .clamp(1, 50) as usize;
+        let save_interests = input
+            .get("save_interests")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let refresh = input
+            .get("refresh")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let interests = match input.get("interests") {
+            Some(value) => {
+                let interests = parse_interests(value)?;
+                if save_interests {
+                    let profile: serde_json::Map<String, Value> = interests
+                        .iter()
+                        .map(|(k, w)| (k.clone(), serde_json::json!(w)))
+                        .collect();
+                    self.db
+                        .upsert_preference(
+                            "news",
+                            NEWS_INTERESTS_KEY,
+                            Value::Object(profile),
+                            1.0,
+                            Some("curate_news"),
+                        )
+                        .await?;
+                }
+                interests
+            }
+            None if save_interests => {
+                return Err(anyhow::anyhow!("'save_interests' needs 'interests'"));
+            }
+            None => self.saved_interests().await?,
+        };
+
+        let feeds = match input.get("feeds").and_then(|v| v.as_array()) {
+            Some(urls) => urls
+                .iter()
+                .filter_map(|u| u.as_str())
+                .map(|url| CuratedFeed {
+                    name: None,
+                    url: url.trim().to_string(),
+                    filter_keywords: Vec::new(),
+                })
+                .collect(),
+            None => self.tracked_feeds().await?,
+        };
+        if feeds.is_empty() {
+            return Ok(
+                "No RSS feeds to curate. Pass 'feeds', or use track_feed with \
+                       source_type 'rss' to add some."
+                    .to_string(),
+            );
+        }
+        if feeds.len() > MAX_CURATED_FEEDS {
+            return Err(anyhow::anyhow!(
+                "Too many feeds (max {})",
+                MAX_CURATED_FEEDS
+            ));
+        }
+
+        debug!(
+            "Curating news from {} feeds with {} interests",
+            feeds.len(),
+            interests.len()
+        );
+
+        let is_http = |url: &str| {
+            url::Url::parse(url)
+                .map(|u| matches!(u.scheme(), "http" | "https"))
+                .unwrap_or(false)
+        };
+
+        // Fetch a few feeds at a time; whatever isn't back by the deadline is
+        // reported instead of holding up the rest
+        let deadline = tokio::time::Instant::now() + self.fetch_deadline;
+        let mut loaded: Vec<Option<Result<FeedContent>>> = feeds.iter().map(|_| None).collect();
+        {
+            let fetches: Vec<_> = feeds
+                .iter()
+                .enumerate()
+                .filter(|(_, feed)| is_http(&feed.url))
+                .map(|(i, feed)| async move { (i, self.load_feed(&feed.url, refresh).await) })
+                .collect();
+            let mut pending = stream::iter(fetches).buffer_unordered(FEED_FETCH_CONCURRENCY);
+            while let Ok(Some((i, result))) =
+                tokio::time::timeout_at(deadline, pending.next()).await
+            {
+                loaded[i] = Some(result);
+            }
+        }
+
+        let mut stories = Vec::new();
+        let mut errors = Vec::new();
+        for (feed, result) in feeds.iter().zip(loaded) {
+            if !is_http(&feed.url) {
+                errors.push(format!("{}: not an http(s) URL", feed.url));
+                continue;
+            }
+
+            let label = feed.name.as_deref().unwrap_or(&feed.url);
+            let content = match result {
+                Some(Ok(content)) => content,
+                Some(Err(e)) => {
+                    errors.push(format!("{}: {}", label, e));
+                    continue;
+                }
+                None => {
+                    errors.push(format!(
+                        "{}: no response within {}s",
+                        label,
+                        self.fetch_deadline.as_secs()
+                    ));
+                    continue;
+                }
+            };
+            let source = feed
+                .name
+                .clone()
+                .or_else(|| content.title.clone().filter(|t| !t.trim().is_empty()))
+                .unwrap_or_else(|| feed.url.clone());
+
+            for item in content.items {
+                let mut story = Story {
+                    title: item.title.trim().to_string(),
+                    link: item.link,
+                    summary: item
+                        .summary
+                        .map(|s| html_to_text(&s))
+                        .filter(|s| !s.trim().is_empty()),
+                    published: item.published,
+                    source: source.clone(),
+                    also_in: Vec::new(),
+                    score: 0.0,
+                    matched: Vec::new(),
+                };
+                if !feed.filter_keywords.is_empty() {
+                    let filter: Vec<(String, f64)> = feed
+                        .filter_keywords
+                        .iter()
+                        .map(|k| (k.clone(), 1.0))
+                        .collect();
+                    if score_story(&story, &filter).1.is_empty() {
+                        continue;
+                    }
+                }
+                (story.score, story.matched) = score_story(&story, &interests);
+                stories.push(story);
+            }
+        }
+
+        let mut stories = dedup_stories(stories);
+        let total = stories.len();
+        stories.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| b.published.cmp(&a.published))
+        });
+        stories.truncate(top_n);
+
+        let mut output = format!(
+            "# Top News — {} of {} stories from {} feeds\n\n",
+            stories.len(),
+            total,
+            feeds.len()
+        );
+        if interests.is_empty() {
+            output.push_str("Interests: none saved — ranked by recency\n\n");
+        } else {
+            output.push_str(&format!(
+                "Interests: {}\n\n",
+                interests
+                    .iter()
+                    .map(|(k, w)| format!("{} ({})", k, w))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        if stories.is_empty() {
+            output.push_str("No stories found.\n");
+        }
+        for (i, story) in stories.iter().enumerate() {
+            output.push_str(&format!(
+                "{}. **{}** — {}\n",
+                i + 1,
+                if story.title.is_empty() {
+                    "(untitled)"
+                } else {
+                    story.title.as_str()
+                },
+                story.source
+            ));
+            if let Some(link) = &story.link {
+                output.push_str(&format!("   Link: {}\n", link));
+            }
+            let mut details = Vec::new();
+            if let Some(published) = story.published {
+                details.push(format!(
+                    "Published: {}",
+                    published.format("%Y-%m-%d %H:%M UTC")
+                ));
+            }
+            if !story.matched.is_empty() {
+                details.push(format!("Matched: {}", story.matched.join(", ")));
+            }
+            if !story.also_in.is_empty() {
+                details.push(format!("Also in: {}", story.also_in.join(", ")));
+            }
+            if !details.is_empty() {
+                output.push_str(&format!("   {}\n", details.join(" | ")));
+            }
+            if let Some(summary) = &story.summary {
+                let summary = truncate_chars(summary.trim(), 200);
+                output.push_str(&format!("   {}\n", summary.replace('\n', " ")));
+            }
+        }
+
+        if !errors.is_empty() {
+            output.push_str("\n## Feed Errors\n");
+            for error in &errors {
+                output.push_str(&format!("- {}\n", error));
+            }
+        }
+
+        Ok(output)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use meepo_scheduler::FeedItem;
 
     fn test_db() -> Arc<KnowledgeDb> {
         Arc::new(KnowledgeDb::new(&std::env::temp_dir().join("test_news.db")).unwrap())
@@ -501,4 +1081,240 @@ mod tests {
         let tool = ContentDigestTool::new(None, test_db());
         assert_eq!(tool.name(), "content_digest");
     }
+
+    fn story(title: &str, link: Option<&str>, source: &str) -> Story {
+        Story {
+            title: title.to_string(),
+            link: link.map(String::from),
+            summary: None,
+            published: None,
+            source: source.to_string(),
+            also_in: Vec::new(),
+            score: 0.0,
+            matched: Vec::new(),
+        }
+    }
+
+    fn item(id: &str, title: &str, link: &str, summary: &str, day: u32) -> FeedItem {
+        FeedItem {
+            id: id.to_string(),
+            title: title.to_string(),
+            link: Some(link.to_string()),
+            summary: Some(summary.to_string()),
+            published: chrono::NaiveDate::from_ymd_opt(2024, 1, day)
+                .and_then(|d| d.and_hms_opt(12, 0, 0))
+                .map(|dt| dt.and_utc()),
+        }
+    }
+
+    /// A tool whose cache already holds fresh copies of `feeds`, so nothing is fetched
+    fn cached_tool(db: Arc<KnowledgeDb>, feeds: Vec<(&str, FeedContent)>) -> CurateNewsTool {
+        let tool = CurateNewsTool::new(db);
+        {
+            let mut cache = tool.cache.lock().unwrap();
+            for (url, content) in feeds {
+                cache.put(
+                    url.to_string(),
+                    CachedFeed {
+                        state: FeedState::default(),
+                        content,
+                        fetched_at: Instant::now(),
+                    },
+                );
+            }
+        }
+        tool
+    }
+
+    #[test]
+    fn test_normalize_url() {
+        assert_eq!(
+            normalize_url("https://www.Example.com/post/1/?utm_source=rss&id=7#top").as_deref(),
+            Some("example.com/post/1?id=7")
+        );
+        assert_eq!(
+            normalize_url("http://example.com/post/1").as_deref(),
+            Some("example.com/post/1")
+        );
+        assert!(normalize_url("not a url").is_none());
+    }
+
+    #[test]
+    fn test_dedup_stories_by_title_and_url() {
+        let stories = vec![
+            story(
+                "Rust 2.0 Released!",
+                Some("https://a.com/rust?utm_medium=x"),
+                "A",
+            ),
+            story("rust 2.0 released", Some("https://b.com/other"), "B"),
+            story("Different headline", Some("https://www.a.com/rust/"), "C"),
+            story("Unrelated", Some("https://c.com/x"), "A"),
+        ];
+        let unique = dedup_stories(stories);
+        assert_eq!(unique.len(), 2);
+        assert_eq!(unique[0].also_in, vec!["B".to_string(), "C".to_string()]);
+        assert_eq!(unique[1].title, "Unrelated");
+    }
+
+    #[test]
+    fn test_parse_interests() {
+        assert_eq!(
+            parse_interests(&serde_json::json!(["Rust", "rust", "Machine-Learning"])).unwrap(),
+            vec![
+                ("rust".to_string(), 1.0),
+                ("machine learning".to_string(), 1.0)
+            ]
+        );
+        assert_eq!(
+            parse_interests(&serde_json::json!({"ai": 2.5})).unwrap(),
+            vec![("ai".to_string(), 2.5)]
+        );
+        assert!(parse_interests(&serde_json::json!({"ai": "high"})).is_err());
+        assert!(parse_interests(&serde_json::json!("ai")).is_err());
+    }
+
+    #[test]
+    fn test_score_story_matches_whole_words() {
+        let mut s = story("New AI model beats benchmarks", None, "A");
+        s.summary = Some("Researchers said Rust helped".to_string());
+        let interests = vec![("ai".to_string(), 2.0), ("rust".to_string(), 1.0)];
+        let (score, matched) = score_story(&s, &interests);
+        assert_eq!(score, 5.0);
+        assert_eq!(matched, vec!["ai".to_string(), "rust".to_string()]);
+
+        // "ai" must not match inside "said"
+        let s = story("Officials said nothing", None, "A");
+        assert_eq!(score_story(&s, &interests).0, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_curate_news_ranks_dedups_and_limits() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(KnowledgeDb::new(dir.path().join("test.db")).unwrap());
+        let tool = cached_tool(
+            db,
+            vec![
+                (
+                    "https://a.example/feed",
+                    FeedContent {
+                        title: Some("Feed A".to_string()),
+                        items: vec![
+                            item("1", "Gardening tips", "https://a.example/1", "", 5),
+                            item("2", "Rust 2.0 released", "https://a.example/2", "", 1),
+                        ],
+                    },
+                ),
+                (
+                    "https://b.example/feed",
+                    FeedContent {
+                        title: Some("Feed B".to_string()),
+                        items: vec![
+                            item("x", "Rust 2.0 Released", "https://b.example/x", "", 2),
+                            item(
+                                "y",
+                                "Markets",
+                                "https://b.example/y",
+                                "<p>AI stocks rally</p>",
+                                3,
+                            ),
+                        ],
+                    },
+                ),
+            ],
+        );
+
+        let output = tool
+            .execute(serde_json::json!({
+                "feeds": ["https://a.example/feed", "https://b.example/feed", "ftp://nope"],
+                "interests": {"rust": 2, "ai": 1},
+                "top_n": 2
+            }))
+            .await
+            .unwrap();
+
+        assert!(output.contains("# Top News — 2 of 3 stories from 3 feeds"));
+        let rust = output.find("1. **Rust 2.0 released** — Feed A").unwrap();
+        let markets = output.find("2. **Markets** — Feed B").unwrap();
+        assert!(rust < markets);
+        assert!(output.contains("Also in: Feed B"));
+        assert!(output.contains("Matched: ai"));
+        assert!(output.contains("   AI stocks rally"));
+        assert!(!output.contains("Gardening"));
+        assert!(output.contains("ftp://nope: not an http(s) URL"));
+    }
+
+    #[tokio::test]
+    async fn test_curate_news_refuses_internal_feeds() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(KnowledgeDb::new(dir.path().join("test.db")).unwrap());
+        let output = CurateNewsTool::new(db)
+            .execute(serde_json::json!({
+                "feeds": ["http://169.254.169.254/latest/meta-data/", "http://localhost:8080/rss"]
+            }))
+            .await
+            .unwrap();
+        assert!(output.contains("Access to link-local address (169.254.x.x) is not allowed"));
+        assert!(output.contains("Access to localhost is not allowed"));
+    }
+
+    #[tokio::test]
+    async fn test_curate_news_saved_interests_and_tracked_feeds() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(KnowledgeDb::new(dir.path().join("test.db")).unwrap());
+        let feed = FeedContent {
+            title: None,
+            items: vec![
+                item("1", "Local weather", "https://n.example/1", "", 2),
+                item("2", "Election results", "https://n.example/2", "", 1),
+            ],
+        };
+        let tool = cached_tool(db.clone(), vec![("https://n.example/rss", feed)]);
+
+        let track = TrackFeedTool::new(db.clone());
+        track
+            .execute(serde_json::json!({
+                "name": "News",
+                "source": "https://n.example/rss",
+                "source_type": "rss",
+                "filter_keywords": ["weather", "election"]
+            }))
+            .await
+            .unwrap();
+        // Non-RSS sources are left to content_digest
+        track
+            .execute(serde_json::json!({"name": "Search", "source": "rust news"}))
+            .await
+            .unwrap();
+
+        assert!(
+            tool.execute(serde_json::json!({"save_interests": true}))
+                .await
+                .is_err()
+        );
+        tool.execute(serde_json::json!({
+            "interests": ["election"],
+            "save_interests": true
+        }))
+        .await
+        .unwrap();
+
+        let output = tool.execute(serde_json::json!({})).await.unwrap();
+        assert!(output.contains("from 1 feeds"));
+        assert!(output.contains("Interests: election (1)"));
+        let election = output.find("1. **Election results** — News").unwrap();
+        let weather = output.find("2. **Local weather** — News").unwrap();
+        assert!(election < weather);
+    }
+
+    #[tokio::test]
+    async fn test_curate_news_without_feeds() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(KnowledgeDb::new(dir.path().join("test.db")).unwrap());
+        let output = CurateNewsTool::new(db)
+            .execute(serde_json::json!({}))
+            .await
+            .unwrap();
+        assert!(output.starts_with("No RSS feeds to curate"));
+    }
 }
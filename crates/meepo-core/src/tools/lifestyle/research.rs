@@ -223,6 +223,10 @@ impl ToolHandler for CompileReportTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let topic = input
             .get("topic")
@@ -473,6 +477,10 @@ impl ToolHandler for FactCheckTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let claim = input
             .get("claim")
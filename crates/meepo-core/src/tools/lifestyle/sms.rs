@@ -9,6 +9,7 @@ use serde_json::Value;
 use std::sync::Arc;
 use tracing::debug;
 
+use crate::text::truncate_chars;
 use crate::tools::{ToolHandler, json_schema};
 use meepo_knowledge::KnowledgeDb;
 
@@ -112,7 +113,7 @@ end tell"#,
                 "sent_message",
                 Some(serde_json::json!({
                     "to": to,
-                    "preview": &message[..message.len().min(100)],
+                    "preview": truncate_chars(message, 100),
                     "timestamp": chrono::Utc::now().to_rfc3339(),
                 })),
             )
@@ -283,6 +284,10 @@ impl ToolHandler for MessageSummaryTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let hours = input
             .get("hours")
@@ -341,7 +346,7 @@ impl ToolHandler for MessageSummaryTool {
                     c.created_at.format("%H:%M"),
                     c.sender,
                     c.channel,
-                    &c.content[..c.content.len().min(200)]
+                    truncate_chars(&c.content, 200)
                 )
             })
             .collect::<Vec<_>>()
@@ -56,6 +56,10 @@ impl ToolHandler for RelationshipSummaryTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let contact = input.get("contact").and_then(|v| v.as_str());
         let include_history = input
@@ -214,6 +214,10 @@ impl ToolHandler for ListTasksTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let status = input
             .get("status")
@@ -540,6 +544,10 @@ impl ToolHandler for ProjectStatusTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let project = input.get("project").and_then(|v| v.as_str());
 
@@ -1,15 +1,19 @@
 //! Travel & Commute Assistant tools
 //!
-//! Weather forecasts, directions, flight status monitoring, and packing lists.
+//! Weather forecasts, directions, flight status monitoring, packing lists, and
+//! itineraries extracted from flight, hotel and car rental confirmation emails.
 //! Cross-references calendar and email for travel context.
 
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime};
 use serde_json::Value;
 use std::sync::Arc;
-use tracing::debug;
+use tracing::{debug, warn};
 
+use crate::platform::{CalendarProvider, EmailProvider, EmailSummary, EventDetails};
 use crate::tavily::TavilyClient;
+use crate::text::{normalize_words, truncate_chars};
 use crate::tools::{ToolHandler, json_schema};
 use meepo_knowledge::KnowledgeDb;
 
@@ -51,6 +55,10 @@ impl ToolHandler for GetWeatherTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let location = input
             .get("location")
@@ -138,6 +146,10 @@ impl ToolHandler for GetDirectionsTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let from = input
             .get("from")
@@ -292,11 +304,9 @@ impl ToolHandler for FlightStatusTool {
                 .unwrap_or_default();
 
             if flights.is_empty() {
-                Ok(
-                    "No flight number provided and no tracked flights found. \
+                Ok("No flight number provided and no tracked flights found. \
                      Provide a flight_number or use read_emails to scan for flight confirmations."
-                        .to_string(),
-                )
+                    .to_string())
             } else {
                 let flight_list = flights
                     .iter()
@@ -403,9 +413,7 @@ impl ToolHandler for PackingListTool {
         let weather = input.get("weather").and_then(|v| v.as_str());
 
         if destination.len() > 200 {
-            return Err(anyhow::anyhow!(
-                "Destination too long (max 200 characters)"
-            ));
+            return Err(anyhow::anyhow!("Destination too long (max 200 characters)"));
         }
 
         debug!(
@@ -464,9 +472,1110 @@ impl ToolHandler for PackingListTool {
     }
 }
 
+/// Words that mark an email as a booking confirmation rather than marketing
+const BOOKING_MARKERS: &[&str] = &[
+    "confirmation",
+    "confirmed",
+    "itinerary",
+    "reservation",
+    "booking",
+    "booked",
+    "e ticket",
+    "eticket",
+    "boarding pass",
+];
+
+/// Keywords scored to tell flight, hotel and car bookings apart
+const FLIGHT_KEYWORDS: &[&str] = &[
+    "flight", "airline", "airlines", "airways", "boarding", "departs", "arrives", "e ticket",
+    "terminal", "gate", "seat",
+];
+const HOTEL_KEYWORDS: &[&str] = &[
+    "hotel",
+    "check in",
+    "check out",
+    "checkin",
+    "checkout",
+    "stay",
+    "night",
+    "nights",
+    "room",
+    "resort",
+    "inn",
+    "suites",
+];
+const CAR_KEYWORDS: &[&str] = &[
+    "car rental",
+    "rental car",
+    "rent a car",
+    "pick up",
+    "pickup",
+    "drop off",
+    "dropoff",
+    "vehicle",
+    "rental",
+    "hertz",
+    "avis",
+    "sixt",
+    "alamo",
+    "europcar",
+    "thrifty",
+];
+
+/// Rental companies named in car bookings, most distinctive first
+const RENTAL_COMPANIES: &[&str] = &[
+    "Hertz",
+    "Avis",
+    "Sixt",
+    "Alamo",
+    "Europcar",
+    "Thrifty",
+    "Enterprise",
+    "National",
+    "Budget",
+    "Dollar",
+];
+
+/// Labels a confirmation code follows, longest first so "confirmation number"
+/// wins over plain "confirmation"
+const CONFIRMATION_LABELS: &[&str] = &[
+    "confirmation number",
+    "confirmation code",
+    "confirmation no",
+    "confirmation #",
+    "booking reference",
+    "booking number",
+    "booking code",
+    "booking ref",
+    "reservation number",
+    "reservation code",
+    "record locator",
+    "reference number",
+    "confirmation",
+    "reference",
+    "pnr",
+];
+
+/// Three-letter uppercase words that aren't airport codes
+const NOT_AIRPORTS: &[&str] = &[
+    "THE", "AND", "FOR", "YOU", "ARE", "NOT", "ALL", "NEW", "OUR", "PNR", "USD", "EUR", "GBP",
+    "CAD", "AUD", "JPY", "INR", "CHF", "TSA", "FAQ", "VAT", "TAX", "ETA", "ETD", "GMT", "UTC",
+    "EST", "EDT", "CST", "CDT", "MST", "MDT", "PST", "PDT", "CET", "BST", "REF", "VIA", "PER",
+    "MON", "TUE", "WED", "THU", "FRI", "SAT", "SUN", "JAN", "FEB", "MAR", "APR", "MAY", "JUN",
+    "JUL", "AUG", "SEP", "OCT", "NOV", "DEC", "CAR", "SUV", "INN", "SPA",
+];
+
+/// Two-character words that look like airline codes but aren't
+const NOT_AIRLINES: &[&str] = &[
+    "AM", "PM", "NO", "TO", "AT", "ON", "OF", "IN", "BY", "OR", "IS", "IT", "ID", "MR", "MS", "DR",
+    "ST", "RM", "PO", "US", "UK",
+];
+
+const CHECK_IN_LABELS: &[&str] = &["check-in", "check in", "checkin", "arrival", "arriving"];
+const CHECK_OUT_LABELS: &[&str] = &[
+    "check-out",
+    "check out",
+    "checkout",
+    "departure",
+    "departing",
+];
+const PICK_UP_LABELS: &[&str] = &["pick-up", "pick up", "pickup"];
+const DROP_OFF_LABELS: &[&str] = &["drop-off", "drop off", "dropoff", "return"];
+
+/// Numeric date formats; two-digit years are tried first so `%Y` never reads "24" as year 24
+const NUMERIC_DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%m/%d/%y", "%m/%d/%Y", "%d.%m.%y", "%d.%m.%Y"];
+/// Month-name date formats, after commas and ordinal suffixes are stripped
+const NAMED_DATE_FORMATS: &[&str] = &["%B %d %Y", "%d %B %Y"];
+
+/// Length of a flight event. Departure and arrival times are local to their
+/// airports, so the time between them isn't the flight's length.
+const DEFAULT_FLIGHT_MINUTES: u64 = 120;
+/// Length of check-in, check-out, pick-up and drop-off events
+const HANDOVER_MINUTES: u64 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BookingKind {
+    Flight,
+    Hotel,
+    Car,
+}
+
+impl BookingKind {
+    fn label(self) -> &'static str {
+        match self {
+            BookingKind::Flight => "flight",
+            BookingKind::Hotel => "hotel",
+            BookingKind::Car => "car rental",
+        }
+    }
+}
+
+/// A date from an email, with the time of day when one goes with it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct When {
+    date: NaiveDate,
+    time: Option<NaiveTime>,
+}
+
+impl When {
+    fn at(&self, default: NaiveTime) -> NaiveDateTime {
+        self.date.and_time(self.time.unwrap_or(default))
+    }
+}
+
+impl std::fmt::Display for When {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.time {
+            Some(time) => write!(f, "{} {}", self.date, time.format("%H:%M")),
+            None => write!(f, "{}", self.date),
+        }
+    }
+}
+
+/// A [`When`] and the byte offset of its date in the text
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Mention {
+    offset: usize,
+    when: When,
+}
+
+/// A booking read from a confirmation email
+#[derive(Debug, Clone, PartialEq)]
+struct Booking {
+    kind: BookingKind,
+    /// Flight number and route, hotel name or rental company
+    name: Option<String>,
+    confirmation: Option<String>,
+    location: Option<String>,
+    /// Departure, check-in or pick-up
+    start: When,
+    /// Arrival, check-out or drop-off
+    end: Option<When>,
+    subject: String,
+    from: String,
+}
+
+/// A calendar event to create for a booking
+#[derive(Debug, Clone, PartialEq)]
+struct PlannedEvent {
+    summary: String,
+    start: NaiveDateTime,
+    duration_minutes: u64,
+}
+
+impl Booking {
+    fn title(&self) -> String {
+        let kind = match self.kind {
+            BookingKind::Flight => "Flight",
+            BookingKind::Hotel => "Hotel stay",
+            BookingKind::Car => "Car rental",
+        };
+        match (&self.name, self.kind) {
+            (Some(name), BookingKind::Flight) => format!("{} {}", kind, name),
+            (Some(name), _) => format!("{}: {}", kind, name),
+            (None, _) => kind.to_string(),
+        }
+    }
+
+    /// Knowledge-graph name used to avoid adding the same booking twice
+    fn key(&self) -> String {
+        format!(
+            "booking:{}:{}:{}",
+            self.title(),
+            self.start.date,
+            self.confirmation.as_deref().unwrap_or("none")
+        )
+    }
+
+    /// Times assumed for the start and end when the email gives only dates:
+    /// typical hotel check-in/check-out and rental desk hours
+    fn default_times(&self) -> (NaiveTime, NaiveTime) {
+        let hour = |h| NaiveTime::from_hms_opt(h, 0, 0).unwrap_or_default();
+        match self.kind {
+            BookingKind::Flight => (NaiveTime::MIN, NaiveTime::MIN),
+            BookingKind::Hotel => (hour(15), hour(11)),
+            BookingKind::Car => (hour(10), hour(10)),
+        }
+    }
+
+    fn starts_at(&self) -> NaiveDateTime {
+        self.start.at(self.default_times().0)
+    }
+
+    /// One event per flight, and one at each end of a hotel stay or car rental.
+    /// Flights need a departure time; stays and rentals fall back to typical times.
+    /// Times are used as written, i.e. local to the airport, hotel or rental desk.
+    fn calendar_events(&self) -> std::result::Result<Vec<PlannedEvent>, &'static str> {
+        let (start_default, end_default) = self.default_times();
+        let suffix = self
+            .name
+            .as_ref()
+            .map(|n| format!(": {}", n))
+            .unwrap_or_default();
+        let handover = |label: &str, when: When, default: NaiveTime| PlannedEvent {
+            summary: format!("{}{}", label, suffix),
+            start: when.at(default),
+            duration_minutes: HANDOVER_MINUTES,
+        };
+
+        match self.kind {
+            BookingKind::Flight => {
+                if self.start.time.is_none() {
+                    return Err("no departure time found");
+                }
+                Ok(vec![PlannedEvent {
+                    summary: self.title(),
+                    start: self.starts_at(),
+                    duration_minutes: DEFAULT_FLIGHT_MINUTES,
+                }])
+            }
+            BookingKind::Hotel | BookingKind::Car => {
+                let (start_label, end_label) = match self.kind {
+                    BookingKind::Hotel => ("Hotel check-in", "Hotel check-out"),
+                    _ => ("Car pick-up", "Car drop-off"),
+                };
+                let mut events = vec![handover(start_label, self.start, start_default)];
+                if let Some(end) = self.end {
+                    events.push(handover(end_label, end, end_default));
+                }
+                Ok(events)
+            }
+        }
+    }
+
+    /// Warning that the booking's times are local to where it happens, which
+    /// may not be the calendar's time zone
+    fn time_zone_note(&self) -> &'static str {
+        match self.kind {
+            BookingKind::Flight => {
+                "Times are local to each airport, as written in the email; \
+                 check them if you're in another time zone"
+            }
+            BookingKind::Hotel | BookingKind::Car => {
+                "Times are local to the booking's location, as written in the email; \
+                 check them if you're in another time zone"
+            }
+        }
+    }
+
+    fn notes(&self) -> String {
+        let mut notes = format!(
+            "Confirmation: {}\nFrom email: \"{}\" ({})",
+            self.confirmation.as_deref().unwrap_or("not found"),
+            self.subject,
+            self.from
+        );
+        if let (BookingKind::Flight, Some(end)) = (self.kind, self.end) {
+            notes.push_str(&format!("\nArrives: {} (local time)", end));
+        }
+        notes.push_str(&format!("\n{}", self.time_zone_note()));
+        notes
+    }
+
+    fn render(&self) -> String {
+        let (start_label, end_label) = match self.kind {
+            BookingKind::Flight => ("Departs", "Arrives"),
+            BookingKind::Hotel => ("Check-in", "Check-out"),
+            BookingKind::Car => ("Pick-up", "Drop-off"),
+        };
+        let mut out = format!("\n## {}\n- {}: {}\n", self.title(), start_label, self.start);
+        if let Some(end) = self.end {
+            out.push_str(&format!("- {}: {}\n", end_label, end));
+        }
+        out.push_str(&format!(
+            "- Confirmation: {}\n",
+            self.confirmation.as_deref().unwrap_or("not found")
+        ));
+        if let Some(ref location) = self.location {
+            out.push_str(&format!("- Location: {}\n", location));
+        }
+        out.push_str(&format!(
+            "- Email: \"{}\" from {}\n",
+            self.subject, self.from
+        ));
+        out.push_str(&format!("- Note: {}\n", self.time_zone_note()));
+        out
+    }
+}
+
+/// Substrings of `text` made of characters that satisfy `keep`, with their byte offsets
+fn spans(text: &str, keep: impl Fn(char) -> bool) -> Vec<(usize, &str)> {
+    let mut out = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        if keep(c) {
+            start.get_or_insert(i);
+        } else if let Some(s) = start.take() {
+            out.push((s, &text[s..i]));
+        }
+    }
+    if let Some(s) = start {
+        out.push((s, &text[s..]));
+    }
+    out
+}
+
+/// Whitespace-separated tokens with surrounding punctuation trimmed
+fn tokens(text: &str) -> Vec<(usize, &str)> {
+    let punctuation = |c: char| matches!(c, ',' | ';' | '(' | ')' | '[' | ']' | '"' | '|' | '*');
+    spans(text, |c| !c.is_whitespace())
+        .into_iter()
+        .filter_map(|(offset, raw)| {
+            let trimmed = raw.trim_start_matches(punctuation);
+            let token = trimmed.trim_end_matches(|c: char| punctuation(c) || c == '.' || c == ':');
+            (!token.is_empty()).then_some((offset + raw.len() - trimmed.len(), token))
+        })
+        .collect()
+}
+
+/// "15th" → "15"
+fn strip_ordinal(token: &str) -> &str {
+    ["st", "nd", "rd", "th"]
+        .into_iter()
+        .find_map(|suffix| {
+            let number = token.strip_suffix(suffix)?;
+            (!number.is_empty() && number.bytes().all(|b| b.is_ascii_digit())).then_some(number)
+        })
+        .unwrap_or(token)
+}
+
+/// A time of day starting at `token` ("10:30", "10:30 AM", "9am", "14:05:00"),
+/// and how many tokens it used
+fn parse_time(token: &str, next: Option<&str>) -> Option<(NaiveTime, usize)> {
+    let lower = token.to_ascii_lowercase().replace('.', "");
+    let next = next.map(|n| n.to_ascii_lowercase().replace('.', ""));
+    let (clock, pm, used) = if let Some(clock) = lower.strip_suffix("am") {
+        (clock, Some(false), 1)
+    } else if let Some(clock) = lower.strip_suffix("pm") {
+        (clock, Some(true), 1)
+    } else {
+        match next.as_deref() {
+            Some("am") => (lower.as_str(), Some(false), 2),
+            Some("pm") => (lower.as_str(), Some(true), 2),
+            _ => (lower.as_str(), None, 1),
+        }
+    };
+
+    let (hour, minute) = match clock.split_once(':') {
+        Some((hour, rest)) => {
+            let minute = rest.get(..2)?;
+            if rest.len() > 2 && !rest[2..].starts_with(':') {
+                return None;
+            }
+            (hour.parse::<u32>().ok()?, minute.parse::<u32>().ok()?)
+        }
+        None if pm.is_some() => (clock.parse::<u32>().ok()?, 0),
+        None => return None,
+    };
+    let hour = match pm {
+        Some(is_pm) if (1..=12).contains(&hour) => hour % 12 + if is_pm { 12 } else { 0 },
+        Some(_) => return None,
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0).map(|time| (time, used))
+}
+
+/// A date starting at `tokens[i]`, and how many tokens it used. Dates without a
+/// year are placed in the year that keeps them from lying well in the past.
+fn parse_date(tokens: &[(usize, &str)], i: usize, reference: NaiveDate) -> Option<(When, usize)> {
+    let token = tokens[i].1;
+    let timestamp = token.trim_end_matches('Z');
+    for format in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M"] {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(timestamp, format) {
+            let when = When {
+                date: dt.date(),
+                time: Some(dt.time()),
+            };
+            return Some((when, 1));
+        }
+    }
+    for format in NUMERIC_DATE_FORMATS {
+        if let Ok(date) = NaiveDate::parse_from_str(token, format) {
+            return Some((When { date, time: None }, 1));
+        }
+    }
+
+    let word = |j: usize| tokens.get(j).map(|(_, t)| strip_ordinal(t));
+    let (first, second) = (word(i)?, word(i + 1)?);
+    if let Some(third) = word(i + 2) {
+        let joined = format!("{} {} {}", first, second, third);
+        if let Some(date) = NAMED_DATE_FORMATS
+            .iter()
+            .find_map(|format| NaiveDate::parse_from_str(&joined, format).ok())
+        {
+            return Some((When { date, time: None }, 3));
+        }
+    }
+
+    let joined = format!("{} {} {}", first, second, reference.year());
+    let date = NAMED_DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(&joined, format).ok())?;
+    let date = if date < reference - Duration::days(60) {
+        date.with_year(reference.year() + 1)?
+    } else {
+        date
+    };
+    Some((When { date, time: None }, 2))
+}
+
+/// Dates in the order they appear, with their times. A time shortly after a
+/// date belongs to it, a later time repeats the last date ("Departs 8:05 AM,
+/// arrives 2:10 PM"), and a time before any date goes with the next one.
+fn date_mentions(text: &str, reference: NaiveDate) -> Vec<Mention> {
+    let tokens = tokens(text);
+    let mut mentions: Vec<Mention> = Vec::new();
+    let mut pending_time = None;
+    let mut last_date_end = 0;
+    let mut i = 0;
+    while i < tokens.len() {
+        if let Some((when, used)) = parse_date(&tokens, i, reference) {
+            let time = when.time.or(pending_time.take());
+            mentions.push(Mention {
+                offset: tokens[i].0,
+                when: When { time, ..when },
+            });
+            i += used;
+            last_date_end = i;
+            continue;
+        }
+        if let Some((time, used)) = parse_time(tokens[i].1, tokens.get(i + 1).map(|t| t.1)) {
+            match mentions.last_mut() {
+                Some(last) if last.when.time.is_none() && i - last_date_end <= 6 => {
+                    last.when.time = Some(time);
+                }
+                Some(last) => {
+                    let date = last.when.date;
+                    mentions.push(Mention {
+                        offset: tokens[i].0,
+                        when: When {
+                            date,
+                            time: Some(time),
+                        },
+                    });
+                }
+                None => pending_time = Some(time),
+            }
+            i += used;
+            continue;
+        }
+        i += 1;
+    }
+    mentions
+}
+
+/// Flight numbers ("UA123", "UA 123", "flight B6 45") with their byte offsets.
+/// Airline codes containing a digit only count right after "flight".
+fn flight_numbers(text: &str) -> Vec<(usize, String)> {
+    let is_number = |s: &str| (1..=4).contains(&s.len()) && s.bytes().all(|b| b.is_ascii_digit());
+    let words = spans(text, |c| c.is_ascii_alphanumeric());
+    let mut found = Vec::new();
+    for (k, &(offset, word)) in words.iter().enumerate() {
+        let (airline, number) = match words.get(k + 1) {
+            Some(&(_, next)) if word.len() == 2 && is_number(next) => (word, next),
+            _ if word.len() > 2 && is_number(&word[2..]) => (&word[..2], &word[2..]),
+            _ => continue,
+        };
+        if !airline
+            .bytes()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+            || NOT_AIRLINES.contains(&airline)
+        {
+            continue;
+        }
+        let after_flight = words[k.saturating_sub(2)..k]
+            .iter()
+            .any(|(_, w)| w.eq_ignore_ascii_case("flight") || w.eq_ignore_ascii_case("flt"));
+        let letters = airline.bytes().filter(|b| b.is_ascii_uppercase()).count();
+        if letters == 2 || (letters == 1 && after_flight) {
+            found.push((offset, format!("{}{}", airline, number)));
+        }
+    }
+    found
+}
+
+/// Distinct three-letter uppercase words that could be airport codes, in order
+fn airports(text: &str) -> Vec<&str> {
+    let mut found: Vec<&str> = Vec::new();
+    for (_, word) in spans(text, |c| c.is_ascii_alphanumeric()) {
+        if word.len() == 3
+            && word.bytes().all(|b| b.is_ascii_uppercase())
+            && !NOT_AIRPORTS.contains(&word)
+            && !found.contains(&word)
+        {
+            found.push(word);
+        }
+    }
+    found
+}
+
+/// The booking code after a label such as "Confirmation number:" or "PNR"
+fn find_confirmation(text: &str) -> Option<String> {
+    let punctuation = |c: char| c.is_whitespace() || matches!(c, ':' | '#' | '-' | '.' | '=');
+    let lower = text.to_ascii_lowercase();
+    for label in CONFIRMATION_LABELS {
+        for (at, _) in lower.match_indices(label) {
+            let mut rest = text[at + label.len()..].trim_start_matches(punctuation);
+            // "Your confirmation number is ABC123"
+            for filler in ["number", "code", "is"] {
+                if let Some(head) = rest.get(..filler.len())
+                    && head.eq_ignore_ascii_case(filler)
+                    && !rest[filler.len()..].starts_with(|c: char| c.is_ascii_alphanumeric())
+                {
+                    rest = rest[filler.len()..].trim_start_matches(punctuation);
+                }
+            }
+            let code: String = rest
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric())
+                .collect();
+            let has_digit = code.bytes().any(|b| b.is_ascii_digit());
+            let is_locator =
+                (5..=8).contains(&code.len()) && code.bytes().all(|b| b.is_ascii_uppercase());
+            if (4..=20).contains(&code.len()) && (has_digit || is_locator) {
+                return Some(code);
+            }
+        }
+    }
+    None
+}
+
+/// The value after `label:` on the first line starting with one of `labels`
+fn labeled_value(text: &str, labels: &[&str]) -> Option<String> {
+    text.lines().find_map(|line| {
+        let line =
+            line.trim_start_matches(|c: char| c.is_whitespace() || matches!(c, '-' | '*' | '•'));
+        let lower = line.to_ascii_lowercase();
+        labels.iter().find_map(|label| {
+            if !lower.starts_with(label) {
+                return None;
+            }
+            let value = line[label.len()..].trim_start().strip_prefix(':')?.trim();
+            (!value.is_empty()).then(|| truncate_chars(value, 100).to_string())
+        })
+    })
+}
+
+/// Which kind of booking the keywords point to; ties go to flights, then hotels
+fn booking_kind(words: &str) -> Option<BookingKind> {
+    let score = |keywords: &[&str]| {
+        keywords
+            .iter()
+            .filter(|k| words.contains(&format!(" {} ", k)))
+            .count()
+    };
+    let scores = [
+        (BookingKind::Flight, score(FLIGHT_KEYWORDS)),
+        (BookingKind::Hotel, score(HOTEL_KEYWORDS)),
+        (BookingKind::Car, score(CAR_KEYWORDS)),
+    ];
+    let best = scores.iter().map(|(_, s)| *s).max().filter(|s| *s > 0)?;
+    scores
+        .iter()
+        .find(|(_, s)| *s == best)
+        .map(|(kind, _)| *kind)
+}
+
+/// The hotel's name: a labelled line, a short line naming a hotel, or what
+/// follows "at" in the subject ("Your reservation at The Grand Hotel")
+fn hotel_name(subject: &str, body: &str) -> Option<String> {
+    const HOTEL_WORDS: &[&str] = &[
+        "hotel", "inn", "resort", "suites", "lodge", "hostel", "marriott", "hilton", "hyatt",
+    ];
+    if let Some(name) = labeled_value(body, &["hotel name", "hotel", "property"]) {
+        return Some(name);
+    }
+    let line = body.lines().map(str::trim).find(|line| {
+        let words = format!(" {} ", normalize_words(line));
+        line.split_whitespace().count() <= 6
+            && !line.contains(':')
+            && !line.ends_with(['.', '!'])
+            && HOTEL_WORDS
+                .iter()
+                .any(|w| words.contains(&format!(" {} ", w)))
+    });
+    if let Some(line) = line {
+        return Some(truncate_chars(line, 100).to_string());
+    }
+
+    let lower = subject.to_ascii_lowercase();
+    let rest = &subject[lower.find(" at ")? + 4..];
+    let rest_lower = rest.to_ascii_lowercase();
+    let end = [" is ", " has ", " - ", ",", "!", " for ", " on ", " from "]
+        .iter()
+        .filter_map(|stop| rest_lower.find(stop))
+        .min()
+        .unwrap_or(rest.len());
+    let name = rest[..end].trim();
+    (!name.is_empty()).then(|| truncate_chars(name, 100).to_string())
+}
+
+/// Start and end of a stay or rental: the first date after each label,
+/// falling back to the order dates appear in
+fn labeled_span(
+    text: &str,
+    mentions: &[Mention],
+    start_labels: &[&str],
+    end_labels: &[&str],
+) -> Option<(When, Option<When>)> {
+    let lower = text.to_ascii_lowercase();
+    let after_label = |labels: &[&str]| {
+        let at = labels.iter().filter_map(|l| lower.find(l)).min()?;
+        mentions.iter().find(|m| m.offset >= at).map(|m| m.when)
+    };
+    let start = after_label(start_labels).or_else(|| mentions.first().map(|m| m.when))?;
+    let end = after_label(end_labels)
+        .filter(|end| *end > start)
+        .or_else(|| {
+            mentions
+                .iter()
+                .map(|m| m.when)
+                .find(|w| w.date > start.date)
+        });
+    Some((start, end))
+}
+
+/// One flight of an itinerary
+#[derive(Debug, Clone, PartialEq)]
+struct FlightLeg {
+    number: Option<String>,
+    origin: Option<String>,
+    destination: Option<String>,
+    departure: Option<When>,
+    arrival: Option<When>,
+}
+
+impl FlightLeg {
+    /// "UA123 SFO → JFK"
+    fn name(&self) -> Option<String> {
+        let route = match (&self.origin, &self.destination) {
+            (Some(from), Some(to)) => Some(format!("{} → {}", from, to)),
+            (Some(from), None) => Some(format!("from {}", from)),
+            _ => None,
+        };
+        let parts: Vec<String> = self.number.iter().cloned().chain(route).collect();
+        (!parts.is_empty()).then(|| parts.join(" "))
+    }
+}
+
+/// Flights in an email, split at each distinct flight number so connecting
+/// itineraries give one leg per flight
+fn flight_legs(text: &str, confirmation: Option<&str>, reference: NaiveDate) -> Vec<FlightLeg> {
+    let mut numbers: Vec<(usize, String)> = Vec::new();
+    for (offset, number) in flight_numbers(text) {
+        if Some(number.as_str()) != confirmation && !numbers.iter().any(|(_, n)| *n == number) {
+            numbers.push((offset, number));
+        }
+    }
+    let segments: Vec<(usize, usize, Option<String>)> = if numbers.len() <= 1 {
+        vec![(0, text.len(), numbers.pop().map(|(_, n)| n))]
+    } else {
+        numbers
+            .iter()
+            .enumerate()
+            .map(|(k, (offset, number))| {
+                let start = if k == 0 { 0 } else { *offset };
+                let end = numbers.get(k + 1).map_or(text.len(), |(next, _)| *next);
+                (start, end, Some(number.clone()))
+            })
+            .collect()
+    };
+
+    segments
+        .into_iter()
+        .map(|(start, end, number)| {
+            let segment = &text[start..end];
+            let airports = airports(segment);
+            let mentions = date_mentions(segment, reference);
+            let departure_at = mentions
+                .iter()
+                .position(|m| m.when.time.is_some())
+                .or((!mentions.is_empty()).then_some(0));
+            let departure = departure_at.map(|i| mentions[i].when);
+            let arrival = departure_at.and_then(|i| {
+                mentions[i + 1..]
+                    .iter()
+                    .map(|m| m.when)
+                    .find(|w| Some(*w) > departure)
+            });
+            FlightLeg {
+                number,
+                origin: airports.first().map(|a| a.to_string()),
+                destination: airports.get(1).map(|a| a.to_string()),
+                departure,
+                arrival,
+            }
+        })
+        .collect()
+}
+
+/// Whether `text` mentions a booking, reservation or confirmation at all
+fn has_booking_marker(text: &str) -> bool {
+    let words = format!(" {} ", normalize_words(text));
+    BOOKING_MARKERS
+        .iter()
+        .any(|m| words.contains(&format!(" {} ", m)))
+}
+
+/// Bookings recognised in one email with the given `body`, plus notes on anything
+/// that looks like a booking but couldn't be read. Emails that aren't booking
+/// confirmations give neither.
+fn extract_bookings(
+    email: &EmailSummary,
+    body: &str,
+    reference: NaiveDate,
+) -> (Vec<Booking>, Vec<String>) {
+    let text = format!("{}\n{}", email.subject, body);
+    if !has_booking_marker(&text) {
+        return (Vec::new(), Vec::new());
+    }
+    let words = format!(" {} ", normalize_words(&text));
+    let Some(kind) = booking_kind(&words) else {
+        return (
+            Vec::new(),
+            vec![format!(
+                "\"{}\" from {} looks like a booking, but it's an unrecognized booking type",
+                email.subject, email.from
+            )],
+        );
+    };
+
+    let confirmation = find_confirmation(&text);
+    let booking = |name, location, start, end| Booking {
+        kind,
+        name,
+        confirmation: confirmation.clone(),
+        location,
+        start,
+        end,
+        subject: email.subject.clone(),
+        from: email.from.clone(),
+    };
+    let problem = |reason: String| {
+        format!(
+            "\"{}\" from {} looks like a {} booking, but {}",
+            email.subject,
+            email.from,
+            kind.label(),
+            reason
+        )
+    };
+
+    let mut bookings = Vec::new();
+    let mut problems = Vec::new();
+    match kind {
+        BookingKind::Flight => {
+            for leg in flight_legs(&text, confirmation.as_deref(), reference) {
+                match leg.departure {
+                    Some(start) => {
+                        bookings.push(booking(leg.name(), leg.origin.clone(), start, leg.arrival))
+                    }
+                    None => problems.push(problem(match leg.number {
+                        Some(ref number) => format!("no date was found for flight {}", number),
+                        None => "no flight date was found".to_string(),
+                    })),
+                }
+            }
+        }
+        BookingKind::Hotel => {
+            let mentions = date_mentions(&text, reference);
+            match labeled_span(&text, &mentions, CHECK_IN_LABELS, CHECK_OUT_LABELS) {
+                Some((start, end)) => {
+                    let name = hotel_name(&email.subject, body);
+                    let location =
+                        labeled_value(body, &["address", "location"]).or_else(|| name.clone());
+                    bookings.push(booking(name, location, start, end));
+                }
+                None => problems.push(problem("no check-in date was found".to_string())),
+            }
+        }
+        BookingKind::Car => {
+            let mentions = date_mentions(&text, reference);
+            match labeled_span(&text, &mentions, PICK_UP_LABELS, DROP_OFF_LABELS) {
+                Some((start, end)) => {
+                    let name = RENTAL_COMPANIES
+                        .iter()
+                        .find(|c| words.contains(&format!(" {} ", c.to_lowercase())))
+                        .map(|c| c.to_string());
+                    let location = labeled_value(
+                        body,
+                        &[
+                            "pick-up location",
+                            "pickup location",
+                            "pick up location",
+                            "location",
+                        ],
+                    );
+                    bookings.push(booking(name, location, start, end));
+                }
+                None => problems.push(problem("no pick-up date was found".to_string())),
+            }
+        }
+    }
+    (bookings, problems)
+}
+
+/// Extract a travel itinerary from booking confirmation emails
+pub struct ExtractItineraryTool {
+    email: Box<dyn EmailProvider>,
+    calendar: Box<dyn CalendarProvider>,
+    db: Arc<KnowledgeDb>,
+}
+
+impl ExtractItineraryTool {
+    pub fn new(db: Arc<KnowledgeDb>) -> Self {
+        Self {
+            email: crate::platform::create_email_provider()
+                .expect("Email provider not available on this platform"),
+            calendar: crate::platform::create_calendar_provider()
+                .expect("Calendar provider not available on this platform"),
+            db,
+        }
+    }
+
+    /// The email's full body if it may be a booking, since the preview often
+    /// stops before the times. Falls back to the preview when the body can't be read.
+    async fn booking_body(&self, email: &EmailSummary, mailbox: &str) -> String {
+        let preview = format!("{}\n{}", email.subject, email.preview);
+        if email.message_id.is_empty() || !has_booking_marker(&preview) {
+            return email.preview.clone();
+        }
+        match self.email.read_email_body(&email.message_id, mailbox).await {
+            Ok(body) if !body.trim().is_empty() => body,
+            Ok(_) => email.preview.clone(),
+            Err(e) => {
+                warn!(
+                    "Couldn't read the full body of \"{}\", using its preview: {}",
+                    email.subject, e
+                );
+                email.preview.clone()
+            }
+        }
+    }
+
+    /// Create the booking's calendar events that earlier runs haven't, returning
+    /// a status line for the itinerary. The events created so far are recorded
+    /// even when one fails, so running again adds only the rest.
+    async fn add_to_calendar(&self, booking: &Booking, calendar: Option<&str>) -> Result<String> {
+        let key = booking.key();
+        let existing = self
+            .db
+            .search_entities(&key, Some("travel_booking"))
+            .await?
+            .into_iter()
+            .find(|e| e.name == key);
+        // Bookings recorded without a list of events had all of them added
+        let mut added: Vec<String> = match &existing {
+            Some(entity) => match entity
+                .metadata
+                .as_ref()
+                .and_then(|m| m.get("added_events"))
+                .and_then(|v| v.as_array())
+            {
+                Some(list) => list
+                    .iter()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect(),
+                None => return Ok("already added".to_string()),
+            },
+            None => Vec::new(),
+        };
+
+        let events = match booking.calendar_events() {
+            Ok(events) => events,
+            Err(reason) => return Ok(format!("not added ({})", reason)),
+        };
+        if events.iter().all(|event| added.contains(&event.summary)) {
+            return Ok("already added".to_string());
+        }
+        let details = EventDetails {
+            location: booking.location.clone(),
+            notes: Some(booking.notes()),
+            ..Default::default()
+        };
+        let pending: Vec<&PlannedEvent> = events
+            .iter()
+            .filter(|event| !added.contains(&event.summary))
+            .collect();
+        let mut created = 0;
+        let mut failure = None;
+        for event in pending {
+            let start = event.start.format("%Y-%m-%dT%H:%M").to_string();
+            match self
+                .calendar
+                .create_event(
+                    &event.summary,
+                    &start,
+                    event.duration_minutes,
+                    calendar,
+                    &details,
+                )
+                .await
+            {
+                Ok(_) => {
+                    created += 1;
+                    added.push(event.summary.clone());
+                }
+                Err(e) => {
+                    failure = Some(format!("failed to add \"{}\": {}", event.summary, e));
+                    break;
+                }
+            }
+        }
+
+        if created > 0 {
+            let metadata = serde_json::json!({
+                "kind": booking.kind.label(),
+                "title": booking.title(),
+                "confirmation": booking.confirmation,
+                "start": booking.start.to_string(),
+                "end": booking.end.map(|e| e.to_string()),
+                "location": booking.location,
+                "email_subject": booking.subject,
+                "calendar_events": events.len(),
+                "added_events": added,
+            });
+            match existing {
+                Some(entity) => {
+                    self.db
+                        .update_entity(&entity.id, None, None, Some(metadata))
+                        .await?;
+                }
+                None => {
+                    self.db
+                        .insert_entity(&key, "travel_booking", Some(metadata))
+                        .await?;
+                }
+            }
+        }
+        Ok(match failure {
+            Some(failure) => format!(
+                "{} ({} of {} event(s) added; run again to add the rest)",
+                failure,
+                added.len(),
+                events.len()
+            ),
+            None => format!("{} event(s) added", created),
+        })
+    }
+}
+
+#[async_trait]
+impl ToolHandler for ExtractItineraryTool {
+    fn name(&self) -> &str {
+        "extract_itinerary"
+    }
+
+    fn description(&self) -> &str {
+        "Read flight, hotel and car rental confirmation emails and build a structured \
+         itinerary with dates, times, confirmation numbers and locations. Adds a calendar \
+         event for each flight, hotel check-in/check-out and car pick-up/drop-off, skipping \
+         bookings added on earlier runs. Emails that look like bookings but can't be read \
+         are listed so they can be checked by hand."
+    }
+
+    fn input_schema(&self) -> Value {
+        json_schema(
+            serde_json::json!({
+                "search": {
+                    "type": "string",
+                    "description": "Only read emails matching this text (e.g. an airline or 'confirmation')"
+                },
+                "mailbox": {
+                    "type": "string",
+                    "description": "Mailbox to read (default: inbox)"
+                },
+                "limit": {
+                    "type": "number",
+                    "description": "Number of recent emails to check (default: 30, max: 100)"
+                },
+                "create_events": {
+                    "type": "boolean",
+                    "description": "Add calendar events for each booking (default: true)"
+                },
+                "calendar": {
+                    "type": "string",
+                    "description": "Calendar to add events to (default: the default calendar)"
+                }
+            }),
+            vec![],
+        )
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        let search = input.get("search").and_then(|v| v.as_str());
+        let mailbox = input
+            .get("mailbox")
+            .and_then(|v| v.as_str())
+            .unwrap_or("inbox");
+        let limit = input
+            .get("limit")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(30)
+            .clamp(1, 100);
+        let create_events = input
+            .get("create_events")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let calendar = input.get("calendar").and_then(|v| v.as_str());
+
+        debug!("Extracting itinerary from {} emails in {}", limit, mailbox);
+
+        let emails = self
+            .email
+            .read_emails_structured(limit, mailbox, search)
+            .await?;
+        let today = chrono::Local::now().date_naive();
+
+        let mut bookings = Vec::new();
+        let mut problems = Vec::new();
+        for email in &emails {
+            let body = self.booking_body(email, mailbox).await;
+            let (found, issues) = extract_bookings(email, &body, today);
+            bookings.extend(found);
+            problems.extend(issues);
+        }
+        bookings.sort_by_key(|b| b.starts_at());
+
+        if bookings.is_empty() && problems.is_empty() {
+            return Ok(format!(
+                "No flight, hotel or car rental confirmations found in the {} email(s) checked.",
+                emails.len()
+            ));
+        }
+
+        let mut output = format!(
+            "# Travel Itinerary\n{} booking(s) found in {} email(s)\n",
+            bookings.len(),
+            emails.len()
+        );
+        for booking in &bookings {
+            output.push_str(&booking.render());
+            let status = if create_events {
+                self.add_to_calendar(booking, calendar).await?
+            } else {
+                "not added (create_events is false)".to_string()
+            };
+            output.push_str(&format!("- Calendar: {}\n", status));
+        }
+
+        if !problems.is_empty() {
+            output.push_str(
+                "\n## Unrecognized Bookings\n\
+                 These emails couldn't be fully read; check them manually:\n",
+            );
+            for problem in &problems {
+                output.push_str(&format!("- {}\n", problem));
+            }
+        }
+        Ok(output)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Mutex;
 
     fn test_db() -> Arc<KnowledgeDb> {
         Arc::new(KnowledgeDb::new(&std::env::temp_dir().join("test_travel.db")).unwrap())
@@ -478,7 +1587,10 @@ mod tests {
         assert_eq!(tool.name(), "get_weather");
         let schema = tool.input_schema();
         let required: Vec<String> = serde_json::from_value(
-            schema.get("required").cloned().unwrap_or(serde_json::json!([])),
+            schema
+                .get("required")
+                .cloned()
+                .unwrap_or(serde_json::json!([])),
         )
         .unwrap_or_default();
         assert!(required.contains(&"location".to_string()));
@@ -501,4 +1613,420 @@ mod tests {
         let tool = PackingListTool::new(test_db());
         assert_eq!(tool.name(), "packing_list");
     }
+
+    fn email(subject: &str, preview: &str) -> EmailSummary {
+        EmailSummary {
+            from: "bookings@example.com".to_string(),
+            subject: subject.to_string(),
+            preview: preview.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    /// Bookings in an email read from its preview, as of the start of 2024
+    fn extract(email: &EmailSummary) -> (Vec<Booking>, Vec<String>) {
+        extract_bookings(email, &email.preview, date(2024, 1, 1))
+    }
+
+    fn connecting_flight() -> EmailSummary {
+        email(
+            "Your United flight confirmation - Mar 15",
+            "Confirmation number: K9X2PL\n\
+             Flight UA 1234 SFO to ORD\n\
+             Departs Fri, Mar 15, 2024 8:05 AM, arrives 2:10 PM\n\
+             Flight UA567 ORD to BOS\n\
+             Departs Mar 15, 2024 3:30 PM, arrives 6:55 PM",
+        )
+    }
+
+    fn hotel_stay() -> EmailSummary {
+        email(
+            "Reservation confirmed at The Grand Hotel",
+            "Confirmation #: 88812345\n\
+             Check-in: 15 March 2024\n\
+             Check-out: 18 March 2024\n\
+             Address: 1 Main St, Springfield\n\
+             2 nights, 1 room",
+        )
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(parse_time("10:30", Some("AM")), Some((time(10, 30), 2)));
+        assert_eq!(parse_time("2:10", Some("p.m.")), Some((time(14, 10), 2)));
+        assert_eq!(parse_time("9am", None), Some((time(9, 0), 1)));
+        assert_eq!(parse_time("12AM", None), Some((time(0, 0), 1)));
+        assert_eq!(parse_time("14:05:00", None), Some((time(14, 5), 1)));
+        assert_eq!(parse_time("14:05", Some("to")), Some((time(14, 5), 1)));
+        assert_eq!(parse_time("13pm", None), None);
+        assert_eq!(parse_time("Sam", None), None);
+        assert_eq!(parse_time("2024", Some("SFO")), None);
+    }
+
+    #[test]
+    fn test_date_mentions() {
+        let reference = date(2026, 10, 16);
+        let whens: Vec<When> = date_mentions(
+            "Departs Fri, Mar 15th, 2024 at 8:05 AM and arrives 2:10 PM. Return 2024-03-20T09:15",
+            reference,
+        )
+        .into_iter()
+        .map(|m| m.when)
+        .collect();
+        assert_eq!(
+            whens,
+            vec![
+                When {
+                    date: date(2024, 3, 15),
+                    time: Some(time(8, 5))
+                },
+                When {
+                    date: date(2024, 3, 15),
+                    time: Some(time(14, 10))
+                },
+                When {
+                    date: date(2024, 3, 20),
+                    time: Some(time(9, 15))
+                },
+            ]
+        );
+
+        // No year: the next March, since last March is long past
+        let whens: Vec<When> = date_mentions("Pick-up 3pm on 15 Mar, drop-off 11/18/26", reference)
+            .into_iter()
+            .map(|m| m.when)
+            .collect();
+        assert_eq!(
+            whens,
+            vec![
+                When {
+                    date: date(2027, 3, 15),
+                    time: Some(time(15, 0))
+                },
+                When {
+                    date: date(2026, 11, 18),
+                    time: None
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_confirmation() {
+        assert_eq!(
+            find_confirmation("Your confirmation number is ABC123.").as_deref(),
+            Some("ABC123")
+        );
+        assert_eq!(
+            find_confirmation("Booking reference: QWERTY\nThanks").as_deref(),
+            Some("QWERTY")
+        );
+        assert_eq!(
+            find_confirmation("Confirmation for your trip. PNR XK4P2Q").as_deref(),
+            Some("XK4P2Q")
+        );
+        assert_eq!(find_confirmation("Thanks for your reservation"), None);
+    }
+
+    #[test]
+    fn test_flight_numbers_and_airports() {
+        let numbers: Vec<String> = flight_numbers(
+            "Flight UA 1234 and DL45 depart at 10 AM 12 from gate B12; flight B6 615, seat 12A",
+        )
+        .into_iter()
+        .map(|(_, n)| n)
+        .collect();
+        assert_eq!(numbers, vec!["UA1234", "DL45", "B6615"]);
+
+        assert_eq!(
+            airports("SFO to JFK, then JFK to LHR. THE fare in USD"),
+            vec!["SFO", "JFK", "LHR"]
+        );
+    }
+
+    #[test]
+    fn test_extract_connecting_flight() {
+        let (bookings, problems) = extract(&connecting_flight());
+        assert!(problems.is_empty(), "{:?}", problems);
+        assert_eq!(bookings.len(), 2);
+
+        let first = &bookings[0];
+        assert_eq!(first.kind, BookingKind::Flight);
+        assert_eq!(first.title(), "Flight UA1234 SFO → ORD");
+        assert_eq!(first.confirmation.as_deref(), Some("K9X2PL"));
+        assert_eq!(first.starts_at().to_string(), "2024-03-15 08:05:00");
+        assert_eq!(first.end.and_then(|e| e.time), Some(time(14, 10)));
+
+        // Departure and arrival are local to different airports, so the gap
+        // between them isn't used as the flight's length
+        let events = first.calendar_events().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].duration_minutes, DEFAULT_FLIGHT_MINUTES);
+        assert!(
+            first
+                .notes()
+                .contains("Arrives: 2024-03-15 14:10 (local time)")
+        );
+        assert!(first.notes().contains("local to each airport"));
+
+        assert_eq!(bookings[1].title(), "Flight UA567 ORD → BOS");
+        assert_eq!(bookings[1].start.time, Some(time(15, 30)));
+        assert_eq!(bookings[1].location.as_deref(), Some("ORD"));
+    }
+
+    #[test]
+    fn test_extract_hotel_and_car() {
+        let (bookings, problems) = extract(&hotel_stay());
+        assert!(problems.is_empty());
+        let hotel = &bookings[0];
+        assert_eq!(hotel.kind, BookingKind::Hotel);
+        assert_eq!(hotel.name.as_deref(), Some("The Grand Hotel"));
+        assert_eq!(hotel.confirmation.as_deref(), Some("88812345"));
+        assert_eq!(hotel.start.date, date(2024, 3, 15));
+        assert_eq!(hotel.end.map(|e| e.date), Some(date(2024, 3, 18)));
+        assert_eq!(hotel.location.as_deref(), Some("1 Main St, Springfield"));
+        let events = hotel.calendar_events().unwrap();
+        assert_eq!(events[0].summary, "Hotel check-in: The Grand Hotel");
+        assert_eq!(events[0].start.to_string(), "2024-03-15 15:00:00");
+        assert_eq!(events[1].start.to_string(), "2024-03-18 11:00:00");
+
+        let car = email(
+            "Your Hertz reservation is confirmed",
+            "Reservation number: H7734521\n\
+             Pick-up location: SFO Airport\n\
+             Pick-up: Mar 20, 2024 10:00 AM\n\
+             Return: Mar 22, 2024 9:00 AM",
+        );
+        let (bookings, _) = extract(&car);
+        let car = &bookings[0];
+        assert_eq!(car.kind, BookingKind::Car);
+        assert_eq!(car.title(), "Car rental: Hertz");
+        assert_eq!(car.location.as_deref(), Some("SFO Airport"));
+        assert_eq!(car.start.to_string(), "2024-03-20 10:00");
+        assert_eq!(
+            car.end.map(|e| e.to_string()).as_deref(),
+            Some("2024-03-22 09:00")
+        );
+    }
+
+    #[test]
+    fn test_extract_reports_unreadable_and_ignores_other_mail() {
+        let unreadable = email(
+            "E-ticket confirmation",
+            "Thanks for flying with us. Flight DL 45 confirmation code QWERTY.",
+        );
+        let (bookings, problems) = extract(&unreadable);
+        assert!(bookings.is_empty());
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("\"E-ticket confirmation\""));
+        assert!(problems[0].contains("no date was found for flight DL45"));
+
+        let unknown = email("Your booking is confirmed", "See you on Mar 15!");
+        let (bookings, problems) = extract(&unknown);
+        assert!(bookings.is_empty());
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("unrecognized booking type"));
+
+        let newsletter = email("Weekly deals", "Cheap flights to Paris from $199 on Mar 15");
+        assert_eq!(extract(&newsletter), (Vec::new(), Vec::new()));
+    }
+
+    /// Inbox of `emails`, with full bodies for the message ids in `bodies`
+    struct FakeInbox {
+        emails: Vec<EmailSummary>,
+        bodies: Vec<(String, String)>,
+    }
+
+    #[async_trait]
+    impl EmailProvider for FakeInbox {
+        async fn read_emails_structured(
+            &self,
+            _limit: u64,
+            _mailbox: &str,
+            _search: Option<&str>,
+        ) -> Result<Vec<EmailSummary>> {
+            Ok(self.emails.clone())
+        }
+
+        async fn read_email_body(&self, message_id: &str, _mailbox: &str) -> Result<String> {
+            self.bodies
+                .iter()
+                .find(|(id, _)| id == message_id)
+                .map(|(_, body)| body.clone())
+                .ok_or_else(|| anyhow::anyhow!("no such message"))
+        }
+
+        async fn send_email(
+            &self,
+            _to: &str,
+            _subject: &str,
+            _body: &str,
+            _cc: Option<&str>,
+            _in_reply_to: Option<&str>,
+            _attachments: &[std::path::PathBuf],
+        ) -> Result<String> {
+            Err(anyhow::anyhow!("not used"))
+        }
+    }
+
+    /// Calendar that records the events created on it, failing to create the
+    /// one whose summary is in `fail_on`
+    #[derive(Default)]
+    struct RecordingCalendar {
+        created: Arc<Mutex<Vec<(String, String, u64)>>>,
+        fail_on: Arc<Mutex<Option<String>>>,
+    }
+
+    #[async_trait]
+    impl CalendarProvider for RecordingCalendar {
+        async fn read_events(&self, _: u64, _: Option<&str>) -> Result<String> {
+            Ok(String::new())
+        }
+
+        async fn list_calendars(&self) -> Result<String> {
+            Ok(String::new())
+        }
+
+        async fn create_event(
+            &self,
+            summary: &str,
+            start_time: &str,
+            duration_minutes: u64,
+            _: Option<&str>,
+            details: &EventDetails,
+        ) -> Result<String> {
+            assert!(
+                details
+                    .notes
+                    .as_deref()
+                    .unwrap()
+                    .starts_with("Confirmation: ")
+            );
+            if self.fail_on.lock().unwrap().as_deref() == Some(summary) {
+                return Err(anyhow::anyhow!("calendar unavailable"));
+            }
+            self.created.lock().unwrap().push((
+                summary.to_string(),
+                start_time.to_string(),
+                duration_minutes,
+            ));
+            Ok("created".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_extract_itinerary_tool() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(KnowledgeDb::new(dir.path().join("travel.db")).unwrap());
+        let calendar = RecordingCalendar::default();
+        let created = calendar.created.clone();
+        // The preview stops before the second flight; the full body has both
+        let full_flight = connecting_flight();
+        let mut flight = full_flight.clone();
+        flight.message_id = "flight-1@example.com".to_string();
+        flight.preview = truncate_chars(&full_flight.preview, 90).to_string();
+        let tool = ExtractItineraryTool {
+            email: Box::new(FakeInbox {
+                emails: vec![
+                    hotel_stay(),
+                    email("Weekly deals", "Nothing new this week"),
+                    flight,
+                    email(
+                        "Booking confirmation",
+                        "Your hotel stay is confirmed. See you soon!",
+                    ),
+                ],
+                bodies: vec![("flight-1@example.com".to_string(), full_flight.preview)],
+            }),
+            calendar: Box::new(calendar),
+            db,
+        };
+        assert_eq!(tool.name(), "extract_itinerary");
+
+        let output = tool.execute(serde_json::json!({})).await.unwrap();
+        assert!(
+            output.contains("3 booking(s) found in 4 email(s)"),
+            "{}",
+            output
+        );
+        let flight = output.find("## Flight UA1234 SFO → ORD").unwrap();
+        let hotel = output.find("## Hotel stay: The Grand Hotel").unwrap();
+        assert!(flight < hotel);
+        assert!(output.contains("- Calendar: 2 event(s) added"));
+        assert!(output.contains("## Unrecognized Bookings"));
+        assert!(output.contains("\"Booking confirmation\" from bookings@example.com"));
+        assert!(output.contains("no check-in date was found"));
+        assert_eq!(
+            created.lock().unwrap()[0],
+            (
+                "Flight UA1234 SFO → ORD".to_string(),
+                "2024-03-15T08:05".to_string(),
+                DEFAULT_FLIGHT_MINUTES
+            )
+        );
+        assert_eq!(created.lock().unwrap().len(), 4);
+
+        // A second run finds the same bookings but doesn't add them again
+        let output = tool.execute(serde_json::json!({})).await.unwrap();
+        assert_eq!(output.matches("- Calendar: already added").count(), 3);
+        assert_eq!(created.lock().unwrap().len(), 4);
+
+        let output = tool
+            .execute(serde_json::json!({"create_events": false}))
+            .await
+            .unwrap();
+        assert!(output.contains("not added (create_events is false)"));
+    }
+
+    #[tokio::test]
+    async fn test_extract_itinerary_records_partly_added_bookings() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(KnowledgeDb::new(dir.path().join("travel.db")).unwrap());
+        let calendar = RecordingCalendar::default();
+        let created = calendar.created.clone();
+        *calendar.fail_on.lock().unwrap() = Some("Hotel check-out: The Grand Hotel".to_string());
+        let fail_on = calendar.fail_on.clone();
+        let tool = ExtractItineraryTool {
+            email: Box::new(FakeInbox {
+                emails: vec![hotel_stay()],
+                bodies: Vec::new(),
+            }),
+            calendar: Box::new(calendar),
+            db,
+        };
+
+        let output = tool.execute(serde_json::json!({})).await.unwrap();
+        assert!(
+            output.contains(
+                "failed to add \"Hotel check-out: The Grand Hotel\": calendar unavailable \
+                 (1 of 2 event(s) added; run again to add the rest)"
+            ),
+            "{}",
+            output
+        );
+        assert_eq!(created.lock().unwrap().len(), 1);
+
+        // Running again adds only the event that failed
+        *fail_on.lock().unwrap() = None;
+        let output = tool.execute(serde_json::json!({})).await.unwrap();
+        assert!(
+            output.contains("- Calendar: 1 event(s) added"),
+            "{}",
+            output
+        );
+        let created = created.lock().unwrap().clone();
+        assert_eq!(created.len(), 2);
+        assert_eq!(created[1].0, "Hotel check-out: The Grand Hotel");
+
+        let output = tool.execute(serde_json::json!({})).await.unwrap();
+        assert!(output.contains("- Calendar: already added"));
+    }
 }
@@ -7,12 +7,16 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::Value;
+use std::path::PathBuf;
 use tracing::debug;
 
+use super::filesystem::{resolve_allowed_dirs, validate_allowed_path};
 use super::{ToolHandler, json_schema};
 use crate::platform::{
-    AppLauncher, CalendarProvider, ClipboardProvider, ContactsProvider, EmailProvider,
-    MusicProvider, NotesProvider, NotificationProvider, RemindersProvider, ScreenCaptureProvider,
+    ALL_MAILBOXES, AppLauncher, CalendarProvider, ClipboardProvider, Contact, ContactsProvider,
+    EmailPage, EmailProvider, EmailReadOptions, EventChanges, EventDetails, MAX_EMAIL_OFFSET,
+    MusicProvider, NotesProvider, NotificationProvider, PageInfo, RemindersProvider,
+    ScreenCaptureProvider, Urgency, format_email_page,
 };
 
 /// Read emails from the default email application
@@ -42,7 +46,10 @@ impl ToolHandler for ReadEmailsTool {
     }
 
     fn description(&self) -> &str {
-        "Read recent emails. Returns sender, subject, date, and preview for the latest emails."
+        "Read recent emails. Returns sender, subject, date, Message-ID, and preview for the latest emails. \
+         Optionally reads the HTML body (converted to text) and lists attachments, or searches \
+         inbox, sent, and archive together. Use offset to page through older emails; the \
+         output ends with the total and whether more are available."
     }
 
     fn input_schema(&self) -> Value {
@@ -52,6 +59,10 @@ impl ToolHandler for ReadEmailsTool {
                     "type": "number",
                     "description": "Number of emails to retrieve (default: 10, max: 50)"
                 },
+                "offset": {
+                    "type": "number",
+                    "description": "Number of newest matching emails to skip, for reading the next page (default: 0, max: 10000)"
+                },
                 "mailbox": {
                     "type": "string",
                     "description": "Mailbox to read from (default: 'inbox'). Options: inbox, sent, drafts, trash"
@@ -59,26 +70,80 @@ impl ToolHandler for ReadEmailsTool {
                 "search": {
                     "type": "string",
                     "description": "Optional search term to filter by subject or sender"
+                },
+                "all_mailboxes": {
+                    "type": "boolean",
+                    "description": "Read inbox, sent, and archive together instead of one mailbox, newest first; limit applies to the total (default: false)"
+                },
+                "prefer_html": {
+                    "type": "boolean",
+                    "description": "Read the HTML body as text when the email has one, with a longer preview (default: false)"
+                },
+                "include_attachments": {
+                    "type": "boolean",
+                    "description": "List attachment names, types, and sizes without downloading them (default: false)"
                 }
             }),
             vec![],
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let limit = input
             .get("limit")
             .and_then(|v| v.as_u64())
             .unwrap_or(10)
             .min(50);
+        let offset = input
+            .get("offset")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0)
+            .min(MAX_EMAIL_OFFSET);
         let mailbox = input
             .get("mailbox")
             .and_then(|v| v.as_str())
             .unwrap_or("inbox");
         let search = input.get("search").and_then(|v| v.as_str());
+        let options = EmailReadOptions {
+            prefer_html: input
+                .get("prefer_html")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            include_attachments: input
+                .get("include_attachments")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+        };
+
+        let all_mailboxes = input
+            .get("all_mailboxes")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        if all_mailboxes {
+            debug!(
+                "Reading {} emails at offset {} across {:?}",
+                limit, offset, ALL_MAILBOXES
+            );
+            // One past the page tells whether more exist
+            let emails = self
+                .provider
+                .read_emails_across(offset + limit + 1, ALL_MAILBOXES, search)
+                .await?;
+            let page = EmailPage::from_window(emails, offset, limit);
+            return Ok(format_email_page(&ALL_MAILBOXES.join(", "), &page));
+        }
 
-        debug!("Reading {} emails from {}", limit, mailbox);
-        self.provider.read_emails(limit, mailbox, search).await
+        debug!(
+            "Reading {} emails at offset {} from {}",
+            limit, offset, mailbox
+        );
+        self.provider
+            .read_emails_with_options(limit, offset, mailbox, search, options)
+            .await
     }
 }
 
@@ -109,7 +174,9 @@ impl ToolHandler for ReadCalendarTool {
     }
 
     fn description(&self) -> &str {
-        "Read upcoming calendar events. Returns today's and upcoming events."
+        "Read upcoming calendar events. Returns today's and upcoming events, each with an ID \
+         for update_calendar_event and delete_calendar_event. Use offset to page through long \
+         ranges; the output ends with the total and whether more are available."
     }
 
     fn input_schema(&self) -> Value {
@@ -118,39 +185,129 @@ impl ToolHandler for ReadCalendarTool {
                 "days_ahead": {
                     "type": "number",
                     "description": "Number of days ahead to look (default: 1)"
+                },
+                "calendar": {
+                    "type": "string",
+                    "description": "Only read this calendar, by name (default: all calendars). See list_calendars"
+                },
+                "limit": {
+                    "type": "number",
+                    "description": "Number of events to return (default: 50, max: 200)"
+                },
+                "offset": {
+                    "type": "number",
+                    "description": "Number of events to skip, for reading the next page (default: 0)"
                 }
             }),
             vec![],
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let days_ahead = input
             .get("days_ahead")
             .and_then(|v| v.as_u64())
             .unwrap_or(1);
+        let limit = input
+            .get("limit")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(50)
+            .clamp(1, 200);
+        let offset = input.get("offset").and_then(|v| v.as_u64()).unwrap_or(0);
+
+        let calendar = input.get("calendar").and_then(|v| v.as_str());
 
         debug!("Reading calendar events for next {} days", days_ahead);
-        self.provider.read_events(days_ahead).await
+        let events = self.provider.read_events(days_ahead, calendar).await?;
+        Ok(paginate_events(&events, offset, limit))
     }
 }
 
-/// Send email via the default email application
-pub struct SendEmailTool {
-    provider: Box<dyn EmailProvider>,
+/// Cut a page out of calendar output, whose events each end with a `---` line,
+/// and append where the page sits. Output without events (e.g. "No events") is
+/// returned as is.
+fn paginate_events(output: &str, offset: u64, limit: u64) -> String {
+    let events: Vec<&str> = output
+        .split_inclusive("---\n")
+        .filter(|event| event.contains("---"))
+        .collect();
+    if events.is_empty() {
+        return output.to_string();
+    }
+    let page: Vec<&str> = events
+        .iter()
+        .skip(offset as usize)
+        .take(limit as usize)
+        .copied()
+        .collect();
+    let info = PageInfo::of_total(offset, page.len() as u64, events.len() as u64);
+    format!("{}{}", page.concat(), info.footer("events"))
+}
+
+/// List the calendars of the default calendar application
+pub struct ListCalendarsTool {
+    provider: Box<dyn CalendarProvider>,
 }
 
-impl Default for SendEmailTool {
+impl Default for ListCalendarsTool {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl SendEmailTool {
+impl ListCalendarsTool {
     pub fn new() -> Self {
+        Self {
+            provider: crate::platform::create_calendar_provider()
+                .expect("Calendar provider not available on this platform"),
+        }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for ListCalendarsTool {
+    fn name(&self) -> &str {
+        "list_calendars"
+    }
+
+    fn description(&self) -> &str {
+        "List the names of available calendars (e.g. Work, Personal), for use as the \
+         'calendar' parameter of read_calendar and create_calendar_event."
+    }
+
+    fn input_schema(&self) -> Value {
+        json_schema(serde_json::json!({}), vec![])
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, _input: Value) -> Result<String> {
+        debug!("Listing calendars");
+        self.provider.list_calendars().await
+    }
+}
+
+/// Send email via the default email application
+pub struct SendEmailTool {
+    provider: Box<dyn EmailProvider>,
+    /// Directories attachments may be read from
+    allowed_dirs: Vec<PathBuf>,
+}
+
+impl SendEmailTool {
+    /// Attachments must live under `allowed_dirs` (the filesystem tools' allowed
+    /// directories), so the agent can't mail out arbitrary files such as SSH keys
+    pub fn new(allowed_dirs: Vec<String>) -> Self {
         Self {
             provider: crate::platform::create_email_provider()
                 .expect("Email provider not available on this platform"),
+            allowed_dirs: resolve_allowed_dirs(&allowed_dirs),
         }
     }
 }
@@ -162,7 +319,8 @@ impl ToolHandler for SendEmailTool {
     }
 
     fn description(&self) -> &str {
-        "Send an email. Composes and sends a message to the specified recipient."
+        "Send an email. Composes and sends a message to the specified recipient. \
+         With dry_run, saves it to Drafts instead and returns the message for the user to review."
     }
 
     fn input_schema(&self) -> Value {
@@ -186,7 +344,16 @@ impl ToolHandler for SendEmailTool {
                 },
                 "in_reply_to": {
                     "type": "string",
-                    "description": "Optional subject line of email to reply to (enables threading)"
+                    "description": "Optional Message-ID (from read_emails) of the email to reply to, for threading. A subject line also works but may match the wrong thread"
+                },
+                "attachments": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Optional paths of files to attach, within the allowed directories (20 MB total at most)"
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "Save the email to Drafts without sending it, for the user to approve (default: false)"
                 }
             }),
             vec!["to", "subject", "body"],
@@ -208,6 +375,21 @@ impl ToolHandler for SendEmailTool {
             .ok_or_else(|| anyhow::anyhow!("Missing 'body' parameter"))?;
         let cc = input.get("cc").and_then(|v| v.as_str());
         let in_reply_to = input.get("in_reply_to").and_then(|v| v.as_str());
+        let attachments: Vec<PathBuf> = input
+            .get("attachments")
+            .and_then(|v| v.as_array())
+            .map(|paths| {
+                paths
+                    .iter()
+                    .filter_map(|p| p.as_str())
+                    .map(|p| {
+                        validate_allowed_path(p, &self.allowed_dirs)
+                            .map_err(|e| anyhow::anyhow!("Attachment {}: {}", p, e))
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
 
         // Input validation: body length limit
         if body.len() > 50_000 {
@@ -217,13 +399,57 @@ impl ToolHandler for SendEmailTool {
             ));
         }
 
+        let dry_run = input
+            .get("dry_run")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        if dry_run {
+            debug!("Saving draft email to: {}", to);
+            let result = self
+                .provider
+                .create_draft(to, subject, body, cc, in_reply_to, &attachments)
+                .await?;
+            return Ok(format!(
+                "{}\n\n{}",
+                result,
+                render_email(to, subject, body, cc, in_reply_to, &attachments)
+            ));
+        }
+
         debug!("Sending email to: {}", to);
         self.provider
-            .send_email(to, subject, body, cc, in_reply_to)
+            .send_email(to, subject, body, cc, in_reply_to, &attachments)
             .await
     }
 }
 
+/// Plain-text rendering of an email for the user to review before sending
+fn render_email(
+    to: &str,
+    subject: &str,
+    body: &str,
+    cc: Option<&str>,
+    in_reply_to: Option<&str>,
+    attachments: &[PathBuf],
+) -> String {
+    let mut rendered = format!("To: {}\n", to);
+    if let Some(cc) = cc {
+        rendered.push_str(&format!("Cc: {}\n", cc));
+    }
+    if let Some(in_reply_to) = in_reply_to {
+        rendered.push_str(&format!("In-Reply-To: {}\n", in_reply_to));
+    }
+    if !attachments.is_empty() {
+        let names: Vec<String> = attachments
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        rendered.push_str(&format!("Attachments: {}\n", names.join(", ")));
+    }
+    rendered.push_str(&format!("Subject: {}\n\n{}", subject, body));
+    rendered
+}
+
 /// Create a calendar event in the default calendar application
 pub struct CreateEventTool {
     provider: Box<dyn CalendarProvider>,
@@ -263,11 +489,32 @@ impl ToolHandler for CreateEventTool {
                 },
                 "start_time": {
                     "type": "string",
-                    "description": "Start time in ISO8601 format or natural language"
+                    "description": "Start time: ISO8601 (2025-03-14T15:00) or natural language with a time of day (\"tomorrow 3pm\", \"friday 10:30\")"
                 },
                 "duration_minutes": {
                     "type": "number",
-                    "description": "Duration in minutes (default: 60)"
+                    "description": "Duration in minutes (default: 60). Ignored if end_time is given"
+                },
+                "end_time": {
+                    "type": "string",
+                    "description": "End time, as an alternative to duration_minutes"
+                },
+                "calendar": {
+                    "type": "string",
+                    "description": "Calendar to add the event to, by name (default: the default calendar). See list_calendars"
+                },
+                "attendees": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Email addresses to invite"
+                },
+                "location": {
+                    "type": "string",
+                    "description": "Event location"
+                },
+                "notes": {
+                    "type": "string",
+                    "description": "Event notes/description"
                 }
             }),
             vec!["summary", "start_time"],
@@ -287,14 +534,181 @@ impl ToolHandler for CreateEventTool {
             .get("duration_minutes")
             .and_then(|v| v.as_u64())
             .unwrap_or(60);
+        let calendar = input.get("calendar").and_then(|v| v.as_str());
+        let text = |key: &str| input.get(key).and_then(|v| v.as_str()).map(String::from);
+        let attendees = match input.get("attendees") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::Array(items)) => items
+                .iter()
+                .map(|item| {
+                    item.as_str()
+                        .map(|address| address.trim().to_string())
+                        .ok_or_else(|| {
+                            anyhow::anyhow!("'attendees' must be a list of email addresses")
+                        })
+                })
+                .collect::<Result<Vec<_>>>()?,
+            Some(_) => {
+                return Err(anyhow::anyhow!(
+                    "'attendees' must be a list of email addresses"
+                ));
+            }
+        };
+        let details = EventDetails {
+            attendees,
+            location: text("location"),
+            notes: text("notes"),
+            end_time: text("end_time"),
+        };
+        details.validate()?;
 
         debug!("Creating calendar event: {}", summary);
         self.provider
-            .create_event(summary, start_time, duration)
+            .create_event(summary, start_time, duration, calendar, &details)
             .await
     }
 }
 
+/// Update an existing calendar event in the default calendar application
+pub struct UpdateEventTool {
+    provider: Box<dyn CalendarProvider>,
+}
+
+impl Default for UpdateEventTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UpdateEventTool {
+    pub fn new() -> Self {
+        Self {
+            provider: crate::platform::create_calendar_provider()
+                .expect("Calendar provider not available on this platform"),
+        }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for UpdateEventTool {
+    fn name(&self) -> &str {
+        "update_calendar_event"
+    }
+
+    fn description(&self) -> &str {
+        "Change an existing calendar event: title, start time, duration, location, or notes. \
+         Get the event ID from read_calendar. Only the given fields change."
+    }
+
+    fn input_schema(&self) -> Value {
+        json_schema(
+            serde_json::json!({
+                "event_id": {
+                    "type": "string",
+                    "description": "Event ID as returned by read_calendar"
+                },
+                "summary": {
+                    "type": "string",
+                    "description": "New event title/summary"
+                },
+                "start_time": {
+                    "type": "string",
+                    "description": "New start time: ISO8601 or natural language with a time of day, like \"tomorrow 3pm\" (keeps the duration)"
+                },
+                "duration_minutes": {
+                    "type": "number",
+                    "description": "New duration in minutes"
+                },
+                "location": {
+                    "type": "string",
+                    "description": "New location"
+                },
+                "notes": {
+                    "type": "string",
+                    "description": "New notes/description"
+                }
+            }),
+            vec!["event_id"],
+        )
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        let event_id = input
+            .get("event_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'event_id' parameter"))?;
+        let text = |key: &str| input.get(key).and_then(|v| v.as_str()).map(String::from);
+        let changes = EventChanges {
+            summary: text("summary"),
+            start_time: text("start_time"),
+            duration_minutes: input.get("duration_minutes").and_then(|v| v.as_u64()),
+            location: text("location"),
+            notes: text("notes"),
+        };
+        if changes.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Nothing to update: give at least one of summary, start_time, duration_minutes, location, notes"
+            ));
+        }
+
+        debug!("Updating calendar event: {}", event_id);
+        self.provider.update_event(event_id, &changes).await
+    }
+}
+
+/// Delete (cancel) a calendar event in the default calendar application
+pub struct DeleteEventTool {
+    provider: Box<dyn CalendarProvider>,
+}
+
+impl Default for DeleteEventTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeleteEventTool {
+    pub fn new() -> Self {
+        Self {
+            provider: crate::platform::create_calendar_provider()
+                .expect("Calendar provider not available on this platform"),
+        }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for DeleteEventTool {
+    fn name(&self) -> &str {
+        "delete_calendar_event"
+    }
+
+    fn description(&self) -> &str {
+        "Delete (cancel) a calendar event. Get the event ID from read_calendar."
+    }
+
+    fn input_schema(&self) -> Value {
+        json_schema(
+            serde_json::json!({
+                "event_id": {
+                    "type": "string",
+                    "description": "Event ID as returned by read_calendar"
+                }
+            }),
+            vec!["event_id"],
+        )
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        let event_id = input
+            .get("event_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'event_id' parameter"))?;
+
+        debug!("Deleting calendar event: {}", event_id);
+        self.provider.delete_event(event_id).await
+    }
+}
+
 /// Open an application by name
 pub struct OpenAppTool {
     launcher: Box<dyn AppLauncher>,
@@ -388,12 +802,82 @@ impl ToolHandler for GetClipboardTool {
         json_schema(serde_json::json!({}), vec![])
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, _input: Value) -> Result<String> {
         debug!("Reading clipboard content");
         self.provider.get_clipboard().await
     }
 }
 
+/// Maximum text length accepted by set_clipboard
+const MAX_CLIPBOARD_LENGTH: usize = 100_000;
+
+/// Set clipboard content
+pub struct SetClipboardTool {
+    provider: Box<dyn ClipboardProvider>,
+}
+
+impl Default for SetClipboardTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SetClipboardTool {
+    pub fn new() -> Self {
+        Self {
+            provider: crate::platform::create_clipboard_provider(),
+        }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for SetClipboardTool {
+    fn name(&self) -> &str {
+        "set_clipboard"
+    }
+
+    fn description(&self) -> &str {
+        "Copy text to the system clipboard, replacing its current content."
+    }
+
+    fn input_schema(&self) -> Value {
+        json_schema(
+            serde_json::json!({
+                "text": {
+                    "type": "string",
+                    "description": "Text to put on the clipboard"
+                }
+            }),
+            vec!["text"],
+        )
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        let text = input
+            .get("text")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'text' parameter"))?;
+        if text.len() > MAX_CLIPBOARD_LENGTH {
+            return Err(anyhow::anyhow!(
+                "Text too long ({} chars, max {})",
+                text.len(),
+                MAX_CLIPBOARD_LENGTH
+            ));
+        }
+
+        debug!("Setting clipboard content ({} chars)", text.len());
+        self.provider.set_clipboard(text).await?;
+        Ok(format!(
+            "Copied {} characters to the clipboard",
+            text.chars().count()
+        ))
+    }
+}
+
 /// List reminders from Apple Reminders
 pub struct ListRemindersTool {
     provider: Box<dyn RemindersProvider>,
@@ -436,6 +920,10 @@ impl ToolHandler for ListRemindersTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let list_name = input.get("list_name").and_then(|v| v.as_str());
         debug!("Listing reminders");
@@ -565,6 +1053,10 @@ impl ToolHandler for ListNotesTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let folder = input.get("folder").and_then(|v| v.as_str());
         let limit = input
@@ -677,7 +1169,7 @@ impl ToolHandler for SendNotificationTool {
     }
 
     fn description(&self) -> &str {
-        "Send a macOS system notification with title and message."
+        "Show a desktop notification with title and message."
     }
 
     fn input_schema(&self) -> Value {
@@ -694,6 +1186,11 @@ impl ToolHandler for SendNotificationTool {
                 "sound": {
                     "type": "string",
                     "description": "Sound name (default: 'default')"
+                },
+                "urgency": {
+                    "type": "string",
+                    "enum": ["low", "normal", "critical"],
+                    "description": "How insistent the notification is (default: normal)"
                 }
             }),
             vec!["title", "message"],
@@ -710,6 +1207,10 @@ impl ToolHandler for SendNotificationTool {
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing 'message' parameter"))?;
         let sound = input.get("sound").and_then(|v| v.as_str());
+        let urgency: Urgency = match input.get("urgency").and_then(|v| v.as_str()) {
+            Some(urgency) => urgency.parse()?,
+            None => Urgency::default(),
+        };
 
         if title.len() > 200 {
             return Err(anyhow::anyhow!("Title too long (max 200 characters)"));
@@ -719,10 +1220,19 @@ impl ToolHandler for SendNotificationTool {
         }
 
         debug!("Sending notification: {}", title);
-        self.provider.send_notification(title, message, sound).await
+        self.provider
+            .send_notification(title, message, sound, urgency)
+            .await
     }
 }
 
+/// Image formats the screen capture provider can write: `screencapture` on macOS
+/// encodes by extension, the other platforms always produce PNG
+#[cfg(target_os = "macos")]
+const SCREENSHOT_EXTENSIONS: &[&str] = &[".png", ".jpg", ".pdf"];
+#[cfg(not(target_os = "macos"))]
+const SCREENSHOT_EXTENSIONS: &[&str] = &[".png"];
+
 /// Capture the screen
 pub struct ScreenCaptureTool {
     provider: Box<dyn ScreenCaptureProvider>,
@@ -750,7 +1260,8 @@ impl ToolHandler for ScreenCaptureTool {
     }
 
     fn description(&self) -> &str {
-        "Capture a screenshot of the screen. Returns the file path of the saved image."
+        "Capture a screenshot of the screen or of a single window. Returns the file path of the saved image, \
+         which can then be viewed to see what is on screen."
     }
 
     fn input_schema(&self) -> Value {
@@ -758,20 +1269,37 @@ impl ToolHandler for ScreenCaptureTool {
             serde_json::json!({
                 "path": {
                     "type": "string",
-                    "description": "Output file path (default: /tmp/meepo-screenshot-{timestamp}.png)"
+                    "description": format!(
+                        "Output file path ending in {} (default: meepo-screenshot-{{timestamp}}.png in the temp directory)",
+                        SCREENSHOT_EXTENSIONS.join(", ")
+                    )
+                },
+                "window": {
+                    "type": "string",
+                    "description": "Capture only the first window whose title contains this text (macOS only)"
                 }
             }),
             vec![],
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let path = input.get("path").and_then(|v| v.as_str());
+        let window = input
+            .get("window")
+            .and_then(|v| v.as_str())
+            .filter(|w| !w.trim().is_empty());
 
         if let Some(p) = path {
-            if !p.ends_with(".png") && !p.ends_with(".jpg") && !p.ends_with(".pdf") {
+            let lower = p.to_lowercase();
+            if !SCREENSHOT_EXTENSIONS.iter().any(|ext| lower.ends_with(ext)) {
                 return Err(anyhow::anyhow!(
-                    "Output path must end with .png, .jpg, or .pdf"
+                    "Output path must end with {}",
+                    SCREENSHOT_EXTENSIONS.join(", ")
                 ));
             }
             if p.len() > 500 {
@@ -779,6 +1307,16 @@ impl ToolHandler for ScreenCaptureTool {
             }
         }
 
+        if let Some(title) = window {
+            if title.len() > 200 {
+                return Err(anyhow::anyhow!(
+                    "Window title too long (max 200 characters)"
+                ));
+            }
+            debug!("Capturing window '{}'", title);
+            return self.provider.capture_window(title, path).await;
+        }
+
         debug!("Capturing screen");
         self.provider.capture_screen(path).await
     }
@@ -818,6 +1356,10 @@ impl ToolHandler for GetCurrentTrackTool {
         json_schema(serde_json::json!({}), vec![])
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, _input: Value) -> Result<String> {
         debug!("Getting current track");
         self.provider.get_current_track().await
@@ -919,6 +1461,10 @@ impl ToolHandler for SearchContactsTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let query = input
             .get("query")
@@ -934,6 +1480,104 @@ impl ToolHandler for SearchContactsTool {
     }
 }
 
+/// Resolve a person's name to their email addresses and phone numbers
+pub struct LookupContactTool {
+    provider: Box<dyn ContactsProvider>,
+}
+
+impl Default for LookupContactTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LookupContactTool {
+    pub fn new() -> Self {
+        Self {
+            provider: crate::platform::create_contacts_provider()
+                .expect("Contacts provider not available on this platform"),
+        }
+    }
+}
+
+/// Format lookup results, listing every candidate when the name is ambiguous
+fn format_contact_matches(name: &str, contacts: &[Contact]) -> String {
+    let describe = |c: &Contact| {
+        let mut line = c.name.clone();
+        if !c.emails.is_empty() {
+            line.push_str(&format!("\n  Email: {}", c.emails.join(", ")));
+        }
+        if !c.phones.is_empty() {
+            line.push_str(&format!("\n  Phone: {}", c.phones.join(", ")));
+        }
+        if c.emails.is_empty() && c.phones.is_empty() {
+            line.push_str("\n  (no email or phone on file)");
+        }
+        line
+    };
+    match contacts {
+        [] => format!("No contacts found matching '{}'", name),
+        [contact] => format!("1 contact matches '{}':\n{}", name, describe(contact)),
+        _ => {
+            let mut out = format!(
+                "{} contacts match '{}'. Ask the user which one they mean before using an address:\n",
+                contacts.len(),
+                name
+            );
+            for (i, contact) in contacts.iter().enumerate() {
+                out.push_str(&format!("{}. {}\n", i + 1, describe(contact)));
+            }
+            out
+        }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for LookupContactTool {
+    fn name(&self) -> &str {
+        "lookup_contact"
+    }
+
+    fn description(&self) -> &str {
+        "Resolve a person's name to their email addresses and phone numbers from Apple Contacts. \
+         Use this before send_email when the user names a recipient instead of giving an address. \
+         If several contacts match, all candidates are returned; ask the user which one they mean."
+    }
+
+    fn input_schema(&self) -> Value {
+        json_schema(
+            serde_json::json!({
+                "name": {
+                    "type": "string",
+                    "description": "Full or partial name of the person (e.g. 'Alice' or 'Alice Smith')"
+                }
+            }),
+            vec!["name"],
+        )
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        let name = input
+            .get("name")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'name' parameter"))?;
+
+        if name.len() > 200 {
+            return Err(anyhow::anyhow!("Name too long (max 200 characters)"));
+        }
+
+        debug!("Looking up contact: {}", name);
+        let contacts = self.provider.lookup(name).await?;
+        Ok(format_contact_matches(name, &contacts))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -946,6 +1590,9 @@ mod tests {
         assert!(!tool.description().is_empty());
         let schema = tool.input_schema();
         assert!(schema.get("properties").is_some());
+        assert!(schema["properties"].get("prefer_html").is_some());
+        assert!(schema["properties"].get("include_attachments").is_some());
+        assert!(schema["properties"].get("offset").is_some());
     }
 
     #[test]
@@ -957,7 +1604,7 @@ mod tests {
 
     #[test]
     fn test_send_email_schema() {
-        let tool = SendEmailTool::new();
+        let tool = SendEmailTool::new(vec![]);
         assert_eq!(tool.name(), "send_email");
         let schema = tool.input_schema();
         let required: Vec<String> = serde_json::from_value(
@@ -970,6 +1617,26 @@ mod tests {
         assert!(required.contains(&"to".to_string()));
         assert!(required.contains(&"subject".to_string()));
         assert!(required.contains(&"body".to_string()));
+        assert!(schema["properties"].get("dry_run").is_some());
+    }
+
+    #[test]
+    fn test_render_email() {
+        assert_eq!(
+            render_email("a@b.com", "Hi", "Body", None, None, &[]),
+            "To: a@b.com\nSubject: Hi\n\nBody"
+        );
+        assert_eq!(
+            render_email(
+                "a@b.com",
+                "Re: Hi",
+                "Body",
+                Some("c@d.com"),
+                Some("<id@x>"),
+                &[PathBuf::from("/tmp/a.pdf"), PathBuf::from("/tmp/b.csv")]
+            ),
+            "To: a@b.com\nCc: c@d.com\nIn-Reply-To: <id@x>\nAttachments: /tmp/a.pdf, /tmp/b.csv\nSubject: Re: Hi\n\nBody"
+        );
     }
 
     #[test]
@@ -988,6 +1655,195 @@ mod tests {
         assert!(required.contains(&"start_time".to_string()));
     }
 
+    /// Calendar with a single event, `evt-1`
+    struct FakeCalendar;
+
+    #[async_trait]
+    impl CalendarProvider for FakeCalendar {
+        async fn read_events(&self, _days_ahead: u64, calendar: Option<&str>) -> Result<String> {
+            match calendar {
+                None | Some("Work") => Ok("Event: Standup\nID: evt-1\n---\n".to_string()),
+                Some(name) => Err(crate::platform::calendar_not_found_error(
+                    name,
+                    &["Work".to_string()],
+                )),
+            }
+        }
+
+        async fn list_calendars(&self) -> Result<String> {
+            Ok("Work".to_string())
+        }
+
+        async fn create_event(
+            &self,
+            summary: &str,
+            _: &str,
+            _: u64,
+            _: Option<&str>,
+            details: &EventDetails,
+        ) -> Result<String> {
+            Ok(format!("Created {} with {:?}", summary, details.attendees))
+        }
+
+        async fn update_event(&self, event_id: &str, changes: &EventChanges) -> Result<String> {
+            if event_id != "evt-1" {
+                return Err(crate::platform::event_not_found_error(event_id));
+            }
+            Ok(format!("Event updated: {:?}", changes))
+        }
+
+        async fn delete_event(&self, event_id: &str) -> Result<String> {
+            if event_id != "evt-1" {
+                return Err(crate::platform::event_not_found_error(event_id));
+            }
+            Ok("Event deleted: Standup".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_calendar_by_name() {
+        let tool = ReadCalendarTool {
+            provider: Box::new(FakeCalendar),
+        };
+        assert!(tool.input_schema()["properties"].get("calendar").is_some());
+        assert!(
+            tool.execute(serde_json::json!({"calendar": "Work"}))
+                .await
+                .is_ok()
+        );
+        let err = tool
+            .execute(serde_json::json!({"calendar": "Hobbies"}))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "No calendar named 'Hobbies' was found. Available calendars: Work"
+        );
+
+        let list = ListCalendarsTool {
+            provider: Box::new(FakeCalendar),
+        };
+        assert_eq!(list.name(), "list_calendars");
+        assert_eq!(list.execute(serde_json::json!({})).await.unwrap(), "Work");
+    }
+
+    #[test]
+    fn test_paginate_events() {
+        let events: String = (1..=5)
+            .map(|i| format!("Event: E{}\nID: evt-{}\n---\n", i, i))
+            .collect();
+        let page = paginate_events(&events, 2, 2);
+        assert!(page.starts_with("Event: E3\nID: evt-3\n---\nEvent: E4"));
+        assert!(!page.contains("E5"));
+        assert!(
+            page.ends_with(
+                "Showing events 3-4 of 5. More available: use offset=4 for the next page."
+            )
+        );
+
+        let page = paginate_events(&events, 0, 50);
+        assert!(page.ends_with("Showing events 1-5 of 5. No more events."));
+        assert_eq!(paginate_events("No events found", 0, 50), "No events found");
+    }
+
+    #[tokio::test]
+    async fn test_read_calendar_paging() {
+        let tool = ReadCalendarTool {
+            provider: Box::new(FakeCalendar),
+        };
+        let schema = tool.input_schema();
+        assert!(schema["properties"].get("offset").is_some());
+        assert!(schema["properties"].get("limit").is_some());
+        let result = tool
+            .execute(serde_json::json!({"limit": 1, "offset": 1}))
+            .await
+            .unwrap();
+        assert_eq!(result, "No events at offset 1 of 1. No more events.");
+    }
+
+    #[tokio::test]
+    async fn test_create_event_attendees() {
+        let tool = CreateEventTool {
+            provider: Box::new(FakeCalendar),
+        };
+        let base = serde_json::json!({"summary": "Sync", "start_time": "2026-03-02 10:00"});
+
+        let mut input = base.clone();
+        input["attendees"] = serde_json::json!(["ann@example.com", " bob@example.com "]);
+        assert_eq!(
+            tool.execute(input).await.unwrap(),
+            r#"Created Sync with ["ann@example.com", "bob@example.com"]"#
+        );
+
+        let mut input = base.clone();
+        input["attendees"] = serde_json::json!(["ann@example.com", "not-an-email"]);
+        let err = tool.execute(input).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid attendee email address(es): not-an-email"
+        );
+
+        let mut input = base;
+        input["attendees"] = serde_json::json!("ann@example.com");
+        assert!(tool.execute(input).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_update_event_tool() {
+        let tool = UpdateEventTool {
+            provider: Box::new(FakeCalendar),
+        };
+        assert_eq!(tool.name(), "update_calendar_event");
+        assert!(tool.execute(serde_json::json!({})).await.is_err());
+        // An ID alone changes nothing
+        assert!(
+            tool.execute(serde_json::json!({"event_id": "evt-1"}))
+                .await
+                .is_err()
+        );
+
+        let result = tool
+            .execute(serde_json::json!({
+                "event_id": "evt-1",
+                "start_time": "2026-03-02 10:00",
+                "location": "Room 4"
+            }))
+            .await
+            .unwrap();
+        let expected = EventChanges {
+            start_time: Some("2026-03-02 10:00".to_string()),
+            location: Some("Room 4".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(result, format!("Event updated: {:?}", expected));
+
+        let err = tool
+            .execute(serde_json::json!({"event_id": "gone", "summary": "x"}))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("No calendar event with ID 'gone'"));
+    }
+
+    #[tokio::test]
+    async fn test_delete_event_tool() {
+        let tool = DeleteEventTool {
+            provider: Box::new(FakeCalendar),
+        };
+        assert_eq!(tool.name(), "delete_calendar_event");
+        assert!(tool.execute(serde_json::json!({})).await.is_err());
+        assert_eq!(
+            tool.execute(serde_json::json!({"event_id": "evt-1"}))
+                .await
+                .unwrap(),
+            "Event deleted: Standup"
+        );
+        let err = tool
+            .execute(serde_json::json!({"event_id": "gone"}))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("read_calendar"));
+    }
+
     #[test]
     fn test_open_app_schema() {
         let tool = OpenAppTool::new();
@@ -1002,9 +1858,96 @@ mod tests {
         assert_eq!(tool.name(), "get_clipboard");
     }
 
+    #[tokio::test]
+    async fn test_set_clipboard_validation() {
+        let tool = SetClipboardTool::new();
+        assert_eq!(tool.name(), "set_clipboard");
+        assert!(tool.execute(serde_json::json!({})).await.is_err());
+        let long = "x".repeat(MAX_CLIPBOARD_LENGTH + 1);
+        let err = tool
+            .execute(serde_json::json!({"text": long}))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("too long"));
+    }
+
+    #[tokio::test]
+    async fn test_send_email_attachments_must_be_in_allowed_dirs() {
+        use std::sync::{Arc, Mutex};
+
+        /// Email provider that records the attachments of each send
+        struct RecordingMail(Arc<Mutex<Vec<Vec<PathBuf>>>>);
+
+        #[async_trait]
+        impl EmailProvider for RecordingMail {
+            async fn read_emails_structured(
+                &self,
+                _limit: u64,
+                _mailbox: &str,
+                _search: Option<&str>,
+            ) -> Result<Vec<crate::platform::EmailSummary>> {
+                Ok(Vec::new())
+            }
+
+            async fn send_email(
+                &self,
+                _to: &str,
+                _subject: &str,
+                _body: &str,
+                _cc: Option<&str>,
+                _in_reply_to: Option<&str>,
+                attachments: &[PathBuf],
+            ) -> Result<String> {
+                self.0.lock().unwrap().push(attachments.to_vec());
+                Ok("sent".to_string())
+            }
+        }
+
+        let allowed = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::write(allowed.path().join("report.pdf"), "pdf").unwrap();
+        std::fs::write(outside.path().join("id_rsa"), "key").unwrap();
+
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let tool = SendEmailTool {
+            provider: Box::new(RecordingMail(sent.clone())),
+            allowed_dirs: resolve_allowed_dirs(&[allowed.path().display().to_string()]),
+        };
+        let email = |path: PathBuf| {
+            serde_json::json!({
+                "to": "a@example.com",
+                "subject": "Report",
+                "body": "Attached",
+                "attachments": [path.display().to_string()]
+            })
+        };
+
+        let err = tool
+            .execute(email(outside.path().join("id_rsa")))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not within allowed directories"));
+        // Relative segments can't climb out either
+        let sneaky = allowed
+            .path()
+            .join("..")
+            .join(outside.path().file_name().unwrap());
+        assert!(tool.execute(email(sneaky.join("id_rsa"))).await.is_err());
+        assert!(sent.lock().unwrap().is_empty());
+
+        tool.execute(email(allowed.path().join("report.pdf")))
+            .await
+            .unwrap();
+        let sent = sent.lock().unwrap();
+        assert_eq!(
+            sent[0],
+            vec![allowed.path().join("report.pdf").canonicalize().unwrap()]
+        );
+    }
+
     #[tokio::test]
     async fn test_send_email_missing_params() {
-        let tool = SendEmailTool::new();
+        let tool = SendEmailTool::new(vec![]);
         let result = tool
             .execute(serde_json::json!({
                 "to": "test@test.com"
@@ -1111,7 +2054,6 @@ mod tests {
     }
 
     // --- Notifications ---
-    #[cfg(target_os = "macos")]
     #[test]
     fn test_send_notification_schema() {
         let tool = SendNotificationTool::new();
@@ -1128,7 +2070,6 @@ mod tests {
         assert!(required.contains(&"message".to_string()));
     }
 
-    #[cfg(target_os = "macos")]
     #[tokio::test]
     async fn test_send_notification_missing_params() {
         let tool = SendNotificationTool::new();
@@ -1136,7 +2077,6 @@ mod tests {
         assert!(result.is_err());
     }
 
-    #[cfg(target_os = "macos")]
     #[tokio::test]
     async fn test_send_notification_title_too_long() {
         let tool = SendNotificationTool::new();
@@ -1150,16 +2090,44 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_send_notification_invalid_urgency() {
+        let tool = SendNotificationTool::new();
+        let err = tool
+            .execute(serde_json::json!({
+                "title": "t",
+                "message": "m",
+                "urgency": "urgent"
+            }))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid urgency 'urgent'"));
+        assert_eq!("Critical".parse::<Urgency>().unwrap(), Urgency::Critical);
+    }
+
     // --- Screen Capture ---
-    #[cfg(target_os = "macos")]
+    struct FakeScreen;
+
+    #[async_trait]
+    impl ScreenCaptureProvider for FakeScreen {
+        async fn capture_screen_png(&self) -> Result<Vec<u8>> {
+            Ok(b"\x89PNG screen".to_vec())
+        }
+
+        async fn capture_window_png(&self, title: &str) -> Result<Vec<u8>> {
+            Ok(format!("\u{89}PNG {}", title).into_bytes())
+        }
+    }
+
     #[test]
     fn test_screen_capture_schema() {
         let tool = ScreenCaptureTool::new();
         assert_eq!(tool.name(), "screen_capture");
         assert!(!tool.description().is_empty());
+        let schema = tool.input_schema();
+        assert!(schema["properties"].get("window").is_some());
     }
 
-    #[cfg(target_os = "macos")]
     #[tokio::test]
     async fn test_screen_capture_invalid_extension() {
         let tool = ScreenCaptureTool::new();
@@ -1169,6 +2137,42 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[cfg(not(target_os = "macos"))]
+    #[tokio::test]
+    async fn test_screen_capture_rejects_non_png_off_macos() {
+        let tool = ScreenCaptureTool {
+            provider: Box::new(FakeScreen),
+        };
+        let err = tool
+            .execute(serde_json::json!({"path": "/tmp/meepo-shot.jpg"}))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Output path must end with .png");
+        assert!(!std::path::Path::new("/tmp/meepo-shot.jpg").exists());
+    }
+
+    #[tokio::test]
+    async fn test_screen_capture_writes_png() {
+        let tool = ScreenCaptureTool {
+            provider: Box::new(FakeScreen),
+        };
+        let dir = tempfile::tempdir_in(std::env::temp_dir()).unwrap();
+        let path = dir.path().join("window.png");
+        let path = path.to_str().unwrap();
+
+        let result = tool
+            .execute(serde_json::json!({"path": path, "window": "Safari"}))
+            .await
+            .unwrap();
+        assert!(result.ends_with(path));
+        assert_eq!(std::fs::read(path).unwrap(), "\u{89}PNG Safari".as_bytes());
+
+        let result = tool.execute(serde_json::json!({})).await.unwrap();
+        let saved = result.trim_start_matches("Screenshot saved to ");
+        assert_eq!(std::fs::read(saved).unwrap(), b"\x89PNG screen");
+        std::fs::remove_file(saved).unwrap();
+    }
+
     // --- Music ---
     #[cfg(target_os = "macos")]
     #[test]
@@ -1235,4 +2239,66 @@ mod tests {
         let result = tool.execute(serde_json::json!({"query": long_query})).await;
         assert!(result.is_err());
     }
+
+    struct FakeContacts;
+
+    #[async_trait]
+    impl ContactsProvider for FakeContacts {
+        async fn search_contacts(&self, _query: &str) -> Result<String> {
+            unimplemented!()
+        }
+
+        async fn lookup(&self, name: &str) -> Result<Vec<Contact>> {
+            let all = [
+                ("Alice Smith", "alice@work.com"),
+                ("Alice Jones", "aj@home.net"),
+                ("Bob Lee", "bob@lee.org"),
+            ];
+            Ok(all
+                .iter()
+                .filter(|(n, _)| n.to_lowercase().contains(&name.to_lowercase()))
+                .map(|(n, e)| Contact {
+                    name: n.to_string(),
+                    emails: vec![e.to_string()],
+                    phones: vec![],
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lookup_contact() {
+        let tool = LookupContactTool {
+            provider: Box::new(FakeContacts),
+        };
+        assert_eq!(tool.name(), "lookup_contact");
+        assert!(
+            tool.execute(serde_json::json!({"name": "  "}))
+                .await
+                .is_err()
+        );
+
+        let result = tool
+            .execute(serde_json::json!({"name": "Bob"}))
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            "1 contact matches 'Bob':\nBob Lee\n  Email: bob@lee.org"
+        );
+
+        let result = tool
+            .execute(serde_json::json!({"name": "alice"}))
+            .await
+            .unwrap();
+        assert!(result.starts_with("2 contacts match 'alice'. Ask the user"));
+        assert!(result.contains("1. Alice Smith\n  Email: alice@work.com"));
+        assert!(result.contains("2. Alice Jones\n  Email: aj@home.net"));
+
+        let result = tool
+            .execute(serde_json::json!({"name": "Zed"}))
+            .await
+            .unwrap();
+        assert_eq!(result, "No contacts found matching 'Zed'");
+    }
 }
@@ -7,7 +7,57 @@ use std::sync::Arc;
 use tracing::debug;
 
 use super::{ToolHandler, json_schema};
-use meepo_knowledge::{KnowledgeDb, KnowledgeGraph};
+use meepo_knowledge::{
+    Collapsed, DEFAULT_FUZZY_THRESHOLD, DEFAULT_SIMILARITY_THRESHOLD, KnowledgeDb, KnowledgeGraph,
+    collapse_similar,
+};
+
+/// Schema properties for the opt-in near-duplicate collapsing of search results
+pub(crate) fn collapse_schema() -> Value {
+    serde_json::json!({
+        "collapse_similar": {
+            "type": "boolean",
+            "description": "Collapse near-identical results (e.g. overlapping document chunks) into one (default: false)"
+        },
+        "similarity_threshold": {
+            "type": "number",
+            "description": "Similarity (0-1) above which results are collapsed (default: 0.8)"
+        }
+    })
+}
+
+/// The similarity threshold to collapse at, or `None` if collapsing wasn't requested
+pub(crate) fn collapse_threshold(input: &Value) -> Option<f32> {
+    if !input
+        .get("collapse_similar")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+    {
+        return None;
+    }
+    Some(
+        input
+            .get("similarity_threshold")
+            .and_then(|v| v.as_f64())
+            .map(|t| t.clamp(0.0, 1.0) as f32)
+            .unwrap_or(DEFAULT_SIMILARITY_THRESHOLD),
+    )
+}
+
+/// Collapse near-duplicates when a threshold is given, otherwise keep every item
+pub(crate) fn maybe_collapse<T>(
+    items: Vec<T>,
+    threshold: Option<f32>,
+    text: impl Fn(&T) -> String,
+) -> Vec<Collapsed<T>> {
+    match threshold {
+        Some(threshold) => collapse_similar(items, threshold, text),
+        None => items
+            .into_iter()
+            .map(|item| Collapsed { item, collapsed: 0 })
+            .collect(),
+    }
+}
 
 /// Remember information by adding to knowledge graph
 pub struct RememberTool {
@@ -93,7 +143,8 @@ impl ToolHandler for RecallTool {
 
     fn description(&self) -> &str {
         "Search the knowledge graph for previously stored information. \
-         Returns matching entities based on name or type."
+         Returns matching entities based on name or type, with their IDs and relevance \
+         scores for use with link_entities or update_entity."
     }
 
     fn input_schema(&self) -> Value {
@@ -106,34 +157,101 @@ impl ToolHandler for RecallTool {
                 "entity_type": {
                     "type": "string",
                     "description": "Optional: filter by entity type"
+                },
+                "fuzzy": {
+                    "type": "boolean",
+                    "description": "Also match names with typos or misremembered spellings (default: false)"
+                },
+                "fuzzy_threshold": {
+                    "type": "number",
+                    "description": "Name similarity (0-1) a fuzzy match needs (default: 0.75)"
+                },
+                "format": {
+                    "type": "string",
+                    "enum": ["text", "json"],
+                    "description": "Output format: readable text, or JSON for reliable parsing (default: text)"
                 }
             }),
             vec!["query"],
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let query = input
             .get("query")
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing 'query' parameter"))?;
         let entity_type = input.get("entity_type").and_then(|v| v.as_str());
+        let json_output = match input.get("format").and_then(|v| v.as_str()) {
+            None | Some("text") => false,
+            Some("json") => true,
+            Some(other) => {
+                return Err(anyhow::anyhow!(
+                    "Unknown format '{}' (expected 'text' or 'json')",
+                    other
+                ));
+            }
+        };
+
+        let fuzzy_threshold = input
+            .get("fuzzy")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+            .then(|| {
+                input
+                    .get("fuzzy_threshold")
+                    .and_then(|v| v.as_f64())
+                    .map(|t| t.clamp(0.0, 1.0) as f32)
+                    .unwrap_or(DEFAULT_FUZZY_THRESHOLD)
+            });
 
         debug!("Searching knowledge graph for: {}", query);
 
-        let results = self
-            .db
-            .search_entities(query, entity_type)
-            .await
-            .context("Failed to search entities")?;
+        let results = match fuzzy_threshold {
+            Some(threshold) => {
+                self.db
+                    .search_entities_fuzzy(query, entity_type, threshold)
+                    .await
+            }
+            None => self.db.search_ranked(query, entity_type, None).await,
+        }
+        .context("Failed to search entities")?;
+
+        if json_output {
+            let entries: Vec<Value> = results
+                .iter()
+                .take(10)
+                .map(|ranked| {
+                    serde_json::json!({
+                        "id": ranked.entity.id,
+                        "name": ranked.entity.name,
+                        "entity_type": ranked.entity.entity_type,
+                        "score": ranked.score,
+                        "metadata": ranked.entity.metadata,
+                    })
+                })
+                .collect();
+            return Ok(serde_json::to_string_pretty(&serde_json::json!({
+                "total": results.len(),
+                "results": entries,
+            }))?);
+        }
 
         if results.is_empty() {
             return Ok("No matching information found.".to_string());
         }
 
         let mut output = format!("Found {} result(s):\n\n", results.len());
-        for entity in results.iter().take(10) {
-            output.push_str(&format!("- {} ({})", entity.name, entity.entity_type));
+        for ranked in results.iter().take(10) {
+            let entity = &ranked.entity;
+            output.push_str(&format!(
+                "- {} ({}) [id: {}, score: {:.2}]",
+                entity.name, entity.entity_type, entity.id, ranked.score
+            ));
             if let Some(metadata) = &entity.metadata {
                 output.push_str(&format!("\n  Metadata: {}", metadata));
             }
@@ -217,6 +335,362 @@ impl ToolHandler for LinkEntitiesTool {
     }
 }
 
+/// Correct a stored entity's name, type, or metadata
+pub struct UpdateEntityTool {
+    db: Arc<KnowledgeDb>,
+    graph: Option<Arc<KnowledgeGraph>>,
+}
+
+impl UpdateEntityTool {
+    /// Create an update tool that also re-indexes the entity in Tantivy
+    pub fn with_graph(graph: Arc<KnowledgeGraph>) -> Self {
+        Self {
+            db: graph.db(),
+            graph: Some(graph),
+        }
+    }
+
+    /// Create an update tool over the database only
+    pub fn new(db: Arc<KnowledgeDb>) -> Self {
+        Self { db, graph: None }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for UpdateEntityTool {
+    fn name(&self) -> &str {
+        "update_entity"
+    }
+
+    fn description(&self) -> &str {
+        "Correct an entity already stored in the knowledge graph. Only the fields given are \
+         changed; metadata is replaced as a whole."
+    }
+
+    fn input_schema(&self) -> Value {
+        json_schema(
+            serde_json::json!({
+                "entity_id": {
+                    "type": "string",
+                    "description": "ID of the entity to update"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "New name for the entity"
+                },
+                "entity_type": {
+                    "type": "string",
+                    "description": "New type for the entity"
+                },
+                "metadata": {
+                    "type": "object",
+                    "description": "New metadata, replacing the existing metadata"
+                }
+            }),
+            vec!["entity_id"],
+        )
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        let entity_id = input
+            .get("entity_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'entity_id' parameter"))?;
+        let name = input.get("name").and_then(|v| v.as_str());
+        let entity_type = input.get("entity_type").and_then(|v| v.as_str());
+        let metadata = input.get("metadata").cloned();
+        if name.is_none() && entity_type.is_none() && metadata.is_none() {
+            return Err(anyhow::anyhow!(
+                "Nothing to update: give at least one of 'name', 'entity_type', or 'metadata'"
+            ));
+        }
+
+        debug!("Updating entity: {}", entity_id);
+
+        let entity = match &self.graph {
+            Some(graph) => {
+                graph
+                    .update_entity(entity_id, name, entity_type, metadata)
+                    .await
+            }
+            None => {
+                self.db
+                    .update_entity(entity_id, name, entity_type, metadata)
+                    .await
+            }
+        }
+        .context("Failed to update entity")?
+        .ok_or_else(|| anyhow::anyhow!("No entity found with ID: {}", entity_id))?;
+
+        Ok(format!(
+            "Updated entity:\n{}",
+            serde_json::to_string_pretty(&entity)?
+        ))
+    }
+}
+
+/// Forget an entity, removing it and its relationships from the knowledge graph
+pub struct ForgetTool {
+    db: Arc<KnowledgeDb>,
+    graph: Option<Arc<KnowledgeGraph>>,
+}
+
+impl ForgetTool {
+    /// Create a forget tool that also removes the entity from the Tantivy index
+    pub fn with_graph(graph: Arc<KnowledgeGraph>) -> Self {
+        Self {
+            db: graph.db(),
+            graph: Some(graph),
+        }
+    }
+
+    /// Create a forget tool over the database only
+    pub fn new(db: Arc<KnowledgeDb>) -> Self {
+        Self { db, graph: None }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for ForgetTool {
+    fn name(&self) -> &str {
+        "forget"
+    }
+
+    fn description(&self) -> &str {
+        "Permanently delete an entity from the knowledge graph, along with every relationship \
+         it takes part in. Use dry_run first to see what would be removed."
+    }
+
+    fn input_schema(&self) -> Value {
+        json_schema(
+            serde_json::json!({
+                "entity_id": {
+                    "type": "string",
+                    "description": "ID of the entity to forget"
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "Report what would be deleted without deleting anything (default: false)"
+                }
+            }),
+            vec!["entity_id"],
+        )
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        let entity_id = input
+            .get("entity_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'entity_id' parameter"))?;
+        let dry_run = input
+            .get("dry_run")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let Some(entity) = self
+            .db
+            .get_entity(entity_id)
+            .await
+            .context("Failed to look up entity")?
+        else {
+            return Ok(format!("No entity found with ID: {}", entity_id));
+        };
+
+        if dry_run {
+            let relationships = self
+                .db
+                .get_relationships_for(entity_id)
+                .await
+                .context("Failed to look up relationships")?;
+            let mut output = format!(
+                "Would delete '{}' ({}) and {} relationship(s):\n",
+                entity.name,
+                entity.entity_type,
+                relationships.len()
+            );
+            for rel in &relationships {
+                output.push_str(&format!(
+                    "- {} -[{}]-> {}\n",
+                    rel.source_id, rel.relation_type, rel.target_id
+                ));
+            }
+            return Ok(output);
+        }
+
+        debug!("Forgetting entity: {} ({})", entity.name, entity_id);
+        let deleted = match &self.graph {
+            Some(graph) => graph.delete_entity(entity_id).await,
+            None => self.db.delete_entity(entity_id).await,
+        }
+        .context("Failed to delete entity")?;
+
+        Ok(format!(
+            "Forgot '{}' ({} rows deleted, including {} relationship(s))",
+            entity.name,
+            deleted,
+            deleted.saturating_sub(1)
+        ))
+    }
+}
+
+/// Maximum duplicate pairs listed by `find_duplicates`
+const MAX_DUPLICATE_PAIRS: usize = 20;
+
+/// Surface entities that look like duplicates of each other
+pub struct FindDuplicatesTool {
+    db: Arc<KnowledgeDb>,
+}
+
+impl FindDuplicatesTool {
+    pub fn new(db: Arc<KnowledgeDb>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for FindDuplicatesTool {
+    fn name(&self) -> &str {
+        "find_duplicates"
+    }
+
+    fn description(&self) -> &str {
+        "Find entities in the knowledge graph that are probably the same thing stored twice \
+         (e.g. 'John Smith' and 'John smith'), by name similarity within the same entity type. \
+         Review the pairs, then combine real duplicates with merge_entities."
+    }
+
+    fn input_schema(&self) -> Value {
+        json_schema(
+            serde_json::json!({
+                "threshold": {
+                    "type": "number",
+                    "minimum": 0,
+                    "maximum": 1,
+                    "description": "Name similarity (0-1) at or above which a pair is reported (default: 0.8)"
+                }
+            }),
+            vec![],
+        )
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        let threshold = input
+            .get("threshold")
+            .and_then(|v| v.as_f64())
+            .map(|t| t as f32)
+            .unwrap_or(DEFAULT_SIMILARITY_THRESHOLD);
+
+        let candidates = self
+            .db
+            .find_duplicates(threshold)
+            .await
+            .context("Failed to find duplicates")?;
+        if candidates.is_empty() {
+            return Ok("No likely duplicates found.".to_string());
+        }
+
+        let mut output = format!("{} likely duplicate pair(s):\n", candidates.len());
+        for candidate in candidates.iter().take(MAX_DUPLICATE_PAIRS) {
+            output.push_str(&format!(
+                "- '{}' (ID: {}) ~ '{}' (ID: {}) [{}, {:.0}% similar]\n",
+                candidate.keep.name,
+                candidate.keep.id,
+                candidate.duplicate.name,
+                candidate.duplicate.id,
+                candidate.keep.entity_type,
+                candidate.similarity * 100.0
+            ));
+        }
+        if candidates.len() > MAX_DUPLICATE_PAIRS {
+            output.push_str(&format!(
+                "... and {} more\n",
+                candidates.len() - MAX_DUPLICATE_PAIRS
+            ));
+        }
+        Ok(output)
+    }
+}
+
+/// Merge a duplicate entity into another, keeping its relationships and metadata
+pub struct MergeEntitiesTool {
+    db: Arc<KnowledgeDb>,
+    graph: Option<Arc<KnowledgeGraph>>,
+}
+
+impl MergeEntitiesTool {
+    /// Create a merge tool that also updates the Tantivy index
+    pub fn with_graph(graph: Arc<KnowledgeGraph>) -> Self {
+        Self {
+            db: graph.db(),
+            graph: Some(graph),
+        }
+    }
+
+    /// Create a merge tool over the database only
+    pub fn new(db: Arc<KnowledgeDb>) -> Self {
+        Self { db, graph: None }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for MergeEntitiesTool {
+    fn name(&self) -> &str {
+        "merge_entities"
+    }
+
+    fn description(&self) -> &str {
+        "Merge a duplicate entity into another. The duplicate's relationships move to the kept \
+         entity, its metadata is added where the kept entity has none, its name is recorded as \
+         an alias, and the duplicate is deleted. This cannot be undone."
+    }
+
+    fn input_schema(&self) -> Value {
+        json_schema(
+            serde_json::json!({
+                "keep_id": {
+                    "type": "string",
+                    "description": "ID of the entity to keep"
+                },
+                "merge_id": {
+                    "type": "string",
+                    "description": "ID of the duplicate to merge into it and delete"
+                }
+            }),
+            vec!["keep_id", "merge_id"],
+        )
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        let keep_id = input
+            .get("keep_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'keep_id' parameter"))?;
+        let merge_id = input
+            .get("merge_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'merge_id' parameter"))?;
+
+        debug!("Merging entity {} into {}", merge_id, keep_id);
+        let summary = match &self.graph {
+            Some(graph) => graph.merge_entities(keep_id, merge_id).await,
+            None => self.db.merge_entities(keep_id, merge_id).await,
+        }?;
+
+        Ok(format!(
+            "Merged {} into '{}' ({} relationship(s) moved, {} dropped as redundant):\n{}",
+            merge_id,
+            summary.entity.name,
+            summary.relationships_moved,
+            summary.relationships_dropped,
+            serde_json::to_string_pretty(&summary.entity)?
+        ))
+    }
+}
+
 /// Search knowledge graph using full-text search
 ///
 /// This tool can work with either KnowledgeGraph (preferred, uses Tantivy)
@@ -251,24 +725,31 @@ impl ToolHandler for SearchKnowledgeTool {
     }
 
     fn description(&self) -> &str {
-        "Perform a full-text search across all stored knowledge. \
-         Uses Tantivy for fast, relevance-ranked full-text search."
+        "Perform a full-text search across all stored knowledge, including the content of \
+         ingested document chunks. Uses Tantivy for fast, relevance-ranked full-text search \
+         and shows a snippet of each match."
     }
 
     fn input_schema(&self) -> Value {
-        json_schema(
-            serde_json::json!({
-                "query": {
-                    "type": "string",
-                    "description": "Search query (supports full-text search)"
-                },
-                "limit": {
-                    "type": "number",
-                    "description": "Maximum number of results (default: 10)"
-                }
-            }),
-            vec!["query"],
-        )
+        let mut properties = serde_json::json!({
+            "query": {
+                "type": "string",
+                "description": "Search query (supports full-text search)"
+            },
+            "limit": {
+                "type": "number",
+                "description": "Maximum number of results (default: 10)"
+            }
+        });
+        if let (Some(props), Value::Object(extra)) = (properties.as_object_mut(), collapse_schema())
+        {
+            props.extend(extra);
+        }
+        json_schema(properties, vec!["query"])
+    }
+
+    fn read_only(&self) -> bool {
+        true
     }
 
     async fn execute(&self, input: Value) -> Result<String> {
@@ -277,6 +758,7 @@ impl ToolHandler for SearchKnowledgeTool {
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing 'query' parameter"))?;
         let limit = input.get("limit").and_then(|v| v.as_u64()).unwrap_or(10) as usize;
+        let threshold = collapse_threshold(&input);
 
         debug!("Full-text search for: {}", query);
 
@@ -291,17 +773,36 @@ impl ToolHandler for SearchKnowledgeTool {
                 return Ok("No results found.".to_string());
             }
 
-            let mut output = format!(
-                "Found {} result(s) (sorted by relevance):\n\n",
-                search_results.len()
-            );
-            for result in search_results.iter().take(limit) {
+            let total = search_results.len();
+            let results = maybe_collapse(search_results, threshold, |r| r.content.clone());
+
+            let mut output = format!("Found {} result(s) (sorted by relevance)", results.len());
+            if results.len() < total {
                 output.push_str(&format!(
-                    "- {} ({})\n  Relevance: {:.2}\n",
-                    result.id, result.entity_type, result.score
+                    ", {} near-duplicate(s) collapsed",
+                    total - results.len()
+                ));
+            }
+            output.push_str(":\n\n");
+            for collapsed in results.iter().take(limit) {
+                let result = &collapsed.item;
+                // The index only stores ids; names live in SQLite
+                let name = match graph.get_entity(&result.id).await {
+                    Ok(Some(entity)) => entity.name,
+                    _ => result.id.clone(),
+                };
+                output.push_str(&format!(
+                    "- {} ({}) [id: {}]\n  Relevance: {:.2}\n",
+                    name, result.entity_type, result.id, result.score
                 ));
                 if let Some(snippet) = &result.snippet {
-                    output.push_str(&format!("  Preview: {}\n", snippet));
+                    output.push_str(&format!("  Snippet: {}\n", snippet));
+                }
+                if collapsed.collapsed > 0 {
+                    output.push_str(&format!(
+                        "  (+{} similar result(s) collapsed)\n",
+                        collapsed.collapsed
+                    ));
                 }
                 output.push('\n');
             }
@@ -319,15 +820,35 @@ impl ToolHandler for SearchKnowledgeTool {
                 return Ok("No results found.".to_string());
             }
 
+            let total = results.len();
+            let results = maybe_collapse(results, threshold, |e| match &e.metadata {
+                Some(metadata) => format!("{} {}", e.name, metadata),
+                None => e.name.clone(),
+            });
+
             let mut output = format!(
-                "Found {} result(s) (basic search):\n\n",
+                "Found {} result(s) (basic search)",
                 results.len().min(limit)
             );
-            for entity in results.iter().take(limit) {
+            if results.len() < total {
+                output.push_str(&format!(
+                    ", {} near-duplicate(s) collapsed",
+                    total - results.len()
+                ));
+            }
+            output.push_str(":\n\n");
+            for collapsed in results.iter().take(limit) {
+                let entity = &collapsed.item;
                 output.push_str(&format!("- {} ({})\n", entity.name, entity.entity_type));
                 if let Some(metadata) = &entity.metadata {
                     output.push_str(&format!("  {}\n", metadata));
                 }
+                if collapsed.collapsed > 0 {
+                    output.push_str(&format!(
+                        "  (+{} similar result(s) collapsed)\n",
+                        collapsed.collapsed
+                    ));
+                }
             }
 
             Ok(output)
@@ -396,6 +917,78 @@ mod tests {
         assert!(result.contains("Rust programming"));
     }
 
+    #[tokio::test]
+    async fn test_recall_includes_ids_and_json_format() {
+        let (db, _temp) = setup();
+        let id = db.insert_entity("Alice", "person", None).await.unwrap();
+        db.insert_entity("Alice's team", "group", None)
+            .await
+            .unwrap();
+        let recall = RecallTool::new(db);
+
+        let text = recall
+            .execute(serde_json::json!({"query": "alice"}))
+            .await
+            .unwrap();
+        assert!(text.contains(&format!("- Alice (person) [id: {}, score: 1.00]", id)));
+
+        let json = recall
+            .execute(serde_json::json!({"query": "alice", "format": "json"}))
+            .await
+            .unwrap();
+        let parsed: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["total"], 2);
+        assert_eq!(parsed["results"][0]["id"], id.as_str());
+        assert_eq!(parsed["results"][0]["entity_type"], "person");
+        assert_eq!(parsed["results"][0]["score"], 1.0);
+
+        let empty = recall
+            .execute(serde_json::json!({"query": "nobody", "format": "json"}))
+            .await
+            .unwrap();
+        let parsed: Value = serde_json::from_str(&empty).unwrap();
+        assert_eq!(parsed["total"], 0);
+
+        assert!(
+            recall
+                .execute(serde_json::json!({"query": "alice", "format": "xml"}))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recall_fuzzy() {
+        let (db, _temp) = setup();
+        let id = db
+            .insert_entity("Abhay Mundhara", "person", None)
+            .await
+            .unwrap();
+        let recall = RecallTool::new(db);
+
+        let exact = recall
+            .execute(serde_json::json!({"query": "Mundara"}))
+            .await
+            .unwrap();
+        assert!(exact.contains("No matching information"));
+
+        let fuzzy = recall
+            .execute(serde_json::json!({"query": "Mundara", "fuzzy": true}))
+            .await
+            .unwrap();
+        assert!(fuzzy.contains(&format!("Abhay Mundhara (person) [id: {}", id)));
+
+        let strict = recall
+            .execute(serde_json::json!({
+                "query": "Mundara",
+                "fuzzy": true,
+                "fuzzy_threshold": 0.95
+            }))
+            .await
+            .unwrap();
+        assert!(strict.contains("No matching information"));
+    }
+
     #[tokio::test]
     async fn test_remember_missing_name() {
         let (db, _temp) = setup();
@@ -466,6 +1059,105 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_update_entity_tool() {
+        let (graph, _temp) = setup_graph();
+        let id = graph.add_entity("Ann", "person", None).await.unwrap();
+        let tool = UpdateEntityTool::with_graph(graph.clone());
+
+        let result = tool
+            .execute(serde_json::json!({"entity_id": id, "name": "Anne"}))
+            .await
+            .unwrap();
+        assert!(result.starts_with("Updated entity:"));
+        assert!(result.contains("\"name\": \"Anne\""));
+        assert_eq!(graph.search("Anne", 10).unwrap()[0].id, id);
+
+        let err = tool
+            .execute(serde_json::json!({"entity_id": "missing", "name": "x"}))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "No entity found with ID: missing");
+
+        assert!(
+            tool.execute(serde_json::json!({"entity_id": id}))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_and_merge_duplicates() {
+        let (graph, _temp) = setup_graph();
+        let john = graph
+            .add_entity("John Smith", "person", None)
+            .await
+            .unwrap();
+        let dup = graph
+            .add_entity("john smith", "person", None)
+            .await
+            .unwrap();
+        graph.add_entity("Acme", "company", None).await.unwrap();
+
+        let find = FindDuplicatesTool::new(graph.db());
+        let result = find.execute(serde_json::json!({})).await.unwrap();
+        assert!(result.starts_with("1 likely duplicate pair(s):"));
+        assert!(result.contains(&format!("'John Smith' (ID: {})", john)));
+
+        let merge = MergeEntitiesTool::with_graph(graph.clone());
+        let result = merge
+            .execute(serde_json::json!({"keep_id": john, "merge_id": dup}))
+            .await
+            .unwrap();
+        assert!(result.starts_with(&format!("Merged {} into 'John Smith'", dup)));
+        let hits: Vec<_> = graph
+            .search("smith", 10)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.id)
+            .collect();
+        assert_eq!(hits, vec![john]);
+
+        let result = find.execute(serde_json::json!({})).await.unwrap();
+        assert_eq!(result, "No likely duplicates found.");
+    }
+
+    #[tokio::test]
+    async fn test_forget_tool() {
+        let (graph, _temp) = setup_graph();
+        let db = graph.db();
+        let alice = db.insert_entity("Alice", "person", None).await.unwrap();
+        let bob = db.insert_entity("Bob", "person", None).await.unwrap();
+        db.insert_relationship(&alice, &bob, "knows", None)
+            .await
+            .unwrap();
+
+        let tool = ForgetTool::with_graph(graph);
+        let preview = tool
+            .execute(serde_json::json!({"entity_id": alice, "dry_run": true}))
+            .await
+            .unwrap();
+        assert!(preview.starts_with("Would delete 'Alice' (person) and 1 relationship(s)"));
+        assert!(db.get_entity(&alice).await.unwrap().is_some());
+
+        let result = tool
+            .execute(serde_json::json!({"entity_id": alice}))
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            "Forgot 'Alice' (2 rows deleted, including 1 relationship(s))"
+        );
+        assert!(db.get_entity(&alice).await.unwrap().is_none());
+        assert!(db.get_relationships_for(&bob).await.unwrap().is_empty());
+
+        let result = tool
+            .execute(serde_json::json!({"entity_id": alice}))
+            .await
+            .unwrap();
+        assert!(result.starts_with("No entity found"));
+    }
+
     #[tokio::test]
     async fn test_search_knowledge_tool() {
         let (db, _temp) = setup();
@@ -566,6 +1258,69 @@ mod tests {
         assert!(result.contains("article"));
     }
 
+    #[tokio::test]
+    async fn test_search_knowledge_collapse_similar() {
+        let (graph, _temp) = setup_graph();
+
+        for suffix in ["", ".", "!"] {
+            graph
+                .add_entity(
+                    &format!("Overlapping chunk about tide tables{}", suffix),
+                    "document_chunk",
+                    Some(
+                        serde_json::json!({"full_content": "Tide tables list high and low water"}),
+                    ),
+                )
+                .await
+                .unwrap();
+        }
+
+        let search = SearchKnowledgeTool::with_graph(graph);
+
+        let result = search
+            .execute(serde_json::json!({"query": "tide"}))
+            .await
+            .unwrap();
+        assert!(result.starts_with("Found 3 result(s)"));
+
+        let result = search
+            .execute(serde_json::json!({"query": "tide", "collapse_similar": true}))
+            .await
+            .unwrap();
+        assert!(result.starts_with("Found 1 result(s)"));
+        assert!(result.contains("2 near-duplicate(s) collapsed"));
+        assert!(result.contains("(+2 similar result(s) collapsed)"));
+    }
+
+    #[tokio::test]
+    async fn test_search_knowledge_shows_chunk_snippet() {
+        let (graph, _temp) = setup_graph();
+        let id = graph
+            .add_entity(
+                "Design notes [chunk 2/5]",
+                "document_chunk",
+                Some(serde_json::json!({
+                    "full_content": "The borrow checker runs after type inference.",
+                    "chunk_index": 1
+                })),
+            )
+            .await
+            .unwrap();
+
+        let search = SearchKnowledgeTool::with_graph(graph);
+        // Only the chunk content mentions inference
+        let result = search
+            .execute(serde_json::json!({"query": "inference"}))
+            .await
+            .unwrap();
+        assert!(result.contains(&format!(
+            "- Design notes [chunk 2/5] (document_chunk) [id: {}]",
+            id
+        )));
+        assert!(result.contains("  Snippet: "));
+        assert!(result.contains("The borrow checker runs after type **inference**."));
+    }
+
     #[tokio::test]
     async fn test_search_knowledge_no_results() {
         let (graph, _temp) = setup_graph();
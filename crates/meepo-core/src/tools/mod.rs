@@ -2,12 +2,18 @@
 
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
+use jsonschema::error::ValidationErrorKind;
+use lru::LruCache;
 use serde_json::Value;
 use std::collections::HashMap;
-use std::sync::Arc;
-use tracing::{debug, warn};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
 
 use crate::api::ToolDefinition;
+use crate::safe_mode::{self, SafeMode};
 
 pub mod accessibility;
 pub mod autonomous;
@@ -39,11 +45,30 @@ pub trait ToolHandler: Send + Sync {
     fn description(&self) -> &str;
     fn input_schema(&self) -> Value;
     async fn execute(&self, input: Value) -> Result<String>;
+
+    /// Whether the tool only reads, changing nothing outside or inside meepo.
+    /// Safe mode runs read-only tools and answers every other call with a
+    /// "would have" message, so a tool has to opt in here to run there.
+    fn read_only(&self) -> bool {
+        false
+    }
+}
+
+/// What the model is told about a tool: its name, description and input schema
+pub type ToolSpec = ToolDefinition;
+
+fn tool_spec(handler: &dyn ToolHandler) -> ToolSpec {
+    ToolSpec {
+        name: handler.name().to_string(),
+        description: handler.description().to_string(),
+        input_schema: handler.input_schema(),
+    }
 }
 
 /// Registry of available tools
 pub struct ToolRegistry {
     tools: HashMap<Arc<str>, Arc<dyn ToolHandler>>,
+    safe_mode: SafeMode,
 }
 
 impl ToolRegistry {
@@ -51,14 +76,41 @@ impl ToolRegistry {
     pub fn new() -> Self {
         Self {
             tools: HashMap::new(),
+            safe_mode: SafeMode::global().clone(),
         }
     }
 
-    /// Register a tool handler
-    pub fn register(&mut self, handler: Arc<dyn ToolHandler>) {
+    /// Use a specific safe-mode flag instead of the process-wide one
+    pub fn with_safe_mode(mut self, safe_mode: SafeMode) -> Self {
+        self.safe_mode = safe_mode;
+        self
+    }
+
+    /// Register a tool handler. Fails if a tool with the same name is already registered.
+    pub fn register(&mut self, handler: Arc<dyn ToolHandler>) -> Result<()> {
         let name: Arc<str> = Arc::from(handler.name());
+        if self.tools.contains_key(&name) {
+            return Err(anyhow!("Tool '{}' is already registered", name));
+        }
         debug!("Registering tool: {}", name);
         self.tools.insert(name, handler);
+        Ok(())
+    }
+
+    /// Replace a registered tool with a wrapper around it (e.g. [`CachingTool`]).
+    /// Returns false if no tool with that name is registered.
+    pub fn wrap(
+        &mut self,
+        name: &str,
+        wrap: impl FnOnce(Arc<dyn ToolHandler>) -> Arc<dyn ToolHandler>,
+    ) -> bool {
+        match self.tools.get_mut(name) {
+            Some(handler) => {
+                *handler = wrap(handler.clone());
+                true
+            }
+            None => false,
+        }
     }
 
     /// Get a tool by name
@@ -76,16 +128,23 @@ impl ToolRegistry {
         self.tools.is_empty()
     }
 
+    /// Specs of every registered tool, sorted by name
+    pub fn list(&self) -> Vec<ToolSpec> {
+        let mut specs: Vec<ToolSpec> = self
+            .tools
+            .values()
+            .map(|handler| tool_spec(handler.as_ref()))
+            .collect();
+        specs.sort_by(|a, b| a.name.cmp(&b.name));
+        specs
+    }
+
     /// Get tool definitions for only the named tools
     pub fn filter_tools(&self, names: &[String]) -> Vec<ToolDefinition> {
         names
             .iter()
             .filter_map(|name| self.tools.get(name.as_str()))
-            .map(|handler| ToolDefinition {
-                name: handler.name().to_string(),
-                description: handler.description().to_string(),
-                input_schema: handler.input_schema(),
-            })
+            .map(|handler| tool_spec(handler.as_ref()))
             .collect()
     }
 }
@@ -106,6 +165,16 @@ impl ToolExecutor for ToolRegistry {
             .get(tool_name)
             .ok_or_else(|| anyhow!("Unknown tool: {}", tool_name))?;
 
+        if let Err(e) = validate_input(&handler.input_schema(), &input) {
+            warn!("Tool {} rejected input: {}", tool_name, e);
+            return Err(e);
+        }
+
+        if self.safe_mode.is_enabled() && !handler.read_only() {
+            info!("Safe mode: skipping tool {}", tool_name);
+            return Ok(safe_mode::would_have(tool_name, &input));
+        }
+
         match handler.execute(input).await {
             Ok(result) => {
                 debug!("Tool {} succeeded", tool_name);
@@ -119,14 +188,262 @@ impl ToolExecutor for ToolRegistry {
     }
 
     fn list_tools(&self) -> Vec<ToolDefinition> {
-        self.tools
-            .values()
-            .map(|handler| ToolDefinition {
-                name: handler.name().to_string(),
-                description: handler.description().to_string(),
-                input_schema: handler.input_schema(),
-            })
-            .collect()
+        self.list()
+    }
+}
+
+/// Wraps a tool so `execute` fails with a timeout error instead of hanging past a deadline.
+/// The inner call is dropped (and so cancelled) when the deadline passes.
+pub struct TimedTool {
+    inner: Arc<dyn ToolHandler>,
+    timeout: Duration,
+}
+
+impl TimedTool {
+    pub fn new(inner: Arc<dyn ToolHandler>, timeout: Duration) -> Self {
+        Self { inner, timeout }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for TimedTool {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn input_schema(&self) -> Value {
+        self.inner.input_schema()
+    }
+
+    fn read_only(&self) -> bool {
+        self.inner.read_only()
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        match tokio::time::timeout(self.timeout, self.inner.execute(input)).await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow!(
+                "Tool {} timed out after {}",
+                self.name(),
+                describe_timeout(self.timeout)
+            )),
+        }
+    }
+}
+
+/// A timeout as whole seconds when it is one, otherwise in milliseconds
+fn describe_timeout(timeout: Duration) -> String {
+    match (timeout.as_secs(), timeout.subsec_millis()) {
+        (1, 0) => "1 second".to_string(),
+        (secs, 0) if secs > 0 => format!("{} seconds", secs),
+        _ => format!("{} ms", timeout.as_millis()),
+    }
+}
+
+/// Counter shared between [`CachingTool`]s and [`InvalidatingTool`]s. Results cached
+/// before the last bump are stale, so one bump invalidates every cache using it.
+#[derive(Debug, Clone, Default)]
+pub struct CacheGeneration(Arc<AtomicU64>);
+
+impl CacheGeneration {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn current(&self) -> u64 {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Invalidate everything cached so far
+    pub fn bump(&self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Wraps a read-only tool so repeated calls with the same input reuse the previous
+/// result until `ttl` passes or its [`CacheGeneration`] is bumped. Entries are keyed
+/// on the tool name plus the input JSON with object keys sorted, and the least
+/// recently used entry is evicted once `max_entries` is reached. Tools that aren't
+/// [`read_only`](ToolHandler::read_only) always run, and errors are never cached.
+pub struct CachingTool {
+    inner: Arc<dyn ToolHandler>,
+    ttl: Duration,
+    generation: CacheGeneration,
+    entries: Mutex<LruCache<String, (Instant, u64, String)>>,
+}
+
+impl CachingTool {
+    pub fn new(inner: Arc<dyn ToolHandler>, ttl: Duration, max_entries: usize) -> Self {
+        let capacity = NonZeroUsize::new(max_entries).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            inner,
+            ttl,
+            generation: CacheGeneration::new(),
+            entries: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    /// Drop cached results whenever `generation` is bumped
+    pub fn with_generation(mut self, generation: CacheGeneration) -> Self {
+        self.generation = generation;
+        self
+    }
+
+    fn cache_key(&self, input: &Value) -> String {
+        format!("{}:{}", self.inner.name(), canonical_json(input))
+    }
+}
+
+#[async_trait]
+impl ToolHandler for CachingTool {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn input_schema(&self) -> Value {
+        self.inner.input_schema()
+    }
+
+    fn read_only(&self) -> bool {
+        self.inner.read_only()
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        if !self.inner.read_only() {
+            return self.inner.execute(input).await;
+        }
+
+        let key = self.cache_key(&input);
+        // Read before running the tool, so a write that lands mid-call marks this result stale
+        let generation = self.generation.current();
+        {
+            let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+            match entries.get(&key) {
+                Some((stored_at, stored_gen, result))
+                    if stored_at.elapsed() < self.ttl && *stored_gen == generation =>
+                {
+                    debug!("Tool {} served from cache", self.name());
+                    return Ok(result.clone());
+                }
+                Some(_) => {
+                    entries.pop(&key);
+                }
+                None => {}
+            }
+        }
+
+        let result = self.inner.execute(input).await?;
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .put(key, (Instant::now(), generation, result.clone()));
+        Ok(result)
+    }
+}
+
+/// Wraps a tool that may write so every call bumps a [`CacheGeneration`], making
+/// cached reads (e.g. `read_calendar` after `create_calendar_event`) run again
+pub struct InvalidatingTool {
+    inner: Arc<dyn ToolHandler>,
+    generation: CacheGeneration,
+}
+
+impl InvalidatingTool {
+    pub fn new(inner: Arc<dyn ToolHandler>, generation: CacheGeneration) -> Self {
+        Self { inner, generation }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for InvalidatingTool {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn input_schema(&self) -> Value {
+        self.inner.input_schema()
+    }
+
+    fn read_only(&self) -> bool {
+        self.inner.read_only()
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        let result = self.inner.execute(input).await;
+        // Even a failed call may have changed something before it errored
+        self.generation.bump();
+        result
+    }
+}
+
+/// Serialize `value` with object keys sorted so equivalent inputs produce the same string
+fn canonical_json(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut fields: Vec<_> = map.iter().collect();
+            fields.sort_by(|a, b| a.0.cmp(b.0));
+            let fields: Vec<String> = fields
+                .into_iter()
+                .map(|(k, v)| format!("{}:{}", Value::String(k.clone()), canonical_json(v)))
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(canonical_json).collect();
+            format!("[{}]", items.join(","))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Check a tool's input against its `input_schema()`, reporting every missing or
+/// mistyped field. Schemas that aren't valid JSON Schema are skipped with a warning.
+pub fn validate_input(schema: &Value, input: &Value) -> Result<()> {
+    let validator = match jsonschema::validator_for(schema) {
+        Ok(validator) => validator,
+        Err(e) => {
+            warn!("Skipping input validation, tool schema is invalid: {}", e);
+            return Ok(());
+        }
+    };
+    let problems: Vec<String> = validator
+        .iter_errors(input)
+        .map(|error| {
+            let path = error
+                .instance_path
+                .as_str()
+                .trim_start_matches('/')
+                .replace('/', ".");
+            match &error.kind {
+                ValidationErrorKind::Required { property } => {
+                    let property = property.as_str().unwrap_or_default();
+                    if path.is_empty() {
+                        format!("Missing field '{}'", property)
+                    } else {
+                        format!("Missing field '{}.{}'", path, property)
+                    }
+                }
+                _ if path.is_empty() => format!("Invalid input: {}", error),
+                _ => format!("Invalid field '{}': {}", path, error),
+            }
+        })
+        .collect();
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("{}", problems.join("; ")))
     }
 }
 
@@ -170,12 +487,16 @@ mod tests {
         async fn execute(&self, _input: Value) -> Result<String> {
             Ok("dummy result".to_string())
         }
+
+        fn read_only(&self) -> bool {
+            true
+        }
     }
 
     #[tokio::test]
     async fn test_tool_registry() {
         let mut registry = ToolRegistry::new();
-        registry.register(Arc::new(DummyTool));
+        registry.register(Arc::new(DummyTool)).unwrap();
 
         assert_eq!(registry.len(), 1);
 
@@ -186,6 +507,279 @@ mod tests {
         assert_eq!(result.unwrap(), "dummy result");
     }
 
+    #[tokio::test]
+    async fn test_timed_tool() {
+        struct SlowTool;
+
+        #[async_trait]
+        impl ToolHandler for SlowTool {
+            fn name(&self) -> &str {
+                "slow"
+            }
+
+            fn description(&self) -> &str {
+                "Never finishes in time"
+            }
+
+            fn input_schema(&self) -> Value {
+                json_schema(serde_json::json!({}), vec![])
+            }
+
+            async fn execute(&self, _input: Value) -> Result<String> {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                Ok("too late".to_string())
+            }
+        }
+
+        let timed = TimedTool::new(Arc::new(SlowTool), Duration::from_millis(20));
+        let err = timed.execute(serde_json::json!({})).await.unwrap_err();
+        assert_eq!(err.to_string(), "Tool slow timed out after 20 ms");
+        assert_eq!(describe_timeout(Duration::from_millis(1500)), "1500 ms");
+        assert_eq!(describe_timeout(Duration::from_secs(1)), "1 second");
+        assert_eq!(describe_timeout(Duration::from_secs(30)), "30 seconds");
+
+        let timed = TimedTool::new(Arc::new(DummyTool), Duration::from_secs(5));
+        assert_eq!(timed.name(), "dummy");
+        assert_eq!(timed.description(), "A dummy tool for testing");
+        assert_eq!(timed.input_schema(), DummyTool.input_schema());
+        let result = timed
+            .execute(serde_json::json!({"message": "test"}))
+            .await
+            .unwrap();
+        assert_eq!(result, "dummy result");
+    }
+
+    /// Counts calls and echoes its input so cache hits are observable
+    struct CountingTool {
+        name: &'static str,
+        read_only: bool,
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    impl CountingTool {
+        fn new(name: &'static str) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                read_only: true,
+                calls: std::sync::atomic::AtomicUsize::new(0),
+            })
+        }
+
+        fn writer(name: &'static str) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                read_only: false,
+                calls: std::sync::atomic::AtomicUsize::new(0),
+            })
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl ToolHandler for CountingTool {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn description(&self) -> &str {
+            "Counts calls"
+        }
+
+        fn input_schema(&self) -> Value {
+            json_schema(serde_json::json!({}), vec![])
+        }
+
+        fn read_only(&self) -> bool {
+            self.read_only
+        }
+
+        async fn execute(&self, input: Value) -> Result<String> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if input.get("fail").is_some() {
+                return Err(anyhow!("failed"));
+            }
+            Ok(input.to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_caching_tool_reuses_results() {
+        let inner = CountingTool::new("read_emails");
+        let cached = CachingTool::new(inner.clone(), Duration::from_secs(60), 10);
+
+        cached
+            .execute(serde_json::json!({"limit": 5, "mailbox": "inbox"}))
+            .await
+            .unwrap();
+        // Same input with keys in a different order is a cache hit
+        cached
+            .execute(serde_json::json!({"mailbox": "inbox", "limit": 5}))
+            .await
+            .unwrap();
+        assert_eq!(inner.calls(), 1);
+
+        cached
+            .execute(serde_json::json!({"mailbox": "sent", "limit": 5}))
+            .await
+            .unwrap();
+        assert_eq!(inner.calls(), 2);
+
+        // Errors are not cached
+        assert!(
+            cached
+                .execute(serde_json::json!({"fail": true}))
+                .await
+                .is_err()
+        );
+        assert!(
+            cached
+                .execute(serde_json::json!({"fail": true}))
+                .await
+                .is_err()
+        );
+        assert_eq!(inner.calls(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_caching_tool_expires_and_evicts() {
+        let inner = CountingTool::new("read_calendar");
+        let cached = CachingTool::new(inner.clone(), Duration::from_millis(20), 10);
+        cached.execute(serde_json::json!({})).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        cached.execute(serde_json::json!({})).await.unwrap();
+        assert_eq!(inner.calls(), 2);
+
+        let inner = CountingTool::new("read_calendar");
+        let cached = CachingTool::new(inner.clone(), Duration::from_secs(60), 1);
+        cached
+            .execute(serde_json::json!({"days": 1}))
+            .await
+            .unwrap();
+        cached
+            .execute(serde_json::json!({"days": 2}))
+            .await
+            .unwrap();
+        cached
+            .execute(serde_json::json!({"days": 1}))
+            .await
+            .unwrap();
+        assert_eq!(inner.calls(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_caching_tool_bypasses_side_effecting_tools() {
+        let inner = CountingTool::writer("send_email");
+        let cached = CachingTool::new(inner.clone(), Duration::from_secs(60), 10);
+        cached
+            .execute(serde_json::json!({"to": "a@b.c"}))
+            .await
+            .unwrap();
+        cached
+            .execute(serde_json::json!({"to": "a@b.c"}))
+            .await
+            .unwrap();
+        assert_eq!(inner.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_side_effecting_tools_invalidate_cache() {
+        let generation = CacheGeneration::new();
+        let reader = CountingTool::new("read_calendar");
+        let cached = CachingTool::new(reader.clone(), Duration::from_secs(60), 10)
+            .with_generation(generation.clone());
+        let writer = InvalidatingTool::new(
+            CountingTool::writer("create_calendar_event"),
+            generation.clone(),
+        );
+
+        cached.execute(serde_json::json!({})).await.unwrap();
+        cached.execute(serde_json::json!({})).await.unwrap();
+        assert_eq!(reader.calls(), 1);
+
+        writer
+            .execute(serde_json::json!({"title": "x"}))
+            .await
+            .unwrap();
+        cached.execute(serde_json::json!({})).await.unwrap();
+        assert_eq!(reader.calls(), 2);
+
+        // Failed writes invalidate too
+        assert!(
+            writer
+                .execute(serde_json::json!({"fail": true}))
+                .await
+                .is_err()
+        );
+        cached.execute(serde_json::json!({})).await.unwrap();
+        assert_eq!(reader.calls(), 3);
+        assert_eq!(writer.name(), "create_calendar_event");
+    }
+
+    #[tokio::test]
+    async fn test_registry_wrap() {
+        let mut registry = ToolRegistry::new();
+        let inner = CountingTool::new("search_knowledge");
+        registry.register(inner.clone()).unwrap();
+
+        assert!(!registry.wrap("missing", |h| h));
+        assert!(registry.wrap("search_knowledge", |h| {
+            Arc::new(CachingTool::new(h, Duration::from_secs(60), 10))
+        }));
+        registry
+            .execute("search_knowledge", serde_json::json!({"query": "x"}))
+            .await
+            .unwrap();
+        registry
+            .execute("search_knowledge", serde_json::json!({"query": "x"}))
+            .await
+            .unwrap();
+        assert_eq!(inner.calls(), 1);
+    }
+
+    #[test]
+    fn test_duplicate_tool_rejected() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Arc::new(DummyTool)).unwrap();
+        let err = registry.register(Arc::new(DummyTool)).unwrap_err();
+        assert_eq!(err.to_string(), "Tool 'dummy' is already registered");
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn test_validate_input() {
+        let schema = json_schema(
+            serde_json::json!({
+                "to": {"type": "string"},
+                "limit": {"type": "number"}
+            }),
+            vec!["to"],
+        );
+        assert!(validate_input(&schema, &serde_json::json!({"to": "a@b.c", "limit": 5})).is_ok());
+
+        let err = validate_input(&schema, &serde_json::json!({"limit": "5"})).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid field 'limit': \"5\" is not of type \"number\"; Missing field 'to'"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_registry_validates_input() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Arc::new(DummyTool)).unwrap();
+        let err = registry
+            .execute("dummy", serde_json::json!({"message": 42}))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid field 'message': 42 is not of type \"string\""
+        );
+    }
+
     #[tokio::test]
     async fn test_unknown_tool() {
         let registry = ToolRegistry::new();
@@ -193,10 +787,86 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_safe_mode_blocks_side_effecting_tools() {
+        struct SendEmailTool;
+
+        #[async_trait]
+        impl ToolHandler for SendEmailTool {
+            fn name(&self) -> &str {
+                "send_email"
+            }
+
+            fn description(&self) -> &str {
+                "Pretends to send an email"
+            }
+
+            fn input_schema(&self) -> Value {
+                json_schema(serde_json::json!({}), vec![])
+            }
+
+            async fn execute(&self, _input: Value) -> Result<String> {
+                Ok("sent".to_string())
+            }
+        }
+
+        let mode = SafeMode::new();
+        let mut registry = ToolRegistry::new().with_safe_mode(mode.clone());
+        registry.register(Arc::new(DummyTool)).unwrap();
+        registry.register(Arc::new(SendEmailTool)).unwrap();
+
+        mode.enable();
+        let result = registry
+            .execute("send_email", serde_json::json!({"to": "a@b.c"}))
+            .await
+            .unwrap();
+        assert!(result.starts_with("[SAFE MODE] would have"));
+
+        // Read-only tools still run
+        let result = registry
+            .execute("dummy", serde_json::json!({"message": "test"}))
+            .await
+            .unwrap();
+        assert_eq!(result, "dummy result");
+
+        // Tools that don't declare themselves read-only are blocked by default
+        let unmarked = CountingTool::writer("mcp_tool");
+        registry.register(unmarked.clone()).unwrap();
+        let result = registry
+            .execute("mcp_tool", serde_json::json!({}))
+            .await
+            .unwrap();
+        assert!(result.starts_with("[SAFE MODE] would have run `mcp_tool`"));
+        assert_eq!(unmarked.calls(), 0);
+
+        mode.disable();
+        let result = registry
+            .execute("send_email", serde_json::json!({}))
+            .await
+            .unwrap();
+        assert_eq!(result, "sent");
+    }
+
+    #[test]
+    fn test_list_tool_specs() {
+        let mut registry = ToolRegistry::new();
+        assert!(registry.list().is_empty());
+        registry
+            .register(CountingTool::new("search_knowledge"))
+            .unwrap();
+        registry.register(Arc::new(DummyTool)).unwrap();
+
+        let specs = registry.list();
+        let names: Vec<&str> = specs.iter().map(|spec| spec.name.as_str()).collect();
+        assert_eq!(names, vec!["dummy", "search_knowledge"]);
+        assert_eq!(specs[0].description, "A dummy tool for testing");
+        assert_eq!(specs[0].input_schema, DummyTool.input_schema());
+    }
+
     #[test]
     fn test_filter_tools() {
         let mut registry = ToolRegistry::new();
-        registry.register(Arc::new(DummyTool));
+        registry.register(Arc::new(DummyTool)).unwrap();
 
         let filtered = registry.filter_tools(&["dummy".to_string()]);
         assert_eq!(filtered.len(), 1);
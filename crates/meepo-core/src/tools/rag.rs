@@ -7,24 +7,37 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{debug, info};
 
+use super::memory::{collapse_schema, collapse_threshold, maybe_collapse};
 use super::{ToolHandler, json_schema};
 use meepo_knowledge::chunking::{
-    ChunkingConfig, DocumentMetadata, chunk_text, detect_content_type,
+    ChunkingConfig, ChunkingStrategy, DocumentMetadata, chunk_text, detect_content_type,
 };
-use meepo_knowledge::graph_rag::{GraphRagConfig, format_graph_context, graph_expand};
-use meepo_knowledge::{KnowledgeDb, KnowledgeGraph};
+use meepo_knowledge::extract::extract_text;
+use meepo_knowledge::graph_rag::{
+    Bm25Reranker, EntitySource, GraphRagConfig, Reranker, format_graph_context, graph_expand,
+    rerank,
+};
+use meepo_knowledge::{KnowledgeDb, KnowledgeGraph, weighted_hybrid_search_rrf};
+
+/// RRF constant used when fusing keyword and semantic rankings
+const RRF_K: f32 = 60.0;
 
 /// Smart recall tool that uses GraphRAG for relationship-aware retrieval.
 ///
 /// Unlike the basic `recall` tool, this traverses entity relationships
-/// to pull in contextually connected knowledge.
+/// to pull in contextually connected knowledge. When the graph has an
+/// embedding provider, keyword and semantic matches are fused (hybrid).
+/// Expanded results are reranked against the query before formatting.
 pub struct SmartRecallTool {
     graph: Arc<KnowledgeGraph>,
     db: Arc<KnowledgeDb>,
     config: GraphRagConfig,
+    semantic_weight: f32,
+    reranker: Arc<dyn Reranker>,
 }
 
 impl SmartRecallTool {
@@ -33,13 +46,79 @@ impl SmartRecallTool {
             graph,
             db,
             config: GraphRagConfig::default(),
+            semantic_weight: 0.5,
+            reranker: Arc::new(Bm25Reranker::default()),
         }
     }
 
+    /// Replace the default BM25 reranker (set `rerank_top_k: None` in the
+    /// config to disable reranking entirely)
+    pub fn with_reranker(mut self, reranker: Arc<dyn Reranker>) -> Self {
+        self.reranker = reranker;
+        self
+    }
+
     pub fn with_config(mut self, config: GraphRagConfig) -> Self {
         self.config = config;
         self
     }
+
+    /// Weight of semantic vs keyword ranking in hybrid search (0.0 = keyword
+    /// only, 1.0 = semantic only). Ignored when the graph has no embeddings.
+    pub fn with_semantic_weight(mut self, weight: f32) -> Self {
+        self.semantic_weight = weight.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Find seed entities: keyword hits, fused with semantic hits when enabled.
+    /// Also returns the highlighted snippet of each keyword hit by entity ID.
+    async fn find_seeds(
+        &self,
+        query: &str,
+        limit: usize,
+        semantic_weight: f32,
+    ) -> Result<(Vec<(String, f32)>, HashMap<String, String>)> {
+        let keyword_results = self
+            .graph
+            .search(query, limit)
+            .context("Failed to search knowledge graph")?;
+        let snippets: HashMap<String, String> = keyword_results
+            .iter()
+            .filter_map(|r| Some((r.id.clone(), r.snippet.clone()?)))
+            .collect();
+
+        if !self.graph.has_embeddings() || semantic_weight <= 0.0 {
+            let seeds = keyword_results
+                .into_iter()
+                .map(|r| (r.id, r.score))
+                .collect();
+            return Ok((seeds, snippets));
+        }
+
+        let semantic_results = self
+            .graph
+            .search_semantic(query, limit)
+            .await
+            .context("Failed to run semantic search")?;
+        let keyword_ids: Vec<String> = keyword_results.into_iter().map(|r| r.id).collect();
+        let fused = weighted_hybrid_search_rrf(
+            &keyword_ids,
+            &semantic_results,
+            1.0 - semantic_weight,
+            semantic_weight,
+            RRF_K,
+            limit,
+        );
+
+        // RRF scores are tiny; rescale so the best seed scores 1.0
+        let top = fused.first().map(|r| r.score).unwrap_or(0.0);
+        let seeds = fused
+            .into_iter()
+            .filter(|r| r.score > 0.0)
+            .map(|r| (r.entity_id, r.score / top))
+            .collect();
+        Ok((seeds, snippets))
+    }
 }
 
 #[async_trait]
@@ -51,27 +130,42 @@ impl ToolHandler for SmartRecallTool {
     fn description(&self) -> &str {
         "Search the knowledge graph with relationship-aware retrieval (GraphRAG). \
          Finds directly matching entities AND related knowledge by traversing \
-         entity relationships. Returns richer context than basic recall."
+         entity relationships. Matches by meaning as well as keywords when semantic \
+         search is enabled. Returns richer context than basic recall."
     }
 
     fn input_schema(&self) -> Value {
-        json_schema(
-            serde_json::json!({
-                "query": {
-                    "type": "string",
-                    "description": "Search query for knowledge retrieval"
-                },
-                "limit": {
-                    "type": "number",
-                    "description": "Maximum number of direct results (default: 5)"
-                },
-                "max_hops": {
-                    "type": "number",
-                    "description": "Maximum relationship hops to traverse (default: 2)"
-                }
-            }),
-            vec!["query"],
-        )
+        let mut properties = serde_json::json!({
+            "query": {
+                "type": "string",
+                "description": "Search query for knowledge retrieval"
+            },
+            "limit": {
+                "type": "number",
+                "description": "Maximum number of direct results (default: 5)"
+            },
+            "max_hops": {
+                "type": "number",
+                "description": "Maximum relationship hops to traverse (default: 2)"
+            },
+            "semantic_weight": {
+                "type": "number",
+                "minimum": 0,
+                "maximum": 1,
+                "description": "How much to favor semantic over keyword matches when semantic \
+                                search is enabled: 0 = keyword only, 1 = semantic only \
+                                (default: 0.5)"
+            }
+        });
+        if let (Some(props), Value::Object(extra)) = (properties.as_object_mut(), collapse_schema())
+        {
+            props.extend(extra);
+        }
+        json_schema(properties, vec!["query"])
+    }
+
+    fn read_only(&self) -> bool {
+        true
     }
 
     async fn execute(&self, input: Value) -> Result<String> {
@@ -81,154 +175,297 @@ impl ToolHandler for SmartRecallTool {
             .ok_or_else(|| anyhow::anyhow!("Missing 'query' parameter"))?;
         let limit = input.get("limit").and_then(|v| v.as_u64()).unwrap_or(5) as usize;
         let max_hops = input.get("max_hops").and_then(|v| v.as_u64()).unwrap_or(2) as usize;
+        let semantic_weight = input
+            .get("semantic_weight")
+            .and_then(|v| v.as_f64())
+            .map(|w| (w as f32).clamp(0.0, 1.0))
+            .unwrap_or(self.semantic_weight);
+        let threshold = collapse_threshold(&input);
 
         debug!(
             "Smart recall for: {} (limit={}, hops={})",
             query, limit, max_hops
         );
 
-        // Step 1: Search using Tantivy full-text search
-        let search_results = self
-            .graph
-            .search(query, limit)
-            .context("Failed to search knowledge graph")?;
+        // Step 1: Search using Tantivy full-text search (plus vectors if enabled)
+        let (seeds, mut snippets) = self.find_seeds(query, limit, semantic_weight).await?;
 
-        if search_results.is_empty() {
+        if seeds.is_empty() {
             return Ok("No matching knowledge found.".to_string());
         }
 
         // Step 2: Expand via GraphRAG
-        let seeds: Vec<(String, f32)> = search_results
-            .iter()
-            .map(|r| (r.id.clone(), r.score))
-            .collect();
-
         let config = GraphRagConfig {
             max_hops,
             max_expanded_results: limit * 3,
             ..self.config.clone()
         };
 
-        let expanded = graph_expand(&self.db, &seeds, &config)
+        let mut expanded = graph_expand(&self.db, &seeds, &config)
             .await
             .context("Failed to expand via GraphRAG")?;
+        for scored in &mut expanded {
+            scored.snippet = snippets.remove(&scored.entity.id);
+        }
+
+        // Step 3: Rerank against the query so the most relevant result comes first
+        if let Some(top_k) = config.rerank_top_k {
+            expanded = rerank(query, expanded, top_k, self.reranker.as_ref());
+        }
 
-        // Step 3: Format results
+        // Step 4: Optionally collapse near-duplicates (results are sorted best first)
+        let total = expanded.len();
+        let expanded: Vec<_> = maybe_collapse(expanded, threshold, |r| match &r.entity.metadata {
+            Some(metadata) => format!("{} {}", r.entity.name, metadata),
+            None => r.entity.name.clone(),
+        })
+        .into_iter()
+        .map(|c| c.item)
+        .collect();
+        let collapsed = total - expanded.len();
+
+        // Step 5: Format results
         let context = format_graph_context(&expanded, &config);
 
         if context.is_empty() {
             return Ok("No matching knowledge found.".to_string());
         }
 
+        let direct = expanded
+            .iter()
+            .filter(|r| matches!(r.source, EntitySource::DirectMatch { .. }))
+            .count();
         let mut output = format!(
-            "Found {} result(s) ({} direct, {} via relationships):\n\n",
+            "Found {} result(s) ({} direct, {} via relationships",
             expanded.len(),
-            search_results.len(),
-            expanded.len().saturating_sub(search_results.len())
+            direct,
+            expanded.len() - direct
         );
+        if collapsed > 0 {
+            output.push_str(&format!(", {} near-duplicate(s) collapsed", collapsed));
+        }
+        output.push_str("):\n\n");
         output.push_str(&context);
 
         Ok(output)
     }
 }
 
-/// Ingest a document into the knowledge graph by chunking and indexing it.
-pub struct IngestDocumentTool {
-    graph: Arc<KnowledgeGraph>,
-    chunking_config: ChunkingConfig,
-}
+/// Maximum hops `get_related` will traverse, whatever the caller asks for
+const MAX_RELATED_HOPS: usize = 5;
 
-impl IngestDocumentTool {
-    pub fn new(graph: Arc<KnowledgeGraph>) -> Self {
-        Self {
-            graph,
-            chunking_config: ChunkingConfig::default(),
-        }
-    }
+/// List what is connected to a known entity by walking its relationships.
+///
+/// Unlike `smart_recall`, this starts from an entity ID rather than a text
+/// query, and reports the path of relationships leading to each result.
+pub struct GetRelatedTool {
+    db: Arc<KnowledgeDb>,
+}
 
-    pub fn with_chunking_config(mut self, config: ChunkingConfig) -> Self {
-        self.chunking_config = config;
-        self
+impl GetRelatedTool {
+    pub fn new(db: Arc<KnowledgeDb>) -> Self {
+        Self { db }
     }
 }
 
 #[async_trait]
-impl ToolHandler for IngestDocumentTool {
+impl ToolHandler for GetRelatedTool {
     fn name(&self) -> &str {
-        "ingest_document"
+        "get_related"
     }
 
     fn description(&self) -> &str {
-        "Ingest a document into the knowledge graph. The document is split into \
-         chunks and each chunk is indexed for later retrieval. Supports text files, \
-         markdown, code, and other text formats. Use this to build up the knowledge \
-         base from files."
+        "List the entities connected to a known entity in the knowledge graph, \
+         with the chain of relationships linking each one back to it."
     }
 
     fn input_schema(&self) -> Value {
         json_schema(
             serde_json::json!({
-                "path": {
+                "entity_id": {
                     "type": "string",
-                    "description": "Path to the file to ingest"
+                    "description": "ID of the entity to start from"
                 },
-                "title": {
+                "max_hops": {
+                    "type": "number",
+                    "description": "Maximum relationship hops to traverse (default: 2, max: 5)"
+                },
+                "relation_type": {
                     "type": "string",
-                    "description": "Optional title for the document (defaults to filename)"
+                    "description": "Only follow relationships of this type (e.g. 'works_with')"
                 },
-                "tags": {
-                    "type": "array",
-                    "items": { "type": "string" },
-                    "description": "Optional tags to associate with this document"
+                "limit": {
+                    "type": "number",
+                    "description": "Maximum number of related entities to return (default: 20)"
                 }
             }),
-            vec!["path"],
+            vec!["entity_id"],
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
-        let path = input
-            .get("path")
+        let entity_id = input
+            .get("entity_id")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
-        let title = input.get("title").and_then(|v| v.as_str());
-        let tags: Vec<String> = input
-            .get("tags")
-            .and_then(|v| v.as_array())
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                    .collect()
-            })
-            .unwrap_or_default();
+            .ok_or_else(|| anyhow::anyhow!("Missing 'entity_id' parameter"))?;
+        let max_hops = (input.get("max_hops").and_then(|v| v.as_u64()).unwrap_or(2) as usize)
+            .clamp(1, MAX_RELATED_HOPS);
+        let limit = input.get("limit").and_then(|v| v.as_u64()).unwrap_or(20) as usize;
+        let relation_type = input
+            .get("relation_type")
+            .and_then(|v| v.as_str())
+            .map(|t| self.db.resolve_relation_type(t))
+            .transpose()?;
 
-        // Expand ~ in path
-        let expanded_path = if let Some(rest) = path.strip_prefix("~/") {
-            if let Some(home) = dirs::home_dir() {
-                home.join(rest)
-            } else {
-                std::path::PathBuf::from(path)
-            }
-        } else {
-            std::path::PathBuf::from(path)
+        let start = self
+            .db
+            .get_entity(entity_id)
+            .await
+            .context("Failed to look up entity")?
+            .ok_or_else(|| anyhow::anyhow!("No entity found with ID: {}", entity_id))?;
+
+        debug!(
+            "Finding entities related to {} (hops={}, relation={:?})",
+            entity_id, max_hops, relation_type
+        );
+
+        let config = GraphRagConfig {
+            max_hops,
+            // The start entity is always part of the expansion
+            max_expanded_results: limit + 1,
+            relation_type,
+            ..Default::default()
         };
+        let seeds = vec![(entity_id.to_string(), 1.0)];
+        let expanded = graph_expand(&self.db, &seeds, &config)
+            .await
+            .context("Failed to traverse relationships")?;
+
+        let names: HashMap<&str, &str> = expanded
+            .iter()
+            .map(|r| (r.entity.id.as_str(), r.entity.name.as_str()))
+            .collect();
+        let name_of = |id: &str| names.get(id).copied().unwrap_or(id).to_string();
+
+        let related: Vec<_> = expanded.iter().filter(|r| !r.path.is_empty()).collect();
+        if related.is_empty() {
+            return Ok(format!("Nothing is connected to '{}'.", start.name));
+        }
+
+        let mut output = format!(
+            "{} entit{} connected to '{}' ({}):\n",
+            related.len(),
+            if related.len() == 1 { "y" } else { "ies" },
+            start.name,
+            start.entity_type
+        );
+        for result in related {
+            let mut chain = start.name.clone();
+            let mut at = start.id.as_str();
+            for rel in &result.path {
+                if rel.source_id == at {
+                    chain.push_str(&format!(
+                        " -[{}]-> {}",
+                        rel.relation_type,
+                        name_of(&rel.target_id)
+                    ));
+                    at = &rel.target_id;
+                } else {
+                    chain.push_str(&format!(
+                        " <-[{}]- {}",
+                        rel.relation_type,
+                        name_of(&rel.source_id)
+                    ));
+                    at = &rel.source_id;
+                }
+            }
+            output.push_str(&format!(
+                "- {} ({}, ID: {}), {} hop(s): {}\n",
+                result.entity.name,
+                result.entity.entity_type,
+                result.entity.id,
+                result.path.len(),
+                chain
+            ));
+        }
+        Ok(output)
+    }
+}
+
+/// Default cap on how many files a directory ingestion will touch
+const DEFAULT_MAX_INGEST_FILES: usize = 100;
+
+/// Ingest a document into the knowledge graph by chunking and indexing it.
+pub struct IngestDocumentTool {
+    graph: Arc<KnowledgeGraph>,
+    chunking_config: ChunkingConfig,
+}
+
+/// Per-call options shared by every file in an ingestion
+struct IngestOptions<'a> {
+    tags: &'a [String],
+    create_document_entity: bool,
+    chunking: ChunkingConfig,
+}
 
-        // Read the file
-        let content = tokio::fs::read_to_string(&expanded_path)
+/// Result of ingesting a single file
+struct IngestedFile {
+    title: String,
+    doc_id: Option<String>,
+    chunk_ids: Vec<String>,
+    total_chars: usize,
+    sections: usize,
+    section_kind: &'static str,
+    content_type: &'static str,
+}
+
+impl IngestDocumentTool {
+    pub fn new(graph: Arc<KnowledgeGraph>) -> Self {
+        Self {
+            graph,
+            chunking_config: ChunkingConfig::default(),
+        }
+    }
+
+    pub fn with_chunking_config(mut self, config: ChunkingConfig) -> Self {
+        self.chunking_config = config;
+        self
+    }
+
+    /// Extract, chunk, and index one file. Returns `None` if it has no text.
+    async fn ingest_file(
+        &self,
+        path: &std::path::Path,
+        title: Option<&str>,
+        options: &IngestOptions<'_>,
+    ) -> Result<Option<IngestedFile>> {
+        let source_path = path.to_string_lossy().to_string();
+
+        // Read the file and pull out its text (PDF and DOCX need extracting)
+        let bytes = tokio::fs::read(path)
             .await
-            .context(format!("Failed to read file: {}", expanded_path.display()))?;
+            .context(format!("Failed to read file: {}", path.display()))?;
+        let content_type = detect_content_type(&source_path);
+        let extracted = tokio::task::spawn_blocking(move || extract_text(&bytes, content_type))
+            .await
+            .context("Text extraction task panicked")?
+            .context(format!("Failed to ingest {}", path.display()))?;
+        let content = extracted.text;
 
-        if content.is_empty() {
-            return Ok("File is empty, nothing to ingest.".to_string());
+        if content.trim().is_empty() {
+            return Ok(None);
         }
 
-        let filename = expanded_path
+        let filename = path
             .file_name()
             .map(|n| n.to_string_lossy().to_string())
             .unwrap_or_else(|| "unknown".to_string());
 
         let doc_title = title.unwrap_or(&filename);
-        let content_type = detect_content_type(path);
 
         info!(
             "Ingesting document: {} ({} chars, {})",
@@ -238,24 +475,31 @@ impl ToolHandler for IngestDocumentTool {
         );
 
         // Chunk the document
-        let chunks = chunk_text(&content, &self.chunking_config);
-
-        // Create a parent document entity
-        let doc_metadata = serde_json::json!({
-            "source_path": path,
-            "content_type": content_type,
-            "total_chars": content.len(),
-            "chunk_count": chunks.len(),
-            "tags": tags,
-        });
+        let chunks = chunk_text(&content, &options.chunking);
 
-        let doc_id = self
-            .graph
-            .add_entity(doc_title, "document", Some(doc_metadata))
-            .await
-            .context("Failed to create document entity")?;
+        // Create a parent document entity, unless only the chunks are wanted
+        let doc_id = if options.create_document_entity {
+            let doc_metadata = serde_json::json!({
+                "source_path": source_path,
+                "content_type": content_type,
+                "total_chars": content.len(),
+                "sections": extracted.sections,
+                "section_kind": extracted.section_kind,
+                "chunk_count": chunks.len(),
+                "tags": options.tags,
+            });
+
+            Some(
+                self.graph
+                    .add_entity(doc_title, "document", Some(doc_metadata))
+                    .await
+                    .context("Failed to create document entity")?,
+            )
+        } else {
+            None
+        };
 
-        // Index each chunk as a child entity linked to the document
+        // Index each chunk as an entity, linked to the document if there is one
         let mut chunk_ids = Vec::new();
         for chunk in &chunks {
             let chunk_name = format!(
@@ -265,14 +509,19 @@ impl ToolHandler for IngestDocumentTool {
                 chunk.total_chunks
             );
 
-            let chunk_metadata = serde_json::json!({
+            let mut chunk_metadata = serde_json::json!({
                 "full_content": chunk.content,
                 "chunk_index": chunk.chunk_index,
                 "start_offset": chunk.start_offset,
                 "end_offset": chunk.end_offset,
                 "total_chunks": chunk.total_chunks,
                 "parent_document": doc_id,
+                "source_path": source_path,
+                "title": doc_title,
             });
+            if !chunk.heading_path.is_empty() {
+                chunk_metadata["heading_path"] = serde_json::json!(chunk.heading_path.join(" > "));
+            }
 
             let chunk_id = self
                 .graph
@@ -281,10 +530,12 @@ impl ToolHandler for IngestDocumentTool {
                 .context("Failed to create chunk entity")?;
 
             // Link chunk to parent document
-            self.graph
-                .link_entities(&doc_id, &chunk_id, "contains_chunk", None)
-                .await
-                .context("Failed to link chunk to document")?;
+            if let Some(doc_id) = &doc_id {
+                self.graph
+                    .link_entities(doc_id, &chunk_id, "contains_chunk", None)
+                    .await
+                    .context("Failed to link chunk to document")?;
+            }
 
             chunk_ids.push(chunk_id);
         }
@@ -297,22 +548,292 @@ impl ToolHandler for IngestDocumentTool {
                 .await;
         }
 
+        Ok(Some(IngestedFile {
+            title: doc_title.to_string(),
+            doc_id,
+            chunk_ids,
+            total_chars: content.len(),
+            sections: extracted.sections,
+            section_kind: extracted.section_kind,
+            content_type,
+        }))
+    }
+
+    /// Ingest every file under `dir` matching `pattern`, linking each one to a
+    /// shared collection entity. Failures are counted rather than aborting.
+    async fn ingest_directory(
+        &self,
+        dir: &std::path::Path,
+        pattern: &str,
+        max_files: usize,
+        title: Option<&str>,
+        options: &IngestOptions<'_>,
+    ) -> Result<String> {
+        let full_pattern = format!(
+            "{}/{}",
+            glob::Pattern::escape(&dir.to_string_lossy()),
+            pattern.trim_start_matches('/')
+        );
+        let mut files: Vec<std::path::PathBuf> = glob::glob(&full_pattern)
+            .context(format!("Invalid glob pattern: {}", pattern))?
+            .filter_map(|entry| entry.ok())
+            .filter(|path| path.is_file())
+            .collect();
+        files.sort();
+
+        if files.is_empty() {
+            return Ok(format!(
+                "No files in {} match '{}', nothing to ingest.",
+                dir.display(),
+                pattern
+            ));
+        }
+
+        let skipped = files.len().saturating_sub(max_files);
+        files.truncate(max_files);
+
+        // Check the link types before creating anything, so a restrictive
+        // relation vocabulary can't leave an unlinked collection behind
+        let db = self.graph.db();
+        for relation_type in ["contains_document", "contains_chunk"] {
+            db.resolve_relation_type(relation_type)
+                .context("Cannot link files to a collection")?;
+        }
+
+        let collection_name = title.map(|t| t.to_string()).unwrap_or_else(|| {
+            dir.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| dir.display().to_string())
+        });
+        let collection_id = self
+            .graph
+            .add_entity(
+                &collection_name,
+                "collection",
+                Some(serde_json::json!({
+                    "source_path": dir.to_string_lossy(),
+                    "glob": pattern,
+                    "tags": options.tags,
+                })),
+            )
+            .await
+            .context("Failed to create collection entity")?;
+
+        let mut ingested = 0;
+        let mut empty = 0;
+        let mut total_chunks = 0;
+        let mut failures = Vec::new();
+        for file in &files {
+            let relative = file.strip_prefix(dir).unwrap_or(file).to_string_lossy();
+            let result = match self.ingest_file(file, Some(&relative), options).await {
+                Ok(Some(doc)) => self
+                    .link_to_collection(&collection_id, &doc)
+                    .await
+                    .map(|_| {
+                        total_chunks += doc.chunk_ids.len();
+                        ingested += 1;
+                    }),
+                Ok(None) => {
+                    empty += 1;
+                    Ok(())
+                }
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                debug!("Failed to ingest {}: {:#}", file.display(), e);
+                failures.push(format!("- {}: {:#}", relative, e));
+            }
+        }
+
+        let mut output = format!(
+            "Ingested collection '{}': {} file(s) succeeded, {} failed, {} empty ({} chunks)\n\
+             Collection ID: {}",
+            collection_name,
+            ingested,
+            failures.len(),
+            empty,
+            total_chunks,
+            collection_id
+        );
+        if skipped > 0 {
+            output.push_str(&format!(
+                "\n{} more matching file(s) not ingested (max_files = {})",
+                skipped, max_files
+            ));
+        }
+        if !failures.is_empty() {
+            output.push_str("\nFailed:\n");
+            output.push_str(&failures.join("\n"));
+        }
+        Ok(output)
+    }
+
+    /// Link an ingested file to its collection: the document entity if there
+    /// is one, otherwise each of its chunks.
+    async fn link_to_collection(&self, collection_id: &str, doc: &IngestedFile) -> Result<()> {
+        match &doc.doc_id {
+            Some(doc_id) => {
+                self.graph
+                    .link_entities(collection_id, doc_id, "contains_document", None)
+                    .await?;
+            }
+            None => {
+                for chunk_id in &doc.chunk_ids {
+                    self.graph
+                        .link_entities(collection_id, chunk_id, "contains_chunk", None)
+                        .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ToolHandler for IngestDocumentTool {
+    fn name(&self) -> &str {
+        "ingest_document"
+    }
+
+    fn description(&self) -> &str {
+        "Ingest a document into the knowledge graph. The document is split into \
+         chunks and each chunk is indexed for later retrieval. Supports PDF, Word (DOCX), \
+         text files, markdown, code, and other text formats. Pass a directory to ingest \
+         every matching file under it as one collection. Use this to build up the \
+         knowledge base from files."
+    }
+
+    fn input_schema(&self) -> Value {
+        json_schema(
+            serde_json::json!({
+                "path": {
+                    "type": "string",
+                    "description": "Path to the file or directory to ingest"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Optional title for the document or collection (defaults to the file or directory name)"
+                },
+                "tags": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Optional tags to associate with this document"
+                },
+                "create_document_entity": {
+                    "type": "boolean",
+                    "description": "Create a parent document entity (default: true). When false, \
+                                    only the chunks are indexed, each carrying the source path \
+                                    and title."
+                },
+                "glob": {
+                    "type": "string",
+                    "description": "For directories: which files to ingest, relative to the \
+                                    directory (default: '**/*', e.g. '**/*.md')"
+                },
+                "strategy": {
+                    "type": "string",
+                    "enum": ["fixed_size", "sentence", "markdown"],
+                    "description": "How to split the text: fixed_size (default), sentence \
+                                    (whole sentences only), or markdown (by heading, keeping \
+                                    code blocks intact and recording each chunk's heading path)"
+                },
+                "max_files": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "For directories: maximum number of files to ingest (default: 100)"
+                }
+            }),
+            vec!["path"],
+        )
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        let path = input
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
+        let title = input.get("title").and_then(|v| v.as_str());
+        let tags: Vec<String> = input
+            .get("tags")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let create_document_entity = input
+            .get("create_document_entity")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let strategy = match input.get("strategy").and_then(|v| v.as_str()) {
+            Some("fixed_size") => ChunkingStrategy::FixedSize,
+            Some("sentence") => ChunkingStrategy::Sentence,
+            Some("markdown") => ChunkingStrategy::Markdown,
+            Some(other) => anyhow::bail!(
+                "Unknown chunking strategy '{}' (expected fixed_size, sentence, or markdown)",
+                other
+            ),
+            None => self.chunking_config.strategy,
+        };
+        let options = IngestOptions {
+            tags: &tags,
+            create_document_entity,
+            chunking: ChunkingConfig {
+                strategy,
+                ..self.chunking_config.clone()
+            },
+        };
+
+        // Expand ~ in path
+        let expanded_path = if let Some(rest) = path.strip_prefix("~/") {
+            if let Some(home) = dirs::home_dir() {
+                home.join(rest)
+            } else {
+                std::path::PathBuf::from(path)
+            }
+        } else {
+            std::path::PathBuf::from(path)
+        };
+
+        if expanded_path.is_dir() {
+            let pattern = input.get("glob").and_then(|v| v.as_str()).unwrap_or("**/*");
+            let max_files = input
+                .get("max_files")
+                .and_then(|v| v.as_u64())
+                .map(|v| (v as usize).max(1))
+                .unwrap_or(DEFAULT_MAX_INGEST_FILES);
+            return self
+                .ingest_directory(&expanded_path, pattern, max_files, title, &options)
+                .await;
+        }
+
+        let Some(doc) = self.ingest_file(&expanded_path, title, &options).await? else {
+            return Ok("File is empty, nothing to ingest.".to_string());
+        };
+
         let metadata = DocumentMetadata {
             source_path: Some(path.to_string()),
-            title: Some(doc_title.to_string()),
-            content_type: content_type.to_string(),
-            total_chars: content.len(),
-            chunk_count: chunks.len(),
+            title: Some(doc.title),
+            content_type: doc.content_type.to_string(),
+            total_chars: doc.total_chars,
+            chunk_count: doc.chunk_ids.len(),
         };
 
-        Ok(format!(
-            "Ingested '{}': {} chunks created from {} chars ({})\nDocument ID: {}",
+        let mut output = format!(
+            "Ingested '{}': {} chunks created from {} chars across {} {}(s) ({})\n",
             metadata.title.as_deref().unwrap_or("unknown"),
             metadata.chunk_count,
             metadata.total_chars,
+            doc.sections,
+            doc.section_kind,
             metadata.content_type,
-            doc_id
-        ))
+        );
+        match doc.doc_id {
+            Some(doc_id) => output.push_str(&format!("Document ID: {}", doc_id)),
+            None => output.push_str("No document entity created (chunks only)"),
+        }
+        Ok(output)
     }
 }
 
@@ -365,6 +886,78 @@ mod tests {
         assert!(result.contains("No matching"));
     }
 
+    #[tokio::test]
+    async fn test_smart_recall_shows_highlighted_snippet() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let graph = Arc::new(
+            KnowledgeGraph::new(temp.path().join("test.db"), temp.path().join("test_index"))
+                .unwrap(),
+        );
+        let db = graph.db();
+        let filler = "Assorted notes on the garden and the weekly shopping. ".repeat(10);
+        graph
+            .add_entity(
+                "Handbook [chunk 4/9]",
+                "document_chunk",
+                Some(serde_json::json!({
+                    "full_content": format!("{}Tide tables list high water. {}", filler, filler)
+                })),
+            )
+            .await
+            .unwrap();
+
+        let recall = SmartRecallTool::new(graph, db);
+        let result = recall
+            .execute(serde_json::json!({"query": "tide"}))
+            .await
+            .unwrap();
+        assert!(result.contains("**Tide**"), "{}", result);
+        // The excerpt replaces the whole chunk
+        assert!(!result.contains("full_content"), "{}", result);
+    }
+
+    #[tokio::test]
+    async fn test_get_related() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let db = Arc::new(KnowledgeDb::new(temp.path().join("test.db")).unwrap());
+        let ann = db.insert_entity("Ann", "person", None).await.unwrap();
+        let bob = db.insert_entity("Bob", "person", None).await.unwrap();
+        let acme = db.insert_entity("Acme", "company", None).await.unwrap();
+        db.insert_relationship(&ann, &bob, "knows", None)
+            .await
+            .unwrap();
+        db.insert_relationship(&acme, &bob, "employs", None)
+            .await
+            .unwrap();
+
+        let tool = GetRelatedTool::new(db);
+        let result = tool
+            .execute(serde_json::json!({"entity_id": ann}))
+            .await
+            .unwrap();
+        assert!(result.starts_with("2 entities connected to 'Ann' (person):"));
+        assert!(result.contains("1 hop(s): Ann -[knows]-> Bob"));
+        assert!(result.contains("2 hop(s): Ann -[knows]-> Bob <-[employs]- Acme"));
+
+        let result = tool
+            .execute(serde_json::json!({"entity_id": ann, "max_hops": 1}))
+            .await
+            .unwrap();
+        assert!(result.starts_with("1 entity connected to 'Ann'"));
+
+        let result = tool
+            .execute(serde_json::json!({"entity_id": ann, "relation_type": "employs"}))
+            .await
+            .unwrap();
+        assert_eq!(result, "Nothing is connected to 'Ann'.");
+
+        assert!(
+            tool.execute(serde_json::json!({"entity_id": "missing"}))
+                .await
+                .is_err()
+        );
+    }
+
     #[tokio::test]
     async fn test_ingest_nonexistent_file() {
         let temp = tempfile::TempDir::new().unwrap();
@@ -408,6 +1001,18 @@ mod tests {
             .unwrap();
         assert!(result.contains("Ingested"));
         assert!(result.contains("Rust Guide"));
+        assert!(result.contains("across 5 line(s)"));
+
+        // Binary files without an extractor are rejected, not chunked
+        let binary_file = temp.path().join("image.png");
+        tokio::fs::write(&binary_file, [0x89, b'P', b'N', b'G', 0, 0xff])
+            .await
+            .unwrap();
+        let err = ingest
+            .execute(serde_json::json!({"path": binary_file.to_str().unwrap()}))
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("Unsupported format"));
 
         // Recall it
         let recall = SmartRecallTool::new(graph, db);
@@ -417,4 +1022,242 @@ mod tests {
             .unwrap();
         assert!(result.contains("Found"));
     }
+
+    #[tokio::test]
+    async fn test_ingest_chunks_only() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let db_path = temp.path().join("test.db");
+        let index_path = temp.path().join("test_index");
+        let graph = Arc::new(KnowledgeGraph::new(&db_path, &index_path).unwrap());
+        let db = graph.db();
+
+        let test_file = temp.path().join("notes.md");
+        tokio::fs::write(&test_file, "Borrow checking keeps Rust memory safe.")
+            .await
+            .unwrap();
+
+        let ingest = IngestDocumentTool::new(graph.clone());
+        let result = ingest
+            .execute(serde_json::json!({
+                "path": test_file.to_str().unwrap(),
+                "title": "Rust Notes",
+                "create_document_entity": false
+            }))
+            .await
+            .unwrap();
+        assert!(result.contains("No document entity created"));
+
+        let entities = graph.get_all_entities().await.unwrap();
+        assert!(entities.iter().all(|e| e.entity_type != "document"));
+        let chunk = entities
+            .iter()
+            .find(|e| e.entity_type == "document_chunk")
+            .unwrap();
+        let metadata = chunk.metadata.as_ref().unwrap();
+        assert_eq!(metadata["title"], "Rust Notes");
+        assert_eq!(metadata["source_path"], test_file.to_str().unwrap());
+        assert!(metadata["parent_document"].is_null());
+
+        // Chunks are still recalled normally
+        let recall = SmartRecallTool::new(graph, db);
+        let result = recall
+            .execute(serde_json::json!({"query": "borrow checking"}))
+            .await
+            .unwrap();
+        assert!(result.contains("Found"));
+    }
+
+    #[tokio::test]
+    async fn test_ingest_directory() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let db_path = temp.path().join("test.db");
+        let index_path = temp.path().join("test_index");
+        let graph = Arc::new(KnowledgeGraph::new(&db_path, &index_path).unwrap());
+        let db = graph.db();
+
+        let notes = temp.path().join("notes");
+        tokio::fs::create_dir_all(notes.join("daily"))
+            .await
+            .unwrap();
+        tokio::fs::write(notes.join("rust.md"), "Lifetimes tie borrows to owners.")
+            .await
+            .unwrap();
+        tokio::fs::write(notes.join("daily/monday.md"), "Reviewed the parser PR.")
+            .await
+            .unwrap();
+        tokio::fs::write(notes.join("broken.md"), [0xff, 0, 0xfe])
+            .await
+            .unwrap();
+        tokio::fs::write(notes.join("todo.txt"), "Not markdown, not ingested.")
+            .await
+            .unwrap();
+
+        let ingest = IngestDocumentTool::new(graph.clone());
+        let result = ingest
+            .execute(serde_json::json!({
+                "path": notes.to_str().unwrap(),
+                "glob": "**/*.md"
+            }))
+            .await
+            .unwrap();
+        assert!(result.contains("Ingested collection 'notes'"));
+        assert!(result.contains("2 file(s) succeeded, 1 failed"));
+        assert!(result.contains("- broken.md: "));
+
+        let entities = graph.get_all_entities().await.unwrap();
+        let collection = entities
+            .iter()
+            .find(|e| e.entity_type == "collection")
+            .unwrap();
+        let documents: Vec<_> = entities
+            .iter()
+            .filter(|e| e.entity_type == "document")
+            .collect();
+        assert_eq!(documents.len(), 2);
+        assert!(documents.iter().any(|d| d.name == "daily/monday.md"));
+        let links = db.get_relationships_for(&collection.id).await.unwrap();
+        assert_eq!(
+            links
+                .iter()
+                .filter(|r| r.relation_type == "contains_document")
+                .count(),
+            2
+        );
+
+        // max_files caps the run
+        let result = ingest
+            .execute(serde_json::json!({
+                "path": notes.to_str().unwrap(),
+                "max_files": 1
+            }))
+            .await
+            .unwrap();
+        assert!(result.contains("0 file(s) succeeded, 1 failed"));
+        assert!(result.contains("3 more matching file(s) not ingested (max_files = 1)"));
+    }
+
+    #[tokio::test]
+    async fn test_ingest_directory_with_relation_vocabulary() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let graph = Arc::new(
+            KnowledgeGraph::new(temp.path().join("test.db"), temp.path().join("test_index"))
+                .unwrap(),
+        );
+        let db = graph.db();
+        db.set_relation_vocabulary(meepo_knowledge::RelationVocabulary::new(["works_with"]));
+
+        let notes = temp.path().join("notes");
+        tokio::fs::create_dir_all(&notes).await.unwrap();
+        tokio::fs::write(notes.join("rust.md"), "Lifetimes tie borrows to owners.")
+            .await
+            .unwrap();
+
+        let result = IngestDocumentTool::new(graph.clone())
+            .execute(serde_json::json!({ "path": notes.to_str().unwrap() }))
+            .await
+            .unwrap();
+        assert!(result.contains("1 file(s) succeeded, 0 failed"));
+
+        let entities = graph.get_all_entities().await.unwrap();
+        let collection = entities
+            .iter()
+            .find(|e| e.entity_type == "collection")
+            .unwrap();
+        let links = db.get_relationships_for(&collection.id).await.unwrap();
+        assert!(links.iter().any(|r| r.relation_type == "contains_document"));
+    }
+
+    #[tokio::test]
+    async fn test_ingest_markdown_strategy() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let db_path = temp.path().join("test.db");
+        let index_path = temp.path().join("test_index");
+        let graph = Arc::new(KnowledgeGraph::new(&db_path, &index_path).unwrap());
+
+        let test_file = temp.path().join("guide.md");
+        tokio::fs::write(
+            &test_file,
+            "# Guide\n\n## Install\n\n```sh\ncargo install meepo\n```\n",
+        )
+        .await
+        .unwrap();
+
+        let ingest = IngestDocumentTool::new(graph.clone());
+        let result = ingest
+            .execute(serde_json::json!({
+                "path": test_file.to_str().unwrap(),
+                "strategy": "markdown"
+            }))
+            .await
+            .unwrap();
+        assert!(result.contains("2 chunks"));
+
+        let entities = graph.get_all_entities().await.unwrap();
+        let install = entities
+            .iter()
+            .find(|e| e.entity_type == "document_chunk" && e.name.contains("[chunk 2/2]"))
+            .unwrap();
+        let metadata = install.metadata.as_ref().unwrap();
+        assert_eq!(metadata["heading_path"], "Guide > Install");
+        assert!(
+            metadata["full_content"]
+                .as_str()
+                .unwrap()
+                .contains("cargo install meepo\n```")
+        );
+
+        let err = ingest
+            .execute(serde_json::json!({
+                "path": test_file.to_str().unwrap(),
+                "strategy": "paragraph"
+            }))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Unknown chunking strategy"));
+    }
+
+    /// Treats "car" and "automobile" as the same concept
+    struct SynonymEmbedder;
+
+    impl meepo_knowledge::EmbeddingProvider for SynonymEmbedder {
+        fn embed(&self, text: &str) -> Result<Vec<f32>> {
+            let text = text.to_lowercase();
+            let is_car = text.contains("car") || text.contains("automobile");
+            Ok(vec![if is_car { 1.0 } else { 0.0 }, 0.1])
+        }
+
+        fn dimensions(&self) -> usize {
+            2
+        }
+    }
+
+    #[tokio::test]
+    async fn test_smart_recall_hybrid() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let graph = Arc::new(
+            KnowledgeGraph::new(temp.path().join("test.db"), temp.path().join("test_index"))
+                .unwrap()
+                .with_embeddings(Arc::new(SynonymEmbedder))
+                .unwrap(),
+        );
+        let db = graph.db();
+        graph
+            .remember("The automobile is due for an oil change", "fact", None)
+            .await
+            .unwrap();
+
+        let recall = SmartRecallTool::new(graph, db);
+        let result = recall
+            .execute(serde_json::json!({"query": "car"}))
+            .await
+            .unwrap();
+        assert!(result.contains("automobile"), "{}", result);
+
+        // Keyword-only search can't bridge the synonym
+        let result = recall
+            .execute(serde_json::json!({"query": "car", "semantic_weight": 0}))
+            .await
+            .unwrap();
+        assert_eq!(result, "No matching knowledge found.");
+    }
 }
@@ -46,6 +46,10 @@ impl ToolHandler for WebSearchTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let query = input
             .get("query")
@@ -9,10 +9,12 @@ use tokio::process::Command;
 use tracing::{debug, warn};
 
 use super::{ToolHandler, json_schema};
+use crate::text::truncate_chars;
+use meepo_scheduler::url_guard::is_private_ip;
 
 /// Validate file path to prevent path traversal attacks
 /// Returns the validated PathBuf or an error if the path is unsafe
-fn validate_file_path(path: &str, for_write: bool) -> Result<PathBuf> {
+pub(crate) fn validate_file_path(path: &str, for_write: bool) -> Result<PathBuf> {
     // Check for suspicious patterns before canonicalization
     if path.contains("..") {
         return Err(anyhow::anyhow!(
@@ -103,8 +105,143 @@ fn validate_file_path(path: &str, for_write: bool) -> Result<PathBuf> {
     Ok(canonical_path)
 }
 
-/// Run a shell command (with safety checks)
-pub struct RunCommandTool;
+/// Binaries `run_command` may execute when no allowlist is configured
+///
+/// Security notes — intentionally EXCLUDED:
+///   env, printenv  — leak all env vars including API keys/tokens (C-1)
+///   curl, wget     — enable data exfiltration, bypass SSRF protection (C-2)
+///   osascript       — bypasses browser JS blocklist & AppleScript sanitization (H-2)
+///   python*, node, ruby — arbitrary code execution via interpreters (M-5)
+///   defaults        — can modify macOS system preferences
+///   sh, bash, zsh   — would reintroduce shell-string execution
+pub const DEFAULT_ALLOWED_COMMANDS: &[&str] = &[
+    // Read-only / informational
+    "ls",
+    "cat",
+    "head",
+    "tail",
+    "wc",
+    "echo",
+    "date",
+    "whoami",
+    "uname",
+    "pwd",
+    "which",
+    "file",
+    "stat",
+    "du",
+    "df",
+    "uptime",
+    "ps",
+    "hostname",
+    "id",
+    "groups",
+    "grep",
+    "find",
+    "sort",
+    "uniq",
+    "cut",
+    "awk",
+    "sed",
+    "tr",
+    "basename",
+    "dirname",
+    "realpath",
+    "readlink",
+    // File operations (mv removed — can overwrite critical files)
+    "mkdir",
+    "cp",
+    "touch",
+    "ln",
+    "chmod",
+    "tar",
+    "zip",
+    "unzip",
+    "gzip",
+    // Networking (read-only diagnostics only)
+    "ping",
+    "dig",
+    "nslookup",
+    // Development tools (build tools only, no interpreters)
+    "git",
+    "npm",
+    "npx",
+    "cargo",
+    "go",
+    "pip",
+    "pip3",
+    "make",
+    "cmake",
+    "brew",
+    // macOS utilities
+    "open",
+    "pbcopy",
+    "pbpaste",
+    "say",
+];
+
+/// Default and maximum run time for a single `run_command` invocation
+const DEFAULT_COMMAND_TIMEOUT_SECS: u64 = 30;
+
+/// Parent environment variables passed through to child processes.
+/// Everything else (API keys, tokens) is cleared before spawning.
+const INHERITED_ENV_VARS: &[&str] = &["PATH", "HOME", "USER", "LANG", "LC_ALL", "TMPDIR", "TERM"];
+
+/// Per-stream output cap so a chatty command can't flood the context window
+const MAX_STREAM_CHARS: usize = 50_000;
+
+/// Variables the agent may set for a command. Anything else is refused: too many
+/// variables (PATH, LD_PRELOAD, GIT_SSH_COMMAND, NODE_OPTIONS, BASH_ENV, ...) make
+/// an allowlisted program load or run other code.
+const ALLOWED_ENV_VARS: &[&str] = &[
+    "LANG",
+    "LC_ALL",
+    "LC_CTYPE",
+    "LC_MESSAGES",
+    "LC_TIME",
+    "TZ",
+    "TERM",
+    "NO_COLOR",
+    "CLICOLOR",
+    "CLICOLOR_FORCE",
+    "FORCE_COLOR",
+    "COLUMNS",
+    "LINES",
+    "CI",
+    "RUST_BACKTRACE",
+    "RUST_LOG",
+    "GIT_AUTHOR_NAME",
+    "GIT_AUTHOR_EMAIL",
+    "GIT_COMMITTER_NAME",
+    "GIT_COMMITTER_EMAIL",
+];
+
+/// Run an allowlisted binary with explicit arguments (no shell)
+pub struct RunCommandTool {
+    allowed_commands: Vec<String>,
+    max_timeout_secs: u64,
+}
+
+impl RunCommandTool {
+    pub fn new(allowed_commands: Vec<String>, max_timeout_secs: u64) -> Self {
+        Self {
+            allowed_commands,
+            max_timeout_secs: max_timeout_secs.max(1),
+        }
+    }
+}
+
+impl Default for RunCommandTool {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_ALLOWED_COMMANDS
+                .iter()
+                .map(|c| c.to_string())
+                .collect(),
+            DEFAULT_COMMAND_TIMEOUT_SECS,
+        )
+    }
+}
 
 #[async_trait]
 impl ToolHandler for RunCommandTool {
@@ -113,7 +250,9 @@ impl ToolHandler for RunCommandTool {
     }
 
     fn description(&self) -> &str {
-        "Run a shell command safely. Some dangerous commands are blocked for safety."
+        "Run an allowlisted program with an explicit argument list (no shell: pipes, redirects, \
+         and globbing are not interpreted). Returns stdout, stderr, and the exit code. \
+         The environment is cleared except for PATH/HOME/locale plus any variables passed in 'env'."
     }
 
     fn input_schema(&self) -> Value {
@@ -121,11 +260,28 @@ impl ToolHandler for RunCommandTool {
             serde_json::json!({
                 "command": {
                     "type": "string",
-                    "description": "Shell command to execute"
+                    "description": "Program name to run, e.g. 'git' (must be on the allowlist; no paths or arguments)"
+                },
+                "args": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Arguments passed verbatim to the program, e.g. [\"status\", \"--short\"]"
                 },
                 "working_dir": {
                     "type": "string",
-                    "description": "Working directory (default: current directory)"
+                    "description": "Working directory within home, current, or temp directory (default: current directory)"
+                },
+                "env": {
+                    "type": "object",
+                    "additionalProperties": { "type": "string" },
+                    "description": format!(
+                        "Extra environment variables for the command. Only these can be set: {}",
+                        ALLOWED_ENV_VARS.join(", ")
+                    )
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": "Timeout in seconds (default and maximum: the configured limit)"
                 }
             }),
             vec!["command"],
@@ -136,200 +292,136 @@ impl ToolHandler for RunCommandTool {
         let command = input
             .get("command")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing 'command' parameter"))?;
-        let working_dir = input
-            .get("working_dir")
-            .and_then(|v| v.as_str())
-            .unwrap_or(".");
+            .ok_or_else(|| anyhow::anyhow!("Missing 'command' parameter"))?
+            .trim();
+        let args: Vec<String> = match input.get("args") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::Array(items)) => items
+                .iter()
+                .map(|v| {
+                    v.as_str()
+                        .map(String::from)
+                        .ok_or_else(|| anyhow::anyhow!("Every entry in 'args' must be a string"))
+                })
+                .collect::<Result<_>>()?,
+            Some(_) => return Err(anyhow::anyhow!("'args' must be an array of strings")),
+        };
 
         // Maximum command length check
         const MAX_COMMAND_LENGTH: usize = 1000;
-        if command.len() > MAX_COMMAND_LENGTH {
-            warn!(
-                "Blocked command exceeding max length: {} chars",
-                command.len()
-            );
+        let total_len = command.len() + args.iter().map(|a| a.len() + 1).sum::<usize>();
+        if total_len > MAX_COMMAND_LENGTH {
+            warn!("Blocked command exceeding max length: {} chars", total_len);
             return Err(anyhow::anyhow!(
                 "Command exceeds maximum length of {} characters",
                 MAX_COMMAND_LENGTH
             ));
         }
 
-        // Allowlist of safe commands
-        //
-        // Security notes — intentionally EXCLUDED:
-        //   env, printenv  — leak all env vars including API keys/tokens (C-1)
-        //   curl, wget     — enable data exfiltration, bypass SSRF protection (C-2)
-        //   osascript       — bypasses browser JS blocklist & AppleScript sanitization (H-2)
-        //   python*, node, ruby — arbitrary code execution via interpreters (M-5)
-        //   defaults        — can modify macOS system preferences
-        const ALLOWED_COMMANDS: &[&str] = &[
-            // Read-only / informational
-            "ls",
-            "cat",
-            "head",
-            "tail",
-            "wc",
-            "echo",
-            "date",
-            "whoami",
-            "uname",
-            "pwd",
-            "which",
-            "file",
-            "stat",
-            "du",
-            "df",
-            "uptime",
-            "ps",
-            "hostname",
-            "id",
-            "groups",
-            "grep",
-            "find",
-            "sort",
-            "uniq",
-            "cut",
-            "awk",
-            "sed",
-            "tr",
-            "basename",
-            "dirname",
-            "realpath",
-            "readlink",
-            // File operations (mv removed — can overwrite critical files)
-            "mkdir",
-            "cp",
-            "touch",
-            "ln",
-            "chmod",
-            "tar",
-            "zip",
-            "unzip",
-            "gzip",
-            // Networking (read-only diagnostics only)
-            "ping",
-            "dig",
-            "nslookup",
-            // Development tools (build tools only, no interpreters)
-            "git",
-            "npm",
-            "npx",
-            "cargo",
-            "go",
-            "pip",
-            "pip3",
-            "make",
-            "cmake",
-            "brew",
-            // macOS utilities
-            "open",
-            "pbcopy",
-            "pbpaste",
-            "say",
-        ];
-
-        // Shell metacharacters that allow chaining/redirecting commands.
-        // We split on these to extract EVERY command in the pipeline and validate each one.
-        const SHELL_CHAIN_CHARS: &[char] = &['|', ';', '&', '\n'];
-
-        // Also block dangerous shell operators that can't be split simply
-        let dangerous_operators = ["`", "$(", ">>", ">", "<(", ">("];
-        for op in &dangerous_operators {
-            if command.contains(op) {
-                warn!(
-                    "Blocked command containing shell operator '{}': {}",
-                    op, command
-                );
-                return Err(anyhow::anyhow!(
-                    "Command blocked: shell operator '{}' is not allowed for security reasons",
-                    op
-                ));
-            }
+        if command.is_empty() {
+            return Err(anyhow::anyhow!("Empty command"));
         }
 
-        // Split on chain characters and validate EVERY command in the pipeline
-        let segments: Vec<&str> = command
-            .split(SHELL_CHAIN_CHARS)
-            .map(|s| s.trim())
-            .filter(|s| !s.is_empty())
-            .collect();
+        // The program must be a bare name so it resolves through PATH, never a
+        // path to an arbitrary binary that happens to share an allowlisted name
+        if command.contains('/') || command.contains('\\') || command.contains(char::is_whitespace)
+        {
+            return Err(anyhow::anyhow!(
+                "Command must be a bare program name; pass arguments in 'args'"
+            ));
+        }
 
-        if segments.is_empty() {
-            return Err(anyhow::anyhow!("Empty command"));
+        if !self.allowed_commands.iter().any(|c| c == command) {
+            warn!("Blocked command not in allowlist: '{}'", command);
+            return Err(anyhow::anyhow!(
+                "Command '{}' is not in the allowlist of safe commands (allowed: {})",
+                command,
+                self.allowed_commands.join(", ")
+            ));
         }
 
-        for segment in &segments {
-            let first_word = segment.split_whitespace().next().unwrap_or("");
-
-            if !ALLOWED_COMMANDS.contains(&first_word) {
-                warn!(
-                    "Blocked command not in allowlist: '{}' (in segment: '{}')",
-                    first_word, segment
-                );
-                return Err(anyhow::anyhow!(
-                    "Command '{}' is not in the allowlist of safe commands",
-                    first_word
-                ));
+        let working_dir = match input.get("working_dir").and_then(|v| v.as_str()) {
+            Some(dir) => {
+                let validated = validate_file_path(dir, false)?;
+                if !validated.is_dir() {
+                    return Err(anyhow::anyhow!(
+                        "Working directory is not a directory: {}",
+                        validated.display()
+                    ));
+                }
+                validated
+            }
+            None => std::env::current_dir().context("Failed to get current directory")?,
+        };
+
+        let mut extra_env = Vec::new();
+        if let Some(env) = input.get("env") {
+            let map = env
+                .as_object()
+                .ok_or_else(|| anyhow::anyhow!("'env' must be an object of string values"))?;
+            for (key, value) in map {
+                if !ALLOWED_ENV_VARS.contains(&key.as_str()) {
+                    return Err(anyhow::anyhow!(
+                        "Environment variable '{}' cannot be set for security reasons",
+                        key
+                    ));
+                }
+                let value = value.as_str().ok_or_else(|| {
+                    anyhow::anyhow!("Environment variable '{}' must be a string", key)
+                })?;
+                extra_env.push((key.clone(), value.to_string()));
             }
         }
 
-        // Secondary blocklist check for extra safety
-        let dangerous_patterns = [
-            "rm -rf /",
-            "rm -rf /*",
-            "sudo rm",
-            "mkfs",
-            "dd if=",
-            ":(){ :|:& };:",
-        ];
+        let timeout_secs = input
+            .get("timeout_secs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(self.max_timeout_secs)
+            .clamp(1, self.max_timeout_secs);
+
+        debug!(
+            "Running command: {} {:?} (in {})",
+            command,
+            args,
+            working_dir.display()
+        );
 
-        for pattern in &dangerous_patterns {
-            if command.contains(pattern) {
-                warn!("Blocked dangerous command: {}", command);
-                return Err(anyhow::anyhow!(
-                    "Command blocked for safety: contains '{}'",
-                    pattern
-                ));
+        let mut cmd = Command::new(command);
+        cmd.args(&args)
+            .current_dir(&working_dir)
+            .env_clear()
+            .stdin(std::process::Stdio::null())
+            .kill_on_drop(true);
+        for key in INHERITED_ENV_VARS {
+            if let Ok(value) = std::env::var(key) {
+                cmd.env(key, value);
             }
         }
+        cmd.envs(extra_env);
 
-        debug!("Running command: {} (in {})", command, working_dir);
-
-        // Execute with timeout
-        let output = tokio::time::timeout(
-            std::time::Duration::from_secs(30),
-            Command::new("sh")
-                .arg("-c")
-                .arg(command)
-                .current_dir(working_dir)
-                .output(),
-        )
-        .await
-        .map_err(|_| anyhow::anyhow!("Command execution timed out after 30 seconds"))?
-        .context("Failed to execute command")?;
+        // Execute with timeout; kill_on_drop reaps the child if it expires
+        let output =
+            tokio::time::timeout(std::time::Duration::from_secs(timeout_secs), cmd.output())
+                .await
+                .map_err(|_| {
+                    anyhow::anyhow!("Command execution timed out after {} seconds", timeout_secs)
+                })?
+                .with_context(|| format!("Failed to execute command '{}'", command))?;
 
         let stdout = String::from_utf8_lossy(&output.stdout);
         let stderr = String::from_utf8_lossy(&output.stderr);
 
-        let mut result = String::new();
+        let mut result = match output.status.code() {
+            Some(code) => format!("Exit code: {}", code),
+            None => "Exit code: none (terminated by signal)".to_string(),
+        };
         if !stdout.is_empty() {
-            result.push_str("STDOUT:\n");
-            result.push_str(&stdout);
+            result.push_str("\n\nSTDOUT:\n");
+            result.push_str(truncate_chars(&stdout, MAX_STREAM_CHARS));
         }
         if !stderr.is_empty() {
-            if !result.is_empty() {
-                result.push_str("\n\n");
-            }
-            result.push_str("STDERR:\n");
-            result.push_str(&stderr);
-        }
-
-        if !output.status.success() {
-            result.push_str(&format!(
-                "\n\nExit code: {}",
-                output.status.code().unwrap_or(-1)
-            ));
+            result.push_str("\n\nSTDERR:\n");
+            result.push_str(truncate_chars(&stderr, MAX_STREAM_CHARS));
         }
 
         Ok(result)
@@ -361,6 +453,10 @@ impl ToolHandler for ReadFileTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         const MAX_READ_SIZE: u64 = 10 * 1024 * 1024; // 10MB
 
@@ -470,42 +566,6 @@ impl ToolHandler for WriteFileTool {
     }
 }
 
-/// Check if an IP address is private/loopback/link-local (unsafe for SSRF)
-fn is_private_ip(ip: &std::net::IpAddr) -> Option<&'static str> {
-    use std::net::IpAddr;
-    match ip {
-        IpAddr::V4(ipv4) => {
-            let octets = ipv4.octets();
-            if octets[0] == 10 {
-                Some("private IP range (10.x.x.x)")
-            } else if octets[0] == 172 && (16..=31).contains(&octets[1]) {
-                Some("private IP range (172.16-31.x.x)")
-            } else if octets[0] == 192 && octets[1] == 168 {
-                Some("private IP range (192.168.x.x)")
-            } else if octets[0] == 169 && octets[1] == 254 {
-                Some("link-local address (169.254.x.x)")
-            } else if octets[0] == 127 {
-                Some("loopback address")
-            } else if octets[0] == 0 {
-                Some("unspecified address (0.x.x.x)")
-            } else {
-                None
-            }
-        }
-        IpAddr::V6(ipv6) => {
-            if ipv6.is_loopback() {
-                Some("IPv6 loopback")
-            } else if ipv6.segments()[0] & 0xffc0 == 0xfe80 {
-                Some("IPv6 link-local address")
-            } else if ipv6.segments()[0] & 0xfe00 == 0xfc00 {
-                Some("IPv6 unique local address")
-            } else {
-                None
-            }
-        }
-    }
-}
-
 /// Validated URL info returned by `validate_url`.
 /// Contains the resolved IPs so callers can pin them in reqwest,
 /// eliminating the TOCTOU gap between DNS check and HTTP request.
@@ -640,6 +700,10 @@ impl ToolHandler for BrowseUrlTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let url = input
             .get("url")
@@ -657,11 +721,11 @@ impl ToolHandler for BrowseUrlTool {
                 Ok(content) => {
                     debug!("Tavily extract succeeded for {}", url);
                     const MAX_LENGTH: usize = 50000;
-                    if content.len() > MAX_LENGTH {
+                    let truncated = truncate_chars(&content, MAX_LENGTH);
+                    if truncated.len() < content.len() {
                         return Ok(format!(
                             "{}\n\n[Content truncated at {} chars]",
-                            &content[..MAX_LENGTH],
-                            MAX_LENGTH
+                            truncated, MAX_LENGTH
                         ));
                     }
                     return Ok(content);
@@ -784,11 +848,11 @@ impl BrowseUrlTool {
             .context("Failed to read response body")?;
 
         const MAX_LENGTH: usize = 50000;
-        if content.len() > MAX_LENGTH {
+        let truncated = truncate_chars(&content, MAX_LENGTH);
+        if truncated.len() < content.len() {
             Ok(format!(
                 "{}\n\n[Content truncated at {} chars]",
-                &content[..MAX_LENGTH],
-                MAX_LENGTH
+                truncated, MAX_LENGTH
             ))
         } else {
             Ok(content)
@@ -804,11 +868,12 @@ mod tests {
 
     #[test]
     fn test_run_command_schema() {
-        let tool = RunCommandTool;
+        let tool = RunCommandTool::default();
         assert_eq!(tool.name(), "run_command");
         assert!(!tool.description().is_empty());
         let schema = tool.input_schema();
         assert!(schema.get("properties").is_some());
+        assert!(schema["properties"].get("args").is_some());
     }
 
     #[test]
@@ -831,29 +896,32 @@ mod tests {
 
     #[tokio::test]
     async fn test_run_command_echo() {
-        let tool = RunCommandTool;
+        let tool = RunCommandTool::default();
         let result = tool
             .execute(serde_json::json!({
-                "command": "echo hello_meepo_test"
+                "command": "echo",
+                "args": ["hello_meepo_test"]
             }))
             .await
             .unwrap();
         assert!(result.contains("hello_meepo_test"));
+        assert!(result.contains("Exit code: 0"));
     }
 
     #[tokio::test]
     async fn test_run_command_missing_param() {
-        let tool = RunCommandTool;
+        let tool = RunCommandTool::default();
         let result = tool.execute(serde_json::json!({})).await;
         assert!(result.is_err());
     }
 
     #[tokio::test]
     async fn test_run_command_blocks_dangerous() {
-        let tool = RunCommandTool;
+        let tool = RunCommandTool::default();
         let result = tool
             .execute(serde_json::json!({
-                "command": "rm -rf /"
+                "command": "rm",
+                "args": ["-rf", "/"]
             }))
             .await;
         assert!(result.is_err());
@@ -861,11 +929,12 @@ mod tests {
 
     #[tokio::test]
     async fn test_run_command_blocks_not_allowlisted() {
-        let tool = RunCommandTool;
+        let tool = RunCommandTool::default();
         // nc (netcat) is not in the allowlist
         let result = tool
             .execute(serde_json::json!({
-                "command": "nc -l 1234"
+                "command": "nc",
+                "args": ["-l", "1234"]
             }))
             .await;
         assert!(result.is_err());
@@ -877,13 +946,57 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_run_command_respects_configured_allowlist() {
+        let tool = RunCommandTool::new(vec!["echo".to_string()], 5);
+        let result = tool.execute(serde_json::json!({ "command": "ls" })).await;
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("not in the allowlist")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_command_rejects_shell_string() {
+        let tool = RunCommandTool::default();
+        let result = tool
+            .execute(serde_json::json!({ "command": "echo hi | sh" }))
+            .await;
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("bare program name")
+        );
+
+        let result = tool
+            .execute(serde_json::json!({ "command": "/bin/echo" }))
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_command_does_not_interpret_shell_syntax() {
+        let tool = RunCommandTool::default();
+        let result = tool
+            .execute(serde_json::json!({
+                "command": "echo",
+                "args": ["$(whoami)", ";", "ls"]
+            }))
+            .await
+            .unwrap();
+        assert!(result.contains("$(whoami) ; ls"));
+    }
+
     #[tokio::test]
     async fn test_run_command_blocks_too_long() {
-        let tool = RunCommandTool;
-        let long_command = "echo ".to_string() + &"A".repeat(1001);
+        let tool = RunCommandTool::default();
         let result = tool
             .execute(serde_json::json!({
-                "command": long_command
+                "command": "echo",
+                "args": ["A".repeat(1001)]
             }))
             .await;
         assert!(result.is_err());
@@ -896,21 +1009,93 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_run_command_safe_command_works() {
-        let tool = RunCommandTool;
+    async fn test_run_command_rejects_non_string_args() {
+        let tool = RunCommandTool::default();
+        let result = tool
+            .execute(serde_json::json!({ "command": "echo", "args": [1, 2] }))
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_command_env_is_explicit() {
+        let tool = RunCommandTool::new(vec!["printenv".to_string()], 5);
         let result = tool
             .execute(serde_json::json!({
-                "command": "ls -la"
+                "command": "printenv",
+                "env": { "NO_COLOR": "1" }
             }))
-            .await;
-        // ls should be allowed and work
-        assert!(
-            result.is_ok()
-                || result
-                    .unwrap_err()
-                    .to_string()
-                    .contains("Failed to execute")
-        );
+            .await
+            .unwrap();
+        assert!(result.contains("NO_COLOR=1"));
+        let stdout = result.split("STDOUT:\n").nth(1).unwrap_or("");
+        for line in stdout.lines().filter(|l| !l.is_empty()) {
+            let key = line.split('=').next().unwrap();
+            assert!(
+                key == "NO_COLOR" || INHERITED_ENV_VARS.contains(&key),
+                "unexpected inherited variable: {}",
+                key
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_command_only_allows_listed_env_vars() {
+        let tool = RunCommandTool::default();
+        for key in [
+            "PATH",
+            "LD_PRELOAD",
+            "GIT_SSH_COMMAND",
+            "GIT_CONFIG_GLOBAL",
+            "RUSTC_WRAPPER",
+            "NODE_OPTIONS",
+            "PYTHONSTARTUP",
+            "BASH_ENV",
+            "MEEPO_TEST_VAR",
+        ] {
+            let result = tool
+                .execute(serde_json::json!({
+                    "command": "ls",
+                    "env": { key: "/tmp/evil" }
+                }))
+                .await;
+            assert!(
+                result.unwrap_err().to_string().contains("cannot be set"),
+                "{} was allowed",
+                key
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_command_working_dir() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("marker_meepo.txt"), "x").unwrap();
+        let tool = RunCommandTool::default();
+        let result = tool
+            .execute(serde_json::json!({
+                "command": "ls",
+                "working_dir": temp.path().to_str().unwrap()
+            }))
+            .await
+            .unwrap();
+        assert!(result.contains("marker_meepo.txt"));
+    }
+
+    #[tokio::test]
+    async fn test_run_command_reports_nonzero_exit() {
+        let temp = TempDir::new().unwrap();
+        let tool = RunCommandTool::default();
+        let result = tool
+            .execute(serde_json::json!({
+                "command": "ls",
+                "args": ["definitely_missing_meepo_file"],
+                "working_dir": temp.path().to_str().unwrap()
+            }))
+            .await
+            .unwrap();
+        assert!(!result.contains("Exit code: 0"));
+        assert!(result.contains("STDERR:"));
     }
 
     #[tokio::test]
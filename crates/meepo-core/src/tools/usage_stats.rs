@@ -44,6 +44,10 @@ impl ToolHandler for GetUsageStatsTool {
         )
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, input: Value) -> Result<String> {
         let period = input
             .get("period")
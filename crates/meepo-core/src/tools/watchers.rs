@@ -24,6 +24,12 @@ pub enum WatcherCommand {
     Cancel {
         id: String,
     },
+    Pause {
+        id: String,
+    },
+    Resume {
+        id: String,
+    },
 }
 
 /// Create a new watcher
@@ -46,7 +52,7 @@ impl ToolHandler for CreateWatcherTool {
 
     fn description(&self) -> &str {
         "Create a new watcher to monitor for specific events. \
-         Watchers can monitor emails, calendar events, files, GitHub, etc."
+         Watchers can monitor emails, calendar events, files, GitHub, RSS/Atom feeds, etc."
     }
 
     fn input_schema(&self) -> Value {
@@ -54,11 +60,11 @@ impl ToolHandler for CreateWatcherTool {
             serde_json::json!({
                 "kind": {
                     "type": "string",
-                    "description": "Type of watcher: 'email', 'calendar', 'file', 'github', 'time'"
+                    "description": "Type of watcher: 'email', 'calendar', 'file', 'github', 'rss', 'time'"
                 },
                 "config": {
                     "type": "object",
-                    "description": "Configuration specific to the watcher type (e.g., file path, email filters)"
+                    "description": "Configuration specific to the watcher type (e.g., file path, email filters, calendar lead_minutes)"
                 },
                 "action": {
                     "type": "string",
@@ -100,6 +106,29 @@ impl ToolHandler for CreateWatcherTool {
 
         debug!("Creating watcher: {} -> {}", kind, action);
 
+        // Reaching the local network is a decision for whoever edits the
+        // watchers file, not something a fetched page can talk the agent into
+        if config
+            .get("allow_private_network")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+        {
+            return Err(anyhow::anyhow!(
+                "allow_private_network can only be set in the watchers file"
+            ));
+        }
+
+        // Reject watchers the scheduler couldn't run before anything is stored
+        let watcher_kind = meepo_scheduler::watcher::WatcherKind::from_config(kind, config.clone())
+            .context("Invalid watcher config")?;
+        meepo_scheduler::watcher::Watcher::new(
+            watcher_kind,
+            action.to_string(),
+            reply_channel.to_string(),
+        )
+        .validate()
+        .context("Invalid watcher")?;
+
         // Store in database
         let watcher_id = self
             .db
@@ -148,6 +177,10 @@ impl ToolHandler for ListWatchersTool {
         json_schema(serde_json::json!({}), vec![])
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, _input: Value) -> Result<String> {
         debug!("Listing active watchers");
 
@@ -239,10 +272,327 @@ impl ToolHandler for CancelWatcherTool {
     }
 }
 
+/// Pause a watcher without tearing it down, so its dedup state survives
+pub struct PauseWatcherTool {
+    state_db: Arc<std::sync::Mutex<rusqlite::Connection>>,
+    command_tx: mpsc::Sender<WatcherCommand>,
+}
+
+impl PauseWatcherTool {
+    pub fn new(
+        state_db: Arc<std::sync::Mutex<rusqlite::Connection>>,
+        command_tx: mpsc::Sender<WatcherCommand>,
+    ) -> Self {
+        Self {
+            state_db,
+            command_tx,
+        }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for PauseWatcherTool {
+    fn name(&self) -> &str {
+        "pause_watcher"
+    }
+
+    fn description(&self) -> &str {
+        "Temporarily pause a watcher by its ID (e.g. during a vacation). It stops firing \
+         but remembers what it has already seen; use resume_watcher to continue."
+    }
+
+    fn input_schema(&self) -> Value {
+        json_schema(
+            serde_json::json!({
+                "watcher_id": {
+                    "type": "string",
+                    "description": "ID of the watcher to pause"
+                }
+            }),
+            vec!["watcher_id"],
+        )
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        let watcher_id = set_paused(&self.state_db, &self.command_tx, &input, true).await?;
+        Ok(format!("Paused watcher: {}", watcher_id))
+    }
+}
+
+/// Resume a paused watcher
+pub struct ResumeWatcherTool {
+    state_db: Arc<std::sync::Mutex<rusqlite::Connection>>,
+    command_tx: mpsc::Sender<WatcherCommand>,
+}
+
+impl ResumeWatcherTool {
+    pub fn new(
+        state_db: Arc<std::sync::Mutex<rusqlite::Connection>>,
+        command_tx: mpsc::Sender<WatcherCommand>,
+    ) -> Self {
+        Self {
+            state_db,
+            command_tx,
+        }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for ResumeWatcherTool {
+    fn name(&self) -> &str {
+        "resume_watcher"
+    }
+
+    fn description(&self) -> &str {
+        "Resume a watcher paused with pause_watcher."
+    }
+
+    fn input_schema(&self) -> Value {
+        json_schema(
+            serde_json::json!({
+                "watcher_id": {
+                    "type": "string",
+                    "description": "ID of the watcher to resume"
+                }
+            }),
+            vec!["watcher_id"],
+        )
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        let watcher_id = set_paused(&self.state_db, &self.command_tx, &input, false).await?;
+        Ok(format!("Resumed watcher: {}", watcher_id))
+    }
+}
+
+/// Persist a watcher's paused flag, then tell the running scheduler.
+/// Returns the watcher ID.
+async fn set_paused<'a>(
+    state_db: &std::sync::Mutex<rusqlite::Connection>,
+    command_tx: &mpsc::Sender<WatcherCommand>,
+    input: &'a Value,
+    paused: bool,
+) -> Result<&'a str> {
+    let watcher_id = input
+        .get("watcher_id")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow::anyhow!("Missing 'watcher_id' parameter"))?;
+
+    debug!(
+        "{} watcher: {}",
+        if paused { "Pausing" } else { "Resuming" },
+        watcher_id
+    );
+
+    let found = {
+        let conn = state_db
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Watcher state database lock poisoned"))?;
+        if paused {
+            meepo_scheduler::pause_watcher(&conn, watcher_id)?
+        } else {
+            meepo_scheduler::resume_watcher(&conn, watcher_id)?
+        }
+    };
+    if !found {
+        return Err(anyhow::anyhow!("Watcher not found: {}", watcher_id));
+    }
+
+    // The flag is stored, so a scheduler that is down picks it up when it starts
+    let id = watcher_id.to_string();
+    let command = if paused {
+        WatcherCommand::Pause { id }
+    } else {
+        WatcherCommand::Resume { id }
+    };
+    if let Err(e) = command_tx.send(command).await {
+        warn!("Failed to send pause/resume command: {}", e);
+    }
+
+    Ok(watcher_id)
+}
+
+/// Maximum number of fire times a preview may request
+const MAX_PREVIEW_COUNT: usize = 50;
+
+/// Preview when a cron or interval watcher would fire, without creating it
+pub struct PreviewWatcherScheduleTool;
+
+#[async_trait]
+impl ToolHandler for PreviewWatcherScheduleTool {
+    fn name(&self) -> &str {
+        "preview_watcher_schedule"
+    }
+
+    fn description(&self) -> &str {
+        "Show the next times a watcher would fire, without creating or running it. \
+         Use before create_watcher to confirm a cron or polling schedule with the user. \
+         Takes the same 'kind' and 'config' as create_watcher."
+    }
+
+    fn input_schema(&self) -> Value {
+        json_schema(
+            serde_json::json!({
+                "kind": {
+                    "type": "string",
+                    "description": "Type of watcher: 'email', 'calendar', 'github', 'rss', 'time', 'oneshot'"
+                },
+                "config": {
+                    "type": "object",
+                    "description": "Watcher configuration, as for create_watcher (e.g. {\"cron_expr\": \"0 0 9 * * Mon-Fri\", \"task\": \"...\"}; cron expressions include a seconds field)"
+                },
+                "count": {
+                    "type": "integer",
+                    "description": "Number of upcoming fire times to show (default: 5, max: 50)"
+                }
+            }),
+            vec!["kind", "config"],
+        )
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        let kind = input
+            .get("kind")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'kind' parameter"))?;
+        let config = input
+            .get("config")
+            .ok_or_else(|| anyhow::anyhow!("Missing 'config' parameter"))?
+            .clone();
+        let count = input
+            .get("count")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(5)
+            .clamp(1, MAX_PREVIEW_COUNT as u64) as usize;
+
+        let watcher_kind = meepo_scheduler::WatcherKind::from_config(kind, config)?;
+        if watcher_kind.is_event_driven() {
+            return Ok(format!(
+                "A '{}' watcher is event-driven and has no fixed schedule.",
+                kind
+            ));
+        }
+
+        let times = meepo_scheduler::preview_fire_times(&watcher_kind, chrono::Utc::now(), count);
+        if times.is_empty() {
+            return Ok(
+                "This watcher would never fire (the scheduled time has passed).".to_string(),
+            );
+        }
+
+        let mut output = format!("Next {} fire time(s):\n", times.len());
+        for t in times {
+            output.push_str(&format!(
+                "- {} (local: {})\n",
+                t.format("%a %Y-%m-%d %H:%M UTC"),
+                t.with_timezone(&chrono::Local)
+                    .format("%a %Y-%m-%d %H:%M %:z")
+            ));
+        }
+        Ok(output)
+    }
+}
+
+/// Get or set a watcher's persistent state variables
+pub struct WatcherStateTool {
+    state_db: Arc<std::sync::Mutex<rusqlite::Connection>>,
+}
+
+impl WatcherStateTool {
+    pub fn new(state_db: Arc<std::sync::Mutex<rusqlite::Connection>>) -> Self {
+        Self { state_db }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for WatcherStateTool {
+    fn name(&self) -> &str {
+        "watcher_state"
+    }
+
+    fn description(&self) -> &str {
+        "Get or set state variables scoped to a watcher (e.g. a counter or the last seen ID). \
+         Values persist across watcher fires and restarts. Omit 'key' to list all variables."
+    }
+
+    fn input_schema(&self) -> Value {
+        json_schema(
+            serde_json::json!({
+                "watcher_id": {
+                    "type": "string",
+                    "description": "ID of the watcher the state belongs to"
+                },
+                "key": {
+                    "type": "string",
+                    "description": "Name of the state variable (omit to list all)"
+                },
+                "value": {
+                    "description": "JSON value to store under 'key' (omit to read the current value)"
+                }
+            }),
+            vec!["watcher_id"],
+        )
+    }
+
+    /// Writes whenever `value` is given, so safe mode blocks it and it is never cached
+    fn read_only(&self) -> bool {
+        false
+    }
+
+    async fn execute(&self, input: Value) -> Result<String> {
+        let watcher_id = input
+            .get("watcher_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'watcher_id' parameter"))?;
+        let key = input.get("key").and_then(|v| v.as_str());
+
+        let conn = self
+            .state_db
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Watcher state database lock poisoned"))?;
+
+        // State for a mistyped id would be stored and read back without complaint
+        if meepo_scheduler::get_watcher_by_id(&conn, watcher_id)?.is_none() {
+            return Err(anyhow::anyhow!("Watcher not found: {}", watcher_id));
+        }
+
+        let Some(key) = key else {
+            let vars = meepo_scheduler::list_watcher_state(&conn, watcher_id)?;
+            if vars.is_empty() {
+                return Ok(format!("No state set for watcher {}.", watcher_id));
+            }
+            let mut output = format!("State for watcher {}:\n", watcher_id);
+            for (key, value) in vars {
+                output.push_str(&format!("- {} = {}\n", key, value));
+            }
+            return Ok(output);
+        };
+
+        match input.get("value") {
+            Some(value) => {
+                debug!("Setting watcher state {}.{}", watcher_id, key);
+                meepo_scheduler::set_watcher_state(&conn, watcher_id, key, value)?;
+                Ok(format!(
+                    "Set {} = {} for watcher {}",
+                    key, value, watcher_id
+                ))
+            }
+            None => match meepo_scheduler::get_watcher_state(&conn, watcher_id, key)? {
+                Some(value) => Ok(format!("{} = {}", key, value)),
+                None => Ok(format!("{} is not set for watcher {}", key, watcher_id)),
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tools::ToolHandler;
+    use crate::tools::{ToolExecutor, ToolHandler};
     use tempfile::TempDir;
 
     fn setup() -> (
@@ -303,7 +653,7 @@ mod tests {
         let result = create
             .execute(serde_json::json!({
                 "kind": "scheduled",
-                "config": {"cron_expr": "0 * * * *", "task": "test task"},
+                "config": {"cron_expr": "0 0 * * * *", "task": "test task"},
                 "action": "Run a test",
                 "reply_channel": "internal"
             }))
@@ -316,4 +666,184 @@ mod tests {
         let result = list.execute(serde_json::json!({})).await.unwrap();
         assert!(result.contains("test") || result.contains("Run"));
     }
+
+    #[tokio::test]
+    async fn test_create_watcher_rejects_invalid_config() {
+        let (db, tx, mut rx, _temp) = setup();
+        let create = CreateWatcherTool::new(db.clone(), tx);
+
+        let err = create
+            .execute(serde_json::json!({
+                "kind": "rss",
+                "config": {"url": "ftp://example.com/feed", "interval_secs": 600},
+                "action": "Summarize new posts",
+                "reply_channel": "internal"
+            }))
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("`url` must be an http or https URL"));
+
+        let err = create
+            .execute(serde_json::json!({
+                "kind": "rss",
+                "config": {
+                    "url": "http://192.168.1.20/feed.xml",
+                    "interval_secs": 600,
+                    "allow_private_network": true
+                },
+                "action": "Summarize new posts",
+                "reply_channel": "internal"
+            }))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("watchers file"), "{}", err);
+
+        // Nothing was stored or sent to the scheduler
+        assert!(db.get_active_watchers().await.unwrap().is_empty());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_preview_watcher_schedule() {
+        let tool = PreviewWatcherScheduleTool;
+        assert_eq!(tool.name(), "preview_watcher_schedule");
+
+        let result = tool
+            .execute(serde_json::json!({
+                "kind": "time",
+                "config": {"cron_expr": "0 0 9 * * Mon-Fri", "task": "standup"},
+                "count": 3
+            }))
+            .await
+            .unwrap();
+        assert!(result.starts_with("Next 3 fire time(s)"));
+        assert_eq!(result.matches("09:00 UTC").count(), 3);
+
+        let result = tool
+            .execute(serde_json::json!({"kind": "file", "config": {"path": "/tmp"}}))
+            .await
+            .unwrap();
+        assert!(result.contains("event-driven"));
+
+        assert!(
+            tool.execute(serde_json::json!({
+                "kind": "time",
+                "config": {"cron_expr": "whenever", "task": "t"}
+            }))
+            .await
+            .is_err()
+        );
+    }
+
+    /// A state database holding one scheduled watcher with id `w1`
+    fn state_db_with_watcher() -> Arc<std::sync::Mutex<rusqlite::Connection>> {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        meepo_scheduler::init_watcher_tables(&conn).unwrap();
+        let mut watcher = meepo_scheduler::Watcher::new(
+            meepo_scheduler::WatcherKind::Scheduled {
+                cron_expr: "0 0 * * * *".to_string(),
+                task: "t".to_string(),
+            },
+            "Count things".to_string(),
+            "internal".to_string(),
+        );
+        watcher.id = "w1".to_string();
+        meepo_scheduler::save_watcher(&conn, &watcher).unwrap();
+        Arc::new(std::sync::Mutex::new(conn))
+    }
+
+    #[tokio::test]
+    async fn test_watcher_state_set_and_get() {
+        let tool = WatcherStateTool::new(state_db_with_watcher());
+        assert_eq!(tool.name(), "watcher_state");
+
+        let err = tool
+            .execute(serde_json::json!({"watcher_id": "w2", "key": "last_id", "value": 1}))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Watcher not found: w2"));
+
+        let result = tool
+            .execute(serde_json::json!({"watcher_id": "w1", "key": "last_id"}))
+            .await
+            .unwrap();
+        assert!(result.contains("not set"));
+
+        tool.execute(serde_json::json!({"watcher_id": "w1", "key": "last_id", "value": 42}))
+            .await
+            .unwrap();
+        let result = tool
+            .execute(serde_json::json!({"watcher_id": "w1", "key": "last_id"}))
+            .await
+            .unwrap();
+        assert_eq!(result, "last_id = 42");
+
+        let result = tool
+            .execute(serde_json::json!({"watcher_id": "w1"}))
+            .await
+            .unwrap();
+        assert!(result.contains("- last_id = 42"));
+    }
+
+    #[tokio::test]
+    async fn test_watcher_state_writes_blocked_in_safe_mode() {
+        let db = state_db_with_watcher();
+        let mode = crate::safe_mode::SafeMode::new();
+        let mut registry = crate::tools::ToolRegistry::new().with_safe_mode(mode.clone());
+        registry
+            .register(Arc::new(WatcherStateTool::new(db.clone())))
+            .unwrap();
+
+        mode.enable();
+        let result = registry
+            .execute(
+                "watcher_state",
+                serde_json::json!({"watcher_id": "w1", "key": "count", "value": 1}),
+            )
+            .await
+            .unwrap();
+        assert!(result.starts_with("[SAFE MODE] would have"));
+        let conn = db.lock().unwrap();
+        assert!(
+            meepo_scheduler::get_watcher_state(&conn, "w1", "count")
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pause_and_resume_watcher() {
+        let db = state_db_with_watcher();
+        let (tx, mut rx) = mpsc::channel(10);
+        let pause = PauseWatcherTool::new(db.clone(), tx.clone());
+        let resume = ResumeWatcherTool::new(db.clone(), tx);
+
+        let result = pause
+            .execute(serde_json::json!({"watcher_id": "w1"}))
+            .await
+            .unwrap();
+        assert_eq!(result, "Paused watcher: w1");
+        assert!(matches!(rx.try_recv(), Ok(WatcherCommand::Pause { id }) if id == "w1"));
+        let stored = meepo_scheduler::get_watcher_by_id(&db.lock().unwrap(), "w1")
+            .unwrap()
+            .unwrap();
+        assert!(stored.paused);
+
+        resume
+            .execute(serde_json::json!({"watcher_id": "w1"}))
+            .await
+            .unwrap();
+        assert!(matches!(rx.try_recv(), Ok(WatcherCommand::Resume { id }) if id == "w1"));
+        let stored = meepo_scheduler::get_watcher_by_id(&db.lock().unwrap(), "w1")
+            .unwrap()
+            .unwrap();
+        assert!(!stored.paused);
+
+        let err = pause
+            .execute(serde_json::json!({"watcher_id": "w2"}))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Watcher not found: w2");
+        assert!(rx.try_recv().is_err());
+    }
 }
@@ -11,6 +11,36 @@ pub struct IncomingMessage {
     pub content: String,
     pub channel: ChannelType,
     pub timestamp: DateTime<Utc>,
+    /// Higher-priority messages are delivered to the agent first
+    #[serde(default)]
+    pub priority: Priority,
+    /// Set when the message is a slash command invocation rather than free text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<SlashCommand>,
+    /// Thread the message was posted in, in the channel's own terms (see
+    /// [`OutgoingMessage::thread_id`]). Replies go back into the same thread.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<String>,
+}
+
+/// A structured command invocation (e.g. a Discord slash command)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct SlashCommand {
+    pub name: String,
+    /// Option name -> value, with subcommands nested as objects
+    #[serde(default)]
+    pub options: serde_json::Map<String, serde_json::Value>,
+}
+
+/// How urgently an incoming message should reach the agent
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    #[default]
+    Normal,
+    /// Jumps ahead of normal messages: DMs and mentions of meepo, shutdown
+    /// notices, rate-limit warnings
+    High,
 }
 
 /// What kind of outgoing message this is
@@ -31,6 +61,10 @@ pub struct OutgoingMessage {
     pub channel: ChannelType,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reply_to: Option<String>, // original message id
+    /// Thread to post into, in the target channel's own terms (Slack thread ts,
+    /// Discord thread channel id, email subject). `None` keeps the default placement.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<String>,
     #[serde(default)]
     pub kind: MessageKind,
 }
@@ -41,6 +75,7 @@ pub struct OutgoingMessage {
 pub enum ChannelType {
     Discord,
     Slack,
+    Telegram,
     IMessage,
     Email,
     Alexa,
@@ -56,6 +91,7 @@ impl ChannelType {
         match s.to_lowercase().as_str() {
             "discord" => Self::Discord,
             "slack" => Self::Slack,
+            "telegram" => Self::Telegram,
             "imessage" => Self::IMessage,
             "email" => Self::Email,
             "alexa" => Self::Alexa,
@@ -72,6 +108,7 @@ impl std::fmt::Display for ChannelType {
         match self {
             Self::Discord => write!(f, "discord"),
             Self::Slack => write!(f, "slack"),
+            Self::Telegram => write!(f, "telegram"),
             Self::IMessage => write!(f, "imessage"),
             Self::Email => write!(f, "email"),
             Self::Alexa => write!(f, "alexa"),
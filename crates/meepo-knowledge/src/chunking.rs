@@ -2,11 +2,26 @@
 //!
 //! Splits documents into overlapping chunks for indexing in the knowledge
 //! graph. Supports recursive character splitting with configurable chunk
-//! size and overlap.
+//! size and overlap, plus sentence-aware and markdown-aware strategies.
 
 use serde::{Deserialize, Serialize};
 use tracing::debug;
 
+/// How a document is split into chunks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChunkingStrategy {
+    /// Recursive character splitting on `separators`
+    #[default]
+    FixedSize,
+    /// Pack whole sentences into chunks, never splitting mid-sentence
+    /// unless a single sentence exceeds the chunk size
+    Sentence,
+    /// Split on headings, keeping fenced code blocks intact; each chunk
+    /// records the heading path it falls under
+    Markdown,
+}
+
 /// Configuration for document chunking
 #[derive(Debug, Clone)]
 pub struct ChunkingConfig {
@@ -14,8 +29,10 @@ pub struct ChunkingConfig {
     pub chunk_size: usize,
     /// Overlap between consecutive chunks in characters
     pub chunk_overlap: usize,
-    /// Separators to split on, in priority order
+    /// Separators to split on, in priority order (used by `FixedSize`)
     pub separators: Vec<String>,
+    /// Splitting strategy
+    pub strategy: ChunkingStrategy,
 }
 
 impl Default for ChunkingConfig {
@@ -33,6 +50,7 @@ impl Default for ChunkingConfig {
                 ", ".to_string(),
                 " ".to_string(),
             ],
+            strategy: ChunkingStrategy::default(),
         }
     }
 }
@@ -50,6 +68,9 @@ pub struct DocumentChunk {
     pub end_offset: usize,
     /// Total number of chunks in the document
     pub total_chunks: usize,
+    /// Headings enclosing this chunk, outermost first (markdown strategy only)
+    #[serde(default)]
+    pub heading_path: Vec<String>,
 }
 
 /// Metadata about an ingested document
@@ -67,16 +88,31 @@ pub struct DocumentMetadata {
     pub chunk_count: usize,
 }
 
-/// Split text into chunks using recursive character splitting.
+/// Split text into chunks using the configured strategy.
 ///
-/// Tries to split on the highest-priority separator that produces chunks
-/// within the target size. Falls back to lower-priority separators, and
-/// ultimately to character-level splitting.
+/// With `FixedSize`, tries to split on the highest-priority separator that
+/// produces chunks within the target size. Falls back to lower-priority
+/// separators, and ultimately to character-level splitting.
 pub fn chunk_text(text: &str, config: &ChunkingConfig) -> Vec<DocumentChunk> {
     if text.is_empty() {
         return Vec::new();
     }
 
+    match config.strategy {
+        ChunkingStrategy::FixedSize => chunk_fixed_size(text, config),
+        ChunkingStrategy::Sentence => {
+            let units: Vec<_> = sentence_spans(text, 0, text.len())
+                .into_iter()
+                .flat_map(|(start, end)| split_oversized(text, start, end, config.chunk_size))
+                .collect();
+            let spans = pack_units(text, &units, config.chunk_size, config.chunk_overlap);
+            build_chunks(text, spans.into_iter().map(|span| (span, Vec::new())))
+        }
+        ChunkingStrategy::Markdown => chunk_markdown(text, config),
+    }
+}
+
+fn chunk_fixed_size(text: &str, config: &ChunkingConfig) -> Vec<DocumentChunk> {
     // If text fits in one chunk, return it directly
     if text.len() <= config.chunk_size {
         return vec![DocumentChunk {
@@ -85,6 +121,7 @@ pub fn chunk_text(text: &str, config: &ChunkingConfig) -> Vec<DocumentChunk> {
             start_offset: 0,
             end_offset: text.len(),
             total_chunks: 1,
+            heading_path: Vec::new(),
         }];
     }
 
@@ -117,6 +154,7 @@ pub fn chunk_text(text: &str, config: &ChunkingConfig) -> Vec<DocumentChunk> {
             start_offset: start,
             end_offset: end.min(text.len()),
             total_chunks: total,
+            heading_path: Vec::new(),
         });
 
         offset = start + chunk_text.len().saturating_sub(config.chunk_overlap);
@@ -210,10 +248,249 @@ fn merge_with_overlap(chunks: &[String], max_size: usize, overlap: usize) -> Vec
     result
 }
 
+/// Turn `(start, end)` byte spans into chunks, trimming trailing whitespace
+fn build_chunks(
+    text: &str,
+    spans: impl Iterator<Item = ((usize, usize), Vec<String>)>,
+) -> Vec<DocumentChunk> {
+    let mut chunks: Vec<DocumentChunk> = spans
+        .filter_map(|((start, end), heading_path)| {
+            let content = text[start..end].trim_end();
+            (!content.trim().is_empty()).then(|| DocumentChunk {
+                content: content.to_string(),
+                chunk_index: 0,
+                start_offset: start,
+                end_offset: start + content.len(),
+                total_chunks: 0,
+                heading_path,
+            })
+        })
+        .collect();
+
+    let total = chunks.len();
+    for (i, chunk) in chunks.iter_mut().enumerate() {
+        chunk.chunk_index = i;
+        chunk.total_chunks = total;
+    }
+    debug!("Split {} chars into {} chunks", text.len(), total);
+    chunks
+}
+
+/// Split `text[start..end]` into sentences. Each span keeps its trailing
+/// whitespace so the spans cover the range without gaps.
+fn sentence_spans(text: &str, start: usize, end: usize) -> Vec<(usize, usize)> {
+    let bytes = text.as_bytes();
+    let mut spans = Vec::new();
+    let mut sentence_start = start;
+    let mut i = start;
+    while i < end {
+        let at_boundary = match bytes[i] {
+            b'.' | b'!' | b'?' => i + 1 == end || bytes[i + 1].is_ascii_whitespace(),
+            b'\n' => i + 1 < end && bytes[i + 1] == b'\n',
+            _ => false,
+        };
+        i += 1;
+        if at_boundary {
+            while i < end && bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            spans.push((sentence_start, i));
+            sentence_start = i;
+        }
+    }
+    if sentence_start < end {
+        spans.push((sentence_start, end));
+    }
+    spans
+}
+
+/// Break a span longer than `size` into pieces, preferring whitespace breaks
+fn split_oversized(text: &str, start: usize, end: usize, size: usize) -> Vec<(usize, usize)> {
+    let size = size.max(1);
+    let mut pieces = Vec::new();
+    let mut piece_start = start;
+    while end - piece_start > size {
+        let mut cut = piece_start + size;
+        while !text.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        if let Some(ws) = text[piece_start..cut].rfind(|c: char| c.is_ascii_whitespace())
+            && ws > 0
+        {
+            cut = piece_start + ws + 1;
+        }
+        if cut == piece_start {
+            cut += text[piece_start..].chars().next().map_or(1, char::len_utf8);
+        }
+        pieces.push((piece_start, cut));
+        piece_start = cut;
+    }
+    pieces.push((piece_start, end));
+    pieces
+}
+
+/// Greedily pack consecutive units into spans of at most `size` bytes. A
+/// unit that is larger on its own gets a span to itself. Each new span
+/// starts with as many trailing units of the previous one as fit in
+/// `overlap`.
+fn pack_units(
+    text: &str,
+    units: &[(usize, usize)],
+    size: usize,
+    overlap: usize,
+) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < units.len() {
+        // Skip whitespace-only units so chunks don't start with blank lines
+        if text[units[i].0..units[i].1].trim().is_empty() {
+            i += 1;
+            continue;
+        }
+        let start = units[i].0;
+        let mut j = i;
+        while j + 1 < units.len() && units[j + 1].1 - start <= size {
+            j += 1;
+        }
+        let end = units[j].1;
+        spans.push((start, end));
+        if j + 1 >= units.len() {
+            break;
+        }
+
+        let mut next = j + 1;
+        while next - 1 > i && end - units[next - 1].0 <= overlap {
+            next -= 1;
+        }
+        i = next;
+    }
+    spans
+}
+
+/// A run of markdown under one heading
+struct MarkdownSection {
+    heading_path: Vec<String>,
+    /// Block spans: the heading line, paragraphs, and whole fenced code blocks
+    blocks: Vec<(usize, usize, bool)>,
+}
+
+/// Split markdown into sections at headings. Fenced code blocks are single
+/// blocks, and `#` lines inside them are not treated as headings.
+fn markdown_sections(text: &str) -> Vec<MarkdownSection> {
+    let mut sections = vec![MarkdownSection {
+        heading_path: Vec::new(),
+        blocks: Vec::new(),
+    }];
+    let mut headings: Vec<(usize, String)> = Vec::new();
+    // Open block: (start, is_code), plus the fence marker when inside code
+    let mut block: Option<(usize, bool)> = None;
+    let mut fence: Option<&str> = None;
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        let line_start = offset;
+        let line_end = offset + line.len();
+        offset = line_end;
+        let trimmed = line.trim_start();
+
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+                if let Some((start, _)) = block.take() {
+                    sections
+                        .last_mut()
+                        .unwrap()
+                        .blocks
+                        .push((start, line_end, true));
+                }
+            }
+            continue;
+        }
+
+        let marker = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m));
+        let heading_level = trimmed.chars().take_while(|c| *c == '#').count();
+        let after_hashes = &trimmed[heading_level..];
+        let is_heading = (1..=6).contains(&heading_level)
+            && (after_hashes.is_empty() || after_hashes.starts_with(char::is_whitespace));
+
+        if (marker.is_some() || is_heading || line.trim().is_empty())
+            && let Some((start, is_code)) = block.take()
+        {
+            sections
+                .last_mut()
+                .unwrap()
+                .blocks
+                .push((start, line_start, is_code));
+        }
+
+        if let Some(marker) = marker {
+            fence = Some(marker);
+            block = Some((line_start, true));
+        } else if is_heading {
+            let title = after_hashes.trim().trim_end_matches('#').trim();
+            headings.retain(|(level, _)| *level < heading_level);
+            headings.push((heading_level, title.to_string()));
+            sections.push(MarkdownSection {
+                heading_path: headings.iter().map(|(_, t)| t.clone()).collect(),
+                blocks: vec![(line_start, line_end, false)],
+            });
+        } else if !line.trim().is_empty() && block.is_none() {
+            block = Some((line_start, false));
+        }
+    }
+    // An unterminated fence runs to the end of the document
+    if let Some((start, is_code)) = block {
+        sections
+            .last_mut()
+            .unwrap()
+            .blocks
+            .push((start, text.len(), is_code));
+    }
+
+    sections.retain(|s| !s.blocks.is_empty());
+    sections
+}
+
+fn chunk_markdown(text: &str, config: &ChunkingConfig) -> Vec<DocumentChunk> {
+    let mut spans = Vec::new();
+    for section in markdown_sections(text) {
+        // Code blocks stay whole; oversized prose falls back to sentences
+        let units: Vec<_> = section
+            .blocks
+            .iter()
+            .flat_map(|&(start, end, is_code)| {
+                if is_code || end - start <= config.chunk_size {
+                    vec![(start, end)]
+                } else {
+                    sentence_spans(text, start, end)
+                        .into_iter()
+                        .flat_map(|(s, e)| split_oversized(text, s, e, config.chunk_size))
+                        .collect()
+                }
+            })
+            .collect();
+        for span in pack_units(text, &units, config.chunk_size, config.chunk_overlap) {
+            spans.push((span, section.heading_path.clone()));
+        }
+    }
+    build_chunks(text, spans.into_iter())
+}
+
+/// Content type reported for PDF files
+pub const PDF_CONTENT_TYPE: &str = "application/pdf";
+
+/// Content type reported for Word (DOCX) files
+pub const DOCX_CONTENT_TYPE: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document";
+
 /// Detect content type from file extension
 pub fn detect_content_type(path: &str) -> &'static str {
     let lower = path.to_lowercase();
-    if lower.ends_with(".md") || lower.ends_with(".markdown") {
+    if lower.ends_with(".pdf") {
+        PDF_CONTENT_TYPE
+    } else if lower.ends_with(".docx") {
+        DOCX_CONTENT_TYPE
+    } else if lower.ends_with(".md") || lower.ends_with(".markdown") {
         "text/markdown"
     } else if lower.ends_with(".txt") {
         "text/plain"
@@ -312,6 +589,105 @@ mod tests {
         assert!(chunks.iter().any(|c| c.content.contains("Fourth")));
     }
 
+    #[test]
+    fn test_chunk_sentence_strategy() {
+        let config = ChunkingConfig {
+            chunk_size: 60,
+            chunk_overlap: 0,
+            strategy: ChunkingStrategy::Sentence,
+            ..Default::default()
+        };
+
+        let text = "Rust has no garbage collector. Ownership frees memory. \
+                    Borrows are checked at compile time! Is that strict? Yes.";
+        let chunks = chunk_text(text, &config);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.content.len() <= config.chunk_size);
+            assert!(
+                chunk.content.ends_with(['.', '!', '?']),
+                "Chunk split mid-sentence: {:?}",
+                chunk.content
+            );
+            assert_eq!(&text[chunk.start_offset..chunk.end_offset], chunk.content);
+            assert!(chunk.heading_path.is_empty());
+        }
+
+        // Overlap repeats the trailing sentence of the previous chunk
+        let overlapping = chunk_text(
+            text,
+            &ChunkingConfig {
+                chunk_overlap: 30,
+                ..config
+            },
+        );
+        assert!(
+            overlapping[1]
+                .content
+                .starts_with("Ownership frees memory.")
+        );
+    }
+
+    #[test]
+    fn test_chunk_markdown_strategy() {
+        let config = ChunkingConfig {
+            chunk_size: 200,
+            chunk_overlap: 0,
+            strategy: ChunkingStrategy::Markdown,
+            ..Default::default()
+        };
+
+        let text = "Intro line.\n\
+                    \n\
+                    # Guide\n\
+                    Overview.\n\
+                    \n\
+                    ## Install\n\
+                    Run this:\n\
+                    \n\
+                    ```sh\n\
+                    # not a heading\n\
+                    \n\
+                    cargo install meepo\n\
+                    ```\n\
+                    \n\
+                    # Usage\n\
+                    Start it.\n";
+        let chunks = chunk_text(text, &config);
+        let paths: Vec<_> = chunks.iter().map(|c| c.heading_path.join(" > ")).collect();
+        assert_eq!(paths, vec!["", "Guide", "Guide > Install", "Usage"]);
+
+        // The fenced block (with its blank line and '#') stays in one chunk
+        assert!(chunks[2].content.starts_with("## Install"));
+        assert!(
+            chunks[2]
+                .content
+                .contains("# not a heading\n\ncargo install meepo\n```")
+        );
+        for chunk in &chunks {
+            assert_eq!(&text[chunk.start_offset..chunk.end_offset], chunk.content);
+            assert_eq!(chunk.total_chunks, 4);
+        }
+
+        // A code block is never split, even when it exceeds the chunk size
+        let small = ChunkingConfig {
+            chunk_size: 20,
+            ..config
+        };
+        let chunks = chunk_text(text, &small);
+        assert!(chunks.iter().any(|c| c.content.starts_with("```sh")
+            && c.content.ends_with("```")
+            && c.content.len() > 20));
+    }
+
+    #[test]
+    fn test_default_strategy_is_fixed_size() {
+        assert_eq!(
+            ChunkingConfig::default().strategy,
+            ChunkingStrategy::FixedSize
+        );
+    }
+
     #[test]
     fn test_detect_content_type() {
         assert_eq!(detect_content_type("readme.md"), "text/markdown");
@@ -0,0 +1,219 @@
+//! Near-duplicate collapsing for search results
+//!
+//! Overlapping ingests leave many chunks with almost the same text. These
+//! helpers collapse such results after retrieval, keeping the best-ranked
+//! representative of each group, without touching the stored data. The same
+//! similarity measure also finds duplicate entity names for merging.
+
+use std::collections::HashSet;
+
+/// Default trigram Jaccard similarity above which two results are collapsed
+pub const DEFAULT_SIMILARITY_THRESHOLD: f32 = 0.8;
+
+/// Default edit similarity at or above which a fuzzy search counts a name as a match
+pub const DEFAULT_FUZZY_THRESHOLD: f32 = 0.75;
+
+/// A result kept after collapsing, with the number of near-duplicates it absorbed
+#[derive(Debug, Clone)]
+pub struct Collapsed<T> {
+    pub item: T,
+    pub collapsed: usize,
+}
+
+/// Character trigrams of `text`, lowercased with whitespace runs collapsed
+fn trigrams(text: &str) -> HashSet<[char; 3]> {
+    let normalized: Vec<char> = text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+        .chars()
+        .collect();
+
+    normalized.windows(3).map(|w| [w[0], w[1], w[2]]).collect()
+}
+
+/// Jaccard similarity (0.0–1.0) of the character trigram sets of two texts
+pub fn trigram_similarity(a: &str, b: &str) -> f32 {
+    jaccard(&trigrams(a), &trigrams(b))
+}
+
+fn jaccard(a: &HashSet<[char; 3]>, b: &HashSet<[char; 3]>) -> f32 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    let intersection = a.intersection(b).count();
+    let union = a.len() + b.len() - intersection;
+    intersection as f32 / union as f32
+}
+
+/// Collapse items whose text similarity exceeds `threshold`.
+///
+/// `items` must be ordered best first (e.g. by descending score): each item
+/// is either kept or folded into the first earlier item it resembles, so the
+/// highest-ranked member of every group is the one that survives.
+pub fn collapse_similar<T>(
+    items: Vec<T>,
+    threshold: f32,
+    text: impl Fn(&T) -> String,
+) -> Vec<Collapsed<T>> {
+    let mut kept: Vec<(Collapsed<T>, HashSet<[char; 3]>)> = Vec::new();
+
+    for item in items {
+        let grams = trigrams(&text(&item));
+        match kept
+            .iter_mut()
+            .find(|(_, kept_grams)| jaccard(&grams, kept_grams) > threshold)
+        {
+            Some((representative, _)) => representative.collapsed += 1,
+            None => kept.push((Collapsed { item, collapsed: 0 }, grams)),
+        }
+    }
+
+    kept.into_iter().map(|(c, _)| c).collect()
+}
+
+/// Normalize a name for duplicate detection: lowercase, punctuation dropped,
+/// whitespace collapsed ("John  Smith." and "john smith" normalize alike)
+pub fn normalize_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Levenshtein distance between two strings, in chars
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != *cb);
+            curr[j + 1] = substitution.min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// Typo tolerance (0.0–1.0) of two names: one minus their Levenshtein
+/// distance over the longer length, after `normalize_name`. Unlike trigram
+/// similarity, a single typo in a short name still scores high
+/// ("Mundhara" vs "Mundara" is 0.875).
+pub fn edit_similarity(a: &str, b: &str) -> f32 {
+    let a = normalize_name(a);
+    let b = normalize_name(b);
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(&a, &b) as f32 / longest as f32
+}
+
+/// How closely `name` matches `query`, allowing typos: the best
+/// `edit_similarity` between the query and the full name or any run of the
+/// name's words as long as the query ("Mundara" matches "Abhay Mundhara")
+pub fn fuzzy_name_similarity(name: &str, query: &str) -> f32 {
+    let name = normalize_name(name);
+    let query = normalize_name(query);
+    let query_words = query.split(' ').count();
+    let words: Vec<&str> = name.split(' ').collect();
+
+    words
+        .windows(query_words.min(words.len()))
+        .map(|window| edit_similarity(&window.join(" "), &query))
+        .fold(edit_similarity(&name, &query), f32::max)
+}
+
+/// All pairs `(i, j)` with `i < j` whose normalized texts have a trigram
+/// similarity of at least `threshold`, most similar first
+pub fn similar_pairs(texts: &[&str], threshold: f32) -> Vec<(usize, usize, f32)> {
+    let normalized: Vec<String> = texts.iter().map(|t| normalize_name(t)).collect();
+    let grams: Vec<HashSet<[char; 3]>> = normalized.iter().map(|t| trigrams(t)).collect();
+
+    let mut pairs = Vec::new();
+    for i in 0..texts.len() {
+        for j in (i + 1)..texts.len() {
+            // Short names have no trigrams; only exact matches count for them
+            let similarity = if grams[i].is_empty() || grams[j].is_empty() {
+                if normalized[i] == normalized[j] {
+                    1.0
+                } else {
+                    0.0
+                }
+            } else {
+                jaccard(&grams[i], &grams[j])
+            };
+            if similarity >= threshold {
+                pairs.push((i, j, similarity));
+            }
+        }
+    }
+    pairs.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trigram_similarity() {
+        assert!((trigram_similarity("hello world", "Hello   World") - 1.0).abs() < 1e-6);
+        assert!(trigram_similarity("the quick brown fox", "the quick brown fox jumps") > 0.7);
+        assert!(trigram_similarity("rust ownership", "calendar invite") < 0.1);
+    }
+
+    #[test]
+    fn test_similar_pairs() {
+        let names = ["John Smith", "Acme Corp", "john  smith.", "Jon Smith", "AI"];
+        let pairs = similar_pairs(&names, 0.5);
+        assert_eq!(pairs[0], (0, 2, 1.0));
+        assert!(pairs.iter().any(|&(i, j, _)| (i, j) == (0, 3)));
+        assert!(pairs.iter().all(|&(i, j, _)| i != 1 && j != 1 && j != 4));
+        assert_eq!(normalize_name("  Acme, Corp. "), "acme corp");
+    }
+
+    #[test]
+    fn test_edit_similarity() {
+        assert!((edit_similarity("Mundhara", "Mundara") - 0.875).abs() < 1e-6);
+        assert!((edit_similarity("John Smith", "john smith.") - 1.0).abs() < 1e-6);
+        assert!(edit_similarity("Alice", "Bob") < 0.3);
+        assert!((fuzzy_name_similarity("Abhay Mundhara", "mundara") - 0.875).abs() < 1e-6);
+        assert!(fuzzy_name_similarity("Abhay Mundhara", "abhay mundara") > 0.9);
+        assert!(fuzzy_name_similarity("Acme Corp", "Mundara") < 0.5);
+    }
+
+    #[test]
+    fn test_collapse_keeps_first_representative() {
+        let items = vec![
+            (
+                "a",
+                "Rust is a systems programming language focused on safety.",
+            ),
+            ("b", "Meeting notes from Tuesday's planning session."),
+            (
+                "c",
+                "Rust is a systems programming language focused on safety!",
+            ),
+            (
+                "d",
+                "rust is a systems programming language, focused on safety.",
+            ),
+        ];
+
+        let collapsed =
+            collapse_similar(items, DEFAULT_SIMILARITY_THRESHOLD, |(_, t)| t.to_string());
+        assert_eq!(collapsed.len(), 2);
+        assert_eq!(collapsed[0].item.0, "a");
+        assert_eq!(collapsed[0].collapsed, 2);
+        assert_eq!(collapsed[1].item.0, "b");
+        assert_eq!(collapsed[1].collapsed, 0);
+    }
+}
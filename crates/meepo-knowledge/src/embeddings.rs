@@ -234,7 +234,7 @@ fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
 }
 
 /// Convert f32 vector to bytes for SQLite blob storage
-fn f32_vec_to_bytes(vec: &[f32]) -> Vec<u8> {
+pub(crate) fn f32_vec_to_bytes(vec: &[f32]) -> Vec<u8> {
     vec.iter().flat_map(|f| f.to_le_bytes()).collect()
 }
 
@@ -261,13 +261,27 @@ pub fn hybrid_search_rrf(
     vector_results: &[VectorSearchResult],
     k: f32, // RRF constant (typically 60.0)
     limit: usize,
+) -> Vec<HybridSearchResult> {
+    weighted_hybrid_search_rrf(keyword_results, vector_results, 1.0, 1.0, k, limit)
+}
+
+/// Reciprocal Rank Fusion with a weight per result list, so one list can
+/// count for more than the other (e.g. `keyword_weight` and `vector_weight`
+/// from `EmbeddingConfig`). A weight of 0.0 ignores that list's ranking.
+pub fn weighted_hybrid_search_rrf(
+    keyword_results: &[String],
+    vector_results: &[VectorSearchResult],
+    keyword_weight: f32,
+    vector_weight: f32,
+    k: f32,
+    limit: usize,
 ) -> Vec<HybridSearchResult> {
     let mut scores: HashMap<String, (f32, Option<usize>, Option<usize>)> = HashMap::new();
 
     // Add keyword scores
     for (rank, entity_id) in keyword_results.iter().enumerate() {
         let entry = scores.entry(entity_id.clone()).or_insert((0.0, None, None));
-        entry.0 += 1.0 / (k + rank as f32 + 1.0);
+        entry.0 += keyword_weight / (k + rank as f32 + 1.0);
         entry.1 = Some(rank + 1);
     }
 
@@ -276,7 +290,7 @@ pub fn hybrid_search_rrf(
         let entry = scores
             .entry(result.entity_id.clone())
             .or_insert((0.0, None, None));
-        entry.0 += 1.0 / (k + rank as f32 + 1.0);
+        entry.0 += vector_weight / (k + rank as f32 + 1.0);
         entry.2 = Some(rank + 1);
     }
 
@@ -430,6 +444,20 @@ mod tests {
         assert!(a_score > c_score);
     }
 
+    #[test]
+    fn test_weighted_hybrid_search_rrf() {
+        let keyword = vec!["a".to_string()];
+        let vector = vec![VectorSearchResult {
+            entity_id: "b".to_string(),
+            similarity: 0.9,
+        }];
+
+        let semantic_heavy = weighted_hybrid_search_rrf(&keyword, &vector, 0.2, 0.8, 60.0, 10);
+        assert_eq!(semantic_heavy[0].entity_id, "b");
+        let keyword_heavy = weighted_hybrid_search_rrf(&keyword, &vector, 0.8, 0.2, 60.0, 10);
+        assert_eq!(keyword_heavy[0].entity_id, "a");
+    }
+
     #[test]
     fn test_noop_provider() {
         let provider = NoOpEmbeddingProvider::new(384);
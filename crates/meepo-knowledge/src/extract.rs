@@ -0,0 +1,163 @@
+//! Text extraction for document ingestion
+//!
+//! Plain-text formats are decoded as UTF-8. PDFs go through `pdf-extract`
+//! and DOCX files are unzipped and their `word/document.xml` walked for text
+//! runs. Anything else that isn't UTF-8 text is rejected rather than chunked
+//! as garbage.
+
+use anyhow::{Context, Result, anyhow, bail};
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use std::io::{Cursor, Read};
+
+use crate::chunking::{DOCX_CONTENT_TYPE, PDF_CONTENT_TYPE};
+
+/// Text pulled out of a document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractedText {
+    pub text: String,
+    /// Number of pages, paragraphs, or lines the text came from
+    pub sections: usize,
+    /// What `sections` counts: "page", "paragraph", or "line"
+    pub section_kind: &'static str,
+}
+
+/// Extract the text of a document given its raw bytes and detected content type
+pub fn extract_text(bytes: &[u8], content_type: &str) -> Result<ExtractedText> {
+    match content_type {
+        PDF_CONTENT_TYPE => extract_pdf(bytes),
+        DOCX_CONTENT_TYPE => extract_docx(bytes),
+        _ => {
+            let text = std::str::from_utf8(bytes)
+                .ok()
+                .filter(|text| !text.contains('\0'))
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Unsupported format: file is not text and there is no extractor for {}",
+                        content_type
+                    )
+                })?;
+            Ok(ExtractedText {
+                text: text.to_string(),
+                sections: text.lines().count(),
+                section_kind: "line",
+            })
+        }
+    }
+}
+
+fn extract_pdf(bytes: &[u8]) -> Result<ExtractedText> {
+    let pages = pdf_extract::extract_text_from_mem_by_pages(bytes)
+        .map_err(|e| anyhow!("Failed to extract text from PDF: {}", e))?;
+    let sections = pages.len();
+    let text = pages
+        .iter()
+        .map(|page| page.trim())
+        .filter(|page| !page.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    Ok(ExtractedText {
+        text,
+        sections,
+        section_kind: "page",
+    })
+}
+
+fn extract_docx(bytes: &[u8]) -> Result<ExtractedText> {
+    let mut archive =
+        zip::ZipArchive::new(Cursor::new(bytes)).context("DOCX file is not a valid zip archive")?;
+    let mut xml = String::new();
+    archive
+        .by_name("word/document.xml")
+        .context("DOCX file has no word/document.xml")?
+        .read_to_string(&mut xml)
+        .context("Failed to read word/document.xml")?;
+
+    let mut reader = Reader::from_str(&xml);
+    let mut paragraphs = Vec::new();
+    let mut paragraph = String::new();
+    let mut in_text_run = false;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) if e.name().as_ref() == b"w:t" => in_text_run = true,
+            Ok(Event::End(e)) if e.name().as_ref() == b"w:t" => in_text_run = false,
+            Ok(Event::Text(t)) if in_text_run => {
+                paragraph.push_str(&t.unescape().context("Invalid text in DOCX")?);
+            }
+            Ok(Event::Empty(e)) => match e.name().as_ref() {
+                b"w:tab" => paragraph.push('\t'),
+                b"w:br" | b"w:cr" => paragraph.push('\n'),
+                _ => {}
+            },
+            Ok(Event::End(e)) if e.name().as_ref() == b"w:p" => {
+                let text = std::mem::take(&mut paragraph);
+                if !text.trim().is_empty() {
+                    paragraphs.push(text);
+                }
+            }
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(e) => bail!("Failed to parse DOCX document XML: {}", e),
+        }
+    }
+
+    Ok(ExtractedText {
+        sections: paragraphs.len(),
+        text: paragraphs.join("\n\n"),
+        section_kind: "paragraph",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn docx(document_xml: &str) -> Vec<u8> {
+        let mut buffer = Cursor::new(Vec::new());
+        let mut writer = zip::ZipWriter::new(&mut buffer);
+        writer
+            .start_file(
+                "word/document.xml",
+                zip::write::SimpleFileOptions::default(),
+            )
+            .unwrap();
+        writer.write_all(document_xml.as_bytes()).unwrap();
+        writer.finish().unwrap();
+        buffer.into_inner()
+    }
+
+    #[test]
+    fn test_extract_docx() {
+        let bytes = docx(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+  <w:body>
+    <w:p><w:r><w:t>Quarterly</w:t></w:r><w:r><w:t xml:space="preserve"> plan &amp; goals</w:t></w:r></w:p>
+    <w:p></w:p>
+    <w:p><w:r><w:t>Ship</w:t><w:tab/><w:t>v2</w:t></w:r></w:p>
+  </w:body>
+</w:document>"#,
+        );
+        let extracted = extract_text(&bytes, DOCX_CONTENT_TYPE).unwrap();
+        assert_eq!(extracted.text, "Quarterly plan & goals\n\nShip\tv2");
+        assert_eq!(extracted.sections, 2);
+        assert_eq!(extracted.section_kind, "paragraph");
+    }
+
+    #[test]
+    fn test_extract_plain_text() {
+        let extracted = extract_text(b"one\ntwo\n", "text/plain").unwrap();
+        assert_eq!(extracted.text, "one\ntwo\n");
+        assert_eq!(extracted.sections, 2);
+        assert_eq!(extracted.section_kind, "line");
+    }
+
+    #[test]
+    fn test_extract_rejects_binary() {
+        let err = extract_text(&[0x89, b'P', b'N', b'G', 0, 0xff], "text/plain").unwrap_err();
+        assert!(err.to_string().starts_with("Unsupported format"));
+        assert!(extract_text(b"not a zip", DOCX_CONTENT_TYPE).is_err());
+        assert!(extract_text(b"not a pdf", PDF_CONTENT_TYPE).is_err());
+    }
+}
@@ -3,12 +3,17 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tracing::{debug, info};
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
 
-use crate::sqlite::{Entity, KnowledgeDb, Relationship};
-use crate::tantivy::{SearchResult, TantivyIndex};
+use crate::embeddings::{EmbeddingProvider, VectorIndex, VectorSearchResult};
+use crate::sqlite::{
+    DuplicateCandidate, Entity, ImportMode, ImportSummary, KnowledgeDb, MergeSummary, RankedEntity,
+    RecencyBoost, Relationship,
+};
+use crate::tantivy::{SearchResult, TantivyIndex, index_content};
 
 /// Context for an entity including relationships and conversations
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,10 +24,57 @@ pub struct EntityContext {
     pub recent_conversations: Vec<crate::sqlite::Conversation>,
 }
 
+/// Capacity of the change broadcast channel; slow subscribers see `Lagged` past this
+const CHANGE_CHANNEL_CAPACITY: usize = 256;
+
+/// A mutation of the knowledge graph, emitted to change subscribers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GraphChange {
+    EntityAdded {
+        id: String,
+    },
+    EntityUpdated {
+        id: String,
+    },
+    EntityDeleted {
+        id: String,
+    },
+    RelationshipAdded {
+        id: String,
+    },
+    RelationshipDeleted {
+        id: String,
+    },
+    /// The full-text index was rebuilt from the database
+    Reindexed,
+}
+
+/// Text embedded for semantic search: the full content when the entity has
+/// one (memories, document chunks), otherwise the same text Tantivy indexes
+fn embedding_content(name: &str, entity_type: &str, metadata: Option<&JsonValue>) -> String {
+    match metadata
+        .and_then(|m| m.get("full_content"))
+        .and_then(|c| c.as_str())
+    {
+        Some(content) => format!("{}\n{}", name, content),
+        None => index_content(name, entity_type, metadata),
+    }
+}
+
+/// Optional embedding backend and the vectors it has produced
+struct SemanticIndex {
+    provider: Arc<dyn EmbeddingProvider>,
+    vectors: VectorIndex,
+}
+
 /// Knowledge graph combining SQLite and Tantivy
 pub struct KnowledgeGraph {
     db: Arc<KnowledgeDb>,
+    db_path: PathBuf,
     index: TantivyIndex,
+    changes: broadcast::Sender<GraphChange>,
+    semantic: Option<SemanticIndex>,
 }
 
 impl KnowledgeGraph {
@@ -34,10 +86,75 @@ impl KnowledgeGraph {
             index_path.as_ref()
         );
 
-        let db = Arc::new(KnowledgeDb::new(db_path)?);
+        let db_path = db_path.as_ref().to_path_buf();
+        let db = Arc::new(KnowledgeDb::new(&db_path)?);
         let index = TantivyIndex::new(index_path)?;
+        let (changes, _) = broadcast::channel(CHANGE_CHANNEL_CAPACITY);
+
+        Ok(Self {
+            db,
+            db_path,
+            index,
+            changes,
+            semantic: None,
+        })
+    }
+
+    /// Enable semantic search: entities are embedded with `provider` as they
+    /// are added or updated, and previously stored vectors are loaded.
+    pub fn with_embeddings(mut self, provider: Arc<dyn EmbeddingProvider>) -> Result<Self> {
+        let vectors = VectorIndex::load_from_db(&self.db_path, provider.dimensions())?;
+        self.semantic = Some(SemanticIndex { provider, vectors });
+        Ok(self)
+    }
+
+    /// Whether an embedding provider is configured
+    pub fn has_embeddings(&self) -> bool {
+        self.semantic.is_some()
+    }
+
+    /// Embed `text` off the async runtime (local models can be slow)
+    async fn embed(provider: &Arc<dyn EmbeddingProvider>, text: String) -> Result<Vec<f32>> {
+        let provider = Arc::clone(provider);
+        tokio::task::spawn_blocking(move || provider.embed(&text))
+            .await
+            .context("Embedding task panicked")?
+    }
+
+    /// Compute and store an entity's vector. Failures are logged, not returned,
+    /// so a broken embedding backend never blocks writes to the graph.
+    async fn embed_entity(
+        &self,
+        id: &str,
+        name: &str,
+        entity_type: &str,
+        metadata: Option<&JsonValue>,
+    ) {
+        let Some(semantic) = &self.semantic else {
+            return;
+        };
+        let text = embedding_content(name, entity_type, metadata);
+        let result = async {
+            let vector = Self::embed(&semantic.provider, text).await?;
+            semantic.vectors.insert(id, vector.clone())?;
+            self.db.upsert_embedding(id, &vector).await
+        }
+        .await;
+        if let Err(e) = result {
+            warn!("Failed to embed entity {}: {:#}", id, e);
+        }
+    }
 
-        Ok(Self { db, index })
+    /// Subscribe to graph mutations (for cache invalidation, live UIs, etc.)
+    pub fn subscribe_changes(&self) -> broadcast::Receiver<GraphChange> {
+        self.changes.subscribe()
+    }
+
+    /// Emit a change event; a no-op when nobody is subscribed
+    fn emit(&self, change: GraphChange) {
+        if self.changes.receiver_count() > 0 {
+            let _ = self.changes.send(change);
+        }
     }
 
     /// Add an entity to the knowledge graph
@@ -56,17 +173,14 @@ impl KnowledgeGraph {
             .await?;
 
         // Index in Tantivy
-        let content = format!(
-            "{} {} {}",
-            name,
-            entity_type,
-            metadata.as_ref().map(|m| m.to_string()).unwrap_or_default()
-        );
-
+        let content = index_content(name, entity_type, metadata.as_ref());
         self.index
             .index_document(&id, &content, entity_type, &chrono::Utc::now().to_rfc3339())?;
+        self.embed_entity(&id, name, entity_type, metadata.as_ref())
+            .await;
 
         info!("Added entity: {} with ID {}", name, id);
+        self.emit(GraphChange::EntityAdded { id: id.clone() });
         Ok(id)
     }
 
@@ -99,15 +213,145 @@ impl KnowledgeGraph {
             .await?;
 
         info!("Linked entities with relationship ID {}", id);
+        self.emit(GraphChange::RelationshipAdded { id: id.clone() });
         Ok(id)
     }
 
+    /// Patch an entity's fields (`None` leaves a field unchanged) and re-index it.
+    /// Returns the updated entity, or `None` if no entity has this ID.
+    pub async fn update_entity(
+        &self,
+        id: &str,
+        name: Option<&str>,
+        entity_type: Option<&str>,
+        metadata: Option<JsonValue>,
+    ) -> Result<Option<Entity>> {
+        debug!("Updating entity: {}", id);
+
+        let Some(entity) = self
+            .db
+            .update_entity(id, name, entity_type, metadata)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        self.index.index_document(
+            &entity.id,
+            &index_content(&entity.name, &entity.entity_type, entity.metadata.as_ref()),
+            &entity.entity_type,
+            &entity.created_at.to_rfc3339(),
+        )?;
+        self.embed_entity(
+            &entity.id,
+            &entity.name,
+            &entity.entity_type,
+            entity.metadata.as_ref(),
+        )
+        .await;
+
+        info!("Updated entity {}", id);
+        self.emit(GraphChange::EntityUpdated { id: id.to_string() });
+        Ok(Some(entity))
+    }
+
+    /// Delete an entity, its relationships, and its search index entry. Returns the
+    /// number of database rows removed (0 if the entity doesn't exist).
+    pub async fn delete_entity(&self, id: &str) -> Result<usize> {
+        debug!("Deleting entity: {}", id);
+
+        let relationships = self.db.get_relationships_for(id).await?;
+        let deleted = self.db.delete_entity(id).await?;
+        if deleted == 0 {
+            return Ok(0);
+        }
+        self.index.delete_document(id)?;
+        if let Some(semantic) = &self.semantic {
+            semantic.vectors.remove(id);
+        }
+
+        info!(
+            "Deleted entity {} and {} relationships",
+            id,
+            relationships.len()
+        );
+        for relationship in relationships {
+            self.emit(GraphChange::RelationshipDeleted {
+                id: relationship.id,
+            });
+        }
+        self.emit(GraphChange::EntityDeleted { id: id.to_string() });
+        Ok(deleted)
+    }
+
+    /// Merge `merge_id` into `keep_id` (see `KnowledgeDb::merge_entities`),
+    /// dropping the merged entity from the search index and re-indexing the
+    /// kept one with its unioned metadata
+    pub async fn merge_entities(&self, keep_id: &str, merge_id: &str) -> Result<MergeSummary> {
+        debug!("Merging entity {} into {}", merge_id, keep_id);
+
+        let summary = self.db.merge_entities(keep_id, merge_id).await?;
+        let entity = &summary.entity;
+        self.index.delete_document(merge_id)?;
+        self.index.index_document(
+            &entity.id,
+            &index_content(&entity.name, &entity.entity_type, entity.metadata.as_ref()),
+            &entity.entity_type,
+            &entity.created_at.to_rfc3339(),
+        )?;
+        if let Some(semantic) = &self.semantic {
+            semantic.vectors.remove(merge_id);
+        }
+        self.embed_entity(
+            &entity.id,
+            &entity.name,
+            &entity.entity_type,
+            entity.metadata.as_ref(),
+        )
+        .await;
+
+        info!("Merged entity {} into {}", merge_id, keep_id);
+        self.emit(GraphChange::EntityDeleted {
+            id: merge_id.to_string(),
+        });
+        self.emit(GraphChange::EntityUpdated {
+            id: keep_id.to_string(),
+        });
+        Ok(summary)
+    }
+
+    /// Candidate duplicate entities by name similarity (see `KnowledgeDb::find_duplicates`)
+    pub async fn find_duplicates(&self, threshold: f32) -> Result<Vec<DuplicateCandidate>> {
+        self.db.find_duplicates(threshold).await
+    }
+
     /// Search the knowledge graph
     pub fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
         debug!("Searching knowledge graph for: {}", query);
         self.index.search(query, limit)
     }
 
+    /// Search by meaning rather than keywords: embeds `query` and returns the
+    /// entities with the most similar vectors. Errors if no embedding provider
+    /// is configured (see `with_embeddings`).
+    pub async fn search_semantic(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<VectorSearchResult>> {
+        let semantic = self
+            .semantic
+            .as_ref()
+            .context("Semantic search is not enabled (no embedding provider configured)")?;
+        debug!("Semantic search for: {}", query);
+
+        let query_vector = Self::embed(&semantic.provider, query.to_string()).await?;
+        let mut results = semantic.vectors.search(&query_vector, limit);
+        // Zero vectors (e.g. from the no-op provider) match nothing
+        results.retain(|r| r.similarity > 0.0);
+        Ok(results)
+    }
+
     /// Get full context for an entity
     pub async fn get_context_for(&self, entity_id: &str) -> Result<EntityContext> {
         debug!("Getting context for entity: {}", entity_id);
@@ -220,6 +464,28 @@ impl KnowledgeGraph {
         self.db.search_entities(query, entity_type).await
     }
 
+    /// Search entities with scores, optionally boosting recently updated ones
+    pub async fn search_ranked(
+        &self,
+        query: &str,
+        entity_type: Option<&str>,
+        recency: Option<RecencyBoost>,
+    ) -> Result<Vec<RankedEntity>> {
+        self.db.search_ranked(query, entity_type, recency).await
+    }
+
+    /// Typo-tolerant entity search (see `KnowledgeDb::search_entities_fuzzy`)
+    pub async fn search_entities_fuzzy(
+        &self,
+        query: &str,
+        entity_type: Option<&str>,
+        threshold: f32,
+    ) -> Result<Vec<RankedEntity>> {
+        self.db
+            .search_entities_fuzzy(query, entity_type, threshold)
+            .await
+    }
+
     /// Get relationships for an entity
     pub async fn get_relationships(&self, entity_id: &str) -> Result<Vec<Relationship>> {
         self.db.get_relationships_for(entity_id).await
@@ -279,7 +545,26 @@ impl KnowledgeGraph {
     pub async fn reindex(&self) -> Result<()> {
         info!("Reindexing all entities");
         let entities = self.db.get_all_entities().await?;
-        self.index.reindex_all_from_entities(&entities)
+        self.index.reindex_all_from_entities(&entities)?;
+        self.emit(GraphChange::Reindexed);
+        Ok(())
+    }
+
+    /// Back up every entity and relationship as JSON (see `KnowledgeDb::export_json`)
+    pub async fn export_json<W: std::io::Write>(&self, writer: W) -> Result<(usize, usize)> {
+        self.db.export_json(writer).await
+    }
+
+    /// Restore a JSON backup and rebuild the search index so imported
+    /// entities are immediately searchable
+    pub async fn import_json<R: std::io::Read>(
+        &self,
+        reader: R,
+        mode: ImportMode,
+    ) -> Result<ImportSummary> {
+        let summary = self.db.import_json(reader, mode).await?;
+        self.reindex().await?;
+        Ok(summary)
     }
 
     /// Get all entities
@@ -370,6 +655,32 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_update_entity_reindexes() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let graph = KnowledgeGraph::new(
+            temp_dir.path().join("update.db"),
+            temp_dir.path().join("update_index"),
+        )?;
+
+        let id = graph.add_entity("Oslo office", "place", None).await?;
+        let entity = graph
+            .update_entity(&id, Some("Bergen office"), None, None)
+            .await?
+            .expect("entity exists");
+        assert_eq!(entity.name, "Bergen office");
+
+        assert!(graph.search("Oslo", 10)?.is_empty());
+        assert_eq!(graph.search("Bergen", 10)?[0].id, id);
+        assert!(
+            graph
+                .update_entity("missing", None, Some("place"), None)
+                .await?
+                .is_none()
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_remember_and_recall() -> Result<()> {
         let temp_dir = env::temp_dir();
@@ -434,4 +745,126 @@ mod tests {
         let _ = std::fs::remove_dir_all(&index_path);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_subscribe_changes() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let graph = KnowledgeGraph::new(
+            temp_dir.path().join("changes.db"),
+            temp_dir.path().join("changes_index"),
+        )?;
+
+        // No subscribers: mutations must still succeed
+        graph.add_entity("Unobserved", "concept", None).await?;
+
+        let mut rx = graph.subscribe_changes();
+        let a = graph.add_entity("Alice", "person", None).await?;
+        let b = graph.add_entity("Bob", "person", None).await?;
+        let rel = graph.link_entities(&a, &b, "knows", None).await?;
+
+        assert_eq!(rx.recv().await?, GraphChange::EntityAdded { id: a.clone() });
+        assert_eq!(rx.recv().await?, GraphChange::EntityAdded { id: b });
+        assert_eq!(
+            rx.recv().await?,
+            GraphChange::RelationshipAdded { id: rel.clone() }
+        );
+        assert!(rx.try_recv().is_err());
+
+        assert_eq!(graph.delete_entity(&a).await?, 2);
+        assert_eq!(
+            rx.recv().await?,
+            GraphChange::RelationshipDeleted { id: rel }
+        );
+        assert_eq!(
+            rx.recv().await?,
+            GraphChange::EntityDeleted { id: a.clone() }
+        );
+        assert!(graph.search("Alice", 10)?.is_empty());
+
+        // Deleting a missing entity is a no-op
+        assert_eq!(graph.delete_entity(&a).await?, 0);
+        assert!(rx.try_recv().is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_import_json_reindexes() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let source = KnowledgeGraph::new(
+            temp_dir.path().join("source.db"),
+            temp_dir.path().join("source_index"),
+        )?;
+        source.add_entity("Tokio runtime", "concept", None).await?;
+        let mut backup = Vec::new();
+        source.export_json(&mut backup).await?;
+
+        let restored = KnowledgeGraph::new(
+            temp_dir.path().join("restored.db"),
+            temp_dir.path().join("restored_index"),
+        )?;
+        restored
+            .import_json(backup.as_slice(), ImportMode::Merge)
+            .await?;
+        assert_eq!(restored.search("Tokio", 5)?.len(), 1);
+        Ok(())
+    }
+
+    /// Maps words onto two "concept" axes so synonyms land close together
+    struct ConceptEmbedder;
+
+    impl EmbeddingProvider for ConceptEmbedder {
+        fn embed(&self, text: &str) -> Result<Vec<f32>> {
+            let mut vector = vec![0.0; 2];
+            for word in text.to_lowercase().split(|c: char| !c.is_alphanumeric()) {
+                match word {
+                    "car" | "automobile" | "vehicle" => vector[0] += 1.0,
+                    "fruit" | "apple" | "banana" => vector[1] += 1.0,
+                    _ => {}
+                }
+            }
+            Ok(vector)
+        }
+
+        fn dimensions(&self) -> usize {
+            2
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_semantic() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let db_path = temp_dir.path().join("semantic.db");
+        let index_path = temp_dir.path().join("semantic_index");
+
+        let plain = KnowledgeGraph::new(&db_path, &index_path)?;
+        assert!(!plain.has_embeddings());
+        assert!(plain.search_semantic("car", 5).await.is_err());
+        drop(plain);
+
+        let graph = KnowledgeGraph::new(&db_path, &index_path)?
+            .with_embeddings(Arc::new(ConceptEmbedder))?;
+        let auto = graph
+            .remember("Automobile needs new tires", "fact", None)
+            .await?;
+        let apple = graph.add_entity("Apple orchard", "place", None).await?;
+
+        // Keyword search misses the synonym; semantic search finds it
+        assert!(graph.search("car", 5)?.is_empty());
+        let results = graph.search_semantic("car", 5).await?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].entity_id, auto);
+
+        // Vectors persist and are reloaded
+        drop(graph);
+        let graph = KnowledgeGraph::new(&db_path, &index_path)?
+            .with_embeddings(Arc::new(ConceptEmbedder))?;
+        assert_eq!(
+            graph.search_semantic("banana", 5).await?[0].entity_id,
+            apple
+        );
+
+        graph.delete_entity(&apple).await?;
+        assert!(graph.search_semantic("banana", 5).await?.is_empty());
+        Ok(())
+    }
 }
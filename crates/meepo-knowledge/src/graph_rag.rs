@@ -2,7 +2,8 @@
 //!
 //! Enhances standard search by traversing the knowledge graph's entity
 //! relationships to pull in contextually connected entities. Combines
-//! keyword/vector search results with graph traversal for richer context.
+//! keyword/vector search results with graph traversal for richer context,
+//! then optionally reranks the expanded set against the original query.
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
@@ -18,10 +19,27 @@ pub struct GraphRagConfig {
     pub max_hops: usize,
     /// Maximum total entities to return after expansion
     pub max_expanded_results: usize,
-    /// Weight decay per hop (multiplied each hop)
+    /// Score multiplier per hop: an entity `n` hops from a seed scores
+    /// `seed_score * hop_decay^n` per path (1.0 disables decay)
     pub hop_decay: f32,
     /// Whether to include relationship metadata in context
     pub include_relationship_context: bool,
+    /// Only traverse relationships of this type (`None` follows every type)
+    pub relation_type: Option<String>,
+    /// Only traverse these relationship types (`None` allows every type)
+    pub allowed_relation_types: Option<HashSet<String>>,
+    /// Never traverse these relationship types, e.g. `contains_chunk` and
+    /// `next_chunk` when only conceptual connections are wanted
+    pub denied_relation_types: HashSet<String>,
+    /// Edge weight per relationship type, multiplied into the score passed
+    /// across that edge. Types not listed use `default_relation_weight`.
+    pub relation_weights: HashMap<String, f32>,
+    /// Edge weight for relationship types missing from `relation_weights`
+    /// (1.0, so unweighted traversal scores only decay per hop)
+    pub default_relation_weight: f32,
+    /// Rerank this many top expanded results against the query (`None`
+    /// keeps traversal order)
+    pub rerank_top_k: Option<usize>,
 }
 
 impl Default for GraphRagConfig {
@@ -31,10 +49,38 @@ impl Default for GraphRagConfig {
             max_expanded_results: 20,
             hop_decay: 0.5,
             include_relationship_context: true,
+            relation_type: None,
+            allowed_relation_types: None,
+            denied_relation_types: HashSet::new(),
+            relation_weights: HashMap::new(),
+            default_relation_weight: 1.0,
+            rerank_top_k: Some(20),
         }
     }
 }
 
+impl GraphRagConfig {
+    /// Whether traversal may follow a relationship of this type
+    pub fn allows_relation(&self, relation_type: &str) -> bool {
+        self.relation_type
+            .as_ref()
+            .is_none_or(|wanted| wanted == relation_type)
+            && self
+                .allowed_relation_types
+                .as_ref()
+                .is_none_or(|allowed| allowed.contains(relation_type))
+            && !self.denied_relation_types.contains(relation_type)
+    }
+
+    /// Edge weight for a relationship type
+    pub fn relation_weight(&self, relation_type: &str) -> f32 {
+        self.relation_weights
+            .get(relation_type)
+            .copied()
+            .unwrap_or(self.default_relation_weight)
+    }
+}
+
 /// An entity with its graph-derived relevance score
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScoredEntity {
@@ -45,6 +91,17 @@ pub struct ScoredEntity {
     pub source: EntitySource,
     /// Relationships connecting this entity to the query results
     pub connecting_relationships: Vec<Relationship>,
+    /// Relationships traversed from a seed to reach this entity, in order
+    /// (empty for direct matches)
+    #[serde(default)]
+    pub path: Vec<Relationship>,
+    /// Query relevance assigned by `rerank` (`None` if not reranked)
+    #[serde(default)]
+    pub rerank_score: Option<f32>,
+    /// Highlighted excerpt from the full-text match that found this entity
+    /// (see `SearchResult::snippet`); shown in place of the raw metadata
+    #[serde(default)]
+    pub snippet: Option<String>,
 }
 
 /// How an entity was discovered during retrieval
@@ -56,11 +113,25 @@ pub enum EntitySource {
     GraphExpansion { hops: usize, from_entity_id: String },
 }
 
+/// Noisy-OR of two path scores, each clamped to [0, 1]
+fn combine_path_scores(a: f32, b: f32) -> f32 {
+    let (a, b) = (a.clamp(0.0, 1.0), b.clamp(0.0, 1.0));
+    1.0 - (1.0 - a) * (1.0 - b)
+}
+
 /// Expand search results by traversing the knowledge graph.
 ///
 /// Starting from a set of seed entity IDs (from keyword/vector search),
 /// traverses relationships up to `max_hops` deep, scoring discovered
-/// entities by their proximity to the seeds.
+/// entities by their proximity to the seeds. Only relationship types
+/// allowed by the config are followed, and each edge's type weight is
+/// multiplied into the score it propagates.
+///
+/// Each hop multiplies the propagated score by `hop_decay`, so distant
+/// neighbors rank lower. An entity reached through several shortest paths
+/// combines their scores as a noisy-OR (`1 - (1 - a)(1 - b)`), letting
+/// well-connected entities outrank a single strong but distant link while
+/// never pushing a combined score past 1.0.
 pub async fn graph_expand(
     db: &KnowledgeDb,
     seed_ids: &[(String, f32)], // (entity_id, initial_score) from search
@@ -82,15 +153,18 @@ pub async fn graph_expand(
                         search_score: *score,
                     },
                     connecting_relationships: Vec::new(),
+                    path: Vec::new(),
+                    rerank_score: None,
+                    snippet: None,
                 },
             );
         }
     }
 
     // BFS expansion through relationships
-    let mut frontier: Vec<(String, f32, usize)> = seed_ids
+    let mut frontier: Vec<(String, f32)> = seed_ids
         .iter()
-        .map(|(id, score)| (id.clone(), *score, 0))
+        .map(|(id, score)| (id.clone(), *score))
         .collect();
 
     for hop in 0..config.max_hops {
@@ -98,16 +172,25 @@ pub async fn graph_expand(
             break;
         }
 
-        let decay = config.hop_decay.powi((hop + 1) as i32);
-        let mut next_frontier = Vec::new();
+        // Kept in discovery order so results don't depend on hash order
+        let mut next_frontier: Vec<String> = Vec::new();
+        let mut in_next_frontier: HashSet<String> = HashSet::new();
 
-        for (entity_id, parent_score, _) in &frontier {
+        for (entity_id, parent_score) in &frontier {
             let relationships = db
                 .get_relationships_for(entity_id)
                 .await
                 .unwrap_or_default();
+            let parent_path = all_entities
+                .get(entity_id)
+                .map(|parent| parent.path.clone())
+                .unwrap_or_default();
 
             for rel in relationships {
+                if !config.allows_relation(&rel.relation_type) {
+                    continue;
+                }
+
                 // Find the other end of the relationship
                 let neighbor_id = if rel.source_id == *entity_id {
                     &rel.target_id
@@ -115,10 +198,17 @@ pub async fn graph_expand(
                     &rel.source_id
                 };
 
+                let edge_score =
+                    parent_score * config.hop_decay * config.relation_weight(&rel.relation_type);
+
                 if visited.contains(neighbor_id) {
                     // If already found, just add the connecting relationship
                     if let Some(existing) = all_entities.get_mut(neighbor_id) {
                         existing.connecting_relationships.push(rel.clone());
+                        // Another shortest path (found in this same hop) adds its score
+                        if in_next_frontier.contains(neighbor_id) {
+                            existing.score = combine_path_scores(existing.score, edge_score);
+                        }
                     }
                     continue;
                 }
@@ -130,27 +220,39 @@ pub async fn graph_expand(
                 visited.insert(neighbor_id.clone());
 
                 if let Some(neighbor_entity) = db.get_entity(neighbor_id).await? {
-                    let neighbor_score = parent_score * decay;
+                    let mut path = parent_path.clone();
+                    path.push(rel.clone());
 
                     all_entities.insert(
                         neighbor_id.clone(),
                         ScoredEntity {
                             entity: neighbor_entity,
-                            score: neighbor_score,
+                            score: edge_score,
                             source: EntitySource::GraphExpansion {
                                 hops: hop + 1,
                                 from_entity_id: entity_id.clone(),
                             },
                             connecting_relationships: vec![rel.clone()],
+                            path,
+                            rerank_score: None,
+                            snippet: None,
                         },
                     );
 
-                    next_frontier.push((neighbor_id.clone(), neighbor_score, hop + 1));
+                    in_next_frontier.insert(neighbor_id.clone());
+                    next_frontier.push(neighbor_id.clone());
                 }
             }
         }
 
-        frontier = next_frontier;
+        // Propagate each new entity's score combined over all its paths
+        frontier = next_frontier
+            .into_iter()
+            .filter_map(|id| {
+                let score = all_entities.get(&id)?.score;
+                Some((id, score))
+            })
+            .collect();
     }
 
     // Sort by score descending
@@ -172,7 +274,161 @@ pub async fn graph_expand(
     Ok(results)
 }
 
+/// Scores documents against a query so expanded results can be reordered
+/// by relevance. Implement this to plug in a cross-encoder or API reranker.
+pub trait Reranker: Send + Sync {
+    /// Relevance of each document to `query`, in the same order (higher is better)
+    fn score(&self, query: &str, documents: &[&str]) -> Vec<f32>;
+}
+
+/// Okapi BM25 over the candidate set itself, so no external index is needed
+#[derive(Debug, Clone)]
+pub struct Bm25Reranker {
+    /// Term frequency saturation
+    pub k1: f32,
+    /// Document length normalization
+    pub b: f32,
+}
+
+impl Default for Bm25Reranker {
+    fn default() -> Self {
+        Self { k1: 1.2, b: 0.75 }
+    }
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+impl Reranker for Bm25Reranker {
+    fn score(&self, query: &str, documents: &[&str]) -> Vec<f32> {
+        let mut query_terms = tokenize(query);
+        query_terms.sort();
+        query_terms.dedup();
+        let docs: Vec<Vec<String>> = documents.iter().map(|d| tokenize(d)).collect();
+        if docs.is_empty() {
+            return Vec::new();
+        }
+
+        let n = docs.len() as f32;
+        let avg_len = docs.iter().map(|d| d.len()).sum::<usize>() as f32 / n;
+        let idf: HashMap<&str, f32> = query_terms
+            .iter()
+            .map(|term| {
+                let df = docs.iter().filter(|d| d.contains(term)).count() as f32;
+                (term.as_str(), ((n - df + 0.5) / (df + 0.5) + 1.0).ln())
+            })
+            .collect();
+
+        docs.iter()
+            .map(|doc| {
+                let len_norm = 1.0 - self.b + self.b * doc.len() as f32 / avg_len.max(1.0);
+                query_terms
+                    .iter()
+                    .map(|term| {
+                        let tf = doc.iter().filter(|t| *t == term).count() as f32;
+                        idf[term.as_str()] * tf * (self.k1 + 1.0) / (tf + self.k1 * len_norm)
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+}
+
+/// Text a reranker sees for an entity: its name plus full content or metadata
+fn rerank_text(entity: &Entity) -> String {
+    let body = match &entity.metadata {
+        Some(metadata) => match metadata.get("full_content").and_then(|c| c.as_str()) {
+            Some(content) => content.to_string(),
+            None => metadata.to_string(),
+        },
+        None => String::new(),
+    };
+    format!("{} {}", entity.name, body)
+}
+
+/// Reorder the first `top_k` results by relevance to `query`, regardless of
+/// how many hops away they were. Ties keep their traversal order, and
+/// results past `top_k` follow unchanged.
+pub fn rerank(
+    query: &str,
+    mut results: Vec<ScoredEntity>,
+    top_k: usize,
+    reranker: &dyn Reranker,
+) -> Vec<ScoredEntity> {
+    let top_k = top_k.min(results.len());
+    let rest = results.split_off(top_k);
+
+    let texts: Vec<String> = results.iter().map(|r| rerank_text(&r.entity)).collect();
+    let text_refs: Vec<&str> = texts.iter().map(|t| t.as_str()).collect();
+    let scores = reranker.score(query, &text_refs);
+    for (result, score) in results.iter_mut().zip(scores) {
+        result.rerank_score = Some(score);
+    }
+    results.sort_by(|a, b| {
+        b.rerank_score
+            .partial_cmp(&a.rerank_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    debug!("Reranked top {} results for query: {}", top_k, query);
+    results.extend(rest);
+    results
+}
+
+/// One result line (plus relationship lines) in the formatted context
+fn format_entry(scored: &ScoredEntity, config: &GraphRagConfig) -> String {
+    let mut entry = format!(
+        "- **{}** ({}) [{}]",
+        scored.entity.name,
+        scored.entity.entity_type,
+        hop_info(scored)
+    );
+    if let Some(detail) = entry_detail(scored) {
+        entry.push_str(&format!(": {}", detail));
+    }
+    entry.push('\n');
+
+    // Add relationship context
+    if config.include_relationship_context {
+        for rel in &scored.connecting_relationships {
+            entry.push_str(&format!(
+                "  → Relationship: {} ({})\n",
+                rel.relation_type,
+                if rel.source_id == scored.entity.id {
+                    "outgoing"
+                } else {
+                    "incoming"
+                }
+            ));
+        }
+    }
+    entry
+}
+
+/// What follows an entry's name: its matched snippet, else its metadata
+fn entry_detail(scored: &ScoredEntity) -> Option<String> {
+    match (&scored.snippet, &scored.entity.metadata) {
+        (Some(snippet), _) => Some(snippet.clone()),
+        (None, Some(metadata)) => Some(metadata.to_string()),
+        (None, None) => None,
+    }
+}
+
+fn hop_info(scored: &ScoredEntity) -> String {
+    match &scored.source {
+        EntitySource::GraphExpansion { hops, .. } => format!("{} hop(s) away", hops),
+        EntitySource::DirectMatch { .. } => "direct match".to_string(),
+    }
+}
+
 /// Format GraphRAG results into a context string for the LLM.
+///
+/// Reranked results are listed in relevance order in a single section;
+/// otherwise direct matches and related knowledge are grouped separately.
 pub fn format_graph_context(results: &[ScoredEntity], config: &GraphRagConfig) -> String {
     if results.is_empty() {
         return String::new();
@@ -180,6 +436,15 @@ pub fn format_graph_context(results: &[ScoredEntity], config: &GraphRagConfig) -
 
     let mut context = String::new();
 
+    if results.iter().any(|r| r.rerank_score.is_some()) {
+        context.push_str("### Results (most relevant first)\n\n");
+        for scored in results {
+            context.push_str(&format_entry(scored, config));
+        }
+        context.push('\n');
+        return context;
+    }
+
     // Group by source type
     let direct: Vec<&ScoredEntity> = results
         .iter()
@@ -197,8 +462,8 @@ pub fn format_graph_context(results: &[ScoredEntity], config: &GraphRagConfig) -
                 "- **{}** ({})",
                 scored.entity.name, scored.entity.entity_type
             ));
-            if let Some(metadata) = &scored.entity.metadata {
-                context.push_str(&format!(": {}", metadata));
+            if let Some(detail) = entry_detail(scored) {
+                context.push_str(&format!(": {}", detail));
             }
             context.push('\n');
         }
@@ -208,33 +473,7 @@ pub fn format_graph_context(results: &[ScoredEntity], config: &GraphRagConfig) -
     if !expanded.is_empty() {
         context.push_str("### Related Knowledge\n\n");
         for scored in &expanded {
-            let hop_info = match &scored.source {
-                EntitySource::GraphExpansion { hops, .. } => format!("{} hop(s) away", hops),
-                _ => String::new(),
-            };
-            context.push_str(&format!(
-                "- **{}** ({}) [{}]",
-                scored.entity.name, scored.entity.entity_type, hop_info
-            ));
-            if let Some(metadata) = &scored.entity.metadata {
-                context.push_str(&format!(": {}", metadata));
-            }
-            context.push('\n');
-
-            // Add relationship context
-            if config.include_relationship_context {
-                for rel in &scored.connecting_relationships {
-                    context.push_str(&format!(
-                        "  → Relationship: {} ({})\n",
-                        rel.relation_type,
-                        if rel.source_id == scored.entity.id {
-                            "outgoing"
-                        } else {
-                            "incoming"
-                        }
-                    ));
-                }
-            }
+            context.push_str(&format_entry(scored, config));
         }
         context.push('\n');
     }
@@ -305,4 +544,223 @@ mod tests {
         assert!(rust_score > sp_score);
         assert!(sp_score > ms_score);
     }
+
+    #[tokio::test]
+    async fn test_graph_expand_paths_and_relation_filter() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let db = KnowledgeDb::new(temp.path().join("test.db")).unwrap();
+
+        let ann = db.insert_entity("Ann", "person", None).await.unwrap();
+        let bob = db.insert_entity("Bob", "person", None).await.unwrap();
+        let acme = db.insert_entity("Acme", "company", None).await.unwrap();
+        let ann_bob = db
+            .insert_relationship(&ann, &bob, "knows", None)
+            .await
+            .unwrap();
+        let bob_acme = db
+            .insert_relationship(&bob, &acme, "works_at", None)
+            .await
+            .unwrap();
+
+        let seeds = vec![(ann.clone(), 1.0)];
+        let results = graph_expand(&db, &seeds, &GraphRagConfig::default())
+            .await
+            .unwrap();
+        let path_to = |id: &str| -> Vec<String> {
+            results
+                .iter()
+                .find(|r| r.entity.id == id)
+                .unwrap()
+                .path
+                .iter()
+                .map(|rel| rel.id.clone())
+                .collect()
+        };
+        assert!(path_to(&ann).is_empty());
+        assert_eq!(path_to(&bob), vec![ann_bob.clone()]);
+        assert_eq!(path_to(&acme), vec![ann_bob, bob_acme]);
+
+        let config = GraphRagConfig {
+            relation_type: Some("works_at".to_string()),
+            ..Default::default()
+        };
+        let results = graph_expand(&db, &seeds, &config).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].entity.id, ann);
+    }
+
+    #[tokio::test]
+    async fn test_graph_expand_hop_decay_and_multiple_paths() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let db = KnowledgeDb::new(temp.path().join("test.db")).unwrap();
+
+        // seed -> a -> far, and seed -> {b, c} -> shared
+        let mut ids = HashMap::new();
+        for name in ["seed", "a", "far", "b", "c", "shared"] {
+            let id = db.insert_entity(name, "concept", None).await.unwrap();
+            ids.insert(name, id);
+        }
+        for (from, to) in [
+            ("seed", "a"),
+            ("a", "far"),
+            ("seed", "b"),
+            ("seed", "c"),
+            ("b", "shared"),
+            ("c", "shared"),
+        ] {
+            db.insert_relationship(&ids[from], &ids[to], "related_to", None)
+                .await
+                .unwrap();
+        }
+        let seeds = vec![(ids["seed"].clone(), 1.0)];
+        let score_of = |results: &[ScoredEntity], name: &str| {
+            results
+                .iter()
+                .find(|r| r.entity.id == ids[name])
+                .unwrap()
+                .score
+        };
+
+        let results = graph_expand(&db, &seeds, &GraphRagConfig::default())
+            .await
+            .unwrap();
+        assert!((score_of(&results, "a") - 0.5).abs() < 1e-6);
+        assert!((score_of(&results, "far") - 0.25).abs() < 1e-6);
+        // Two 2-hop paths outweigh one: 1 - 0.75 * 0.75
+        assert!((score_of(&results, "shared") - 0.4375).abs() < 1e-6);
+        assert!(score_of(&results, "shared") > score_of(&results, "far"));
+
+        let config = GraphRagConfig {
+            hop_decay: 1.0,
+            ..Default::default()
+        };
+        let results = graph_expand(&db, &seeds, &config).await.unwrap();
+        assert!((score_of(&results, "far") - 1.0).abs() < 1e-6);
+        assert!((score_of(&results, "a") - 1.0).abs() < 1e-6);
+        // Extra paths never push a score past 1.0
+        assert!((score_of(&results, "shared") - 1.0).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_graph_expand_relation_weights_and_deny_list() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let db = KnowledgeDb::new(temp.path().join("test.db")).unwrap();
+
+        let doc = db
+            .insert_entity("Design doc", "document", None)
+            .await
+            .unwrap();
+        let chunk = db
+            .insert_entity("Design doc [chunk 1/2]", "document_chunk", None)
+            .await
+            .unwrap();
+        let topic = db
+            .insert_entity("Borrowing", "concept", None)
+            .await
+            .unwrap();
+        db.insert_relationship(&doc, &chunk, "contains_chunk", None)
+            .await
+            .unwrap();
+        db.insert_relationship(&doc, &topic, "mentions", None)
+            .await
+            .unwrap();
+        let seeds = vec![(doc.clone(), 1.0)];
+        let score_of = |results: &[ScoredEntity], id: &str| {
+            results.iter().find(|r| r.entity.id == id).map(|r| r.score)
+        };
+
+        // Unweighted: both neighbors score the same
+        let results = graph_expand(&db, &seeds, &GraphRagConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(score_of(&results, &chunk), score_of(&results, &topic));
+
+        let config = GraphRagConfig {
+            relation_weights: HashMap::from([
+                ("mentions".to_string(), 2.0),
+                ("contains_chunk".to_string(), 0.1),
+            ]),
+            ..Default::default()
+        };
+        let results = graph_expand(&db, &seeds, &config).await.unwrap();
+        assert!((score_of(&results, &topic).unwrap() - 1.0).abs() < 1e-6);
+        assert!((score_of(&results, &chunk).unwrap() - 0.05).abs() < 1e-6);
+
+        let config = GraphRagConfig {
+            denied_relation_types: HashSet::from(["contains_chunk".to_string()]),
+            ..Default::default()
+        };
+        let results = graph_expand(&db, &seeds, &config).await.unwrap();
+        assert!(score_of(&results, &chunk).is_none());
+        assert!(score_of(&results, &topic).is_some());
+
+        let config = GraphRagConfig {
+            allowed_relation_types: Some(HashSet::from(["contains_chunk".to_string()])),
+            ..Default::default()
+        };
+        let results = graph_expand(&db, &seeds, &config).await.unwrap();
+        assert!(score_of(&results, &chunk).is_some());
+        assert!(score_of(&results, &topic).is_none());
+    }
+
+    #[test]
+    fn test_bm25_reranker() {
+        let scores = Bm25Reranker::default().score(
+            "borrow checker",
+            &[
+                "Weekly grocery list",
+                "The borrow checker enforces ownership rules",
+                "Borrow a ladder from the neighbors",
+            ],
+        );
+        assert_eq!(scores[0], 0.0);
+        assert!(scores[1] > scores[2]);
+        assert!(scores[2] > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_rerank_puts_relevant_neighbor_first() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let db = KnowledgeDb::new(temp.path().join("test.db")).unwrap();
+
+        let project = db
+            .insert_entity("Compiler project", "project", None)
+            .await
+            .unwrap();
+        let meeting = db
+            .insert_entity("Kickoff meeting", "event", None)
+            .await
+            .unwrap();
+        let chunk = db
+            .insert_entity(
+                "Design notes [chunk 3/7]",
+                "document_chunk",
+                Some(serde_json::json!({
+                    "full_content": "The borrow checker runs after type inference."
+                })),
+            )
+            .await
+            .unwrap();
+        db.insert_relationship(&project, &meeting, "has_event", None)
+            .await
+            .unwrap();
+        db.insert_relationship(&meeting, &chunk, "discussed", None)
+            .await
+            .unwrap();
+
+        let config = GraphRagConfig::default();
+        let results = graph_expand(&db, &[(project.clone(), 1.0)], &config)
+            .await
+            .unwrap();
+        assert_eq!(results[0].entity.id, project);
+
+        let reranked = rerank("borrow checker", results, 10, &Bm25Reranker::default());
+        assert_eq!(reranked[0].entity.id, chunk);
+        assert!(reranked.iter().all(|r| r.rerank_score.is_some()));
+
+        let context = format_graph_context(&reranked, &config);
+        assert!(context.starts_with("### Results (most relevant first)"));
+        assert!(context.contains("**Design notes [chunk 3/7]** (document_chunk) [2 hop(s) away]"));
+        assert!(context.find("Design notes").unwrap() < context.find("Compiler project").unwrap());
+    }
 }
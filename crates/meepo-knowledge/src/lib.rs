@@ -4,34 +4,54 @@
 //! - SQLite storage for entities, relationships, conversations, and watchers
 //! - Tantivy full-text search index
 //! - Knowledge graph operations combining both
+//! - Controlled vocabulary for relationship types
 //! - MEMORY.md synchronization
 
 pub mod chunking;
+pub mod dedup;
 pub mod embeddings;
+pub mod extract;
 pub mod graph;
 pub mod graph_rag;
 pub mod memory_sync;
 pub mod sqlite;
 pub mod tantivy;
+pub mod vocabulary;
 
 // Re-export main types
 pub use chunking::{
-    ChunkingConfig, DocumentChunk, DocumentMetadata, chunk_text, detect_content_type,
+    ChunkingConfig, ChunkingStrategy, DOCX_CONTENT_TYPE, DocumentChunk, DocumentMetadata,
+    PDF_CONTENT_TYPE, chunk_text, detect_content_type,
+};
+pub use dedup::{
+    Collapsed, DEFAULT_FUZZY_THRESHOLD, DEFAULT_SIMILARITY_THRESHOLD, collapse_similar,
+    edit_similarity, fuzzy_name_similarity, normalize_name, similar_pairs, trigram_similarity,
 };
 pub use embeddings::{
     EmbeddingConfig, EmbeddingProvider, HybridSearchResult, NoOpEmbeddingProvider, VectorIndex,
-    VectorSearchResult, hybrid_search_rrf,
+    VectorSearchResult, hybrid_search_rrf, weighted_hybrid_search_rrf,
 };
-pub use graph::KnowledgeGraph;
+pub use extract::{ExtractedText, extract_text};
+pub use graph::{GraphChange, KnowledgeGraph};
 pub use graph_rag::{
-    EntitySource, GraphRagConfig, ScoredEntity, format_graph_context, graph_expand,
+    Bm25Reranker, EntitySource, GraphRagConfig, Reranker, ScoredEntity, format_graph_context,
+    graph_expand, rerank,
+};
+pub use memory_sync::{
+    MemoryEntry, append_memory, append_memory_tagged, content_hash, format_memory, load_memory,
+    load_soul, parse_memory, save_memory, select_relevant_memory,
 };
-pub use memory_sync::{load_memory, load_soul, save_memory};
 pub use sqlite::{
-    ActionLogEntry, BackgroundTask, Conversation, Entity, Goal, KnowledgeDb, ModelUsage,
-    Relationship, SourceUsage, UsageSummary, UserPreference, Watcher,
+    ActionLogEntry, BackgroundTask, Conversation, DuplicateCandidate, EXPORT_FORMAT_VERSION,
+    Entity, Goal, ImportMode, ImportSummary, KnowledgeDb, KnowledgeExport, MergeSummary,
+    ModelUsage, RankedEntity, RecencyBoost, Relationship, SourceUsage, UsageSummary,
+    UserPreference, Watcher,
 };
 pub use tantivy::{SearchResult, TantivyIndex};
+pub use vocabulary::{
+    BUILTIN_RELATION_TYPES, DEFAULT_SNAP_THRESHOLD, RelationVocabulary, UnknownRelation,
+    normalize_relation_type,
+};
 
 #[cfg(test)]
 mod tests {
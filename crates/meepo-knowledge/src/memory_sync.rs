@@ -1,9 +1,52 @@
 //! MEMORY.md and SOUL.md synchronization
 
 use anyhow::{Context, Result};
-use std::path::Path;
+use fs2::FileExt;
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
 
+use crate::graph_rag::{Bm25Reranker, Reranker};
+
+/// Line prefix under a heading that tags every entry in that section
+const TAGS_PREFIX: &str = "Tags:";
+
+/// One fact from MEMORY.md: a list item or a paragraph, with the heading it sits under
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryEntry {
+    pub section: Option<String>,
+    pub text: String,
+    /// Tags from the `Tags:` line under the entry's heading
+    pub tags: Vec<String>,
+    /// `content_hash` of the text, used to skip repeated facts
+    pub hash: u64,
+}
+
+impl MemoryEntry {
+    pub fn new(section: Option<String>, text: impl Into<String>, tags: Vec<String>) -> Self {
+        let text = text.into();
+        Self {
+            section,
+            hash: content_hash(&text),
+            text,
+            tags,
+        }
+    }
+}
+
+/// Hash of `text` with whitespace collapsed, so reflowed copies of a fact match
+pub fn content_hash(text: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
 /// Load MEMORY.md contents
 pub fn load_memory<P: AsRef<Path>>(path: P) -> Result<String> {
     let path = path.as_ref();
@@ -25,6 +68,9 @@ pub fn load_memory<P: AsRef<Path>>(path: P) -> Result<String> {
 }
 
 /// Save MEMORY.md contents
+///
+/// The content is written to a temporary file next to `path` and renamed over
+/// it, so a crash mid-write leaves either the old file or the new one.
 pub fn save_memory<P: AsRef<Path>>(path: P, content: &str) -> Result<()> {
     let path = path.as_ref();
     debug!("Saving memory to {:?}", path);
@@ -35,7 +81,17 @@ pub fn save_memory<P: AsRef<Path>>(path: P, content: &str) -> Result<()> {
             .context(format!("Failed to create directory {:?}", parent))?;
     }
 
-    std::fs::write(path, content).context(format!("Failed to write memory file at {:?}", path))?;
+    let temp_path = sibling_path(path, &format!("{}.tmp", uuid::Uuid::new_v4()));
+    let written = File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(content.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| std::fs::rename(&temp_path, path));
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(e).context(format!("Failed to write memory file at {:?}", path));
+    }
 
     info!("Saved {} bytes to memory file", content.len());
     Ok(())
@@ -61,6 +117,199 @@ pub fn load_soul<P: AsRef<Path>>(path: P) -> Result<String> {
     Ok(content)
 }
 
+/// Split MEMORY.md into entries
+///
+/// Each list item (with its indented continuation lines) and each paragraph
+/// becomes one entry, placed under the nearest heading above it and carrying
+/// that heading's `Tags:` line, if any.
+pub fn parse_memory(content: &str) -> Vec<MemoryEntry> {
+    let mut entries = Vec::new();
+    let mut section: Option<String> = None;
+    let mut tags: Vec<String> = Vec::new();
+    let mut current: Option<String> = None;
+    let mut flush = |current: &mut Option<String>, section: &Option<String>, tags: &[String]| {
+        if let Some(text) = current.take() {
+            entries.push(MemoryEntry::new(section.clone(), text, tags.to_vec()));
+        }
+    };
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            flush(&mut current, &section, &tags);
+        } else if trimmed.starts_with('#') {
+            flush(&mut current, &section, &tags);
+            section = Some(trimmed.trim_start_matches('#').trim().to_string());
+            tags.clear();
+        } else if let Some(line_tags) = trimmed.strip_prefix(TAGS_PREFIX) {
+            flush(&mut current, &section, &tags);
+            tags = line_tags
+                .split(',')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .map(str::to_string)
+                .collect();
+        } else if let Some(item) = list_item(trimmed) {
+            flush(&mut current, &section, &tags);
+            current = Some(item.to_string());
+        } else if let Some(text) = current.as_mut() {
+            text.push(' ');
+            text.push_str(trimmed);
+        } else {
+            current = Some(trimmed.to_string());
+        }
+    }
+    flush(&mut current, &section, &tags);
+    entries
+}
+
+/// Render entries as MEMORY.md markdown that `parse_memory` reads back unchanged
+///
+/// Consecutive entries sharing a heading and tags are grouped under one
+/// `## heading` and `Tags:` line, each entry as a list item.
+pub fn format_memory(entries: &[MemoryEntry]) -> String {
+    let mut output = String::new();
+    let mut group = None;
+    for entry in entries {
+        if group != Some((&entry.section, &entry.tags)) {
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            if let Some(section) = &entry.section {
+                output.push_str(&format!("## {}\n", section));
+            }
+            if !entry.tags.is_empty() {
+                output.push_str(&format!("{} {}\n", TAGS_PREFIX, entry.tags.join(", ")));
+            }
+            group = Some((&entry.section, &entry.tags));
+        }
+        output.push_str(&format!("- {}\n", entry.text));
+    }
+    output
+}
+
+/// Append `content` to MEMORY.md under a timestamp heading, skipping repeats
+///
+/// Returns how many entries were appended. Entries whose hash is already in
+/// the file are dropped, and if nothing new is left the file is not touched.
+pub fn append_memory<P: AsRef<Path>>(path: P, content: &str) -> Result<usize> {
+    append_memory_tagged(path, content, &[])
+}
+
+/// Like `append_memory`, with a `Tags:` line categorizing the new entries
+pub fn append_memory_tagged<P: AsRef<Path>>(
+    path: P,
+    content: &str,
+    tags: &[&str],
+) -> Result<usize> {
+    let path = path.as_ref();
+    let _lock = lock_memory(path)?;
+    let existing = load_memory(path)?;
+    let mut seen: HashSet<u64> = parse_memory(&existing).iter().map(|e| e.hash).collect();
+
+    let section = Some(chrono::Utc::now().format("%Y-%m-%d %H:%M UTC").to_string());
+    let tags: Vec<String> = tags
+        .iter()
+        .map(|tag| tag.trim())
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect();
+    let new_entries: Vec<MemoryEntry> = parse_memory(content)
+        .into_iter()
+        .filter(|entry| seen.insert(entry.hash))
+        .map(|entry| MemoryEntry::new(section.clone(), entry.text, tags.clone()))
+        .collect();
+    if new_entries.is_empty() {
+        debug!("Nothing new to append to memory file {:?}", path);
+        return Ok(0);
+    }
+
+    let mut updated = existing;
+    if !updated.is_empty() {
+        if !updated.ends_with('\n') {
+            updated.push('\n');
+        }
+        updated.push('\n');
+    }
+    updated.push_str(&format_memory(&new_entries));
+    save_memory(path, &updated)?;
+    Ok(new_entries.len())
+}
+
+/// `{dir}/.{file name}.{suffix}` next to `path`
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.{}", file_name, suffix))
+}
+
+/// Take an exclusive advisory lock guarding read-modify-write of `path`
+///
+/// The lock lives on a separate `.lock` file because `save_memory` replaces
+/// the memory file itself. It is released when the returned file is dropped.
+fn lock_memory(path: &Path) -> Result<File> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .context(format!("Failed to create directory {:?}", parent))?;
+    }
+    let lock_path = sibling_path(path, "lock");
+    let lock = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .context(format!("Failed to open lock file {:?}", lock_path))?;
+    lock.lock_exclusive()
+        .context(format!("Failed to lock memory file {:?}", path))?;
+    Ok(lock)
+}
+
+/// The text of a markdown list item, or None if `line` isn't one
+fn list_item(line: &str) -> Option<&str> {
+    if let Some(item) = ["- ", "* ", "+ "].iter().find_map(|m| line.strip_prefix(m)) {
+        return Some(item.trim());
+    }
+    let (number, rest) = line.split_once(". ")?;
+    (!number.is_empty() && number.chars().all(|c| c.is_ascii_digit())).then(|| rest.trim())
+}
+
+/// Pick the `max_entries` entries most relevant to `query` and format them for a prompt
+///
+/// Entries are scored with BM25 against their heading, tags and text; those that
+/// share no terms with the query are left out. The chosen entries keep their
+/// original order and are grouped under their headings. Returns an empty
+/// string when nothing matches.
+pub fn select_relevant_memory(entries: &[MemoryEntry], query: &str, max_entries: usize) -> String {
+    let documents: Vec<String> = entries
+        .iter()
+        .map(|entry| {
+            format!(
+                "{} {} {}",
+                entry.section.as_deref().unwrap_or_default(),
+                entry.tags.join(" "),
+                entry.text
+            )
+        })
+        .collect();
+    let documents: Vec<&str> = documents.iter().map(String::as_str).collect();
+    let scores = Bm25Reranker::default().score(query, &documents);
+
+    let mut ranked: Vec<usize> = (0..entries.len()).filter(|&i| scores[i] > 0.0).collect();
+    ranked.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+    ranked.truncate(max_entries);
+    ranked.sort_unstable();
+    debug!(
+        "Selected {} of {} memory entries for query",
+        ranked.len(),
+        entries.len()
+    );
+
+    let selected: Vec<MemoryEntry> = ranked.into_iter().map(|i| entries[i].clone()).collect();
+    format_memory(&selected)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -81,6 +330,130 @@ mod tests {
         Ok(())
     }
 
+    const MEMORY: &str = "# Meepo - Memory
+
+Long-term knowledge auto-loaded each session.
+
+## Preferences
+- Prefers Rust for systems work
+- Drinks oat milk lattes,
+  never dairy
+1. Wakes up at 6am
+
+## Projects
+- Working on the meepo knowledge graph
+";
+
+    #[test]
+    fn test_parse_memory() {
+        let entries = parse_memory(MEMORY);
+        assert_eq!(entries.len(), 5);
+        assert_eq!(entries[0].section.as_deref(), Some("Meepo - Memory"));
+        assert_eq!(
+            entries[2],
+            MemoryEntry::new(
+                Some("Preferences".to_string()),
+                "Drinks oat milk lattes, never dairy",
+                vec![],
+            )
+        );
+        assert_eq!(entries[3].text, "Wakes up at 6am");
+        assert_eq!(entries[4].section.as_deref(), Some("Projects"));
+    }
+
+    #[test]
+    fn test_format_memory_round_trip() {
+        let entries = vec![
+            MemoryEntry::new(None, "Untitled fact", vec![]),
+            MemoryEntry::new(
+                Some("Work".to_string()),
+                "Standup is at 10am",
+                vec!["schedule".to_string(), "work".to_string()],
+            ),
+            MemoryEntry::new(Some("Work".to_string()), "Manager is Dana", vec![]),
+        ];
+        let formatted = format_memory(&entries);
+        assert_eq!(
+            formatted,
+            "- Untitled fact\n\n## Work\nTags: schedule, work\n- Standup is at 10am\n\n\
+             ## Work\n- Manager is Dana\n"
+        );
+        assert_eq!(parse_memory(&formatted), entries);
+    }
+
+    #[test]
+    fn test_append_memory_skips_duplicates() -> Result<()> {
+        let temp = tempfile::TempDir::new()?;
+        let path = temp.path().join("MEMORY.md");
+        save_memory(&path, MEMORY)?;
+
+        assert_eq!(append_memory(&path, "Prefers   Rust for\nsystems work")?, 0);
+        assert_eq!(load_memory(&path)?, MEMORY);
+
+        let added = append_memory_tagged(
+            &path,
+            "- Has a cat named Miso\n- Prefers Rust for systems work",
+            &["pets"],
+        )?;
+        assert_eq!(added, 1);
+        let entries = parse_memory(&load_memory(&path)?);
+        let last = entries.last().unwrap();
+        assert_eq!(last.text, "Has a cat named Miso");
+        assert_eq!(last.tags, vec!["pets"]);
+        assert!(last.section.as_deref().unwrap().ends_with("UTC"));
+
+        assert_eq!(append_memory(&path, "Has a cat named Miso")?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_concurrent_appends_keep_every_entry() -> Result<()> {
+        let temp = tempfile::TempDir::new()?;
+        let path = temp.path().join("MEMORY.md");
+        save_memory(&path, MEMORY)?;
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let path = path.clone();
+                std::thread::spawn(move || append_memory(&path, &format!("Concurrent fact {}", i)))
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap()?, 1);
+        }
+
+        let entries = parse_memory(&load_memory(&path)?);
+        for i in 0..8 {
+            let fact = format!("Concurrent fact {}", i);
+            assert!(entries.iter().any(|e| e.text == fact), "lost {}", fact);
+        }
+        assert_eq!(entries.len(), parse_memory(MEMORY).len() + 8);
+
+        // Only MEMORY.md and its lock file remain, no stray temp files
+        assert_eq!(std::fs::read_dir(temp.path())?.count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_select_relevant_memory() {
+        let entries = parse_memory(MEMORY);
+        let selected = select_relevant_memory(&entries, "what milk should I order?", 1);
+        assert_eq!(
+            selected,
+            "## Preferences\n- Drinks oat milk lattes, never dairy\n"
+        );
+
+        let selected = select_relevant_memory(&entries, "rust knowledge graph", 5);
+        assert_eq!(
+            selected,
+            "## Meepo - Memory\n- Long-term knowledge auto-loaded each session.\n\n\
+             ## Preferences\n- Prefers Rust for systems work\n\n\
+             ## Projects\n- Working on the meepo knowledge graph\n"
+        );
+
+        assert_eq!(select_relevant_memory(&entries, "weather", 5), "");
+    }
+
     #[test]
     fn test_load_nonexistent() -> Result<()> {
         let temp_path = env::temp_dir().join("nonexistent_memory.md");
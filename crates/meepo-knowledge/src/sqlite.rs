@@ -5,11 +5,15 @@ use chrono::{DateTime, Utc};
 use rusqlite::{Connection, OptionalExtension, params};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+use crate::dedup::{fuzzy_name_similarity, similar_pairs};
+use crate::vocabulary::RelationVocabulary;
+
 /// Entity in the knowledge graph
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Entity {
@@ -34,6 +38,129 @@ pub struct Relationship {
     pub created_at: DateTime<Utc>,
 }
 
+/// Version of the JSON format written by `KnowledgeDb::export_json`
+pub const EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// Serialized knowledge graph, as written by `export_json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnowledgeExport {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub entities: Vec<Entity>,
+    pub relationships: Vec<Relationship>,
+}
+
+/// How `import_json` treats records whose IDs already exist
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Keep the existing record and skip the imported one
+    Merge,
+    /// Replace the existing record with the imported one
+    Overwrite,
+}
+
+/// Counts of what `import_json` wrote and skipped
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub entities_imported: usize,
+    pub entities_skipped: usize,
+    pub relationships_imported: usize,
+    /// Already present (merge mode) or pointing at an entity that doesn't exist
+    pub relationships_skipped: usize,
+}
+
+/// Two entities that look like the same thing, as found by `find_duplicates`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateCandidate {
+    /// The older of the two (usually the one to keep)
+    pub keep: Entity,
+    pub duplicate: Entity,
+    /// Trigram similarity of the normalized names (0.0–1.0)
+    pub similarity: f32,
+}
+
+/// Outcome of `merge_entities`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeSummary {
+    /// The kept entity, with unioned metadata
+    pub entity: Entity,
+    /// Relationships repointed from the merged entity onto the kept one
+    pub relationships_moved: usize,
+    /// Relationships dropped because they linked the two entities or became
+    /// exact duplicates of an existing relationship
+    pub relationships_dropped: usize,
+}
+
+/// Recency boost for `search_ranked`: an entity's score halves every
+/// `half_life` since it was last updated
+#[derive(Debug, Clone, Copy)]
+pub struct RecencyBoost {
+    pub half_life: chrono::Duration,
+}
+
+impl RecencyBoost {
+    pub fn new(half_life: chrono::Duration) -> Self {
+        Self { half_life }
+    }
+
+    /// Multiplier in (0.0, 1.0] for an entity last updated at `updated_at`.
+    /// Timestamps in the future count as brand new.
+    pub fn factor(&self, updated_at: DateTime<Utc>, now: DateTime<Utc>) -> f32 {
+        let half_life_secs = self.half_life.num_seconds();
+        if half_life_secs <= 0 {
+            return 1.0;
+        }
+        let age_secs = (now - updated_at).num_seconds().max(0);
+        0.5f64.powf(age_secs as f64 / half_life_secs as f64) as f32
+    }
+}
+
+/// An entity matched by `search_ranked`, with the scores used to order it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankedEntity {
+    pub entity: Entity,
+    /// `text_score * recency_factor`
+    pub score: f32,
+    /// How well the name or type matched the query (0.0–1.0)
+    pub text_score: f32,
+    /// Recency multiplier (1.0 when no boost was requested)
+    pub recency_factor: f32,
+}
+
+/// Score a `search_entities` match: exact name beats a name prefix, which
+/// beats a name substring, which beats a type-only match
+fn text_match_score(entity: &Entity, query: &str) -> f32 {
+    let name = entity.name.to_lowercase();
+    let query = query.to_lowercase();
+    if name == query {
+        1.0
+    } else if name.starts_with(&query) {
+        0.75
+    } else if name.contains(&query) {
+        0.5
+    } else {
+        0.25
+    }
+}
+
+/// Union two metadata values. Object keys from `keep` win; keys only in
+/// `merged` are added. Anything else prefers `keep` when present.
+fn union_metadata(keep: Option<JsonValue>, merged: Option<JsonValue>) -> Option<JsonValue> {
+    match (keep, merged) {
+        (Some(JsonValue::Object(mut keep)), Some(JsonValue::Object(merged))) => {
+            for (key, value) in merged {
+                keep.entry(key).or_insert(value);
+            }
+            Some(JsonValue::Object(keep))
+        }
+        (Some(keep), _) => Some(keep),
+        (None, merged) => merged,
+    }
+}
+
+/// Entity types skipped by `find_duplicates` (chunk names are near-identical by design)
+const DEDUP_SKIPPED_TYPES: &[&str] = &["document_chunk"];
+
 /// Conversation record
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Conversation {
@@ -162,6 +289,7 @@ pub struct BackgroundTask {
 /// SQLite database wrapper (thread-safe via Arc<Mutex>)
 pub struct KnowledgeDb {
     conn: Arc<Mutex<Connection>>,
+    relations: RwLock<RelationVocabulary>,
 }
 
 impl KnowledgeDb {
@@ -406,13 +534,40 @@ impl KnowledgeDb {
             [],
         )?;
 
+        // Create embeddings table for semantic search (same schema as VectorIndex)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS embeddings (
+                entity_id TEXT PRIMARY KEY,
+                vector BLOB NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )",
+            [],
+        )?;
+
         debug!("Database schema initialized successfully");
 
         Ok(Self {
             conn: Arc::new(Mutex::new(conn)),
+            relations: RwLock::new(RelationVocabulary::default()),
         })
     }
 
+    /// Restrict and canonicalize relation types written by `insert_relationship`
+    pub fn set_relation_vocabulary(&self, vocabulary: RelationVocabulary) {
+        *self
+            .relations
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = vocabulary;
+    }
+
+    /// Resolve a relation type against the configured vocabulary
+    pub fn resolve_relation_type(&self, relation_type: &str) -> Result<String> {
+        self.relations
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .resolve(relation_type)
+    }
+
     /// Insert a new entity
     pub async fn insert_entity(
         &self,
@@ -505,6 +660,53 @@ impl KnowledgeDb {
         .context("spawn_blocking task panicked")?
     }
 
+    /// Patch an entity's fields, leaving those given as `None` unchanged. Returns the
+    /// updated entity, or `None` if no entity has this ID.
+    pub async fn update_entity(
+        &self,
+        id: &str,
+        name: Option<&str>,
+        entity_type: Option<&str>,
+        metadata: Option<JsonValue>,
+    ) -> Result<Option<Entity>> {
+        let conn = Arc::clone(&self.conn);
+        let id_owned = id.to_owned();
+        let name = name.map(str::to_owned);
+        let entity_type = entity_type.map(str::to_owned);
+
+        let updated = tokio::task::spawn_blocking(move || -> Result<usize> {
+            let metadata_json = metadata.map(|m| serde_json::to_string(&m)).transpose()?;
+            let conn = conn.lock().unwrap_or_else(|poisoned| {
+                warn!("Database mutex was poisoned, recovering");
+                poisoned.into_inner()
+            });
+            let count = conn.execute(
+                "UPDATE entities
+                 SET name = COALESCE(?2, name),
+                     entity_type = COALESCE(?3, entity_type),
+                     metadata = COALESCE(?4, metadata),
+                     updated_at = ?5
+                 WHERE id = ?1",
+                params![
+                    &id_owned,
+                    name,
+                    entity_type,
+                    metadata_json,
+                    Utc::now().to_rfc3339(),
+                ],
+            )?;
+            debug!("Updated entity {} ({} rows)", id_owned, count);
+            Ok(count)
+        })
+        .await
+        .context("spawn_blocking task panicked")??;
+
+        if updated == 0 {
+            return Ok(None);
+        }
+        self.get_entity(id).await
+    }
+
     /// Search entities by name or type
     pub async fn search_entities(
         &self,
@@ -550,6 +752,90 @@ impl KnowledgeDb {
         .context("spawn_blocking task panicked")?
     }
 
+    /// Search entities like `search_entities`, returning each match with a score,
+    /// best first. With `recency` set, each text score is multiplied by the
+    /// entity's recency factor so recently updated facts rank higher; pass
+    /// `None` for deterministic, text-only ranking.
+    pub async fn search_ranked(
+        &self,
+        query: &str,
+        entity_type: Option<&str>,
+        recency: Option<RecencyBoost>,
+    ) -> Result<Vec<RankedEntity>> {
+        let now = Utc::now();
+        let mut ranked: Vec<RankedEntity> = self
+            .search_entities(query, entity_type)
+            .await?
+            .into_iter()
+            .map(|entity| {
+                let text_score = text_match_score(&entity, query);
+                let recency_factor =
+                    recency.map_or(1.0, |boost| boost.factor(entity.updated_at, now));
+                RankedEntity {
+                    entity,
+                    score: text_score * recency_factor,
+                    text_score,
+                    recency_factor,
+                }
+            })
+            .collect();
+        // Stable sort: equal scores keep the most recently updated first
+        ranked.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        Ok(ranked)
+    }
+
+    /// Typo-tolerant search: the substring matches of `search_ranked`, plus
+    /// entities whose name is within `threshold` edit similarity (0.0–1.0) of
+    /// the query, e.g. "Mundara" finds "Abhay Mundhara". Fuzzy-only matches
+    /// score half their similarity, so they follow every name substring match
+    /// and are ordered by how close they are.
+    pub async fn search_entities_fuzzy(
+        &self,
+        query: &str,
+        entity_type: Option<&str>,
+        threshold: f32,
+    ) -> Result<Vec<RankedEntity>> {
+        let mut ranked = self.search_ranked(query, entity_type, None).await?;
+        let matched: HashSet<String> = ranked.iter().map(|r| r.entity.id.clone()).collect();
+
+        let mut fuzzy: Vec<RankedEntity> = self
+            .get_all_entities()
+            .await?
+            .into_iter()
+            .filter(|entity| {
+                !matched.contains(&entity.id)
+                    && entity_type.is_none_or(|wanted| entity.entity_type == wanted)
+            })
+            .filter_map(|entity| {
+                let similarity = fuzzy_name_similarity(&entity.name, query);
+                (similarity >= threshold).then(|| RankedEntity {
+                    entity,
+                    score: similarity / 2.0,
+                    text_score: similarity / 2.0,
+                    recency_factor: 1.0,
+                })
+            })
+            .collect();
+        fuzzy.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        debug!(
+            "Fuzzy search for '{}' added {} match(es) to {} substring match(es)",
+            query,
+            fuzzy.len(),
+            ranked.len()
+        );
+        ranked.extend(fuzzy);
+        Ok(ranked)
+    }
+
     /// Get all entities (capped to prevent OOM on large databases)
     pub async fn get_all_entities(&self) -> Result<Vec<Entity>> {
         let conn = Arc::clone(&self.conn);
@@ -606,6 +892,335 @@ impl KnowledgeDb {
         })
     }
 
+    /// Helper to convert row to Relationship
+    fn row_to_relationship(row: &rusqlite::Row) -> rusqlite::Result<Relationship> {
+        let metadata_str: Option<String> = row.get(4)?;
+        let metadata = metadata_str
+            .map(|s| serde_json::from_str(&s))
+            .transpose()
+            .map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    4,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })?;
+
+        Ok(Relationship {
+            id: row.get(0)?,
+            source_id: row.get(1)?,
+            target_id: row.get(2)?,
+            relation_type: row.get(3)?,
+            metadata,
+            created_at: row
+                .get::<_, String>(5)?
+                .parse()
+                .unwrap_or_else(|_| Utc::now()),
+        })
+    }
+
+    /// Merge `merge_id` into `keep_id`: relationships are repointed onto the
+    /// kept entity, metadata is unioned (the merged name is recorded under
+    /// `aliases`), and the merged entity is deleted. Runs in one transaction.
+    pub async fn merge_entities(&self, keep_id: &str, merge_id: &str) -> Result<MergeSummary> {
+        if keep_id == merge_id {
+            anyhow::bail!("Cannot merge an entity into itself");
+        }
+        let keep = self
+            .get_entity(keep_id)
+            .await?
+            .with_context(|| format!("No entity found with ID: {}", keep_id))?;
+        let merged = self
+            .get_entity(merge_id)
+            .await?
+            .with_context(|| format!("No entity found with ID: {}", merge_id))?;
+
+        let mut metadata = union_metadata(keep.metadata.clone(), merged.metadata.clone());
+        if merged.name != keep.name {
+            let metadata = metadata.get_or_insert_with(|| serde_json::json!({}));
+            if let Some(object) = metadata.as_object_mut() {
+                let aliases = object
+                    .entry("aliases")
+                    .or_insert_with(|| serde_json::json!([]));
+                if let Some(list) = aliases.as_array_mut()
+                    && !list
+                        .iter()
+                        .any(|a| a.as_str() == Some(merged.name.as_str()))
+                {
+                    list.push(JsonValue::String(merged.name.clone()));
+                }
+            }
+        }
+
+        let conn = Arc::clone(&self.conn);
+        let keep_id_owned = keep_id.to_owned();
+        let merge_id_owned = merge_id.to_owned();
+        let (moved, dropped) = tokio::task::spawn_blocking(move || -> Result<(usize, usize)> {
+            let metadata_json = metadata.map(|m| serde_json::to_string(&m)).transpose()?;
+            let mut conn = conn.lock().unwrap_or_else(|poisoned| {
+                warn!("Database mutex was poisoned, recovering");
+                poisoned.into_inner()
+            });
+            let (keep_id, merge_id) = (&keep_id_owned, &merge_id_owned);
+            let tx = conn.transaction()?;
+
+            // Links between the two would become self-loops
+            let mut dropped = tx.execute(
+                "DELETE FROM relationships
+                 WHERE (source_id = ?1 AND target_id = ?2) OR (source_id = ?2 AND target_id = ?1)",
+                params![keep_id, merge_id],
+            )?;
+            let moved = tx.execute(
+                "UPDATE relationships SET source_id = ?1 WHERE source_id = ?2",
+                params![keep_id, merge_id],
+            )? + tx.execute(
+                "UPDATE relationships SET target_id = ?1 WHERE target_id = ?2",
+                params![keep_id, merge_id],
+            )?;
+            // Both entities may have had the same link to a third entity
+            let duplicates = tx.execute(
+                "DELETE FROM relationships
+                 WHERE (source_id = ?1 OR target_id = ?1)
+                   AND rowid NOT IN (
+                       SELECT MIN(rowid) FROM relationships
+                       WHERE source_id = ?1 OR target_id = ?1
+                       GROUP BY source_id, target_id, relation_type
+                   )",
+                params![keep_id],
+            )?;
+            dropped += duplicates;
+
+            tx.execute(
+                "UPDATE entities SET metadata = ?2, updated_at = ?3 WHERE id = ?1",
+                params![keep_id, metadata_json, Utc::now().to_rfc3339()],
+            )?;
+            tx.execute("DELETE FROM entities WHERE id = ?1", params![merge_id])?;
+            tx.execute(
+                "DELETE FROM embeddings WHERE entity_id = ?1",
+                params![merge_id],
+            )?;
+            tx.commit()?;
+
+            debug!(
+                "Merged entity {} into {} ({} relationships moved, {} dropped)",
+                merge_id, keep_id, moved, dropped
+            );
+            Ok((moved.saturating_sub(duplicates), dropped))
+        })
+        .await
+        .context("spawn_blocking task panicked")??;
+
+        let entity = self
+            .get_entity(keep_id)
+            .await?
+            .context("Kept entity disappeared during merge")?;
+        Ok(MergeSummary {
+            entity,
+            relationships_moved: moved,
+            relationships_dropped: dropped,
+        })
+    }
+
+    /// Find pairs of same-typed entities whose normalized names have a trigram
+    /// similarity of at least `threshold`, most similar first. Document chunks
+    /// are skipped.
+    pub async fn find_duplicates(&self, threshold: f32) -> Result<Vec<DuplicateCandidate>> {
+        let entities = self.get_all_entities().await?;
+
+        let mut by_type: HashMap<&str, Vec<&Entity>> = HashMap::new();
+        for entity in &entities {
+            if !DEDUP_SKIPPED_TYPES.contains(&entity.entity_type.as_str()) {
+                by_type
+                    .entry(entity.entity_type.as_str())
+                    .or_default()
+                    .push(entity);
+            }
+        }
+
+        let mut candidates = Vec::new();
+        for group in by_type.values() {
+            let names: Vec<&str> = group.iter().map(|e| e.name.as_str()).collect();
+            for (i, j, similarity) in similar_pairs(&names, threshold) {
+                let (keep, duplicate) = if group[i].created_at <= group[j].created_at {
+                    (group[i], group[j])
+                } else {
+                    (group[j], group[i])
+                };
+                candidates.push(DuplicateCandidate {
+                    keep: keep.clone(),
+                    duplicate: duplicate.clone(),
+                    similarity,
+                });
+            }
+        }
+        candidates.sort_by(|a, b| {
+            b.similarity
+                .partial_cmp(&a.similarity)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        Ok(candidates)
+    }
+
+    /// Write every entity and relationship to `writer` as JSON (see
+    /// `KnowledgeExport`). Returns the number of entities and relationships.
+    pub async fn export_json<W: std::io::Write>(&self, writer: W) -> Result<(usize, usize)> {
+        let conn = Arc::clone(&self.conn);
+
+        let export = tokio::task::spawn_blocking(move || -> Result<KnowledgeExport> {
+            let conn = conn.lock().unwrap_or_else(|poisoned| {
+                warn!("Database mutex was poisoned, recovering");
+                poisoned.into_inner()
+            });
+            let entities = conn
+                .prepare(
+                    "SELECT id, name, entity_type, metadata, created_at, updated_at
+                     FROM entities
+                     ORDER BY created_at",
+                )?
+                .query_map([], Self::row_to_entity)?
+                .collect::<Result<Vec<_>, _>>()?;
+            let relationships = conn
+                .prepare(
+                    "SELECT id, source_id, target_id, relation_type, metadata, created_at
+                     FROM relationships
+                     ORDER BY created_at",
+                )?
+                .query_map([], Self::row_to_relationship)?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(KnowledgeExport {
+                version: EXPORT_FORMAT_VERSION,
+                exported_at: Utc::now(),
+                entities,
+                relationships,
+            })
+        })
+        .await
+        .context("spawn_blocking task panicked")??;
+
+        serde_json::to_writer_pretty(writer, &export).context("Failed to write export")?;
+        info!(
+            "Exported {} entities and {} relationships",
+            export.entities.len(),
+            export.relationships.len()
+        );
+        Ok((export.entities.len(), export.relationships.len()))
+    }
+
+    /// Restore entities and relationships written by `export_json`, keeping
+    /// their original IDs and timestamps. Relationships whose endpoints don't
+    /// exist after the entity import are skipped. Runs in one transaction.
+    pub async fn import_json<R: std::io::Read>(
+        &self,
+        reader: R,
+        mode: ImportMode,
+    ) -> Result<ImportSummary> {
+        let export: KnowledgeExport =
+            serde_json::from_reader(reader).context("Failed to parse knowledge export")?;
+        if export.version > EXPORT_FORMAT_VERSION {
+            anyhow::bail!(
+                "Unsupported export version {} (this build reads up to {})",
+                export.version,
+                EXPORT_FORMAT_VERSION
+            );
+        }
+        let conn = Arc::clone(&self.conn);
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = conn.lock().unwrap_or_else(|poisoned| {
+                warn!("Database mutex was poisoned, recovering");
+                poisoned.into_inner()
+            });
+            let (entity_sql, relationship_sql) = match mode {
+                ImportMode::Merge => (
+                    "INSERT OR IGNORE INTO entities (id, name, entity_type, metadata, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    "INSERT OR IGNORE INTO relationships (id, source_id, target_id, relation_type, metadata, created_at)
+                     SELECT ?1, ?2, ?3, ?4, ?5, ?6
+                     WHERE EXISTS (SELECT 1 FROM entities WHERE id = ?2)
+                       AND EXISTS (SELECT 1 FROM entities WHERE id = ?3)",
+                ),
+                // Upsert rather than REPLACE so existing rows aren't deleted
+                // (which would cascade to their relationships)
+                ImportMode::Overwrite => (
+                    "INSERT INTO entities (id, name, entity_type, metadata, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                     ON CONFLICT(id) DO UPDATE SET
+                        name = excluded.name,
+                        entity_type = excluded.entity_type,
+                        metadata = excluded.metadata,
+                        created_at = excluded.created_at,
+                        updated_at = excluded.updated_at",
+                    "INSERT INTO relationships (id, source_id, target_id, relation_type, metadata, created_at)
+                     SELECT ?1, ?2, ?3, ?4, ?5, ?6
+                     WHERE EXISTS (SELECT 1 FROM entities WHERE id = ?2)
+                       AND EXISTS (SELECT 1 FROM entities WHERE id = ?3)
+                     ON CONFLICT(id) DO UPDATE SET
+                        source_id = excluded.source_id,
+                        target_id = excluded.target_id,
+                        relation_type = excluded.relation_type,
+                        metadata = excluded.metadata,
+                        created_at = excluded.created_at",
+                ),
+            };
+
+            let tx = conn.transaction()?;
+            let mut summary = ImportSummary::default();
+            for entity in &export.entities {
+                let metadata_json = entity
+                    .metadata
+                    .as_ref()
+                    .map(serde_json::to_string)
+                    .transpose()?;
+                let written = tx.execute(
+                    entity_sql,
+                    params![
+                        entity.id,
+                        entity.name,
+                        entity.entity_type,
+                        metadata_json,
+                        entity.created_at.to_rfc3339(),
+                        entity.updated_at.to_rfc3339(),
+                    ],
+                )?;
+                if written > 0 {
+                    summary.entities_imported += 1;
+                } else {
+                    summary.entities_skipped += 1;
+                }
+            }
+            for rel in &export.relationships {
+                let metadata_json = rel
+                    .metadata
+                    .as_ref()
+                    .map(serde_json::to_string)
+                    .transpose()?;
+                let written = tx.execute(
+                    relationship_sql,
+                    params![
+                        rel.id,
+                        rel.source_id,
+                        rel.target_id,
+                        rel.relation_type,
+                        metadata_json,
+                        rel.created_at.to_rfc3339(),
+                    ],
+                )?;
+                if written > 0 {
+                    summary.relationships_imported += 1;
+                } else {
+                    summary.relationships_skipped += 1;
+                }
+            }
+            tx.commit()?;
+
+            info!("Imported knowledge export: {:?}", summary);
+            Ok(summary)
+        })
+        .await
+        .context("spawn_blocking task panicked")?
+    }
+
     /// Insert a relationship
     pub async fn insert_relationship(
         &self,
@@ -614,10 +1229,10 @@ impl KnowledgeDb {
         relation_type: &str,
         metadata: Option<JsonValue>,
     ) -> Result<String> {
+        let relation_type = self.resolve_relation_type(relation_type)?;
         let conn = Arc::clone(&self.conn);
         let source_id = source_id.to_owned();
         let target_id = target_id.to_owned();
-        let relation_type = relation_type.to_owned();
 
         tokio::task::spawn_blocking(move || {
             let id = Uuid::new_v4().to_string();
@@ -666,31 +1281,7 @@ impl KnowledgeDb {
             )?;
 
             let relationships = stmt
-                .query_map(params![&entity_id], |row| {
-                    let metadata_str: Option<String> = row.get(4)?;
-                    let metadata = metadata_str
-                        .map(|s| serde_json::from_str(&s))
-                        .transpose()
-                        .map_err(|e| {
-                            rusqlite::Error::FromSqlConversionFailure(
-                                4,
-                                rusqlite::types::Type::Text,
-                                Box::new(e),
-                            )
-                        })?;
-
-                    Ok(Relationship {
-                        id: row.get(0)?,
-                        source_id: row.get(1)?,
-                        target_id: row.get(2)?,
-                        relation_type: row.get(3)?,
-                        metadata,
-                        created_at: row
-                            .get::<_, String>(5)?
-                            .parse()
-                            .unwrap_or_else(|_| Utc::now()),
-                    })
-                })?
+                .query_map(params![&entity_id], Self::row_to_relationship)?
                 .collect::<Result<Vec<_>, _>>()?;
 
             Ok(relationships)
@@ -699,6 +1290,58 @@ impl KnowledgeDb {
         .context("spawn_blocking task panicked")?
     }
 
+    /// Delete an entity and every relationship it takes part in. Returns the number of
+    /// rows removed (0 if the entity doesn't exist).
+    pub async fn delete_entity(&self, id: &str) -> Result<usize> {
+        let conn = Arc::clone(&self.conn);
+        let id = id.to_owned();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = conn.lock().unwrap_or_else(|poisoned| {
+                warn!("Database mutex was poisoned, recovering");
+                poisoned.into_inner()
+            });
+            // Relationships go first and explicitly, rather than relying on ON DELETE
+            // CASCADE, so databases opened without foreign keys don't keep dangling rows
+            let tx = conn.transaction()?;
+            let relationships = tx.execute(
+                "DELETE FROM relationships WHERE source_id = ?1 OR target_id = ?1",
+                params![&id],
+            )?;
+            let entities = tx.execute("DELETE FROM entities WHERE id = ?1", params![&id])?;
+            tx.execute("DELETE FROM embeddings WHERE entity_id = ?1", params![&id])?;
+            tx.commit()?;
+            debug!(
+                "Deleted entity {} ({} rows, {} relationships)",
+                id, entities, relationships
+            );
+            Ok(entities + relationships)
+        })
+        .await
+        .context("spawn_blocking task panicked")?
+    }
+
+    /// Store (or replace) the embedding vector for an entity
+    pub async fn upsert_embedding(&self, entity_id: &str, vector: &[f32]) -> Result<()> {
+        let conn = Arc::clone(&self.conn);
+        let entity_id = entity_id.to_owned();
+        let blob = crate::embeddings::f32_vec_to_bytes(vector);
+
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap_or_else(|poisoned| {
+                warn!("Database mutex was poisoned, recovering");
+                poisoned.into_inner()
+            });
+            conn.execute(
+                "INSERT OR REPLACE INTO embeddings (entity_id, vector) VALUES (?1, ?2)",
+                params![entity_id, blob],
+            )?;
+            Ok(())
+        })
+        .await
+        .context("spawn_blocking task panicked")?
+    }
+
     /// Insert a conversation
     pub async fn insert_conversation(
         &self,
@@ -1856,6 +2499,82 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_recency_boost_factor() {
+        let boost = RecencyBoost::new(chrono::Duration::days(30));
+        let now = Utc::now();
+        assert!((boost.factor(now, now) - 1.0).abs() < 1e-6);
+        assert!((boost.factor(now - chrono::Duration::days(30), now) - 0.5).abs() < 1e-6);
+        assert!((boost.factor(now - chrono::Duration::days(60), now) - 0.25).abs() < 1e-6);
+        assert!((boost.factor(now + chrono::Duration::days(1), now) - 1.0).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_search_ranked_recency_boost() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let db = KnowledgeDb::new(temp_dir.path().join("test.db"))?;
+
+        let old_id = db.insert_entity("Berlin", "city", None).await?;
+        let new_id = db.insert_entity("Berlin office", "place", None).await?;
+        {
+            let conn = db.conn.lock().unwrap();
+            conn.execute(
+                "UPDATE entities SET updated_at = ?2 WHERE id = ?1",
+                params![
+                    &old_id,
+                    (Utc::now() - chrono::Duration::days(60)).to_rfc3339()
+                ],
+            )?;
+        }
+
+        // Text-only: the exact name match wins regardless of age
+        let results = db.search_ranked("berlin", None, None).await?;
+        assert_eq!(results[0].entity.id, old_id);
+        assert_eq!(results[0].score, 1.0);
+        assert_eq!(results[1].score, 0.75);
+        assert!(results.iter().all(|r| r.recency_factor == 1.0));
+
+        // Boosted: the recent prefix match outranks the two-half-lives-old exact match
+        let boost = RecencyBoost::new(chrono::Duration::days(30));
+        let results = db.search_ranked("berlin", None, Some(boost)).await?;
+        assert_eq!(results[0].entity.id, new_id);
+        assert!((results[1].score - 0.25).abs() < 1e-3);
+        assert!((results[1].recency_factor - 0.25).abs() < 1e-3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_entities_fuzzy() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let db = KnowledgeDb::new(temp_dir.path().join("test.db"))?;
+
+        let abhay = db.insert_entity("Abhay Mundhara", "person", None).await?;
+        let exact = db.insert_entity("Mundara Trail", "place", None).await?;
+        db.insert_entity("Acme Corp", "company", None).await?;
+
+        // Exact matching stays the default
+        let results = db.search_ranked("Mundara", None, None).await?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].entity.id, exact);
+
+        let results = db.search_entities_fuzzy("Mundara", None, 0.75).await?;
+        let ids: Vec<&str> = results.iter().map(|r| r.entity.id.as_str()).collect();
+        assert_eq!(ids, vec![exact.as_str(), abhay.as_str()]);
+        assert!((results[1].score - 0.4375).abs() < 1e-6);
+
+        // The threshold and type filter still apply
+        assert_eq!(
+            db.search_entities_fuzzy("Mundara", None, 0.9).await?.len(),
+            1
+        );
+        let people = db
+            .search_entities_fuzzy("Mundara", Some("person"), 0.75)
+            .await?;
+        assert_eq!(people.len(), 1);
+        assert_eq!(people[0].entity.id, abhay);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_relationship_operations() -> Result<()> {
         let temp_path = env::temp_dir().join("test_relationships.db");
@@ -1881,6 +2600,198 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_update_entity() -> Result<()> {
+        let temp_path = env::temp_dir().join("test_update_entity.db");
+        let _ = std::fs::remove_file(&temp_path);
+
+        let db = KnowledgeDb::new(&temp_path)?;
+
+        let id = db
+            .insert_entity("Ann", "person", Some(serde_json::json!({"city": "Oslo"})))
+            .await?;
+        let updated = db
+            .update_entity(&id, Some("Anne"), None, None)
+            .await?
+            .expect("entity exists");
+        assert_eq!(updated.name, "Anne");
+        assert_eq!(updated.entity_type, "person");
+        assert_eq!(updated.metadata, Some(serde_json::json!({"city": "Oslo"})));
+
+        assert!(
+            db.update_entity("missing", Some("x"), None, None)
+                .await?
+                .is_none()
+        );
+
+        let _ = std::fs::remove_file(&temp_path);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_entity() -> Result<()> {
+        let temp_path = env::temp_dir().join("test_delete_entity.db");
+        let _ = std::fs::remove_file(&temp_path);
+
+        let db = KnowledgeDb::new(&temp_path)?;
+
+        let a = db.insert_entity("a", "concept", None).await?;
+        let b = db.insert_entity("b", "concept", None).await?;
+        let c = db.insert_entity("c", "concept", None).await?;
+        db.insert_relationship(&a, &b, "relates_to", None).await?;
+        db.insert_relationship(&c, &a, "relates_to", None).await?;
+        db.insert_relationship(&b, &c, "relates_to", None).await?;
+
+        // The entity plus both relationships it's part of
+        assert_eq!(db.delete_entity(&a).await?, 3);
+        assert!(db.get_entity(&a).await?.is_none());
+        assert_eq!(db.get_relationships_for(&b).await?.len(), 1);
+        assert_eq!(db.delete_entity(&a).await?, 0);
+
+        let _ = std::fs::remove_file(&temp_path);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_merge_entities() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let db = KnowledgeDb::new(temp_dir.path().join("merge.db"))?;
+
+        let john = db
+            .insert_entity(
+                "John Smith",
+                "person",
+                Some(serde_json::json!({"email": "john@example.com"})),
+            )
+            .await?;
+        let dup = db
+            .insert_entity(
+                "John smith",
+                "person",
+                Some(serde_json::json!({"email": "old@example.com", "phone": "555-0100"})),
+            )
+            .await?;
+        let acme = db.insert_entity("Acme", "company", None).await?;
+        let bob = db.insert_entity("Bob", "person", None).await?;
+        db.insert_relationship(&john, &acme, "works_at", None)
+            .await?;
+        db.insert_relationship(&dup, &acme, "works_at", None)
+            .await?;
+        db.insert_relationship(&bob, &dup, "knows", None).await?;
+        db.insert_relationship(&john, &dup, "same_as", None).await?;
+
+        let candidates = db.find_duplicates(0.9).await?;
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].keep.id, john);
+        assert_eq!(candidates[0].duplicate.id, dup);
+
+        let summary = db.merge_entities(&john, &dup).await?;
+        assert_eq!(summary.relationships_moved, 1);
+        assert_eq!(summary.relationships_dropped, 2);
+        assert_eq!(
+            summary.entity.metadata,
+            Some(serde_json::json!({
+                "email": "john@example.com",
+                "phone": "555-0100",
+                "aliases": ["John smith"]
+            }))
+        );
+        assert!(db.get_entity(&dup).await?.is_none());
+
+        let relationships = db.get_relationships_for(&john).await?;
+        assert_eq!(relationships.len(), 2);
+        assert!(
+            relationships
+                .iter()
+                .any(|r| r.source_id == bob && r.target_id == john)
+        );
+        assert!(db.find_duplicates(0.9).await?.is_empty());
+
+        assert!(db.merge_entities(&john, &john).await.is_err());
+        let err = db.merge_entities(&john, &dup).await.unwrap_err();
+        assert_eq!(err.to_string(), format!("No entity found with ID: {}", dup));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_export_import_round_trip() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let source = KnowledgeDb::new(temp_dir.path().join("source.db"))?;
+        let a = source
+            .insert_entity("a", "concept", Some(serde_json::json!({"note": "x"})))
+            .await?;
+        let b = source.insert_entity("b", "concept", None).await?;
+        let c = source.insert_entity("c", "person", None).await?;
+        source
+            .insert_relationship(&a, &b, "relates_to", None)
+            .await?;
+        source.insert_relationship(&b, &c, "knows", None).await?;
+
+        let mut backup = Vec::new();
+        assert_eq!(source.export_json(&mut backup).await?, (3, 2));
+
+        let restored = KnowledgeDb::new(temp_dir.path().join("restored.db"))?;
+        let summary = restored
+            .import_json(backup.as_slice(), ImportMode::Merge)
+            .await?;
+        assert_eq!(summary.entities_imported, 3);
+        assert_eq!(summary.relationships_imported, 2);
+        assert_eq!(restored.get_all_entities().await?.len(), 3);
+        assert_eq!(restored.get_relationships_for(&b).await?.len(), 2);
+        let entity = restored.get_entity(&a).await?.unwrap();
+        assert_eq!(entity.metadata, Some(serde_json::json!({"note": "x"})));
+
+        // Merge keeps local edits; overwrite restores the backup
+        restored
+            .update_entity(&a, Some("renamed"), None, None)
+            .await?;
+        let summary = restored
+            .import_json(backup.as_slice(), ImportMode::Merge)
+            .await?;
+        assert_eq!(summary.entities_skipped, 3);
+        assert_eq!(summary.relationships_skipped, 2);
+        assert_eq!(restored.get_entity(&a).await?.unwrap().name, "renamed");
+
+        let summary = restored
+            .import_json(backup.as_slice(), ImportMode::Overwrite)
+            .await?;
+        assert_eq!(summary.entities_imported, 3);
+        assert_eq!(restored.get_entity(&a).await?.unwrap().name, "a");
+        assert_eq!(restored.get_relationships_for(&b).await?.len(), 2);
+
+        assert!(
+            restored
+                .import_json(&b"not json"[..], ImportMode::Merge)
+                .await
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_relationship_vocabulary() -> Result<()> {
+        let temp_path = env::temp_dir().join("test_relationship_vocabulary.db");
+        let _ = std::fs::remove_file(&temp_path);
+
+        let db = KnowledgeDb::new(&temp_path)?;
+        db.set_relation_vocabulary(
+            RelationVocabulary::new(["works_with"]).with_synonym("coworker", "works_with"),
+        );
+
+        let a = db.insert_entity("alice", "person", None).await?;
+        let b = db.insert_entity("bob", "person", None).await?;
+
+        db.insert_relationship(&a, &b, "coworker", None).await?;
+        assert!(db.insert_relationship(&a, &b, "likes", None).await.is_err());
+
+        let rels = db.get_relationships_for(&a).await?;
+        assert_eq!(rels.len(), 1);
+        assert_eq!(rels[0].relation_type, "works_with");
+
+        let _ = std::fs::remove_file(&temp_path);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_goal_operations() -> Result<()> {
         let temp_path = env::temp_dir().join("test_goals.db");
@@ -4,13 +4,19 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use tantivy::{
-    Index, IndexWriter, ReloadPolicy, TantivyDocument, collector::TopDocs, query::QueryParser,
+    Index, IndexWriter, ReloadPolicy, TantivyDocument,
+    collector::TopDocs,
+    query::QueryParser,
     schema::*,
+    snippet::{Snippet, SnippetGenerator},
 };
 use tracing::{debug, info};
 
 use crate::sqlite::Entity;
 
+/// Longest excerpt returned as a search result's snippet
+const SNIPPET_MAX_CHARS: usize = 200;
+
 /// Search result with score and snippet
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResult {
@@ -18,10 +24,84 @@ pub struct SearchResult {
     pub content: String,
     pub entity_type: String,
     pub score: f32,
+    /// Excerpt around the matched terms, which are marked `**term**`
+    /// (the start of the content when no term could be located)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub snippet: Option<String>,
 }
 
+/// Text indexed for an entity: name and type, then its full content (for
+/// memories and document chunks) as plain text so snippets read naturally,
+/// then any other metadata as JSON
+pub(crate) fn index_content(
+    name: &str,
+    entity_type: &str,
+    metadata: Option<&serde_json::Value>,
+) -> String {
+    let mut content = format!("{} {}", name, entity_type);
+    match metadata {
+        Some(serde_json::Value::Object(fields)) => {
+            if let Some(full_content) = fields.get("full_content").and_then(|c| c.as_str()) {
+                content.push('\n');
+                content.push_str(full_content);
+            }
+            let rest: serde_json::Map<String, serde_json::Value> = fields
+                .iter()
+                .filter(|(key, _)| key.as_str() != "full_content")
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect();
+            if !rest.is_empty() {
+                content.push(' ');
+                content.push_str(&serde_json::Value::Object(rest).to_string());
+            }
+        }
+        Some(other) => {
+            content.push(' ');
+            content.push_str(&other.to_string());
+        }
+        None => {}
+    }
+    content
+}
+
+/// The start of `content`, truncated on a char boundary
+fn leading_snippet(content: &str) -> String {
+    if content.chars().count() > SNIPPET_MAX_CHARS {
+        let truncated: String = content.chars().take(SNIPPET_MAX_CHARS - 3).collect();
+        format!("{}...", truncated)
+    } else {
+        content.to_string()
+    }
+}
+
+/// Render a Tantivy snippet with its matched terms in Markdown bold and
+/// ellipses where the excerpt cuts into `content`. `None` if nothing matched.
+fn highlighted_snippet(snippet: &Snippet, content: &str) -> Option<String> {
+    if snippet.highlighted().is_empty() {
+        return None;
+    }
+    let fragment = snippet.fragment();
+    let start = content.find(fragment).unwrap_or(0);
+
+    let mut excerpt = String::new();
+    if start > 0 {
+        excerpt.push_str("...");
+    }
+    let mut last = 0;
+    for range in snippet.highlighted() {
+        excerpt.push_str(&fragment[last..range.start]);
+        excerpt.push_str("**");
+        excerpt.push_str(&fragment[range.clone()]);
+        excerpt.push_str("**");
+        last = range.end;
+    }
+    excerpt.push_str(&fragment[last..]);
+    if start + fragment.len() < content.trim_end().len() {
+        excerpt.push_str("...");
+    }
+    Some(excerpt)
+}
+
 /// Tantivy search index wrapper
 pub struct TantivyIndex {
     index: Index,
@@ -114,6 +194,9 @@ impl TantivyIndex {
 
         // Search
         let top_docs = searcher.search(&query, &TopDocs::with_limit(limit))?;
+        let mut snippet_generator =
+            SnippetGenerator::create(&searcher, &*query, self.content_field)?;
+        snippet_generator.set_max_num_chars(SNIPPET_MAX_CHARS);
 
         let mut results = Vec::new();
         for (score, doc_address) in top_docs {
@@ -137,19 +220,16 @@ impl TantivyIndex {
                 .unwrap_or("")
                 .to_string();
 
-            // Generate snippet (first 200 chars)
-            let snippet = if content.len() > 200 {
-                Some(format!("{}...", &content[..197]))
-            } else {
-                Some(content.clone())
-            };
+            // Excerpt around the matched terms
+            let snippet = highlighted_snippet(&snippet_generator.snippet(&content), &content)
+                .unwrap_or_else(|| leading_snippet(&content));
 
             results.push(SearchResult {
                 id,
                 content,
                 entity_type,
                 score,
-                snippet,
+                snippet: Some(snippet),
             });
         }
 
@@ -189,16 +269,8 @@ impl TantivyIndex {
         let entity_count = entities.len();
         // Index all entities
         for entity in entities {
-            let content = format!(
-                "{} {} {}",
-                entity.name,
-                entity.entity_type,
-                entity
-                    .metadata
-                    .as_ref()
-                    .map(|m: &serde_json::Value| m.to_string())
-                    .unwrap_or_default()
-            );
+            let content =
+                index_content(&entity.name, &entity.entity_type, entity.metadata.as_ref());
 
             let mut doc = TantivyDocument::default();
             doc.add_text(self.id_field, &entity.id);
@@ -269,6 +341,52 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_snippet_truncates_on_char_boundary() {
+        // Byte 197 falls inside a multibyte character
+        let content = format!("snippets {}", "語😀".repeat(150));
+        let snippet = leading_snippet(&content);
+        assert!(snippet.ends_with("..."));
+        assert_eq!(snippet.chars().count(), 200);
+        assert_eq!(leading_snippet("short"), "short");
+    }
+
+    #[test]
+    fn test_snippet_highlights_match_in_long_content() -> Result<()> {
+        let temp_path =
+            env::temp_dir().join(format!("test_tantivy_highlight_{}", uuid::Uuid::new_v4()));
+        let _ = std::fs::remove_dir_all(&temp_path);
+
+        let index = TantivyIndex::new(&temp_path)?;
+
+        let filler = "Unrelated notes about the weekly grocery run. ".repeat(20);
+        let content = format!("{}The borrow checker rejects this code. {}", filler, filler);
+        index.index_document("chunk", &content, "document_chunk", "2024-01-01")?;
+
+        let results = index.search("borrow", 10)?;
+        let snippet = results[0].snippet.as_deref().unwrap();
+        assert!(snippet.contains("**borrow**"), "{}", snippet);
+        assert!(snippet.starts_with("...") && snippet.ends_with("..."));
+        assert!(snippet.len() < content.len() / 4);
+
+        let _ = std::fs::remove_dir_all(&temp_path);
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_content_uses_plain_full_content() {
+        let content = index_content(
+            "Notes [chunk 1/2]",
+            "document_chunk",
+            Some(&serde_json::json!({"full_content": "Line one\nLine \"two\"", "chunk_index": 0})),
+        );
+        assert_eq!(
+            content,
+            "Notes [chunk 1/2] document_chunk\nLine one\nLine \"two\" {\"chunk_index\":0}"
+        );
+        assert_eq!(index_content("Ann", "person", None), "Ann person");
+    }
+
     #[test]
     fn test_delete_document() -> Result<()> {
         let temp_path =
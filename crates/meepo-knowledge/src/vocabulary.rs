@@ -0,0 +1,221 @@
+//! Controlled vocabulary for relationship types
+//!
+//! Left to itself the agent invents near-identical relation types
+//! ("works_with", "colleague_of", "coworker"), which fragments the graph and
+//! weakens GraphRAG traversal. A [`RelationVocabulary`] maps variants onto
+//! canonical terms and, when a set of terms is registered, rejects (or snaps)
+//! anything outside it. An empty vocabulary imposes no restriction.
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+use crate::dedup::trigram_similarity;
+
+/// Relation types written by meepo itself (document ingestion, lifestyle
+/// tools); always accepted so a restrictive vocabulary can't break them
+pub const BUILTIN_RELATION_TYPES: &[&str] = &[
+    "contains_chunk",
+    "contains_document",
+    "next_chunk",
+    "instance_of",
+    "belongs_to",
+    "paid_to",
+    "categorized_as",
+];
+
+/// Default trigram similarity required to snap an unknown type to a term
+pub const DEFAULT_SNAP_THRESHOLD: f32 = 0.5;
+
+/// What to do with a relation type that isn't in the vocabulary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnknownRelation {
+    /// Fail the insert, listing the allowed types
+    #[default]
+    Reject,
+    /// Use the most similar canonical term, rejecting if none is close enough
+    Snap,
+}
+
+/// Registered relation types plus synonyms mapping variants to canonical forms
+#[derive(Debug, Clone)]
+pub struct RelationVocabulary {
+    terms: BTreeSet<String>,
+    synonyms: HashMap<String, String>,
+    unknown: UnknownRelation,
+    snap_threshold: f32,
+}
+
+impl Default for RelationVocabulary {
+    fn default() -> Self {
+        Self {
+            terms: BTreeSet::new(),
+            synonyms: HashMap::new(),
+            unknown: UnknownRelation::default(),
+            snap_threshold: DEFAULT_SNAP_THRESHOLD,
+        }
+    }
+}
+
+impl RelationVocabulary {
+    /// Create a vocabulary restricted to `terms` (empty = unrestricted)
+    pub fn new<I, S>(terms: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            terms: terms
+                .into_iter()
+                .map(|t| normalize_relation_type(t.as_ref()))
+                .filter(|t| !t.is_empty())
+                .collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Map `variant` to the canonical `canonical` type
+    pub fn with_synonym(mut self, variant: &str, canonical: &str) -> Self {
+        let variant = normalize_relation_type(variant);
+        let canonical = normalize_relation_type(canonical);
+        if !variant.is_empty() && !canonical.is_empty() {
+            self.synonyms.insert(variant, canonical);
+        }
+        self
+    }
+
+    /// Choose how types outside the vocabulary are handled
+    pub fn with_unknown(mut self, unknown: UnknownRelation) -> Self {
+        self.unknown = unknown;
+        self
+    }
+
+    /// Set the minimum similarity for [`UnknownRelation::Snap`]
+    pub fn with_snap_threshold(mut self, threshold: f32) -> Self {
+        self.snap_threshold = threshold;
+        self
+    }
+
+    /// True when neither terms nor synonyms are registered
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.synonyms.is_empty()
+    }
+
+    /// Registered canonical terms, sorted
+    pub fn terms(&self) -> impl Iterator<Item = &str> {
+        self.terms.iter().map(String::as_str)
+    }
+
+    /// Resolve `relation_type` to the type that should be stored.
+    ///
+    /// Synonyms are applied first. With no registered terms anything else
+    /// passes through unchanged; otherwise the type must be a registered term
+    /// or a builtin, or be snapped to one when snapping is enabled.
+    pub fn resolve(&self, relation_type: &str) -> Result<String> {
+        if self.is_empty() {
+            return Ok(relation_type.to_string());
+        }
+
+        let key = normalize_relation_type(relation_type);
+        if let Some(canonical) = self.synonyms.get(&key) {
+            return Ok(canonical.clone());
+        }
+        if self.terms.is_empty() {
+            return Ok(relation_type.to_string());
+        }
+        if self.terms.contains(&key) || BUILTIN_RELATION_TYPES.contains(&key.as_str()) {
+            return Ok(key);
+        }
+
+        if self.unknown == UnknownRelation::Snap
+            && let Some(canonical) = self.nearest(&key)
+        {
+            return Ok(canonical.to_string());
+        }
+
+        bail!(
+            "Unknown relation type '{}'; allowed types: {}",
+            relation_type,
+            self.terms().collect::<Vec<_>>().join(", ")
+        )
+    }
+
+    /// Most similar canonical term (via terms or synonym variants) above the threshold
+    fn nearest(&self, key: &str) -> Option<&str> {
+        let candidates = self
+            .terms
+            .iter()
+            .map(|t| (t.as_str(), t.as_str()))
+            .chain(self.synonyms.iter().map(|(v, c)| (v.as_str(), c.as_str())));
+
+        candidates
+            .map(|(text, canonical)| (trigram_similarity(key, text), canonical))
+            .filter(|(score, _)| *score >= self.snap_threshold)
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, canonical)| canonical)
+    }
+}
+
+/// Lowercase and join words with underscores ("Works With" -> "works_with")
+pub fn normalize_relation_type(relation_type: &str) -> String {
+    relation_type
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vocab() -> RelationVocabulary {
+        RelationVocabulary::new(["works_with", "knows", "part_of"])
+            .with_synonym("colleague_of", "works_with")
+            .with_synonym("coworker", "works_with")
+    }
+
+    #[test]
+    fn test_empty_vocabulary_is_unrestricted() {
+        let vocab = RelationVocabulary::default();
+        assert!(vocab.is_empty());
+        assert_eq!(vocab.resolve("Anything Goes").unwrap(), "Anything Goes");
+    }
+
+    #[test]
+    fn test_synonyms_and_normalization() {
+        let vocab = vocab();
+        assert_eq!(vocab.resolve("coworker").unwrap(), "works_with");
+        assert_eq!(vocab.resolve("Colleague Of").unwrap(), "works_with");
+        assert_eq!(vocab.resolve("Works-With").unwrap(), "works_with");
+        assert_eq!(vocab.resolve("contains_chunk").unwrap(), "contains_chunk");
+        assert_eq!(
+            vocab.resolve("contains_document").unwrap(),
+            "contains_document"
+        );
+    }
+
+    #[test]
+    fn test_unknown_rejected_by_default() {
+        let err = vocab().resolve("likes").unwrap_err().to_string();
+        assert!(err.contains("Unknown relation type 'likes'"));
+        assert!(err.contains("knows, part_of, works_with"));
+    }
+
+    #[test]
+    fn test_unknown_snaps_to_nearest() {
+        let vocab = vocab().with_unknown(UnknownRelation::Snap);
+        assert_eq!(vocab.resolve("work_with").unwrap(), "works_with");
+        assert_eq!(vocab.resolve("coworkers").unwrap(), "works_with");
+        assert!(vocab.resolve("likes").is_err());
+    }
+
+    #[test]
+    fn test_synonyms_only_pass_other_types_through() {
+        let vocab = RelationVocabulary::default().with_synonym("coworker", "works_with");
+        assert_eq!(vocab.resolve("coworker").unwrap(), "works_with");
+        assert_eq!(vocab.resolve("likes").unwrap(), "likes");
+    }
+}
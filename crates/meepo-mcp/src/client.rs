@@ -11,6 +11,7 @@ use tracing::{debug, info};
 
 use crate::protocol::McpTool;
 use meepo_core::tools::ToolHandler;
+use meepo_core::truncate_chars;
 
 /// Configuration for an external MCP server
 #[derive(Debug, Clone)]
@@ -230,7 +231,7 @@ impl McpClient {
             let msg: Value = serde_json::from_str(line).with_context(|| {
                 format!(
                     "Invalid JSON from MCP server: {}",
-                    &line[..line.len().min(100)]
+                    truncate_chars(line, 100)
                 )
             })?;
 
@@ -242,7 +243,7 @@ impl McpClient {
             }
 
             // Otherwise it's a notification — log and continue
-            debug!("MCP notification: {}", &line[..line.len().min(200)]);
+            debug!("MCP notification: {}", truncate_chars(line, 200));
         }
     }
 
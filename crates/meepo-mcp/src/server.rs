@@ -9,6 +9,7 @@ use tracing::{debug, info, warn};
 
 use crate::adapter::McpToolAdapter;
 use crate::protocol::*;
+use meepo_core::truncate_chars;
 
 /// MCP server that communicates over STDIO
 pub struct McpServer {
@@ -36,7 +37,7 @@ impl McpServer {
                 continue;
             }
 
-            debug!("MCP received: {}", &line[..line.len().min(200)]);
+            debug!("MCP received: {}", truncate_chars(&line, 200));
 
             let request: JsonRpcRequest = match serde_json::from_str(&line) {
                 Ok(r) => r,
@@ -151,7 +152,7 @@ async fn write_response<W: AsyncWriteExt + Unpin>(
     response: &JsonRpcResponse,
 ) -> Result<()> {
     let json = serde_json::to_string(response).context("Failed to serialize response")?;
-    debug!("MCP sending: {}", &json[..json.len().min(200)]);
+    debug!("MCP sending: {}", truncate_chars(&json, 200));
     writer.write_all(json.as_bytes()).await?;
     writer.write_all(b"\n").await?;
     writer.flush().await?;
@@ -0,0 +1,47 @@
+//! Pluggable calendar access for calendar watchers
+//!
+//! Calendar watchers don't talk to a calendar app themselves: the host
+//! application supplies a [`CalendarSource`] (e.g. wrapping the platform
+//! calendar provider) via
+//! [`WatcherRunner::with_calendar_source`](crate::WatcherRunner::with_calendar_source).
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// An upcoming event seen while polling a calendar
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PolledCalendarEvent {
+    /// Provider identifier of the event (may be empty)
+    pub id: String,
+    pub title: String,
+    pub start: DateTime<Utc>,
+    /// All-day events start at midnight and stay relevant for the whole day
+    pub all_day: bool,
+}
+
+impl PolledCalendarEvent {
+    /// Key used to dedup reminders across polls. It includes the start time, so
+    /// a rescheduled event gets a fresh reminder.
+    pub fn reminder_key(&self) -> String {
+        let id = if self.id.is_empty() {
+            &self.title
+        } else {
+            &self.id
+        };
+        format!("{}@{}", id, self.start.to_rfc3339())
+    }
+}
+
+/// Calendars that calendar watchers can poll
+#[async_trait]
+pub trait CalendarSource: Send + Sync {
+    /// Events starting within the next `days_ahead` days, from every calendar
+    /// unless `calendar` picks one
+    async fn upcoming_events(
+        &self,
+        days_ahead: u64,
+        calendar: Option<&str>,
+    ) -> Result<Vec<PolledCalendarEvent>>;
+}
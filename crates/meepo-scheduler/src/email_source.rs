@@ -0,0 +1,41 @@
+//! Pluggable mailbox access for email watchers
+//!
+//! Out of the box, email watchers poll Mail.app (macOS only). An
+//! [`EmailSource`] lets the runner poll any mailbox instead — the host
+//! application supplies one (e.g. an IMAP account) via
+//! [`WatcherRunner::with_email_source`](crate::WatcherRunner::with_email_source),
+//! which also makes email watchers work on platforms without Mail.app.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+
+/// A message seen while polling a mailbox
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PolledEmail {
+    /// Message-ID header, used to dedup across polls (may be empty)
+    pub message_id: String,
+    pub from: String,
+    pub subject: String,
+    pub date: String,
+    /// Body text, when requested
+    pub body: Option<String>,
+}
+
+impl PolledEmail {
+    /// Key used to dedup emails across polls (message id when the server provides one)
+    pub fn dedup_key(&self) -> String {
+        if self.message_id.is_empty() {
+            format!("{}|{}|{}", self.from, self.subject, self.date)
+        } else {
+            self.message_id.clone()
+        }
+    }
+}
+
+/// A mailbox email watchers can poll
+#[async_trait]
+pub trait EmailSource: Send + Sync {
+    /// The newest `limit` inbox messages, newest first; bodies only if `include_body`
+    async fn recent_emails(&self, limit: usize, include_body: bool) -> Result<Vec<PolledEmail>>;
+}
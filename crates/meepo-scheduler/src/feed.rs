@@ -0,0 +1,223 @@
+//! RSS/Atom feed fetching
+//!
+//! Shared by feed watchers and anything else that reads feeds (such as the
+//! news curator). Requests are conditional: the `ETag` and `Last-Modified`
+//! validators kept in a [`FeedState`] are sent back, so unchanged feeds cost
+//! a `304 Not Modified` instead of a full download.
+
+use crate::persistence::FeedState;
+use crate::url_guard::check_public_url;
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::{DateTime, Utc};
+use reqwest::Url;
+use serde::Serialize;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// One entry of a parsed feed
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FeedItem {
+    /// Entry GUID (feed-rs derives one when the feed omits it)
+    pub id: String,
+    pub title: String,
+    pub link: Option<String>,
+    pub summary: Option<String>,
+    /// Publication time, falling back to the entry's last update
+    pub published: Option<DateTime<Utc>>,
+}
+
+/// A parsed feed. Items keep the feed's own order, usually newest first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeedContent {
+    pub title: Option<String>,
+    pub items: Vec<FeedItem>,
+}
+
+/// Outcome of a conditional feed fetch
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeedFetch {
+    /// The server answered `304 Not Modified`; any cached copy is current
+    NotModified,
+    /// The server answered with an error status
+    Unavailable(reqwest::StatusCode),
+    Fetched(FeedContent),
+}
+
+/// Parse an RSS or Atom document
+pub fn parse_feed(body: &[u8]) -> Result<FeedContent> {
+    let feed = feed_rs::parser::parse(body).context("Failed to parse feed")?;
+
+    let items = feed
+        .entries
+        .into_iter()
+        .map(|entry| FeedItem {
+            title: entry.title.map(|t| t.content).unwrap_or_default(),
+            link: entry.links.into_iter().next().map(|l| l.href),
+            summary: entry.summary.map(|t| t.content),
+            published: entry.published.or(entry.updated),
+            id: entry.id,
+        })
+        .collect();
+
+    Ok(FeedContent {
+        title: feed.title.map(|t| t.content),
+        items,
+    })
+}
+
+/// Redirects followed before a feed fetch gives up
+const MAX_REDIRECTS: usize = 5;
+
+/// Fetch and parse a feed, sending the cache validators in `state` and storing the
+/// new ones after a successful parse. Seen GUIDs in `state` are left untouched.
+///
+/// Any http(s) host can be fetched, including ones on the local network, since
+/// feed watchers are set up by the user. Use [`fetch_public_feed`] for URLs that
+/// come from elsewhere.
+pub async fn fetch_feed(url: &str, state: &mut FeedState) -> Result<FeedFetch> {
+    fetch(url, state, false).await
+}
+
+/// [`fetch_feed`] for URLs the agent picked: the URL and every redirect target go
+/// through [`check_public_url`], so the feed can't be used to reach localhost or
+/// the private network
+pub async fn fetch_public_feed(url: &str, state: &mut FeedState) -> Result<FeedFetch> {
+    fetch(url, state, true).await
+}
+
+/// The URL to request next and a client for it that doesn't follow redirects,
+/// checked with [`check_public_url`] when `public_only`
+async fn prepare(url: &str, public_only: bool) -> Result<(Url, reqwest::ClientBuilder)> {
+    if public_only {
+        let target = check_public_url(url).await?;
+        return Ok((target.url.clone(), target.client_builder()));
+    }
+    let url = Url::parse(url).map_err(|e| anyhow!("Invalid URL '{}': {}", url, e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        bail!("Only HTTP and HTTPS URLs can be fetched");
+    }
+    let builder = reqwest::Client::builder().redirect(reqwest::redirect::Policy::none());
+    Ok((url, builder))
+}
+
+async fn fetch(url: &str, state: &mut FeedState, public_only: bool) -> Result<FeedFetch> {
+    let (mut target, mut builder) = prepare(url, public_only).await?;
+    let mut redirects = 0;
+    let response = loop {
+        let client = builder
+            .user_agent("meepo-agent/1.0")
+            .timeout(Duration::from_secs(30))
+            .build()?;
+
+        let mut request = client.get(target.clone());
+        if let Some(etag) = &state.etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &state.last_modified {
+            request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_redirection() || status == reqwest::StatusCode::NOT_MODIFIED {
+            break response;
+        }
+
+        let location = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| anyhow!("Feed {} redirected without a Location", url))?;
+        redirects += 1;
+        if redirects > MAX_REDIRECTS {
+            bail!("Feed {} redirected more than {} times", url, MAX_REDIRECTS);
+        }
+        let next = target.join(location)?;
+        debug!("Feed {} redirected to {}", url, next);
+        (target, builder) = prepare(next.as_str(), public_only).await?;
+    };
+
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        debug!("Feed {} not modified", url);
+        return Ok(FeedFetch::NotModified);
+    }
+    if !response.status().is_success() {
+        warn!("Feed {} returned status {}", url, response.status());
+        return Ok(FeedFetch::Unavailable(response.status()));
+    }
+
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string())
+    };
+    let etag = header(reqwest::header::ETAG);
+    let last_modified = header(reqwest::header::LAST_MODIFIED);
+
+    let body = response.bytes().await?;
+    let content = parse_feed(&body[..])?;
+    state.etag = etag;
+    state.last_modified = last_modified;
+
+    Ok(FeedFetch::Fetched(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_feed_rss_and_atom() {
+        let rss = r#"<?xml version="1.0"?>
+<rss version="2.0"><channel><title>Blog</title>
+  <item><guid>post-2</guid><title>Second</title><link>https://example.com/2</link>
+    <description>Second post</description><pubDate>Tue, 02 Jan 2024 10:00:00 GMT</pubDate></item>
+  <item><guid>post-1</guid><title>First</title><link>https://example.com/1</link></item>
+</channel></rss>"#;
+        let feed = parse_feed(rss.as_bytes()).unwrap();
+        assert_eq!(feed.title.as_deref(), Some("Blog"));
+        assert_eq!(feed.items.len(), 2);
+        assert_eq!(feed.items[0].id, "post-2");
+        assert_eq!(feed.items[0].link.as_deref(), Some("https://example.com/2"));
+        assert_eq!(feed.items[0].summary.as_deref(), Some("Second post"));
+        assert!(feed.items[0].published.is_some());
+        assert!(feed.items[1].published.is_none());
+
+        let atom = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom"><title>Atom Blog</title>
+  <entry><id>urn:a</id><title>Hello</title><link href="https://example.com/a"/>
+    <updated>2024-01-03T00:00:00Z</updated></entry>
+</feed>"#;
+        let feed = parse_feed(atom.as_bytes()).unwrap();
+        assert_eq!(feed.items[0].title, "Hello");
+        assert!(feed.items[0].published.is_some());
+
+        assert!(parse_feed(b"not a feed").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_feed_unreachable() {
+        // Nothing listens on port 1, so this fails to connect rather than being refused
+        let mut state = FeedState::default();
+        let err = fetch_feed("http://127.0.0.1:1/feed.xml", &mut state)
+            .await
+            .unwrap_err();
+        assert!(!err.to_string().contains("not allowed"), "{}", err);
+        assert_eq!(state, FeedState::default());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_public_feed_refuses_local_hosts() {
+        let mut state = FeedState::default();
+        let err = fetch_public_feed("http://127.0.0.1:1/feed.xml", &mut state)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Access to loopback address is not allowed");
+        assert!(
+            fetch_feed("ftp://example.com/feed", &mut state)
+                .await
+                .is_err()
+        );
+    }
+}
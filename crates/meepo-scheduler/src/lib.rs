@@ -1,21 +1,38 @@
 //! meepo-scheduler - Reactive watchers and task scheduling
 //!
 //! This crate provides functionality for:
-//! - Defining various types of watchers (email, calendar, GitHub, file, etc.)
+//! - Defining various types of watchers (email, calendar, GitHub, RSS, file, etc.)
 //! - Persisting watchers to SQLite
 //! - Running watchers as tokio tasks with event emission
 //! - Scheduling one-shot and recurring tasks
 
+pub mod calendar_source;
+pub mod email_source;
+pub mod feed;
+pub mod lifecycle;
 pub mod persistence;
 pub mod runner;
+pub mod url_guard;
 pub mod watcher;
+pub mod watcher_file;
 
+pub use calendar_source::{CalendarSource, PolledCalendarEvent};
+pub use email_source::{EmailSource, PolledEmail};
+pub use feed::{FeedContent, FeedFetch, FeedItem, fetch_feed, fetch_public_feed, parse_feed};
+pub use lifecycle::{LoggingLifecycleListener, WatcherLifecycleListener};
 pub use persistence::{
-    deactivate_watcher, delete_watcher, get_active_watchers, get_watcher_by_id,
-    init_watcher_tables, save_watcher,
+    ExecutionOutcome, ExecutionRecord, FeedState, SCHEMA_VERSION, deactivate_watcher,
+    delete_watcher, get_active_watchers, get_feed_state, get_runnable_watchers, get_watcher_by_id,
+    get_watcher_history, get_watcher_state, get_watchers_by_channel, get_watchers_by_kind,
+    init_watcher_tables, list_watcher_state, pause_watcher, prune_watcher_executions,
+    record_execution, resume_watcher, save_feed_state, save_watcher, schema_version,
+    set_watcher_state, update_watcher,
 };
-pub use runner::{WatcherConfig, WatcherRunner};
-pub use watcher::{Watcher, WatcherEvent, WatcherKind};
+pub use runner::{WatcherConfig, WatcherMetrics, WatcherRunner};
+pub use watcher::{
+    DedupKey, GitHubEventKind, Watcher, WatcherEvent, WatcherKind, preview_fire_times,
+};
+pub use watcher_file::{WatcherFileSync, load_watchers_from_file, sync_watchers_from_file};
 
 #[cfg(test)]
 mod tests {
@@ -31,10 +48,13 @@ mod tests {
                 from: Some("test@example.com".to_string()),
                 subject_contains: Some("invoice".to_string()),
                 interval_secs: 300,
+                include_body: true,
+                dedup_key: None,
             },
             action: "Process incoming invoices".to_string(),
             reply_channel: "slack-finance".to_string(),
             active: true,
+            paused: false,
             created_at: Utc::now(),
         };
 
@@ -0,0 +1,66 @@
+//! Operational lifecycle hooks for watchers
+//!
+//! `WatcherEvent`s carry the data a watcher produced; lifecycle events describe
+//! the watcher itself (started, paused, failing, auto-disabled). The runner
+//! reports them to a [`WatcherLifecycleListener`] so they can be forwarded to
+//! monitoring or a chat channel without mixing into the agent's event stream.
+
+use crate::watcher::Watcher;
+use tracing::{info, warn};
+
+/// Receives watcher lifecycle notifications from the runner
+///
+/// Methods are called from watcher tasks and must not block; forward to a
+/// channel if the handling needs to await. All methods default to no-ops.
+pub trait WatcherLifecycleListener: Send + Sync {
+    /// A watcher task was started (or a message watcher registered)
+    fn on_started(&self, _watcher: &Watcher) {}
+
+    /// A watcher was stopped on request
+    fn on_stopped(&self, _watcher_id: &str) {}
+
+    /// A watcher produced work; `detail` summarizes what was emitted
+    fn on_fired(&self, _watcher_id: &str, _detail: &str) {}
+
+    /// A watcher run failed; `consecutive_failures` counts failures since the last success
+    fn on_error(&self, _watcher_id: &str, _error: &str, _consecutive_failures: u32) {}
+
+    /// A watcher was stopped and deactivated after failing too many times in a row
+    fn on_auto_disabled(&self, _watcher_id: &str, _reason: &str) {}
+
+    /// A running watcher was paused
+    fn on_paused(&self, _watcher_id: &str) {}
+}
+
+/// Default listener: writes lifecycle events to the log
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LoggingLifecycleListener;
+
+impl WatcherLifecycleListener for LoggingLifecycleListener {
+    fn on_started(&self, watcher: &Watcher) {
+        info!("Watcher {} started ({})", watcher.id, watcher.description());
+    }
+
+    fn on_stopped(&self, watcher_id: &str) {
+        info!("Watcher {} stopped", watcher_id);
+    }
+
+    fn on_fired(&self, watcher_id: &str, detail: &str) {
+        info!("Watcher {} fired: {}", watcher_id, detail);
+    }
+
+    fn on_error(&self, watcher_id: &str, error: &str, consecutive_failures: u32) {
+        warn!(
+            "Watcher {} failed ({} in a row): {}",
+            watcher_id, consecutive_failures, error
+        );
+    }
+
+    fn on_auto_disabled(&self, watcher_id: &str, reason: &str) {
+        warn!("Watcher {} auto-disabled: {}", watcher_id, reason);
+    }
+
+    fn on_paused(&self, watcher_id: &str) {
+        info!("Watcher {} paused", watcher_id);
+    }
+}
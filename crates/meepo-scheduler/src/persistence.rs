@@ -3,16 +3,130 @@
 //! This module handles saving and loading watchers from SQLite,
 //! reusing the same database connection as the knowledge graph.
 
-use crate::watcher::Watcher;
+use crate::watcher::{Watcher, WatcherKind};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use rusqlite::{Connection, params};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use tracing::{debug, info, warn};
 
+/// Maximum length (in chars) of the message stored with an execution record
+const MAX_EXECUTION_MESSAGE_CHARS: usize = 500;
+
+/// Outcome of a single watcher execution (poll, scheduled fire, file event)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecutionOutcome {
+    Success,
+    Failure,
+}
+
+impl ExecutionOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Failure => "failure",
+        }
+    }
+
+    pub fn from_string(s: &str) -> Self {
+        match s {
+            "success" => Self::Success,
+            _ => Self::Failure,
+        }
+    }
+}
+
+/// A row from the `watcher_executions` audit table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionRecord {
+    pub id: i64,
+    pub watcher_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub outcome: ExecutionOutcome,
+    pub message: String,
+}
+
+/// Maximum number of entry GUIDs (or email message ids) remembered per watcher
+pub const MAX_FEED_GUIDS: usize = 1000;
+
+/// Persisted polling state of a feed or email watcher, so restarts don't re-emit old entries
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeedState {
+    /// GUIDs of entries (or message ids of emails) already seen, oldest first
+    pub seen_guids: Vec<String>,
+    /// `ETag` header from the last successful fetch
+    pub etag: Option<String>,
+    /// `Last-Modified` header from the last successful fetch
+    pub last_modified: Option<String>,
+}
+
+impl FeedState {
+    /// Whether an entry GUID has been seen before
+    pub fn has_seen(&self, guid: &str) -> bool {
+        self.seen_guids.iter().any(|g| g == guid)
+    }
+
+    /// Remember an entry GUID, dropping the oldest once over `MAX_FEED_GUIDS`
+    pub fn mark_seen(&mut self, guid: &str) {
+        if self.has_seen(guid) {
+            return;
+        }
+        self.seen_guids.push(guid.to_string());
+        if self.seen_guids.len() > MAX_FEED_GUIDS {
+            let excess = self.seen_guids.len() - MAX_FEED_GUIDS;
+            self.seen_guids.drain(..excess);
+        }
+    }
+}
+
+/// Current schema version of the scheduler tables
+pub const SCHEMA_VERSION: i64 = 6;
+
+/// A single schema upgrade step, taking the database from `version - 1` to `version`
+struct Migration {
+    version: i64,
+    description: &'static str,
+    apply: fn(&Connection) -> Result<()>,
+}
+
+/// Ordered schema migrations applied on top of the version 1 tables.
+///
+/// Never edit or reorder an existing entry; append a new one and bump
+/// `SCHEMA_VERSION` instead.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 2,
+        description: "add paused flag to scheduler_watchers",
+        apply: migrate_v2_paused,
+    },
+    Migration {
+        version: 3,
+        description: "add watcher_executions audit table",
+        apply: migrate_v3_executions,
+    },
+    Migration {
+        version: 4,
+        description: "add watcher_feed_state table",
+        apply: migrate_v4_feed_state,
+    },
+    Migration {
+        version: 5,
+        description: "add watcher_state key-value table",
+        apply: migrate_v5_watcher_state,
+    },
+    Migration {
+        version: 6,
+        description: "index scheduler_watchers by kind and reply channel",
+        apply: migrate_v6_watcher_indexes,
+    },
+];
+
 /// Initialize watcher tables in the database
 ///
-/// Creates the necessary tables for storing watchers if they don't exist.
-/// Safe to call multiple times.
+/// Creates the version 1 tables if they don't exist, then applies any pending
+/// migrations so older databases are upgraded in place. Safe to call multiple times.
 pub fn init_watcher_tables(conn: &Connection) -> Result<()> {
     debug!("Initializing watcher tables");
 
@@ -64,28 +178,189 @@ pub fn init_watcher_tables(conn: &Connection) -> Result<()> {
     )
     .context("Failed to create watcher_events timestamp index")?;
 
+    // Schema version tracking (prefixed like scheduler_watchers, as the file is shared)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS scheduler_meta (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        )",
+        [],
+    )
+    .context("Failed to create scheduler_meta table")?;
+
+    run_migrations(conn)?;
+
     info!("Watcher tables initialized successfully");
     Ok(())
 }
 
+/// Get the schema version recorded in the database (1 if never migrated)
+pub fn schema_version(conn: &Connection) -> Result<i64> {
+    let result = conn.query_row(
+        "SELECT value FROM scheduler_meta WHERE key = 'schema_version'",
+        [],
+        |row| row.get::<_, String>(0),
+    );
+
+    match result {
+        Ok(value) => value
+            .parse()
+            .with_context(|| format!("Invalid schema_version in scheduler_meta: {}", value)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(1),
+        Err(e) => Err(e).context("Failed to read schema version"),
+    }
+}
+
+/// Apply every migration newer than the database's recorded version
+fn run_migrations(conn: &Connection) -> Result<()> {
+    let current = schema_version(conn)?;
+    if current > SCHEMA_VERSION {
+        warn!(
+            "Scheduler schema version {} is newer than supported version {}",
+            current, SCHEMA_VERSION
+        );
+        return Ok(());
+    }
+
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+        let tx = conn
+            .unchecked_transaction()
+            .context("Failed to begin migration transaction")?;
+
+        (migration.apply)(&tx).with_context(|| {
+            format!(
+                "Failed to apply scheduler migration v{} ({})",
+                migration.version, migration.description
+            )
+        })?;
+
+        tx.execute(
+            "INSERT INTO scheduler_meta (key, value) VALUES ('schema_version', ?1)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![migration.version.to_string()],
+        )
+        .context("Failed to record schema version")?;
+
+        tx.commit()
+            .context("Failed to commit migration transaction")?;
+
+        info!(
+            "Applied scheduler migration v{}: {}",
+            migration.version, migration.description
+        );
+    }
+
+    Ok(())
+}
+
+fn migrate_v2_paused(conn: &Connection) -> Result<()> {
+    // Databases upgraded by earlier builds may already have the column
+    if !has_column(conn, "scheduler_watchers", "paused")? {
+        conn.execute(
+            "ALTER TABLE scheduler_watchers ADD COLUMN paused INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+fn migrate_v3_executions(conn: &Connection) -> Result<()> {
+    // Table for tracking watcher executions (when each watcher ran and how it went)
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS watcher_executions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            watcher_id TEXT NOT NULL,
+            timestamp TEXT NOT NULL,
+            outcome TEXT NOT NULL,
+            message TEXT NOT NULL DEFAULT ''
+        );
+        CREATE INDEX IF NOT EXISTS idx_watcher_executions_watcher_ts
+            ON watcher_executions(watcher_id, timestamp);",
+    )?;
+    Ok(())
+}
+
+fn migrate_v4_feed_state(conn: &Connection) -> Result<()> {
+    // Per-watcher state for feed watchers: seen entry GUIDs and HTTP cache validators
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS watcher_feed_state (
+            watcher_id TEXT PRIMARY KEY,
+            seen_guids_json TEXT NOT NULL DEFAULT '[]',
+            etag TEXT,
+            last_modified TEXT,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn migrate_v5_watcher_state(conn: &Connection) -> Result<()> {
+    // Free-form per-watcher variables that survive fires and restarts
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS watcher_state (
+            watcher_id TEXT NOT NULL,
+            key TEXT NOT NULL,
+            value_json TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY (watcher_id, key)
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn migrate_v6_watcher_indexes(conn: &Connection) -> Result<()> {
+    // Lets kind lookups match the serialized discriminant without parsing every row
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_sched_watchers_kind
+         ON scheduler_watchers(json_extract(kind_json, '$.type'))",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_sched_watchers_reply_channel
+         ON scheduler_watchers(reply_channel)",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Check whether a table has a column with the given name
+fn has_column(conn: &Connection, table: &str, column: &str) -> Result<bool> {
+    let mut stmt = conn
+        .prepare(&format!("PRAGMA table_info({})", table))
+        .context("Failed to read table info")?;
+    let names = stmt
+        .query_map([], |row| row.get::<_, String>(1))
+        .context("Failed to query table info")?
+        .collect::<rusqlite::Result<Vec<String>>>()?;
+    Ok(names.iter().any(|n| n == column))
+}
+
 /// Save a watcher to the database
 ///
 /// If a watcher with the same ID exists, it will be updated.
-/// Otherwise, a new watcher will be inserted.
+/// Otherwise, a new watcher will be inserted. Watchers that fail
+/// [`Watcher::validate`] are rejected rather than stored.
 pub fn save_watcher(conn: &Connection, watcher: &Watcher) -> Result<()> {
+    watcher
+        .validate()
+        .with_context(|| format!("Invalid watcher {}", watcher.id))?;
+
     let kind_json =
         serde_json::to_string(&watcher.kind).context("Failed to serialize watcher kind")?;
 
     let created_at = watcher.created_at.to_rfc3339();
 
     conn.execute(
-        "INSERT INTO scheduler_watchers (id, kind_json, action, reply_channel, active, created_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+        "INSERT INTO scheduler_watchers (id, kind_json, action, reply_channel, active, created_at, paused)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
          ON CONFLICT(id) DO UPDATE SET
             kind_json = excluded.kind_json,
             action = excluded.action,
             reply_channel = excluded.reply_channel,
-            active = excluded.active",
+            active = excluded.active,
+            paused = excluded.paused",
         params![
             &watcher.id,
             &kind_json,
@@ -93,6 +368,7 @@ pub fn save_watcher(conn: &Connection, watcher: &Watcher) -> Result<()> {
             &watcher.reply_channel,
             watcher.active as i32,
             &created_at,
+            watcher.paused as i32,
         ],
     )
     .context("Failed to save watcher")?;
@@ -101,27 +377,120 @@ pub fn save_watcher(conn: &Connection, watcher: &Watcher) -> Result<()> {
     Ok(())
 }
 
-/// Get all active watchers from the database
+/// Update an existing watcher in place, keeping its id and `created_at`
+///
+/// Fails if no watcher has this id, rather than inserting one. Watchers that
+/// fail [`Watcher::validate`] are rejected.
+pub fn update_watcher(conn: &Connection, watcher: &Watcher) -> Result<()> {
+    watcher
+        .validate()
+        .with_context(|| format!("Invalid watcher {}", watcher.id))?;
+    let kind_json =
+        serde_json::to_string(&watcher.kind).context("Failed to serialize watcher kind")?;
+
+    let rows_affected = conn
+        .execute(
+            "UPDATE scheduler_watchers
+             SET kind_json = ?2, action = ?3, reply_channel = ?4, active = ?5, paused = ?6
+             WHERE id = ?1",
+            params![
+                &watcher.id,
+                &kind_json,
+                &watcher.action,
+                &watcher.reply_channel,
+                watcher.active as i32,
+                watcher.paused as i32,
+            ],
+        )
+        .context("Failed to update watcher")?;
+    if rows_affected == 0 {
+        anyhow::bail!("Watcher not found: {}", watcher.id);
+    }
+
+    debug!("Updated watcher: {} ({})", watcher.id, watcher.action);
+    Ok(())
+}
+
+/// Get all active watchers from the database (including paused ones)
 pub fn get_active_watchers(conn: &Connection) -> Result<Vec<Watcher>> {
+    let watchers = query_watchers(conn, "active = 1", [])?;
+    debug!("Retrieved {} active watchers", watchers.len());
+    Ok(watchers)
+}
+
+/// Get active watchers that are not paused
+pub fn get_runnable_watchers(conn: &Connection) -> Result<Vec<Watcher>> {
+    let watchers = query_watchers(conn, "active = 1 AND paused = 0", [])?;
+    debug!("Retrieved {} runnable watchers", watchers.len());
+    Ok(watchers)
+}
+
+/// Get all watchers of one kind (active or not), oldest first. `kind` is the
+/// serialized discriminant, e.g. "EmailWatch" or "RssWatch".
+pub fn get_watchers_by_kind(conn: &Connection, kind: &str) -> Result<Vec<Watcher>> {
+    let watchers = query_watchers(
+        conn,
+        "json_extract(kind_json, '$.type') = ?1 ORDER BY created_at",
+        params![kind],
+    )?;
+    debug!("Retrieved {} {} watchers", watchers.len(), kind);
+    Ok(watchers)
+}
+
+/// Get all watchers (active or not) that reply to `reply_channel`, oldest first
+pub fn get_watchers_by_channel(conn: &Connection, reply_channel: &str) -> Result<Vec<Watcher>> {
+    let watchers = query_watchers(
+        conn,
+        "reply_channel = ?1 ORDER BY created_at",
+        params![reply_channel],
+    )?;
+    debug!(
+        "Retrieved {} watchers replying to {}",
+        watchers.len(),
+        reply_channel
+    );
+    Ok(watchers)
+}
+
+/// Load all watchers matching a fixed SQL condition (with `params` bound to its
+/// placeholders), skipping unreadable rows
+fn query_watchers(
+    conn: &Connection,
+    condition: &str,
+    params: impl rusqlite::Params,
+) -> Result<Vec<Watcher>> {
     let mut stmt = conn
-        .prepare("SELECT id, kind_json, action, reply_channel, active, created_at FROM scheduler_watchers WHERE active = 1")
-        .context("Failed to prepare query for active watchers")?;
+        .prepare(&format!(
+            "SELECT id, kind_json, action, reply_channel, active, created_at, paused
+             FROM scheduler_watchers WHERE {}",
+            condition
+        ))
+        .context("Failed to prepare query for watchers")?;
 
     let watchers: Vec<Watcher> = stmt
-        .query_map([], |row| {
+        .query_map(params, |row| {
             let id: String = row.get(0)?;
             let kind_json: String = row.get(1)?;
             let action: String = row.get(2)?;
             let reply_channel: String = row.get(3)?;
             let active: i32 = row.get(4)?;
             let created_at_str: String = row.get(5)?;
+            let paused: i32 = row.get(6)?;
 
-            Ok((id, kind_json, action, reply_channel, active, created_at_str))
+            Ok((
+                id,
+                kind_json,
+                action,
+                reply_channel,
+                active,
+                created_at_str,
+                paused,
+            ))
         })
-        .context("Failed to query active watchers")?
+        .context("Failed to query watchers")?
         .filter_map(|result| match result {
-            Ok((id, kind_json, action, reply_channel, active, created_at_str)) => {
-                let kind = match serde_json::from_str(&kind_json) {
+            Ok((id, kind_json, action, reply_channel, active, created_at_str, paused)) => {
+                let kind = match WatcherKind::from_json(&kind_json) {
                     Ok(k) => k,
                     Err(e) => {
                         warn!("Failed to deserialize watcher kind for {}: {}", id, e);
@@ -143,6 +512,7 @@ pub fn get_active_watchers(conn: &Connection) -> Result<Vec<Watcher>> {
                     action,
                     reply_channel,
                     active: active != 0,
+                    paused: paused != 0,
                     created_at,
                 })
             }
@@ -153,14 +523,13 @@ pub fn get_active_watchers(conn: &Connection) -> Result<Vec<Watcher>> {
         })
         .collect();
 
-    debug!("Retrieved {} active watchers", watchers.len());
     Ok(watchers)
 }
 
 /// Get a specific watcher by ID
 pub fn get_watcher_by_id(conn: &Connection, id: &str) -> Result<Option<Watcher>> {
     let mut stmt = conn
-        .prepare("SELECT id, kind_json, action, reply_channel, active, created_at FROM scheduler_watchers WHERE id = ?1")
+        .prepare("SELECT id, kind_json, action, reply_channel, active, created_at, paused FROM scheduler_watchers WHERE id = ?1")
         .context("Failed to prepare query for watcher by ID")?;
 
     let result = stmt.query_row(params![id], |row| {
@@ -170,14 +539,23 @@ pub fn get_watcher_by_id(conn: &Connection, id: &str) -> Result<Option<Watcher>>
         let reply_channel: String = row.get(3)?;
         let active: i32 = row.get(4)?;
         let created_at_str: String = row.get(5)?;
-
-        Ok((id, kind_json, action, reply_channel, active, created_at_str))
+        let paused: i32 = row.get(6)?;
+
+        Ok((
+            id,
+            kind_json,
+            action,
+            reply_channel,
+            active,
+            created_at_str,
+            paused,
+        ))
     });
 
     match result {
-        Ok((id, kind_json, action, reply_channel, active, created_at_str)) => {
+        Ok((id, kind_json, action, reply_channel, active, created_at_str, paused)) => {
             let kind =
-                serde_json::from_str(&kind_json).context("Failed to deserialize watcher kind")?;
+                WatcherKind::from_json(&kind_json).context("Failed to deserialize watcher kind")?;
 
             let created_at = DateTime::parse_from_rfc3339(&created_at_str)
                 .context("Failed to parse created_at")?
@@ -189,6 +567,7 @@ pub fn get_watcher_by_id(conn: &Connection, id: &str) -> Result<Option<Watcher>>
                 action,
                 reply_channel,
                 active: active != 0,
+                paused: paused != 0,
                 created_at,
             }))
         }
@@ -218,14 +597,67 @@ pub fn deactivate_watcher(conn: &Connection, id: &str) -> Result<bool> {
     }
 }
 
+/// Pause a watcher (set paused = true)
+///
+/// Unlike deactivation, the watcher keeps running so its dedup state survives;
+/// it just stops firing until resumed.
+pub fn pause_watcher(conn: &Connection, id: &str) -> Result<bool> {
+    set_watcher_paused(conn, id, true)
+}
+
+/// Resume a paused watcher (set paused = false)
+pub fn resume_watcher(conn: &Connection, id: &str) -> Result<bool> {
+    set_watcher_paused(conn, id, false)
+}
+
+fn set_watcher_paused(conn: &Connection, id: &str, paused: bool) -> Result<bool> {
+    let rows_affected = conn
+        .execute(
+            "UPDATE scheduler_watchers SET paused = ?1 WHERE id = ?2",
+            params![paused as i32, id],
+        )
+        .context("Failed to update watcher paused state")?;
+
+    let state = if paused { "paused" } else { "resumed" };
+    if rows_affected > 0 {
+        info!("Watcher {} {}", id, state);
+        Ok(true)
+    } else {
+        warn!(
+            "Attempted to set {} state on non-existent watcher: {}",
+            state, id
+        );
+        Ok(false)
+    }
+}
+
 /// Permanently delete a watcher from the database
 ///
-/// This also deletes all associated events due to the CASCADE constraint.
+/// This also deletes all associated events due to the CASCADE constraint,
+/// and the watcher's execution history and state variables.
 pub fn delete_watcher(conn: &Connection, id: &str) -> Result<bool> {
     let rows_affected = conn
         .execute("DELETE FROM scheduler_watchers WHERE id = ?1", params![id])
         .context("Failed to delete watcher")?;
 
+    conn.execute(
+        "DELETE FROM watcher_executions WHERE watcher_id = ?1",
+        params![id],
+    )
+    .context("Failed to delete watcher execution history")?;
+
+    conn.execute(
+        "DELETE FROM watcher_feed_state WHERE watcher_id = ?1",
+        params![id],
+    )
+    .context("Failed to delete watcher feed state")?;
+
+    conn.execute(
+        "DELETE FROM watcher_state WHERE watcher_id = ?1",
+        params![id],
+    )
+    .context("Failed to delete watcher state")?;
+
     if rows_affected > 0 {
         info!("Deleted watcher: {}", id);
         Ok(true)
@@ -331,6 +763,219 @@ pub fn cleanup_old_events(conn: &Connection, days_to_keep: u32) -> Result<usize>
     Ok(rows_deleted)
 }
 
+/// Record a single watcher execution in the audit table
+pub fn record_execution(
+    conn: &Connection,
+    watcher_id: &str,
+    outcome: ExecutionOutcome,
+    message: &str,
+) -> Result<()> {
+    let message: String = message.chars().take(MAX_EXECUTION_MESSAGE_CHARS).collect();
+    let timestamp = Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO watcher_executions (watcher_id, timestamp, outcome, message)
+         VALUES (?1, ?2, ?3, ?4)",
+        params![watcher_id, &timestamp, outcome.as_str(), &message],
+    )
+    .context("Failed to record watcher execution")?;
+
+    debug!(
+        "Recorded {} execution for watcher {}",
+        outcome.as_str(),
+        watcher_id
+    );
+    Ok(())
+}
+
+/// Get the most recent executions for a watcher, newest first
+pub fn get_watcher_history(
+    conn: &Connection,
+    watcher_id: &str,
+    limit: usize,
+) -> Result<Vec<ExecutionRecord>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, watcher_id, timestamp, outcome, message FROM watcher_executions
+             WHERE watcher_id = ?1
+             ORDER BY timestamp DESC, id DESC
+             LIMIT ?2",
+        )
+        .context("Failed to prepare query for watcher history")?;
+
+    let records = stmt
+        .query_map(params![watcher_id, limit as i64], |row| {
+            let id: i64 = row.get(0)?;
+            let watcher_id: String = row.get(1)?;
+            let timestamp_str: String = row.get(2)?;
+            let outcome: String = row.get(3)?;
+            let message: String = row.get(4)?;
+
+            Ok((id, watcher_id, timestamp_str, outcome, message))
+        })
+        .context("Failed to query watcher history")?
+        .filter_map(|result| match result {
+            Ok((id, watcher_id, timestamp_str, outcome, message)) => {
+                let timestamp = match DateTime::parse_from_rfc3339(&timestamp_str) {
+                    Ok(dt) => dt.with_timezone(&Utc),
+                    Err(e) => {
+                        warn!("Failed to parse execution timestamp: {}", e);
+                        return None;
+                    }
+                };
+
+                Some(ExecutionRecord {
+                    id,
+                    watcher_id,
+                    timestamp,
+                    outcome: ExecutionOutcome::from_string(&outcome),
+                    message,
+                })
+            }
+            Err(e) => {
+                warn!("Failed to read execution row: {}", e);
+                None
+            }
+        })
+        .collect();
+
+    Ok(records)
+}
+
+/// Prune execution records older than the retention window (in days)
+pub fn prune_watcher_executions(conn: &Connection, days_to_keep: u32) -> Result<usize> {
+    let cutoff = Utc::now() - chrono::Duration::days(days_to_keep as i64);
+    let cutoff_str = cutoff.to_rfc3339();
+
+    let rows_deleted = conn
+        .execute(
+            "DELETE FROM watcher_executions WHERE timestamp < ?1",
+            params![&cutoff_str],
+        )
+        .context("Failed to prune watcher executions")?;
+
+    if rows_deleted > 0 {
+        info!("Pruned {} old watcher execution records", rows_deleted);
+    }
+
+    Ok(rows_deleted)
+}
+
+/// Load the persisted state of a feed watcher, or `None` if it has never been polled
+pub fn get_feed_state(conn: &Connection, watcher_id: &str) -> Result<Option<FeedState>> {
+    let result = conn.query_row(
+        "SELECT seen_guids_json, etag, last_modified FROM watcher_feed_state WHERE watcher_id = ?1",
+        params![watcher_id],
+        |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+            ))
+        },
+    );
+
+    match result {
+        Ok((seen_json, etag, last_modified)) => {
+            let seen_guids =
+                serde_json::from_str(&seen_json).context("Failed to parse seen feed GUIDs")?;
+            Ok(Some(FeedState {
+                seen_guids,
+                etag,
+                last_modified,
+            }))
+        }
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e).context("Failed to query feed state"),
+    }
+}
+
+/// Persist the state of a feed watcher
+pub fn save_feed_state(conn: &Connection, watcher_id: &str, state: &FeedState) -> Result<()> {
+    let seen_json =
+        serde_json::to_string(&state.seen_guids).context("Failed to serialize seen feed GUIDs")?;
+
+    conn.execute(
+        "INSERT INTO watcher_feed_state (watcher_id, seen_guids_json, etag, last_modified, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(watcher_id) DO UPDATE SET
+            seen_guids_json = excluded.seen_guids_json,
+            etag = excluded.etag,
+            last_modified = excluded.last_modified,
+            updated_at = excluded.updated_at",
+        params![
+            watcher_id,
+            seen_json,
+            state.etag,
+            state.last_modified,
+            Utc::now().to_rfc3339(),
+        ],
+    )
+    .context("Failed to save feed state")?;
+
+    Ok(())
+}
+
+/// Read a state variable scoped to a watcher, or `None` if it was never set
+pub fn get_watcher_state(conn: &Connection, watcher_id: &str, key: &str) -> Result<Option<Value>> {
+    let result = conn.query_row(
+        "SELECT value_json FROM watcher_state WHERE watcher_id = ?1 AND key = ?2",
+        params![watcher_id, key],
+        |row| row.get::<_, String>(0),
+    );
+
+    match result {
+        Ok(json) => Ok(Some(
+            serde_json::from_str(&json).context("Failed to parse watcher state value")?,
+        )),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e).context("Failed to query watcher state"),
+    }
+}
+
+/// Set a state variable scoped to a watcher, replacing any previous value
+pub fn set_watcher_state(
+    conn: &Connection,
+    watcher_id: &str,
+    key: &str,
+    value: &Value,
+) -> Result<()> {
+    let json = serde_json::to_string(value).context("Failed to serialize watcher state value")?;
+
+    conn.execute(
+        "INSERT INTO watcher_state (watcher_id, key, value_json, updated_at)
+         VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(watcher_id, key) DO UPDATE SET
+            value_json = excluded.value_json,
+            updated_at = excluded.updated_at",
+        params![watcher_id, key, json, Utc::now().to_rfc3339()],
+    )
+    .context("Failed to save watcher state")?;
+
+    Ok(())
+}
+
+/// List all state variables of a watcher, ordered by key
+pub fn list_watcher_state(conn: &Connection, watcher_id: &str) -> Result<Vec<(String, Value)>> {
+    let mut stmt = conn
+        .prepare("SELECT key, value_json FROM watcher_state WHERE watcher_id = ?1 ORDER BY key")
+        .context("Failed to prepare watcher state query")?;
+
+    let rows = stmt
+        .query_map(params![watcher_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .context("Failed to query watcher state")?;
+
+    let mut vars = Vec::new();
+    for row in rows {
+        let (key, json) = row.context("Failed to read watcher state row")?;
+        let value = serde_json::from_str(&json).context("Failed to parse watcher state value")?;
+        vars.push((key, value));
+    }
+    Ok(vars)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -351,6 +996,8 @@ mod tests {
                 from: Some("test@example.com".to_string()),
                 subject_contains: None,
                 interval_secs: 300,
+                include_body: true,
+                dedup_key: None,
             },
             "Test action".to_string(),
             "test-channel".to_string(),
@@ -364,6 +1011,84 @@ mod tests {
         assert_eq!(loaded.reply_channel, watcher.reply_channel);
     }
 
+    #[test]
+    fn test_save_watcher_rejects_invalid() {
+        let conn = setup_test_db();
+        let watcher = Watcher::new(
+            WatcherKind::EmailWatch {
+                from: None,
+                subject_contains: None,
+                interval_secs: 0,
+                include_body: true,
+                dedup_key: None,
+            },
+            "Busy loop".to_string(),
+            "slack".to_string(),
+        );
+
+        let err = format!("{:#}", save_watcher(&conn, &watcher).unwrap_err());
+        assert!(err.contains("interval_secs"), "{}", err);
+        assert!(get_watcher_by_id(&conn, &watcher.id).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_update_watcher_in_place() {
+        let conn = setup_test_db();
+        let mut watcher = Watcher::new(
+            WatcherKind::RssWatch {
+                url: "https://example.com/feed".to_string(),
+                interval_secs: 600,
+                dedup_key: None,
+                allow_private_network: false,
+            },
+            "New posts".to_string(),
+            "slack".to_string(),
+        );
+        watcher.created_at = Utc::now() - chrono::Duration::days(3);
+        save_watcher(&conn, &watcher).unwrap();
+
+        let mut edited = watcher.clone();
+        edited.kind = WatcherKind::RssWatch {
+            url: "https://example.com/feed".to_string(),
+            interval_secs: 3600,
+            dedup_key: None,
+            allow_private_network: false,
+        };
+        edited.action = "Summarize new posts".to_string();
+        edited.created_at = Utc::now();
+        update_watcher(&conn, &edited).unwrap();
+
+        let loaded = get_watcher_by_id(&conn, &watcher.id).unwrap().unwrap();
+        assert_eq!(loaded.action, "Summarize new posts");
+        assert!(matches!(
+            loaded.kind,
+            WatcherKind::RssWatch {
+                interval_secs: 3600,
+                ..
+            }
+        ));
+        assert_eq!(
+            loaded.created_at.timestamp(),
+            watcher.created_at.timestamp()
+        );
+
+        // Unknown ids fail instead of being inserted
+        let unknown = Watcher::new(
+            WatcherKind::MessageWatch {
+                keyword: "deploy".to_string(),
+            },
+            "Notify".to_string(),
+            "slack".to_string(),
+        );
+        let err = update_watcher(&conn, &unknown).unwrap_err();
+        assert!(err.to_string().contains("not found"), "{}", err);
+        assert!(get_watcher_by_id(&conn, &unknown.id).unwrap().is_none());
+
+        // Invalid edits are rejected
+        edited.action = String::new();
+        assert!(update_watcher(&conn, &edited).is_err());
+    }
+
     #[test]
     fn test_get_active_watchers() {
         let conn = setup_test_db();
@@ -371,6 +1096,7 @@ mod tests {
         let watcher1 = Watcher::new(
             WatcherKind::FileWatch {
                 path: "/tmp/test".to_string(),
+                recursive: true,
             },
             "Watch test file".to_string(),
             "alerts".to_string(),
@@ -378,8 +1104,10 @@ mod tests {
 
         let mut watcher2 = Watcher::new(
             WatcherKind::CalendarWatch {
-                lookahead_hours: 24,
+                lead_minutes: 15,
+                calendar: None,
                 interval_secs: 600,
+                dedup_key: None,
             },
             "Calendar check".to_string(),
             "calendar".to_string(),
@@ -394,6 +1122,74 @@ mod tests {
         assert_eq!(active[0].id, watcher1.id);
     }
 
+    #[test]
+    fn test_get_watchers_by_kind_and_channel() {
+        let conn = setup_test_db();
+        let feed = |url: &str, channel: &str| {
+            Watcher::new(
+                WatcherKind::RssWatch {
+                    url: url.to_string(),
+                    interval_secs: 600,
+                    dedup_key: None,
+                    allow_private_network: false,
+                },
+                "New posts".to_string(),
+                channel.to_string(),
+            )
+        };
+        let mut inactive_feed = feed("https://a.example/feed", "slack");
+        inactive_feed.active = false;
+        let file = Watcher::new(
+            WatcherKind::FileWatch {
+                path: "/tmp/test".to_string(),
+                recursive: true,
+            },
+            "Watch test file".to_string(),
+            "slack".to_string(),
+        );
+        for watcher in [
+            &inactive_feed,
+            &feed("https://b.example/feed", "discord"),
+            &file,
+        ] {
+            save_watcher(&conn, watcher).unwrap();
+        }
+
+        let feeds = get_watchers_by_kind(&conn, "RssWatch").unwrap();
+        assert_eq!(feeds.len(), 2);
+        assert!(
+            feeds
+                .iter()
+                .all(|w| matches!(w.kind, WatcherKind::RssWatch { .. }))
+        );
+        assert_eq!(get_watchers_by_kind(&conn, "FileWatch").unwrap().len(), 1);
+        assert!(
+            get_watchers_by_kind(&conn, "EmailWatch")
+                .unwrap()
+                .is_empty()
+        );
+
+        let slack: Vec<String> = get_watchers_by_channel(&conn, "slack")
+            .unwrap()
+            .into_iter()
+            .map(|w| w.id)
+            .collect();
+        assert_eq!(slack.len(), 2);
+        assert!(slack.contains(&inactive_feed.id) && slack.contains(&file.id));
+        assert!(get_watchers_by_channel(&conn, "email").unwrap().is_empty());
+
+        // The kind filter is answered from the expression index
+        let plan: String = conn
+            .query_row(
+                "EXPLAIN QUERY PLAN SELECT id FROM scheduler_watchers
+                 WHERE json_extract(kind_json, '$.type') = 'RssWatch'",
+                [],
+                |row| row.get(3),
+            )
+            .unwrap();
+        assert!(plan.contains("idx_sched_watchers_kind"), "{}", plan);
+    }
+
     #[test]
     fn test_deactivate_watcher() {
         let conn = setup_test_db();
@@ -401,6 +1197,7 @@ mod tests {
         let watcher = Watcher::new(
             WatcherKind::FileWatch {
                 path: "/tmp/test".to_string(),
+                recursive: true,
             },
             "Test".to_string(),
             "test".to_string(),
@@ -420,6 +1217,7 @@ mod tests {
         let watcher = Watcher::new(
             WatcherKind::FileWatch {
                 path: "/tmp/test".to_string(),
+                recursive: true,
             },
             "Test".to_string(),
             "test".to_string(),
@@ -439,6 +1237,7 @@ mod tests {
         let watcher = Watcher::new(
             WatcherKind::FileWatch {
                 path: "/tmp".to_string(),
+                recursive: true,
             },
             "Test".to_string(),
             "test".to_string(),
@@ -457,4 +1256,182 @@ mod tests {
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].0, "file_changed");
     }
+
+    #[test]
+    fn test_record_and_get_history() {
+        let conn = setup_test_db();
+        // Idempotent table creation
+        init_watcher_tables(&conn).unwrap();
+
+        record_execution(&conn, "w1", ExecutionOutcome::Success, "2 new items").unwrap();
+        record_execution(&conn, "w1", ExecutionOutcome::Failure, "timed out").unwrap();
+        record_execution(&conn, "w2", ExecutionOutcome::Success, "other").unwrap();
+
+        let history = get_watcher_history(&conn, "w1", 10).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].outcome, ExecutionOutcome::Failure);
+        assert_eq!(history[0].message, "timed out");
+        assert_eq!(history[1].outcome, ExecutionOutcome::Success);
+
+        let limited = get_watcher_history(&conn, "w1", 1).unwrap();
+        assert_eq!(limited.len(), 1);
+    }
+
+    #[test]
+    fn test_prune_and_delete_executions() {
+        let conn = setup_test_db();
+
+        let old = (Utc::now() - chrono::Duration::days(40)).to_rfc3339();
+        conn.execute(
+            "INSERT INTO watcher_executions (watcher_id, timestamp, outcome, message)
+             VALUES ('w1', ?1, 'success', 'old')",
+            params![&old],
+        )
+        .unwrap();
+        record_execution(&conn, "w1", ExecutionOutcome::Success, "new").unwrap();
+
+        assert_eq!(prune_watcher_executions(&conn, 30).unwrap(), 1);
+        let history = get_watcher_history(&conn, "w1", 10).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].message, "new");
+
+        delete_watcher(&conn, "w1").unwrap();
+        assert!(get_watcher_history(&conn, "w1", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_pause_and_resume_watcher() {
+        let conn = setup_test_db();
+
+        let watcher = Watcher::new(
+            WatcherKind::FileWatch {
+                path: "/tmp/test".to_string(),
+                recursive: true,
+            },
+            "Test".to_string(),
+            "test".to_string(),
+        );
+        save_watcher(&conn, &watcher).unwrap();
+
+        assert!(pause_watcher(&conn, &watcher.id).unwrap());
+        let loaded = get_watcher_by_id(&conn, &watcher.id).unwrap().unwrap();
+        assert!(loaded.paused);
+        assert!(loaded.active);
+
+        // Paused watchers stay active but are not runnable
+        assert_eq!(get_active_watchers(&conn).unwrap().len(), 1);
+        assert!(get_runnable_watchers(&conn).unwrap().is_empty());
+
+        assert!(resume_watcher(&conn, &watcher.id).unwrap());
+        assert_eq!(get_runnable_watchers(&conn).unwrap().len(), 1);
+        assert!(!pause_watcher(&conn, "missing").unwrap());
+    }
+
+    #[test]
+    fn test_feed_state_roundtrip() {
+        let conn = setup_test_db();
+        assert!(get_feed_state(&conn, "feed").unwrap().is_none());
+
+        let mut state = FeedState {
+            etag: Some("\"abc\"".to_string()),
+            ..Default::default()
+        };
+        state.mark_seen("guid-1");
+        state.mark_seen("guid-2");
+        state.mark_seen("guid-1");
+        save_feed_state(&conn, "feed", &state).unwrap();
+
+        let loaded = get_feed_state(&conn, "feed").unwrap().unwrap();
+        assert_eq!(loaded, state);
+        assert_eq!(loaded.seen_guids, vec!["guid-1", "guid-2"]);
+
+        delete_watcher(&conn, "feed").unwrap();
+        assert!(get_feed_state(&conn, "feed").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_watcher_state_scoped_and_persisted() {
+        let conn = setup_test_db();
+        assert!(get_watcher_state(&conn, "w1", "count").unwrap().is_none());
+
+        set_watcher_state(&conn, "w1", "count", &serde_json::json!(1)).unwrap();
+        set_watcher_state(&conn, "w1", "count", &serde_json::json!(2)).unwrap();
+        set_watcher_state(&conn, "w1", "last", &serde_json::json!({"id": "abc"})).unwrap();
+        set_watcher_state(&conn, "w2", "count", &serde_json::json!(99)).unwrap();
+
+        assert_eq!(
+            get_watcher_state(&conn, "w1", "count").unwrap(),
+            Some(serde_json::json!(2))
+        );
+        let vars = list_watcher_state(&conn, "w1").unwrap();
+        assert_eq!(vars.len(), 2);
+        assert_eq!(vars[0].0, "count");
+        assert_eq!(vars[1].1, serde_json::json!({"id": "abc"}));
+
+        delete_watcher(&conn, "w1").unwrap();
+        assert!(list_watcher_state(&conn, "w1").unwrap().is_empty());
+        assert_eq!(
+            get_watcher_state(&conn, "w2", "count").unwrap(),
+            Some(serde_json::json!(99))
+        );
+    }
+
+    #[test]
+    fn test_feed_state_bounded() {
+        let mut state = FeedState::default();
+        for i in 0..MAX_FEED_GUIDS + 5 {
+            state.mark_seen(&format!("guid-{}", i));
+        }
+        assert_eq!(state.seen_guids.len(), MAX_FEED_GUIDS);
+        assert!(!state.has_seen("guid-0"));
+        assert!(state.has_seen(&format!("guid-{}", MAX_FEED_GUIDS + 4)));
+    }
+
+    #[test]
+    fn test_migrates_v1_database() {
+        // Seed a database with the original (version 1) schema and data
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE scheduler_watchers (
+                id TEXT PRIMARY KEY,
+                kind_json TEXT NOT NULL,
+                action TEXT NOT NULL,
+                reply_channel TEXT NOT NULL,
+                active INTEGER NOT NULL DEFAULT 1,
+                created_at TEXT NOT NULL
+            );
+            CREATE TABLE watcher_events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                watcher_id TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                payload_json TEXT NOT NULL,
+                timestamp TEXT NOT NULL
+            );",
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO scheduler_watchers (id, kind_json, action, reply_channel, active, created_at)
+             VALUES ('old', ?1, 'Legacy action', 'discord', 1, ?2)",
+            params![r#"{"type":"FileWatch","path":"/tmp"}"#, Utc::now().to_rfc3339()],
+        )
+        .unwrap();
+        assert_eq!(schema_version(&conn).unwrap_or(1), 1);
+
+        init_watcher_tables(&conn).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+        assert!(has_column(&conn, "scheduler_watchers", "paused").unwrap());
+
+        // Existing rows survive and pick up defaults for new columns
+        let loaded = get_watcher_by_id(&conn, "old").unwrap().unwrap();
+        assert_eq!(loaded.action, "Legacy action");
+        assert!(!loaded.paused);
+
+        // Tables added by later migrations are usable
+        record_execution(&conn, "old", ExecutionOutcome::Success, "ok").unwrap();
+        assert_eq!(get_watcher_history(&conn, "old", 10).unwrap().len(), 1);
+
+        // Re-running is a no-op
+        init_watcher_tables(&conn).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+    }
 }
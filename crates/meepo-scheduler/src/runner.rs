@@ -3,24 +3,29 @@
 //! This module manages the lifecycle of watcher tasks, spawning them as
 //! tokio tasks and coordinating their execution.
 
-use crate::watcher::{Watcher, WatcherEvent, WatcherKind};
+use crate::calendar_source::{CalendarSource, PolledCalendarEvent};
+use crate::email_source::{EmailSource, PolledEmail};
+use crate::feed::{self, FeedFetch, FeedItem};
+use crate::lifecycle::{LoggingLifecycleListener, WatcherLifecycleListener};
+use crate::persistence::{self, ExecutionOutcome, FeedState};
+use crate::watcher::{DedupKey, FileChangeKind, Watcher, WatcherEvent, WatcherKind};
 use anyhow::{Context, Result};
-use chrono::{NaiveTime, Utc};
-#[cfg(target_os = "macos")]
+use chrono::{DateTime, NaiveTime, Utc};
 use lru::LruCache;
 use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher as NotifyWatcher};
-use std::collections::HashMap;
-#[cfg(target_os = "macos")]
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
-#[cfg(target_os = "macos")]
 use std::num::NonZeroUsize;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
 use std::time::Duration;
 #[cfg(target_os = "macos")]
 use tokio::process::Command;
-use tokio::sync::{RwLock, mpsc};
+use tokio::sync::{RwLock, Semaphore, SemaphorePermit, mpsc};
+use tokio::task::JoinHandle;
 use tokio::time::{Instant, sleep_until};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
@@ -39,6 +44,28 @@ pub struct WatcherConfig {
 
     /// Whether to enforce active hours check
     pub enforce_active_hours: bool,
+
+    /// How many days of execution history to keep (older rows are pruned)
+    pub history_retention_days: u32,
+
+    /// Random spread applied to polling intervals, as a fraction of the interval.
+    /// Each cycle sleeps `interval ± rand(0..interval * jitter_fraction)` so
+    /// watchers sharing an interval don't all fire at once. 0.0 disables jitter.
+    pub jitter_fraction: f64,
+
+    /// How long `shutdown` waits for watcher tasks to finish their current
+    /// cycle before aborting them
+    pub shutdown_timeout_secs: u64,
+
+    /// Consecutive failed polls after which a polling watcher is stopped and
+    /// deactivated. 0 (the default) keeps retrying forever, so a network outage
+    /// or rate limit doesn't switch watchers off for good.
+    pub max_consecutive_failures: u32,
+
+    /// Polls (each may spawn an osascript or network call) allowed to run at once
+    /// across all watchers. Polls over the cap wait for a free slot rather than
+    /// being skipped. 0 means no cap.
+    pub max_concurrent_polls: usize,
 }
 
 impl Default for WatcherConfig {
@@ -48,7 +75,146 @@ impl Default for WatcherConfig {
             min_poll_interval_secs: 10,
             active_hours: None,
             enforce_active_hours: false,
+            history_retention_days: 30,
+            jitter_fraction: 0.0,
+            shutdown_timeout_secs: 10,
+            max_consecutive_failures: 0,
+            max_concurrent_polls: 4,
+        }
+    }
+}
+
+/// Counters for tuning `max_concurrent_polls`
+#[derive(Debug, Default)]
+pub struct WatcherMetrics {
+    polls_started: AtomicU64,
+    polls_queued: AtomicU64,
+}
+
+impl WatcherMetrics {
+    /// Polls that have run (or are running) across all watchers
+    pub fn polls_started(&self) -> u64 {
+        self.polls_started.load(Ordering::Relaxed)
+    }
+
+    /// Polls that had to wait for a free slot because `max_concurrent_polls` were running
+    pub fn polls_queued(&self) -> u64 {
+        self.polls_queued.load(Ordering::Relaxed)
+    }
+}
+
+/// Caps how many polls run at once, shared by every watcher of a runner
+struct PollGate {
+    /// `None` when `max_concurrent_polls` is 0 (no cap)
+    permits: Option<Semaphore>,
+    metrics: Arc<WatcherMetrics>,
+}
+
+impl PollGate {
+    fn new(max_concurrent_polls: usize) -> Self {
+        Self {
+            permits: (max_concurrent_polls > 0).then(|| Semaphore::new(max_concurrent_polls)),
+            metrics: Arc::new(WatcherMetrics::default()),
+        }
+    }
+
+    /// Wait for a slot to poll in. The slot is held until the permit is dropped.
+    async fn acquire(&self, watcher_id: &str) -> Option<SemaphorePermit<'_>> {
+        self.metrics.polls_started.fetch_add(1, Ordering::Relaxed);
+        let permits = self.permits.as_ref()?;
+        if let Ok(permit) = permits.try_acquire() {
+            return Some(permit);
+        }
+        self.metrics.polls_queued.fetch_add(1, Ordering::Relaxed);
+        debug!("Watcher {} waiting for a free poll slot", watcher_id);
+        // The semaphore is never closed, so this only fails if that changes
+        permits.acquire().await.ok()
+    }
+}
+
+/// Compute a polling delay of `base ± offset`, where `offset` is drawn from
+/// `0..base * jitter_fraction`. A fraction of 0.0 returns `base` unchanged.
+fn jittered_interval(base: Duration, jitter_fraction: f64) -> Duration {
+    let fraction = jitter_fraction.clamp(0.0, 1.0);
+    if fraction == 0.0 {
+        return base;
+    }
+    let spread = base.as_secs_f64() * fraction;
+    let offset = rand::thread_rng().gen_range(-spread..=spread);
+    // Never poll more often than once a second, even at full jitter
+    Duration::from_secs_f64((base.as_secs_f64() + offset).max(1.0))
+}
+
+/// How often a paused one-shot watcher checks whether it has been resumed
+const PAUSED_ONESHOT_RECHECK_SECS: u64 = 30;
+
+/// Minimum time between retention prunes of the execution history
+const HISTORY_PRUNE_INTERVAL_SECS: i64 = 3600;
+
+/// Records watcher executions into the `watcher_executions` audit table
+#[derive(Clone)]
+struct ExecutionLog {
+    db: Arc<std::sync::Mutex<rusqlite::Connection>>,
+    retention_days: u32,
+    /// Unix timestamp of the last retention prune
+    last_prune: Arc<AtomicI64>,
+}
+
+impl ExecutionLog {
+    fn record(&self, watcher_id: &str, outcome: ExecutionOutcome, message: &str) {
+        let Ok(conn) = self.db.lock() else {
+            warn!("Execution log database lock poisoned");
+            return;
+        };
+
+        if let Err(e) = persistence::record_execution(&conn, watcher_id, outcome, message) {
+            warn!(
+                "Failed to record execution for watcher {}: {}",
+                watcher_id, e
+            );
+        }
+
+        let now = Utc::now().timestamp();
+        let last = self.last_prune.load(Ordering::Relaxed);
+        if now - last >= HISTORY_PRUNE_INTERVAL_SECS
+            && self
+                .last_prune
+                .compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+            && let Err(e) = persistence::prune_watcher_executions(&conn, self.retention_days)
+        {
+            warn!("Failed to prune watcher execution history: {}", e);
+        }
+    }
+}
+
+/// Record an execution if the runner has an execution log attached
+fn record_execution(
+    log: &Option<ExecutionLog>,
+    watcher_id: &str,
+    outcome: ExecutionOutcome,
+    message: &str,
+) {
+    if let Some(log) = log {
+        log.record(watcher_id, outcome, message);
+    }
+}
+
+/// Mark an auto-disabled watcher inactive so it isn't restarted on the next launch
+fn deactivate_persisted(
+    db: &Option<Arc<std::sync::Mutex<rusqlite::Connection>>>,
+    watcher_id: &str,
+) {
+    let Some(db) = db else {
+        return;
+    };
+    match db.lock() {
+        Ok(conn) => {
+            if let Err(e) = persistence::deactivate_watcher(&conn, watcher_id) {
+                warn!("Failed to deactivate watcher {}: {}", watcher_id, e);
+            }
         }
+        Err(_) => warn!("Watcher state database lock poisoned"),
     }
 }
 
@@ -63,8 +229,32 @@ pub struct WatcherRunner {
     /// Active watcher tasks (watcher_id -> CancellationToken)
     active_tasks: Arc<RwLock<HashMap<String, CancellationToken>>>,
 
+    /// Join handles of spawned watcher tasks, awaited on shutdown
+    task_handles: Arc<RwLock<HashMap<String, JoinHandle<()>>>>,
+
+    /// IDs of running watchers that are paused (task alive, but not firing)
+    paused: Arc<RwLock<HashSet<String>>>,
+
     /// Global shutdown token
     shutdown_token: CancellationToken,
+
+    /// Optional audit log of watcher executions
+    execution_log: Option<ExecutionLog>,
+
+    /// Optional database for polling state that must survive restarts
+    state_db: Option<Arc<std::sync::Mutex<rusqlite::Connection>>>,
+
+    /// Receives operational events (started, failing, auto-disabled, ...)
+    lifecycle: Arc<dyn WatcherLifecycleListener>,
+
+    /// Mailbox polled by email watchers instead of Mail.app
+    email_source: Option<Arc<dyn EmailSource>>,
+
+    /// Calendars polled by calendar watchers
+    calendar_source: Option<Arc<dyn CalendarSource>>,
+
+    /// Limits concurrent polls across all watchers and counts queued ones
+    poll_gate: Arc<PollGate>,
 }
 
 impl WatcherRunner {
@@ -79,13 +269,57 @@ impl WatcherRunner {
         config: WatcherConfig,
     ) -> Self {
         Self {
-            config,
             event_tx,
             active_tasks: Arc::new(RwLock::new(HashMap::new())),
+            task_handles: Arc::new(RwLock::new(HashMap::new())),
+            paused: Arc::new(RwLock::new(HashSet::new())),
             shutdown_token: CancellationToken::new(),
+            execution_log: None,
+            state_db: None,
+            lifecycle: Arc::new(LoggingLifecycleListener),
+            email_source: None,
+            calendar_source: None,
+            poll_gate: Arc::new(PollGate::new(config.max_concurrent_polls)),
+            config,
         }
     }
 
+    /// Record every watcher execution into the `watcher_executions` table of `db`
+    pub fn with_execution_db(mut self, db: Arc<std::sync::Mutex<rusqlite::Connection>>) -> Self {
+        self.execution_log = Some(ExecutionLog {
+            db,
+            retention_days: self.config.history_retention_days,
+            last_prune: Arc::new(AtomicI64::new(0)),
+        });
+        self
+    }
+
+    /// Persist polling state (such as seen feed entries) and watcher state variables in `db`
+    /// so restarts don't re-emit items or lose state. Auto-disabled watchers are also
+    /// marked inactive there.
+    pub fn with_state_db(mut self, db: Arc<std::sync::Mutex<rusqlite::Connection>>) -> Self {
+        self.state_db = Some(db);
+        self
+    }
+
+    /// Report watcher lifecycle events to `listener` instead of just logging them
+    pub fn with_lifecycle_listener(mut self, listener: Arc<dyn WatcherLifecycleListener>) -> Self {
+        self.lifecycle = listener;
+        self
+    }
+
+    /// Poll `source` for email watchers instead of Mail.app (which is macOS-only)
+    pub fn with_email_source(mut self, source: Arc<dyn EmailSource>) -> Self {
+        self.email_source = Some(source);
+        self
+    }
+
+    /// Poll `source` for calendar watchers; without one they are skipped
+    pub fn with_calendar_source(mut self, source: Arc<dyn CalendarSource>) -> Self {
+        self.calendar_source = Some(source);
+        self
+    }
+
     /// Start a watcher
     pub async fn start_watcher(&self, watcher: Watcher) -> Result<()> {
         // Check if we've reached max concurrent watchers
@@ -118,16 +352,19 @@ impl WatcherRunner {
             .await
             .insert(watcher.id.clone(), token.clone());
 
+        if watcher.paused {
+            self.paused.write().await.insert(watcher.id.clone());
+        }
+
         // Spawn the appropriate task based on watcher kind
-        match &watcher.kind {
+        let watcher_id = watcher.id.clone();
+        let started = watcher.clone();
+        let handle = match &watcher.kind {
             WatcherKind::EmailWatch { .. }
             | WatcherKind::CalendarWatch { .. }
-            | WatcherKind::GitHubWatch { .. } => {
-                self.spawn_polling_watcher(watcher, token).await?;
-            }
-            WatcherKind::FileWatch { .. } => {
-                self.spawn_file_watcher(watcher, token).await?;
-            }
+            | WatcherKind::GitHubWatch { .. }
+            | WatcherKind::RssWatch { .. } => self.spawn_polling_watcher(watcher, token).await?,
+            WatcherKind::FileWatch { .. } => self.spawn_file_watcher(watcher, token).await?,
             WatcherKind::MessageWatch { .. } => {
                 // Message watchers are handled externally by the message handler
                 // We just track that they're active
@@ -135,25 +372,33 @@ impl WatcherRunner {
                     "Message watcher {} registered (handled externally)",
                     watcher.id
                 );
+                self.lifecycle.on_started(&watcher);
+                return Ok(());
             }
-            WatcherKind::Scheduled { .. } => {
-                self.spawn_scheduled_watcher(watcher, token).await?;
-            }
-            WatcherKind::OneShot { .. } => {
-                self.spawn_oneshot_watcher(watcher, token).await?;
-            }
-        }
+            WatcherKind::Scheduled { .. } => self.spawn_scheduled_watcher(watcher, token).await?,
+            WatcherKind::OneShot { .. } => self.spawn_oneshot_watcher(watcher, token).await?,
+        };
 
+        let mut handles = self.task_handles.write().await;
+        handles.retain(|_, h| !h.is_finished());
+        handles.insert(watcher_id, handle);
+        drop(handles);
+
+        self.lifecycle.on_started(&started);
         Ok(())
     }
 
     /// Stop a specific watcher
+    ///
+    /// Only this watcher's task is cancelled; it exits after its current cycle.
     pub async fn stop_watcher(&self, id: &str) -> Result<bool> {
         let mut tasks = self.active_tasks.write().await;
 
         if let Some(token) = tasks.remove(id) {
             info!("Stopping watcher: {}", id);
             token.cancel();
+            self.paused.write().await.remove(id);
+            self.lifecycle.on_stopped(id);
             Ok(true)
         } else {
             warn!("Attempted to stop non-running watcher: {}", id);
@@ -161,6 +406,41 @@ impl WatcherRunner {
         }
     }
 
+    /// Apply an edited watcher (matched by id) without touching other watchers.
+    ///
+    /// The edit is persisted with [`persistence::update_watcher`] when a state
+    /// database is set, which fails for unknown ids. A running task is then
+    /// stopped, given `shutdown_timeout_secs` to finish its current cycle, and
+    /// started again with the new definition unless the watcher is now inactive.
+    pub async fn update_watcher(&self, watcher: Watcher) -> Result<()> {
+        match &self.state_db {
+            Some(db) => {
+                let conn = db
+                    .lock()
+                    .map_err(|_| anyhow::anyhow!("Watcher state database lock poisoned"))?;
+                persistence::update_watcher(&conn, &watcher)?;
+            }
+            None => watcher.validate()?,
+        }
+
+        if self.stop_watcher(&watcher.id).await? {
+            let handle = self.task_handles.write().await.remove(&watcher.id);
+            if let Some(mut handle) = handle {
+                let timeout = Duration::from_secs(self.config.shutdown_timeout_secs);
+                if tokio::time::timeout(timeout, &mut handle).await.is_err() {
+                    warn!("Watcher {} did not stop in time, aborting", watcher.id);
+                    handle.abort();
+                }
+            }
+        }
+
+        info!("Reloading watcher {} after update", watcher.id);
+        if watcher.active {
+            self.start_watcher(watcher).await?;
+        }
+        Ok(())
+    }
+
     /// Stop all watchers
     pub async fn stop_all(&self) {
         info!("Stopping all watchers");
@@ -174,10 +454,40 @@ impl WatcherRunner {
             debug!("Cancelling watcher: {}", id);
             token.cancel();
         }
+        self.paused.write().await.clear();
 
         info!("All watchers stopped");
     }
 
+    /// Stop all watchers and wait for their tasks to exit
+    ///
+    /// Each task gets `shutdown_timeout_secs` to finish its current cycle
+    /// (e.g. a running osascript), after which stragglers are aborted.
+    pub async fn shutdown(self) {
+        self.shutdown_and_wait().await
+    }
+
+    /// Like `shutdown`, for runners that are shared and can't be consumed
+    pub async fn shutdown_and_wait(&self) {
+        self.stop_all().await;
+
+        let handles: Vec<(String, JoinHandle<()>)> =
+            self.task_handles.write().await.drain().collect();
+        let deadline = Instant::now() + Duration::from_secs(self.config.shutdown_timeout_secs);
+
+        for (id, mut handle) in handles {
+            if tokio::time::timeout_at(deadline, &mut handle)
+                .await
+                .is_err()
+            {
+                warn!("Watcher {} did not stop in time, aborting", id);
+                handle.abort();
+            }
+        }
+
+        info!("Watcher runner shut down");
+    }
+
     /// Get the number of active watchers
     pub async fn active_count(&self) -> usize {
         self.active_tasks.read().await.len()
@@ -188,28 +498,98 @@ impl WatcherRunner {
         self.active_tasks.read().await.contains_key(id)
     }
 
+    /// Pause a running watcher without tearing down its task or dedup state
+    ///
+    /// Returns false if the watcher isn't running.
+    pub async fn pause_watcher(&self, id: &str) -> bool {
+        if !self.is_running(id).await {
+            warn!("Attempted to pause non-running watcher: {}", id);
+            return false;
+        }
+        info!("Pausing watcher: {}", id);
+        self.paused.write().await.insert(id.to_string());
+        self.lifecycle.on_paused(id);
+        true
+    }
+
+    /// Resume a paused watcher
+    ///
+    /// Returns false if the watcher wasn't paused.
+    pub async fn resume_watcher(&self, id: &str) -> bool {
+        let resumed = self.paused.write().await.remove(id);
+        if resumed {
+            info!("Resuming watcher: {}", id);
+        } else {
+            warn!("Attempted to resume watcher that isn't paused: {}", id);
+        }
+        resumed
+    }
+
+    /// Poll counters, including how often polls waited for a free slot
+    pub fn metrics(&self) -> Arc<WatcherMetrics> {
+        self.poll_gate.metrics.clone()
+    }
+
+    /// Check if a watcher is currently paused
+    pub async fn is_paused(&self, id: &str) -> bool {
+        self.paused.read().await.contains(id)
+    }
+
+    /// Read a state variable scoped to watcher `id`
+    pub fn get_state(&self, id: &str, key: &str) -> Result<Option<serde_json::Value>> {
+        let db = self
+            .state_db
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Watcher state requires a state database"))?;
+        let conn = db
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Watcher state database lock poisoned"))?;
+        persistence::get_watcher_state(&conn, id, key)
+    }
+
+    /// Set a state variable scoped to watcher `id`, persisting it across fires and restarts
+    pub fn set_state(&self, id: &str, key: &str, value: serde_json::Value) -> Result<()> {
+        let db = self
+            .state_db
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Watcher state requires a state database"))?;
+        let conn = db
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Watcher state database lock poisoned"))?;
+        persistence::set_watcher_state(&conn, id, key, &value)
+    }
+
     /// Spawn a polling-based watcher task
     async fn spawn_polling_watcher(
         &self,
         watcher: Watcher,
         cancel_token: CancellationToken,
-    ) -> Result<()> {
+    ) -> Result<JoinHandle<()>> {
         let event_tx = self.event_tx.clone();
         let config = self.config.clone();
         let global_shutdown = self.shutdown_token.clone();
         let active_tasks = self.active_tasks.clone();
-
-        tokio::spawn(async move {
+        let paused = self.paused.clone();
+        let execution_log = self.execution_log.clone();
+        let state_db = self.state_db.clone();
+        let lifecycle = self.lifecycle.clone();
+        let email_source = self.email_source.clone();
+        let calendar_source = self.calendar_source.clone();
+        let poll_gate = self.poll_gate.clone();
+
+        let handle = tokio::spawn(async move {
             let interval_secs = match &watcher.kind {
                 WatcherKind::EmailWatch { interval_secs, .. } => *interval_secs,
                 WatcherKind::CalendarWatch { interval_secs, .. } => *interval_secs,
                 WatcherKind::GitHubWatch { interval_secs, .. } => *interval_secs,
+                WatcherKind::RssWatch { interval_secs, .. } => *interval_secs,
                 _ => unreachable!(),
             };
 
             // Enforce minimum interval
             let interval_secs = interval_secs.max(config.min_poll_interval_secs);
-            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+            let base_interval = Duration::from_secs(interval_secs);
+            let mut interval = tokio::time::interval(base_interval);
             interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
             debug!(
@@ -218,6 +598,10 @@ impl WatcherRunner {
             );
 
             let mut poll_state = PollState::new();
+            poll_state.state_db = state_db.clone();
+            poll_state.email_source = email_source;
+            poll_state.calendar_source = calendar_source;
+            let mut consecutive_failures: u32 = 0;
 
             loop {
                 tokio::select! {
@@ -230,6 +614,16 @@ impl WatcherRunner {
                         break;
                     }
                     _ = interval.tick() => {
+                        // Re-roll the jitter every cycle so watchers drift apart
+                        if config.jitter_fraction > 0.0 {
+                            interval.reset_after(jittered_interval(base_interval, config.jitter_fraction));
+                        }
+
+                        if paused.read().await.contains(&watcher.id) {
+                            debug!("Watcher {} is paused, skipping poll", watcher.id);
+                            continue;
+                        }
+
                         // Check active hours
                         if config.enforce_active_hours
                             && let Some((start, end)) = config.active_hours
@@ -247,119 +641,220 @@ impl WatcherRunner {
                             }
                         }
 
+                        // Wait for a free poll slot, still honouring stop requests
+                        let permit = tokio::select! {
+                            permit = poll_gate.acquire(&watcher.id) => permit,
+                            _ = cancel_token.cancelled() => {
+                                info!("Watcher {} cancelled", watcher.id);
+                                break;
+                            }
+                            _ = global_shutdown.cancelled() => {
+                                info!("Watcher {} stopped due to global shutdown", watcher.id);
+                                break;
+                            }
+                        };
+
                         // Execute the poll
-                        if let Err(e) = poll_watcher(&watcher, &event_tx, &mut poll_state).await {
-                            error!("Error polling watcher {}: {}", watcher.id, e);
+                        let result = poll_watcher(&watcher, &event_tx, &mut poll_state).await;
+                        drop(permit);
+                        match result {
+                            Ok(emitted) => {
+                                consecutive_failures = 0;
+                                let detail = format!("{} new item(s)", emitted);
+                                if emitted > 0 {
+                                    lifecycle.on_fired(&watcher.id, &detail);
+                                }
+                                record_execution(
+                                    &execution_log,
+                                    &watcher.id,
+                                    ExecutionOutcome::Success,
+                                    &detail,
+                                );
+                            }
+                            Err(e) => {
+                                error!("Error polling watcher {}: {}", watcher.id, e);
+                                consecutive_failures += 1;
+                                lifecycle.on_error(
+                                    &watcher.id,
+                                    &e.to_string(),
+                                    consecutive_failures,
+                                );
+                                record_execution(
+                                    &execution_log,
+                                    &watcher.id,
+                                    ExecutionOutcome::Failure,
+                                    &e.to_string(),
+                                );
+
+                                if config.max_consecutive_failures > 0
+                                    && consecutive_failures >= config.max_consecutive_failures
+                                {
+                                    let reason = format!(
+                                        "{} consecutive failures, last: {}",
+                                        consecutive_failures, e
+                                    );
+                                    deactivate_persisted(&state_db, &watcher.id);
+                                    lifecycle.on_auto_disabled(&watcher.id, &reason);
+                                    break;
+                                }
+                            }
                         }
                     }
                 }
             }
 
-            // Clean up - idempotent, entry may already be removed by stop_watcher()
-            let mut tasks = active_tasks.write().await;
-            if tasks.remove(&watcher.id).is_some() {
+            if forget_task(&active_tasks, &paused, &watcher.id).await {
                 debug!(
                     "Polling watcher {} cleaned up from active tasks",
                     watcher.id
                 );
             }
-            drop(tasks);
             debug!("Polling watcher {} task ended", watcher.id);
         });
 
-        Ok(())
+        Ok(handle)
     }
 
     /// Spawn a file watcher task
+    ///
+    /// Changes arrive as OS events via `notify`. If the path doesn't exist yet,
+    /// its nearest existing ancestor is watched until the path appears (and
+    /// again if it is later deleted).
     async fn spawn_file_watcher(
         &self,
         watcher: Watcher,
         cancel_token: CancellationToken,
-    ) -> Result<()> {
-        let path = match &watcher.kind {
-            WatcherKind::FileWatch { path } => path.clone(),
+    ) -> Result<JoinHandle<()>> {
+        let (path, recursive) = match &watcher.kind {
+            WatcherKind::FileWatch { path, recursive } => (PathBuf::from(path), *recursive),
             _ => unreachable!(),
         };
         let event_tx = self.event_tx.clone();
         let watcher_id = watcher.id.clone();
         let global_shutdown = self.shutdown_token.clone();
         let active_tasks = self.active_tasks.clone();
-
-        tokio::spawn(async move {
-            // Create a channel for file events
-            let (tx, mut rx) = mpsc::unbounded_channel();
-
-            // Create the file watcher
-            let mut file_watcher: RecommendedWatcher = match notify::recommended_watcher(
-                move |res: Result<Event, notify::Error>| match res {
-                    Ok(event) => {
-                        if tx.send(event).is_err() {
-                            error!("Failed to send file event");
+        let paused = self.paused.clone();
+        let execution_log = self.execution_log.clone();
+        let lifecycle = self.lifecycle.clone();
+
+        let handle = tokio::spawn(async move {
+            // Errors setting up the watch skip straight to clean-up
+            'watch: {
+                // Create a channel for file events
+                let (tx, mut rx) = mpsc::unbounded_channel();
+
+                // Create the file watcher
+                let mut file_watcher: RecommendedWatcher =
+                    match notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
+                        match res {
+                            Ok(event) => {
+                                if tx.send(event).is_err() {
+                                    error!("Failed to send file event");
+                                }
+                            }
+                            Err(e) => error!("File watch error: {:?}", e),
+                        }
+                    }) {
+                        Ok(w) => w,
+                        Err(e) => {
+                            error!(
+                                "Failed to create file watcher for {}: {}",
+                                path.display(),
+                                e
+                            );
+                            break 'watch;
                         }
+                    };
+
+                // Start watching the path, or the closest ancestor that exists
+                let mut watched = match arm_file_watch(&mut file_watcher, &path, recursive, None) {
+                    Ok(watched) => watched,
+                    Err(e) => {
+                        error!("Failed to watch path {}: {}", path.display(), e);
+                        break 'watch;
                     }
-                    Err(e) => error!("File watch error: {:?}", e),
-                },
-            ) {
-                Ok(w) => w,
-                Err(e) => {
-                    error!("Failed to create file watcher for {}: {}", path, e);
-                    return;
-                }
-            };
+                };
 
-            // Start watching
-            if let Err(e) = file_watcher.watch(Path::new(&path), RecursiveMode::Recursive) {
-                error!("Failed to watch path {}: {}", path, e);
-                return;
-            }
+                info!("File watcher started for: {}", path.display());
+
+                loop {
+                    tokio::select! {
+                        _ = cancel_token.cancelled() => {
+                            info!("File watcher {} cancelled", watcher_id);
+                            break;
+                        }
+                        _ = global_shutdown.cancelled() => {
+                            info!("File watcher {} stopped due to global shutdown", watcher_id);
+                            break;
+                        }
+                        Some(event) = rx.recv() => {
+                            debug!("File event for {}: {:?}", watcher_id, event);
+
+                            // Waiting for the path to appear, or it just went away: re-arm
+                            let target_removed = matches!(event.kind, notify::EventKind::Remove(_))
+                                && event.paths.iter().any(|p| p == &path);
+                            if watched != path || target_removed {
+                                match arm_file_watch(&mut file_watcher, &path, recursive, Some(&watched)) {
+                                    Ok(now_watched) => watched = now_watched,
+                                    Err(e) => warn!("Failed to re-arm file watcher {}: {}", watcher_id, e),
+                                }
+                            }
 
-            info!("File watcher started for: {}", path);
+                            let change_kind = match event.kind {
+                                notify::EventKind::Create(_) => FileChangeKind::Created,
+                                notify::EventKind::Modify(_) => FileChangeKind::Modified,
+                                notify::EventKind::Remove(_) => FileChangeKind::Deleted,
+                                _ => continue,
+                            };
 
-            loop {
-                tokio::select! {
-                    _ = cancel_token.cancelled() => {
-                        info!("File watcher {} cancelled", watcher_id);
-                        break;
-                    }
-                    _ = global_shutdown.cancelled() => {
-                        info!("File watcher {} stopped due to global shutdown", watcher_id);
-                        break;
-                    }
-                    Some(event) = rx.recv() => {
-                        debug!("File event for {}: {:?}", watcher_id, event);
-
-                        let change_type = match event.kind {
-                            notify::EventKind::Create(_) => "created",
-                            notify::EventKind::Modify(_) => "modified",
-                            notify::EventKind::Remove(_) => "removed",
-                            _ => "changed",
-                        };
+                            // Only report the watched path and what's inside it
+                            let paths: Vec<PathBuf> = event
+                                .paths
+                                .into_iter()
+                                .filter(|p| p.starts_with(&path))
+                                .collect();
+                            if paths.is_empty() {
+                                continue;
+                            }
 
-                        for path in event.paths {
-                            let watcher_event = WatcherEvent::file_changed(
-                                watcher_id.clone(),
-                                path.to_string_lossy().to_string(),
-                                change_type.to_string(),
-                            );
+                            if paused.read().await.contains(&watcher_id) {
+                                debug!("File watcher {} is paused, dropping event", watcher_id);
+                                continue;
+                            }
 
-                            if let Err(e) = event_tx.send(watcher_event) {
-                                error!("Failed to send watcher event: {}", e);
+                            let path_count = paths.len();
+                            for changed in paths {
+                                let watcher_event = WatcherEvent::file_changed(
+                                    watcher_id.clone(),
+                                    changed.to_string_lossy().to_string(),
+                                    change_kind,
+                                );
+
+                                if let Err(e) = event_tx.send(watcher_event) {
+                                    error!("Failed to send watcher event: {}", e);
+                                }
                             }
+
+                            let detail = format!("{} path(s) {}", path_count, change_kind);
+                            lifecycle.on_fired(&watcher_id, &detail);
+                            record_execution(
+                                &execution_log,
+                                &watcher_id,
+                                ExecutionOutcome::Success,
+                                &detail,
+                            );
                         }
                     }
                 }
             }
 
-            // Clean up - idempotent, entry may already be removed by stop_watcher()
-            let mut tasks = active_tasks.write().await;
-            if tasks.remove(&watcher_id).is_some() {
+            if forget_task(&active_tasks, &paused, &watcher_id).await {
                 debug!("File watcher {} cleaned up from active tasks", watcher_id);
             }
-            drop(tasks);
             debug!("File watcher {} task ended", watcher_id);
         });
 
-        Ok(())
+        Ok(handle)
     }
 
     /// Spawn a scheduled (cron) watcher task
@@ -367,7 +862,7 @@ impl WatcherRunner {
         &self,
         watcher: Watcher,
         cancel_token: CancellationToken,
-    ) -> Result<()> {
+    ) -> Result<JoinHandle<()>> {
         let (cron_expr, task) = match &watcher.kind {
             WatcherKind::Scheduled { cron_expr, task } => (cron_expr.clone(), task.clone()),
             _ => unreachable!(),
@@ -382,8 +877,11 @@ impl WatcherRunner {
         let task_name = task.clone();
         let global_shutdown = self.shutdown_token.clone();
         let active_tasks = self.active_tasks.clone();
+        let paused = self.paused.clone();
+        let execution_log = self.execution_log.clone();
+        let lifecycle = self.lifecycle.clone();
 
-        tokio::spawn(async move {
+        let handle = tokio::spawn(async move {
             info!("Scheduled watcher {} started: {}", watcher_id, cron_expr);
 
             loop {
@@ -415,6 +913,11 @@ impl WatcherRunner {
                         break;
                     }
                     _ = sleep_until(wake_time) => {
+                        if paused.read().await.contains(&watcher_id) {
+                            debug!("Scheduled watcher {} is paused, skipping run", watcher_id);
+                            continue;
+                        }
+
                         // Execute the task
                         let watcher_event = WatcherEvent::task(
                             watcher_id.clone(),
@@ -423,26 +926,38 @@ impl WatcherRunner {
 
                         if let Err(e) = event_tx.send(watcher_event) {
                             error!("Failed to send scheduled task event: {}", e);
+                            lifecycle.on_error(&watcher_id, &e.to_string(), 1);
+                            record_execution(
+                                &execution_log,
+                                &watcher_id,
+                                ExecutionOutcome::Failure,
+                                &e.to_string(),
+                            );
                         } else {
                             info!("Scheduled task '{}' triggered", task_name);
+                            let detail = format!("Triggered task '{}'", task_name);
+                            lifecycle.on_fired(&watcher_id, &detail);
+                            record_execution(
+                                &execution_log,
+                                &watcher_id,
+                                ExecutionOutcome::Success,
+                                &detail,
+                            );
                         }
                     }
                 }
             }
 
-            // Clean up - idempotent, entry may already be removed by stop_watcher()
-            let mut tasks = active_tasks.write().await;
-            if tasks.remove(&watcher_id).is_some() {
+            if forget_task(&active_tasks, &paused, &watcher_id).await {
                 debug!(
                     "Scheduled watcher {} cleaned up from active tasks",
                     watcher_id
                 );
             }
-            drop(tasks);
             debug!("Scheduled watcher {} task ended", watcher_id);
         });
 
-        Ok(())
+        Ok(handle)
     }
 
     /// Spawn a one-shot watcher task
@@ -450,7 +965,7 @@ impl WatcherRunner {
         &self,
         watcher: Watcher,
         cancel_token: CancellationToken,
-    ) -> Result<()> {
+    ) -> Result<JoinHandle<()>> {
         let (target_time, task_name) = match &watcher.kind {
             WatcherKind::OneShot { at, task } => (*at, task.clone()),
             _ => unreachable!(),
@@ -459,8 +974,20 @@ impl WatcherRunner {
         let watcher_id = watcher.id.clone();
         let global_shutdown = self.shutdown_token.clone();
         let active_tasks = self.active_tasks.clone();
+        let paused = self.paused.clone();
+        let execution_log = self.execution_log.clone();
+        let lifecycle = self.lifecycle.clone();
+
+        let handle = tokio::spawn(async move {
+            // A paused one-shot defers until resumed rather than being skipped
+            while paused.read().await.contains(&watcher_id) {
+                tokio::select! {
+                    _ = cancel_token.cancelled() => return,
+                    _ = global_shutdown.cancelled() => return,
+                    _ = tokio::time::sleep(Duration::from_secs(PAUSED_ONESHOT_RECHECK_SECS)) => {}
+                }
+            }
 
-        tokio::spawn(async move {
             let now = Utc::now();
 
             if target_time <= now {
@@ -473,11 +1000,25 @@ impl WatcherRunner {
 
                 if let Err(e) = event_tx.send(watcher_event) {
                     error!("Failed to send one-shot task event: {}", e);
+                    lifecycle.on_error(&watcher_id, &e.to_string(), 1);
+                    record_execution(
+                        &execution_log,
+                        &watcher_id,
+                        ExecutionOutcome::Failure,
+                        &e.to_string(),
+                    );
+                } else {
+                    let detail = format!("Triggered task '{}' (late)", task_name);
+                    lifecycle.on_fired(&watcher_id, &detail);
+                    record_execution(
+                        &execution_log,
+                        &watcher_id,
+                        ExecutionOutcome::Success,
+                        &detail,
+                    );
                 }
 
-                // Clean up - idempotent, entry may already be removed by stop_watcher()
-                let mut tasks = active_tasks.write().await;
-                if tasks.remove(&watcher_id).is_some() {
+                if forget_task(&active_tasks, &paused, &watcher_id).await {
                     debug!(
                         "One-shot watcher {} cleaned up from active tasks (immediate execution)",
                         watcher_id
@@ -504,6 +1045,15 @@ impl WatcherRunner {
                     info!("One-shot watcher {} stopped due to global shutdown", watcher_id);
                 }
                 _ = sleep_until(wake_time) => {
+                    // Defer while paused
+                    while paused.read().await.contains(&watcher_id) {
+                        tokio::select! {
+                            _ = cancel_token.cancelled() => return,
+                            _ = global_shutdown.cancelled() => return,
+                            _ = tokio::time::sleep(Duration::from_secs(PAUSED_ONESHOT_RECHECK_SECS)) => {}
+                        }
+                    }
+
                     // Execute the task
                     let watcher_event = WatcherEvent::task(
                         watcher_id.clone(),
@@ -512,292 +1062,640 @@ impl WatcherRunner {
 
                     if let Err(e) = event_tx.send(watcher_event) {
                         error!("Failed to send one-shot task event: {}", e);
+                        lifecycle.on_error(&watcher_id, &e.to_string(), 1);
+                        record_execution(
+                            &execution_log,
+                            &watcher_id,
+                            ExecutionOutcome::Failure,
+                            &e.to_string(),
+                        );
                     } else {
                         info!("One-shot task '{}' triggered", task_name);
+                        let detail = format!("Triggered task '{}'", task_name);
+                        lifecycle.on_fired(&watcher_id, &detail);
+                        record_execution(
+                            &execution_log,
+                            &watcher_id,
+                            ExecutionOutcome::Success,
+                            &detail,
+                        );
                     }
                 }
             }
 
-            // Clean up - idempotent, entry may already be removed by stop_watcher()
-            let mut tasks = active_tasks.write().await;
-            if tasks.remove(&watcher_id).is_some() {
+            if forget_task(&active_tasks, &paused, &watcher_id).await {
                 debug!(
                     "One-shot watcher {} cleaned up from active tasks",
                     watcher_id
                 );
             }
-            drop(tasks);
             debug!("One-shot watcher {} task ended", watcher_id);
         });
 
-        Ok(())
+        Ok(handle)
     }
 }
 
 /// State maintained across poll cycles for dedup
 struct PollState {
-    /// Hashes of previously seen items (emails, calendar events) - bounded LRU cache
-    #[cfg(target_os = "macos")]
+    /// Hashes of previously seen items (emails, calendar events, keyed feed items) - bounded LRU cache
     seen_hashes: LruCache<u64, ()>,
     /// Last GitHub event ID seen
     last_github_event_id: Option<String>,
+    /// Unix timestamp until which GitHub polls are skipped to respect the rate limit
+    github_backoff_until: Option<i64>,
+    /// Whether the unauthenticated-GitHub warning has been logged
+    warned_unauthenticated: bool,
+    /// Seen entries and cache validators of a feed watcher, or seen message ids
+    /// and reminders of an email or calendar watcher (loaded on first poll)
+    feed: Option<FeedState>,
+    /// Where to persist state that must survive restarts
+    state_db: Option<Arc<std::sync::Mutex<rusqlite::Connection>>>,
+    /// Mailbox for email watchers, when not using Mail.app
+    email_source: Option<Arc<dyn EmailSource>>,
+    /// Calendars for calendar watchers
+    calendar_source: Option<Arc<dyn CalendarSource>>,
 }
 
 impl PollState {
     fn new() -> Self {
         Self {
-            #[cfg(target_os = "macos")]
             seen_hashes: LruCache::new(NonZeroUsize::new(10_000).unwrap()),
             last_github_event_id: None,
+            github_backoff_until: None,
+            warned_unauthenticated: false,
+            feed: None,
+            state_db: None,
+            email_source: None,
+            calendar_source: None,
+        }
+    }
+
+    /// Take the feed state for a poll, loading it from the database the first time
+    fn take_feed_state(&mut self, watcher_id: &str) -> FeedState {
+        if let Some(feed) = self.feed.take() {
+            return feed;
+        }
+
+        self.state_db
+            .as_ref()
+            .and_then(|db| {
+                let conn = db.lock().ok()?;
+                persistence::get_feed_state(&conn, watcher_id)
+                    .map_err(|e| warn!("Failed to load feed state for {}: {}", watcher_id, e))
+                    .ok()
+                    .flatten()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Keep the feed state for the next poll and persist it
+    fn store_feed_state(&mut self, watcher_id: &str, feed: FeedState) {
+        if let Some(db) = &self.state_db
+            && let Ok(conn) = db.lock()
+            && let Err(e) = persistence::save_feed_state(&conn, watcher_id, &feed)
+        {
+            warn!("Failed to save feed state for {}: {}", watcher_id, e);
+        }
+        self.feed = Some(feed);
+    }
+
+    /// Record an email or calendar reminder as seen, returning true the first time.
+    /// Unlike [`Self::mark_seen`], seen keys are persisted so restarts don't repeat them.
+    fn mark_seen_persistent(&mut self, watcher_id: &str, key: &str) -> bool {
+        let mut seen = self.take_feed_state(watcher_id);
+        if seen.has_seen(key) {
+            self.feed = Some(seen);
+            return false;
         }
+        seen.mark_seen(key);
+        self.store_feed_state(watcher_id, seen);
+        true
     }
 
-    #[cfg(target_os = "macos")]
     fn hash_item(s: &str) -> u64 {
         let mut hasher = std::collections::hash_map::DefaultHasher::new();
         s.hash(&mut hasher);
         hasher.finish()
     }
+
+    /// Record a dedup key, returning true if it hasn't been seen before
+    fn mark_seen(&mut self, key: &str) -> bool {
+        let hash = Self::hash_item(key);
+        if self.seen_hashes.get(&hash).is_some() {
+            return false;
+        }
+        self.seen_hashes.put(hash, ());
+        true
+    }
 }
 
-/// Poll a watcher for new events
-async fn poll_watcher(
-    watcher: &Watcher,
-    event_tx: &mpsc::UnboundedSender<WatcherEvent>,
-    state: &mut PollState,
-) -> Result<()> {
-    match &watcher.kind {
-        WatcherKind::EmailWatch {
-            from,
-            subject_contains,
-            ..
-        } => {
-            #[cfg(not(target_os = "macos"))]
-            {
-                let _ = (from, subject_contains, event_tx, state);
-                warn!(
-                    "Email watcher {} skipped — email watcher polling is macOS-only (use read_emails tool on Windows instead)",
-                    watcher.id
-                );
-                return Ok(());
-            }
+/// Forget a watcher whose task has ended, including its paused flag, so a later
+/// watcher reusing the id starts clean. Idempotent, as stop_watcher() may have
+/// got there first; returns whether the task was still registered.
+async fn forget_task(
+    active_tasks: &RwLock<HashMap<String, CancellationToken>>,
+    paused: &RwLock<HashSet<String>>,
+    watcher_id: &str,
+) -> bool {
+    paused.write().await.remove(watcher_id);
+    active_tasks.write().await.remove(watcher_id).is_some()
+}
 
-            #[cfg(target_os = "macos")]
-            {
-                debug!(
-                    "Polling email watcher {} (from: {:?}, subject: {:?})",
-                    watcher.id, from, subject_contains
-                );
+/// Closest existing path at or above `path`
+fn nearest_existing_ancestor(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|p| !p.as_os_str().is_empty() && p.exists())
+        .map(Path::to_path_buf)
+}
 
-                let script = r#"
-tell application "Mail"
-    try
-        set msgs to messages 1 thru 20 of inbox
-        set output to ""
-        repeat with m in msgs
-            set output to output & "From: " & (sender of m) & "\n"
-            set output to output & "Subject: " & (subject of m) & "\n"
-            set output to output & "Date: " & (date received of m as string) & "\n"
-            set output to output & "Body: " & (content of m as string) & "\n"
-            set output to output & "---\n"
-        end repeat
-        return output
-    on error errMsg
-        return "Error: " & errMsg
-    end try
-end tell
-"#;
+/// Point `file_watcher` at `target` if it exists, otherwise at its nearest
+/// existing ancestor (non-recursively, just to notice the target appearing).
+/// `current` is what is watched now; returns what is watched afterwards.
+fn arm_file_watch(
+    file_watcher: &mut RecommendedWatcher,
+    target: &Path,
+    recursive: bool,
+    current: Option<&Path>,
+) -> Result<PathBuf> {
+    let (next, mode) = if target.exists() {
+        let mode = if recursive {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        (target.to_path_buf(), mode)
+    } else {
+        let ancestor = nearest_existing_ancestor(target)
+            .with_context(|| format!("No existing ancestor of {}", target.display()))?;
+        (ancestor, RecursiveMode::NonRecursive)
+    };
+
+    if current == Some(next.as_path()) && next.as_path() != target {
+        return Ok(next);
+    }
+    if let Some(current) = current {
+        // The old path may already be gone along with its OS watch
+        let _ = file_watcher.unwatch(current);
+    }
+    file_watcher
+        .watch(&next, mode)
+        .with_context(|| format!("Failed to watch {}", next.display()))?;
 
-                let output = tokio::time::timeout(
-                    std::time::Duration::from_secs(30),
-                    Command::new("osascript").arg("-e").arg(script).output(),
-                )
-                .await
-                .map_err(|_| {
-                    anyhow::anyhow!("AppleScript execution timed out after 30 seconds")
-                })??;
-
-                if !output.status.success() {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    warn!("Email polling failed: {}", stderr);
-                    return Ok(());
-                }
+    if next.as_path() != target {
+        info!(
+            "{} does not exist yet, watching {} until it appears",
+            target.display(),
+            next.display()
+        );
+    }
+    Ok(next)
+}
 
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                if stdout.starts_with("Error:") {
-                    warn!("Email polling returned error: {}", stdout);
-                    return Ok(());
-                }
+/// Stop polling GitHub once this few requests remain in the rate-limit window
+const GITHUB_RATE_LIMIT_FLOOR: i64 = 5;
+
+/// Back-off used when GitHub reports a low limit without a reset time
+const GITHUB_DEFAULT_BACKOFF_SECS: i64 = 900;
+
+/// Decide whether to pause GitHub polling based on the rate-limit headers,
+/// returning the Unix timestamp to resume at
+fn github_rate_limit_backoff(
+    remaining: Option<i64>,
+    reset_at: Option<i64>,
+    now: i64,
+) -> Option<i64> {
+    let remaining = remaining?;
+    if remaining > GITHUB_RATE_LIMIT_FLOOR {
+        return None;
+    }
+    Some(
+        reset_at
+            .filter(|reset| *reset > now)
+            .unwrap_or(now + GITHUB_DEFAULT_BACKOFF_SECS),
+    )
+}
 
-                for entry in stdout.split("---\n").filter(|e| !e.trim().is_empty()) {
-                    let mut email_from = String::new();
-                    let mut email_subject = String::new();
-                    let mut email_date = String::new();
-                    let mut email_body = String::new();
-
-                    for line in entry.lines() {
-                        if let Some(val) = line.strip_prefix("From: ") {
-                            email_from = val.trim().to_string();
-                        } else if let Some(val) = line.strip_prefix("Subject: ") {
-                            email_subject = val.trim().to_string();
-                        } else if let Some(val) = line.strip_prefix("Date: ") {
-                            email_date = val.trim().to_string();
-                        } else if let Some(val) = line.strip_prefix("Body: ") {
-                            email_body = val.trim().to_string();
-                        }
-                    }
+/// Build events for feed entries not yet in `state`, marking them as seen.
+/// Entries are returned oldest first.
+fn new_feed_events(
+    watcher_id: &str,
+    feed_url: &str,
+    items: &[FeedItem],
+    dedup_key: Option<&DedupKey>,
+    state: &mut FeedState,
+) -> Vec<WatcherEvent> {
+    let mut events = Vec::new();
+
+    // Feeds list newest first
+    for item in items.iter().rev() {
+        let key = DedupKey::key_or_else(dedup_key, item, || item.id.clone());
+        if state.has_seen(&key) {
+            continue;
+        }
+        state.mark_seen(&key);
+
+        events.push(WatcherEvent::feed_item(
+            watcher_id.to_string(),
+            feed_url.to_string(),
+            item.id.clone(),
+            item.title.clone(),
+            item.link.clone(),
+            item.summary.clone(),
+        ));
+    }
 
-                    // Filter by criteria
-                    if let Some(filter_from) = from
-                        && !email_from
-                            .to_lowercase()
-                            .contains(&filter_from.to_lowercase())
-                    {
-                        continue;
-                    }
-                    if let Some(filter_subject) = subject_contains
-                        && !email_subject
-                            .to_lowercase()
-                            .contains(&filter_subject.to_lowercase())
-                    {
+    events
+}
+
+/// Fetch a feed and emit events for new entries, honoring ETag/Last-Modified caching.
+/// On the first poll of a watcher the existing entries are recorded without emitting.
+async fn poll_feed(
+    watcher_id: &str,
+    url: &str,
+    dedup_key: Option<&DedupKey>,
+    allow_private_network: bool,
+    state: &mut FeedState,
+    first_poll: bool,
+    event_tx: &mpsc::UnboundedSender<WatcherEvent>,
+) -> Result<usize> {
+    let fetched = if allow_private_network {
+        feed::fetch_feed(url, state).await
+    } else {
+        feed::fetch_public_feed(url, state).await
+    };
+    let content = match fetched? {
+        FeedFetch::Fetched(content) => content,
+        FeedFetch::NotModified | FeedFetch::Unavailable(_) => return Ok(0),
+    };
+
+    let events = new_feed_events(watcher_id, url, &content.items, dedup_key, state);
+    if first_poll {
+        info!(
+            "Feed watcher {} primed with {} existing entries",
+            watcher_id,
+            events.len()
+        );
+        return Ok(0);
+    }
+
+    let mut emitted = 0;
+    for event in events {
+        if let Err(e) = event_tx.send(event) {
+            error!("Failed to send feed event: {}", e);
+        } else {
+            emitted += 1;
+        }
+    }
+    Ok(emitted)
+}
+
+/// Messages fetched per email watcher poll
+const EMAIL_POLL_LIMIT: usize = 20;
+
+/// Whether an email passes a watcher's sender and subject filters (case-insensitive)
+fn email_matches(email: &PolledEmail, from: Option<&str>, subject_contains: Option<&str>) -> bool {
+    let contains =
+        |haystack: &str, needle: &str| haystack.to_lowercase().contains(&needle.to_lowercase());
+    from.is_none_or(|f| contains(&email.from, f))
+        && subject_contains.is_none_or(|s| contains(&email.subject, s))
+}
+
+/// Cap an email body for the event (char-safe to avoid slicing mid-UTF-8)
+fn email_preview(body: String) -> String {
+    if body.chars().count() > 500 {
+        let truncated: String = body.chars().take(497).collect();
+        format!("{}...", truncated)
+    } else {
+        body
+    }
+}
+
+/// Parse the `Id:/From:/Subject:/Date:` records produced by the header script
+#[cfg(any(target_os = "macos", test))]
+fn parse_email_headers(output: &str) -> Vec<PolledEmail> {
+    output
+        .split("---\n")
+        .filter(|e| !e.trim().is_empty())
+        .map(|entry| {
+            let mut header = PolledEmail::default();
+            for line in entry.lines() {
+                if let Some(val) = line.strip_prefix("Id: ") {
+                    header.message_id = val.trim().to_string();
+                } else if let Some(val) = line.strip_prefix("From: ") {
+                    header.from = val.trim().to_string();
+                } else if let Some(val) = line.strip_prefix("Subject: ") {
+                    header.subject = val.trim().to_string();
+                } else if let Some(val) = line.strip_prefix("Date: ") {
+                    header.date = val.trim().to_string();
+                }
+            }
+            header
+        })
+        .collect()
+}
+
+/// Build a script fetching the bodies of the given messages, one record per
+/// message separated by ASCII record separators (bodies may contain anything)
+#[cfg(any(target_os = "macos", test))]
+fn email_body_script(message_ids: &[&str]) -> String {
+    let ids = message_ids
+        .iter()
+        .map(|id| format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\"")))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        r#"
+tell application "Mail"
+    try
+        set sep to ASCII character 30
+        set output to ""
+        repeat with msgId in {{{}}}
+            set found to (messages of inbox whose message id is (msgId as string))
+            if (count of found) > 0 then
+                set output to output & msgId & "\n" & (content of (item 1 of found) as string) & sep
+            end if
+        end repeat
+        return output
+    on error errMsg
+        return "Error: " & errMsg
+    end try
+end tell
+"#,
+        ids
+    )
+}
+
+/// Parse the output of `email_body_script` into message id -> body
+#[cfg(any(target_os = "macos", test))]
+fn parse_email_bodies(output: &str) -> HashMap<String, String> {
+    output
+        .split('\u{1e}')
+        .filter_map(|record| {
+            let record = record.trim_start_matches('\n');
+            let (id, body) = record.split_once('\n')?;
+            Some((id.trim().to_string(), body.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Run a Mail.app AppleScript, failing if osascript or the script reports an error
+#[cfg(target_os = "macos")]
+async fn run_email_script(script: &str) -> Result<String> {
+    let output = tokio::time::timeout(
+        std::time::Duration::from_secs(30),
+        Command::new("osascript").arg("-e").arg(script).output(),
+    )
+    .await
+    .map_err(|_| anyhow::anyhow!("AppleScript execution timed out after 30 seconds"))??;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow::anyhow!("Email polling failed: {}", stderr.trim()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    if stdout.starts_with("Error:") {
+        return Err(anyhow::anyhow!("Email polling returned {}", stdout.trim()));
+    }
+
+    Ok(stdout)
+}
+
+/// Whether a calendar watcher should remind about `event` at `now`: timed events
+/// from `lead_minutes` before they start until they start, all-day events from
+/// `lead_minutes` before their day begins until it ends
+fn reminder_due(event: &PolledCalendarEvent, lead_minutes: u64, now: DateTime<Utc>) -> bool {
+    let lead = chrono::Duration::try_minutes(lead_minutes.min(i64::MAX as u64) as i64)
+        .unwrap_or(chrono::Duration::MAX);
+    let window_start = event.start.checked_sub_signed(lead);
+    let window_end = if event.all_day {
+        event.start + chrono::Duration::days(1)
+    } else {
+        event.start
+    };
+    window_start.is_none_or(|start| now >= start) && now < window_end
+}
+
+/// Poll a watcher for new events, returning how many events were emitted
+async fn poll_watcher(
+    watcher: &Watcher,
+    event_tx: &mpsc::UnboundedSender<WatcherEvent>,
+    state: &mut PollState,
+) -> Result<usize> {
+    let mut emitted = 0;
+
+    match &watcher.kind {
+        WatcherKind::EmailWatch {
+            from,
+            subject_contains,
+            include_body,
+            dedup_key,
+            ..
+        } => {
+            if let Some(source) = state.email_source.clone() {
+                debug!("Polling email watcher {} via email source", watcher.id);
+                let emails = source
+                    .recent_emails(EMAIL_POLL_LIMIT, *include_body)
+                    .await?;
+                for email in emails {
+                    if !email_matches(&email, from.as_deref(), subject_contains.as_deref()) {
                         continue;
                     }
-
-                    // Dedup - check if we've seen this before
-                    let hash_key = format!("{}|{}|{}", email_from, email_subject, email_date);
-                    let hash = PollState::hash_item(&hash_key);
-                    if state.seen_hashes.get(&hash).is_some() {
+                    let key =
+                        DedupKey::key_or_else(dedup_key.as_ref(), &email, || email.dedup_key());
+                    if !state.mark_seen_persistent(&watcher.id, &key) {
                         continue;
                     }
-                    state.seen_hashes.put(hash, ());
-
-                    // Truncate body for the event (char-safe to avoid slicing mid-UTF-8)
-                    let body_preview = if email_body.chars().count() > 500 {
-                        let truncated: String = email_body.chars().take(497).collect();
-                        format!("{}...", truncated)
-                    } else {
-                        email_body
-                    };
 
                     let event = WatcherEvent::email(
                         watcher.id.clone(),
-                        email_from,
-                        email_subject,
-                        body_preview,
+                        email.from,
+                        email.subject,
+                        email_preview(email.body.unwrap_or_default()),
                     );
-
                     if let Err(e) = event_tx.send(event) {
                         error!("Failed to send email event: {}", e);
+                    } else {
+                        emitted += 1;
                     }
                 }
+                return Ok(emitted);
             }
-        }
-        WatcherKind::CalendarWatch {
-            lookahead_hours, ..
-        } => {
+
             #[cfg(not(target_os = "macos"))]
             {
-                let _ = (lookahead_hours, event_tx, state);
                 warn!(
-                    "Calendar watcher {} skipped — calendar watcher polling is macOS-only (use read_calendar tool on Windows instead)",
+                    "Email watcher {} skipped — without an IMAP account or Outlook, email watcher polling is macOS-only",
                     watcher.id
                 );
-                return Ok(());
+                return Ok(0);
             }
 
             #[cfg(target_os = "macos")]
             {
                 debug!(
-                    "Polling calendar watcher {} (lookahead: {}h)",
-                    watcher.id, lookahead_hours
+                    "Polling email watcher {} (from: {:?}, subject: {:?})",
+                    watcher.id, from, subject_contains
                 );
 
-                let days_ahead = (*lookahead_hours as f64 / 24.0).ceil().max(1.0) as u64;
-                let script = format!(
-                    r#"
-tell application "Calendar"
+                // Headers only: fetching content for every message is what makes
+                // polling a busy inbox slow, so bodies are fetched after matching.
+                let script = r#"
+tell application "Mail"
     try
-        set startDate to current date
-        set endDate to (current date) + ({} * days)
+        set msgs to messages 1 thru 20 of inbox
         set output to ""
-        repeat with cal in calendars
-            set calName to name of cal
-            set theEvents to (every event of cal whose start date is greater than or equal to startDate and start date is less than or equal to endDate)
-            repeat with evt in theEvents
-                set output to output & "Event: " & (summary of evt) & "\n"
-                set output to output & "Start: " & (start date of evt as string) & "\n"
-                set output to output & "End: " & (end date of evt as string) & "\n"
-                set output to output & "---\n"
-            end repeat
+        repeat with m in msgs
+            set output to output & "Id: " & (message id of m) & "\n"
+            set output to output & "From: " & (sender of m) & "\n"
+            set output to output & "Subject: " & (subject of m) & "\n"
+            set output to output & "Date: " & (date received of m as string) & "\n"
+            set output to output & "---\n"
         end repeat
         return output
     on error errMsg
         return "Error: " & errMsg
     end try
 end tell
-"#,
-                    days_ahead
-                );
+"#;
 
-                let output = tokio::time::timeout(
-                    std::time::Duration::from_secs(30),
-                    Command::new("osascript").arg("-e").arg(&script).output(),
-                )
-                .await
-                .map_err(|_| {
-                    anyhow::anyhow!("AppleScript execution timed out after 30 seconds")
-                })??;
-
-                if !output.status.success() {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    warn!("Calendar polling failed: {}", stderr);
-                    return Ok(());
-                }
+                let stdout = run_email_script(script).await?;
 
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                if stdout.starts_with("Error:") {
-                    warn!("Calendar polling returned error: {}", stdout);
-                    return Ok(());
+                let mut matched = Vec::new();
+                for header in parse_email_headers(&stdout) {
+                    // Filter by criteria
+                    if !email_matches(&header, from.as_deref(), subject_contains.as_deref()) {
+                        continue;
+                    }
+
+                    // Dedup - check if we've seen this before
+                    let key =
+                        DedupKey::key_or_else(dedup_key.as_ref(), &header, || header.dedup_key());
+                    if !state.mark_seen_persistent(&watcher.id, &key) {
+                        continue;
+                    }
+
+                    matched.push(header);
                 }
 
-                for entry in stdout.split("---\n").filter(|e| !e.trim().is_empty()) {
-                    let mut event_title = String::new();
-                    let mut event_start = String::new();
+                if matched.is_empty() {
+                    return Ok(emitted);
+                }
 
-                    for line in entry.lines() {
-                        if let Some(val) = line.strip_prefix("Event: ") {
-                            event_title = val.trim().to_string();
-                        } else if let Some(val) = line.strip_prefix("Start: ") {
-                            event_start = val.trim().to_string();
+                // The matched emails are already marked seen, so a failed body fetch
+                // still emits them, just without a preview
+                let mut bodies = HashMap::new();
+                if *include_body {
+                    let ids: Vec<&str> = matched
+                        .iter()
+                        .map(|h| h.message_id.as_str())
+                        .filter(|id| !id.is_empty())
+                        .collect();
+                    if !ids.is_empty() {
+                        match run_email_script(&email_body_script(&ids)).await {
+                            Ok(stdout) => bodies = parse_email_bodies(&stdout),
+                            Err(e) => warn!(
+                                "Email watcher {} couldn't fetch bodies, emitting without them: {}",
+                                watcher.id, e
+                            ),
                         }
                     }
+                }
 
-                    // Dedup - check if we've seen this before
-                    let hash_key = format!("{}|{}", event_title, event_start);
-                    let hash = PollState::hash_item(&hash_key);
-                    if state.seen_hashes.get(&hash).is_some() {
-                        continue;
-                    }
-                    state.seen_hashes.put(hash, ());
+                for header in matched {
+                    let email_body = bodies.remove(&header.message_id).unwrap_or_default();
 
-                    let event = WatcherEvent::calendar(
+                    let event = WatcherEvent::email(
                         watcher.id.clone(),
-                        event_title,
-                        Utc::now(), // Use current time as proxy since AppleScript date parsing is unreliable
+                        header.from,
+                        header.subject,
+                        email_preview(email_body),
                     );
 
                     if let Err(e) = event_tx.send(event) {
-                        error!("Failed to send calendar event: {}", e);
+                        error!("Failed to send email event: {}", e);
+                    } else {
+                        emitted += 1;
                     }
                 }
             }
         }
+        WatcherKind::CalendarWatch {
+            lead_minutes,
+            calendar,
+            dedup_key,
+            ..
+        } => {
+            let Some(source) = state.calendar_source.clone() else {
+                warn!(
+                    "Calendar watcher {} skipped — no calendar source configured",
+                    watcher.id
+                );
+                return Ok(0);
+            };
+            debug!(
+                "Polling calendar watcher {} (lead: {}m, calendar: {:?})",
+                watcher.id, lead_minutes, calendar
+            );
+
+            let days_ahead = lead_minutes.div_ceil(24 * 60).max(1);
+            let events = source
+                .upcoming_events(days_ahead, calendar.as_deref())
+                .await?;
+            let now = Utc::now();
+            for event in events {
+                if !reminder_due(&event, *lead_minutes, now) {
+                    continue;
+                }
+                // One reminder per event and start time by default; a rescheduled event re-arms
+                let key =
+                    DedupKey::key_or_else(dedup_key.as_ref(), &event, || event.reminder_key());
+                if !state.mark_seen_persistent(&watcher.id, &key) {
+                    continue;
+                }
+
+                let title = if event.all_day {
+                    format!("{} (all day)", event.title)
+                } else {
+                    event.title
+                };
+                let event = WatcherEvent::calendar(watcher.id.clone(), title, event.start);
+                if let Err(e) = event_tx.send(event) {
+                    error!("Failed to send calendar event: {}", e);
+                } else {
+                    emitted += 1;
+                }
+            }
+        }
         WatcherKind::GitHubWatch {
             repo,
             events,
             github_token,
+            dedup_key,
             ..
         } => {
+            let now = Utc::now().timestamp();
+            if let Some(until) = state.github_backoff_until {
+                if now < until {
+                    debug!(
+                        "GitHub watcher {} backing off for rate limit ({}s left)",
+                        watcher.id,
+                        until - now
+                    );
+                    return Ok(emitted);
+                }
+                state.github_backoff_until = None;
+            }
+
+            if github_token.is_none() && !state.warned_unauthenticated {
+                warn!(
+                    "GitHub watcher {} polls {} without a token; unauthenticated requests are limited to 60/hour",
+                    watcher.id, repo
+                );
+                state.warned_unauthenticated = true;
+            }
+
             debug!(
                 "Polling GitHub watcher {} (repo: {}, events: {:?})",
                 watcher.id, repo, events
@@ -815,13 +1713,31 @@ end tell
             }
             let response = request.send().await?;
 
-            if !response.status().is_success() {
+            let header_num = |name: &str| {
+                response
+                    .headers()
+                    .get(name)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse::<i64>().ok())
+            };
+            let remaining = header_num("x-ratelimit-remaining");
+            let reset_at = header_num("x-ratelimit-reset");
+            if let Some(until) = github_rate_limit_backoff(remaining, reset_at, now) {
                 warn!(
+                    "GitHub rate limit low for watcher {} ({} requests left), pausing polls for {}s",
+                    watcher.id,
+                    remaining.unwrap_or(0),
+                    until - now
+                );
+                state.github_backoff_until = Some(until);
+            }
+
+            if !response.status().is_success() {
+                return Err(anyhow::anyhow!(
                     "GitHub API returned status {} for {}",
                     response.status(),
                     repo
-                );
-                return Ok(());
+                ));
             }
 
             let body: serde_json::Value = response.json().await?;
@@ -840,8 +1756,16 @@ end tell
                     .unwrap_or("")
                     .to_string();
 
-                // Skip if we've already seen this event (compare as u64 since GitHub IDs are numeric strings)
-                if let Some(last_id) = &state.last_github_event_id {
+                if let Some(dedup_key) = dedup_key {
+                    // Configured key: an item is new if its key hasn't been seen
+                    let key = dedup_key
+                        .extract(gh_event)
+                        .unwrap_or_else(|| event_id.clone());
+                    if !state.mark_seen(&key) {
+                        continue;
+                    }
+                } else if let Some(last_id) = &state.last_github_event_id {
+                    // Skip if we've already seen this event (compare as u64 since GitHub IDs are numeric strings)
                     let current: u64 = event_id.parse().unwrap_or(0);
                     let last: u64 = last_id.parse().unwrap_or(0);
                     if current <= last {
@@ -849,15 +1773,9 @@ end tell
                     }
                 }
 
-                // Filter by requested event types (if specified)
-                if !events.is_empty() {
-                    let type_lower = event_type.to_lowercase();
-                    let matches = events
-                        .iter()
-                        .any(|e| type_lower.contains(&e.to_lowercase()));
-                    if !matches {
-                        continue;
-                    }
+                // Filter by subscribed event kinds (if specified)
+                if !events.is_empty() && !events.iter().any(|kind| kind.matches(gh_event)) {
+                    continue;
                 }
 
                 let watcher_event =
@@ -865,6 +1783,8 @@ end tell
 
                 if let Err(e) = event_tx.send(watcher_event) {
                     error!("Failed to send GitHub event: {}", e);
+                } else {
+                    emitted += 1;
                 }
             }
 
@@ -875,18 +1795,259 @@ end tell
                 state.last_github_event_id = Some(id.to_string());
             }
         }
+        WatcherKind::RssWatch {
+            url,
+            dedup_key,
+            allow_private_network,
+            ..
+        } => {
+            debug!("Polling feed watcher {} (url: {})", watcher.id, url);
+
+            let mut feed = state.take_feed_state(&watcher.id);
+            // Nothing recorded yet: treat the current entries as the baseline
+            let first_poll = feed.seen_guids.is_empty() && feed.etag.is_none();
+            let result = poll_feed(
+                &watcher.id,
+                url,
+                dedup_key.as_ref(),
+                *allow_private_network,
+                &mut feed,
+                first_poll,
+                event_tx,
+            )
+            .await;
+            // Keep whatever was marked seen, even if the poll failed part way
+            state.store_feed_state(&watcher.id, feed);
+            emitted += result?;
+        }
         _ => {
             warn!("poll_watcher called on non-polling watcher: {}", watcher.id);
         }
     }
 
-    Ok(())
+    Ok(emitted)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::watcher::{Watcher, WatcherKind};
+    use crate::watcher::{Watcher, WatcherKind, WatcherPayload};
+
+    #[test]
+    fn test_nearest_existing_ancestor() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("a").join("b");
+        assert_eq!(nearest_existing_ancestor(&missing).unwrap(), dir.path());
+        assert_eq!(nearest_existing_ancestor(dir.path()).unwrap(), dir.path());
+    }
+
+    #[tokio::test]
+    async fn test_file_watcher_waits_for_missing_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("inbox");
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let runner = WatcherRunner::new(tx);
+        let watcher = Watcher::new(
+            WatcherKind::FileWatch {
+                path: target.to_string_lossy().to_string(),
+                recursive: true,
+            },
+            "Test".to_string(),
+            "test".to_string(),
+        );
+        runner.start_watcher(watcher).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        // Unrelated siblings are ignored
+        std::fs::write(dir.path().join("other.txt"), "x").unwrap();
+        std::fs::create_dir(&target).unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.payload.to_json()["change_type"], "created");
+        assert_eq!(
+            event.payload.to_json()["path"],
+            target.to_string_lossy().as_ref()
+        );
+
+        // Once it exists, changes inside it are delivered
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let file = target.join("note.txt");
+        std::fs::write(&file, "hello").unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let event = rx.recv().await.unwrap();
+                if event.payload.to_json()["path"] == file.to_string_lossy().as_ref() {
+                    return event;
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(event.kind(), "file_changed");
+
+        runner.stop_all().await;
+    }
+
+    #[test]
+    fn test_github_rate_limit_backoff() {
+        let now = 1_000_000;
+        assert_eq!(github_rate_limit_backoff(None, None, now), None);
+        assert_eq!(
+            github_rate_limit_backoff(Some(4000), Some(now + 60), now),
+            None
+        );
+        assert_eq!(
+            github_rate_limit_backoff(Some(2), Some(now + 60), now),
+            Some(now + 60)
+        );
+        // Missing or stale reset time falls back to a fixed back-off
+        assert_eq!(
+            github_rate_limit_backoff(Some(0), Some(now - 5), now),
+            Some(now + GITHUB_DEFAULT_BACKOFF_SECS)
+        );
+    }
+
+    #[test]
+    fn test_new_feed_events() {
+        let rss = r#"<?xml version="1.0"?>
+<rss version="2.0"><channel><title>Blog</title>
+  <item><guid>post-2</guid><title>Second</title><link>https://example.com/2</link></item>
+  <item><guid>post-1</guid><title>First</title><link>https://example.com/1</link></item>
+</channel></rss>"#;
+        let feed = feed::parse_feed(rss.as_bytes()).unwrap();
+        let mut state = FeedState::default();
+
+        let events = new_feed_events(
+            "w1",
+            "https://example.com/feed",
+            &feed.items,
+            None,
+            &mut state,
+        );
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].kind(), "feed_item");
+        assert_eq!(events[0].payload.to_json()["guid"], "post-1");
+        assert_eq!(events[0].payload.to_json()["title"], "First");
+        assert_eq!(events[1].payload.to_json()["link"], "https://example.com/2");
+
+        // Already-seen entries aren't emitted again
+        let events = new_feed_events(
+            "w1",
+            "https://example.com/feed",
+            &feed.items,
+            None,
+            &mut state,
+        );
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_new_feed_events_with_dedup_key() {
+        // A feed that regenerates GUIDs on every build but keeps links stable
+        let rss = r#"<?xml version="1.0"?>
+<rss version="2.0"><channel><title>Blog</title>
+  <item><guid>build-2-post-1</guid><title>First</title><link>https://example.com/1</link></item>
+  <item><guid>build-1-post-1</guid><title>First</title><link>https://example.com/1</link></item>
+</channel></rss>"#;
+        let feed = feed::parse_feed(rss.as_bytes()).unwrap();
+        let by_link = DedupKey::Path {
+            path: "link".to_string(),
+        };
+        let mut state = FeedState::default();
+
+        let events = new_feed_events(
+            "w1",
+            "https://example.com/feed",
+            &feed.items,
+            Some(&by_link),
+            &mut state,
+        );
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].payload.to_json()["guid"], "build-1-post-1");
+        assert_eq!(state.seen_guids, vec!["https://example.com/1"]);
+    }
+
+    #[tokio::test]
+    async fn test_poll_feed_refuses_internal_urls() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut state = FeedState::default();
+        let err = poll_feed(
+            "w1",
+            "http://127.0.0.1:9/feed",
+            None,
+            false,
+            &mut state,
+            false,
+            &tx,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Access to loopback address is not allowed");
+        assert!(rx.try_recv().is_err());
+
+        // Opting in reaches the host; nothing listens there, so the request fails
+        let err = poll_feed(
+            "w1",
+            "http://127.0.0.1:9/feed",
+            None,
+            true,
+            &mut state,
+            false,
+            &tx,
+        )
+        .await
+        .unwrap_err();
+        assert!(!err.to_string().contains("not allowed"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_email_headers() {
+        let output = "Id: <a@mail>\nFrom: Boss <boss@co.com>\nSubject: Urgent\nDate: Monday\n---\n\
+                      Id: \nFrom: x@y.z\nSubject: Hi\nDate: Tuesday\n---\n";
+        let headers = parse_email_headers(output);
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers[0].message_id, "<a@mail>");
+        assert_eq!(headers[0].from, "Boss <boss@co.com>");
+        assert_eq!(headers[0].subject, "Urgent");
+        assert_eq!(headers[0].dedup_key(), "<a@mail>");
+        // Falls back to sender/subject/date when there's no message id
+        assert_eq!(headers[1].dedup_key(), "x@y.z|Hi|Tuesday");
+    }
+
+    #[test]
+    fn test_email_body_fetch() {
+        let script = email_body_script(&["<a@mail>", "quo\"te"]);
+        assert!(script.contains(r#"{"<a@mail>", "quo\"te"}"#));
+
+        let output = "<a@mail>\nLine one\n---\nLine two\u{1e}<b@mail>\nSecond\u{1e}";
+        let bodies = parse_email_bodies(output);
+        assert_eq!(bodies.len(), 2);
+        assert_eq!(bodies["<a@mail>"], "Line one\n---\nLine two");
+        assert_eq!(bodies["<b@mail>"], "Second");
+    }
+
+    #[test]
+    fn test_jittered_interval() {
+        let base = Duration::from_secs(300);
+
+        // Zero jitter is exactly the configured interval
+        assert_eq!(jittered_interval(base, 0.0), base);
+
+        for _ in 0..100 {
+            let d = jittered_interval(base, 0.2);
+            assert!(d >= Duration::from_secs(240) && d <= Duration::from_secs(360));
+        }
+
+        // Full jitter never drops below one second
+        for _ in 0..100 {
+            assert!(jittered_interval(Duration::from_secs(2), 1.0) >= Duration::from_secs(1));
+        }
+    }
 
     #[tokio::test]
     async fn test_runner_creation() {
@@ -906,6 +2067,8 @@ mod tests {
                 from: None,
                 subject_contains: None,
                 interval_secs: 60,
+                include_body: true,
+                dedup_key: None,
             },
             "Test".to_string(),
             "test".to_string(),
@@ -923,6 +2086,46 @@ mod tests {
         assert!(!runner.is_running(&watcher_id).await);
     }
 
+    #[tokio::test]
+    async fn test_shutdown_terminates_tasks() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let runner = WatcherRunner::new(tx);
+
+        let mut ids = Vec::new();
+        for i in 0..2 {
+            let watcher = Watcher::new(
+                WatcherKind::EmailWatch {
+                    from: None,
+                    subject_contains: None,
+                    interval_secs: 60,
+                    include_body: true,
+                    dedup_key: None,
+                },
+                format!("Test {}", i),
+                "test".to_string(),
+            );
+            ids.push(watcher.id.clone());
+            runner.start_watcher(watcher).await.unwrap();
+        }
+
+        let tasks: Vec<_> = {
+            let handles = runner.task_handles.read().await;
+            ids.iter().map(|id| handles[id].abort_handle()).collect()
+        };
+
+        // Stopping one watcher leaves the other running
+        runner.stop_watcher(&ids[0]).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(tasks[0].is_finished());
+        assert!(!tasks[1].is_finished());
+        assert!(runner.is_running(&ids[1]).await);
+
+        tokio::time::timeout(Duration::from_secs(5), runner.shutdown())
+            .await
+            .expect("shutdown should not hit the abort timeout");
+        assert!(tasks.iter().all(|t| t.is_finished()));
+    }
+
     #[tokio::test]
     async fn test_stop_all_watchers() {
         let (tx, _rx) = mpsc::unbounded_channel();
@@ -934,6 +2137,8 @@ mod tests {
                     from: None,
                     subject_contains: None,
                     interval_secs: 60,
+                    include_body: true,
+                    dedup_key: None,
                 },
                 format!("Test {}", i),
                 "test".to_string(),
@@ -973,7 +2178,42 @@ mod tests {
             .expect("Timeout waiting for event")
             .expect("Channel closed");
 
-        assert_eq!(event.kind, "task_triggered");
+        assert_eq!(event.kind(), "task_triggered");
+    }
+
+    #[tokio::test]
+    async fn test_poll_gate_queues_over_cap() {
+        let gate = Arc::new(PollGate::new(1));
+        let first = gate.acquire("a").await;
+        assert!(first.is_some());
+
+        // A second poll waits for the slot instead of being skipped
+        let waiting = tokio::spawn({
+            let gate = gate.clone();
+            async move { gate.acquire("b").await.is_some() }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiting.is_finished());
+        assert_eq!(gate.metrics.polls_queued(), 1);
+
+        drop(first);
+        assert!(
+            tokio::time::timeout(Duration::from_secs(1), waiting)
+                .await
+                .unwrap()
+                .unwrap()
+        );
+        assert_eq!(gate.metrics.polls_started(), 2);
+
+        // 0 disables the cap
+        let unlimited = PollGate::new(0);
+        assert!(unlimited.acquire("a").await.is_none());
+        assert!(unlimited.acquire("b").await.is_none());
+        assert_eq!(unlimited.metrics.polls_queued(), 0);
+
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let runner = WatcherRunner::new(tx);
+        assert_eq!(runner.metrics().polls_queued(), 0);
     }
 
     #[tokio::test]
@@ -992,6 +2232,8 @@ mod tests {
                     from: None,
                     subject_contains: None,
                     interval_secs: 60,
+                    include_body: true,
+                    dedup_key: None,
                 },
                 format!("Test {}", i),
                 "test".to_string(),
@@ -1008,6 +2250,8 @@ mod tests {
                 from: None,
                 subject_contains: None,
                 interval_secs: 60,
+                include_body: true,
+                dedup_key: None,
             },
             "Test 3".to_string(),
             "test".to_string(),
@@ -1016,4 +2260,455 @@ mod tests {
         let result = runner.start_watcher(watcher3).await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_execution_history_recorded() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        persistence::init_watcher_tables(&conn).unwrap();
+        let db = Arc::new(std::sync::Mutex::new(conn));
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let runner = WatcherRunner::new(tx).with_execution_db(db.clone());
+
+        let watcher = Watcher::new(
+            WatcherKind::OneShot {
+                at: Utc::now() - chrono::Duration::seconds(10),
+                task: "Recorded task".to_string(),
+            },
+            "Test history".to_string(),
+            "test".to_string(),
+        );
+        let watcher_id = watcher.id.clone();
+
+        runner.start_watcher(watcher).await.unwrap();
+        tokio::time::timeout(Duration::from_secs(1), rx.recv())
+            .await
+            .expect("Timeout waiting for event")
+            .expect("Channel closed");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let history =
+            persistence::get_watcher_history(&db.lock().unwrap(), &watcher_id, 10).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].outcome, ExecutionOutcome::Success);
+        assert!(history[0].message.contains("Recorded task"));
+    }
+
+    #[test]
+    fn test_watcher_state_variables() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let runner = WatcherRunner::new(tx.clone());
+        assert!(runner.get_state("w1", "count").is_err());
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        persistence::init_watcher_tables(&conn).unwrap();
+        let db = Arc::new(std::sync::Mutex::new(conn));
+        let runner = WatcherRunner::new(tx.clone()).with_state_db(db.clone());
+        runner
+            .set_state("w1", "count", serde_json::json!(3))
+            .unwrap();
+
+        // A fresh runner on the same database sees the value, as after a restart
+        let restarted = WatcherRunner::new(tx).with_state_db(db);
+        assert_eq!(
+            restarted.get_state("w1", "count").unwrap(),
+            Some(serde_json::json!(3))
+        );
+        assert!(restarted.get_state("w2", "count").unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pause_and_resume_watcher() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let runner = WatcherRunner::new(tx);
+
+        let mut watcher = Watcher::new(
+            WatcherKind::OneShot {
+                at: Utc::now() - chrono::Duration::seconds(10),
+                task: "Deferred task".to_string(),
+            },
+            "Test pause".to_string(),
+            "test".to_string(),
+        );
+        watcher.paused = true;
+        let watcher_id = watcher.id.clone();
+
+        runner.start_watcher(watcher).await.unwrap();
+        assert!(runner.is_paused(&watcher_id).await);
+        assert!(runner.is_running(&watcher_id).await);
+
+        // Paused: nothing fires, task stays alive
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(rx.try_recv().is_err());
+        assert!(runner.is_running(&watcher_id).await);
+
+        assert!(runner.resume_watcher(&watcher_id).await);
+        assert!(!runner.is_paused(&watcher_id).await);
+        assert!(!runner.resume_watcher(&watcher_id).await);
+        assert!(!runner.pause_watcher("missing").await);
+    }
+
+    #[tokio::test]
+    async fn test_ended_file_watcher_clears_paused() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let runner = WatcherRunner::new(tx);
+
+        // A relative path with no existing ancestor can't be watched, so the task ends
+        let mut watcher = Watcher::new(
+            WatcherKind::FileWatch {
+                path: "meepo-missing-dir/notes.txt".to_string(),
+                recursive: false,
+            },
+            "Unwatchable".to_string(),
+            "test".to_string(),
+        );
+        watcher.paused = true;
+        let watcher_id = watcher.id.clone();
+
+        runner.start_watcher(watcher).await.unwrap();
+        let handle = runner.task_handles.write().await.remove(&watcher_id);
+        handle.unwrap().await.unwrap();
+
+        assert!(!runner.is_running(&watcher_id).await);
+        assert!(!runner.is_paused(&watcher_id).await);
+        assert!(runner.paused.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_watcher_reloads_only_that_watcher() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        persistence::init_watcher_tables(&conn).unwrap();
+        let db = Arc::new(std::sync::Mutex::new(conn));
+        let listener = Arc::new(RecordingListener::default());
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let runner = WatcherRunner::new(tx)
+            .with_state_db(db.clone())
+            .with_lifecycle_listener(listener.clone());
+
+        let scheduled = |task: &str| {
+            Watcher::new(
+                WatcherKind::Scheduled {
+                    cron_expr: "0 0 0 1 1 * 2099".to_string(),
+                    task: task.to_string(),
+                },
+                "Run it".to_string(),
+                "test".to_string(),
+            )
+        };
+        let edited_watcher = scheduled("report");
+        let other = scheduled("backup");
+        for watcher in [&edited_watcher, &other] {
+            persistence::save_watcher(&db.lock().unwrap(), watcher).unwrap();
+            runner.start_watcher(watcher.clone()).await.unwrap();
+        }
+
+        let mut edited = edited_watcher.clone();
+        edited.action = "Run it weekly".to_string();
+        runner.update_watcher(edited).await.unwrap();
+
+        assert!(runner.is_running(&edited_watcher.id).await);
+        assert!(runner.is_running(&other.id).await);
+        assert_eq!(
+            *listener.events.lock().unwrap(),
+            vec![
+                format!("started {}", edited_watcher.id),
+                format!("started {}", other.id),
+                format!("started {}", edited_watcher.id),
+            ]
+        );
+        let stored = persistence::get_watcher_by_id(&db.lock().unwrap(), &edited_watcher.id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.action, "Run it weekly");
+
+        // Deactivating through an update stops the task for good
+        let mut deactivated = stored;
+        deactivated.active = false;
+        runner.update_watcher(deactivated).await.unwrap();
+        assert!(!runner.is_running(&edited_watcher.id).await);
+        assert!(runner.is_running(&other.id).await);
+
+        // Unknown watchers are an error
+        assert!(runner.update_watcher(scheduled("nope")).await.is_err());
+    }
+
+    #[derive(Default)]
+    struct RecordingListener {
+        events: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl WatcherLifecycleListener for RecordingListener {
+        fn on_started(&self, watcher: &Watcher) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("started {}", watcher.id));
+        }
+
+        fn on_error(&self, watcher_id: &str, _error: &str, consecutive_failures: u32) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("error {} {}", watcher_id, consecutive_failures));
+        }
+
+        fn on_auto_disabled(&self, watcher_id: &str, _reason: &str) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("auto_disabled {}", watcher_id));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failing_watcher_is_auto_disabled() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        persistence::init_watcher_tables(&conn).unwrap();
+        let watcher = Watcher::new(
+            WatcherKind::RssWatch {
+                // `.invalid` never resolves, so every poll fails fast
+                url: "http://feed.invalid/feed.xml".to_string(),
+                interval_secs: 1,
+                dedup_key: None,
+                allow_private_network: false,
+            },
+            "Broken feed".to_string(),
+            "test".to_string(),
+        );
+        persistence::save_watcher(&conn, &watcher).unwrap();
+        let db = Arc::new(std::sync::Mutex::new(conn));
+
+        let listener = Arc::new(RecordingListener::default());
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let config = WatcherConfig {
+            min_poll_interval_secs: 1,
+            max_consecutive_failures: 2,
+            ..Default::default()
+        };
+        let runner = WatcherRunner::with_config(tx, config)
+            .with_state_db(db.clone())
+            .with_lifecycle_listener(listener.clone());
+
+        let watcher_id = watcher.id.clone();
+        runner.start_watcher(watcher).await.unwrap();
+
+        // First poll is immediate, the second a second later
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while runner.is_running(&watcher_id).await {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("watcher should stop after repeated failures");
+
+        assert_eq!(
+            *listener.events.lock().unwrap(),
+            vec![
+                format!("started {}", watcher_id),
+                format!("error {} 1", watcher_id),
+                format!("error {} 2", watcher_id),
+                format!("auto_disabled {}", watcher_id),
+            ]
+        );
+        let stored = persistence::get_watcher_by_id(&db.lock().unwrap(), &watcher_id)
+            .unwrap()
+            .unwrap();
+        assert!(!stored.active);
+    }
+
+    struct FakeInbox;
+
+    #[async_trait::async_trait]
+    impl EmailSource for FakeInbox {
+        async fn recent_emails(
+            &self,
+            _limit: usize,
+            include_body: bool,
+        ) -> Result<Vec<PolledEmail>> {
+            assert!(include_body);
+            Ok(vec![
+                PolledEmail {
+                    message_id: "<1@mail>".to_string(),
+                    from: "Boss <boss@co.com>".to_string(),
+                    subject: "URGENT: invoice".to_string(),
+                    date: "Monday".to_string(),
+                    body: Some("Please pay".to_string()),
+                },
+                PolledEmail {
+                    message_id: "<2@mail>".to_string(),
+                    from: "news@shop.com".to_string(),
+                    subject: "Sale".to_string(),
+                    date: "Monday".to_string(),
+                    body: None,
+                },
+            ])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_email_watch_polls_email_source() {
+        let watcher = Watcher::new(
+            WatcherKind::EmailWatch {
+                from: Some("boss@".to_string()),
+                subject_contains: Some("urgent".to_string()),
+                interval_secs: 60,
+                include_body: true,
+                dedup_key: None,
+            },
+            "Boss mail".to_string(),
+            "test".to_string(),
+        );
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut state = PollState::new();
+        state.email_source = Some(Arc::new(FakeInbox));
+
+        assert_eq!(poll_watcher(&watcher, &tx, &mut state).await.unwrap(), 1);
+        match rx.try_recv().unwrap().payload {
+            WatcherPayload::EmailArrived {
+                subject, preview, ..
+            } => {
+                assert_eq!(subject, "URGENT: invoice");
+                assert_eq!(preview, "Please pay");
+            }
+            other => panic!("unexpected payload: {:?}", other),
+        }
+
+        // Already-seen messages aren't emitted again
+        assert_eq!(poll_watcher(&watcher, &tx, &mut state).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_email_watch_reports_each_message_once() {
+        let watcher = Watcher::new(
+            WatcherKind::EmailWatch {
+                from: None,
+                subject_contains: None,
+                interval_secs: 60,
+                include_body: true,
+                dedup_key: None,
+            },
+            "All mail".to_string(),
+            "test".to_string(),
+        );
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        persistence::init_watcher_tables(&conn).unwrap();
+        let db = Arc::new(std::sync::Mutex::new(conn));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        let mut state = PollState::new();
+        state.state_db = Some(db.clone());
+        state.email_source = Some(Arc::new(FakeInbox));
+        assert_eq!(poll_watcher(&watcher, &tx, &mut state).await.unwrap(), 2);
+        assert_eq!(poll_watcher(&watcher, &tx, &mut state).await.unwrap(), 0);
+        assert!(rx.try_recv().is_ok());
+        assert!(rx.try_recv().is_ok());
+        assert!(rx.try_recv().is_err());
+
+        // Seen ids are persisted, so a restarted runner doesn't re-report them
+        let mut restarted = PollState::new();
+        restarted.state_db = Some(db.clone());
+        restarted.email_source = Some(Arc::new(FakeInbox));
+        assert_eq!(
+            poll_watcher(&watcher, &tx, &mut restarted).await.unwrap(),
+            0
+        );
+
+        let saved = persistence::get_feed_state(&db.lock().unwrap(), &watcher.id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.seen_guids, vec!["<1@mail>", "<2@mail>"]);
+    }
+
+    #[tokio::test]
+    async fn test_email_watch_uses_configured_dedup_key() {
+        let watcher = Watcher::new(
+            WatcherKind::EmailWatch {
+                from: None,
+                subject_contains: None,
+                interval_secs: 60,
+                include_body: true,
+                dedup_key: Some(DedupKey::Path {
+                    path: "date".to_string(),
+                }),
+            },
+            "One per day".to_string(),
+            "test".to_string(),
+        );
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut state = PollState::new();
+        state.email_source = Some(Arc::new(FakeInbox));
+
+        // Both messages share a date, so only the first counts as new
+        assert_eq!(poll_watcher(&watcher, &tx, &mut state).await.unwrap(), 1);
+        assert_eq!(poll_watcher(&watcher, &tx, &mut state).await.unwrap(), 0);
+    }
+
+    struct FakeCalendar(std::sync::Mutex<Vec<PolledCalendarEvent>>);
+
+    #[async_trait::async_trait]
+    impl CalendarSource for FakeCalendar {
+        async fn upcoming_events(
+            &self,
+            days_ahead: u64,
+            calendar: Option<&str>,
+        ) -> Result<Vec<PolledCalendarEvent>> {
+            assert_eq!((days_ahead, calendar), (1, Some("Work")));
+            Ok(self.0.lock().unwrap().clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_calendar_watch_reminds_once_per_start_time() {
+        let watcher = Watcher::new(
+            WatcherKind::CalendarWatch {
+                lead_minutes: 15,
+                calendar: Some("Work".to_string()),
+                interval_secs: 300,
+                dedup_key: None,
+            },
+            "Meeting reminders".to_string(),
+            "test".to_string(),
+        );
+        let now = Utc::now();
+        let event = |id: &str, start: DateTime<Utc>, all_day: bool| PolledCalendarEvent {
+            id: id.to_string(),
+            title: id.to_string(),
+            start,
+            all_day,
+        };
+        let calendar = Arc::new(FakeCalendar(std::sync::Mutex::new(vec![
+            event("standup", now + chrono::Duration::minutes(10), false),
+            event("review", now + chrono::Duration::hours(2), false),
+            event("offsite", now - chrono::Duration::hours(1), true),
+            event("lunch", now - chrono::Duration::minutes(5), false),
+        ])));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut state = PollState::new();
+        state.calendar_source = Some(calendar.clone());
+
+        // Only events inside the lead window fire; ongoing all-day events count
+        assert_eq!(poll_watcher(&watcher, &tx, &mut state).await.unwrap(), 2);
+        let mut titles = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let WatcherPayload::EventCreated { title, .. } = event.payload {
+                titles.push(title);
+            }
+        }
+        assert_eq!(titles, vec!["standup", "offsite (all day)"]);
+
+        // Frequent polling doesn't repeat a reminder
+        assert_eq!(poll_watcher(&watcher, &tx, &mut state).await.unwrap(), 0);
+
+        // Rescheduling re-arms it
+        calendar.0.lock().unwrap()[0].start = now + chrono::Duration::minutes(12);
+        assert_eq!(poll_watcher(&watcher, &tx, &mut state).await.unwrap(), 1);
+    }
+
+    #[test]
+    fn test_poll_state_mark_seen() {
+        let mut state = PollState::new();
+        assert!(state.mark_seen("item-1"));
+        assert!(!state.mark_seen("item-1"));
+        assert!(state.mark_seen("item-2"));
+    }
 }
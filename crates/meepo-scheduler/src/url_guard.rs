@@ -0,0 +1,173 @@
+//! SSRF guard for URLs fetched on the agent's behalf
+//!
+//! Feed URLs come from the agent or from watcher definitions, so before every
+//! request (including each redirect hop) the URL must be http(s) and its host
+//! must not be, or resolve to, a loopback, private or link-local address.
+
+use anyhow::{Result, anyhow, bail};
+use reqwest::Url;
+use std::net::{IpAddr, SocketAddr};
+
+/// Why `ip` must not be fetched, or `None` for a public address
+pub fn is_private_ip(ip: &IpAddr) -> Option<&'static str> {
+    match ip {
+        IpAddr::V4(ipv4) => {
+            let octets = ipv4.octets();
+            if octets[0] == 10 {
+                Some("private IP range (10.x.x.x)")
+            } else if octets[0] == 172 && (16..=31).contains(&octets[1]) {
+                Some("private IP range (172.16-31.x.x)")
+            } else if octets[0] == 192 && octets[1] == 168 {
+                Some("private IP range (192.168.x.x)")
+            } else if octets[0] == 169 && octets[1] == 254 {
+                Some("link-local address (169.254.x.x)")
+            } else if octets[0] == 127 {
+                Some("loopback address")
+            } else if octets[0] == 0 {
+                Some("unspecified address (0.x.x.x)")
+            } else {
+                None
+            }
+        }
+        IpAddr::V6(ipv6) => {
+            if let Some(mapped) = ipv6.to_ipv4_mapped() {
+                is_private_ip(&IpAddr::V4(mapped))
+            } else if ipv6.is_loopback() {
+                Some("IPv6 loopback")
+            } else if ipv6.is_unspecified() {
+                Some("IPv6 unspecified address")
+            } else if ipv6.segments()[0] & 0xffc0 == 0xfe80 {
+                Some("IPv6 link-local address")
+            } else if ipv6.segments()[0] & 0xfe00 == 0xfc00 {
+                Some("IPv6 unique local address")
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// A URL that passed [`check_public_url`], with the addresses its host resolved
+/// to. Pin them in the client so a second DNS lookup can't point elsewhere.
+#[derive(Debug, Clone)]
+pub struct PublicUrl {
+    pub url: Url,
+    pub host: String,
+    pub addrs: Vec<SocketAddr>,
+}
+
+impl PublicUrl {
+    /// A client that only connects to the checked addresses and doesn't follow
+    /// redirects, so each hop can be checked before it's requested
+    pub fn client_builder(&self) -> reqwest::ClientBuilder {
+        let mut builder = reqwest::Client::builder().redirect(reqwest::redirect::Policy::none());
+        for addr in &self.addrs {
+            builder = builder.resolve(&self.host, *addr);
+        }
+        builder
+    }
+}
+
+/// The checks that need no DNS lookup: refuse localhost and private IP literals.
+/// Returns the host without IPv6 brackets.
+pub fn check_host(url: &Url) -> Result<String> {
+    let host = url
+        .host_str()
+        .ok_or_else(|| anyhow!("URL must have a host"))?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+    let lower = host.to_ascii_lowercase();
+    if lower == "localhost" || lower.ends_with(".localhost") {
+        bail!("Access to localhost is not allowed");
+    }
+    if let Ok(ip) = host.parse::<IpAddr>()
+        && let Some(reason) = is_private_ip(&ip)
+    {
+        bail!("Access to {} is not allowed", reason);
+    }
+    Ok(host)
+}
+
+/// Parse `url` and refuse it unless it's http(s) and every address its host
+/// resolves to is public
+pub async fn check_public_url(url: &str) -> Result<PublicUrl> {
+    let url = Url::parse(url).map_err(|e| anyhow!("Invalid URL '{}': {}", url, e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        bail!("Only HTTP and HTTPS URLs can be fetched");
+    }
+
+    let host = check_host(&url)?;
+    let port = url.port_or_known_default().unwrap_or(80);
+    let addrs: Vec<SocketAddr> = match host.parse::<IpAddr>() {
+        Ok(ip) => vec![SocketAddr::new(ip, port)],
+        Err(_) => tokio::net::lookup_host((host.as_str(), port))
+            .await
+            .map_err(|e| anyhow!("Could not resolve {}: {}", host, e))?
+            .collect(),
+    };
+    for addr in &addrs {
+        if let Some(reason) = is_private_ip(&addr.ip()) {
+            bail!(
+                "Access denied: {} resolves to {} ({})",
+                host,
+                addr.ip(),
+                reason
+            );
+        }
+    }
+
+    Ok(PublicUrl { url, host, addrs })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_private_ip() {
+        for ip in [
+            "10.0.0.1",
+            "172.16.5.4",
+            "192.168.1.1",
+            "169.254.169.254",
+            "127.0.0.1",
+            "0.0.0.0",
+            "::1",
+            "::",
+            "fe80::1",
+            "fd00::1",
+            "::ffff:127.0.0.1",
+        ] {
+            let ip: IpAddr = ip.parse().unwrap();
+            assert!(is_private_ip(&ip).is_some(), "{} should be private", ip);
+        }
+        for ip in ["93.184.216.34", "172.32.0.1", "2606:4700::1111"] {
+            let ip: IpAddr = ip.parse().unwrap();
+            assert!(is_private_ip(&ip).is_none(), "{} should be public", ip);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_public_url_rejects_internal_hosts() {
+        for url in [
+            "http://169.254.169.254/latest/meta-data/",
+            "http://localhost:8080/feed",
+            "http://127.0.0.1/feed",
+            "http://[::1]/feed",
+            "http://10.1.2.3/rss",
+        ] {
+            assert!(check_public_url(url).await.is_err(), "{} was allowed", url);
+        }
+        let err = check_public_url("ftp://example.com/feed")
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Only HTTP and HTTPS URLs can be fetched");
+
+        let checked = check_public_url("http://93.184.216.34:8080/rss")
+            .await
+            .unwrap();
+        assert_eq!(checked.host, "93.184.216.34");
+        assert_eq!(checked.addrs, vec!["93.184.216.34:8080".parse().unwrap()]);
+    }
+}
@@ -4,8 +4,10 @@
 //! components that monitor various sources (email, calendar, files, etc.)
 //! and emit events when conditions are met.
 
+use anyhow::{Context, Result, bail};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 /// A watcher monitors a specific source and triggers actions when conditions are met
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +27,10 @@ pub struct Watcher {
     /// Whether this watcher is currently active
     pub active: bool,
 
+    /// Whether this watcher is temporarily paused (kept running, but not firing)
+    #[serde(default)]
+    pub paused: bool,
+
     /// When this watcher was created
     pub created_at: DateTime<Utc>,
 }
@@ -38,10 +44,19 @@ impl Watcher {
             action,
             reply_channel,
             active: true,
+            paused: false,
             created_at: Utc::now(),
         }
     }
 
+    /// Check that this watcher can actually run: a non-empty action and reply
+    /// channel, plus the constraints of its kind. Errors name the offending field.
+    pub fn validate(&self) -> Result<()> {
+        not_blank("action", &self.action)?;
+        not_blank("reply_channel", &self.reply_channel)?;
+        self.kind.validate()
+    }
+
     /// Get a human-readable description of this watcher
     pub fn description(&self) -> String {
         match &self.kind {
@@ -49,6 +64,7 @@ impl Watcher {
                 from,
                 subject_contains,
                 interval_secs,
+                ..
             } => {
                 let mut desc = format!("Email watcher (every {}s)", interval_secs);
                 if let Some(f) = from {
@@ -60,13 +76,19 @@ impl Watcher {
                 desc
             }
             WatcherKind::CalendarWatch {
-                lookahead_hours,
+                lead_minutes,
+                calendar,
                 interval_secs,
+                ..
             } => {
-                format!(
-                    "Calendar watcher ({}h lookahead, every {}s)",
-                    lookahead_hours, interval_secs
-                )
+                let mut desc = format!(
+                    "Calendar watcher ({}m before events, every {}s)",
+                    lead_minutes, interval_secs
+                );
+                if let Some(c) = calendar {
+                    desc.push_str(&format!(" calendar: {}", c));
+                }
+                desc
             }
             WatcherKind::GitHubWatch {
                 repo,
@@ -79,8 +101,17 @@ impl Watcher {
                     repo, events, interval_secs
                 )
             }
-            WatcherKind::FileWatch { path } => {
-                format!("File watcher for {}", path)
+            WatcherKind::RssWatch {
+                url, interval_secs, ..
+            } => {
+                format!("Feed watcher for {} (every {}s)", url, interval_secs)
+            }
+            WatcherKind::FileWatch { path, recursive } => {
+                if *recursive {
+                    format!("File watcher for {} (recursive)", path)
+                } else {
+                    format!("File watcher for {}", path)
+                }
             }
             WatcherKind::MessageWatch { keyword } => {
                 format!("Message watcher for keyword: {}", keyword)
@@ -95,6 +126,42 @@ impl Watcher {
     }
 }
 
+/// Longest polling interval a watcher may use (30 days)
+pub const MAX_INTERVAL_SECS: u64 = 30 * 24 * 60 * 60;
+
+/// Longest reminder lead time of a calendar watcher (one week)
+pub const MAX_LEAD_MINUTES: u64 = 7 * 24 * 60;
+
+fn not_blank(field: &str, value: &str) -> Result<()> {
+    if value.trim().is_empty() {
+        bail!("`{}` must not be empty", field);
+    }
+    Ok(())
+}
+
+fn validate_interval(interval_secs: u64) -> Result<()> {
+    if interval_secs == 0 || interval_secs > MAX_INTERVAL_SECS {
+        bail!(
+            "`interval_secs` must be between 1 and {} (30 days), got {}",
+            MAX_INTERVAL_SECS,
+            interval_secs
+        );
+    }
+    Ok(())
+}
+
+fn default_include_body() -> bool {
+    true
+}
+
+fn default_recursive() -> bool {
+    true
+}
+
+fn default_lead_minutes() -> u64 {
+    15
+}
+
 /// The different types of watchers available
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -109,15 +176,38 @@ pub enum WatcherKind {
 
         /// How often to poll for new emails (in seconds)
         interval_secs: u64,
+
+        /// Whether to fetch message bodies for matching emails. Polling always
+        /// matches on headers first; bodies are only fetched for new matches.
+        #[serde(default = "default_include_body")]
+        include_body: bool,
+
+        /// What makes an email distinct (fields: message_id, from, subject, date);
+        /// defaults to the Message-ID header
+        #[serde(default)]
+        dedup_key: Option<DedupKey>,
     },
 
-    /// Watch calendar for upcoming events
+    /// Remind about calendar events shortly before they start
     CalendarWatch {
-        /// How far ahead to look for events (in hours)
-        lookahead_hours: u64,
+        /// How long before an event starts to remind about it (in minutes).
+        /// Reminders fire on the first poll inside this window, so keep it
+        /// longer than the poll interval.
+        #[serde(default = "default_lead_minutes")]
+        lead_minutes: u64,
+
+        /// Only watch this calendar (default: all calendars)
+        #[serde(default)]
+        calendar: Option<String>,
 
         /// How often to check the calendar (in seconds)
         interval_secs: u64,
+
+        /// What makes a reminder distinct (fields: id, title, start, all_day);
+        /// defaults to the event id plus its start time, so a rescheduled event
+        /// is reminded about again
+        #[serde(default)]
+        dedup_key: Option<DedupKey>,
     },
 
     /// Watch GitHub repository for events
@@ -125,21 +215,50 @@ pub enum WatcherKind {
         /// Repository in "owner/repo" format
         repo: String,
 
-        /// Event types to watch for (e.g., "push", "pull_request", "issues")
-        events: Vec<String>,
+        /// Event kinds to emit (e.g., "issue", "pull_request", "release", "comment").
+        /// Empty means every event.
+        #[serde(default)]
+        events: Vec<GitHubEventKind>,
 
         /// How often to poll GitHub API (in seconds)
         interval_secs: u64,
 
         /// Optional GitHub token for authenticated API calls (higher rate limits, private repos)
-        #[serde(default)]
+        #[serde(default, alias = "token")]
         github_token: Option<String>,
+
+        /// What makes an event distinct; defaults to comparing numeric event IDs
+        #[serde(default)]
+        dedup_key: Option<DedupKey>,
+    },
+
+    /// Watch an RSS or Atom feed for new entries
+    RssWatch {
+        /// Feed URL
+        url: String,
+
+        /// How often to fetch the feed (in seconds)
+        interval_secs: u64,
+
+        /// What makes an entry distinct (fields: id, title, link, summary,
+        /// published); defaults to the entry GUID
+        #[serde(default)]
+        dedup_key: Option<DedupKey>,
+
+        /// Allow a feed on localhost or the private network, such as one served
+        /// by a NAS. Off by default, so a watcher can't be used to probe the LAN.
+        #[serde(default)]
+        allow_private_network: bool,
     },
 
     /// Watch filesystem for changes
     FileWatch {
-        /// Path to file or directory to watch
+        /// Path to file or directory to watch (may not exist yet)
         path: String,
+
+        /// Whether to watch subdirectories of a directory too
+        #[serde(default = "default_recursive")]
+        recursive: bool,
     },
 
     /// Watch for messages containing a keyword
@@ -174,6 +293,7 @@ impl WatcherKind {
             Self::EmailWatch { .. } => 60,     // Email: minimum 1 minute
             Self::CalendarWatch { .. } => 300, // Calendar: minimum 5 minutes
             Self::GitHubWatch { .. } => 30,    // GitHub: minimum 30 seconds (API rate limits)
+            Self::RssWatch { .. } => 300,      // Feeds: minimum 5 minutes (be polite)
             Self::FileWatch { .. } => 0,       // File: event-driven, no polling
             Self::MessageWatch { .. } => 0,    // Message: event-driven
             Self::Scheduled { .. } => 0,       // Scheduled: based on cron
@@ -181,11 +301,107 @@ impl WatcherKind {
         }
     }
 
+    /// Check the kind's own fields: polling intervals within bounds, filters
+    /// that aren't blank, an http(s) feed URL, an `owner/repo` GitHub name and a
+    /// parseable cron expression. Errors name the offending field.
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            Self::EmailWatch {
+                from,
+                subject_contains,
+                interval_secs,
+                ..
+            } => {
+                validate_interval(*interval_secs)?;
+                if let Some(from) = from {
+                    not_blank("from", from)?;
+                }
+                if let Some(subject) = subject_contains {
+                    not_blank("subject_contains", subject)?;
+                }
+            }
+            Self::CalendarWatch {
+                lead_minutes,
+                calendar,
+                interval_secs,
+                ..
+            } => {
+                validate_interval(*interval_secs)?;
+                if *lead_minutes == 0 || *lead_minutes > MAX_LEAD_MINUTES {
+                    bail!(
+                        "`lead_minutes` must be between 1 and {} (one week), got {}",
+                        MAX_LEAD_MINUTES,
+                        lead_minutes
+                    );
+                }
+                if let Some(calendar) = calendar {
+                    not_blank("calendar", calendar)?;
+                }
+            }
+            Self::GitHubWatch {
+                repo,
+                interval_secs,
+                ..
+            } => {
+                validate_interval(*interval_secs)?;
+                let valid = repo.split_once('/').is_some_and(|(owner, name)| {
+                    [owner, name].iter().all(|part| {
+                        !part.is_empty()
+                            && part
+                                .chars()
+                                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+                    })
+                });
+                if !valid {
+                    bail!("`repo` must be in \"owner/repo\" format, got '{}'", repo);
+                }
+            }
+            Self::RssWatch {
+                url,
+                interval_secs,
+                allow_private_network,
+                ..
+            } => {
+                validate_interval(*interval_secs)?;
+                let parsed = reqwest::Url::parse(url)
+                    .with_context(|| format!("`url` is not a valid URL: '{}'", url))?;
+                if !matches!(parsed.scheme(), "http" | "https") {
+                    bail!("`url` must be an http or https URL, got '{}'", url);
+                }
+                // Hostnames are checked again after DNS on every fetch
+                if !allow_private_network {
+                    crate::url_guard::check_host(&parsed).with_context(|| {
+                        format!(
+                            "`url` can't be watched: '{}' (set allow_private_network for feeds on your network)",
+                            url
+                        )
+                    })?;
+                }
+            }
+            Self::FileWatch { path, .. } => not_blank("path", path)?,
+            Self::MessageWatch { keyword } => not_blank("keyword", keyword)?,
+            Self::Scheduled { cron_expr, task } => {
+                cron::Schedule::from_str(cron_expr).with_context(|| {
+                    format!(
+                        "`cron_expr` is not a valid cron expression: '{}'",
+                        cron_expr
+                    )
+                })?;
+                not_blank("task", task)?;
+            }
+            Self::OneShot { task, .. } => not_blank("task", task)?,
+        }
+        Ok(())
+    }
+
     /// Check if this is a polling-based watcher
     pub fn is_polling(&self) -> bool {
         matches!(
             self,
-            Self::EmailWatch { .. } | Self::CalendarWatch { .. } | Self::GitHubWatch { .. }
+            Self::EmailWatch { .. }
+                | Self::CalendarWatch { .. }
+                | Self::GitHubWatch { .. }
+                | Self::RssWatch { .. }
         )
     }
 
@@ -198,6 +414,372 @@ impl WatcherKind {
     pub fn is_scheduled(&self) -> bool {
         matches!(self, Self::Scheduled { .. } | Self::OneShot { .. })
     }
+
+    /// Build a kind from the `create_watcher` tool's kind name and config object.
+    ///
+    /// Cron expressions are validated here so a bad schedule is rejected up front.
+    pub fn from_config(kind: &str, config: serde_json::Value) -> Result<Self> {
+        // Map the tool's kind string to the serde tag variant name
+        let type_tag = match kind {
+            "email" => "EmailWatch",
+            "calendar" => "CalendarWatch",
+            "github" => "GitHubWatch",
+            "rss" | "feed" => "RssWatch",
+            "file" => "FileWatch",
+            "message" => "MessageWatch",
+            "scheduled" | "time" => "Scheduled",
+            "oneshot" => "OneShot",
+            other => bail!("Unknown watcher kind: {}", other),
+        };
+
+        let serde_json::Value::Object(mut map) = config else {
+            bail!("Watcher config is not a JSON object");
+        };
+        map.insert("type".to_string(), type_tag.into());
+        migrate_legacy_fields(&mut map)?;
+
+        let kind: Self = serde_json::from_value(serde_json::Value::Object(map))
+            .context("Failed to deserialize watcher kind")?;
+        if let Self::Scheduled { cron_expr, .. } = &kind {
+            cron::Schedule::from_str(cron_expr)
+                .with_context(|| format!("Invalid cron expression: {}", cron_expr))?;
+        }
+        Ok(kind)
+    }
+
+    /// Parse a kind as stored in the database, migrating fields written by
+    /// older versions
+    pub fn from_json(json: &str) -> Result<Self> {
+        let mut value: serde_json::Value = serde_json::from_str(json)?;
+        if let serde_json::Value::Object(map) = &mut value {
+            migrate_legacy_fields(map)?;
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+/// Calendar watchers created before reminders existed looked `lookahead_hours`
+/// ahead; that window becomes the reminder lead, `lead_minutes`. A config
+/// setting both is rejected rather than guessed at.
+fn migrate_legacy_fields(map: &mut serde_json::Map<String, serde_json::Value>) -> Result<()> {
+    if map.get("type").and_then(|t| t.as_str()) != Some("CalendarWatch") {
+        return Ok(());
+    }
+    let Some(hours) = map.remove("lookahead_hours") else {
+        return Ok(());
+    };
+    if map.contains_key("lead_minutes") {
+        bail!("`lookahead_hours` was replaced by `lead_minutes`; set only `lead_minutes`");
+    }
+    let hours = hours
+        .as_u64()
+        .context("`lookahead_hours` must be a whole number of hours")?;
+    map.insert("lead_minutes".to_string(), hours.saturating_mul(60).into());
+    Ok(())
+}
+
+/// The next `count` times a watcher of this kind would fire after `from`.
+///
+/// Pure: nothing is created or run. Polling watchers tick immediately and
+/// then every `interval_secs` (clamped to the kind's minimum, ignoring jitter);
+/// cron schedules use the `cron` crate's iterator; one-shots fire once if
+/// still in the future. Event-driven watchers and invalid cron expressions
+/// have no predictable fire times and yield an empty list.
+pub fn preview_fire_times(
+    kind: &WatcherKind,
+    from: DateTime<Utc>,
+    count: usize,
+) -> Vec<DateTime<Utc>> {
+    match kind {
+        WatcherKind::EmailWatch { interval_secs, .. }
+        | WatcherKind::CalendarWatch { interval_secs, .. }
+        | WatcherKind::GitHubWatch { interval_secs, .. }
+        | WatcherKind::RssWatch { interval_secs, .. } => {
+            let step = chrono::Duration::seconds(
+                (*interval_secs).max(kind.min_interval_secs()).max(1) as i64,
+            );
+            std::iter::successors(Some(from), |t| t.checked_add_signed(step))
+                .take(count)
+                .collect()
+        }
+        WatcherKind::Scheduled { cron_expr, .. } => match cron::Schedule::from_str(cron_expr) {
+            Ok(schedule) => schedule.after(&from).take(count).collect(),
+            Err(_) => Vec::new(),
+        },
+        WatcherKind::OneShot { at, .. } => {
+            if *at > from && count > 0 {
+                vec![*at]
+            } else {
+                Vec::new()
+            }
+        }
+        WatcherKind::FileWatch { .. } | WatcherKind::MessageWatch { .. } => Vec::new(),
+    }
+}
+
+/// A kind of GitHub activity a `GitHubWatch` can subscribe to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum GitHubEventKind {
+    /// A new issue was opened
+    Issue,
+    /// A new pull request was opened
+    PullRequest,
+    /// A new release was published
+    Release,
+    /// A new comment on an issue, pull request, or commit
+    Comment,
+    /// Commits were pushed
+    Push,
+    /// Any other event, matched against the API event type (e.g., "WatchEvent")
+    Other(String),
+}
+
+impl GitHubEventKind {
+    pub fn from_string(s: &str) -> Self {
+        match s.trim().to_lowercase().as_str() {
+            "issue" | "issues" | "new_issue" => Self::Issue,
+            "pull_request" | "pull_requests" | "pr" | "new_pr" => Self::PullRequest,
+            "release" | "releases" | "new_release" => Self::Release,
+            "comment" | "comments" | "issue_comment" | "new_comment" => Self::Comment,
+            "push" => Self::Push,
+            _ => Self::Other(s.trim().to_string()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Issue => "issue",
+            Self::PullRequest => "pull_request",
+            Self::Release => "release",
+            Self::Comment => "comment",
+            Self::Push => "push",
+            Self::Other(s) => s,
+        }
+    }
+
+    /// Whether an item from the GitHub events API is of this kind
+    pub fn matches(&self, event: &serde_json::Value) -> bool {
+        let event_type = event.get("type").and_then(|v| v.as_str()).unwrap_or("");
+        let action = event
+            .pointer("/payload/action")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+
+        match self {
+            Self::Issue => event_type == "IssuesEvent" && action == "opened",
+            Self::PullRequest => event_type == "PullRequestEvent" && action == "opened",
+            Self::Release => event_type == "ReleaseEvent" && action == "published",
+            Self::Comment => match event_type {
+                "IssueCommentEvent" | "PullRequestReviewCommentEvent" => action == "created",
+                // Commit comments carry no action
+                "CommitCommentEvent" => true,
+                _ => false,
+            },
+            Self::Push => event_type == "PushEvent",
+            Self::Other(s) => event_type.to_lowercase().contains(&s.to_lowercase()),
+        }
+    }
+}
+
+impl From<String> for GitHubEventKind {
+    fn from(s: String) -> Self {
+        Self::from_string(&s)
+    }
+}
+
+impl From<GitHubEventKind> for String {
+    fn from(kind: GitHubEventKind) -> Self {
+        kind.as_str().to_string()
+    }
+}
+
+impl std::fmt::Display for GitHubEventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// How a polling watcher decides whether a fetched item has been seen before
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum DedupKey {
+    /// A single value in the item, as a JSON pointer ("/payload/id") or dotted path ("payload.id")
+    Path { path: String },
+
+    /// A hash of several values in the item, each a JSON pointer or dotted path
+    Fields { fields: Vec<String> },
+}
+
+impl DedupKey {
+    /// Extract the dedup key for an item, or `None` if the configured values are missing
+    pub fn extract(&self, item: &serde_json::Value) -> Option<String> {
+        match self {
+            Self::Path { path } => lookup_path(item, path).map(value_to_key),
+            Self::Fields { fields } => {
+                let parts: Vec<String> = fields
+                    .iter()
+                    .filter_map(|f| lookup_path(item, f).map(value_to_key))
+                    .collect();
+                if parts.is_empty() {
+                    None
+                } else {
+                    Some(parts.join("|"))
+                }
+            }
+        }
+    }
+
+    /// The key for a polled item: the configured key when there is one and its
+    /// values are present, otherwise the watcher's built-in `default`
+    pub fn key_or_else<T: Serialize>(
+        key: Option<&Self>,
+        item: &T,
+        default: impl FnOnce() -> String,
+    ) -> String {
+        key.and_then(|key| key.extract(&serde_json::to_value(item).ok()?))
+            .unwrap_or_else(default)
+    }
+}
+
+/// Resolve a JSON pointer or dotted path against a value
+fn lookup_path<'a>(item: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    let value = if path.starts_with('/') {
+        item.pointer(path)
+    } else {
+        path.split('.')
+            .filter(|segment| !segment.is_empty())
+            .try_fold(item, |current, segment| match current {
+                serde_json::Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+                _ => current.get(segment),
+            })
+    };
+    value.filter(|v| !v.is_null())
+}
+
+fn value_to_key(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// What happened to a watched file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileChangeKind {
+    Created,
+    Modified,
+    Deleted,
+}
+
+impl FileChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Modified => "modified",
+            Self::Deleted => "deleted",
+        }
+    }
+}
+
+impl std::fmt::Display for FileChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// What a watcher observed, one variant per source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WatcherPayload {
+    /// A new email matched the watcher's filters
+    EmailArrived {
+        from: String,
+        subject: String,
+        /// Truncated body (empty when the watcher only fetches headers)
+        preview: String,
+    },
+    /// A calendar event starts within the watcher's lead time
+    EventCreated { title: String, time: DateTime<Utc> },
+    /// A file or directory under the watched path changed
+    FileChanged { path: String, kind: FileChangeKind },
+    /// A GitHub event on the watched repository, as returned by the API
+    GitHub {
+        event_type: String,
+        data: serde_json::Value,
+    },
+    /// A new entry appeared in an RSS/Atom feed
+    FeedItem {
+        feed_url: String,
+        guid: String,
+        title: String,
+        link: Option<String>,
+        summary: Option<String>,
+    },
+    /// A scheduled or one-shot task fired
+    TaskTriggered { task: String },
+}
+
+impl WatcherPayload {
+    /// Event kind name, e.g. "email_received" or "github_push"
+    pub fn kind(&self) -> String {
+        match self {
+            Self::EmailArrived { .. } => "email_received".to_string(),
+            Self::EventCreated { .. } => "calendar_event".to_string(),
+            Self::FileChanged { .. } => "file_changed".to_string(),
+            Self::GitHub { event_type, .. } => format!("github_{}", event_type),
+            Self::FeedItem { .. } => "feed_item".to_string(),
+            Self::TaskTriggered { .. } => "task_triggered".to_string(),
+        }
+    }
+
+    /// The untyped JSON form events carried before payloads were typed
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Self::EmailArrived {
+                from,
+                subject,
+                preview,
+            } => serde_json::json!({
+                "from": from,
+                "subject": subject,
+                "body": preview,
+            }),
+            Self::EventCreated { title, time } => serde_json::json!({
+                "title": title,
+                "time": time,
+            }),
+            Self::FileChanged { path, kind } => serde_json::json!({
+                "path": path,
+                "change_type": kind.as_str(),
+            }),
+            Self::GitHub { data, .. } => data.clone(),
+            Self::FeedItem {
+                feed_url,
+                guid,
+                title,
+                link,
+                summary,
+            } => serde_json::json!({
+                "feed_url": feed_url,
+                "guid": guid,
+                "title": title,
+                "link": link,
+                "summary": summary,
+            }),
+            Self::TaskTriggered { task } => serde_json::json!({
+                "task": task,
+            }),
+        }
+    }
+}
+
+/// Renders the legacy JSON form, so existing prompts and logs are unchanged
+impl std::fmt::Display for WatcherPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_json())
+    }
 }
 
 /// An event emitted by a watcher when triggered
@@ -206,11 +788,8 @@ pub struct WatcherEvent {
     /// The ID of the watcher that emitted this event
     pub watcher_id: String,
 
-    /// The kind of event (e.g., "email_received", "file_changed", "task_scheduled")
-    pub kind: String,
-
-    /// Event-specific payload data
-    pub payload: serde_json::Value,
+    /// What the watcher observed
+    pub payload: WatcherPayload,
 
     /// When this event occurred
     pub timestamp: DateTime<Utc>,
@@ -218,25 +797,28 @@ pub struct WatcherEvent {
 
 impl WatcherEvent {
     /// Create a new watcher event
-    pub fn new(watcher_id: String, kind: String, payload: serde_json::Value) -> Self {
+    pub fn new(watcher_id: String, payload: WatcherPayload) -> Self {
         Self {
             watcher_id,
-            kind,
             payload,
             timestamp: Utc::now(),
         }
     }
 
+    /// Event kind name (e.g., "email_received", "file_changed", "task_triggered")
+    pub fn kind(&self) -> String {
+        self.payload.kind()
+    }
+
     /// Create an email event
-    pub fn email(watcher_id: String, from: String, subject: String, body: String) -> Self {
+    pub fn email(watcher_id: String, from: String, subject: String, preview: String) -> Self {
         Self::new(
             watcher_id,
-            "email_received".to_string(),
-            serde_json::json!({
-                "from": from,
-                "subject": subject,
-                "body": body,
-            }),
+            WatcherPayload::EmailArrived {
+                from,
+                subject,
+                preview,
+            },
         )
     }
 
@@ -244,43 +826,60 @@ impl WatcherEvent {
     pub fn calendar(watcher_id: String, event_title: String, event_time: DateTime<Utc>) -> Self {
         Self::new(
             watcher_id,
-            "calendar_event".to_string(),
-            serde_json::json!({
-                "title": event_title,
-                "time": event_time,
-            }),
+            WatcherPayload::EventCreated {
+                title: event_title,
+                time: event_time,
+            },
         )
     }
 
     /// Create a file change event
-    pub fn file_changed(watcher_id: String, path: String, change_type: String) -> Self {
-        Self::new(
-            watcher_id,
-            "file_changed".to_string(),
-            serde_json::json!({
-                "path": path,
-                "change_type": change_type,
-            }),
-        )
+    pub fn file_changed(watcher_id: String, path: String, kind: FileChangeKind) -> Self {
+        Self::new(watcher_id, WatcherPayload::FileChanged { path, kind })
     }
 
     /// Create a GitHub event
     pub fn github(watcher_id: String, event_type: String, data: serde_json::Value) -> Self {
-        Self::new(watcher_id, format!("github_{}", event_type), data)
+        Self::new(watcher_id, WatcherPayload::GitHub { event_type, data })
+    }
+
+    /// Create a feed entry event
+    pub fn feed_item(
+        watcher_id: String,
+        feed_url: String,
+        guid: String,
+        title: String,
+        link: Option<String>,
+        summary: Option<String>,
+    ) -> Self {
+        Self::new(
+            watcher_id,
+            WatcherPayload::FeedItem {
+                feed_url,
+                guid,
+                title,
+                link,
+                summary,
+            },
+        )
     }
 
     /// Create a task execution event
     pub fn task(watcher_id: String, task_name: String) -> Self {
         Self::new(
             watcher_id,
-            "task_triggered".to_string(),
-            serde_json::json!({
-                "task": task_name,
-            }),
+            WatcherPayload::TaskTriggered { task: task_name },
         )
     }
 }
 
+/// Renders the string form used in agent prompts before payloads were typed
+impl std::fmt::Display for WatcherEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Watcher {} triggered: {}", self.watcher_id, self.payload)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -292,6 +891,8 @@ mod tests {
                 from: Some("boss@company.com".to_string()),
                 subject_contains: Some("urgent".to_string()),
                 interval_secs: 300,
+                include_body: true,
+                dedup_key: None,
             },
             "Notify on urgent emails".to_string(),
             "slack-alerts".to_string(),
@@ -308,21 +909,87 @@ mod tests {
             from: None,
             subject_contains: None,
             interval_secs: 30,
+            include_body: true,
+            dedup_key: None,
         };
         assert_eq!(email_watch.min_interval_secs(), 60);
 
         let file_watch = WatcherKind::FileWatch {
             path: "/tmp/test".to_string(),
+            recursive: true,
         };
         assert_eq!(file_watch.min_interval_secs(), 0);
     }
 
+    #[test]
+    fn test_preview_fire_times() {
+        let from: DateTime<Utc> = "2026-10-16T08:30:00Z".parse().unwrap();
+
+        // Interval watchers tick immediately, clamped to the kind's minimum
+        let email = WatcherKind::EmailWatch {
+            from: None,
+            subject_contains: None,
+            interval_secs: 30,
+            include_body: false,
+            dedup_key: None,
+        };
+        let times = preview_fire_times(&email, from, 3);
+        assert_eq!(times.len(), 3);
+        assert_eq!(times[0], from);
+        assert_eq!(times[2] - times[0], chrono::Duration::seconds(120));
+
+        // 9am on weekdays
+        let cron = WatcherKind::Scheduled {
+            cron_expr: "0 0 9 * * Mon-Fri".to_string(),
+            task: "standup".to_string(),
+        };
+        let times: Vec<String> = preview_fire_times(&cron, from, 3)
+            .iter()
+            .map(|t| t.format("%a %H:%M").to_string())
+            .collect();
+        assert_eq!(times, ["Fri 09:00", "Mon 09:00", "Tue 09:00"]);
+
+        let past = WatcherKind::OneShot {
+            at: from - chrono::Duration::hours(1),
+            task: "late".to_string(),
+        };
+        assert!(preview_fire_times(&past, from, 5).is_empty());
+
+        let file = WatcherKind::FileWatch {
+            path: "/tmp".to_string(),
+            recursive: false,
+        };
+        assert!(preview_fire_times(&file, from, 5).is_empty());
+    }
+
+    #[test]
+    fn test_kind_from_config() {
+        let kind = WatcherKind::from_config(
+            "time",
+            serde_json::json!({"cron_expr": "0 0 9 * * *", "task": "t"}),
+        )
+        .unwrap();
+        assert!(kind.is_scheduled());
+
+        let err = WatcherKind::from_config(
+            "scheduled",
+            serde_json::json!({"cron_expr": "not cron", "task": "t"}),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Invalid cron expression"));
+
+        assert!(WatcherKind::from_config("bogus", serde_json::json!({})).is_err());
+        assert!(WatcherKind::from_config("file", serde_json::json!("x")).is_err());
+    }
+
     #[test]
     fn test_watcher_kind_classification() {
         let email = WatcherKind::EmailWatch {
             from: None,
             subject_contains: None,
             interval_secs: 60,
+            include_body: true,
+            dedup_key: None,
         };
         assert!(email.is_polling());
         assert!(!email.is_event_driven());
@@ -330,6 +997,7 @@ mod tests {
 
         let file = WatcherKind::FileWatch {
             path: "/tmp".to_string(),
+            recursive: true,
         };
         assert!(!file.is_polling());
         assert!(file.is_event_driven());
@@ -339,6 +1007,227 @@ mod tests {
             task: "Daily backup".to_string(),
         };
         assert!(scheduled.is_scheduled());
+
+        let rss = WatcherKind::RssWatch {
+            url: "https://example.com/feed.xml".to_string(),
+            interval_secs: 600,
+            dedup_key: None,
+            allow_private_network: false,
+        };
+        assert!(rss.is_polling());
+        assert_eq!(rss.min_interval_secs(), 300);
+    }
+
+    #[test]
+    fn test_github_event_kind_matching() {
+        let opened_issue =
+            serde_json::json!({"type": "IssuesEvent", "payload": {"action": "opened"}});
+        let closed_issue =
+            serde_json::json!({"type": "IssuesEvent", "payload": {"action": "closed"}});
+        let comment =
+            serde_json::json!({"type": "IssueCommentEvent", "payload": {"action": "created"}});
+        let release =
+            serde_json::json!({"type": "ReleaseEvent", "payload": {"action": "published"}});
+
+        assert!(GitHubEventKind::Issue.matches(&opened_issue));
+        assert!(!GitHubEventKind::Issue.matches(&closed_issue));
+        assert!(GitHubEventKind::Comment.matches(&comment));
+        assert!(!GitHubEventKind::PullRequest.matches(&comment));
+        assert!(GitHubEventKind::Release.matches(&release));
+        assert!(GitHubEventKind::Other("Issues".to_string()).matches(&closed_issue));
+    }
+
+    #[test]
+    fn test_github_watch_deserializes_event_strings() {
+        let kind: WatcherKind = serde_json::from_value(serde_json::json!({
+            "type": "GitHubWatch",
+            "repo": "owner/repo",
+            "events": ["issues", "pull_request", "WatchEvent"],
+            "interval_secs": 300,
+            "token": "ghp_x",
+        }))
+        .unwrap();
+
+        match kind {
+            WatcherKind::GitHubWatch {
+                events,
+                github_token,
+                ..
+            } => {
+                assert_eq!(
+                    events,
+                    vec![
+                        GitHubEventKind::Issue,
+                        GitHubEventKind::PullRequest,
+                        GitHubEventKind::Other("WatchEvent".to_string()),
+                    ]
+                );
+                assert_eq!(github_token.as_deref(), Some("ghp_x"));
+            }
+            _ => panic!("expected GitHubWatch"),
+        }
+    }
+
+    #[test]
+    fn test_calendar_watch_config() {
+        let kind = WatcherKind::from_config(
+            "calendar",
+            serde_json::json!({"lead_minutes": 10, "calendar": "Work", "interval_secs": 300}),
+        )
+        .unwrap();
+        match &kind {
+            WatcherKind::CalendarWatch {
+                lead_minutes,
+                calendar,
+                ..
+            } => {
+                assert_eq!(*lead_minutes, 10);
+                assert_eq!(calendar.as_deref(), Some("Work"));
+            }
+            _ => panic!("expected CalendarWatch"),
+        }
+
+        // Watchers saved before reminders existed keep their look-ahead window
+        let legacy = WatcherKind::from_json(
+            r#"{"type": "CalendarWatch", "lookahead_hours": 24, "interval_secs": 600}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            legacy,
+            WatcherKind::CalendarWatch {
+                lead_minutes: 1440,
+                calendar: None,
+                ..
+            }
+        ));
+        let legacy = WatcherKind::from_config(
+            "calendar",
+            serde_json::json!({"lookahead_hours": 2, "interval_secs": 600}),
+        )
+        .unwrap();
+        assert!(matches!(
+            legacy,
+            WatcherKind::CalendarWatch {
+                lead_minutes: 120,
+                ..
+            }
+        ));
+        let err = WatcherKind::from_config(
+            "calendar",
+            serde_json::json!({"lookahead_hours": 2, "lead_minutes": 5, "interval_secs": 600}),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("lookahead_hours"));
+    }
+
+    #[test]
+    fn test_validate_accepts_valid_watchers() {
+        let watcher = Watcher::new(
+            WatcherKind::GitHubWatch {
+                repo: "kavymi/meepo".to_string(),
+                events: Vec::new(),
+                interval_secs: 300,
+                github_token: None,
+                dedup_key: None,
+            },
+            "Triage new issues".to_string(),
+            "slack".to_string(),
+        );
+        assert!(watcher.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_impossible_configs() {
+        let email = |interval_secs, from: Option<&str>| WatcherKind::EmailWatch {
+            from: from.map(str::to_string),
+            subject_contains: None,
+            interval_secs,
+            include_body: true,
+            dedup_key: None,
+        };
+        let github = |repo: &str| WatcherKind::GitHubWatch {
+            repo: repo.to_string(),
+            events: Vec::new(),
+            interval_secs: 300,
+            github_token: None,
+            dedup_key: None,
+        };
+        let rss = |url: &str| WatcherKind::RssWatch {
+            url: url.to_string(),
+            interval_secs: 600,
+            dedup_key: None,
+            allow_private_network: false,
+        };
+        let calendar = |lead_minutes| WatcherKind::CalendarWatch {
+            lead_minutes,
+            calendar: None,
+            interval_secs: 300,
+            dedup_key: None,
+        };
+        let cases = [
+            (email(0, None), "interval_secs"),
+            (email(MAX_INTERVAL_SECS + 1, None), "interval_secs"),
+            (email(60, Some("  ")), "from"),
+            (calendar(0), "lead_minutes"),
+            (calendar(MAX_LEAD_MINUTES + 1), "lead_minutes"),
+            (github("meepo"), "repo"),
+            (github("owner/"), "repo"),
+            (github("a/b/c"), "repo"),
+            (github("owner/re po"), "repo"),
+            (rss("not a url"), "url"),
+            (rss("file:///etc/passwd"), "url"),
+            (rss("http://169.254.169.254/latest/meta-data/"), "url"),
+            (rss("http://localhost:8080/feed.xml"), "url"),
+            (
+                WatcherKind::FileWatch {
+                    path: String::new(),
+                    recursive: true,
+                },
+                "path",
+            ),
+            (
+                WatcherKind::MessageWatch {
+                    keyword: " ".to_string(),
+                },
+                "keyword",
+            ),
+            (
+                WatcherKind::Scheduled {
+                    cron_expr: "every day".to_string(),
+                    task: "x".to_string(),
+                },
+                "cron_expr",
+            ),
+            (
+                WatcherKind::OneShot {
+                    at: Utc::now(),
+                    task: String::new(),
+                },
+                "task",
+            ),
+        ];
+        for (kind, field) in cases {
+            let watcher = Watcher::new(kind, "Act".to_string(), "slack".to_string());
+            let err = format!("{:#}", watcher.validate().unwrap_err());
+            assert!(err.contains(&format!("`{}`", field)), "{}: {}", field, err);
+        }
+
+        let mut watcher = Watcher::new(email(60, None), " ".to_string(), "slack".to_string());
+        let err = watcher.validate().unwrap_err().to_string();
+        assert!(err.contains("`action`"), "{}", err);
+        watcher.action = "Act".to_string();
+        watcher.reply_channel = String::new();
+        let err = watcher.validate().unwrap_err().to_string();
+        assert!(err.contains("`reply_channel`"), "{}", err);
+
+        let lan_feed = WatcherKind::RssWatch {
+            url: "http://192.168.1.20/feed.xml".to_string(),
+            interval_secs: 600,
+            dedup_key: None,
+            allow_private_network: true,
+        };
+        let watcher = Watcher::new(lan_feed, "Act".to_string(), "slack".to_string());
+        assert!(watcher.validate().is_ok());
     }
 
     #[test]
@@ -351,7 +1240,130 @@ mod tests {
         );
 
         assert_eq!(event.watcher_id, "watcher-123");
-        assert_eq!(event.kind, "email_received");
-        assert!(event.payload.get("from").is_some());
+        assert_eq!(event.kind(), "email_received");
+        assert!(matches!(
+            &event.payload,
+            WatcherPayload::EmailArrived { from, .. } if from == "sender@example.com"
+        ));
+        assert_eq!(event.payload.to_json()["body"], "Test body");
+    }
+
+    #[test]
+    fn test_watcher_event_display_matches_legacy_form() {
+        let event = WatcherEvent::file_changed(
+            "w-1".to_string(),
+            "/tmp/a.txt".to_string(),
+            FileChangeKind::Created,
+        );
+        assert_eq!(event.kind(), "file_changed");
+        assert_eq!(
+            event.to_string(),
+            r#"Watcher w-1 triggered: {"change_type":"created","path":"/tmp/a.txt"}"#
+        );
+
+        let github = WatcherEvent::github(
+            "w-2".to_string(),
+            "push".to_string(),
+            serde_json::json!({"id": "1"}),
+        );
+        assert_eq!(github.kind(), "github_push");
+        assert_eq!(github.payload.to_string(), r#"{"id":"1"}"#);
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["payload"]["type"], "file_changed");
+        let back: WatcherEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(back.payload, event.payload);
+    }
+
+    #[test]
+    fn test_dedup_key_extract() {
+        let item = serde_json::json!({
+            "id": "123",
+            "type": "PushEvent",
+            "payload": { "commits": [{ "sha": "abc" }], "size": 2 }
+        });
+
+        let by_pointer = DedupKey::Path {
+            path: "/payload/commits/0/sha".to_string(),
+        };
+        assert_eq!(by_pointer.extract(&item), Some("abc".to_string()));
+
+        let by_dotted = DedupKey::Path {
+            path: "payload.size".to_string(),
+        };
+        assert_eq!(by_dotted.extract(&item), Some("2".to_string()));
+
+        let by_fields = DedupKey::Fields {
+            fields: vec!["type".to_string(), "id".to_string(), "missing".to_string()],
+        };
+        assert_eq!(by_fields.extract(&item), Some("PushEvent|123".to_string()));
+
+        let missing = DedupKey::Path {
+            path: "nope".to_string(),
+        };
+        assert_eq!(missing.extract(&item), None);
+    }
+
+    #[test]
+    fn test_dedup_key_deserialize() {
+        let kind: WatcherKind = serde_json::from_value(serde_json::json!({
+            "type": "GitHubWatch",
+            "repo": "owner/repo",
+            "events": [],
+            "interval_secs": 60,
+            "dedup_key": { "mode": "path", "path": "/id" }
+        }))
+        .unwrap();
+
+        match kind {
+            WatcherKind::GitHubWatch { dedup_key, .. } => assert_eq!(
+                dedup_key,
+                Some(DedupKey::Path {
+                    path: "/id".to_string()
+                })
+            ),
+            _ => panic!("expected GitHubWatch"),
+        }
+
+        let kind = WatcherKind::from_config(
+            "rss",
+            serde_json::json!({
+                "url": "https://example.com/feed",
+                "interval_secs": 600,
+                "dedup_key": { "mode": "fields", "fields": ["title", "link"] }
+            }),
+        )
+        .unwrap();
+        match kind {
+            WatcherKind::RssWatch { dedup_key, .. } => assert_eq!(
+                dedup_key,
+                Some(DedupKey::Fields {
+                    fields: vec!["title".to_string(), "link".to_string()]
+                })
+            ),
+            _ => panic!("expected RssWatch"),
+        }
+    }
+
+    #[test]
+    fn test_dedup_key_or_else() {
+        let item = serde_json::json!({ "id": "a", "title": "Hello" });
+        let by_title = DedupKey::Path {
+            path: "title".to_string(),
+        };
+        let missing = DedupKey::Path {
+            path: "uid".to_string(),
+        };
+        let default = || "fallback".to_string();
+
+        assert_eq!(DedupKey::key_or_else(None, &item, default), "fallback");
+        assert_eq!(
+            DedupKey::key_or_else(Some(&by_title), &item, default),
+            "Hello"
+        );
+        assert_eq!(
+            DedupKey::key_or_else(Some(&missing), &item, default),
+            "fallback"
+        );
     }
 }
@@ -0,0 +1,418 @@
+//! Declarative watcher definitions
+//!
+//! Watchers can be kept in a TOML or JSON file next to the rest of the
+//! configuration, so they are reviewable and version-controlled. Entries use
+//! the same `kind`/`config` shape as the `create_watcher` tool:
+//!
+//! ```toml
+//! [[watchers]]
+//! id = "boss-mail"    # optional, keeps history across edits
+//! kind = "email"
+//! action = "Summarize urgent mail from my boss"
+//! reply_channel = "slack"
+//! config = { from = "boss@company.com", interval_secs = 300 }
+//! ```
+//!
+//! A JSON file holds the same entries as a top-level array, or under a
+//! `"watchers"` key. [`sync_watchers_from_file`] makes the database match the
+//! file; watchers created at runtime (by the agent) are left alone.
+
+use crate::persistence;
+use crate::watcher::{Watcher, WatcherKind};
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, params};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::Path;
+use tracing::info;
+
+/// `scheduler_meta` key listing the ids of watchers that came from the file
+const FILE_WATCHER_IDS_KEY: &str = "file_watcher_ids";
+
+fn default_active() -> bool {
+    true
+}
+
+/// A watcher as written in a watcher file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WatcherEntry {
+    #[serde(default)]
+    id: Option<String>,
+    kind: String,
+    #[serde(default)]
+    config: serde_json::Map<String, serde_json::Value>,
+    action: String,
+    reply_channel: String,
+    #[serde(default = "default_active")]
+    active: bool,
+    #[serde(default)]
+    paused: bool,
+    #[serde(default)]
+    created_at: Option<DateTime<Utc>>,
+}
+
+impl WatcherEntry {
+    fn into_watcher(self) -> Result<Watcher> {
+        if self.id.as_deref().is_some_and(|id| id.trim().is_empty()) {
+            bail!("`id` must not be empty");
+        }
+
+        let kind = WatcherKind::from_config(&self.kind, self.config.into())
+            .with_context(|| format!("field `config` of a '{}' watcher", self.kind))?;
+        let mut watcher = Watcher::new(kind, self.action, self.reply_channel);
+        if let Some(id) = self.id {
+            watcher.id = id;
+        }
+        if let Some(created_at) = self.created_at {
+            watcher.created_at = created_at;
+        }
+        watcher.active = self.active;
+        watcher.paused = self.paused;
+        watcher.validate()?;
+        Ok(watcher)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TomlWatcherFile {
+    #[serde(default)]
+    watchers: Vec<toml::Spanned<WatcherEntry>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonWatcherFile {
+    watchers: Vec<WatcherEntry>,
+}
+
+/// Entries of a watcher file, with the line each starts on when the format reports it
+fn parse_entries(text: &str, path: &Path) -> Result<Vec<(WatcherEntry, Option<usize>)>> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => {
+            let file: TomlWatcherFile = toml::from_str(text)
+                .with_context(|| format!("Invalid watcher file {}", path.display()))?;
+            Ok(file
+                .watchers
+                .into_iter()
+                .map(|entry| {
+                    let line = text[..entry.span().start].matches('\n').count() + 1;
+                    (entry.into_inner(), Some(line))
+                })
+                .collect())
+        }
+        Some("json") => {
+            let entries = if text.trim_start().starts_with('[') {
+                serde_json::from_str::<Vec<WatcherEntry>>(text)
+            } else {
+                serde_json::from_str::<JsonWatcherFile>(text).map(|file| file.watchers)
+            }
+            .with_context(|| format!("Invalid watcher file {}", path.display()))?;
+            Ok(entries.into_iter().map(|entry| (entry, None)).collect())
+        }
+        _ => bail!(
+            "Watcher file {} must have a .toml or .json extension",
+            path.display()
+        ),
+    }
+}
+
+/// Watchers defined in `path`, each with whether the file gave its id
+fn read_watcher_file(path: &Path) -> Result<Vec<(Watcher, bool)>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read watcher file {}", path.display()))?;
+
+    let mut watchers = Vec::new();
+    let mut errors = Vec::new();
+    let mut ids = HashSet::new();
+    for (index, (entry, line)) in parse_entries(&text, path)?.into_iter().enumerate() {
+        let location = match line {
+            Some(line) => format!("watcher {} (line {})", index + 1, line),
+            None => format!("watcher {}", index + 1),
+        };
+        let explicit_id = entry.id.is_some();
+        match entry.into_watcher() {
+            Ok(watcher) if explicit_id && !ids.insert(watcher.id.clone()) => {
+                errors.push(format!("{}: duplicate id '{}'", location, watcher.id));
+            }
+            Ok(watcher) => watchers.push((watcher, explicit_id)),
+            Err(e) => errors.push(format!("{}: {:#}", location, e)),
+        }
+    }
+
+    if !errors.is_empty() {
+        bail!(
+            "Invalid watchers in {}:\n  {}",
+            path.display(),
+            errors.join("\n  ")
+        );
+    }
+    Ok(watchers)
+}
+
+/// Parse the watchers defined in a TOML or JSON file.
+///
+/// Entries without an `id` get a fresh one and entries without `created_at`
+/// the current time. Every malformed entry is reported, with its position in
+/// the file and the offending field, rather than skipped.
+pub fn load_watchers_from_file(path: &Path) -> Result<Vec<Watcher>> {
+    Ok(read_watcher_file(path)?
+        .into_iter()
+        .map(|(watcher, _)| watcher)
+        .collect())
+}
+
+/// What [`sync_watchers_from_file`] changed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WatcherFileSync {
+    /// Watchers inserted or updated from the file
+    pub saved: usize,
+    /// Watchers from an earlier sync that the file no longer lists
+    pub deactivated: usize,
+}
+
+/// Make the database match the watchers defined in `path`.
+///
+/// Every watcher in the file is saved with [`persistence::save_watcher`].
+/// Watchers saved by an earlier sync that the file no longer lists are
+/// deactivated; watchers created any other way are not touched. An entry
+/// without an `id` keeps the watcher an earlier sync created for it as long as
+/// its definition is unchanged. Nothing is written if the file is invalid.
+pub fn sync_watchers_from_file(conn: &Connection, path: &Path) -> Result<WatcherFileSync> {
+    let loaded = read_watcher_file(path)?;
+
+    let previous_ids = file_watcher_ids(conn)?;
+    let explicit_ids: HashSet<&str> = loaded
+        .iter()
+        .filter(|(_, explicit_id)| *explicit_id)
+        .map(|(watcher, _)| watcher.id.as_str())
+        .collect();
+    let mut unclaimed = Vec::new();
+    for id in previous_ids
+        .iter()
+        .filter(|id| !explicit_ids.contains(id.as_str()))
+    {
+        if let Some(watcher) = persistence::get_watcher_by_id(conn, id)? {
+            unclaimed.push(watcher);
+        }
+    }
+
+    let tx = conn
+        .unchecked_transaction()
+        .context("Failed to begin watcher sync transaction")?;
+    let mut sync = WatcherFileSync::default();
+    let mut current_ids = Vec::new();
+    for (mut watcher, explicit_id) in loaded {
+        if !explicit_id
+            && let Some(pos) = unclaimed
+                .iter()
+                .position(|existing| same_definition(existing, &watcher))
+        {
+            let existing = unclaimed.swap_remove(pos);
+            watcher.id = existing.id;
+            watcher.created_at = existing.created_at;
+        }
+        persistence::save_watcher(&tx, &watcher)?;
+        current_ids.push(watcher.id);
+        sync.saved += 1;
+    }
+
+    for id in previous_ids.iter().filter(|id| !current_ids.contains(*id)) {
+        if persistence::deactivate_watcher(&tx, id)? {
+            sync.deactivated += 1;
+        }
+    }
+
+    let ids_json =
+        serde_json::to_string(&current_ids).context("Failed to serialize watcher ids")?;
+    tx.execute(
+        "INSERT INTO scheduler_meta (key, value) VALUES (?1, ?2)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        params![FILE_WATCHER_IDS_KEY, ids_json],
+    )
+    .context("Failed to record file watcher ids")?;
+    tx.commit()
+        .context("Failed to commit watcher sync transaction")?;
+
+    info!(
+        "Synced watchers from {}: {} saved, {} deactivated",
+        path.display(),
+        sync.saved,
+        sync.deactivated
+    );
+    Ok(sync)
+}
+
+/// Ids of the watchers saved by the last sync
+fn file_watcher_ids(conn: &Connection) -> Result<Vec<String>> {
+    let result = conn.query_row(
+        "SELECT value FROM scheduler_meta WHERE key = ?1",
+        params![FILE_WATCHER_IDS_KEY],
+        |row| row.get::<_, String>(0),
+    );
+
+    match result {
+        Ok(json) => serde_json::from_str(&json).context("Failed to parse file watcher ids"),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(Vec::new()),
+        Err(e) => Err(e).context("Failed to read file watcher ids"),
+    }
+}
+
+/// Whether two watchers are defined the same way, ignoring id and state
+fn same_definition(a: &Watcher, b: &Watcher) -> bool {
+    a.action == b.action
+        && a.reply_channel == b.reply_channel
+        && serde_json::to_value(&a.kind).ok() == serde_json::to_value(&b.kind).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        persistence::init_watcher_tables(&conn).unwrap();
+        conn
+    }
+
+    fn write(dir: &tempfile::TempDir, name: &str, text: &str) -> std::path::PathBuf {
+        let path = dir.path().join(name);
+        std::fs::write(&path, text).unwrap();
+        path
+    }
+
+    const TOML_WATCHERS: &str = r#"
+[[watchers]]
+id = "boss-mail"
+kind = "email"
+action = "Summarize mail from my boss"
+reply_channel = "slack"
+config = { from = "boss@company.com", interval_secs = 300 }
+
+[[watchers]]
+kind = "scheduled"
+action = "Morning briefing"
+reply_channel = "discord"
+config = { cron_expr = "0 0 8 * * *", task = "briefing" }
+"#;
+
+    #[test]
+    fn test_load_toml_and_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let watchers = load_watchers_from_file(&write(&dir, "w.toml", TOML_WATCHERS)).unwrap();
+        assert_eq!(watchers.len(), 2);
+        assert_eq!(watchers[0].id, "boss-mail");
+        assert!(matches!(watchers[0].kind, WatcherKind::EmailWatch { .. }));
+        assert!(!watchers[1].id.is_empty());
+        assert!(watchers[1].active);
+
+        let json = r#"[{"kind": "rss", "action": "New posts", "reply_channel": "slack",
+                        "config": {"url": "https://example.com/feed", "interval_secs": 600},
+                        "paused": true}]"#;
+        let watchers = load_watchers_from_file(&write(&dir, "w.json", json)).unwrap();
+        assert!(matches!(watchers[0].kind, WatcherKind::RssWatch { .. }));
+        assert!(watchers[0].paused);
+    }
+
+    #[test]
+    fn test_malformed_entries_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let text = r#"
+[[watchers]]
+kind = "email"
+action = "Missing interval"
+reply_channel = "slack"
+config = { from = "a@b.com" }
+
+[[watchers]]
+kind = "scheduled"
+action = "Bad cron"
+reply_channel = "slack"
+config = { cron_expr = "whenever", task = "x" }
+"#;
+        let err = load_watchers_from_file(&write(&dir, "w.toml", text)).unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(message.contains("watcher 1 (line 2)"), "{}", message);
+        assert!(message.contains("interval_secs"), "{}", message);
+        assert!(message.contains("watcher 2 (line 8)"), "{}", message);
+        assert!(message.contains("Invalid cron expression"), "{}", message);
+
+        // Unknown fields are rejected by the parser, with its own position
+        let err = load_watchers_from_file(&write(
+            &dir,
+            "typo.json",
+            r#"[{"kind": "file", "action": "a", "reply_chanel": "slack"}]"#,
+        ))
+        .unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(message.contains("reply_chanel"), "{}", message);
+        assert!(message.contains("line 1"), "{}", message);
+    }
+
+    #[test]
+    fn test_sync_saves_and_deactivates_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = setup_test_db();
+        let runtime = Watcher::new(
+            WatcherKind::MessageWatch {
+                keyword: "deploy".to_string(),
+            },
+            "Created by the agent".to_string(),
+            "slack".to_string(),
+        );
+        persistence::save_watcher(&conn, &runtime).unwrap();
+
+        let path = write(&dir, "w.toml", TOML_WATCHERS);
+        let sync = sync_watchers_from_file(&conn, &path).unwrap();
+        assert_eq!(
+            sync,
+            WatcherFileSync {
+                saved: 2,
+                deactivated: 0
+            }
+        );
+        let first = persistence::get_active_watchers(&conn).unwrap();
+        assert_eq!(first.len(), 3);
+        let briefing_id = first
+            .iter()
+            .find(|w| w.action == "Morning briefing")
+            .unwrap()
+            .id
+            .clone();
+
+        // Re-syncing an unchanged file keeps ids, even for entries without one
+        sync_watchers_from_file(&conn, &path).unwrap();
+        assert_eq!(persistence::get_active_watchers(&conn).unwrap().len(), 3);
+        assert!(
+            persistence::get_watcher_by_id(&conn, &briefing_id)
+                .unwrap()
+                .unwrap()
+                .active
+        );
+
+        // Dropping an entry deactivates its watcher, but not runtime ones
+        let only_boss = TOML_WATCHERS.split("\n[[watchers]]\nkind").next().unwrap();
+        let path = write(&dir, "w.toml", only_boss);
+        let sync = sync_watchers_from_file(&conn, &path).unwrap();
+        assert_eq!(sync.deactivated, 1);
+        let active: Vec<String> = persistence::get_active_watchers(&conn)
+            .unwrap()
+            .into_iter()
+            .map(|w| w.id)
+            .collect();
+        assert_eq!(active.len(), 2);
+        assert!(active.contains(&"boss-mail".to_string()));
+        assert!(active.contains(&runtime.id));
+    }
+
+    #[test]
+    fn test_sync_writes_nothing_for_invalid_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = setup_test_db();
+        let path = write(&dir, "w.toml", "[[watchers]]\nkind = \"nope\"\n");
+        assert!(sync_watchers_from_file(&conn, &path).is_err());
+        assert!(persistence::get_active_watchers(&conn).unwrap().is_empty());
+    }
+}